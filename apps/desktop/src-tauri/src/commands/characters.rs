@@ -1,8 +1,9 @@
 use crate::db::Pool;
 use crate::error::AppError;
 use crate::models::{
-    Character, CharacterAbilities, CharacterClass, CharacterSearchFilters, CharacterSearchResult,
-    CharacterSpellbookEntry, UpdateAbilitiesInput, UpdateCharacterDetailsInput,
+    Character, CharacterAbilities, CharacterChange, CharacterClass, CharacterSearchFilters,
+    CharacterSearchResult, CharacterSpellbookEntry, OrphanedSpellbookEntry, UpdateAbilitiesInput,
+    UpdateCharacterDetailsInput,
 };
 use rusqlite::ToSql;
 use rusqlite::{params, Connection, OptionalExtension};
@@ -392,6 +393,135 @@ fn upsert_character_class_spell_with_hash(
     Ok(())
 }
 
+fn get_character_with_conn(conn: &Connection, id: i64) -> Result<Option<Character>, AppError> {
+    let character = conn
+        .query_row(
+            "SELECT id, name, type, race, alignment, com_enabled, notes, created_at, updated_at FROM \"character\" WHERE id=?",
+            params![id],
+            |row| {
+                Ok(Character {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    character_type: row.get(2)?,
+                    race: row.get(3)?,
+                    alignment: row.get(4)?,
+                    com_enabled: row.get(5)?,
+                    notes: row.get(6)?,
+                    created_at: row.get(7)?,
+                    updated_at: row.get(8)?,
+                })
+            },
+        )
+        .optional()?;
+    Ok(character)
+}
+
+/// Field-level diff of a character edit, in the same `(field, old, new)` shape as `diff_spells`.
+fn diff_character(
+    old: &Character,
+    new: &UpdateCharacterDetailsInput,
+) -> Vec<(String, String, String)> {
+    let mut changes = vec![];
+
+    if old.name != new.name {
+        changes.push(("name".into(), old.name.clone(), new.name.clone()));
+    }
+    if old.character_type != new.character_type {
+        changes.push((
+            "type".into(),
+            old.character_type.clone(),
+            new.character_type.clone(),
+        ));
+    }
+    if old.race != new.race {
+        changes.push((
+            "race".into(),
+            old.race.clone().unwrap_or_default(),
+            new.race.clone().unwrap_or_default(),
+        ));
+    }
+    if old.alignment != new.alignment {
+        changes.push((
+            "alignment".into(),
+            old.alignment.clone().unwrap_or_default(),
+            new.alignment.clone().unwrap_or_default(),
+        ));
+    }
+    if old.com_enabled != new.com_enabled {
+        changes.push((
+            "com_enabled".into(),
+            old.com_enabled.to_string(),
+            new.com_enabled.to_string(),
+        ));
+    }
+    if old.notes != new.notes {
+        changes.push((
+            "notes".into(),
+            old.notes.clone().unwrap_or_default(),
+            new.notes.clone().unwrap_or_default(),
+        ));
+    }
+
+    changes
+}
+
+fn log_character_changes(
+    conn: &Connection,
+    character_id: i64,
+    changes: Vec<(String, String, String)>,
+) -> Result<(), AppError> {
+    for (field, old_val, new_val) in changes {
+        conn.execute(
+            "INSERT INTO character_change_log (character_id, field, old_value, new_value) VALUES (?, ?, ?, ?)",
+            params![character_id, field, old_val, new_val],
+        )?;
+    }
+    Ok(())
+}
+
+fn update_character_details_with_conn(
+    conn: &Connection,
+    input: &UpdateCharacterDetailsInput,
+) -> Result<(), AppError> {
+    let tx = conn.unchecked_transaction()?;
+    let old = get_character_with_conn(&tx, input.id)?
+        .ok_or_else(|| AppError::NotFound(format!("Character {} not found", input.id)))?;
+    log_character_changes(&tx, input.id, diff_character(&old, input))?;
+    tx.execute(
+        "UPDATE \"character\" SET name=?, type=?, race=?, alignment=?, com_enabled=?, notes=?, updated_at=CURRENT_TIMESTAMP WHERE id=?",
+        params![input.name, input.character_type, input.race, input.alignment, input.com_enabled, input.notes, input.id],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn get_character_history_with_conn(
+    conn: &Connection,
+    character_id: i64,
+) -> Result<Vec<CharacterChange>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, character_id, changed_at, field, old_value, new_value
+         FROM character_change_log WHERE character_id = ?
+         ORDER BY changed_at DESC, id DESC",
+    )?;
+    let rows = stmt.query_map(params![character_id], |row| {
+        Ok(CharacterChange {
+            id: row.get(0)?,
+            character_id: row.get(1)?,
+            changed_at: row.get(2)?,
+            field: row.get(3)?,
+            old_value: row.get(4)?,
+            new_value: row.get(5)?,
+        })
+    })?;
+
+    let mut out = vec![];
+    for row in rows {
+        out.push(row?);
+    }
+    Ok(out)
+}
+
 #[tauri::command]
 pub async fn create_character(
     state: State<'_, Arc<Pool>>,
@@ -427,11 +557,7 @@ pub async fn update_character_details(
     let pool = state.inner().clone();
     tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        conn.execute(
-            "UPDATE \"character\" SET name=?, type=?, race=?, alignment=?, com_enabled=?, notes=?, updated_at=CURRENT_TIMESTAMP WHERE id=?",
-            params![input.name, input.character_type, input.race, input.alignment, input.com_enabled, input.notes, input.id],
-        )?;
-        Ok::<(), AppError>(())
+        update_character_details_with_conn(&conn, &input)
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
@@ -439,6 +565,22 @@ pub async fn update_character_details(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn get_character_history(
+    state: State<'_, Arc<Pool>>,
+    character_id: i64,
+) -> Result<Vec<CharacterChange>, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_character_history_with_conn(&conn, character_id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn delete_character(state: State<'_, Arc<Pool>>, id: i64) -> Result<(), AppError> {
     let pool = state.inner().clone();
@@ -901,53 +1043,321 @@ pub async fn update_character_spell_notes(
 // Deprecated: legacy spellbook commands for backward compatibility during transition.
 // They still work on the old 'spellbook' table.
 
+/// Legacy spellbook rows for a character, optionally limited to spells of `max_level`
+/// or lower (castable at the character's current level).
+fn get_character_spellbook_with_conn(
+    conn: &Connection,
+    character_id: i64,
+    max_level: Option<i64>,
+) -> Result<Vec<CharacterSpellbookEntry>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.name, s.level, s.school, s.sphere, s.is_quest_spell, s.is_cantrip, sb.prepared, sb.known, sb.notes, s.tags
+         FROM spellbook sb
+         JOIN spell s ON s.id = sb.spell_id
+         WHERE sb.character_id = ?1 AND (?2 IS NULL OR s.level <= ?2)
+         ORDER BY s.level, s.name",
+    )?;
+    let rows = stmt.query_map(rusqlite::params![character_id, max_level], |row| {
+        Ok(CharacterSpellbookEntry {
+            character_id,
+            spell_id: row.get(0)?,
+            spell_name: row.get(1)?,
+            spell_level: row.get(2)?,
+            spell_school: row.get(3)?,
+            spell_sphere: row.get(4)?,
+            is_quest_spell: row.get(5)?,
+            is_cantrip: row.get(6)?,
+            prepared: row.get(7)?,
+            known: row.get(8)?,
+            notes: row.get(9)?,
+            tags: row.get(10)?,
+            spell_content_hash: None,
+            missing_from_library: false,
+            available_upgrade_hash: None,
+            available_upgrade_spell_id: None,
+        })
+    })?;
+
+    let mut out = vec![];
+    for row in rows {
+        out.push(row?);
+    }
+    Ok(out)
+}
+
 /// Deprecated: legacy spellbook command. Use the per-class system instead.
 #[tauri::command]
 pub async fn get_character_spellbook(
     state: State<'_, Arc<Pool>>,
     character_id: i64,
+    max_level: Option<i64>,
 ) -> Result<Vec<CharacterSpellbookEntry>, AppError> {
     let pool = state.inner().clone();
     let result = tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT s.id, s.name, s.level, s.school, s.sphere, s.is_quest_spell, s.is_cantrip, sb.prepared, sb.known, sb.notes, s.tags
-             FROM spellbook sb
-             JOIN spell s ON s.id = sb.spell_id
-             WHERE sb.character_id = ?
-             ORDER BY s.level, s.name",
+        get_character_spellbook_with_conn(&conn, character_id, max_level)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+const ORPHANED_SPELLBOOK_WHERE: &str =
+    "NOT EXISTS (SELECT 1 FROM spell s WHERE s.id = spellbook.spell_id)";
+
+fn find_orphaned_spellbook_entries_with_conn(
+    conn: &Connection,
+) -> Result<Vec<OrphanedSpellbookEntry>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT character_id, spell_id FROM spellbook
+         WHERE {ORPHANED_SPELLBOOK_WHERE}
+         ORDER BY character_id, spell_id"
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok(OrphanedSpellbookEntry {
+            character_id: row.get(0)?,
+            spell_id: row.get(1)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+fn prune_orphaned_spellbook_entries_with_conn(conn: &Connection) -> Result<usize, AppError> {
+    Ok(conn.execute(
+        &format!("DELETE FROM spellbook WHERE {ORPHANED_SPELLBOOK_WHERE}"),
+        [],
+    )?)
+}
+
+/// Lists legacy `spellbook` rows that point at deleted spells (left behind when
+/// foreign keys were off, e.g. after a restore or a manual edit).
+#[tauri::command]
+pub async fn find_orphaned_spellbook_entries(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<OrphanedSpellbookEntry>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        find_orphaned_spellbook_entries_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Deletes the rows reported by `find_orphaned_spellbook_entries`; returns how many went.
+#[tauri::command]
+pub async fn prune_orphaned_spellbook_entries(
+    state: State<'_, Arc<Pool>>,
+) -> Result<usize, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        prune_orphaned_spellbook_entries_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Copies one legacy spellbook row (prepared, known, notes) from `from_character` to
+/// `to_character`, replacing any row the target already has for the spell. With
+/// `move_entry` the source row is removed in the same transaction.
+fn copy_spellbook_entry_with_conn(
+    conn: &mut Connection,
+    from_character: i64,
+    to_character: i64,
+    spell_id: i64,
+    move_entry: bool,
+) -> Result<(), AppError> {
+    if from_character == to_character {
+        return Err(AppError::Validation(
+            "Source and target character must differ".to_string(),
+        ));
+    }
+    let tx = conn.transaction()?;
+    for character_id in [from_character, to_character] {
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM \"character\" WHERE id = ?)",
+            [character_id],
+            |row| row.get(0),
         )?;
-        let rows = stmt.query_map([character_id], |row| {
-            Ok(CharacterSpellbookEntry {
-                character_id,
-                spell_id: row.get(0)?,
-                spell_name: row.get(1)?,
-                spell_level: row.get(2)?,
-                spell_school: row.get(3)?,
-                spell_sphere: row.get(4)?,
-                is_quest_spell: row.get(5)?,
-                is_cantrip: row.get(6)?,
-                prepared: row.get(7)?,
-                known: row.get(8)?,
-                notes: row.get(9)?,
-                tags: row.get(10)?,
-                spell_content_hash: None,
-                missing_from_library: false,
-                available_upgrade_hash: None,
-                available_upgrade_spell_id: None,
-            })
+        if !exists {
+            return Err(AppError::NotFound(format!(
+                "Character {} not found",
+                character_id
+            )));
+        }
+    }
+
+    let (prepared, known, notes): (i64, i64, Option<String>) = tx
+        .query_row(
+            "SELECT prepared, known, notes FROM spellbook WHERE character_id = ? AND spell_id = ?",
+            params![from_character, spell_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Spell {} is not in character {}'s spellbook",
+                spell_id, from_character
+            ))
         })?;
 
-        let mut out = vec![];
-        for row in rows {
-            out.push(row?);
-        }
-        Ok::<Vec<CharacterSpellbookEntry>, AppError>(out)
+    tx.execute(
+        "INSERT INTO spellbook (character_id, spell_id, prepared, known, notes)
+         VALUES (?, ?, ?, ?, ?)
+         ON CONFLICT(character_id, spell_id) DO UPDATE SET
+            prepared=excluded.prepared,
+            known=excluded.known,
+            notes=excluded.notes",
+        params![to_character, spell_id, prepared, known, notes],
+    )?;
+    if move_entry {
+        tx.execute(
+            "DELETE FROM spellbook WHERE character_id = ? AND spell_id = ?",
+            params![from_character, spell_id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn copy_spellbook_entry(
+    state: State<'_, Arc<Pool>>,
+    from_character: i64,
+    to_character: i64,
+    spell_id: i64,
+    move_entry: bool,
+) -> Result<(), AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get()?;
+        copy_spellbook_entry_with_conn(
+            &mut conn,
+            from_character,
+            to_character,
+            spell_id,
+            move_entry,
+        )
     })
     .await
-    .map_err(|e| AppError::Unknown(e.to_string()))??;
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
 
-    Ok(result)
+/// Adds `spell_id` to a character's legacy spellbook (or updates its flags, keeping notes)
+/// and returns the resulting entry.
+fn learn_spell_with_conn(
+    conn: &mut Connection,
+    character_id: i64,
+    spell_id: i64,
+    prepared: bool,
+    known: bool,
+) -> Result<CharacterSpellbookEntry, AppError> {
+    let tx = conn.transaction()?;
+    let character_exists: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM \"character\" WHERE id = ?)",
+        [character_id],
+        |row| row.get(0),
+    )?;
+    if !character_exists {
+        return Err(AppError::NotFound(format!(
+            "Character {} not found",
+            character_id
+        )));
+    }
+    let spell_exists: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM spell WHERE id = ?)",
+        [spell_id],
+        |row| row.get(0),
+    )?;
+    if !spell_exists {
+        return Err(AppError::NotFound(format!("Spell {} not found", spell_id)));
+    }
+
+    tx.execute(
+        "INSERT INTO spellbook (character_id, spell_id, prepared, known)
+         VALUES (?, ?, ?, ?)
+         ON CONFLICT(character_id, spell_id) DO UPDATE SET
+            prepared=excluded.prepared,
+            known=excluded.known",
+        params![character_id, spell_id, prepared as i64, known as i64],
+    )?;
+    tx.commit()?;
+
+    get_character_spellbook_with_conn(conn, character_id, None)?
+        .into_iter()
+        .find(|entry| entry.spell_id == spell_id)
+        .ok_or_else(|| AppError::Unknown("Spellbook entry missing after insert".to_string()))
+}
+
+/// Resolves a spell id by exact (case-insensitive) name, optionally narrowed by source.
+/// More than one match is an error so the caller can ask for a source.
+fn resolve_spell_id_by_name(
+    conn: &Connection,
+    name: &str,
+    source: Option<&str>,
+) -> Result<i64, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM spell
+         WHERE name = ?1 COLLATE NOCASE AND (?2 IS NULL OR source = ?2 COLLATE NOCASE)
+         ORDER BY id",
+    )?;
+    let ids = stmt
+        .query_map(params![name.trim(), source], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    match ids.as_slice() {
+        [] => Err(AppError::NotFound(format!("Spell '{}' not found", name))),
+        [id] => Ok(*id),
+        _ => Err(AppError::Validation(format!(
+            "Spell name '{}' is ambiguous ({} matches); specify a source",
+            name,
+            ids.len()
+        ))),
+    }
+}
+
+#[tauri::command]
+pub async fn learn_spell(
+    state: State<'_, Arc<Pool>>,
+    character_id: i64,
+    spell_id: i64,
+    prepared: bool,
+    known: bool,
+) -> Result<CharacterSpellbookEntry, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get()?;
+        learn_spell_with_conn(&mut conn, character_id, spell_id, prepared, known)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// [`learn_spell`] by spell name. `prepared` defaults to false and `known` to true.
+#[tauri::command]
+pub async fn learn_spell_by_name(
+    state: State<'_, Arc<Pool>>,
+    character_id: i64,
+    name: String,
+    source: Option<String>,
+    prepared: Option<bool>,
+    known: Option<bool>,
+) -> Result<CharacterSpellbookEntry, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get()?;
+        let spell_id = resolve_spell_id_by_name(&conn, &name, source.as_deref())?;
+        learn_spell_with_conn(
+            &mut conn,
+            character_id,
+            spell_id,
+            prepared.unwrap_or(false),
+            known.unwrap_or(true),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
 }
 
 /// Test-only: inserts a spell row by name and content_hash for E2E (e.g. restoring an orphan).
@@ -1832,6 +2242,291 @@ mod tests {
             "both KNOWN and PREPARED rows must be deleted after remove-by-hash"
         );
     }
+
+    #[test]
+    fn test_update_character_details_records_history_newest_first() {
+        let conn = Connection::open_in_memory().expect("open db");
+        conn.execute_batch(
+            r#"
+            CREATE TABLE "character" (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                type TEXT NOT NULL DEFAULT 'PC',
+                race TEXT,
+                alignment TEXT,
+                com_enabled INTEGER NOT NULL DEFAULT 0,
+                notes TEXT,
+                created_at TEXT,
+                updated_at TEXT
+            );
+            INSERT INTO "character" (id, name, notes) VALUES (1, 'Elminster', 'first');
+            "#,
+        )
+        .expect("create character schema");
+        conn.execute_batch(include_str!(
+            "../../../../../db/migrations/0017_character_change_log.sql"
+        ))
+        .expect("apply character_change_log migration");
+
+        let edit = |notes: &str| UpdateCharacterDetailsInput {
+            id: 1,
+            name: "Elminster".into(),
+            character_type: "PC".into(),
+            race: None,
+            alignment: None,
+            com_enabled: 0,
+            notes: Some(notes.into()),
+        };
+        update_character_details_with_conn(&conn, &edit("second")).expect("first edit");
+        update_character_details_with_conn(&conn, &edit("third")).expect("second edit");
+
+        let history = get_character_history_with_conn(&conn, 1).expect("load history");
+        let entries: Vec<(&str, Option<&str>, Option<&str>)> = history
+            .iter()
+            .map(|c| {
+                (
+                    c.field.as_str(),
+                    c.old_value.as_deref(),
+                    c.new_value.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("notes", Some("second"), Some("third")),
+                ("notes", Some("first"), Some("second")),
+            ]
+        );
+
+        assert!(matches!(
+            update_character_details_with_conn(
+                &conn,
+                &UpdateCharacterDetailsInput {
+                    id: 99,
+                    ..edit("x")
+                }
+            ),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_character_spellbook_with_conn_filters_by_max_level() {
+        let conn = Connection::open_in_memory().expect("open db");
+        conn.execute_batch(
+            r#"
+            CREATE TABLE spell (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                level INTEGER NOT NULL,
+                school TEXT,
+                sphere TEXT,
+                is_quest_spell INTEGER DEFAULT 0,
+                is_cantrip INTEGER DEFAULT 0,
+                tags TEXT
+            );
+            CREATE TABLE spellbook (
+                character_id INTEGER,
+                spell_id INTEGER,
+                prepared INTEGER DEFAULT 0,
+                known INTEGER DEFAULT 1,
+                notes TEXT,
+                PRIMARY KEY(character_id, spell_id)
+            );
+            INSERT INTO spell (id, name, level) VALUES
+                (1, 'Magic Missile', 1), (2, 'Web', 2), (3, 'Fireball', 3), (4, 'Cone of Cold', 5);
+            INSERT INTO spellbook (character_id, spell_id) VALUES (7, 1), (7, 2), (7, 3), (7, 4), (8, 1);
+            "#,
+        )
+        .expect("create schema");
+
+        let all = get_character_spellbook_with_conn(&conn, 7, None).unwrap();
+        assert_eq!(all.len(), 4);
+
+        let castable = get_character_spellbook_with_conn(&conn, 7, Some(2)).unwrap();
+        let names: Vec<&str> = castable.iter().map(|e| e.spell_name.as_str()).collect();
+        assert_eq!(names, vec!["Magic Missile", "Web"]);
+    }
+
+    #[test]
+    fn test_orphaned_spellbook_entries_are_found_and_pruned() {
+        let conn = Connection::open_in_memory().expect("open db");
+        conn.execute_batch(
+            r#"
+            PRAGMA foreign_keys = OFF;
+            CREATE TABLE spell (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+            CREATE TABLE spellbook (
+                character_id INTEGER,
+                spell_id INTEGER REFERENCES spell(id) ON DELETE CASCADE,
+                prepared INTEGER DEFAULT 0,
+                known INTEGER DEFAULT 1,
+                notes TEXT,
+                PRIMARY KEY(character_id, spell_id)
+            );
+            INSERT INTO spell (id, name) VALUES (1, 'Sleep');
+            INSERT INTO spellbook (character_id, spell_id) VALUES (3, 1), (3, 42);
+            "#,
+        )
+        .expect("create schema");
+
+        assert_eq!(
+            find_orphaned_spellbook_entries_with_conn(&conn).unwrap(),
+            vec![OrphanedSpellbookEntry {
+                character_id: 3,
+                spell_id: 42,
+            }]
+        );
+
+        assert_eq!(
+            prune_orphaned_spellbook_entries_with_conn(&conn).unwrap(),
+            1
+        );
+        assert!(find_orphaned_spellbook_entries_with_conn(&conn)
+            .unwrap()
+            .is_empty());
+        let remaining: i64 = conn
+            .query_row("SELECT COUNT(*) FROM spellbook", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 1);
+    }
+
+    fn setup_spellbook_copy_test_db() -> Connection {
+        let conn = Connection::open_in_memory().expect("open db");
+        conn.execute_batch(
+            r#"
+            CREATE TABLE "character" (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+            CREATE TABLE spellbook (
+                character_id INTEGER,
+                spell_id INTEGER,
+                prepared INTEGER DEFAULT 0,
+                known INTEGER DEFAULT 1,
+                notes TEXT,
+                PRIMARY KEY(character_id, spell_id)
+            );
+            INSERT INTO "character" (id, name) VALUES (1, 'Elminster'), (2, 'Mordenkainen');
+            INSERT INTO spellbook (character_id, spell_id, prepared, known, notes)
+                VALUES (1, 10, 1, 1, 'Keep a spare scroll');
+            INSERT INTO spellbook (character_id, spell_id, prepared, known, notes)
+                VALUES (2, 10, 0, 1, NULL);
+            "#,
+        )
+        .expect("create schema");
+        conn
+    }
+
+    fn spellbook_row(conn: &Connection, character_id: i64) -> Option<(i64, i64, Option<String>)> {
+        conn.query_row(
+            "SELECT prepared, known, notes FROM spellbook WHERE character_id = ? AND spell_id = 10",
+            [character_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_copy_spellbook_entry_upserts_flags_and_notes() {
+        let mut conn = setup_spellbook_copy_test_db();
+
+        copy_spellbook_entry_with_conn(&mut conn, 1, 2, 10, false).unwrap();
+
+        let expected = Some((1, 1, Some("Keep a spare scroll".to_string())));
+        assert_eq!(spellbook_row(&conn, 2), expected);
+        assert_eq!(spellbook_row(&conn, 1), expected);
+
+        assert!(matches!(
+            copy_spellbook_entry_with_conn(&mut conn, 1, 99, 10, false),
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            copy_spellbook_entry_with_conn(&mut conn, 1, 2, 11, false),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_copy_spellbook_entry_move_empties_source() {
+        let mut conn = setup_spellbook_copy_test_db();
+
+        copy_spellbook_entry_with_conn(&mut conn, 1, 2, 10, true).unwrap();
+
+        assert_eq!(
+            spellbook_row(&conn, 2),
+            Some((1, 1, Some("Keep a spare scroll".to_string())))
+        );
+        assert_eq!(spellbook_row(&conn, 1), None);
+    }
+
+    fn setup_learn_spell_test_db() -> Connection {
+        let conn = setup_spellbook_copy_test_db();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE spell (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                level INTEGER NOT NULL,
+                school TEXT,
+                sphere TEXT,
+                source TEXT,
+                tags TEXT,
+                is_quest_spell INTEGER DEFAULT 0,
+                is_cantrip INTEGER DEFAULT 0
+            );
+            INSERT INTO spell (id, name, level, school, source) VALUES
+                (10, 'Magic Missile', 1, 'Evocation', 'PHB'),
+                (20, 'Light', 1, 'Alteration', 'PHB'),
+                (21, 'Light', 1, 'Evocation', 'Tome');
+            "#,
+        )
+        .expect("create spell table");
+        conn
+    }
+
+    #[test]
+    fn test_learn_spell_upserts_entry_and_keeps_notes() {
+        let mut conn = setup_learn_spell_test_db();
+
+        let entry = learn_spell_with_conn(&mut conn, 1, 10, false, true).unwrap();
+        assert_eq!(entry.spell_name, "Magic Missile");
+        assert!(!entry.prepared && entry.known);
+        assert_eq!(entry.notes.as_deref(), Some("Keep a spare scroll"));
+
+        let entry = learn_spell_with_conn(&mut conn, 2, 20, true, true).unwrap();
+        assert_eq!((entry.character_id, entry.spell_id), (2, 20));
+        assert!(entry.prepared);
+
+        assert!(matches!(
+            learn_spell_with_conn(&mut conn, 99, 10, false, true),
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            learn_spell_with_conn(&mut conn, 1, 999, false, true),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_learn_spell_by_name_resolves_and_rejects_ambiguity() {
+        let mut conn = setup_learn_spell_test_db();
+
+        assert_eq!(
+            resolve_spell_id_by_name(&conn, "magic missile", None).unwrap(),
+            10
+        );
+        assert!(matches!(
+            resolve_spell_id_by_name(&conn, "Light", None),
+            Err(AppError::Validation(msg)) if msg.contains("ambiguous")
+        ));
+        let spell_id = resolve_spell_id_by_name(&conn, "Light", Some("Tome")).unwrap();
+        assert_eq!(spell_id, 21);
+        let entry = learn_spell_with_conn(&mut conn, 1, spell_id, false, true).unwrap();
+        assert_eq!(entry.spell_school.as_deref(), Some("Evocation"));
+        assert!(matches!(
+            resolve_spell_id_by_name(&conn, "Wish", None),
+            Err(AppError::NotFound(_))
+        ));
+    }
 }
 
 /// Deprecated: legacy spellbook command. Use the per-class system instead.
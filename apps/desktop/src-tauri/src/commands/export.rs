@@ -1,19 +1,32 @@
-use crate::commands::spells::get_spell_from_conn;
+use crate::commands::search::search_result_ids_capped;
+use crate::commands::spells::{
+    canonicalize_spell_detail, diff_spells, get_spell_from_conn, spell_detail_to_update,
+    validate_all_spells_with_conn,
+};
+use crate::commands::vault::load_conversion_config;
 use crate::db::Pool;
 use crate::error::AppError;
 use crate::models::{
     canonical_spell::{CanonicalSpell, BUNDLE_FORMAT_VERSION, CURRENT_SCHEMA_VERSION},
-    CharacterAbilities, CharacterClass, PrintableCharacter, PrintableSpellbookEntry,
+    format_numeric, CharacterAbilities, CharacterClass, ConversionConfig, DurationUnit,
+    PrintableCharacter, PrintableSpellbook, PrintableSpellbookEntry, RangeUnit, SearchFilters,
+    SpellDetail, SpellValidationResult,
 };
 use crate::sidecar::call_sidecar;
+use crate::utils::compression::write_export_json;
+use chrono::Utc;
 use dirs::data_dir as system_data_dir;
 use rusqlite::OptionalExtension;
 use serde::Serialize;
 use serde_json::json;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::State;
+use tauri_plugin_fs::FsExt;
+use tracing::warn;
 
 /// Envelope for bundle export. Keys are snake_case per canonical contract.
 #[derive(Serialize)]
@@ -24,6 +37,49 @@ struct SpellBundleExport {
     spells: Vec<CanonicalSpell>,
 }
 
+/// Result of a sidecar PDF print. `pages` and `spell_pages` (spell id to starting page)
+/// are only set when the sidecar reports them, for building a table of contents.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintResult {
+    pub path: String,
+    pub pages: Option<i64>,
+    pub spell_pages: Option<HashMap<i64, i64>>,
+}
+
+/// Reads `path`, `pages`, and `spell_page_map` from a sidecar `export` result;
+/// map entries whose key or page is not an integer are dropped.
+fn print_result_from_sidecar(result: &serde_json::Value) -> PrintResult {
+    let spell_pages = result
+        .get("spell_page_map")
+        .and_then(|v| v.as_object())
+        .map(|map| {
+            map.iter()
+                .filter_map(|(id, page)| Some((id.parse::<i64>().ok()?, page.as_i64()?)))
+                .collect()
+        });
+    PrintResult {
+        path: result
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string(),
+        pages: result.get("pages").and_then(|v| v.as_i64()),
+        spell_pages,
+    }
+}
+
+/// Serializes a native JSON export, indented when `pretty` is set. Layout only;
+/// the content hash is always computed over the JCS form and is unaffected.
+fn to_export_json<T: Serialize>(value: &T, pretty: bool) -> Result<String, AppError> {
+    let out = if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    };
+    out.map_err(|e| AppError::Export(e.to_string()))
+}
+
 fn app_data_dir() -> Result<PathBuf, AppError> {
     if let Ok(override_dir) = std::env::var("SPELLBOOK_DATA_DIR") {
         let dir = PathBuf::from(override_dir);
@@ -37,6 +93,81 @@ fn app_data_dir() -> Result<PathBuf, AppError> {
     Ok(dir)
 }
 
+/// Directory an export or print is written to: `app_data_dir()/<default_subdir>` when
+/// `output_dir` is `None`. An absolute `output_dir` must already exist and be in the fs
+/// plugin's scope, which the dialog plugin's folder picker adds it to; a relative one is
+/// taken under the data dir and created, and may not climb out of it with `..`. Either way
+/// it must be writable.
+pub(crate) fn resolve_output_dir<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    output_dir: Option<&str>,
+    default_subdir: &str,
+) -> Result<PathBuf, AppError> {
+    let scope = app.try_fs_scope();
+    resolve_output_dir_in_scope(output_dir, default_subdir, |path| {
+        scope.as_ref().is_some_and(|scope| scope.is_allowed(path))
+    })
+}
+
+/// `resolve_output_dir` with the scope check supplied by the caller.
+fn resolve_output_dir_in_scope(
+    output_dir: Option<&str>,
+    default_subdir: &str,
+    in_scope: impl Fn(&Path) -> bool,
+) -> Result<PathBuf, AppError> {
+    let root = app_data_dir()?;
+    let dir = match output_dir.map(str::trim).filter(|dir| !dir.is_empty()) {
+        None => {
+            let dir = root.join(default_subdir);
+            fs::create_dir_all(&dir)?;
+            return Ok(dir);
+        }
+        Some(dir) => {
+            let path = Path::new(dir);
+            if path.is_absolute() {
+                if !path.is_dir() {
+                    return Err(AppError::Validation(format!(
+                        "Output directory does not exist: {}",
+                        dir
+                    )));
+                }
+                if !in_scope(path) {
+                    return Err(AppError::Validation(format!(
+                        "Output directory was not chosen through the folder picker: {}",
+                        dir
+                    )));
+                }
+                path.to_path_buf()
+            } else {
+                if path
+                    .components()
+                    .any(|c| !matches!(c, std::path::Component::Normal(_)))
+                {
+                    return Err(AppError::Validation(format!(
+                        "Relative output directory must stay inside the data directory: {}",
+                        dir
+                    )));
+                }
+                let path = root.join(path);
+                fs::create_dir_all(&path)?;
+                path
+            }
+        }
+    };
+
+    let probe = dir.join(format!(".spellbook-write-check-{}", std::process::id()));
+    fs::write(&probe, b"")
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|e| {
+            AppError::Validation(format!(
+                "Output directory is not writable ({}): {}",
+                dir.display(),
+                e
+            ))
+        })?;
+    Ok(dir)
+}
+
 fn load_character_printable_spells(
     conn: &rusqlite::Connection,
     character_id: i64,
@@ -187,18 +318,406 @@ fn load_character_printable_spells(
     Ok(out)
 }
 
+/// List exports `export_spells` can write without the Python sidecar.
+const NATIVE_EXPORT_FORMATS: &[&str] = &["json", "csv", "md"];
+
+const NATIVE_CSV_COLUMNS: &[&str] = &[
+    "name",
+    "level",
+    "school",
+    "sphere",
+    "class_list",
+    "range",
+    "components",
+    "casting_time",
+    "duration",
+    "area",
+    "saving_throw",
+    "source",
+];
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_native_csv(spells: &[SpellDetail]) -> String {
+    let mut out = NATIVE_CSV_COLUMNS.join(",");
+    out.push('\n');
+    for spell in spells {
+        let level = spell.level.to_string();
+        let row = [
+            spell.name.as_str(),
+            level.as_str(),
+            spell.school.as_deref().unwrap_or(""),
+            spell.sphere.as_deref().unwrap_or(""),
+            spell.class_list.as_deref().unwrap_or(""),
+            spell.range.as_deref().unwrap_or(""),
+            spell.components.as_deref().unwrap_or(""),
+            spell.casting_time.as_deref().unwrap_or(""),
+            spell.duration.as_deref().unwrap_or(""),
+            spell.area.as_deref().unwrap_or(""),
+            spell.saving_throw.as_deref().unwrap_or(""),
+            spell.source.as_deref().unwrap_or(""),
+        ];
+        out.push_str(&row.map(csv_field).join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Mirrors the sidecar's list-mode markdown: one heading and description per spell.
+fn render_native_markdown(spells: &[SpellDetail]) -> String {
+    let blocks: Vec<String> = spells
+        .iter()
+        .map(|spell| {
+            format!(
+                "# {}\n\n{}",
+                markdown_escape(spell.name.trim()),
+                markdown_escape(spell.description.trim())
+            )
+        })
+        .collect();
+    format!("{}\n", blocks.join("\n\n").trim())
+}
+
+/// Writes a list export for one of `NATIVE_EXPORT_FORMATS` and returns its path.
+fn write_native_spell_export(
+    spells: &[SpellDetail],
+    format: &str,
+    pretty: bool,
+    output_dir: &Path,
+) -> Result<PathBuf, AppError> {
+    let contents = match format {
+        "json" => to_export_json(&spells, pretty)?,
+        "csv" => render_native_csv(spells),
+        "md" => render_native_markdown(spells),
+        other => {
+            return Err(AppError::Export(format!(
+                "Unsupported native export format: {}",
+                other
+            )))
+        }
+    };
+    fs::create_dir_all(output_dir)?;
+    let path = output_dir.join(format!(
+        "spellbook_export_{}.{}",
+        Utc::now().format("%Y%m%dT%H%M%S%3f"),
+        format
+    ));
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Writes a `validate_all_spells` report as `csv` or `json` and returns its path.
+fn write_validation_report(
+    rows: &[SpellValidationResult],
+    format: &str,
+    output_dir: &Path,
+) -> Result<PathBuf, AppError> {
+    let contents = match format {
+        "json" => to_export_json(&rows, true)?,
+        "csv" => {
+            let mut out = String::from("id,name,valid,error\n");
+            for row in rows {
+                let id = row.id.to_string();
+                let valid = row.valid.to_string();
+                let fields = [
+                    id.as_str(),
+                    row.name.as_str(),
+                    valid.as_str(),
+                    row.error.as_deref().unwrap_or(""),
+                ];
+                out.push_str(&fields.map(csv_field).join(","));
+                out.push('\n');
+            }
+            out
+        }
+        other => {
+            return Err(AppError::Export(format!(
+                "Unsupported validation report format: {} (expected \"csv\" or \"json\")",
+                other
+            )))
+        }
+    };
+    fs::create_dir_all(output_dir)?;
+    let path = output_dir.join(format!(
+        "validation_report_{}.{}",
+        Utc::now().format("%Y%m%dT%H%M%S%3f"),
+        format
+    ));
+    fs::write(&path, contents)?;
+    Ok(path)
+}
+
+/// Runs `validate_all_spells` over the library and writes one `{id, name, valid, error}`
+/// row per spell to the exports dir.
+#[tauri::command]
+pub async fn export_validation_report(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    format: String,
+    output_dir: Option<String>,
+) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    let rows = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        validate_all_spells_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    let output_dir = resolve_output_dir(&app, output_dir.as_deref(), "exports")?;
+    let path = write_validation_report(&rows, &format, &output_dir)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
 #[tauri::command]
 pub async fn export_spells(
+    app: tauri::AppHandle,
     state: State<'_, Arc<Pool>>,
     ids: Vec<i64>,
     format: String,
+    output_dir: Option<String>,
+    pretty: Option<bool>,
+) -> Result<String, AppError> {
+    export_spell_list(
+        &app,
+        state.inner().clone(),
+        ids,
+        format,
+        output_dir,
+        pretty.unwrap_or(true),
+    )
+    .await
+}
+
+/// Recomputes canonical JSON for `spell` and stores it in `canonical_cache` under the
+/// spell's stored `content_hash`, the key lookups use. A row whose stored hash no longer
+/// matches its content is not cached; `repair_content_hashes` fixes it first.
+fn store_canonical_cache(
+    conn: &rusqlite::Connection,
+    spell: &SpellDetail,
+) -> Result<(String, String), AppError> {
+    let spell_id = spell
+        .id
+        .ok_or_else(|| AppError::Validation("Spell has no id".into()))?;
+    let (_, computed_hash, canonical_json) = canonicalize_spell_detail(spell.clone())?;
+    if spell.content_hash.as_deref() != Some(computed_hash.as_str()) {
+        return Err(AppError::Validation(format!(
+            "Spell id {} has a stale content hash; repair it before caching canonical JSON",
+            spell_id
+        )));
+    }
+    conn.execute(
+        "INSERT INTO canonical_cache (spell_id, content_hash, canonical_json, computed_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(spell_id) DO UPDATE SET
+           content_hash = excluded.content_hash,
+           canonical_json = excluded.canonical_json,
+           computed_at = excluded.computed_at",
+        rusqlite::params![
+            spell_id,
+            computed_hash,
+            canonical_json,
+            Utc::now().to_rfc3339()
+        ],
+    )?;
+    Ok((computed_hash, canonical_json))
+}
+
+/// SQL expression for the `canonical_cache` entry of the `spell s` row, `NULL` when there
+/// is none for the row's current content hash.
+const EXPORT_CACHED_JSON_SQL: &str = "(SELECT c.canonical_json FROM canonical_cache c
+     WHERE c.spell_id = s.id AND c.content_hash = s.content_hash)";
+
+/// Canonical JSON for `spell`, served from `canonical_cache` when the entry was computed for
+/// the spell's current `content_hash`. A missing or stale entry is recomputed and stored; a
+/// row whose stored hash has drifted from its content is not cached and is served from its
+/// `canonical_data`. The flag is `true` on a cache hit.
+fn cached_canonical_json(
+    conn: &rusqlite::Connection,
+    spell: &SpellDetail,
+) -> Result<(String, bool), AppError> {
+    if let (Some(spell_id), Some(current_hash)) = (spell.id, spell.content_hash.as_deref()) {
+        let cached: Option<String> = conn
+            .query_row(
+                "SELECT canonical_json FROM canonical_cache WHERE spell_id = ? AND content_hash = ?",
+                rusqlite::params![spell_id, current_hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(canonical_json) = cached {
+            return Ok((canonical_json, true));
+        }
+    }
+    match store_canonical_cache(conn, spell) {
+        Ok((_, canonical_json)) => Ok((canonical_json, false)),
+        Err(AppError::Validation(_)) => match &spell.canonical_data {
+            Some(canonical_json) => Ok((canonical_json.clone(), false)),
+            None => {
+                let (_, _, canonical_json) = canonicalize_spell_detail(spell.clone())?;
+                Ok((canonical_json, false))
+            }
+        },
+        Err(e) => Err(e),
+    }
+}
+
+/// Canonical JSON for an export row: the cache entry [`EXPORT_CACHED_JSON_SQL`] found, or
+/// on a miss the spell's JSON through [`cached_canonical_json`], which fills the cache.
+fn export_row_canonical_json(
+    conn: &rusqlite::Connection,
+    spell_id: i64,
+    cached_json: Option<String>,
+) -> Result<String, AppError> {
+    if let Some(canonical_json) = cached_json {
+        return Ok(canonical_json);
+    }
+    let spell = get_spell_from_conn(conn, spell_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Spell id {} not found", spell_id)))?;
+    cached_canonical_json(conn, &spell).map(|(canonical_json, _)| canonical_json)
+}
+
+/// The columns the canonical JSON exports read for one spell.
+struct ExportCanonicalRow {
+    name: String,
+    content_hash: Option<String>,
+    cached_json: Option<String>,
+    logical_id: Option<String>,
+}
+
+fn export_canonical_row(
+    conn: &rusqlite::Connection,
+    spell_id: i64,
+) -> Result<Option<ExportCanonicalRow>, AppError> {
+    Ok(conn
+        .query_row(
+            &format!(
+                "SELECT s.name, s.content_hash, {EXPORT_CACHED_JSON_SQL}, s.logical_id
+                 FROM spell s WHERE s.id = ?"
+            ),
+            [spell_id],
+            |row| {
+                Ok(ExportCanonicalRow {
+                    name: row.get(0)?,
+                    content_hash: row.get(1)?,
+                    cached_json: row.get(2)?,
+                    logical_id: row.get(3)?,
+                })
+            },
+        )
+        .optional()?)
+}
+
+fn refresh_canonical_cache_with_conn(
+    conn: &rusqlite::Connection,
+    spell_id: i64,
+) -> Result<String, AppError> {
+    let spell = get_spell_from_conn(conn, spell_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Spell id {} not found", spell_id)))?;
+    let (content_hash, _) = store_canonical_cache(conn, &spell)?;
+    Ok(content_hash)
+}
+
+/// Recomputes and caches the canonical JSON for one spell; returns the content hash.
+#[tauri::command]
+pub async fn refresh_canonical_cache(
+    state: State<'_, Arc<Pool>>,
+    id: i64,
+) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        refresh_canonical_cache_with_conn(&conn, id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+fn get_canonical_json_with_conn(
+    conn: &rusqlite::Connection,
+    spell_id: i64,
+) -> Result<String, AppError> {
+    let spell = get_spell_from_conn(conn, spell_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Spell id {} not found", spell_id)))?;
+    let canonical = CanonicalSpell::try_from(spell).map_err(AppError::Validation)?;
+    // compute_hash validates against the schema; surface its reason before serializing.
+    canonical.compute_hash().map_err(AppError::Validation)?;
+    canonical.to_canonical_json().map_err(AppError::Validation)
+}
+
+/// Raw JCS canonical JSON for one spell, for external signing tools; its SHA-256 is the
+/// spell's content hash.
+#[tauri::command]
+pub async fn get_canonical_json(state: State<'_, Arc<Pool>>, id: i64) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_canonical_json_with_conn(&conn, id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Exports every spell matching a search (not just the first page). `format` "bundle"
+/// returns the canonical bundle JSON like `export_spell_bundle_json`; any other format is
+/// written to a file like `export_spells` and its path is returned.
+#[tauri::command]
+pub async fn export_search_results(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    query: String,
+    filters: Option<SearchFilters>,
+    format: String,
+    output_dir: Option<String>,
+    pretty: Option<bool>,
 ) -> Result<String, AppError> {
+    let pretty = pretty.unwrap_or(true);
     let pool = state.inner().clone();
+    let ids = {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = pool.get()?;
+            search_result_ids_capped(&conn, &query, filters, "exporting")
+        })
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))??
+    };
+
+    if format == "bundle" {
+        return tokio::task::spawn_blocking(move || {
+            let conn = pool.get()?;
+            export_spell_bundle_json_impl(&conn, ids, pretty)
+        })
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))?;
+    }
+    export_spell_list(&app, pool, ids, format, output_dir, pretty).await
+}
+
+async fn export_spell_list(
+    app: &tauri::AppHandle,
+    pool: Arc<Pool>,
+    ids: Vec<i64>,
+    format: String,
+    output_dir: Option<String>,
+    pretty: bool,
+) -> Result<String, AppError> {
     let spells = tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
         let mut spells = vec![];
         for id in ids {
-            if let Some(spell) = get_spell_from_conn(&conn, id)? {
+            if let Some(mut spell) = get_spell_from_conn(&conn, id)? {
+                match cached_canonical_json(&conn, &spell) {
+                    Ok((canonical_json, _)) => spell.canonical_data = Some(canonical_json),
+                    Err(e) => {
+                        warn!(spell_id = id, error = %e, "canonical JSON unavailable for export")
+                    }
+                }
                 spells.push(spell);
             }
         }
@@ -207,12 +726,23 @@ pub async fn export_spells(
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
 
-    let output_dir = app_data_dir()?.join("exports");
-    let result = call_sidecar(
+    let output_dir = resolve_output_dir(app, output_dir.as_deref(), "exports")?;
+    let result = match call_sidecar(
         "export",
         json!({"spells": spells, "format": format, "output_dir": output_dir}),
     )
-    .await?;
+    .await
+    {
+        Ok(result) => result,
+        Err(AppError::SidecarUnavailable(reason))
+            if NATIVE_EXPORT_FORMATS.contains(&format.as_str()) =>
+        {
+            warn!(%reason, %format, "sidecar unavailable; using native spell export");
+            let path = write_native_spell_export(&spells, &format, pretty, &output_dir)?;
+            return Ok(path.to_string_lossy().into_owned());
+        }
+        Err(e) => return Err(e),
+    };
 
     Ok(result
         .get("path")
@@ -221,15 +751,425 @@ pub async fn export_spells(
         .to_string())
 }
 
+/// Fields shown in a spell comparison, in display order. Names match `diff_spells`.
+const COMPARISON_FIELDS: &[&str] = &[
+    "name",
+    "level",
+    "school",
+    "sphere",
+    "class_list",
+    "range",
+    "components",
+    "material_components",
+    "casting_time",
+    "duration",
+    "area",
+    "saving_throw",
+    "damage",
+    "magic_resistance",
+    "reversible",
+    "is_cantrip",
+    "tags",
+    "source",
+    "edition",
+    "author",
+    "license",
+    "description",
+];
+
+/// Converted magnitude for a structured range, duration or area: feet for non-foot ranges,
+/// rounds for timed durations, and the square size for tile areas. Turns, segments and
+/// squares follow the table's house rules.
+fn converted_field_note(
+    spell: &SpellDetail,
+    field: &str,
+    config: &ConversionConfig,
+) -> Option<String> {
+    match field {
+        "range" => {
+            let spec = spell.range_spec.as_ref()?;
+            if spec.unit == Some(RangeUnit::Ft) {
+                return None;
+            }
+            spec.in_feet()
+                .map(|feet| format!("{} ft", format_numeric(feet)))
+        }
+        "duration" => {
+            let spec = spell.duration_spec.as_ref()?;
+            if spec.unit == Some(DurationUnit::Round) {
+                return None;
+            }
+            spec.in_rounds_with(config).map(|rounds| {
+                let unit = if rounds == 1.0 { "round" } else { "rounds" };
+                format!("{} {}", format_numeric(rounds), unit)
+            })
+        }
+        "area" => spell
+            .area_spec
+            .as_ref()?
+            .tile_edge_in_feet(config)
+            .map(|feet| format!("{}-ft squares", format_numeric(feet))),
+        _ => None,
+    }
+}
+
+fn comparison_field_value(spell: &SpellDetail, field: &str, config: &ConversionConfig) -> String {
+    let text = |value: &Option<String>| value.clone().unwrap_or_default();
+    let converted = |value: &Option<String>| match converted_field_note(spell, field, config) {
+        Some(note) => format!("{} ({})", text(value), note),
+        None => text(value),
+    };
+    match field {
+        "name" => spell.name.clone(),
+        "level" => spell.level.to_string(),
+        "school" => text(&spell.school),
+        "sphere" => text(&spell.sphere),
+        "class_list" => text(&spell.class_list),
+        "range" => converted(&spell.range),
+        "components" => text(&spell.components),
+        "material_components" => text(&spell.material_components),
+        "casting_time" => text(&spell.casting_time),
+        "duration" => converted(&spell.duration),
+        "area" => converted(&spell.area),
+        "saving_throw" => text(&spell.saving_throw),
+        "damage" => text(&spell.damage),
+        "magic_resistance" => text(&spell.magic_resistance),
+        "reversible" => spell.reversible.unwrap_or(0).to_string(),
+        "is_cantrip" => spell.is_cantrip.to_string(),
+        "tags" => text(&spell.tags),
+        "source" => text(&spell.source),
+        "edition" => text(&spell.edition),
+        "author" => text(&spell.author),
+        "license" => text(&spell.license),
+        "description" => spell.description.clone(),
+        _ => String::new(),
+    }
+}
+
+/// "casting_time" -> "Casting Time".
+fn comparison_field_label(field: &str) -> String {
+    field
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Backslash-escapes markdown inline control characters (and raw `<`/`>`, which most
+/// renderers pass through as HTML) so spell text reads literally.
+fn markdown_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn markdown_cell(value: &str) -> String {
+    markdown_escape(value.trim())
+        .replace('|', "\\|")
+        .replace('\n', "<br>")
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Renders a side-by-side comparison of two spells as `markdown` or `html`. Rows for
+/// fields reported by `diff_spells` are highlighted; structured ranges, durations and
+/// areas carry their converted magnitude under `config`.
+fn render_spell_comparison(
+    a: &SpellDetail,
+    b: &SpellDetail,
+    format: &str,
+    config: &ConversionConfig,
+) -> Result<String, AppError> {
+    let differing: Vec<String> = diff_spells(a, &spell_detail_to_update(b, a.id.unwrap_or(0)))
+        .into_iter()
+        .map(|(field, _, _)| field)
+        .collect();
+    let rows: Vec<(String, String, String, bool)> = COMPARISON_FIELDS
+        .iter()
+        .map(|field| {
+            (
+                comparison_field_label(field),
+                comparison_field_value(a, field, config),
+                comparison_field_value(b, field, config),
+                differing.iter().any(|d| d == field),
+            )
+        })
+        .collect();
+
+    match format {
+        "markdown" => {
+            let mut out = format!(
+                "# Spell comparison: {} vs {}\n\n{} of {} fields differ.\n\n",
+                markdown_escape(&a.name),
+                markdown_escape(&b.name),
+                differing.len(),
+                COMPARISON_FIELDS.len()
+            );
+            out.push_str(&format!(
+                "| Field | A: {} | B: {} |\n| --- | --- | --- |\n",
+                markdown_cell(&a.name),
+                markdown_cell(&b.name)
+            ));
+            for (label, left, right, differs) in rows {
+                if differs {
+                    out.push_str(&format!(
+                        "| **{}** | **{}** | **{}** |\n",
+                        label,
+                        markdown_cell(&left),
+                        markdown_cell(&right)
+                    ));
+                } else {
+                    out.push_str(&format!(
+                        "| {} | {} | {} |\n",
+                        label,
+                        markdown_cell(&left),
+                        markdown_cell(&right)
+                    ));
+                }
+            }
+            Ok(out)
+        }
+        "html" => {
+            let title = format!(
+                "Spell comparison: {} vs {}",
+                html_escape(&a.name),
+                html_escape(&b.name)
+            );
+            let mut out = format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+                 <style>\n\
+                 table {{ border-collapse: collapse; width: 100%; }}\n\
+                 th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; white-space: pre-wrap; }}\n\
+                 tr.differs td {{ background: #fff3cd; font-weight: bold; }}\n\
+                 </style>\n</head>\n<body>\n<h1>{title}</h1>\n\
+                 <p>{} of {} fields differ.</p>\n<table>\n\
+                 <tr><th>Field</th><th>A: {}</th><th>B: {}</th></tr>\n",
+                differing.len(),
+                COMPARISON_FIELDS.len(),
+                html_escape(&a.name),
+                html_escape(&b.name)
+            );
+            for (label, left, right, differs) in rows {
+                let class = if differs { " class=\"differs\"" } else { "" };
+                out.push_str(&format!(
+                    "<tr{class}><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    label,
+                    html_escape(left.trim()),
+                    html_escape(right.trim())
+                ));
+            }
+            out.push_str("</table>\n</body>\n</html>\n");
+            Ok(out)
+        }
+        other => Err(AppError::Validation(format!(
+            "Unsupported comparison format: {} (expected \"markdown\" or \"html\")",
+            other
+        ))),
+    }
+}
+
+/// Writes a side-by-side comparison of two spells to the exports dir and returns its path.
+#[tauri::command]
+pub async fn export_spell_comparison(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    id_a: i64,
+    id_b: i64,
+    format: String,
+    output_dir: Option<String>,
+) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    let (a, b, config) = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let load = |id: i64| {
+            get_spell_from_conn(&conn, id)?
+                .ok_or_else(|| AppError::NotFound(format!("Spell {} not found", id)))
+        };
+        let config = load_conversion_config(&app_data_dir()?)?;
+        Ok::<_, AppError>((load(id_a)?, load(id_b)?, config))
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    let contents = render_spell_comparison(&a, &b, &format, &config)?;
+    let extension = if format == "html" { "html" } else { "md" };
+    let output_dir = resolve_output_dir(&app, output_dir.as_deref(), "exports")?;
+    let path = output_dir.join(format!(
+        "spell_comparison_{}.{}",
+        Utc::now().format("%Y%m%dT%H%M%S%3f"),
+        extension
+    ));
+    fs::write(&path, contents)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Page number placeholder printed next to each index entry, filled in by hand once the
+/// PDF is laid out.
+const SPELL_INDEX_PAGE_PLACEHOLDER: &str = "p. ___";
+
+fn spell_index_group(spell: &SpellDetail, by: &str) -> String {
+    match by {
+        "level" => format!("Level {}", spell.level),
+        "school" => spell
+            .school
+            .as_deref()
+            .map(str::trim)
+            .filter(|school| !school.is_empty())
+            .unwrap_or("Unspecified")
+            .to_string(),
+        _ => spell
+            .name
+            .trim()
+            .chars()
+            .next()
+            .map(|c| c.to_uppercase().collect())
+            .unwrap_or_else(|| "#".to_string()),
+    }
+}
+
+/// Renders an index of `spells` grouped by `by` ("name", "level" or "school") as
+/// `markdown` or `html`; entries within a group are sorted by name.
+fn render_spell_index(spells: &[SpellDetail], by: &str, format: &str) -> Result<String, AppError> {
+    if !matches!(by, "name" | "level" | "school") {
+        return Err(AppError::Validation(format!(
+            "Unsupported index order: {} (expected \"name\", \"level\" or \"school\")",
+            by
+        )));
+    }
+    let mut sorted: Vec<&SpellDetail> = spells.iter().collect();
+    sorted.sort_by_cached_key(|spell| {
+        let group_key = match by {
+            "level" => format!("{:04}", spell.level),
+            _ => spell_index_group(spell, by).to_lowercase(),
+        };
+        (group_key, spell.name.to_lowercase(), spell.level)
+    });
+
+    let mut groups: Vec<(String, Vec<&SpellDetail>)> = vec![];
+    for spell in sorted {
+        let group = spell_index_group(spell, by);
+        match groups.last_mut() {
+            Some((current, entries)) if *current == group => entries.push(spell),
+            _ => groups.push((group, vec![spell])),
+        }
+    }
+    let entry_detail = |spell: &SpellDetail| match spell.school.as_deref() {
+        Some(school) if !school.trim().is_empty() => {
+            format!("Level {}, {}", spell.level, school.trim())
+        }
+        _ => format!("Level {}", spell.level),
+    };
+
+    match format {
+        "markdown" => {
+            let mut out = format!("# Spell index (by {})\n", by);
+            for (group, entries) in groups {
+                out.push_str(&format!("\n## {}\n\n", markdown_escape(&group)));
+                for spell in entries {
+                    out.push_str(&format!(
+                        "- {} — {} ... {}\n",
+                        markdown_cell(&spell.name),
+                        entry_detail(spell),
+                        SPELL_INDEX_PAGE_PLACEHOLDER
+                    ));
+                }
+            }
+            Ok(out)
+        }
+        "html" => {
+            let title = format!("Spell index (by {})", by);
+            let mut out = format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+                 </head>\n<body>\n<h1>{title}</h1>\n"
+            );
+            for (group, entries) in groups {
+                out.push_str(&format!("<h2>{}</h2>\n<ul>\n", html_escape(&group)));
+                for spell in entries {
+                    out.push_str(&format!(
+                        "<li>{} — {} ... {}</li>\n",
+                        html_escape(&spell.name),
+                        html_escape(&entry_detail(spell)),
+                        SPELL_INDEX_PAGE_PLACEHOLDER
+                    ));
+                }
+                out.push_str("</ul>\n");
+            }
+            out.push_str("</body>\n</html>\n");
+            Ok(out)
+        }
+        other => Err(AppError::Validation(format!(
+            "Unsupported index format: {} (expected \"markdown\" or \"html\")",
+            other
+        ))),
+    }
+}
+
+/// Writes an alphabetical, by-level or by-school index of the given spells to the exports
+/// dir and returns its path. Companion to the sidecar-rendered spellbook PDF.
+#[tauri::command]
+pub async fn export_spell_index(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    ids: Vec<i64>,
+    by: String,
+    format: Option<String>,
+    output_dir: Option<String>,
+) -> Result<String, AppError> {
+    let format = format.unwrap_or_else(|| "markdown".to_string());
+    let pool = state.inner().clone();
+    let spells = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        ids.iter()
+            .map(|id| {
+                get_spell_from_conn(&conn, *id)?
+                    .ok_or_else(|| AppError::NotFound(format!("Spell {} not found", id)))
+            })
+            .collect::<Result<Vec<_>, AppError>>()
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    let contents = render_spell_index(&spells, &by, &format)?;
+    let extension = if format == "html" { "html" } else { "md" };
+    let output_dir = resolve_output_dir(&app, output_dir.as_deref(), "exports")?;
+    let path = output_dir.join(format!(
+        "spell_index_{}_{}.{}",
+        by,
+        Utc::now().format("%Y%m%dT%H%M%S%3f"),
+        extension
+    ));
+    fs::write(&path, contents)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
 #[tauri::command]
 pub async fn export_spell_as_json(
     state: State<'_, Arc<Pool>>,
     spell_id: i64,
+    pretty: Option<bool>,
 ) -> Result<String, AppError> {
     let pool = state.inner().clone();
     tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        export_spell_as_json_impl(&conn, spell_id)
+        export_spell_as_json_impl(&conn, spell_id, pretty.unwrap_or(true))
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))?
@@ -238,68 +1178,90 @@ pub async fn export_spell_as_json(
 fn export_spell_as_json_impl(
     conn: &rusqlite::Connection,
     spell_id: i64,
+    pretty: bool,
 ) -> Result<String, AppError> {
-    let spell = get_spell_from_conn(conn, spell_id)?
+    let spell = export_canonical_row(conn, spell_id)?
         .ok_or_else(|| AppError::NotFound(format!("Spell id {} not found", spell_id)))?;
     let content_hash = spell.content_hash.as_ref().ok_or_else(|| {
         AppError::Export(
             "Spell has no content hash. Run the migration to backfill hashes (e.g. restart the app or use the CLI).".to_string(),
         )
     })?;
-    let canonical_json = spell.canonical_data.as_ref().ok_or_else(|| {
-        AppError::Export(
-            "Spell has no canonical data; cannot export as CanonicalSpell JSON.".to_string(),
-        )
-    })?;
-    let mut canonical: CanonicalSpell = serde_json::from_str(canonical_json)
+    let canonical_json = export_row_canonical_json(conn, spell_id, spell.cached_json)?;
+    let mut canonical: CanonicalSpell = serde_json::from_str(&canonical_json)
         .map_err(|e| AppError::Export(format!("Invalid canonical_data for spell: {}", e)))?;
     canonical.id = Some(content_hash.clone());
     canonical.schema_version = CURRENT_SCHEMA_VERSION;
-    serde_json::to_string(&canonical).map_err(|e| AppError::Export(e.to_string()))
+    to_export_json(&canonical, pretty)
 }
 
 #[tauri::command]
 pub async fn export_spell_bundle_json(
     state: State<'_, Arc<Pool>>,
     ids: Vec<i64>,
+    pretty: Option<bool>,
 ) -> Result<String, AppError> {
     let pool = state.inner().clone();
     tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        export_spell_bundle_json_impl(&conn, ids)
+        export_spell_bundle_json_impl(&conn, ids, pretty.unwrap_or(true))
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))?
 }
 
-fn export_spell_bundle_json_impl(
+/// Writes the bundle to the exports dir (or `output_dir`) as `.json`, or `.json.gz` with
+/// `compress`, and returns its path.
+#[tauri::command]
+pub async fn export_spell_bundle_file(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    ids: Vec<i64>,
+    pretty: Option<bool>,
+    compress: Option<bool>,
+    output_dir: Option<String>,
+) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let json = export_spell_bundle_json_impl(&conn, ids, pretty.unwrap_or(true))?;
+        let path = write_export_json(
+            &resolve_output_dir(&app, output_dir.as_deref(), "exports")?,
+            &format!("spell_bundle_{}", Utc::now().format("%Y%m%dT%H%M%S%3f")),
+            &json,
+            compress.unwrap_or(false),
+        )?;
+        Ok(path.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+pub(crate) fn export_spell_bundle_json_impl(
     conn: &rusqlite::Connection,
     ids: Vec<i64>,
+    pretty: bool,
 ) -> Result<String, AppError> {
     let mut spells: Vec<CanonicalSpell> = Vec::with_capacity(ids.len());
     let mut missing_hashes: Vec<String> = vec![];
     for id in ids {
-        let spell = match get_spell_from_conn(conn, id)? {
+        let spell = match export_canonical_row(conn, id)? {
             Some(s) => s,
             None => return Err(AppError::NotFound(format!("Spell id {} not found", id))),
         };
         let Some(content_hash) = &spell.content_hash else {
-            missing_hashes.push(format!("{} (id {})", spell.name, spell.id.unwrap_or(id)));
+            missing_hashes.push(format!("{} (id {})", spell.name, id));
             continue;
         };
-        let canonical_json = spell.canonical_data.as_ref().ok_or_else(|| {
-            AppError::Export(format!(
-                "Spell '{}' has no canonical data; cannot export.",
-                spell.name
-            ))
-        })?;
-        let mut canonical: CanonicalSpell = serde_json::from_str(canonical_json).map_err(|e| {
+        let canonical_json = export_row_canonical_json(conn, id, spell.cached_json.clone())?;
+        let mut canonical: CanonicalSpell = serde_json::from_str(&canonical_json).map_err(|e| {
             AppError::Export(format!(
                 "Invalid canonical_data for spell '{}': {}",
                 spell.name, e
             ))
         })?;
         canonical.id = Some(content_hash.clone());
+        canonical.logical_id = spell.logical_id.clone();
         canonical.schema_version = CURRENT_SCHEMA_VERSION;
         spells.push(canonical);
     }
@@ -314,16 +1276,93 @@ fn export_spell_bundle_json_impl(
         bundle_format_version: BUNDLE_FORMAT_VERSION,
         spells,
     };
-    serde_json::to_string(&envelope).map_err(|e| AppError::Export(e.to_string()))
+    to_export_json(&envelope, pretty)
+}
+
+/// Streams canonical spells to `out` as NDJSON: one compact `CanonicalSpell` per line, in
+/// id order, written as each row is read so memory stays flat however large the library.
+/// `ids` of `None` exports every spell. Returns the number of lines written.
+pub(crate) fn write_spells_ndjson<W: Write>(
+    conn: &rusqlite::Connection,
+    ids: Option<&[i64]>,
+    out: W,
+) -> Result<usize, AppError> {
+    let ids_json = ids
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| AppError::Export(e.to_string()))?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT s.id, s.name, s.content_hash, {EXPORT_CACHED_JSON_SQL} FROM spell s
+         WHERE ?1 IS NULL OR s.id IN (SELECT value FROM json_each(?1))
+         ORDER BY s.id"
+    ))?;
+    let mut rows = stmt.query([ids_json])?;
+    let mut out = BufWriter::new(out);
+    let mut written = 0usize;
+    while let Some(row) = rows.next()? {
+        let id: i64 = row.get(0)?;
+        let name: String = row.get(1)?;
+        let content_hash: Option<String> = row.get(2)?;
+        let cached_json: Option<String> = row.get(3)?;
+        let content_hash = content_hash.ok_or_else(|| {
+            AppError::Export(format!(
+                "Spell '{}' (id {}) has no content hash (run migration to backfill).",
+                name, id
+            ))
+        })?;
+        let canonical_json = export_row_canonical_json(conn, id, cached_json)?;
+        let mut canonical: CanonicalSpell = serde_json::from_str(&canonical_json).map_err(|e| {
+            AppError::Export(format!(
+                "Invalid canonical_data for spell '{}': {}",
+                name, e
+            ))
+        })?;
+        canonical.id = Some(content_hash);
+        canonical.schema_version = CURRENT_SCHEMA_VERSION;
+        serde_json::to_writer(&mut out, &canonical).map_err(|e| AppError::Export(e.to_string()))?;
+        out.write_all(b"\n")?;
+        written += 1;
+    }
+    out.flush()?;
+    Ok(written)
+}
+
+/// Writes `spells_<timestamp>.ndjson` to the exports dir (or `output_dir`) and returns its
+/// path. `ids` of `None` exports the whole library; a failed export leaves no partial file.
+#[tauri::command]
+pub async fn export_ndjson(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    ids: Option<Vec<i64>>,
+    output_dir: Option<String>,
+) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let path = resolve_output_dir(&app, output_dir.as_deref(), "exports")?.join(format!(
+            "spells_{}.ndjson",
+            Utc::now().format("%Y%m%dT%H%M%S%3f")
+        ));
+        let file = fs::File::create(&path)?;
+        if let Err(e) = write_spells_ndjson(&conn, ids.as_deref(), file) {
+            let _ = fs::remove_file(&path);
+            return Err(e);
+        }
+        Ok(path.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
 }
 
 #[tauri::command]
 pub async fn print_spell(
+    app: tauri::AppHandle,
     state: State<'_, Arc<Pool>>,
     spell_id: i64,
     layout: String,
     page_size: Option<String>,
-) -> Result<String, AppError> {
+    output_dir: Option<String>,
+) -> Result<PrintResult, AppError> {
     let pool = state.inner().clone();
     let spell = tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
@@ -333,7 +1372,7 @@ pub async fn print_spell(
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
 
-    let output_dir = app_data_dir()?.join("prints");
+    let output_dir = resolve_output_dir(&app, output_dir.as_deref(), "prints")?;
     let result = call_sidecar(
         "export",
         json!({
@@ -347,127 +1386,130 @@ pub async fn print_spell(
     )
     .await?;
 
-    Ok(result
-        .get("path")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string())
-}
-
-#[tauri::command]
-pub async fn print_spellbook(
-    state: State<'_, Arc<Pool>>,
-    character_id: i64,
-    layout: String,
-    page_size: Option<String>,
-) -> Result<String, AppError> {
-    let pool = state.inner().clone();
-    let (character, spells) = tokio::task::spawn_blocking(move || {
-        let conn = pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT name, type, race, alignment, notes FROM \"character\" WHERE id = ?",
-        )?;
-
-        // Fetch core character data
-        let (name, char_type, race, alignment, notes) = stmt.query_row(
-            [character_id],
-            |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, Option<String>>(2)?,
-                    row.get::<_, Option<String>>(3)?,
-                    row.get::<_, Option<String>>(4)?,
-                ))
-            },
-        )?;
-
-        // Fetch abilities
-        let abilities: Option<CharacterAbilities> = conn.query_row(
-            "SELECT id, character_id, str, dex, con, int, wis, cha, com FROM character_ability WHERE character_id = ?",
-            [character_id],
-            |row| {
-                Ok(CharacterAbilities {
-                    id: row.get(0)?,
-                    character_id: row.get(1)?,
-                    str: row.get(2)?,
-                    dex: row.get(3)?,
-                    con: row.get(4)?,
-                    int: row.get(5)?,
-                    wis: row.get(6)?,
-                    cha: row.get(7)?,
-                    com: row.get(8)?,
-                })
-            }
-        ).optional()?;
+    Ok(print_result_from_sidecar(&result))
+}
 
-        // Fetch classes
-        let mut stmt = conn.prepare("SELECT id, character_id, class_name, class_label, level FROM character_class WHERE character_id = ?")?;
-        let class_rows = stmt.query_map([character_id], |row| {
-            Ok(CharacterClass {
+/// Loads the print-ready character and spellbook dataset shared by `print_spellbook`
+/// (PDF via sidecar) and `get_printable_spellbook` (structured preview).
+fn load_printable_spellbook(
+    conn: &rusqlite::Connection,
+    character_id: i64,
+) -> Result<PrintableSpellbook, AppError> {
+    let mut stmt =
+        conn.prepare("SELECT name, type, race, alignment, notes FROM \"character\" WHERE id = ?")?;
+
+    // Fetch core character data
+    let (name, char_type, race, alignment, notes) = stmt.query_row([character_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+        ))
+    })?;
+
+    // Fetch abilities
+    let abilities: Option<CharacterAbilities> = conn.query_row(
+        "SELECT id, character_id, str, dex, con, int, wis, cha, com FROM character_ability WHERE character_id = ?",
+        [character_id],
+        |row| {
+            Ok(CharacterAbilities {
                 id: row.get(0)?,
                 character_id: row.get(1)?,
-                class_name: row.get(2)?,
-                class_label: row.get(3)?,
-                level: row.get(4)?,
+                str: row.get(2)?,
+                dex: row.get(3)?,
+                con: row.get(4)?,
+                int: row.get(5)?,
+                wis: row.get(6)?,
+                cha: row.get(7)?,
+                com: row.get(8)?,
             })
-        })?;
-        let mut classes = vec![];
-        for r in class_rows {
-            classes.push(r?);
         }
+    ).optional()?;
+
+    // Fetch classes
+    let mut stmt = conn.prepare("SELECT id, character_id, class_name, class_label, level FROM character_class WHERE character_id = ?")?;
+    let class_rows = stmt.query_map([character_id], |row| {
+        Ok(CharacterClass {
+            id: row.get(0)?,
+            character_id: row.get(1)?,
+            class_name: row.get(2)?,
+            class_label: row.get(3)?,
+            level: row.get(4)?,
+        })
+    })?;
+    let mut classes = vec![];
+    for r in class_rows {
+        classes.push(r?);
+    }
 
-        let character = PrintableCharacter {
-            name,
-            character_type: char_type,
-            race,
-            alignment,
-            notes,
-            character_spells: vec![],
-            abilities,
-            classes,
-            include_com: true,
-            include_notes: true,
-        };
+    let character = PrintableCharacter {
+        name,
+        character_type: char_type,
+        race,
+        alignment,
+        notes,
+        character_spells: vec![],
+        abilities,
+        classes,
+        include_com: true,
+        include_notes: true,
+    };
 
-        let mut stmt = conn.prepare(
-            "SELECT s.id, s.name, s.level, s.school, s.class_list, s.range, s.components,
-                    s.duration, s.saving_throw, s.description, sb.prepared, sb.known, sb.notes
-             FROM spellbook sb
-             JOIN spell s ON s.id = sb.spell_id
-             WHERE sb.character_id = ?
-             ORDER BY s.level, s.name",
-        )?;
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.name, s.level, s.school, s.class_list, s.range, s.components,
+                s.duration, s.saving_throw, s.description, sb.prepared, sb.known, sb.notes
+         FROM spellbook sb
+         JOIN spell s ON s.id = sb.spell_id
+         WHERE sb.character_id = ?
+         ORDER BY s.level, s.name",
+    )?;
+
+    let rows = stmt.query_map([character_id], |row| {
+        Ok(PrintableSpellbookEntry {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            level: row.get(2)?,
+            school: row.get(3)?,
+            class_list: row.get(4)?,
+            range: row.get(5)?,
+            components: row.get(6)?,
+            duration: row.get(7)?,
+            saving_throw: row.get(8)?,
+            description: row.get(9)?,
+            prepared: row.get(10)?,
+            known: row.get(11)?,
+            notes: row.get(12)?,
+            class_name: None,
+        })
+    })?;
 
-        let rows = stmt.query_map([character_id], |row| {
-            Ok(PrintableSpellbookEntry {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                level: row.get(2)?,
-                school: row.get(3)?,
-                class_list: row.get(4)?,
-                range: row.get(5)?,
-                components: row.get(6)?,
-                duration: row.get(7)?,
-                saving_throw: row.get(8)?,
-                description: row.get(9)?,
-                prepared: row.get(10)?,
-                known: row.get(11)?,
-                notes: row.get(12)?,
-                class_name: None,
-            })
-        })?;
+    let mut spells = vec![];
+    for row in rows {
+        spells.push(row?);
+    }
+    Ok(PrintableSpellbook { character, spells })
+}
 
-        let mut out = vec![];
-        for row in rows {
-            out.push(row?);
-        }
-        Ok::<_, AppError>((character, out))
+#[tauri::command]
+pub async fn print_spellbook(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    character_id: i64,
+    layout: String,
+    page_size: Option<String>,
+    output_dir: Option<String>,
+) -> Result<PrintResult, AppError> {
+    let pool = state.inner().clone();
+    let PrintableSpellbook { character, spells } = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        load_printable_spellbook(&conn, character_id)
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
 
-    let output_dir = app_data_dir()?.join("prints");
+    let output_dir = resolve_output_dir(&app, output_dir.as_deref(), "prints")?;
     let result = call_sidecar(
         "export",
         json!({
@@ -482,20 +1524,34 @@ pub async fn print_spellbook(
     )
     .await?;
 
-    Ok(result
-        .get("path")
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .to_string())
+    Ok(print_result_from_sidecar(&result))
+}
+
+#[tauri::command]
+pub async fn get_printable_spellbook(
+    state: State<'_, Arc<Pool>>,
+    character_id: i64,
+) -> Result<PrintableSpellbook, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        load_printable_spellbook(&conn, character_id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
 }
 
 #[tauri::command]
 pub async fn export_character_sheet(
+    app: tauri::AppHandle,
     state: State<'_, Arc<Pool>>,
     character_id: i64,
     format: String, // "html" or "md"
     include_com: bool,
     include_notes: bool,
+    output_dir: Option<String>,
 ) -> Result<String, AppError> {
     let pool = state.inner().clone();
     let character = tokio::task::spawn_blocking(move || {
@@ -572,7 +1628,7 @@ pub async fn export_character_sheet(
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
 
-    let output_dir = app_data_dir()?.join("prints");
+    let output_dir = resolve_output_dir(&app, output_dir.as_deref(), "prints")?;
 
     // Map internal "pdf" to "html" if it comes from legacy UI or stays for compat
     let effective_format = if format == "pdf" { "html" } else { &format };
@@ -597,13 +1653,16 @@ pub async fn export_character_sheet(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn export_character_spellbook_pack(
+    app: tauri::AppHandle,
     state: State<'_, Arc<Pool>>,
     character_id: i64,
     class_name: String, // e.g. "Mage"
     layout: String,     // "compact" or "full"
     format: String,     // "html" or "md"
     include_notes: bool,
+    output_dir: Option<String>,
 ) -> Result<String, AppError> {
     let pool = state.inner().clone();
     let class_name_query = class_name.clone();
@@ -637,7 +1696,7 @@ pub async fn export_character_spellbook_pack(
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
 
-    let output_dir = app_data_dir()?.join("prints");
+    let output_dir = resolve_output_dir(&app, output_dir.as_deref(), "prints")?;
     let effective_format = if format == "pdf" { "html" } else { &format };
 
     let result = call_sidecar(
@@ -665,6 +1724,7 @@ pub async fn export_character_spellbook_pack(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::vault::VaultTestEnvGuard;
     use rusqlite::params;
 
     fn setup_test_db() -> rusqlite::Connection {
@@ -698,7 +1758,8 @@ mod tests {
                 updated_at TEXT,
                 canonical_data TEXT,
                 content_hash TEXT,
-                schema_version INTEGER
+                schema_version INTEGER,
+                logical_id TEXT
             )",
             [],
         )
@@ -715,6 +1776,14 @@ mod tests {
             [],
         )
         .unwrap();
+        conn.execute_batch(include_str!(
+            "../../../../../db/migrations/0020_canonical_cache.sql"
+        ))
+        .unwrap();
+        conn.execute_batch(include_str!(
+            "../../../../../db/migrations/0030_canonical_cache_invalidation.sql"
+        ))
+        .unwrap();
         conn
     }
 
@@ -780,7 +1849,7 @@ mod tests {
         )
         .unwrap();
 
-        let json = export_spell_as_json_impl(&conn, 1).unwrap();
+        let json = export_spell_as_json_impl(&conn, 1, true).unwrap();
         let exported: serde_json::Value = serde_json::from_str(&json).unwrap();
 
         assert_eq!(exported["id"], hash);
@@ -826,7 +1895,7 @@ mod tests {
         )
         .unwrap();
 
-        let json = export_spell_bundle_json_impl(&conn, vec![1, 2]).unwrap();
+        let json = export_spell_bundle_json_impl(&conn, vec![1, 2], true).unwrap();
         let exported: serde_json::Value = serde_json::from_str(&json).unwrap();
 
         assert_eq!(exported["bundle_format_version"], BUNDLE_FORMAT_VERSION);
@@ -835,6 +1904,437 @@ mod tests {
         assert_eq!(exported["spells"][1]["id"], hash2);
     }
 
+    #[test]
+    fn test_export_spell_as_json_pretty_and_compact_match() {
+        let conn = setup_test_db();
+        let mut spell = CanonicalSpell::new(
+            "Layout".into(),
+            2,
+            "ARCANE".into(),
+            "Same content either way".into(),
+        );
+        spell.school = Some("Divination".into());
+        let hash = spell.compute_hash().unwrap();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, school, canonical_data, content_hash, schema_version, is_quest_spell, is_cantrip, reversible)
+             VALUES (1, 'Layout', 2, 'Same content either way', 'Divination', ?, ?, 2, 0, 0, 0)",
+            params![serde_json::to_string(&spell).unwrap(), hash],
+        )
+        .unwrap();
+
+        let pretty = export_spell_as_json_impl(&conn, 1, true).unwrap();
+        let compact = export_spell_as_json_impl(&conn, 1, false).unwrap();
+        assert!(pretty.contains('\n'));
+        assert!(!compact.contains('\n'));
+
+        let from_pretty: CanonicalSpell = serde_json::from_str(&pretty).unwrap();
+        let from_compact: CanonicalSpell = serde_json::from_str(&compact).unwrap();
+        assert_eq!(
+            serde_json::to_value(&from_pretty).unwrap(),
+            serde_json::to_value(&from_compact).unwrap()
+        );
+        assert_eq!(from_pretty.id.as_deref(), Some(hash.as_str()));
+        assert_eq!(from_pretty.compute_hash().unwrap(), hash);
+        assert_eq!(from_compact.compute_hash().unwrap(), hash);
+    }
+
+    #[test]
+    fn test_render_spell_comparison_markdown_matches_golden() {
+        let a = SpellDetail {
+            id: Some(1),
+            name: "Fireball".into(),
+            level: 3,
+            school: Some("Evocation".into()),
+            class_list: Some("Wizard".into()),
+            range: Some("10 yards + 10 yards/level".into()),
+            source: Some("PHB".into()),
+            description: "A burst of flame | roaring.".into(),
+            ..Default::default()
+        };
+        let b = SpellDetail {
+            id: Some(2),
+            level: 4,
+            school: Some("Alteration".into()),
+            ..a.clone()
+        };
+
+        let golden = "\
+# Spell comparison: Fireball vs Fireball
+
+2 of 22 fields differ.
+
+| Field | A: Fireball | B: Fireball |
+| --- | --- | --- |
+| Name | Fireball | Fireball |
+| **Level** | **3** | **4** |
+| **School** | **Evocation** | **Alteration** |
+| Sphere |  |  |
+| Class List | Wizard | Wizard |
+| Range | 10 yards + 10 yards/level | 10 yards + 10 yards/level |
+| Components |  |  |
+| Material Components |  |  |
+| Casting Time |  |  |
+| Duration |  |  |
+| Area |  |  |
+| Saving Throw |  |  |
+| Damage |  |  |
+| Magic Resistance |  |  |
+| Reversible | 0 | 0 |
+| Is Cantrip | 0 | 0 |
+| Tags |  |  |
+| Source | PHB | PHB |
+| Edition |  |  |
+| Author |  |  |
+| License |  |  |
+| Description | A burst of flame \\| roaring. | A burst of flame \\| roaring. |
+";
+        assert_eq!(
+            render_spell_comparison(&a, &b, "markdown", &ConversionConfig::default()).unwrap(),
+            golden
+        );
+
+        let html = render_spell_comparison(&a, &b, "html", &ConversionConfig::default()).unwrap();
+        assert_eq!(html.matches("<tr class=\"differs\">").count(), 2);
+        assert!(html.contains("<tr class=\"differs\"><td>Level</td><td>3</td><td>4</td></tr>"));
+
+        assert!(matches!(
+            render_spell_comparison(&a, &b, "pdf", &ConversionConfig::default()),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_render_spell_comparison_converts_with_house_rules() {
+        use crate::models::{
+            AreaKind, AreaSpec, DurationKind, DurationSpec, RangeKind, RangeSpec, SpellScalar,
+            TileUnit,
+        };
+        let a = SpellDetail {
+            id: Some(1),
+            name: "Wall of Fog".into(),
+            level: 1,
+            range: Some("10 yd".into()),
+            range_spec: Some(RangeSpec {
+                kind: RangeKind::Distance,
+                unit: Some(RangeUnit::Yd),
+                distance: Some(SpellScalar::fixed(10.0)),
+                ..Default::default()
+            }),
+            duration: Some("2 turns".into()),
+            duration_spec: Some(DurationSpec {
+                kind: DurationKind::Time,
+                unit: Some(DurationUnit::Turn),
+                duration: Some(SpellScalar::fixed(2.0)),
+                ..Default::default()
+            }),
+            area: Some("4 squares".into()),
+            area_spec: Some(AreaSpec {
+                kind: AreaKind::Tiles,
+                tile_unit: Some(TileUnit::Square),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let b = SpellDetail {
+            id: Some(2),
+            ..a.clone()
+        };
+        let house_rules = ConversionConfig {
+            rounds_per_turn: 6,
+            feet_per_square: 5,
+            ..Default::default()
+        };
+
+        let markdown = render_spell_comparison(&a, &b, "markdown", &house_rules).unwrap();
+        assert!(markdown.contains("| Range | 10 yd (30 ft) | 10 yd (30 ft) |"));
+        assert!(markdown.contains("| Duration | 2 turns (12 rounds) | 2 turns (12 rounds) |"));
+        assert!(markdown.contains("| Area | 4 squares (5-ft squares) | 4 squares (5-ft squares) |"));
+
+        let default_rules =
+            render_spell_comparison(&a, &b, "markdown", &ConversionConfig::default()).unwrap();
+        assert!(default_rules.contains("| Duration | 2 turns (20 rounds) |"));
+        assert!(default_rules.contains("| Area | 4 squares (10-ft squares) |"));
+    }
+
+    #[test]
+    fn test_native_renderers_escape_spell_text() {
+        let a = SpellDetail {
+            id: Some(1),
+            name: "Tasha's <i>Laughter</i>".into(),
+            level: 1,
+            school: Some("Enchantment".into()),
+            description: "Victim <b>&\" laughs_*uncontrollably*".into(),
+            ..Default::default()
+        };
+        let b = SpellDetail {
+            id: Some(2),
+            level: 2,
+            ..a.clone()
+        };
+
+        let html = render_spell_comparison(&a, &b, "html", &ConversionConfig::default()).unwrap();
+        assert!(html.contains("Victim &lt;b&gt;&amp;&quot; laughs_*uncontrollably*"));
+        assert!(html.contains("<title>Spell comparison: Tasha&#39;s &lt;i&gt;Laughter&lt;/i&gt;"));
+        assert!(!html.contains("<b>"));
+        assert!(!html.contains("<i>"));
+
+        let index = render_spell_index(std::slice::from_ref(&a), "name", "html").unwrap();
+        assert!(index.contains("<li>Tasha&#39;s &lt;i&gt;Laughter&lt;/i&gt; — "));
+
+        let markdown =
+            render_spell_comparison(&a, &b, "markdown", &ConversionConfig::default()).unwrap();
+        assert!(markdown.starts_with("# Spell comparison: Tasha's \\<i\\>Laughter\\</i\\> vs "));
+        assert!(markdown.contains("| Victim \\<b\\>&\" laughs\\_\\*uncontrollably\\* |"));
+
+        assert_eq!(
+            render_native_markdown(std::slice::from_ref(&a)),
+            "# Tasha's \\<i\\>Laughter\\</i\\>\n\nVictim \\<b\\>&\" laughs\\_\\*uncontrollably\\*\n"
+        );
+    }
+
+    #[test]
+    fn test_render_spell_index_by_level_groups_levels() {
+        let spell = |name: &str, level: i64, school: Option<&str>| SpellDetail {
+            name: name.into(),
+            level,
+            school: school.map(Into::into),
+            description: "Text.".into(),
+            ..Default::default()
+        };
+        let spells = vec![
+            spell("Web", 2, Some("Evocation")),
+            spell("Magic Missile", 1, Some("Evocation")),
+            spell("Fireball", 3, Some("Evocation")),
+            spell("Armor", 1, None),
+            spell("Blur", 2, Some("Illusion")),
+        ];
+
+        let index = render_spell_index(&spells, "level", "markdown").unwrap();
+
+        let golden = "\
+# Spell index (by level)
+
+## Level 1
+
+- Armor — Level 1 ... p. ___
+- Magic Missile — Level 1, Evocation ... p. ___
+
+## Level 2
+
+- Blur — Level 2, Illusion ... p. ___
+- Web — Level 2, Evocation ... p. ___
+
+## Level 3
+
+- Fireball — Level 3, Evocation ... p. ___
+";
+        assert_eq!(index, golden);
+        assert!(matches!(
+            render_spell_index(&spells, "page", "markdown"),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_native_spell_export_csv_quotes_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let spells = vec![SpellDetail {
+            name: "Bigby's \"Fist\"".into(),
+            level: 5,
+            school: Some("Evocation".into()),
+            components: Some("V, S, M".into()),
+            description: "Punch.".into(),
+            ..Default::default()
+        }];
+
+        let path = write_native_spell_export(&spells, "csv", true, dir.path()).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let mut lines = text.lines();
+
+        assert_eq!(lines.next(), Some(NATIVE_CSV_COLUMNS.join(",").as_str()));
+        assert_eq!(
+            lines.next(),
+            Some("\"Bigby's \"\"Fist\"\"\",5,Evocation,,,,\"V, S, M\",,,,,")
+        );
+        assert!(path.extension().is_some_and(|ext| ext == "csv"));
+
+        let md_path = write_native_spell_export(&spells, "md", true, dir.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(md_path).unwrap(),
+            "# Bigby's \"Fist\"\n\nPunch.\n"
+        );
+    }
+
+    #[test]
+    fn test_write_native_spell_export_json_pretty_and_compact_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let spells = vec![SpellDetail {
+            name: "Shield".into(),
+            level: 1,
+            school: Some("Evocation".into()),
+            description: "A barrier.".into(),
+            ..Default::default()
+        }];
+
+        let pretty = fs::read_to_string(
+            write_native_spell_export(&spells, "json", true, dir.path()).unwrap(),
+        )
+        .unwrap();
+        let compact = fs::read_to_string(
+            write_native_spell_export(&spells, "json", false, dir.path()).unwrap(),
+        )
+        .unwrap();
+
+        assert!(pretty.contains('\n'));
+        assert!(!compact.contains('\n'));
+        let pretty: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        let compact: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        assert_eq!(pretty, compact);
+    }
+
+    #[test]
+    fn test_export_validation_report_lists_invalid_spell() {
+        let dir = tempfile::tempdir().unwrap();
+        let conn = setup_test_db();
+        let canonical = |name: &str, level: i64| {
+            let mut spell = CanonicalSpell::new(
+                name.to_string(),
+                level,
+                "ARCANE".to_string(),
+                "Text.".to_string(),
+            );
+            spell.school = Some("Evocation".to_string());
+            serde_json::to_string(&spell).unwrap()
+        };
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, is_quest_spell, is_cantrip,
+                                canonical_data)
+             VALUES (1, 'Fireball', 3, 'Text.', 0, 0, ?), (2, 'Broken', 42, 'Text.', 0, 0, ?)",
+            params![canonical("Fireball", 3), canonical("Broken", 42)],
+        )
+        .unwrap();
+
+        let rows = validate_all_spells_with_conn(&conn).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].valid && rows[0].error.is_none());
+        assert!(!rows[1].valid);
+
+        let mut rows = rows;
+        rows.push(SpellValidationResult {
+            id: 3,
+            name: "Multi".into(),
+            valid: false,
+            error: Some("first problem\nsecond problem".into()),
+        });
+        let path = write_validation_report(&rows, "csv", dir.path()).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("id,name,valid,error\n1,Fireball,true,\n"));
+        assert!(
+            text.contains("\n2,Broken,false,") && text.contains("Validation error"),
+            "invalid spell row missing: {text}"
+        );
+        assert!(text.ends_with("3,Multi,false,\"first problem\nsecond problem\"\n"));
+
+        let json_path = write_validation_report(&rows, "json", dir.path()).unwrap();
+        let parsed: Vec<SpellValidationResult> =
+            serde_json::from_str(&fs::read_to_string(json_path).unwrap()).unwrap();
+        assert_eq!(parsed, rows);
+    }
+
+    #[test]
+    fn test_resolve_output_dir_exports_to_chosen_directory() {
+        let vault = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let chosen = tempfile::tempdir().unwrap();
+        let spells = vec![SpellDetail {
+            name: "Magic Missile".into(),
+            level: 1,
+            description: "Darts.".into(),
+            ..Default::default()
+        }];
+        let allowed = |path: &Path| path.starts_with(chosen.path());
+
+        let dir = resolve_output_dir_in_scope(chosen.path().to_str(), "exports", allowed).unwrap();
+        let path = write_native_spell_export(&spells, "md", true, &dir).unwrap();
+        assert_eq!(path.parent(), Some(chosen.path()));
+        assert!(path.is_file());
+
+        assert_eq!(
+            resolve_output_dir_in_scope(None, "exports", allowed).unwrap(),
+            vault.path().join("exports")
+        );
+        assert_eq!(
+            resolve_output_dir_in_scope(Some("exports/session-1"), "exports", allowed).unwrap(),
+            vault.path().join("exports").join("session-1")
+        );
+        assert!(matches!(
+            resolve_output_dir_in_scope(Some("../outside"), "exports", allowed),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            resolve_output_dir_in_scope(chosen.path().join("missing").to_str(), "exports", allowed),
+            Err(AppError::Validation(_))
+        ));
+        assert!(
+            matches!(
+                resolve_output_dir_in_scope(vault.path().to_str(), "exports", allowed),
+                Err(AppError::Validation(_))
+            ),
+            "an absolute path outside the picked folder's scope is refused"
+        );
+    }
+
+    #[test]
+    fn test_search_result_ids_feed_bundle_export_with_exactly_matching_spells() {
+        let conn = setup_test_db();
+        let seeds = [
+            (1, "Fireball", "Evocation", 3),
+            (2, "Burning Hands", "Evocation", 1),
+            (3, "Shield", "Abjuration", 1),
+        ];
+        for (id, name, school, level) in seeds {
+            let spell = CanonicalSpell {
+                name: name.into(),
+                tradition: "ARCANE".into(),
+                level,
+                description: format!("{} description", name),
+                school: Some(school.into()),
+                version: "2.0.0".into(),
+                ..Default::default()
+            };
+            conn.execute(
+                "INSERT INTO spell (id, name, level, description, school, canonical_data, content_hash, schema_version, is_quest_spell, is_cantrip, reversible)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, 2, 0, 0, 0)",
+                params![
+                    id,
+                    name,
+                    level,
+                    spell.description,
+                    school,
+                    serde_json::to_string(&spell).unwrap(),
+                    format!("{:064}", id)
+                ],
+            )
+            .unwrap();
+        }
+
+        let filters = SearchFilters {
+            schools: Some(vec!["Evocation".into()]),
+            ..Default::default()
+        };
+        let ids = search_result_ids_capped(&conn, "", Some(filters), "exporting").unwrap();
+        assert_eq!(ids, vec![2, 1]);
+
+        let json = export_spell_bundle_json_impl(&conn, ids, false).unwrap();
+        let exported: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let names: Vec<&str> = exported["spells"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|spell| spell["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["Burning Hands", "Fireball"]);
+    }
+
     #[test]
     fn test_export_spell_as_json_rejects_null_content_hash() {
         let conn = setup_test_db();
@@ -854,7 +2354,7 @@ mod tests {
         )
         .unwrap();
 
-        let err = export_spell_as_json_impl(&conn, 10)
+        let err = export_spell_as_json_impl(&conn, 10, true)
             .expect_err("single export should fail when content_hash is NULL");
         assert!(err.to_string().contains("no content hash"));
     }
@@ -894,7 +2394,7 @@ mod tests {
         )
         .unwrap();
 
-        let err = export_spell_bundle_json_impl(&conn, vec![11, 12])
+        let err = export_spell_bundle_json_impl(&conn, vec![11, 12], true)
             .expect_err("bundle export should fail when any spell hash is NULL");
         let msg = err.to_string();
         assert!(msg.contains("no content hash"));
@@ -912,7 +2412,7 @@ mod tests {
         )
         .unwrap();
 
-        let err = export_spell_as_json_impl(&conn, 13)
+        let err = export_spell_as_json_impl(&conn, 13, true)
             .expect_err("single export should fail on invalid canonical_data");
         assert!(err.to_string().contains("Invalid canonical_data"));
     }
@@ -928,7 +2428,7 @@ mod tests {
         )
         .unwrap();
 
-        let err = export_spell_bundle_json_impl(&conn, vec![14])
+        let err = export_spell_bundle_json_impl(&conn, vec![14], true)
             .expect_err("bundle export should fail on invalid canonical_data");
         assert!(err.to_string().contains("Invalid canonical_data"));
         assert!(err.to_string().contains("BadCanonicalBundle"));
@@ -967,6 +2467,45 @@ mod tests {
         assert_eq!(spells[0].notes.as_deref(), Some("restored row"));
     }
 
+    #[test]
+    fn test_load_printable_spellbook_orders_by_level_then_name_with_flags() {
+        let conn = setup_character_export_db();
+        conn.execute_batch(
+            "CREATE TABLE character_ability (
+                id INTEGER PRIMARY KEY,
+                character_id INTEGER NOT NULL,
+                str INTEGER, dex INTEGER, con INTEGER, int INTEGER,
+                wis INTEGER, cha INTEGER, com INTEGER
+            );
+            CREATE TABLE spellbook (
+                character_id INTEGER,
+                spell_id INTEGER,
+                prepared INTEGER DEFAULT 0,
+                known INTEGER DEFAULT 1,
+                notes TEXT,
+                PRIMARY KEY(character_id, spell_id)
+            );
+            INSERT INTO \"character\" (id, name, type) VALUES (1, 'Mordenkainen', 'PC');
+            INSERT INTO spell (id, name, level, description, school) VALUES
+                (1, 'Lightning Bolt', 3, 'Zap', 'Evocation'),
+                (2, 'Fireball', 3, 'Boom', 'Evocation'),
+                (3, 'Magic Missile', 1, 'Pew', 'Evocation');
+            INSERT INTO spellbook (character_id, spell_id, prepared, known, notes) VALUES
+                (1, 1, 0, 1, NULL),
+                (1, 2, 1, 1, 'memorized twice'),
+                (1, 3, 1, 1, NULL);",
+        )
+        .unwrap();
+
+        let book = load_printable_spellbook(&conn, 1).expect("load printable spellbook");
+        assert_eq!(book.character.name, "Mordenkainen");
+        let names: Vec<&str> = book.spells.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Magic Missile", "Fireball", "Lightning Bolt"]);
+        let flags: Vec<(i64, i64)> = book.spells.iter().map(|s| (s.prepared, s.known)).collect();
+        assert_eq!(flags, vec![(1, 1), (1, 1), (0, 1)]);
+        assert_eq!(book.spells[1].notes.as_deref(), Some("memorized twice"));
+    }
+
     #[test]
     fn test_load_character_printable_spells_rejects_orphaned_hash_rows() {
         let conn = setup_character_export_db();
@@ -995,4 +2534,209 @@ mod tests {
             .to_string()
             .contains("reference spells that are no longer in the library"));
     }
+
+    #[test]
+    fn test_cached_canonical_json_fills_on_miss_and_recomputes_stale_entries() {
+        let conn = setup_test_db();
+        let cache_entry = |conn: &rusqlite::Connection| -> Option<(String, String)> {
+            conn.query_row(
+                "SELECT content_hash, canonical_json FROM canonical_cache WHERE spell_id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .unwrap()
+        };
+
+        // A spell as every write path stores it: canonical_data and content_hash together.
+        let detail = SpellDetail {
+            id: Some(1),
+            name: "Cached Spell".into(),
+            level: 1,
+            description: "Original".into(),
+            school: Some("Evocation".into()),
+            ..Default::default()
+        };
+        let (canonical, hash, json) = canonicalize_spell_detail(detail.clone()).unwrap();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, school, canonical_data, content_hash, is_quest_spell, is_cantrip)
+             VALUES (1, 'Cached Spell', 1, 'Original', 'Evocation', ?, ?, 0, 0)",
+            params![json, hash],
+        )
+        .unwrap();
+
+        // A miss recomputes and fills the cache under the current hash.
+        let spell = get_spell_from_conn(&conn, 1).unwrap().unwrap();
+        assert_eq!(cache_entry(&conn), None);
+        let (canonical_json, hit) = cached_canonical_json(&conn, &spell).unwrap();
+        assert!(!hit);
+        assert_eq!(canonical_json, json);
+        assert_eq!(cache_entry(&conn), Some((hash.clone(), json.clone())));
+
+        // Mark the cached JSON so a hit is distinguishable from recomputation.
+        let marked = serde_json::to_string(&CanonicalSpell {
+            description: "Served from cache".into(),
+            ..canonical
+        })
+        .unwrap();
+        conn.execute(
+            "UPDATE canonical_cache SET canonical_json = ? WHERE spell_id = 1",
+            params![marked],
+        )
+        .unwrap();
+        let (canonical_json, hit) = cached_canonical_json(&conn, &spell).unwrap();
+        assert!(hit);
+        assert_eq!(canonical_json, marked);
+        for exported in [
+            export_spell_as_json_impl(&conn, 1, false).unwrap(),
+            export_spell_bundle_json_impl(&conn, vec![1], false).unwrap(),
+        ] {
+            assert!(exported.contains("Served from cache"), "{exported}");
+        }
+        let mut ndjson = Vec::new();
+        write_spells_ndjson(&conn, Some(&[1]), &mut ndjson).unwrap();
+        assert!(String::from_utf8(ndjson)
+            .unwrap()
+            .contains("Served from cache"));
+
+        // An entry computed for another hash is stale: recomputed and overwritten.
+        conn.execute(
+            "UPDATE canonical_cache SET content_hash = 'stale-hash' WHERE spell_id = 1",
+            [],
+        )
+        .unwrap();
+        let exported = export_spell_as_json_impl(&conn, 1, false).unwrap();
+        assert!(!exported.contains("Served from cache"), "{exported}");
+        assert_eq!(cache_entry(&conn), Some((hash.clone(), json.clone())));
+
+        // A content write drops the entry; the next export fills it with the edited JSON.
+        let edited = SpellDetail {
+            description: "Edited".into(),
+            ..detail
+        };
+        let (_, edited_hash, edited_json) = canonicalize_spell_detail(edited).unwrap();
+        conn.execute(
+            "UPDATE spell SET description = 'Edited', canonical_data = ?, content_hash = ?
+             WHERE id = 1",
+            params![edited_json, edited_hash],
+        )
+        .unwrap();
+        assert_eq!(cache_entry(&conn), None, "a content write drops the entry");
+        export_spell_bundle_json_impl(&conn, vec![1], false).unwrap();
+        assert_eq!(
+            cache_entry(&conn),
+            Some((edited_hash.clone(), edited_json.clone()))
+        );
+        assert_eq!(
+            refresh_canonical_cache_with_conn(&conn, 1).unwrap(),
+            edited_hash
+        );
+
+        // A drifted stored hash is refused rather than cached under the wrong key; exports
+        // serve the stored canonical_data.
+        conn.execute("UPDATE spell SET description = 'Drifted' WHERE id = 1", [])
+            .unwrap();
+        conn.execute("DELETE FROM canonical_cache", []).unwrap();
+        assert!(matches!(
+            refresh_canonical_cache_with_conn(&conn, 1),
+            Err(AppError::Validation(_))
+        ));
+        let spell = get_spell_from_conn(&conn, 1).unwrap().unwrap();
+        let (canonical_json, hit) = cached_canonical_json(&conn, &spell).unwrap();
+        assert!(!hit);
+        assert_eq!(canonical_json, edited_json);
+        assert_eq!(cache_entry(&conn), None);
+    }
+
+    #[test]
+    fn test_get_canonical_json_hashes_to_compute_hash() {
+        use sha2::{Digest, Sha256};
+
+        let conn = setup_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, school, is_quest_spell, is_cantrip)
+             VALUES (1, 'Signed Spell', 2, 'For external signing', 'Abjuration', 0, 0)",
+            [],
+        )
+        .unwrap();
+
+        let canonical_json = get_canonical_json_with_conn(&conn, 1).unwrap();
+        let spell = get_spell_from_conn(&conn, 1).unwrap().unwrap();
+        let expected_hash = CanonicalSpell::try_from(spell)
+            .unwrap()
+            .compute_hash()
+            .unwrap();
+
+        assert_eq!(
+            hex::encode(Sha256::digest(canonical_json.as_bytes())),
+            expected_hash
+        );
+        assert!(matches!(
+            get_canonical_json_with_conn(&conn, 99),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_compressed_bundle_export_round_trips_and_is_smaller() {
+        use crate::utils::compression::read_export_json;
+
+        let conn = setup_test_db();
+        for (id, name) in [(1, "Gzip Spell One"), (2, "Gzip Spell Two")] {
+            let detail = SpellDetail {
+                id: Some(id),
+                name: name.into(),
+                level: 2,
+                description: "A description long enough to be worth compressing.".into(),
+                school: Some("Evocation".into()),
+                ..Default::default()
+            };
+            let (_, hash, json) = canonicalize_spell_detail(detail).unwrap();
+            conn.execute(
+                "INSERT INTO spell (id, name, level, description, school, canonical_data, content_hash, is_quest_spell, is_cantrip)
+                 VALUES (?, ?, 2, 'A description long enough to be worth compressing.', 'Evocation', ?, ?, 0, 0)",
+                params![id, name, json, hash],
+            )
+            .unwrap();
+        }
+        let json = export_spell_bundle_json_impl(&conn, vec![1, 2], true).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let plain = write_export_json(dir.path(), "bundle", &json, false).unwrap();
+        let gz = write_export_json(dir.path(), "bundle", &json, true).unwrap();
+        assert!(gz.to_string_lossy().ends_with(".json.gz"));
+        assert!(fs::metadata(&gz).unwrap().len() < fs::metadata(&plain).unwrap().len());
+
+        assert_eq!(read_export_json(&gz).unwrap(), json);
+        assert_eq!(read_export_json(&plain).unwrap(), json);
+        let spells = serde_json::from_str::<serde_json::Value>(&read_export_json(&gz).unwrap())
+            .unwrap()["spells"]
+            .as_array()
+            .map(Vec::len);
+        assert_eq!(spells, Some(2));
+    }
+
+    #[test]
+    fn test_print_result_surfaces_sidecar_page_map() {
+        let sidecar = json!({
+            "path": "/tmp/prints/spellbook.pdf",
+            "pages": 12,
+            "spell_page_map": { "3": 1, "7": 4, "bogus": 9 }
+        });
+        let result = print_result_from_sidecar(&sidecar);
+        assert_eq!(result.path, "/tmp/prints/spellbook.pdf");
+        assert_eq!(result.pages, Some(12));
+        let spell_pages = result.spell_pages.expect("page map");
+        assert_eq!(spell_pages.len(), 2);
+        assert_eq!(spell_pages.get(&3), Some(&1));
+        assert_eq!(spell_pages.get(&7), Some(&4));
+
+        let serialized = serde_json::to_value(print_result_from_sidecar(&sidecar)).unwrap();
+        assert_eq!(serialized["spellPages"]["7"], json!(4));
+
+        let bare = print_result_from_sidecar(&json!({ "path": "/tmp/prints/spell.pdf" }));
+        assert_eq!(bare.path, "/tmp/prints/spell.pdf");
+        assert_eq!(bare.pages, None);
+        assert_eq!(bare.spell_pages, None);
+    }
 }
@@ -1,6 +1,10 @@
 use crate::commands::spells::{
-    apply_spell_update_with_conn, canonicalize_spell_detail, diff_spells, get_spell_from_conn,
-    log_changes, validate_epic_and_quest_spells,
+    apply_spell_update_with_actor, canonicalize_spell_detail, diff_spells,
+    finalize_canonical_spell, flag_needs_review_with_conn, get_spell_from_conn,
+    is_spell_locked_with_conn, log_changes, normalize_list_column, replace_class_spell_levels,
+    set_spell_reverse_name_with_conn, skip_locked_overwrite, spell_detail_to_update,
+    upsert_by_logical_id_with_conn, validate_epic_and_quest_spells, SpellCache,
+    IMPORT_CHANGE_LOG_ACTOR, USER_CHANGE_LOG_ACTOR,
 };
 use crate::commands::vault::{
     run_vault_gc_with_root, write_spell_json_atomically, VaultMaintenanceState,
@@ -8,18 +12,23 @@ use crate::commands::vault::{
 use crate::db::Pool;
 use crate::error::AppError;
 use crate::models::canonical_spell::{
-    validate_tradition_school_sphere_consistency, CanonicalSpell, SourceRef, BUNDLE_FORMAT_VERSION,
-    CURRENT_SCHEMA_VERSION,
+    parse_list_column, validate_tradition_school_sphere_consistency, CanonicalFieldDiff,
+    CanonicalSpell, SourceRef, BUNDLE_FORMAT_VERSION, CURRENT_SCHEMA_VERSION,
 };
 use crate::models::{
-    ConflictsResolved, DuplicatesSkipped, ImportArtifact, ImportConflict, ImportConflictField,
-    ImportConflictResolution, ImportFile, ImportResult, ImportSpell, ImportSpellJsonConflict,
+    BatchResult, ConflictsResolved, DuplicatesSkipped, ImportArtifact, ImportConflict,
+    ImportConflictField, ImportConflictResolution, ImportFile, ImportNdjsonResult,
+    ImportPlanAction, ImportPlanItem, ImportResult, ImportSpell, ImportSpellJsonConflict,
     ImportSpellJsonConflictResolution, ImportSpellJsonFailure, ImportSpellJsonResolveOptions,
-    ImportSpellJsonResult, ParseConflict, PreviewImportSpellJsonResult, PreviewResult,
-    PreviewSpell, PreviewSpellJsonItem, ResolveImportResult, SpellDetail, SpellUpdate,
+    ImportSpellJsonResult, ParseConflict, PreviewConfidenceStats, PreviewImportSpellJsonResult,
+    PreviewResult, PreviewSpell, PreviewSpellJsonItem, PreviewValidation, QueuedImportConflict,
+    ReparseArtifactResult, ReparseFieldChange, ReparseResult, ResolveImportResult, SpellDetail,
+    SpellUpdate,
 };
 use crate::sidecar::call_sidecar;
+use crate::utils::compression::{read_export_json, MAX_IMPORT_PAYLOAD_BYTES};
 use crate::utils::migration_manager;
+use crate::utils::parsers::components::ComponentsParser;
 use chrono::Utc;
 use dirs::data_dir as system_data_dir;
 use regex::Regex;
@@ -27,22 +36,28 @@ use rusqlite::params;
 use rusqlite::OptionalExtension;
 use serde::de::{self, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
 use serde_json::json;
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fs;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 use tracing::warn;
+use unicode_normalization::UnicodeNormalization;
 
-use tauri::State;
+use tauri::{Emitter, State, Window};
 
 // --- JSON spell import (Task 2.1: parse, classify, normalize, hash) ---
 
 const MAX_TAGS: usize = 100;
 const MAX_SOURCE_REFS: usize = 50;
-const MAX_IMPORT_PAYLOAD_BYTES: usize = 100 * 1024 * 1024;
 const MAX_IMPORT_BUNDLE_SPELLS: usize = 10_000;
 const MAX_IMPORT_JSON_DEPTH: usize = 50;
+/// Files (or override spells) sent to the sidecar / DB per chunk in legacy imports.
+const IMPORT_BATCH_SIZE: usize = 10;
+/// Extensions the sidecar can parse; anything else is skipped by `import_directory`.
+const LEGACY_IMPORT_EXTENSIONS: &[&str] = &["md", "pdf", "docx"];
 
 /// Allowed URL schemes for SourceRef (import allowlist). Rejects javascript:, data:, ipfs:, etc.
 const ALLOWED_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
@@ -248,6 +263,11 @@ pub fn is_duplicate_source_ref(a: &SourceRef, b: &SourceRef) -> bool {
     system_a == system_b && a.book.trim() == b.book.trim() && a.page == b.page && note_a == note_b
 }
 
+/// `items` in the `class_list`/`tags` storage form of [`normalize_list_column`].
+fn list_column(items: &[String]) -> Option<String> {
+    normalize_list_column(&serde_json::to_string(items).ok())
+}
+
 /// Merge tags and source_refs into existing canonical_data JSON.
 /// Tags: union, sorted, deduped, cap 100.
 /// SourceRefs: existing first, then append new, dedup by Spec #1 policy, cap 50.
@@ -270,11 +290,7 @@ fn merge_canonical_metadata(
     canon.tags.sort();
     canon.tags.dedup();
     canon.tags.truncate(MAX_TAGS);
-    let tags_col = if canon.tags.is_empty() {
-        None
-    } else {
-        Some(canon.tags.join(", "))
-    };
+    let tags_col = list_column(&canon.tags);
 
     // 2. Merge Source Refs (re-dedup existing just in case, then append new)
     let existing_refs = std::mem::take(&mut canon.source_refs);
@@ -481,11 +497,7 @@ fn process_spell(spell: &mut CanonicalSpell) -> Result<(String, Vec<String>), Ap
             "Saving throw notes truncated during migration (exceeded limit)".into(),
         ));
     }
-    let class_list = if spell.class_list.is_empty() {
-        None
-    } else {
-        Some(spell.class_list.join(", "))
-    };
+    let class_list = list_column(&spell.class_list);
     validate_epic_and_quest_spells(
         spell.level,
         &class_list,
@@ -507,6 +519,45 @@ fn process_spell(spell: &mut CanonicalSpell) -> Result<(String, Vec<String>), Ap
     Ok((hash, warnings))
 }
 
+/// Preview pipeline for one incoming spell: source_ref URL policy, metadata truncation,
+/// then `process_spell`. Spell-level warnings and failure reasons are also pushed to
+/// `global_warnings`.
+fn preview_spell_json_item(
+    mut spell: CanonicalSpell,
+    policy: SourceRefUrlPolicy,
+    global_warnings: &mut Vec<String>,
+) -> Result<PreviewSpellJsonItem, ImportSpellJsonFailure> {
+    let mut url_warnings = Vec::new();
+    if process_source_ref_urls(&mut spell, policy, &mut url_warnings).is_err() {
+        let reason = url_warnings
+            .last()
+            .cloned()
+            .unwrap_or_else(|| "Invalid SourceRef URL(s); policy reject-spell".to_string());
+        global_warnings.push(reason.clone());
+        return Err(ImportSpellJsonFailure {
+            spell_name: spell.name,
+            reason,
+        });
+    }
+    global_warnings.extend(url_warnings);
+    normalize_truncate_metadata(&mut spell);
+    match process_spell(&mut spell) {
+        Ok((content_hash, warnings)) => Ok(PreviewSpellJsonItem {
+            spell,
+            content_hash,
+            warnings,
+        }),
+        Err(e) => {
+            let reason = e.to_string();
+            global_warnings.push(format!("Spell '{}': {}", spell.name, reason));
+            Err(ImportSpellJsonFailure {
+                spell_name: spell.name,
+                reason,
+            })
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn preview_import_spell_json(
     payload: String,
@@ -514,42 +565,14 @@ pub async fn preview_import_spell_json(
 ) -> Result<PreviewImportSpellJsonResult, AppError> {
     validate_import_payload_guardrails(&payload)?;
     let policy = parse_source_ref_url_policy(source_ref_url_policy.as_deref());
-    let mut spells = parse_and_classify_payload(&payload)?;
+    let spells = parse_and_classify_payload(&payload)?;
     let mut global_warnings = Vec::new();
     let mut items = Vec::with_capacity(spells.len());
     let mut failures = Vec::new();
-    for spell in &mut spells {
-        let mut url_warnings = Vec::new();
-        if process_source_ref_urls(spell, policy, &mut url_warnings).is_err() {
-            let reason = url_warnings
-                .last()
-                .cloned()
-                .unwrap_or_else(|| "Invalid SourceRef URL(s); policy reject-spell".to_string());
-            global_warnings.push(reason.clone());
-            failures.push(ImportSpellJsonFailure {
-                spell_name: spell.name.clone(),
-                reason,
-            });
-            continue;
-        }
-        global_warnings.extend(url_warnings);
-        normalize_truncate_metadata(spell);
-        match process_spell(spell) {
-            Ok((content_hash, warnings)) => {
-                items.push(PreviewSpellJsonItem {
-                    spell: spell.clone(),
-                    content_hash,
-                    warnings,
-                });
-            }
-            Err(e) => {
-                let reason = e.to_string();
-                global_warnings.push(format!("Spell '{}': {}", spell.name, reason));
-                failures.push(ImportSpellJsonFailure {
-                    spell_name: spell.name.clone(),
-                    reason,
-                });
-            }
+    for spell in spells {
+        match preview_spell_json_item(spell, policy, &mut global_warnings) {
+            Ok(item) => items.push(item),
+            Err(failure) => failures.push(failure),
         }
     }
     Ok(PreviewImportSpellJsonResult {
@@ -589,16 +612,8 @@ fn canonical_spell_to_flat_row(
     i64,
     i64,
 ) {
-    let class_list = if spell.class_list.is_empty() {
-        None
-    } else {
-        Some(spell.class_list.join(", "))
-    };
-    let tags_str = if spell.tags.is_empty() {
-        None
-    } else {
-        Some(spell.tags.join(", "))
-    };
+    let class_list = list_column(&spell.class_list);
+    let tags_str = list_column(&spell.tags);
     let range = spell
         .range
         .as_ref()
@@ -658,16 +673,8 @@ fn diff_canonical_vs_detail(
     old: &SpellDetail,
     incoming: &CanonicalSpell,
 ) -> Vec<(String, String, String)> {
-    let class_list = if incoming.class_list.is_empty() {
-        None
-    } else {
-        Some(incoming.class_list.join(", "))
-    };
-    let tags_str = if incoming.tags.is_empty() {
-        None
-    } else {
-        Some(incoming.tags.join(", "))
-    };
+    let class_list = list_column(&incoming.class_list);
+    let tags_str = list_column(&incoming.tags);
     let range = incoming
         .range
         .as_ref()
@@ -718,7 +725,12 @@ fn diff_canonical_vs_detail(
     }
     push_opt(&mut changes, "school", &old.school, &incoming.school);
     push_opt(&mut changes, "sphere", &old.sphere, &incoming.sphere);
-    push_opt(&mut changes, "class_list", &old.class_list, &class_list);
+    push_opt(
+        &mut changes,
+        "class_list",
+        &normalize_list_column(&old.class_list),
+        &class_list,
+    );
     if old.level != incoming.level {
         changes.push((
             "level".into(),
@@ -762,7 +774,12 @@ fn diff_canonical_vs_detail(
             incoming.description.clone(),
         ));
     }
-    push_opt(&mut changes, "tags", &old.tags, &tags_str);
+    push_opt(
+        &mut changes,
+        "tags",
+        &normalize_list_column(&old.tags),
+        &tags_str,
+    );
     push_opt(&mut changes, "source", &old.source, &source);
     push_opt(&mut changes, "edition", &old.edition, &incoming.edition);
     push_opt(&mut changes, "author", &old.author, &incoming.author);
@@ -961,7 +978,7 @@ fn replace_with_new_impl(
             )?;
         }
     }
-    log_changes(tx, existing_id, changes)?;
+    log_changes(tx, existing_id, changes, IMPORT_CHANGE_LOG_ACTOR)?;
     Ok(stored_hash)
 }
 
@@ -987,12 +1004,25 @@ fn resolve_action_for_conflict(
     }
 }
 
+/// [`apply_import_spell_json_with_ids`] without `hash_ids`.
+#[cfg(test)]
+fn apply_import_spell_json_impl(
+    conn: &rusqlite::Connection,
+    items: Vec<PreviewSpellJsonItem>,
+    resolve_options: Option<ImportSpellJsonResolveOptions>,
+) -> Result<ImportSpellJsonResult, AppError> {
+    apply_import_spell_json_with_ids(conn, items, resolve_options, false)
+}
+
 /// Apply phase: process preview items in document order; dedup by hash, conflict by name, insert or merge.
 /// When resolve_options is Some, conflicts are resolved using per-conflict resolutions or default_action (skip_all/replace_all/keep_all).
-fn apply_import_spell_json_impl(
+/// When `hash_ids` is set (the `import_with_hash_ids` mode), every row the import touched is
+/// given a `logical_id` in the same transaction.
+fn apply_import_spell_json_with_ids(
     conn: &rusqlite::Connection,
     items: Vec<PreviewSpellJsonItem>,
     resolve_options: Option<ImportSpellJsonResolveOptions>,
+    hash_ids: bool,
 ) -> Result<ImportSpellJsonResult, AppError> {
     let mut imported_count = 0usize;
     let mut merged_count = 0usize;
@@ -1001,7 +1031,7 @@ fn apply_import_spell_json_impl(
     let mut conflicts_resolved = ConflictsResolved::default();
     let mut failures = Vec::<ImportSpellJsonFailure>::new();
     let mut imported_spells = Vec::<SpellDetail>::new();
-    let warnings = Vec::<String>::new();
+    let mut warnings = Vec::<String>::new();
 
     let resolutions = resolve_options
         .as_ref()
@@ -1016,6 +1046,8 @@ fn apply_import_spell_json_impl(
     // For Keep Both: track names we insert in this batch (base name or "Name (N)") so we pick unique N.
     let mut keep_both_names: HashSet<String> = HashSet::new();
     let mut vault_spell_json_to_refresh: HashMap<String, String> = HashMap::new();
+    // Bundle-supplied logical ids by content hash, for the hash_ids stamp after the loop.
+    let mut incoming_logical_ids: HashMap<String, String> = HashMap::new();
 
     let mut tx = conn.unchecked_transaction().map_err(AppError::Database)?;
 
@@ -1023,6 +1055,9 @@ fn apply_import_spell_json_impl(
         let res = (|| -> Result<(), AppError> {
             let (content_hash, _warnings) = process_spell(&mut item.spell)?;
             item.content_hash = content_hash.clone();
+            if let Some(logical_id) = &item.spell.logical_id {
+                incoming_logical_ids.insert(content_hash.clone(), logical_id.clone());
+            }
             let name = item.spell.name.clone();
             let sp = tx.savepoint().map_err(AppError::Database)?;
 
@@ -1058,6 +1093,65 @@ fn apply_import_spell_json_impl(
                 return Ok(());
             }
 
+            // 2a) import_with_hash_ids: a row already holding this spell's logical id is the
+            // same spell from an earlier sync; update it in place rather than adding a copy.
+            if hash_ids {
+                let logical_id = item
+                    .spell
+                    .logical_id
+                    .clone()
+                    .unwrap_or_else(|| content_hash.clone());
+                let existing_by_logical_id: Option<(i64, Option<String>)> = sp
+                    .query_row(
+                        "SELECT id, content_hash FROM spell WHERE logical_id = ?",
+                        params![logical_id],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+                if let Some((spell_id, existing_hash)) = existing_by_logical_id {
+                    if existing_hash.as_deref() != Some(content_hash.as_str()) {
+                        let collision: Option<(i64, String)> = sp
+                            .query_row(
+                                "SELECT id, name FROM spell WHERE content_hash = ? AND id != ?",
+                                params![content_hash, spell_id],
+                                |row| Ok((row.get(0)?, row.get(1)?)),
+                            )
+                            .optional()?;
+                        if let Some((other_id, other_name)) = collision {
+                            warnings.push(format!(
+                                "Skipped '{}': logical id '{}' belongs to spell id {}, but its content already exists on '{}' (id {}).",
+                                name, logical_id, spell_id, other_name, other_id
+                            ));
+                            sp.commit().map_err(AppError::Database)?;
+                            return Ok(());
+                        }
+                        let mut skipped = Vec::new();
+                        if let Some(updated_id) = upsert_by_logical_id_with_conn(
+                            &sp,
+                            canonical_spell_to_detail(&item.spell),
+                            &logical_id,
+                            false,
+                            &mut skipped,
+                            &mut warnings,
+                        )? {
+                            let stored_hash: String = sp.query_row(
+                                "SELECT content_hash FROM spell WHERE id = ?",
+                                params![updated_id],
+                                |row| row.get(0),
+                            )?;
+                            seen_hash_in_batch.insert(stored_hash, updated_id);
+                            conflicts_resolved.replace_count += 1;
+                            migration_manager::sync_check_spell(&sp, updated_id);
+                            if let Some(detail) = get_spell_from_conn(&sp, updated_id)? {
+                                imported_spells.push(detail);
+                            }
+                        }
+                        sp.commit().map_err(AppError::Database)?;
+                        return Ok(());
+                    }
+                }
+            }
+
             // 2) Lookup by content_hash (parameterized).
             let existing_by_hash: Option<(i64, Option<String>, String)> = sp
                 .query_row(
@@ -1258,6 +1352,15 @@ fn apply_import_spell_json_impl(
         }
     }
 
+    if hash_ids {
+        stamp_hash_logical_ids(
+            &tx,
+            &seen_hash_in_batch,
+            &incoming_logical_ids,
+            &mut warnings,
+        )?;
+    }
+
     // Commit the database transaction first so the DB is consistent before touching the filesystem.
     tx.commit().map_err(AppError::Database)?;
 
@@ -1294,15 +1397,56 @@ fn apply_import_spell_json_impl(
     })
 }
 
+/// `import_with_hash_ids` stamp: gives each row in `row_ids` (content hash -> spell id)
+/// without a `logical_id` the bundle's logical id for that hash, or else the hash itself,
+/// so importing the same bundle into two vaults yields the same identities whatever
+/// autoincrement ids the rows get. An id already held by another row is left alone and
+/// reported in `warnings`.
+fn stamp_hash_logical_ids(
+    conn: &rusqlite::Connection,
+    row_ids: &HashMap<String, i64>,
+    incoming_logical_ids: &HashMap<String, String>,
+    warnings: &mut Vec<String>,
+) -> Result<usize, AppError> {
+    let mut stamped = 0;
+    for (content_hash, spell_id) in row_ids {
+        let logical_id = incoming_logical_ids
+            .get(content_hash)
+            .unwrap_or(content_hash);
+        let holder: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM spell WHERE logical_id = ?",
+                params![logical_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match holder {
+            Some(holder_id) if holder_id != *spell_id => warnings.push(format!(
+                "Logical id '{}' is already used by spell id {}; spell id {} was left without it.",
+                logical_id, holder_id, spell_id
+            )),
+            Some(_) => {}
+            None => {
+                stamped += conn.execute(
+                    "UPDATE spell SET logical_id = ? WHERE id = ? AND logical_id IS NULL",
+                    params![logical_id, spell_id],
+                )?;
+            }
+        }
+    }
+    Ok(stamped)
+}
+
 fn apply_import_spell_json_with_maintenance(
     conn: &rusqlite::Connection,
     root: &std::path::Path,
     maintenance_state: &VaultMaintenanceState,
     items: Vec<PreviewSpellJsonItem>,
     resolve_options: Option<ImportSpellJsonResolveOptions>,
+    hash_ids: bool,
 ) -> Result<ImportSpellJsonResult, AppError> {
     let import_guard = maintenance_state.start_import()?;
-    let result = apply_import_spell_json_impl(conn, items, resolve_options)?;
+    let result = apply_import_spell_json_with_ids(conn, items, resolve_options, hash_ids)?;
     let changed_count = result.imported_count
         + result.duplicates_skipped.merged_count
         + result
@@ -1444,6 +1588,7 @@ fn spell_update_from_import_spell(id: i64, spell: &ImportSpell) -> SpellUpdate {
         damage: spell.damage.clone(),
         magic_resistance: spell.magic_resistance.clone(),
         reversible: spell.reversible,
+        reverse_name: spell.reverse_name.clone(),
         description: spell.description.clone(),
         tags: spell.tags.clone(),
         source: spell.source.clone(),
@@ -1456,6 +1601,39 @@ fn spell_update_from_import_spell(id: i64, spell: &ImportSpell) -> SpellUpdate {
     }
 }
 
+/// When an overwrite import carries batch labels, keeps the tags already on the row and adds
+/// `apply_tags` to the incoming tags, so the labels add to the user's tags instead of
+/// replacing them. Without batch labels the incoming tags replace the row's as usual.
+fn union_batch_tags(
+    conn: &rusqlite::Connection,
+    update: &mut SpellUpdate,
+    apply_tags: &[String],
+) -> Result<(), AppError> {
+    if apply_tags.iter().all(|tag| tag.trim().is_empty()) {
+        return Ok(());
+    }
+    let existing: Option<String> = conn
+        .query_row("SELECT tags FROM spell WHERE id = ?", [update.id], |row| {
+            row.get(0)
+        })
+        .optional()?
+        .flatten();
+    let Some(existing) = existing else {
+        return Ok(());
+    };
+    let mut tags = parse_list_column(&existing);
+    tags.extend(
+        update
+            .tags
+            .as_deref()
+            .map(parse_list_column)
+            .unwrap_or_default(),
+    );
+    tags.extend(apply_tags.iter().map(|tag| tag.trim().to_string()));
+    update.tags = normalize_list_column(&serde_json::to_string(&tags).ok());
+    Ok(())
+}
+
 fn upsert_import_artifact(
     conn: &rusqlite::Connection,
     spell_id: i64,
@@ -1546,6 +1724,7 @@ fn apply_legacy_conflict_resolution_update(
         damage: spell.damage.clone(),
         magic_resistance: spell.magic_resistance.clone(),
         reversible: spell.reversible,
+        reverse_name: spell.reverse_name.clone(),
         description: spell.description.clone(),
         tags: spell.tags.clone(),
         source: spell.source.clone(),
@@ -1558,6 +1737,9 @@ fn apply_legacy_conflict_resolution_update(
         artifacts: None,
         canonical_data: None,
         content_hash: None,
+        created_at: None,
+        updated_at: None,
+        material_has_cost: false,
         range_spec: spell.range_spec.clone(),
         components_spec: spell.components_spec.clone(),
         material_components_spec: spell.material_components_spec.clone(),
@@ -1581,7 +1763,7 @@ fn apply_legacy_conflict_resolution_update(
             spell.name,
             spell.school,
             spell.sphere,
-            spell.class_list,
+            normalize_list_column(&spell.class_list),
             spell.level,
             spell.range,
             spell.components,
@@ -1594,7 +1776,7 @@ fn apply_legacy_conflict_resolution_update(
             spell.magic_resistance,
             spell.reversible.unwrap_or(0),
             spell.description,
-            spell.tags,
+            normalize_list_column(&spell.tags),
             spell.source,
             spell.edition,
             spell.author,
@@ -1626,7 +1808,8 @@ fn apply_legacy_conflict_resolution_update(
         }
     }
 
-    log_changes(conn, spell.id, changes)?;
+    set_spell_reverse_name_with_conn(conn, spell.id, spell.reverse_name.as_deref())?;
+    log_changes(conn, spell.id, changes, IMPORT_CHANGE_LOG_ACTOR)?;
     migration_manager::sync_check_spell(conn, spell.id);
 
     Ok(PendingVaultSpellWrite {
@@ -1635,6 +1818,117 @@ fn apply_legacy_conflict_resolution_update(
     })
 }
 
+/// Persists the spell conflicts in `conflicts` to `import_conflict_queue` so they can be
+/// resolved in a later session. Parse conflicts have nothing to resolve and are not queued,
+/// and a conflict already queued for the same existing spell and incoming content is skipped.
+/// Returns the number of rows queued.
+pub(crate) fn enqueue_import_conflicts_with_conn(
+    conn: &rusqlite::Connection,
+    conflicts: &[ImportConflict],
+) -> Result<usize, AppError> {
+    let mut queued = 0;
+    for conflict in conflicts {
+        let ImportConflict::Spell {
+            existing, incoming, ..
+        } = conflict
+        else {
+            continue;
+        };
+        // An incoming spell that cannot be canonicalized is still queued; a NULL hash is
+        // never deduplicated.
+        let incoming_hash = CanonicalSpell::try_from(incoming.as_ref().clone())
+            .and_then(|canonical| canonical.compute_hash())
+            .ok();
+        let conflict_json = serde_json::to_string(conflict)
+            .map_err(|e| AppError::Import(format!("Failed to serialize import conflict: {}", e)))?;
+        queued += conn.execute(
+            "INSERT OR IGNORE INTO import_conflict_queue (existing_id, incoming_hash, conflict_json)
+             VALUES (?, ?, ?)",
+            params![existing.id, incoming_hash, conflict_json],
+        )?;
+    }
+    Ok(queued)
+}
+
+pub(crate) fn list_pending_conflicts_with_conn(
+    conn: &rusqlite::Connection,
+) -> Result<Vec<QueuedImportConflict>, AppError> {
+    let mut stmt =
+        conn.prepare("SELECT id, conflict_json, queued_at FROM import_conflict_queue ORDER BY id")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<String>>(2)?,
+        ))
+    })?;
+    let mut pending = vec![];
+    for row in rows {
+        let (id, conflict_json, queued_at) = row?;
+        let conflict = serde_json::from_str(&conflict_json).map_err(|e| {
+            AppError::Import(format!(
+                "Queued import conflict {} is unreadable: {}",
+                id, e
+            ))
+        })?;
+        pending.push(QueuedImportConflict {
+            id,
+            conflict,
+            queued_at,
+        });
+    }
+    Ok(pending)
+}
+
+/// Fills `resolution` from queued conflict `queue_id`: the incoming spell and artifact are
+/// used unless the caller supplied their own.
+fn fill_resolution_from_queue(
+    conn: &rusqlite::Connection,
+    queue_id: i64,
+    resolution: &mut ImportConflictResolution,
+) -> Result<(), AppError> {
+    let conflict_json: String = conn
+        .query_row(
+            "SELECT conflict_json FROM import_conflict_queue WHERE id = ?",
+            [queue_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Queued import conflict {} not found", queue_id))
+        })?;
+    let conflict: ImportConflict = serde_json::from_str(&conflict_json).map_err(|e| {
+        AppError::Import(format!(
+            "Queued import conflict {} is unreadable: {}",
+            queue_id, e
+        ))
+    })?;
+    let ImportConflict::Spell {
+        existing,
+        incoming,
+        artifact,
+        ..
+    } = conflict
+    else {
+        return Err(AppError::Validation(format!(
+            "Queued import conflict {} is not a spell conflict",
+            queue_id
+        )));
+    };
+    if existing.id != Some(resolution.existing_id) {
+        return Err(AppError::Validation(
+            "conflict resolution id mismatch".into(),
+        ));
+    }
+    if resolution.spell.is_none() {
+        resolution.spell = Some(spell_detail_to_update(&incoming, resolution.existing_id));
+    }
+    if resolution.artifact.is_none() {
+        resolution.artifact = artifact;
+    }
+    Ok(())
+}
+
 fn resolve_import_conflicts_with_conn_and_root(
     conn: &rusqlite::Connection,
     root: &std::path::Path,
@@ -1646,7 +1940,11 @@ fn resolve_import_conflicts_with_conn_and_root(
         let warnings = Vec::new();
         let mut pending_vault_writes = Vec::new();
 
-        for resolution in resolutions {
+        for mut resolution in resolutions {
+            let queue_id = resolution.queue_id;
+            if let Some(queue_id) = queue_id {
+                fill_resolution_from_queue(tx, queue_id, &mut resolution)?;
+            }
             let step = match resolution.action.as_str() {
                 "skip" => {
                     if let Some(existing_spell) = get_spell_from_conn(tx, resolution.existing_id)? {
@@ -1688,6 +1986,9 @@ fn resolve_import_conflicts_with_conn_and_root(
             };
 
             step?;
+            if let Some(queue_id) = queue_id {
+                tx.execute("DELETE FROM import_conflict_queue WHERE id = ?", [queue_id])?;
+            }
         }
 
         Ok((
@@ -1714,9 +2015,12 @@ fn run_with_import_maintenance<T>(
 pub async fn import_spell_json(
     state: State<'_, Arc<Pool>>,
     maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
     payload: String,
     source_ref_url_policy: Option<String>,
+    import_with_hash_ids: Option<bool>,
 ) -> Result<ImportSpellJsonResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
     let preview = preview_import_spell_json(payload, source_ref_url_policy).await?;
     if preview.spells.is_empty() && preview.failures.is_empty() {
         return Ok(ImportSpellJsonResult {
@@ -1742,6 +2046,7 @@ pub async fn import_spell_json(
             maintenance_state.as_ref(),
             items,
             None,
+            import_with_hash_ids.unwrap_or(false),
         )
     })
     .await
@@ -1753,16 +2058,234 @@ pub async fn import_spell_json(
     Ok(out)
 }
 
+/// `import_spell_json` for a bundle on disk. `.json.gz` files (or any gzip payload) are
+/// decompressed transparently; plain `.json` still works.
+#[tauri::command]
+pub async fn import_spell_json_file(
+    state: State<'_, Arc<Pool>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    path: String,
+    source_ref_url_policy: Option<String>,
+    import_with_hash_ids: Option<bool>,
+) -> Result<ImportSpellJsonResult, AppError> {
+    let payload = tokio::task::spawn_blocking(move || read_export_json(Path::new(&path)))
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))??;
+    import_spell_json(
+        state,
+        maintenance_state,
+        spell_cache,
+        payload,
+        source_ref_url_policy,
+        import_with_hash_ids,
+    )
+    .await
+}
+
+/// Spells applied per transaction by `import_ndjson`; only one chunk of parsed spells is
+/// held in memory at a time.
+const NDJSON_IMPORT_CHUNK_SIZE: usize = 500;
+
+/// Conflicts, failures and warnings `import_ndjson` returns verbatim; the rest are counted.
+const NDJSON_IMPORT_SAMPLE_LIMIT: usize = 100;
+
+/// Appends `items` to the capped `sample`, returning how many there were.
+fn push_capped<T>(sample: &mut Vec<T>, items: impl IntoIterator<Item = T>) -> usize {
+    let mut count = 0;
+    for item in items {
+        if sample.len() < NDJSON_IMPORT_SAMPLE_LIMIT {
+            sample.push(item);
+        }
+        count += 1;
+    }
+    count
+}
+
+/// Folds one chunk's counts, ids and capped conflict/failure/warning samples into the
+/// running NDJSON import result.
+fn merge_import_spell_json_result(total: &mut ImportNdjsonResult, chunk: ImportSpellJsonResult) {
+    total.imported_count += chunk.imported_count;
+    total
+        .imported_ids
+        .extend(chunk.imported_spells.iter().filter_map(|spell| spell.id));
+    total.duplicates_skipped.total += chunk.duplicates_skipped.total;
+    total.duplicates_skipped.merged_count += chunk.duplicates_skipped.merged_count;
+    total.duplicates_skipped.no_change_count += chunk.duplicates_skipped.no_change_count;
+    total.conflict_count += push_capped(&mut total.conflicts, chunk.conflicts);
+    if let Some(resolved) = chunk.conflicts_resolved {
+        let into = total
+            .conflicts_resolved
+            .get_or_insert_with(Default::default);
+        into.keep_existing_count += resolved.keep_existing_count;
+        into.replace_count += resolved.replace_count;
+        into.keep_both_count += resolved.keep_both_count;
+    }
+    total.failure_count += push_capped(&mut total.failures, chunk.failures);
+    total.warning_count += push_capped(&mut total.warnings, chunk.warnings);
+}
+
+/// Discards the rest of the current line (through its newline) from `reader`.
+fn skip_rest_of_line(reader: &mut impl BufRead) -> Result<(), AppError> {
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            return Ok(());
+        }
+        match available.iter().position(|&b| b == b'\n') {
+            Some(end) => {
+                reader.consume(end + 1);
+                return Ok(());
+            }
+            None => {
+                let len = available.len();
+                reader.consume(len);
+            }
+        }
+    }
+}
+
+/// Imports NDJSON (one `CanonicalSpell` per line, as written by `export_ndjson`) read line
+/// by line, applying every `chunk_size` previewed spells in their own transaction. No more
+/// than `max_line_bytes` of a line is buffered. Blank lines are skipped; a line that is
+/// too long or not a valid spell is reported as a failure named `line N` and does not stop
+/// the import.
+pub(crate) fn import_ndjson_from_reader(
+    conn: &rusqlite::Connection,
+    root: &std::path::Path,
+    maintenance_state: &VaultMaintenanceState,
+    mut reader: impl BufRead,
+    policy: SourceRefUrlPolicy,
+    chunk_size: usize,
+    max_line_bytes: usize,
+) -> Result<ImportNdjsonResult, AppError> {
+    let chunk_size = chunk_size.max(1);
+    let mut total = ImportNdjsonResult::default();
+    let mut chunk = Vec::with_capacity(chunk_size);
+    let mut line_number = 0usize;
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let read = std::io::Read::take(&mut reader, max_line_bytes as u64 + 1)
+            .read_until(b'\n', &mut buf)?;
+        if read == 0 {
+            break;
+        }
+        line_number += 1;
+        let mut failure = |reason: String| {
+            total.failure_count += push_capped(
+                &mut total.failures,
+                [ImportSpellJsonFailure {
+                    spell_name: format!("line {}", line_number),
+                    reason,
+                }],
+            );
+        };
+        if buf.len() > max_line_bytes && buf.last() != Some(&b'\n') {
+            skip_rest_of_line(&mut reader)?;
+            failure(format!("Line exceeds {} bytes", max_line_bytes));
+            continue;
+        }
+        let line = match std::str::from_utf8(&buf) {
+            Ok(line) => line.trim(),
+            Err(e) => {
+                failure(e.to_string());
+                continue;
+            }
+        };
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = validate_import_payload_guardrails(line).and_then(|_| {
+            serde_json::from_str::<CanonicalSpell>(line)
+                .map_err(|e| AppError::Import(e.to_string()))
+        });
+        let spell = match parsed {
+            Ok(spell) => spell,
+            Err(e) => {
+                failure(e.to_string());
+                continue;
+            }
+        };
+        let mut warnings = Vec::new();
+        let previewed = preview_spell_json_item(spell, policy, &mut warnings);
+        total.warning_count += push_capped(&mut total.warnings, warnings);
+        match previewed {
+            Ok(item) => chunk.push(item),
+            Err(e) => total.failure_count += push_capped(&mut total.failures, [e]),
+        }
+        if chunk.len() >= chunk_size {
+            let items = std::mem::replace(&mut chunk, Vec::with_capacity(chunk_size));
+            let applied = apply_import_spell_json_with_maintenance(
+                conn,
+                root,
+                maintenance_state,
+                items,
+                None,
+                false,
+            )?;
+            merge_import_spell_json_result(&mut total, applied);
+        }
+    }
+    if !chunk.is_empty() {
+        let applied = apply_import_spell_json_with_maintenance(
+            conn,
+            root,
+            maintenance_state,
+            chunk,
+            None,
+            false,
+        )?;
+        merge_import_spell_json_result(&mut total, applied);
+    }
+    Ok(total)
+}
+
+/// Imports an NDJSON file written by `export_ndjson`, streaming it line by line instead of
+/// loading the whole payload the way `import_spell_json_file` does.
+#[tauri::command]
+pub async fn import_ndjson(
+    state: State<'_, Arc<Pool>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    path: String,
+    source_ref_url_policy: Option<String>,
+) -> Result<ImportNdjsonResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let policy = parse_source_ref_url_policy(source_ref_url_policy.as_deref());
+    let pool = state.inner().clone();
+    let maintenance_state = maintenance_state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let root = app_data_dir()?;
+        let reader = std::io::BufReader::new(fs::File::open(&path)?);
+        import_ndjson_from_reader(
+            &conn,
+            &root,
+            maintenance_state.as_ref(),
+            reader,
+            policy,
+            NDJSON_IMPORT_CHUNK_SIZE,
+            MAX_IMPORT_PAYLOAD_BYTES,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
 /// Resolve JSON import conflicts: same payload as import_spell_json, plus resolutions and optional default_action.
 /// Runs preview then apply with the given resolve options (per-conflict resolutions and/or skip_all/replace_all/keep_all).
 #[tauri::command]
 pub async fn resolve_import_spell_json(
     state: State<'_, Arc<Pool>>,
     maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
     payload: String,
     resolve_options: ImportSpellJsonResolveOptions,
     source_ref_url_policy: Option<String>,
+    import_with_hash_ids: Option<bool>,
 ) -> Result<ImportSpellJsonResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
     let preview = preview_import_spell_json(payload, source_ref_url_policy).await?;
     if preview.spells.is_empty() && preview.failures.is_empty() {
         return Ok(ImportSpellJsonResult {
@@ -1789,6 +2312,7 @@ pub async fn resolve_import_spell_json(
             maintenance_state.as_ref(),
             items,
             Some(options),
+            import_with_hash_ids.unwrap_or(false),
         )
     })
     .await
@@ -1817,10 +2341,30 @@ fn app_data_dir() -> Result<PathBuf, AppError> {
     Ok(dir)
 }
 
+/// NFC-normalizes `name` and replaces anything but Unicode alphanumerics, `.`, `_` and `-`
+/// (separators, control characters, whitespace) with `_`. Leading dots are replaced too,
+/// so the result can never be `..` or a hidden file. `changed` reports real sanitization
+/// only; NFC normalization alone does not count.
 fn sanitize_import_filename(name: &str) -> (String, bool) {
-    let re = Regex::new(r"[^a-zA-Z0-9._-]").expect("static regex");
-    let sanitized = re.replace_all(name, "_").to_string();
-    let changed = sanitized != name;
+    let normalized: String = name.nfc().collect();
+    let mut sanitized: String = normalized
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let leading_dots = sanitized.len() - sanitized.trim_start_matches('.').len();
+    if leading_dots > 0 {
+        sanitized.replace_range(..leading_dots, &"_".repeat(leading_dots));
+    }
+    if sanitized.is_empty() {
+        sanitized.push('_');
+    }
+    let changed = sanitized != normalized;
     (sanitized, changed)
 }
 
@@ -1966,42 +2510,455 @@ fn build_conflict_fields(
         Some(incoming.is_cantrip.to_string()),
     );
 
-    fields
+    drop_cosmetic_conflicts(existing, fields)
 }
 
-#[tauri::command]
-pub async fn preview_import(files: Vec<ImportFile>) -> Result<PreviewResult, AppError> {
-    let dir = app_data_dir()?.join("imports");
-    fs::create_dir_all(&dir)?;
+/// Copies only the text columns of `detail`, so canonicalization parses the
+/// same legacy strings that the incoming import spell carries.
+fn legacy_text_detail(detail: &SpellDetail) -> SpellDetail {
+    SpellDetail {
+        id: detail.id,
+        name: detail.name.clone(),
+        school: detail.school.clone(),
+        sphere: detail.sphere.clone(),
+        class_list: detail.class_list.clone(),
+        level: detail.level,
+        range: detail.range.clone(),
+        components: detail.components.clone(),
+        material_components: detail.material_components.clone(),
+        casting_time: detail.casting_time.clone(),
+        duration: detail.duration.clone(),
+        area: detail.area.clone(),
+        saving_throw: detail.saving_throw.clone(),
+        damage: detail.damage.clone(),
+        magic_resistance: detail.magic_resistance.clone(),
+        reversible: detail.reversible,
+        description: detail.description.clone(),
+        tags: detail.tags.clone(),
+        source: detail.source.clone(),
+        edition: detail.edition.clone(),
+        author: detail.author.clone(),
+        license: detail.license.clone(),
+        is_quest_spell: detail.is_quest_spell,
+        is_cantrip: detail.is_cantrip,
+        schema_version: detail.schema_version,
+        ..Default::default()
+    }
+}
 
-    let mut paths = vec![];
-    let mut seen_names = HashMap::new();
-    for file in files {
-        let (safe_name, _) = sanitize_import_filename(&file.name);
-        if let Some(original) = seen_names.get(&safe_name) {
-            return Err(AppError::Validation(format!(
-                "Filename collision: '{}' and '{}' both sanitize to '{}'",
-                original, file.name, safe_name
-            )));
-        }
-        seen_names.insert(safe_name.clone(), file.name.clone());
+/// Writes a conflict field's incoming value into `detail`. Returns `false` for
+/// fields or values that cannot be mapped back, which keeps the raw conflict.
+fn apply_conflict_value(detail: &mut SpellDetail, field: &str, value: Option<String>) -> bool {
+    match field {
+        "name" => detail.name = value.unwrap_or_default(),
+        "school" => detail.school = value,
+        "sphere" => detail.sphere = value,
+        "class_list" => detail.class_list = value,
+        "level" => match value.and_then(|v| v.parse().ok()) {
+            Some(level) => detail.level = level,
+            None => return false,
+        },
+        "range" => detail.range = value,
+        "components" => detail.components = value,
+        "material_components" => detail.material_components = value,
+        "casting_time" => detail.casting_time = value,
+        "duration" => detail.duration = value,
+        "area" => detail.area = value,
+        "saving_throw" => detail.saving_throw = value,
+        "damage" => detail.damage = value,
+        "magic_resistance" => detail.magic_resistance = value,
+        "reversible" => match value.map(|v| v.parse()) {
+            Some(Ok(reversible)) => detail.reversible = Some(reversible),
+            Some(Err(_)) => return false,
+            None => detail.reversible = None,
+        },
+        "description" => detail.description = value.unwrap_or_default(),
+        "tags" => detail.tags = value,
+        "source" => detail.source = value,
+        "edition" => detail.edition = value,
+        "author" => detail.author = value,
+        "license" => detail.license = value,
+        "is_cantrip" => match value.and_then(|v| v.parse().ok()) {
+            Some(is_cantrip) => detail.is_cantrip = is_cantrip,
+            None => return false,
+        },
+        _ => return false,
+    }
+    true
+}
 
-        let path = dir.join(&safe_name);
-        fs::write(&path, &file.content)?;
-        paths.push(path);
+/// Drops conflict fields whose incoming value canonicalizes to the same spell
+/// hash as the existing value (whitespace, casing, class-list order). When
+/// either side fails to canonicalize the raw comparison stands.
+fn drop_cosmetic_conflicts(
+    existing: &SpellDetail,
+    fields: Vec<ImportConflictField>,
+) -> Vec<ImportConflictField> {
+    if fields.is_empty() {
+        return fields;
     }
+    let base = legacy_text_detail(existing);
+    let Ok((_, base_hash, _)) = canonicalize_spell_detail(base.clone()) else {
+        return fields;
+    };
 
-    let result = call_sidecar("import", json!({"files": paths})).await?;
+    fields
+        .into_iter()
+        .filter(|field| {
+            let mut candidate = base.clone();
+            if !apply_conflict_value(&mut candidate, &field.field, field.incoming.clone()) {
+                return true;
+            }
+            match canonicalize_spell_detail(candidate) {
+                Ok((_, hash, _)) => hash != base_hash,
+                Err(_) => true,
+            }
+        })
+        .collect()
+}
 
-    let spells: Vec<PreviewSpell> =
-        serde_json::from_value(result.get("spells").cloned().unwrap_or(json!([])))
-            .map_err(|e| AppError::Sidecar(format!("Failed to parse preview spells: {}", e)))?;
+/// Field confidence below this marks a previewed spell as needing attention.
+const DEFAULT_LOW_CONFIDENCE_THRESHOLD: f32 = 0.5;
 
-    let artifacts: Vec<ImportArtifact> =
-        serde_json::from_value(result.get("artifacts").cloned().unwrap_or(json!([])))
-            .map_err(|e| AppError::Sidecar(format!("Failed to parse preview artifacts: {}", e)))?;
+fn preview_confidence_stats(spells: &[PreviewSpell], threshold: f32) -> PreviewConfidenceStats {
+    let mut stats = PreviewConfidenceStats::default();
+    let mut scored = 0usize;
+    let mut total = 0.0f32;
 
-    let parse_conflicts: Vec<ParseConflict> =
+    for spell in spells {
+        if spell.confidence.is_empty() {
+            continue;
+        }
+        total += spell.confidence.values().sum::<f32>() / spell.confidence.len() as f32;
+        scored += 1;
+        if spell.confidence.values().any(|v| *v < threshold) {
+            stats.low_confidence_count += 1;
+        }
+        if spell.confidence.values().all(|v| *v >= 1.0) {
+            stats.fully_parsed_count += 1;
+        }
+    }
+
+    if scored > 0 {
+        stats.mean_confidence = total / scored as f32;
+    }
+    stats
+}
+
+/// Editable [`SpellDetail`] for a bundle spell, carrying its structured specs so an
+/// in-place update keeps them.
+fn canonical_spell_to_detail(spell: &CanonicalSpell) -> SpellDetail {
+    SpellDetail {
+        range_spec: spell.range.clone(),
+        components_spec: spell.components.clone(),
+        material_components_spec: spell.material_components.clone(),
+        casting_time_spec: spell.casting_time.clone(),
+        duration_spec: spell.duration.clone(),
+        area_spec: spell.area.clone(),
+        saving_throw_spec: spell.saving_throw.clone(),
+        damage_spec: spell.damage.clone(),
+        magic_resistance_spec: spell.magic_resistance.clone(),
+        ..import_spell_to_detail(&canonical_spell_to_import_spell(spell), None)
+    }
+}
+
+/// Flattens a canonical spell (from a prior JSON export) into the text columns the
+/// legacy import path works with, keeping the spell itself as `canonical_data` so the
+/// structured fields the text columns cannot hold survive the import.
+fn canonical_spell_to_import_spell(spell: &CanonicalSpell) -> ImportSpell {
+    let (
+        name,
+        school,
+        sphere,
+        class_list,
+        level,
+        range,
+        _,
+        _,
+        casting_time,
+        duration,
+        area,
+        saving_throw,
+        damage,
+        magic_resistance,
+        reversible,
+        description,
+        tags,
+        source,
+        edition,
+        author,
+        license,
+        is_quest_spell,
+        is_cantrip,
+        schema_version,
+    ) = canonical_spell_to_flat_row(spell);
+    let components = spell
+        .components
+        .as_ref()
+        .map(ComponentsParser::render_components);
+    let material_components = spell
+        .material_components
+        .as_ref()
+        .filter(|materials| !materials.is_empty())
+        .map(|materials| {
+            materials
+                .iter()
+                .map(|m| m.description.clone().unwrap_or_else(|| m.name.clone()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+    ImportSpell {
+        name,
+        school,
+        sphere,
+        class_list,
+        level,
+        range,
+        components: components.filter(|c| !c.is_empty()),
+        material_components,
+        casting_time,
+        duration,
+        area,
+        saving_throw,
+        damage,
+        magic_resistance,
+        reversible: Some(reversible),
+        reverse_name: None,
+        description,
+        tags,
+        source,
+        edition,
+        author,
+        license,
+        is_quest_spell,
+        is_cantrip,
+        schema_version: Some(schema_version),
+        source_file: None,
+        class_levels: None,
+        canonical_data: serde_json::to_string(spell).ok(),
+    }
+}
+
+fn spell_detail_to_import_spell(detail: SpellDetail) -> ImportSpell {
+    ImportSpell {
+        name: detail.name,
+        school: detail.school,
+        sphere: detail.sphere,
+        class_list: detail.class_list,
+        level: detail.level,
+        range: detail.range,
+        components: detail.components,
+        material_components: detail.material_components,
+        casting_time: detail.casting_time,
+        duration: detail.duration,
+        area: detail.area,
+        saving_throw: detail.saving_throw,
+        damage: detail.damage,
+        magic_resistance: detail.magic_resistance,
+        reversible: detail.reversible,
+        description: detail.description,
+        tags: detail.tags,
+        source: detail.source,
+        edition: detail.edition,
+        author: detail.author,
+        license: detail.license,
+        reverse_name: detail.reverse_name,
+        source_file: None,
+        is_quest_spell: detail.is_quest_spell,
+        is_cantrip: detail.is_cantrip,
+        schema_version: detail.schema_version,
+        class_levels: None,
+        canonical_data: detail.canonical_data,
+    }
+}
+
+/// Reads a `.json` import file written by one of our exports: an array (or single object)
+/// of `CanonicalSpell`, tried first, or `SpellDetail`, or a spell bundle.
+fn parse_native_json_spells(payload: &str) -> Result<Vec<ImportSpell>, String> {
+    validate_import_payload_guardrails(payload).map_err(|e| e.to_string())?;
+    let value: serde_json::Value =
+        serde_json::from_str(payload).map_err(|e| format!("invalid_json: {}", e))?;
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(mut obj) if obj.contains_key("spells") => {
+            match obj.remove("spells") {
+                Some(serde_json::Value::Array(items)) => items,
+                _ => return Err("invalid_json: 'spells' must be an array".into()),
+            }
+        }
+        other => vec![other],
+    };
+
+    if let Ok(spells) = items
+        .iter()
+        .map(|item| serde_json::from_value::<CanonicalSpell>(item.clone()))
+        .collect::<Result<Vec<_>, _>>()
+    {
+        return Ok(spells.iter().map(canonical_spell_to_import_spell).collect());
+    }
+    items
+        .into_iter()
+        .map(|item| serde_json::from_value::<SpellDetail>(item).map(spell_detail_to_import_spell))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("parsing_error: {}", e))
+}
+
+/// A preview of one natively parsed spell. Every field the file provides is taken as-is,
+/// so it scores 1.0; absent fields are left unscored.
+fn native_preview_spell(detail: ImportSpell, source_file: &Path) -> PreviewSpell {
+    let mut confidence = HashMap::from([
+        ("name".to_string(), 1.0),
+        ("level".to_string(), 1.0),
+        ("description".to_string(), 1.0),
+    ]);
+    for (field, value) in [
+        ("school", &detail.school),
+        ("sphere", &detail.sphere),
+        ("class_list", &detail.class_list),
+        ("range", &detail.range),
+        ("components", &detail.components),
+        ("material_components", &detail.material_components),
+        ("casting_time", &detail.casting_time),
+        ("duration", &detail.duration),
+        ("area", &detail.area),
+        ("saving_throw", &detail.saving_throw),
+        ("damage", &detail.damage),
+        ("magic_resistance", &detail.magic_resistance),
+        ("tags", &detail.tags),
+        ("source", &detail.source),
+    ] {
+        if value.as_deref().is_some_and(|v| !v.trim().is_empty()) {
+            confidence.insert(field.to_string(), 1.0);
+        }
+    }
+    PreviewSpell {
+        name: detail.name,
+        level: detail.level,
+        school: detail.school,
+        sphere: detail.sphere,
+        class_list: detail.class_list,
+        range: detail.range,
+        components: detail.components,
+        material_components: detail.material_components,
+        casting_time: detail.casting_time,
+        duration: detail.duration,
+        area: detail.area,
+        saving_throw: detail.saving_throw,
+        damage: detail.damage,
+        magic_resistance: detail.magic_resistance,
+        reversible: detail.reversible,
+        description: detail.description,
+        tags: detail.tags,
+        source: detail.source,
+        edition: detail.edition,
+        author: detail.author,
+        license: detail.license,
+        confidence,
+        raw_text: None,
+        source_file: source_file.to_string_lossy().into_owned(),
+        is_quest_spell: detail.is_quest_spell,
+        is_cantrip: detail.is_cantrip,
+        schema_version: detail.schema_version,
+        canonical_data: detail.canonical_data,
+    }
+}
+
+/// Parses `.json` files in Rust into the same `{spells, artifacts, conflicts}` shape the
+/// sidecar's `import` returns. Unreadable or malformed files become parse conflicts.
+fn parse_native_json_files(paths: &[PathBuf]) -> serde_json::Value {
+    let mut spells = Vec::new();
+    let mut artifacts = Vec::new();
+    let mut conflicts = Vec::new();
+    for path in paths {
+        let path_str = path.to_string_lossy().into_owned();
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(_) => {
+                conflicts.push(json!({"path": path_str, "reason": "missing"}));
+                continue;
+            }
+        };
+        let parsed = String::from_utf8(bytes.clone())
+            .map_err(|e| format!("invalid_json: {}", e))
+            .and_then(|payload| parse_native_json_spells(&payload));
+        match parsed {
+            Ok(details) => {
+                spells.extend(details.into_iter().map(|detail| {
+                    serde_json::to_value(native_preview_spell(detail, path)).unwrap_or_default()
+                }));
+                artifacts.push(json!({
+                    "type": "json",
+                    "path": path_str,
+                    "hash": hex::encode(Sha256::digest(&bytes)),
+                    "imported_at": Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                }));
+            }
+            Err(reason) => conflicts.push(json!({"path": path_str, "reason": reason})),
+        }
+    }
+    json!({"spells": spells, "artifacts": artifacts, "conflicts": conflicts})
+}
+
+/// Runs import files through the parsers: `.json` files natively, everything else
+/// through the sidecar, with both results merged into the sidecar's response shape.
+async fn parse_import_files(paths: &[PathBuf]) -> Result<serde_json::Value, AppError> {
+    let (json_paths, sidecar_paths): (Vec<PathBuf>, Vec<PathBuf>) =
+        paths.iter().cloned().partition(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+        });
+    let mut result = parse_native_json_files(&json_paths);
+    if sidecar_paths.is_empty() {
+        return Ok(result);
+    }
+    let sidecar = call_sidecar("import", json!({"files": sidecar_paths})).await?;
+    for key in ["spells", "artifacts", "conflicts"] {
+        if let (Some(merged), Some(serde_json::Value::Array(items))) =
+            (result[key].as_array_mut(), sidecar.get(key))
+        {
+            merged.extend(items.iter().cloned());
+        }
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn preview_import(
+    files: Vec<ImportFile>,
+    low_confidence_threshold: Option<f32>,
+) -> Result<PreviewResult, AppError> {
+    let dir = app_data_dir()?.join("imports");
+    fs::create_dir_all(&dir)?;
+
+    let mut paths = vec![];
+    let mut seen_names = HashMap::new();
+    for file in files {
+        let (safe_name, _) = sanitize_import_filename(&file.name);
+        if let Some(original) = seen_names.get(&safe_name) {
+            return Err(AppError::Validation(format!(
+                "Filename collision: '{}' and '{}' both sanitize to '{}'",
+                original, file.name, safe_name
+            )));
+        }
+        seen_names.insert(safe_name.clone(), file.name.clone());
+
+        let path = dir.join(&safe_name);
+        fs::write(&path, &file.content)?;
+        paths.push(path);
+    }
+
+    let result = parse_import_files(&paths).await?;
+
+    let spells: Vec<PreviewSpell> =
+        serde_json::from_value(result.get("spells").cloned().unwrap_or(json!([])))
+            .map_err(|e| AppError::Sidecar(format!("Failed to parse preview spells: {}", e)))?;
+
+    let artifacts: Vec<ImportArtifact> =
+        serde_json::from_value(result.get("artifacts").cloned().unwrap_or(json!([])))
+            .map_err(|e| AppError::Sidecar(format!("Failed to parse preview artifacts: {}", e)))?;
+
+    let parse_conflicts: Vec<ParseConflict> =
         serde_json::from_value(result.get("conflicts").cloned().unwrap_or(json!([])))
             .map_err(|e| AppError::Sidecar(format!("Failed to parse preview conflicts: {}", e)))?;
 
@@ -2013,320 +2970,695 @@ pub async fn preview_import(files: Vec<ImportFile>) -> Result<PreviewResult, App
         })
         .collect();
 
+    let stats = preview_confidence_stats(
+        &spells,
+        low_confidence_threshold.unwrap_or(DEFAULT_LOW_CONFIDENCE_THRESHOLD),
+    );
+
     Ok(PreviewResult {
         spells,
         artifacts,
         conflicts,
+        stats,
     })
 }
 
-#[tauri::command]
-pub async fn import_files(
-    state: State<'_, Arc<Pool>>,
-    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
-    files: Vec<ImportFile>,
-    allow_overwrite: bool,
-    spells: Option<Vec<ImportSpell>>,
-    artifacts: Option<Vec<ImportArtifact>>,
-    conflicts: Option<Vec<ImportConflict>>,
-) -> Result<ImportResult, AppError> {
-    let pool = state.inner().clone();
-    let gc_pool = pool.clone();
-    let maintenance_state = maintenance_state.inner().clone();
-    let import_guard = maintenance_state.start_import()?;
-    let result = async move {
-        let dir = app_data_dir()?.join("imports");
-        fs::create_dir_all(&dir)?;
-
-        // BATCH SIZE CONFIGURATION
-        const BATCH_SIZE: usize = 10;
-
-        let mut all_imported_spells = vec![];
-        let mut all_artifacts = vec![];
-        let mut all_conflicts = vec![];
-        let mut all_warnings = vec![];
-        let mut all_skipped = vec![];
-        let mut mutated_spell_count = 0usize;
+fn preview_spell_to_detail(spell: &PreviewSpell) -> SpellDetail {
+    SpellDetail {
+        name: spell.name.clone(),
+        level: spell.level,
+        school: spell.school.clone(),
+        sphere: spell.sphere.clone(),
+        class_list: spell.class_list.clone(),
+        range: spell.range.clone(),
+        components: spell.components.clone(),
+        material_components: spell.material_components.clone(),
+        casting_time: spell.casting_time.clone(),
+        duration: spell.duration.clone(),
+        area: spell.area.clone(),
+        saving_throw: spell.saving_throw.clone(),
+        damage: spell.damage.clone(),
+        magic_resistance: spell.magic_resistance.clone(),
+        reversible: spell.reversible,
+        description: spell.description.clone(),
+        tags: spell.tags.clone(),
+        source: spell.source.clone(),
+        edition: spell.edition.clone(),
+        author: spell.author.clone(),
+        license: spell.license.clone(),
+        is_quest_spell: spell.is_quest_spell,
+        is_cantrip: spell.is_cantrip,
+        schema_version: spell.schema_version,
+        ..Default::default()
+    }
+}
 
-        // Pre-save all files to disk (keep this fast and simple)
-        let mut file_paths_map = HashMap::new();
-        let mut seen_names = HashMap::new();
+/// Runs each preview through the same canonicalization and schema validation a commit
+/// would, without touching the database.
+fn validate_preview_spells(spells: &[PreviewSpell]) -> Vec<PreviewValidation> {
+    spells
+        .iter()
+        .map(|spell| {
+            // compute_hash normalizes and schema-validates, as a commit would.
+            let outcome = CanonicalSpell::try_from(preview_spell_to_detail(spell))
+                .and_then(|canonical| canonical.compute_hash().map(|_| ()));
+            PreviewValidation {
+                name: spell.name.clone(),
+                valid: outcome.is_ok(),
+                error: outcome.err(),
+            }
+        })
+        .collect()
+}
 
-        for file in &files {
-            let (safe_name, changed) = sanitize_import_filename(&file.name);
+#[tauri::command]
+pub async fn validate_preview(
+    spells: Vec<PreviewSpell>,
+) -> Result<Vec<PreviewValidation>, AppError> {
+    tokio::task::spawn_blocking(move || validate_preview_spells(&spells))
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))
+}
 
-            if let Some(original) = seen_names.get(&safe_name) {
-                return Err(AppError::Validation(format!(
-                    "Filename collision: '{}' and '{}' both sanitize to '{}'",
-                    original, file.name, safe_name
-                )));
-            }
-            seen_names.insert(safe_name.clone(), file.name.clone());
+/// Merges batch labels into each spell's tags, stored as a sorted, deduplicated JSON array.
+fn apply_import_tags(spells: &mut [ImportSpell], apply_tags: &[String]) {
+    if apply_tags.iter().all(|tag| tag.trim().is_empty()) {
+        return;
+    }
+    for spell in spells {
+        let mut tags = spell
+            .tags
+            .as_deref()
+            .map(parse_list_column)
+            .unwrap_or_default();
+        tags.extend(apply_tags.iter().map(|tag| tag.trim().to_string()));
+        spell.tags = normalize_list_column(&serde_json::to_string(&tags).ok());
+    }
+}
 
-            if changed {
-                all_warnings.push(format!(
-                    "Sanitized import file name '{}' to '{}'.",
-                    file.name, safe_name
-                ));
+/// Splits per-class levels written into the class list ("Wizard 3, Cleric (4)") into
+/// `class_levels`, leaving bare class names in `class_list`. Entries without a level are
+/// kept as-is; levels supplied by the sidecar take precedence.
+fn extract_class_levels(spells: &mut [ImportSpell]) {
+    static CLASS_LEVEL_RE: OnceLock<Regex> = OnceLock::new();
+    let re = CLASS_LEVEL_RE.get_or_init(|| {
+        Regex::new(r"^(.*?[^\d\s(])\s*\(?\s*(\d{1,2})\s*\)?$").expect("static regex")
+    });
+    for spell in spells {
+        let Some(class_list) = spell.class_list.as_deref() else {
+            continue;
+        };
+        let mut levels = HashMap::new();
+        let mut classes = Vec::new();
+        for entry in parse_list_column(class_list) {
+            match re.captures(&entry) {
+                Some(caps) => {
+                    let class = caps[1].trim().to_string();
+                    if let Ok(level) = caps[2].parse::<i64>() {
+                        levels.insert(class.clone(), level);
+                    }
+                    classes.push(class);
+                }
+                None => classes.push(entry),
             }
-            let path = dir.join(&safe_name);
-            fs::write(&path, &file.content)?;
-            file_paths_map.insert(file.name.clone(), path);
         }
+        if levels.is_empty() {
+            continue;
+        }
+        spell.class_list = list_column(&classes);
+        let merged = spell.class_levels.get_or_insert_with(HashMap::new);
+        for (class, level) in levels {
+            merged.entry(class).or_insert(level);
+        }
+    }
+}
 
-        // Branch Process:
-        // 1. Initial Import (needs_parsing=true): Chunk files -> Sidecar -> DB
-        // 2. Confirmation (needs_parsing=false): Chunk spells (overrides) -> DB
+/// Runs one chunk of on-disk files through `parse_import_files` and applies the parsed spells in
+/// a single vault-write transaction. This is the initial-import path of `import_files`.
+async fn import_file_chunk(
+    pool: Arc<Pool>,
+    chunk_paths: Vec<PathBuf>,
+    allow_overwrite: bool,
+    force: bool,
+    apply_tags: &[String],
+    persist_conflicts: bool,
+) -> Result<ImportResult, AppError> {
+    let result = parse_import_files(&chunk_paths).await?;
+    let apply_tags = apply_tags.to_vec();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let root = app_data_dir()?;
+        apply_import_file_chunk_with_conn(
+            &conn,
+            &root,
+            result,
+            allow_overwrite,
+            force,
+            &apply_tags,
+            persist_conflicts,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
 
-        let needs_parsing = spells.is_none();
+/// Applies one chunk of `parse_import_files` output (`spells`, `artifacts`, `conflicts`) in a
+/// single vault-write transaction. With `persist_conflicts`, the chunk's spell conflicts are
+/// queued in that same transaction.
+fn apply_import_file_chunk_with_conn(
+    conn: &rusqlite::Connection,
+    root: &std::path::Path,
+    result: serde_json::Value,
+    allow_overwrite: bool,
+    force: bool,
+    apply_tags: &[String],
+    persist_conflicts: bool,
+) -> Result<ImportResult, AppError> {
+    // Parse Sidecar Result
+    let mut parsed_spells: Vec<ImportSpell> =
+        serde_json::from_value(result.get("spells").cloned().unwrap_or(json!([])))
+            .map_err(|e| AppError::Sidecar(format!("Failed to parse spells: {}", e)))?;
+    apply_import_tags(&mut parsed_spells, apply_tags);
+    extract_class_levels(&mut parsed_spells);
+    let parsed_artifacts: Vec<ImportArtifact> =
+        serde_json::from_value(result.get("artifacts").cloned().unwrap_or(json!([])))
+            .map_err(|e| AppError::Sidecar(format!("Failed to parse artifacts: {}", e)))?;
+    let parsed_conflicts_raw: Vec<ParseConflict> =
+        serde_json::from_value(result.get("conflicts").cloned().unwrap_or(json!([])))
+            .map_err(|e| AppError::Sidecar(format!("Failed to parse conflicts: {}", e)))?;
 
-        if needs_parsing {
-            // --- PATH A: INITIAL IMPORT (Sidecar -> DB) ---
-            for chunk in files.chunks(BATCH_SIZE) {
-                let chunk_paths: Vec<PathBuf> = chunk
-                    .iter()
-                    .filter_map(|f| file_paths_map.get(&f.name).cloned())
-                    .collect();
+    let batch_conflicts: Vec<ImportConflict> = parsed_conflicts_raw
+        .into_iter()
+        .map(|c| ImportConflict::Parse {
+            path: c.path,
+            reason: c.reason,
+        })
+        .collect();
 
-                if chunk_paths.is_empty() {
-                    continue;
-                }
+    // DB Transaction
+    let allow_overwrite_clone = allow_overwrite;
 
-                let result = call_sidecar("import", json!({"files": chunk_paths})).await?;
+    run_legacy_import_chunk_with_vault_writes(conn, root, |conn| {
+        let mut local_skipped = vec![];
+        let mut local_warnings = vec![];
+        let mut local_imported = vec![];
+        let mut local_vault_refresh = HashMap::new();
+        let mut artifacts_by_path = HashMap::new();
 
-            // Parse Sidecar Result
-            let parsed_spells: Vec<ImportSpell> =
-                serde_json::from_value(result.get("spells").cloned().unwrap_or(json!([])))
-                    .map_err(|e| AppError::Sidecar(format!("Failed to parse spells: {}", e)))?;
-            let parsed_artifacts: Vec<ImportArtifact> =
-                serde_json::from_value(result.get("artifacts").cloned().unwrap_or(json!([])))
-                    .map_err(|e| AppError::Sidecar(format!("Failed to parse artifacts: {}", e)))?;
-            let parsed_conflicts_raw: Vec<ParseConflict> =
-                serde_json::from_value(result.get("conflicts").cloned().unwrap_or(json!([])))
-                    .map_err(|e| AppError::Sidecar(format!("Failed to parse conflicts: {}", e)))?;
+        for artifact in &parsed_artifacts {
+            artifacts_by_path.insert(normalize_key(&artifact.path), artifact.clone());
+        }
 
-            let batch_conflicts: Vec<ImportConflict> = parsed_conflicts_raw
-                .into_iter()
-                .map(|c| ImportConflict::Parse {
-                    path: c.path,
-                    reason: c.reason,
-                })
-                .collect();
+        let spell_sources: Vec<Option<String>> = parsed_artifacts
+            .iter()
+            .map(|artifact| Some(normalize_key(&artifact.path)))
+            .collect();
 
-            // DB Transaction
-            let pool = pool.clone();
-            let allow_overwrite_clone = allow_overwrite;
-
-            let result = tokio::task::spawn_blocking(move || {
-                let conn = pool.get()?;
-                let root = app_data_dir()?;
-                run_legacy_import_chunk_with_vault_writes(&conn, &root, |conn| {
-                    let mut local_skipped = vec![];
-                    let mut local_imported = vec![];
-                    let mut local_vault_refresh = HashMap::new();
-                    let mut artifacts_by_path = HashMap::new();
-
-                    for artifact in &parsed_artifacts {
-                        artifacts_by_path.insert(normalize_key(&artifact.path), artifact.clone());
-                    }
+        let mut local_conflicts = batch_conflicts;
+
+        for (i, spell) in parsed_spells.iter().enumerate() {
+            let detail = SpellDetail {
+                id: None,
+                name: spell.name.clone(),
+                school: spell.school.clone(),
+                sphere: spell.sphere.clone(),
+                class_list: spell.class_list.clone(),
+                level: spell.level,
+                range: spell.range.clone(),
+                components: spell.components.clone(),
+                material_components: spell.material_components.clone(),
+                casting_time: spell.casting_time.clone(),
+                duration: spell.duration.clone(),
+                area: spell.area.clone(),
+                saving_throw: spell.saving_throw.clone(),
+                damage: spell.damage.clone(),
+                magic_resistance: spell.magic_resistance.clone(),
+                reversible: spell.reversible,
+                description: spell.description.clone(),
+                tags: spell.tags.clone(),
+                source: spell.source.clone(),
+                edition: spell.edition.clone(),
+                author: spell.author.clone(),
+                license: spell.license.clone(),
+                is_quest_spell: spell.is_quest_spell,
+                is_cantrip: spell.is_cantrip,
+                schema_version: spell.schema_version,
+                artifacts: None,
+                canonical_data: None,
+                content_hash: None,
+                ..Default::default()
+            };
+            let (canonical, hash, json) = canonicalize_import_spell(spell, detail.clone())?;
+            let vault_hash = hash.clone();
+            let vault_json = json.clone();
 
-                    let spell_sources: Vec<Option<String>> = parsed_artifacts
-                        .iter()
-                        .map(|artifact| Some(normalize_key(&artifact.path)))
-                        .collect();
-
-                    let mut local_conflicts = batch_conflicts;
-
-                    for (i, spell) in parsed_spells.iter().enumerate() {
-                        let detail = SpellDetail {
-                            id: None,
-                            name: spell.name.clone(),
-                            school: spell.school.clone(),
-                            sphere: spell.sphere.clone(),
-                            class_list: spell.class_list.clone(),
-                            level: spell.level,
-                            range: spell.range.clone(),
-                            components: spell.components.clone(),
-                            material_components: spell.material_components.clone(),
-                            casting_time: spell.casting_time.clone(),
-                            duration: spell.duration.clone(),
-                            area: spell.area.clone(),
-                            saving_throw: spell.saving_throw.clone(),
-                            damage: spell.damage.clone(),
-                            magic_resistance: spell.magic_resistance.clone(),
-                            reversible: spell.reversible,
-                            description: spell.description.clone(),
-                            tags: spell.tags.clone(),
-                            source: spell.source.clone(),
-                            edition: spell.edition.clone(),
-                            author: spell.author.clone(),
-                            license: spell.license.clone(),
-                            is_quest_spell: spell.is_quest_spell,
-                            is_cantrip: spell.is_cantrip,
-                            schema_version: spell.schema_version,
-                            artifacts: None,
-                            canonical_data: None,
-                            content_hash: None,
-                            ..Default::default()
-                        };
-                        let (canonical, hash, json) = canonicalize_spell_detail(detail.clone())?;
-                        let vault_hash = hash.clone();
-                        let vault_json = json.clone();
-
-                        let existing_id: Option<i64> = conn.query_row(
-                            "SELECT id FROM spell WHERE name = ? AND level = ? AND source IS ?",
-                            params![spell.name, spell.level, spell.source],
-                            |row| row.get(0),
-                        ).optional()?;
-
-                        let (spell_id, current_content_hash) = if let Some(id) = existing_id {
-                            if !allow_overwrite_clone {
-                                let existing_spell = get_spell_from_conn(conn, id)?.ok_or_else(|| {
-                                    AppError::NotFound("Failed to fetch existing spell".into())
-                                })?;
-
-                                let source_path = spell.source_file.clone();
-                                let artifact_opt = source_path
-                                    .as_ref()
-                                    .map(|p| normalize_key(p))
-                                    .and_then(|p| artifacts_by_path.get(&p).cloned())
-                                    .or_else(|| {
-                                        spell_sources
-                                            .get(i)
-                                            .and_then(|s| s.as_ref())
-                                            .and_then(|p| artifacts_by_path.get(p).cloned())
-                                    });
-
-                                let fields = build_conflict_fields(&existing_spell, spell);
-                                if fields.is_empty() {
-                                    local_skipped.push(spell.name.clone());
-                                } else {
-                                    local_conflicts.push(ImportConflict::Spell {
-                                        existing: Box::new(existing_spell),
-                                        incoming: Box::new(detail),
-                                        fields,
-                                        artifact: artifact_opt,
-                                    });
-                                }
-                                continue;
-                            }
+            let existing_id: Option<i64> = conn
+                .query_row(
+                    "SELECT id FROM spell WHERE name = ? AND level = ? AND source IS ?",
+                    params![spell.name, spell.level, spell.source],
+                    |row| row.get(0),
+                )
+                .optional()?;
 
-                            let update = spell_update_from_import_spell(id, spell);
-                            let pending_write =
-                                apply_legacy_conflict_resolution_update(conn, &update)?;
-                            local_vault_refresh.insert(
-                                pending_write.content_hash.clone(),
-                                pending_write.canonical_json.clone(),
-                            );
-                            (id, pending_write.content_hash)
-                        } else {
-                            conn.execute(
-                                "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
-                                material_components, casting_time, duration, area, saving_throw, damage,
-                                magic_resistance, reversible, description, tags, source, edition, author,
-                                license, is_quest_spell, is_cantrip, canonical_data, content_hash,
-                                schema_version)
-                                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-                                params![
-                                    spell.name, spell.school, spell.sphere, spell.class_list, spell.level, spell.range, spell.components,
-                                    spell.material_components, spell.casting_time, spell.duration, spell.area, spell.saving_throw,
-                                    spell.damage, spell.magic_resistance,
-                                    spell.reversible.unwrap_or(0),
-                                    spell.description, spell.tags, spell.source, spell.edition, spell.author, spell.license, spell.is_quest_spell, spell.is_cantrip,
-                                    json, hash, canonical.schema_version
-                                ],
-                            )?;
-                            let spell_id = conn.last_insert_rowid();
-                            local_vault_refresh.insert(vault_hash.clone(), vault_json.clone());
-                            (spell_id, vault_hash.clone())
-                        };
-
-                        migration_manager::sync_check_spell(conn, spell_id);
-                        local_imported.push(SpellDetail {
-                            id: Some(spell_id),
-                            name: spell.name.clone(),
-                            school: spell.school.clone(),
-                            level: spell.level,
-                            description: spell.description.clone(),
-                            source: spell.source.clone(),
-                            sphere: spell.sphere.clone(),
-                            class_list: spell.class_list.clone(),
-                            range: spell.range.clone(),
-                            components: spell.components.clone(),
-                            material_components: spell.material_components.clone(),
-                            casting_time: spell.casting_time.clone(),
-                            duration: spell.duration.clone(),
-                            area: spell.area.clone(),
-                            saving_throw: spell.saving_throw.clone(),
-                            damage: spell.damage.clone(),
-                            magic_resistance: spell.magic_resistance.clone(),
-                            reversible: spell.reversible,
-                            tags: spell.tags.clone(),
-                            edition: spell.edition.clone(),
-                            author: spell.author.clone(),
-                            license: spell.license.clone(),
-                            is_quest_spell: spell.is_quest_spell,
-                            is_cantrip: spell.is_cantrip,
-                            schema_version: spell.schema_version,
-                            artifacts: None,
-                            canonical_data: None,
-                            content_hash: None,
-                            ..Default::default()
+            let (spell_id, current_content_hash) = if let Some(id) = existing_id {
+                if !allow_overwrite_clone {
+                    let existing_spell = get_spell_from_conn(conn, id)?.ok_or_else(|| {
+                        AppError::NotFound("Failed to fetch existing spell".into())
+                    })?;
+
+                    let source_path = spell.source_file.clone();
+                    let artifact_opt = source_path
+                        .as_ref()
+                        .map(|p| normalize_key(p))
+                        .and_then(|p| artifacts_by_path.get(&p).cloned())
+                        .or_else(|| {
+                            spell_sources
+                                .get(i)
+                                .and_then(|s| s.as_ref())
+                                .and_then(|p| artifacts_by_path.get(p).cloned())
                         });
 
-                        let source_path = spell.source_file.clone();
-                        let artifact_val = source_path
-                            .as_ref()
-                            .map(|p| normalize_key(p))
-                            .and_then(|p| artifacts_by_path.get(&p))
-                            .or_else(|| {
-                                artifacts_by_path
-                                    .get(&spell_sources.get(i).cloned().flatten().unwrap_or_default())
-                            });
-
-                        if let Some(artifact_val) = artifact_val {
-                            upsert_import_artifact(
-                                conn,
-                                spell_id,
-                                &current_content_hash,
-                                artifact_val,
-                            )?;
-                        }
+                    let fields = build_conflict_fields(&existing_spell, spell);
+                    if fields.is_empty() {
+                        local_skipped.push(spell.name.clone());
+                    } else {
+                        local_conflicts.push(ImportConflict::Spell {
+                            existing: Box::new(existing_spell),
+                            incoming: Box::new(detail),
+                            fields,
+                            artifact: artifact_opt,
+                        });
                     }
+                    continue;
+                }
+                if skip_locked_overwrite(
+                    conn,
+                    id,
+                    &spell.name,
+                    force,
+                    &mut local_skipped,
+                    &mut local_warnings,
+                )? {
+                    continue;
+                }
+
+                let mut update = spell_update_from_import_spell(id, spell);
+                union_batch_tags(conn, &mut update, apply_tags)?;
+                let pending_write = apply_legacy_conflict_resolution_update(conn, &update)?;
+                local_vault_refresh.insert(
+                    pending_write.content_hash.clone(),
+                    pending_write.canonical_json.clone(),
+                );
+                (id, pending_write.content_hash)
+            } else {
+                conn.execute(
+                        "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
+                        material_components, casting_time, duration, area, saving_throw, damage,
+                        magic_resistance, reversible, description, tags, source, edition, author,
+                        license, is_quest_spell, is_cantrip, canonical_data, content_hash,
+                        schema_version)
+                        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                        params![
+                            spell.name, spell.school, spell.sphere, normalize_list_column(&spell.class_list), spell.level, spell.range, spell.components,
+                            spell.material_components, spell.casting_time, spell.duration, spell.area, spell.saving_throw,
+                            spell.damage, spell.magic_resistance,
+                            spell.reversible.unwrap_or(0),
+                            spell.description, normalize_list_column(&spell.tags), spell.source, spell.edition, spell.author, spell.license, spell.is_quest_spell, spell.is_cantrip,
+                            json, hash, canonical.schema_version
+                        ],
+                    )?;
+                let spell_id = conn.last_insert_rowid();
+                local_vault_refresh.insert(vault_hash.clone(), vault_json.clone());
+                (spell_id, vault_hash.clone())
+            };
+
+            flag_needs_review_with_conn(conn, spell_id, &canonical)?;
+            if let Some(levels) = spell.class_levels.as_ref() {
+                replace_class_spell_levels(conn, spell_id, levels)?;
+            }
+            migration_manager::sync_check_spell(conn, spell_id);
+            local_imported.push(SpellDetail {
+                id: Some(spell_id),
+                name: spell.name.clone(),
+                school: spell.school.clone(),
+                level: spell.level,
+                description: spell.description.clone(),
+                source: spell.source.clone(),
+                sphere: spell.sphere.clone(),
+                class_list: spell.class_list.clone(),
+                range: spell.range.clone(),
+                components: spell.components.clone(),
+                material_components: spell.material_components.clone(),
+                casting_time: spell.casting_time.clone(),
+                duration: spell.duration.clone(),
+                area: spell.area.clone(),
+                saving_throw: spell.saving_throw.clone(),
+                damage: spell.damage.clone(),
+                magic_resistance: spell.magic_resistance.clone(),
+                reversible: spell.reversible,
+                tags: spell.tags.clone(),
+                edition: spell.edition.clone(),
+                author: spell.author.clone(),
+                license: spell.license.clone(),
+                is_quest_spell: spell.is_quest_spell,
+                is_cantrip: spell.is_cantrip,
+                schema_version: spell.schema_version,
+                artifacts: None,
+                canonical_data: None,
+                content_hash: None,
+                ..Default::default()
+            });
+
+            let source_path = spell.source_file.clone();
+            let artifact_val = source_path
+                .as_ref()
+                .map(|p| normalize_key(p))
+                .and_then(|p| artifacts_by_path.get(&p))
+                .or_else(|| {
+                    artifacts_by_path
+                        .get(&spell_sources.get(i).cloned().flatten().unwrap_or_default())
+                });
+
+            if let Some(artifact_val) = artifact_val {
+                upsert_import_artifact(conn, spell_id, &current_content_hash, artifact_val)?;
+            }
+        }
+
+        if persist_conflicts {
+            enqueue_import_conflicts_with_conn(conn, &local_conflicts)?;
+        }
 
-                    Ok::<(ImportResult, Vec<PendingVaultSpellWrite>), AppError>((
-                        ImportResult {
-                            spells: local_imported,
-                            artifacts: serde_json::to_value(&parsed_artifacts)
-                                .unwrap_or_default()
-                                .as_array()
-                                .cloned()
-                                .unwrap_or_default(),
-                            conflicts: local_conflicts,
-                            warnings: vec![],
-                            skipped: local_skipped,
-                        },
-                        local_vault_refresh
-                            .into_iter()
-                            .map(|(content_hash, canonical_json)| PendingVaultSpellWrite {
-                                content_hash,
-                                canonical_json,
-                            })
-                            .collect(),
-                    ))
+        Ok::<(ImportResult, Vec<PendingVaultSpellWrite>), AppError>((
+            ImportResult {
+                spells: local_imported,
+                artifacts: serde_json::to_value(&parsed_artifacts)
+                    .unwrap_or_default()
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default(),
+                conflicts: local_conflicts,
+                warnings: local_warnings,
+                skipped: local_skipped,
+            },
+            local_vault_refresh
+                .into_iter()
+                .map(|(content_hash, canonical_json)| PendingVaultSpellWrite {
+                    content_hash,
+                    canonical_json,
                 })
+                .collect(),
+        ))
+    })
+}
+
+/// Applies already-parsed spells from the confirmation pass of `import_files`. Existing
+/// spells are overwritten only with `allow_overwrite` (and, when locked, `force`);
+/// otherwise they become conflicts, or are skipped when nothing differs.
+fn import_override_spells_with_conn(
+    conn: &rusqlite::Connection,
+    spells: &[ImportSpell],
+    allow_overwrite: bool,
+    force: bool,
+    apply_tags: &[String],
+    artifacts_by_path: &HashMap<String, ImportArtifact>,
+) -> Result<(ImportResult, Vec<PendingVaultSpellWrite>), AppError> {
+    let mut local_imported = vec![];
+    let mut local_skipped = vec![];
+    let mut local_warnings = vec![];
+    let mut local_conflicts = vec![];
+    let mut local_vault_refresh = HashMap::new();
+
+    for spell in spells {
+        let detail = SpellDetail {
+            id: None,
+            name: spell.name.clone(),
+            school: spell.school.clone(),
+            sphere: spell.sphere.clone(),
+            class_list: spell.class_list.clone(),
+            level: spell.level,
+            range: spell.range.clone(),
+            components: spell.components.clone(),
+            material_components: spell.material_components.clone(),
+            casting_time: spell.casting_time.clone(),
+            duration: spell.duration.clone(),
+            area: spell.area.clone(),
+            saving_throw: spell.saving_throw.clone(),
+            damage: spell.damage.clone(),
+            magic_resistance: spell.magic_resistance.clone(),
+            reversible: spell.reversible,
+            description: spell.description.clone(),
+            tags: spell.tags.clone(),
+            source: spell.source.clone(),
+            edition: spell.edition.clone(),
+            author: spell.author.clone(),
+            license: spell.license.clone(),
+            is_quest_spell: spell.is_quest_spell,
+            is_cantrip: spell.is_cantrip,
+            schema_version: spell.schema_version,
+            artifacts: None,
+            canonical_data: None,
+            content_hash: None,
+            ..Default::default()
+        };
+        let (canonical, hash, json) = canonicalize_import_spell(spell, detail.clone())?;
+        let vault_hash = hash.clone();
+        let vault_json = json.clone();
+
+        let existing_id: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM spell WHERE name = ? AND level = ? AND source IS ?",
+                params![spell.name, spell.level, spell.source],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let (spell_id, current_content_hash) = if let Some(id) = existing_id {
+            if !allow_overwrite {
+                let existing_spell = get_spell_from_conn(conn, id)?
+                    .ok_or_else(|| AppError::NotFound("Failed to fetch existing spell".into()))?;
+                let fields = build_conflict_fields(&existing_spell, spell);
+                if fields.is_empty() {
+                    local_skipped.push(spell.name.clone());
+                } else {
+                    let source_path = spell.source_file.clone();
+                    let artifact_opt = source_path
+                        .as_ref()
+                        .map(|p| normalize_key(p))
+                        .and_then(|p| artifacts_by_path.get(&p).cloned());
+                    local_conflicts.push(ImportConflict::Spell {
+                        existing: Box::new(existing_spell),
+                        incoming: Box::new(detail),
+                        fields,
+                        artifact: artifact_opt,
+                    });
+                }
+                continue;
+            }
+            if skip_locked_overwrite(
+                conn,
+                id,
+                &spell.name,
+                force,
+                &mut local_skipped,
+                &mut local_warnings,
+            )? {
+                continue;
+            }
+
+            let mut update = spell_update_from_import_spell(id, spell);
+            union_batch_tags(conn, &mut update, apply_tags)?;
+            let pending_write = apply_legacy_conflict_resolution_update(conn, &update)?;
+            local_vault_refresh.insert(
+                pending_write.content_hash.clone(),
+                pending_write.canonical_json.clone(),
+            );
+            (id, pending_write.content_hash)
+        } else {
+            conn.execute(
+                "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
+                material_components, casting_time, duration, area, saving_throw, damage,
+                magic_resistance, reversible, description, tags, source, edition, author,
+                license, is_quest_spell, is_cantrip, canonical_data, content_hash,
+                schema_version)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    spell.name, spell.school, spell.sphere, normalize_list_column(&spell.class_list), spell.level, spell.range, spell.components,
+                    spell.material_components, spell.casting_time, spell.duration, spell.area, spell.saving_throw,
+                    spell.damage, spell.magic_resistance,
+                    spell.reversible.unwrap_or(0),
+                    spell.description, normalize_list_column(&spell.tags), spell.source, spell.edition, spell.author, spell.license, spell.is_quest_spell, spell.is_cantrip,
+                    json, hash, canonical.schema_version
+                ],
+            )?;
+            let spell_id = conn.last_insert_rowid();
+            set_spell_reverse_name_with_conn(conn, spell_id, spell.reverse_name.as_deref())?;
+            local_vault_refresh.insert(vault_hash.clone(), vault_json.clone());
+            (spell_id, vault_hash.clone())
+        };
+
+        flag_needs_review_with_conn(conn, spell_id, &canonical)?;
+        if let Some(levels) = spell.class_levels.as_ref() {
+            replace_class_spell_levels(conn, spell_id, levels)?;
+        }
+        migration_manager::sync_check_spell(conn, spell_id);
+        local_imported.push(SpellDetail {
+            id: Some(spell_id),
+            name: spell.name.clone(),
+            school: spell.school.clone(),
+            level: spell.level,
+            description: spell.description.clone(),
+            source: spell.source.clone(),
+            sphere: spell.sphere.clone(),
+            class_list: spell.class_list.clone(),
+            range: spell.range.clone(),
+            components: spell.components.clone(),
+            material_components: spell.material_components.clone(),
+            casting_time: spell.casting_time.clone(),
+            duration: spell.duration.clone(),
+            area: spell.area.clone(),
+            saving_throw: spell.saving_throw.clone(),
+            damage: spell.damage.clone(),
+            magic_resistance: spell.magic_resistance.clone(),
+            reversible: spell.reversible,
+            tags: spell.tags.clone(),
+            edition: spell.edition.clone(),
+            author: spell.author.clone(),
+            license: spell.license.clone(),
+            is_quest_spell: spell.is_quest_spell,
+            is_cantrip: spell.is_cantrip,
+            schema_version: spell.schema_version,
+            artifacts: None,
+            canonical_data: None,
+            content_hash: None,
+            ..Default::default()
+        });
+
+        let source_path = spell.source_file.clone();
+        if let Some(p) = source_path {
+            if let Some(artifact_val) = artifacts_by_path.get(&normalize_key(&p)) {
+                upsert_import_artifact(conn, spell_id, &current_content_hash, artifact_val)?;
+            }
+        }
+    }
+
+    Ok((
+        ImportResult {
+            spells: local_imported,
+            artifacts: vec![],
+            conflicts: local_conflicts,
+            warnings: local_warnings,
+            skipped: local_skipped,
+        },
+        local_vault_refresh
+            .into_iter()
+            .map(|(content_hash, canonical_json)| PendingVaultSpellWrite {
+                content_hash,
+                canonical_json,
+            })
+            .collect(),
+    ))
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn import_files(
+    state: State<'_, Arc<Pool>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    files: Vec<ImportFile>,
+    allow_overwrite: bool,
+    spells: Option<Vec<ImportSpell>>,
+    artifacts: Option<Vec<ImportArtifact>>,
+    conflicts: Option<Vec<ImportConflict>>,
+    apply_tags: Option<Vec<String>>,
+    persist_conflicts: Option<bool>,
+    force: Option<bool>,
+) -> Result<ImportResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let apply_tags = apply_tags.unwrap_or_default();
+    let force = force.unwrap_or(false);
+    let persist_conflicts = persist_conflicts.unwrap_or(false);
+    let pool = state.inner().clone();
+    let gc_pool = pool.clone();
+    let maintenance_state = maintenance_state.inner().clone();
+    let import_guard = maintenance_state.start_import()?;
+    let result = async move {
+        let dir = app_data_dir()?.join("imports");
+        fs::create_dir_all(&dir)?;
+
+        let mut all_imported_spells = vec![];
+        let mut all_artifacts = vec![];
+        let mut all_conflicts = vec![];
+        let mut all_warnings = vec![];
+        let mut all_skipped = vec![];
+        let mut mutated_spell_count = 0usize;
+
+        // Pre-save all files to disk (keep this fast and simple)
+        let mut file_paths_map = HashMap::new();
+        let mut seen_names = HashMap::new();
+
+        for file in &files {
+            let (safe_name, changed) = sanitize_import_filename(&file.name);
+
+            if let Some(original) = seen_names.get(&safe_name) {
+                return Err(AppError::Validation(format!(
+                    "Filename collision: '{}' and '{}' both sanitize to '{}'",
+                    original, file.name, safe_name
+                )));
+            }
+            seen_names.insert(safe_name.clone(), file.name.clone());
+
+            if changed {
+                all_warnings.push(format!(
+                    "Sanitized import file name '{}' to '{}'.",
+                    file.name, safe_name
+                ));
+            }
+            let path = dir.join(&safe_name);
+            fs::write(&path, &file.content)?;
+            file_paths_map.insert(file.name.clone(), path);
+        }
+
+        // Branch Process:
+        // 1. Initial Import (needs_parsing=true): Chunk files -> Sidecar -> DB
+        // 2. Confirmation (needs_parsing=false): Chunk spells (overrides) -> DB
+
+        let needs_parsing = spells.is_none();
+
+        if needs_parsing {
+            // --- PATH A: INITIAL IMPORT (Sidecar -> DB) ---
+            for chunk in files.chunks(IMPORT_BATCH_SIZE) {
+                let chunk_paths: Vec<PathBuf> = chunk
+                    .iter()
+                    .filter_map(|f| file_paths_map.get(&f.name).cloned())
+                    .collect();
+
+                if chunk_paths.is_empty() {
+                    continue;
+                }
 
-            }).await.map_err(|e| AppError::Unknown(e.to_string()))??;
+                let result = import_file_chunk(
+                    pool.clone(),
+                    chunk_paths,
+                    allow_overwrite,
+                    force,
+                    &apply_tags,
+                    persist_conflicts,
+                )
+                .await?;
 
                 mutated_spell_count += result.spells.len();
                 all_imported_spells.extend(result.spells);
                 all_conflicts.extend(result.conflicts);
+                all_warnings.extend(result.warnings);
                 all_skipped.extend(result.skipped);
                 all_artifacts.extend(result.artifacts);
             }
         } else {
             // --- PATH B: CONFIRMATION (Offsets provided) ---
-            let override_spells = spells.unwrap_or_default();
+            let mut override_spells = spells.unwrap_or_default();
+            apply_import_tags(&mut override_spells, &apply_tags);
+            extract_class_levels(&mut override_spells);
             let override_artifacts = artifacts.unwrap_or_default();
             let override_conflicts = conflicts.unwrap_or_default();
 
@@ -2336,192 +3668,65 @@ pub async fn import_files(
                 artifacts_by_path.insert(normalize_key(&artifact.path), artifact.clone());
             }
 
+            // Conflicts carried over from the initial pass are queued with the first chunk.
+            let mut carried_conflicts = if persist_conflicts {
+                override_conflicts.clone()
+            } else {
+                vec![]
+            };
             all_conflicts = override_conflicts;
 
             // Batch the spells
-            for chunk in override_spells.chunks(BATCH_SIZE) {
+            for chunk in override_spells.chunks(IMPORT_BATCH_SIZE) {
                 let pool = pool.clone();
                 let chunk_spells = chunk.to_vec();
                 let allow_overwrite_clone = allow_overwrite;
                 let artifacts_map_clone = artifacts_by_path.clone();
+                let apply_tags = apply_tags.clone();
+                let carried = std::mem::take(&mut carried_conflicts);
 
                 let result = tokio::task::spawn_blocking(move || {
-                let conn = pool.get()?;
-                let root = app_data_dir()?;
-                run_legacy_import_chunk_with_vault_writes(&conn, &root, |conn| {
-                    let mut local_imported = vec![];
-                    let mut local_skipped = vec![];
-                    let mut local_conflicts = vec![];
-                    let mut local_vault_refresh = HashMap::new();
-
-                    for spell in chunk_spells.iter() {
-                        let detail = SpellDetail {
-                            id: None,
-                            name: spell.name.clone(),
-                            school: spell.school.clone(),
-                            sphere: spell.sphere.clone(),
-                            class_list: spell.class_list.clone(),
-                            level: spell.level,
-                            range: spell.range.clone(),
-                            components: spell.components.clone(),
-                            material_components: spell.material_components.clone(),
-                            casting_time: spell.casting_time.clone(),
-                            duration: spell.duration.clone(),
-                            area: spell.area.clone(),
-                            saving_throw: spell.saving_throw.clone(),
-                            damage: spell.damage.clone(),
-                            magic_resistance: spell.magic_resistance.clone(),
-                            reversible: spell.reversible,
-                            description: spell.description.clone(),
-                            tags: spell.tags.clone(),
-                            source: spell.source.clone(),
-                            edition: spell.edition.clone(),
-                            author: spell.author.clone(),
-                            license: spell.license.clone(),
-                            is_quest_spell: spell.is_quest_spell,
-                            is_cantrip: spell.is_cantrip,
-                            schema_version: spell.schema_version,
-                            artifacts: None,
-                            canonical_data: None,
-                            content_hash: None,
-                            ..Default::default()
-                        };
-                        let (canonical, hash, json) = canonicalize_spell_detail(detail.clone())?;
-                        let vault_hash = hash.clone();
-                        let vault_json = json.clone();
-
-                        let existing_id: Option<i64> = conn.query_row(
-                            "SELECT id FROM spell WHERE name = ? AND level = ? AND source IS ?",
-                            params![spell.name, spell.level, spell.source],
-                            |row| row.get(0),
-                        ).optional()?;
-
-                        let (spell_id, current_content_hash) = if let Some(id) = existing_id {
-                            if !allow_overwrite_clone {
-                                let existing_spell = get_spell_from_conn(conn, id)?
-                                    .ok_or_else(|| AppError::NotFound("Failed to fetch existing spell".into()))?;
-                                let fields = build_conflict_fields(&existing_spell, spell);
-                                if fields.is_empty() {
-                                    local_skipped.push(spell.name.clone());
-                                } else {
-                                    let source_path = spell.source_file.clone();
-                                    let artifact_opt = source_path
-                                        .as_ref()
-                                        .map(|p| normalize_key(p))
-                                        .and_then(|p| artifacts_map_clone.get(&p).cloned());
-                                    local_conflicts.push(ImportConflict::Spell {
-                                        existing: Box::new(existing_spell),
-                                        incoming: Box::new(detail),
-                                        fields,
-                                        artifact: artifact_opt,
-                                    });
-                                }
-                                continue;
-                            }
-
-                            let update = spell_update_from_import_spell(id, spell);
-                            let pending_write =
-                                apply_legacy_conflict_resolution_update(conn, &update)?;
-                            local_vault_refresh.insert(
-                                pending_write.content_hash.clone(),
-                                pending_write.canonical_json.clone(),
-                            );
-                            (id, pending_write.content_hash)
-                        } else {
-                            conn.execute(
-                                "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
-                                material_components, casting_time, duration, area, saving_throw, damage,
-                                magic_resistance, reversible, description, tags, source, edition, author,
-                                license, is_quest_spell, is_cantrip, canonical_data, content_hash,
-                                schema_version)
-                                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-                                params![
-                                    spell.name, spell.school, spell.sphere, spell.class_list, spell.level, spell.range, spell.components,
-                                    spell.material_components, spell.casting_time, spell.duration, spell.area, spell.saving_throw,
-                                    spell.damage, spell.magic_resistance,
-                                    spell.reversible.unwrap_or(0),
-                                    spell.description, spell.tags, spell.source, spell.edition, spell.author, spell.license, spell.is_quest_spell, spell.is_cantrip,
-                                    json, hash, canonical.schema_version
-                                ],
-                            )?;
-                            let spell_id = conn.last_insert_rowid();
-                            local_vault_refresh.insert(vault_hash.clone(), vault_json.clone());
-                            (spell_id, vault_hash.clone())
-                        };
-
-                        migration_manager::sync_check_spell(conn, spell_id);
-                        local_imported.push(SpellDetail {
-                            id: Some(spell_id),
-                            name: spell.name.clone(),
-                            school: spell.school.clone(),
-                            level: spell.level,
-                            description: spell.description.clone(),
-                            source: spell.source.clone(),
-                            sphere: spell.sphere.clone(),
-                            class_list: spell.class_list.clone(),
-                            range: spell.range.clone(),
-                            components: spell.components.clone(),
-                            material_components: spell.material_components.clone(),
-                            casting_time: spell.casting_time.clone(),
-                            duration: spell.duration.clone(),
-                            area: spell.area.clone(),
-                            saving_throw: spell.saving_throw.clone(),
-                            damage: spell.damage.clone(),
-                            magic_resistance: spell.magic_resistance.clone(),
-                            reversible: spell.reversible,
-                            tags: spell.tags.clone(),
-                            edition: spell.edition.clone(),
-                            author: spell.author.clone(),
-                            license: spell.license.clone(),
-                            is_quest_spell: spell.is_quest_spell,
-                            is_cantrip: spell.is_cantrip,
-                            schema_version: spell.schema_version,
-                            artifacts: None,
-                            canonical_data: None,
-                            content_hash: None,
-                            ..Default::default()
-                        });
-
-                        let source_path = spell.source_file.clone();
-                        if let Some(p) = source_path {
-                            if let Some(artifact_val) = artifacts_map_clone.get(&normalize_key(&p)) {
-                                upsert_import_artifact(
-                                    conn,
-                                    spell_id,
-                                    &current_content_hash,
-                                    artifact_val,
-                                )?;
-                            }
+                    let conn = pool.get()?;
+                    let root = app_data_dir()?;
+                    run_legacy_import_chunk_with_vault_writes(&conn, &root, |conn| {
+                        let (result, writes) = import_override_spells_with_conn(
+                            conn,
+                            &chunk_spells,
+                            allow_overwrite_clone,
+                            force,
+                            &apply_tags,
+                            &artifacts_map_clone,
+                        )?;
+                        if persist_conflicts {
+                            enqueue_import_conflicts_with_conn(conn, &carried)?;
+                            enqueue_import_conflicts_with_conn(conn, &result.conflicts)?;
                         }
-                    }
-
-                    Ok::<(ImportResult, Vec<PendingVaultSpellWrite>), AppError>((
-                        ImportResult {
-                            spells: local_imported,
-                            artifacts: vec![],
-                            conflicts: local_conflicts,
-                            warnings: vec![],
-                            skipped: local_skipped,
-                        },
-                        local_vault_refresh
-                            .into_iter()
-                            .map(|(content_hash, canonical_json)| PendingVaultSpellWrite {
-                                content_hash,
-                                canonical_json,
-                            })
-                            .collect(),
-
-                    ))
+                        Ok((result, writes))
+                    })
                 })
-
-                }).await.map_err(|e| AppError::Unknown(e.to_string()))??;
+                .await
+                .map_err(|e| AppError::Unknown(e.to_string()))??;
 
                 mutated_spell_count += result.spells.len();
                 all_imported_spells.extend(result.spells);
                 all_conflicts.extend(result.conflicts);
+                all_warnings.extend(result.warnings);
                 all_skipped.extend(result.skipped);
             }
 
+            if !carried_conflicts.is_empty() {
+                let pool = pool.clone();
+                tokio::task::spawn_blocking(move || {
+                    let mut conn = pool.get()?;
+                    let tx = conn.transaction()?;
+                    enqueue_import_conflicts_with_conn(&tx, &carried_conflicts)?;
+                    tx.commit()?;
+                    Ok::<(), AppError>(())
+                })
+                .await
+                .map_err(|e| AppError::Unknown(e.to_string()))??;
+            }
+
             all_artifacts = serde_json::to_value(override_artifacts)
                 .unwrap_or_default()
                 .as_array()
@@ -2529,13 +3734,16 @@ pub async fn import_files(
                 .unwrap_or_default();
         }
 
-        Ok((ImportResult {
-            spells: all_imported_spells,
-            artifacts: all_artifacts,
-            conflicts: all_conflicts,
-            warnings: all_warnings,
-            skipped: all_skipped,
-        }, mutated_spell_count))
+        Ok((
+            ImportResult {
+                spells: all_imported_spells,
+                artifacts: all_artifacts,
+                conflicts: all_conflicts,
+                warnings: all_warnings,
+                skipped: all_skipped,
+            },
+            mutated_spell_count,
+        ))
     }
     .await;
 
@@ -2561,51 +3769,465 @@ pub async fn import_files(
     Ok(result)
 }
 
-#[tauri::command]
-pub async fn resolve_import_conflicts(
-    state: State<'_, Arc<Pool>>,
-    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
-    resolutions: Vec<ImportConflictResolution>,
-) -> Result<ResolveImportResult, AppError> {
-    let pool = state.inner().clone();
-    let gc_pool = pool.clone();
-    let maintenance_state = maintenance_state.inner().clone();
-    let import_guard = maintenance_state.start_import()?;
-    let result = tokio::task::spawn_blocking(move || {
-        let conn = pool.get()?;
-        let root = app_data_dir()?;
-        resolve_import_conflicts_with_conn_and_root(&conn, &root, resolutions)
-    })
-    .await
-    .map_err(|e| AppError::Unknown(e.to_string()))??;
-
-    if result.resolved.is_empty() {
-        drop(import_guard);
-        return Ok(result);
+fn import_spell_to_detail(spell: &ImportSpell, id: Option<i64>) -> SpellDetail {
+    SpellDetail {
+        id,
+        name: spell.name.clone(),
+        school: spell.school.clone(),
+        sphere: spell.sphere.clone(),
+        class_list: spell.class_list.clone(),
+        level: spell.level,
+        range: spell.range.clone(),
+        components: spell.components.clone(),
+        material_components: spell.material_components.clone(),
+        casting_time: spell.casting_time.clone(),
+        duration: spell.duration.clone(),
+        area: spell.area.clone(),
+        saving_throw: spell.saving_throw.clone(),
+        damage: spell.damage.clone(),
+        magic_resistance: spell.magic_resistance.clone(),
+        reversible: spell.reversible,
+        reverse_name: spell.reverse_name.clone(),
+        description: spell.description.clone(),
+        tags: spell.tags.clone(),
+        source: spell.source.clone(),
+        edition: spell.edition.clone(),
+        author: spell.author.clone(),
+        license: spell.license.clone(),
+        is_quest_spell: spell.is_quest_spell,
+        is_cantrip: spell.is_cantrip,
+        schema_version: spell.schema_version,
+        ..Default::default()
     }
+}
 
-    let changed_count = result.resolved.len();
-    let _gc_guard = import_guard.into_gc_guard()?;
-    tokio::task::spawn_blocking(move || {
-        let conn = gc_pool.get()?;
-        let root = app_data_dir()?;
-        run_post_import_gc_if_needed(&conn, &root, changed_count)
-    })
-    .await
-    .map_err(|e| AppError::Unknown(e.to_string()))??;
-
-    Ok(result)
+/// Canonicalizes an incoming spell. A native JSON import carries its `canonical_data`;
+/// while the text columns still match it, that form is kept (with the incoming tags) so
+/// structured fields survive. Otherwise `detail` is canonicalized from the text columns.
+fn canonicalize_import_spell(
+    spell: &ImportSpell,
+    detail: SpellDetail,
+) -> Result<(CanonicalSpell, String, String), AppError> {
+    let carried = spell
+        .canonical_data
+        .as_deref()
+        .and_then(|data| serde_json::from_str::<CanonicalSpell>(data).ok());
+    if let Some(mut canonical) = carried {
+        let stored = canonical_spell_to_import_spell(&canonical);
+        let unchanged = stored.name == spell.name
+            && stored.level == spell.level
+            && stored.school == spell.school
+            && stored.sphere == spell.sphere
+            && normalize_list_column(&stored.class_list)
+                == normalize_list_column(&spell.class_list)
+            && stored.range == spell.range
+            && stored.components == spell.components
+            && stored.material_components == spell.material_components
+            && stored.casting_time == spell.casting_time
+            && stored.duration == spell.duration
+            && stored.area == spell.area
+            && stored.saving_throw == spell.saving_throw
+            && stored.damage == spell.damage
+            && stored.magic_resistance == spell.magic_resistance
+            && stored.reversible.unwrap_or(0) == spell.reversible.unwrap_or(0)
+            && stored.description == spell.description
+            && stored.source == spell.source
+            && stored.edition == spell.edition
+            && stored.author == spell.author
+            && stored.license == spell.license
+            && stored.is_quest_spell == spell.is_quest_spell
+            && stored.is_cantrip == spell.is_cantrip;
+        if unchanged {
+            canonical.tags = spell
+                .tags
+                .as_deref()
+                .map(parse_list_column)
+                .unwrap_or_default();
+            return finalize_canonical_spell(canonical);
+        }
+    }
+    canonicalize_spell_detail(detail)
 }
 
-/// Resolves the spell ID and artifact path for a given artifact row.
-///
-/// Uses `artifact.spell_content_hash` as the primary identifier (hash-first) and falls back
-/// to `artifact.spell_id` during the migration period before `spell_id` is officially dropped.
-///
-/// Returns `AppError::NotFound` when neither column resolves to a live spell.
-fn resolve_artifact_spell_id(
+/// Applies a reviewed import plan. Every item runs against the caller's transaction, so an
+/// `Update` whose target no longer exists aborts the whole plan instead of half-applying it.
+/// An `Update` of a locked spell (without `force`) and an `Insert` whose content already
+/// exists are skipped with a warning.
+fn apply_import_plan_with_conn(
     conn: &rusqlite::Connection,
-    artifact_id: i64,
+    plan: &[ImportPlanItem],
+    force: bool,
+) -> Result<(ImportResult, Vec<PendingVaultSpellWrite>), AppError> {
+    let mut imported = vec![];
+    let mut skipped = vec![];
+    let mut warnings = vec![];
+    let mut vault_writes = HashMap::new();
+
+    for item in plan {
+        let spell = &item.spell;
+        let (spell_id, content_hash, canonical) = match item.action {
+            ImportPlanAction::Skip => {
+                skipped.push(spell.name.clone());
+                continue;
+            }
+            ImportPlanAction::Update => {
+                let target_id = match item.existing_id {
+                    Some(id) => Some(id),
+                    None => conn
+                        .query_row(
+                            "SELECT id FROM spell WHERE name = ? AND level = ? AND source IS ?",
+                            params![spell.name, spell.level, spell.source],
+                            |row| row.get(0),
+                        )
+                        .optional()?,
+                };
+                let id = target_id.ok_or_else(|| {
+                    AppError::NotFound(format!("No existing spell to update for '{}'", spell.name))
+                })?;
+                if skip_locked_overwrite(conn, id, &spell.name, force, &mut skipped, &mut warnings)?
+                {
+                    continue;
+                }
+                let update = spell_update_from_import_spell(id, spell);
+                let pending_write = apply_legacy_conflict_resolution_update(conn, &update)?;
+                let (canonical, _, _) =
+                    canonicalize_spell_detail(import_spell_to_detail(spell, Some(id)))?;
+                vault_writes.insert(
+                    pending_write.content_hash.clone(),
+                    pending_write.canonical_json,
+                );
+                (id, pending_write.content_hash, canonical)
+            }
+            ImportPlanAction::Insert => {
+                let (canonical, hash, json) =
+                    canonicalize_import_spell(spell, import_spell_to_detail(spell, None))?;
+                let duplicate: Option<String> = conn
+                    .query_row(
+                        "SELECT name FROM spell WHERE content_hash = ?",
+                        [&hash],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                if let Some(existing_name) = duplicate {
+                    skipped.push(spell.name.clone());
+                    warnings.push(format!(
+                        "Skipped '{}': identical content already exists as '{}'.",
+                        spell.name, existing_name
+                    ));
+                    continue;
+                }
+                conn.execute(
+                    "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
+                    material_components, casting_time, duration, area, saving_throw, damage,
+                    magic_resistance, reversible, description, tags, source, edition, author,
+                    license, is_quest_spell, is_cantrip, canonical_data, content_hash,
+                    schema_version)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    params![
+                        spell.name,
+                        spell.school,
+                        spell.sphere,
+                        normalize_list_column(&spell.class_list),
+                        spell.level,
+                        spell.range,
+                        spell.components,
+                        spell.material_components,
+                        spell.casting_time,
+                        spell.duration,
+                        spell.area,
+                        spell.saving_throw,
+                        spell.damage,
+                        spell.magic_resistance,
+                        spell.reversible.unwrap_or(0),
+                        spell.description,
+                        normalize_list_column(&spell.tags),
+                        spell.source,
+                        spell.edition,
+                        spell.author,
+                        spell.license,
+                        spell.is_quest_spell,
+                        spell.is_cantrip,
+                        json,
+                        hash,
+                        canonical.schema_version
+                    ],
+                )?;
+                let id = conn.last_insert_rowid();
+                set_spell_reverse_name_with_conn(conn, id, spell.reverse_name.as_deref())?;
+                vault_writes.insert(hash.clone(), json);
+                (id, hash, canonical)
+            }
+        };
+
+        flag_needs_review_with_conn(conn, spell_id, &canonical)?;
+        if let Some(levels) = spell.class_levels.as_ref() {
+            replace_class_spell_levels(conn, spell_id, levels)?;
+        }
+        migration_manager::sync_check_spell(conn, spell_id);
+        if let Some(artifact) = item.artifact.as_ref() {
+            upsert_import_artifact(conn, spell_id, &content_hash, artifact)?;
+        }
+        imported.push(import_spell_to_detail(spell, Some(spell_id)));
+    }
+
+    let artifacts = plan
+        .iter()
+        .filter(|item| item.action != ImportPlanAction::Skip)
+        .filter_map(|item| item.artifact.as_ref())
+        .filter_map(|artifact| serde_json::to_value(artifact).ok())
+        .collect();
+
+    Ok((
+        ImportResult {
+            spells: imported,
+            artifacts,
+            conflicts: vec![],
+            warnings,
+            skipped,
+        },
+        vault_writes
+            .into_iter()
+            .map(|(content_hash, canonical_json)| PendingVaultSpellWrite {
+                content_hash,
+                canonical_json,
+            })
+            .collect(),
+    ))
+}
+
+/// Applies a user-approved import plan (insert, update or skip per spell) in one transaction.
+#[tauri::command]
+pub async fn apply_import_plan(
+    state: State<'_, Arc<Pool>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    plan: Vec<ImportPlanItem>,
+    force: Option<bool>,
+) -> Result<ImportResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    let gc_pool = pool.clone();
+    let maintenance_state = maintenance_state.inner().clone();
+    let import_guard = maintenance_state.start_import()?;
+
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let root = app_data_dir()?;
+        run_legacy_import_chunk_with_vault_writes(&conn, &root, |conn| {
+            apply_import_plan_with_conn(conn, &plan, force.unwrap_or(false))
+        })
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    let changed_count = result.spells.len();
+    if changed_count == 0 {
+        drop(import_guard);
+        return Ok(result);
+    }
+
+    let _gc_guard = import_guard.into_gc_guard()?;
+    tokio::task::spawn_blocking(move || {
+        let conn = gc_pool.get()?;
+        let root = app_data_dir()?;
+        run_post_import_gc_if_needed(&conn, &root, changed_count)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+/// Walks `dir` for files the sidecar can parse (see `LEGACY_IMPORT_EXTENSIONS`), sorted
+/// by path. Files larger than `max_file_bytes` or unreadable entries are skipped with a
+/// warning. `dir` must be an existing directory.
+fn collect_import_directory_files(
+    dir: &std::path::Path,
+    recursive: bool,
+    max_file_bytes: u64,
+) -> Result<(Vec<PathBuf>, Vec<String>), AppError> {
+    if !dir.is_dir() {
+        return Err(AppError::Validation(format!(
+            "Import path is not an existing directory: {}",
+            dir.display()
+        )));
+    }
+
+    let mut walker = walkdir::WalkDir::new(dir).min_depth(1).sort_by_file_name();
+    if !recursive {
+        walker = walker.max_depth(1);
+    }
+
+    let mut paths = vec![];
+    let mut warnings = vec![];
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                warnings.push(format!("Skipped unreadable entry: {}", e));
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let allowed = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| LEGACY_IMPORT_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+        if !allowed {
+            continue;
+        }
+        match entry.metadata() {
+            Ok(meta) if meta.len() > max_file_bytes => warnings.push(format!(
+                "Skipped '{}': {} bytes exceeds the {} byte import limit.",
+                path.display(),
+                meta.len(),
+                max_file_bytes
+            )),
+            Ok(_) => paths.push(path.to_path_buf()),
+            Err(e) => warnings.push(format!("Skipped '{}': {}", path.display(), e)),
+        }
+    }
+
+    Ok((paths, warnings))
+}
+
+/// Body of [`import_directory`]. `parse` turns one chunk of paths into sidecar `import` output
+/// (`parse_import_files` in the command) and each chunk is applied in its own transaction;
+/// the import guard is held throughout and vault GC runs once at the end.
+fn import_directory_with_conn(
+    conn: &rusqlite::Connection,
+    root: &std::path::Path,
+    maintenance_state: &VaultMaintenanceState,
+    dir: &std::path::Path,
+    recursive: bool,
+    mut parse: impl FnMut(&[PathBuf]) -> Result<serde_json::Value, AppError>,
+) -> Result<ImportResult, AppError> {
+    let import_guard = maintenance_state.start_import()?;
+    let (paths, warnings) =
+        collect_import_directory_files(dir, recursive, MAX_IMPORT_PAYLOAD_BYTES as u64)?;
+
+    let mut result = ImportResult {
+        spells: vec![],
+        artifacts: vec![],
+        conflicts: vec![],
+        warnings,
+        skipped: vec![],
+    };
+    for chunk in paths.chunks(IMPORT_BATCH_SIZE) {
+        let parsed = parse(chunk)?;
+        let chunk_result =
+            apply_import_file_chunk_with_conn(conn, root, parsed, false, false, &[], false)?;
+        result.spells.extend(chunk_result.spells);
+        result.artifacts.extend(chunk_result.artifacts);
+        result.conflicts.extend(chunk_result.conflicts);
+        result.warnings.extend(chunk_result.warnings);
+        result.skipped.extend(chunk_result.skipped);
+    }
+
+    let changed_count = result.spells.len();
+    if changed_count == 0 {
+        return Ok(result);
+    }
+
+    let _gc_guard = import_guard.into_gc_guard()?;
+    run_post_import_gc_if_needed(conn, root, changed_count)?;
+    Ok(result)
+}
+
+/// Imports every supported file under a local directory. Paths go straight to the
+/// sidecar, so file bytes never cross the IPC bridge or get copied into `imports/`.
+/// Existing spells are reported as conflicts, as with `import_files`.
+#[tauri::command]
+pub async fn import_directory(
+    state: State<'_, Arc<Pool>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    path: String,
+    recursive: bool,
+) -> Result<ImportResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    let maintenance_state = maintenance_state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let root = app_data_dir()?;
+        import_directory_with_conn(
+            &conn,
+            &root,
+            maintenance_state.as_ref(),
+            std::path::Path::new(&path),
+            recursive,
+            |chunk| tauri::async_runtime::block_on(parse_import_files(chunk)),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Conflicts queued by `import_files(persist_conflicts: true)` that are still unresolved.
+#[tauri::command]
+pub async fn list_pending_conflicts(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<QueuedImportConflict>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        list_pending_conflicts_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn resolve_import_conflicts(
+    state: State<'_, Arc<Pool>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    resolutions: Vec<ImportConflictResolution>,
+) -> Result<ResolveImportResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    let gc_pool = pool.clone();
+    let maintenance_state = maintenance_state.inner().clone();
+    let import_guard = maintenance_state.start_import()?;
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let root = app_data_dir()?;
+        resolve_import_conflicts_with_conn_and_root(&conn, &root, resolutions)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    if result.resolved.is_empty() {
+        drop(import_guard);
+        return Ok(result);
+    }
+
+    let changed_count = result.resolved.len();
+    let _gc_guard = import_guard.into_gc_guard()?;
+    tokio::task::spawn_blocking(move || {
+        let conn = gc_pool.get()?;
+        let root = app_data_dir()?;
+        run_post_import_gc_if_needed(&conn, &root, changed_count)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+/// Resolves the spell ID and artifact path for a given artifact row.
+///
+/// Uses `artifact.spell_content_hash` as the primary identifier (hash-first) and falls back
+/// to `artifact.spell_id` during the migration period before `spell_id` is officially dropped.
+///
+/// Returns `AppError::NotFound` when neither column resolves to a live spell.
+fn resolve_artifact_spell_id(
+    conn: &rusqlite::Connection,
+    artifact_id: i64,
 ) -> Result<(i64, String), AppError> {
     let has_hash_col = crate::db::table_has_column(conn, "artifact", "spell_content_hash");
 
@@ -2652,18 +4274,145 @@ fn resolve_artifact_spell_id(
     Ok((spell_id, path))
 }
 
+/// Artifact ids linked to `spell_id`, primary first then by id. Uses the same hash-first /
+/// legacy `spell_id` linkage as artifact loading in `get_spell_from_conn`.
+fn spell_artifact_ids_primary_first(
+    conn: &rusqlite::Connection,
+    spell_id: i64,
+) -> Result<Vec<i64>, AppError> {
+    let order = if crate::db::table_has_column(conn, "artifact", "is_primary") {
+        "ORDER BY is_primary DESC, id"
+    } else {
+        "ORDER BY id"
+    };
+    let ids = if crate::db::table_has_column(conn, "artifact", "spell_content_hash") {
+        let content_hash: Option<String> = conn
+            .query_row(
+                "SELECT content_hash FROM spell WHERE id = ?",
+                [spell_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id FROM artifact
+             WHERE (spell_content_hash IS NOT NULL AND spell_content_hash = ?1)
+                OR (spell_content_hash IS NULL AND spell_id = ?2)
+             {order}"
+        ))?;
+        let rows = stmt.query_map(params![content_hash, spell_id], |row| row.get(0))?;
+        rows.collect::<Result<Vec<i64>, _>>()?
+    } else {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id FROM artifact WHERE spell_id = ? {order}"
+        ))?;
+        let rows = stmt.query_map([spell_id], |row| row.get(0))?;
+        rows.collect::<Result<Vec<i64>, _>>()?
+    };
+    Ok(ids)
+}
+
+/// The artifact to reparse for a spell: its primary artifact, else its oldest one.
+fn primary_artifact_for_spell(conn: &rusqlite::Connection, spell_id: i64) -> Result<i64, AppError> {
+    spell_artifact_ids_primary_first(conn, spell_id)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound(format!("Spell {spell_id} has no artifacts to reparse")))
+}
+
+/// Picks the artifact for a single reparse. An explicit `artifact_id` wins; otherwise
+/// the primary artifact of `spell_id` is used.
+fn resolve_reparse_artifact_id(
+    conn: &rusqlite::Connection,
+    artifact_id: Option<i64>,
+    spell_id: Option<i64>,
+) -> Result<i64, AppError> {
+    match (artifact_id, spell_id) {
+        (Some(artifact_id), _) => Ok(artifact_id),
+        (None, Some(spell_id)) => primary_artifact_for_spell(conn, spell_id),
+        (None, None) => Err(AppError::Validation(
+            "reparse_artifact requires an artifact id or a spell id".into(),
+        )),
+    }
+}
+
+/// Marks `artifact_id` as the primary artifact of its spell and clears the flag on the
+/// spell's other artifacts, in one transaction.
+pub(crate) fn set_primary_artifact_with_conn(
+    conn: &mut rusqlite::Connection,
+    artifact_id: i64,
+) -> Result<(), AppError> {
+    let (spell_id, _) = resolve_artifact_spell_id(conn, artifact_id).map_err(|e| match e {
+        AppError::Database(rusqlite::Error::QueryReturnedNoRows) => {
+            AppError::NotFound(format!("Artifact {artifact_id} not found"))
+        }
+        other => other,
+    })?;
+    let sibling_ids = spell_artifact_ids_primary_first(conn, spell_id)?;
+
+    let tx = conn.transaction()?;
+    for id in sibling_ids.iter().filter(|&&id| id != artifact_id) {
+        tx.execute("UPDATE artifact SET is_primary = 0 WHERE id = ?", [id])?;
+    }
+    tx.execute(
+        "UPDATE artifact SET is_primary = 1 WHERE id = ?",
+        [artifact_id],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
 #[tauri::command]
-pub async fn reparse_artifact(
+pub async fn set_primary_artifact(
     state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
     artifact_id: i64,
-) -> Result<SpellDetail, AppError> {
+) -> Result<(), AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get()?;
+        set_primary_artifact_with_conn(&mut conn, artifact_id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(())
+}
+
+fn locked_reparse_error(spell_id: i64) -> AppError {
+    AppError::Validation(format!(
+        "Spell {spell_id} is locked; unlock it or reparse with force to overwrite it"
+    ))
+}
+
+/// Reparses one artifact. Pass `artifact_id` to target a specific file, or `spell_id`
+/// to reparse that spell from its primary artifact. Locked spells are refused unless
+/// `force` is set.
+#[tauri::command]
+pub async fn reparse_artifact(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    artifact_id: Option<i64>,
+    spell_id: Option<i64>,
+    preserve_edited: Option<bool>,
+    force: Option<bool>,
+) -> Result<ReparseResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let preserve_edited = preserve_edited.unwrap_or(true);
+    let force = force.unwrap_or(false);
     let pool = state.inner().clone();
 
-    let (spell_id, artifact_path) = {
+    let (artifact_id, spell_id, artifact_path) = {
         let pool = pool.clone();
         tokio::task::spawn_blocking(move || {
             let conn = pool.get()?;
-            resolve_artifact_spell_id(&conn, artifact_id)
+            let artifact_id = resolve_reparse_artifact_id(&conn, artifact_id, spell_id)?;
+            let (spell_id, path) = resolve_artifact_spell_id(&conn, artifact_id)?;
+            if !force && is_spell_locked_with_conn(&conn, spell_id)? {
+                return Err(locked_reparse_error(spell_id));
+            }
+            Ok::<(i64, i64, String), AppError>((artifact_id, spell_id, path))
         })
         .await
         .map_err(|e| AppError::Unknown(e.to_string()))??
@@ -2692,81 +4441,485 @@ pub async fn reparse_artifact(
         })?
     };
 
-    let result = call_sidecar("import", json!({"files": [artifact_path]})).await?;
-    let spells: Vec<SpellDetail> =
-        serde_json::from_value(result.get("spells").cloned().unwrap_or(json!([])))
-            .map_err(|e| AppError::Sidecar(format!("Failed to parse sidecar response: {}", e)))?;
-
-    let parsed_spell = spells
-        .into_iter()
-        .next()
-        .ok_or_else(|| AppError::Sidecar("Sidecar did not return any parsed spells".to_string()))?;
+    let result = parse_import_files(&[PathBuf::from(&artifact_path)]).await?;
+    let parsed_spell = parsed_spells_by_path(&result)
+        .remove(&normalize_key(&artifact_path))
+        .ok_or_else(|| AppError::Sidecar("Sidecar did not return any parsed spells".to_string()))?
+        .map_err(AppError::Sidecar)?;
 
     let pool = pool.clone();
-    let updated_spell = tokio::task::spawn_blocking(move || {
+    let result = tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-
-        let update_for_diff = SpellUpdate {
-            id: spell_id,
-            name: parsed_spell.name.clone(),
-            school: parsed_spell.school.clone(),
-            sphere: parsed_spell.sphere.clone(),
-            class_list: parsed_spell.class_list.clone(),
-            level: parsed_spell.level,
-            range: parsed_spell.range.clone(),
-            components: parsed_spell.components.clone(),
-            material_components: parsed_spell.material_components.clone(),
-            casting_time: parsed_spell.casting_time.clone(),
-            duration: parsed_spell.duration.clone(),
-            area: parsed_spell.area.clone(),
-            saving_throw: parsed_spell.saving_throw.clone(),
-            damage: parsed_spell.damage.clone(),
-            magic_resistance: parsed_spell.magic_resistance.clone(),
-            reversible: parsed_spell.reversible,
-            description: parsed_spell.description.clone(),
-            tags: parsed_spell.tags.clone(),
-            source: parsed_spell.source.clone(),
-            edition: parsed_spell.edition.clone(),
-            author: parsed_spell.author.clone(),
-            license: parsed_spell.license.clone(),
-            is_quest_spell: parsed_spell.is_quest_spell,
-            is_cantrip: parsed_spell.is_cantrip,
-            ..Default::default()
-        };
-
-        apply_spell_update_with_conn(&conn, &update_for_diff)?;
-
-        conn.execute(
-            "UPDATE artifact SET imported_at = ? WHERE id = ?",
-            params![Utc::now().to_rfc3339(), artifact_id],
+        let changes = apply_artifact_reparse_with_conn(
+            &conn,
+            artifact_id,
+            spell_id,
+            &parsed_spell,
+            preserve_edited,
         )?;
-
-        get_spell_from_conn(&conn, spell_id)?
-            .ok_or_else(|| AppError::NotFound("Failed to fetch updated spell".to_string()))
+        let spell = get_spell_from_conn(&conn, spell_id)?
+            .ok_or_else(|| AppError::NotFound("Failed to fetch updated spell".to_string()))?;
+        Ok::<ReparseResult, AppError>(ReparseResult { spell, changes })
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
 
-    Ok(updated_spell)
+    Ok(result)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::commands::vault::{
-        optimize_vault_with_root, VaultMaintenanceState, VaultTestEnvGuard,
-    };
-    use crate::models::canonical_spell::{CanonicalSpell, SourceRef};
-    use rusqlite::{params, Connection};
+/// Path of the primary artifact of `spell_id`; NotFound when the spell has none.
+fn primary_artifact_path_with_conn(
+    conn: &rusqlite::Connection,
+    spell_id: i64,
+) -> Result<String, AppError> {
+    let artifact_id = primary_artifact_for_spell(conn, spell_id)?;
+    let (_, path) = resolve_artifact_spell_id(conn, artifact_id)?;
+    Ok(path)
+}
 
-    fn minimal_spell_json(name: &str) -> String {
-        format!(
-            r#"{{"name":"{}","tradition":"ARCANE","level":1,"description":"X","school":"Abjuration"}}"#,
-            name
-        )
-    }
+/// Canonical fields where the stored spell (`new`) has drifted from a fresh parse of
+/// its source (`old`). Nothing is written.
+fn diff_spell_against_parsed_with_conn(
+    conn: &rusqlite::Connection,
+    spell_id: i64,
+    parsed: &SpellDetail,
+) -> Result<Vec<CanonicalFieldDiff>, AppError> {
+    let stored = get_spell_from_conn(conn, spell_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Spell {spell_id} not found")))?;
+    let (source, _, _) = canonicalize_spell_detail(parsed.clone())?;
+    let (current, _, _) = canonicalize_spell_detail(stored)?;
+    source.diff(&current).map_err(AppError::Validation)
+}
 
-    fn payload_with_exact_bytes(target_bytes: usize) -> String {
+/// Reparses the primary artifact of `spell_id` in memory and reports how the stored
+/// spell differs from it, e.g. a hand-edited description or added tags.
+#[tauri::command]
+pub async fn diff_spell_against_source(
+    state: State<'_, Arc<Pool>>,
+    spell_id: i64,
+) -> Result<Vec<CanonicalFieldDiff>, AppError> {
+    let pool = state.inner().clone();
+    let artifact_path = {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = pool.get()?;
+            primary_artifact_path_with_conn(&conn, spell_id)
+        })
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))??
+    };
+
+    let path = PathBuf::from(&artifact_path);
+    if !path.exists() {
+        return Err(AppError::NotFound(format!(
+            "Artifact file no longer exists at: {}",
+            artifact_path
+        )));
+    }
+
+    let result = parse_import_files(&[path]).await?;
+    let parsed_spell = serde_json::from_value::<Vec<SpellDetail>>(
+        result.get("spells").cloned().unwrap_or(json!([])),
+    )
+    .map_err(|e| AppError::Sidecar(format!("Failed to parse sidecar response: {}", e)))?
+    .into_iter()
+    .next()
+    .ok_or_else(|| AppError::Sidecar("Sidecar did not return any parsed spells".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        diff_spell_against_parsed_with_conn(&conn, spell_id, &parsed_spell)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Maps a sidecar-parsed spell onto an update of the artifact's existing spell row.
+fn reparsed_spell_update(spell_id: i64, parsed: &SpellDetail) -> SpellUpdate {
+    SpellUpdate {
+        id: spell_id,
+        name: parsed.name.clone(),
+        school: parsed.school.clone(),
+        sphere: parsed.sphere.clone(),
+        class_list: parsed.class_list.clone(),
+        level: parsed.level,
+        range: parsed.range.clone(),
+        components: parsed.components.clone(),
+        material_components: parsed.material_components.clone(),
+        casting_time: parsed.casting_time.clone(),
+        duration: parsed.duration.clone(),
+        area: parsed.area.clone(),
+        saving_throw: parsed.saving_throw.clone(),
+        damage: parsed.damage.clone(),
+        magic_resistance: parsed.magic_resistance.clone(),
+        reversible: parsed.reversible,
+        description: parsed.description.clone(),
+        tags: parsed.tags.clone(),
+        source: parsed.source.clone(),
+        edition: parsed.edition.clone(),
+        author: parsed.author.clone(),
+        license: parsed.license.clone(),
+        is_quest_spell: parsed.is_quest_spell,
+        is_cantrip: parsed.is_cantrip,
+        ..Default::default()
+    }
+}
+
+/// `change_log` actor recorded for rows written by a reparse.
+const REPARSE_CHANGE_LOG_ACTOR: &str = "reparse";
+
+/// Fields whose most recent `change_log` entry was written by a user rather than an
+/// import or reparse. Empty when the log has no `actor` column (older test schemas).
+fn user_edited_fields(
+    conn: &rusqlite::Connection,
+    spell_id: i64,
+) -> Result<HashSet<String>, AppError> {
+    if !crate::db::table_has_column(conn, "change_log", "actor") {
+        return Ok(HashSet::new());
+    }
+    let mut stmt = conn.prepare(
+        "SELECT cl.field FROM change_log cl
+         WHERE cl.spell_id = ?1
+           AND cl.id = (SELECT MAX(latest.id) FROM change_log latest
+                        WHERE latest.spell_id = cl.spell_id AND latest.field = cl.field)
+           AND IFNULL(cl.actor, ?2) NOT IN (?3, ?4)",
+    )?;
+    let fields = stmt
+        .query_map(
+            params![
+                spell_id,
+                USER_CHANGE_LOG_ACTOR,
+                IMPORT_CHANGE_LOG_ACTOR,
+                REPARSE_CHANGE_LOG_ACTOR
+            ],
+            |row| row.get::<_, String>(0),
+        )?
+        .collect::<Result<HashSet<_>, _>>()?;
+    Ok(fields)
+}
+
+/// Resets `field` on `update` to the spell's current value so the reparse leaves it alone.
+fn keep_existing_field(update: &mut SpellUpdate, existing: &SpellDetail, field: &str) {
+    match field {
+        "name" => update.name = existing.name.clone(),
+        "school" => update.school = existing.school.clone(),
+        "sphere" => update.sphere = existing.sphere.clone(),
+        "class_list" => update.class_list = existing.class_list.clone(),
+        "level" => update.level = existing.level,
+        "range" => update.range = existing.range.clone(),
+        "components" => update.components = existing.components.clone(),
+        "material_components" => update.material_components = existing.material_components.clone(),
+        "casting_time" => update.casting_time = existing.casting_time.clone(),
+        "duration" => update.duration = existing.duration.clone(),
+        "area" => update.area = existing.area.clone(),
+        "saving_throw" => update.saving_throw = existing.saving_throw.clone(),
+        "damage" => update.damage = existing.damage.clone(),
+        "magic_resistance" => update.magic_resistance = existing.magic_resistance.clone(),
+        "reversible" => update.reversible = existing.reversible,
+        "description" => update.description = existing.description.clone(),
+        "tags" => update.tags = existing.tags.clone(),
+        "source" => update.source = existing.source.clone(),
+        "edition" => update.edition = existing.edition.clone(),
+        "author" => update.author = existing.author.clone(),
+        "license" => update.license = existing.license.clone(),
+        "is_quest_spell" => update.is_quest_spell = existing.is_quest_spell,
+        "is_cantrip" => update.is_cantrip = existing.is_cantrip,
+        _ => {}
+    }
+}
+
+/// Applies one sidecar reparse to the artifact's spell through the regular update path
+/// (diff + change_log + vault export) and bumps the artifact's `imported_at`.
+/// With `preserve_edited`, fields a user last edited keep their current value.
+/// Returns the names of the fields the reparse changed.
+fn apply_artifact_reparse_with_conn(
+    conn: &rusqlite::Connection,
+    artifact_id: i64,
+    spell_id: i64,
+    parsed: &SpellDetail,
+    preserve_edited: bool,
+) -> Result<Vec<ReparseFieldChange>, AppError> {
+    let mut update = reparsed_spell_update(spell_id, parsed);
+    let existing = get_spell_from_conn(conn, spell_id)?.ok_or_else(|| {
+        AppError::NotFound(
+            "The spell referenced by this artifact is no longer in the library".to_string(),
+        )
+    })?;
+    if preserve_edited {
+        for field in user_edited_fields(conn, spell_id)? {
+            keep_existing_field(&mut update, &existing, &field);
+        }
+    }
+    let changes = diff_spells(&existing, &update)
+        .into_iter()
+        .map(|(field, old, new)| ReparseFieldChange { field, old, new })
+        .collect();
+
+    apply_spell_update_with_actor(conn, &update, REPARSE_CHANGE_LOG_ACTOR)?;
+
+    conn.execute(
+        "UPDATE artifact SET imported_at = ? WHERE id = ?",
+        params![Utc::now().to_rfc3339(), artifact_id],
+    )?;
+
+    Ok(changes)
+}
+
+/// An artifact resolved for batch reparse: `Ok((spell_id, path))` when its spell exists,
+/// is not locked (or `force` is set), and the file is still on disk, otherwise the reason
+/// it is skipped.
+type ReparsePlanItem = (i64, Result<(i64, String), String>);
+
+fn plan_artifact_reparse(
+    conn: &rusqlite::Connection,
+    artifact_ids: &[i64],
+    force: bool,
+) -> Vec<ReparsePlanItem> {
+    artifact_ids
+        .iter()
+        .map(|&artifact_id| {
+            let resolved = resolve_artifact_spell_id(conn, artifact_id)
+                .map_err(|e| e.to_string())
+                .and_then(|(spell_id, path)| {
+                    match is_spell_locked_with_conn(conn, spell_id) {
+                        Ok(true) if !force => {
+                            return Err(locked_reparse_error(spell_id).to_string())
+                        }
+                        Ok(_) => {}
+                        Err(e) => return Err(e.to_string()),
+                    }
+                    if std::path::Path::new(&path).exists() {
+                        Ok((spell_id, path))
+                    } else {
+                        Err(format!("Artifact file no longer exists at: {}", path))
+                    }
+                });
+            (artifact_id, resolved)
+        })
+        .collect()
+}
+
+/// Applies sidecar results (keyed by `normalize_key(path)`) to every planned artifact.
+/// A failure on one artifact is recorded in its result and never aborts the batch.
+fn apply_artifact_reparse_batch(
+    conn: &rusqlite::Connection,
+    plan: Vec<ReparsePlanItem>,
+    parsed_by_path: &HashMap<String, Result<SpellDetail, String>>,
+    preserve_edited: bool,
+    progress: &mut impl FnMut(u32, u32),
+) -> Vec<ReparseArtifactResult> {
+    let total = plan.len() as u32;
+    let mut results = Vec::with_capacity(plan.len());
+
+    for (i, (artifact_id, resolved)) in plan.into_iter().enumerate() {
+        let result = match resolved {
+            Err(error) => ReparseArtifactResult {
+                artifact_id,
+                spell_id: None,
+                changed_fields: vec![],
+                changes: vec![],
+                error: Some(error),
+            },
+            Ok((spell_id, path)) => {
+                let outcome = match parsed_by_path.get(&normalize_key(&path)) {
+                    Some(Ok(parsed)) => apply_artifact_reparse_with_conn(
+                        conn,
+                        artifact_id,
+                        spell_id,
+                        parsed,
+                        preserve_edited,
+                    )
+                    .map_err(|e| e.to_string()),
+                    Some(Err(reason)) => Err(reason.clone()),
+                    None => Err("Sidecar did not return a parsed spell for this artifact".into()),
+                };
+                match outcome {
+                    Ok(changes) => ReparseArtifactResult {
+                        artifact_id,
+                        spell_id: Some(spell_id),
+                        changed_fields: changes.iter().map(|c| c.field.clone()).collect(),
+                        changes,
+                        error: None,
+                    },
+                    Err(error) => {
+                        warn!(artifact_id, spell_id, %error, "Artifact reparse failed");
+                        ReparseArtifactResult {
+                            artifact_id,
+                            spell_id: Some(spell_id),
+                            changed_fields: vec![],
+                            changes: vec![],
+                            error: Some(error),
+                        }
+                    }
+                }
+            }
+        };
+        results.push(result);
+        progress((i + 1) as u32, total);
+    }
+
+    results
+}
+
+/// Artifact ids for a batch reparse: the explicit `artifact_ids`, plus the primary
+/// artifact of each spell in `spell_ids`. With neither, every artifact in the library.
+/// Spells without any artifact are skipped with a warning.
+fn reparse_artifact_ids_with_conn(
+    conn: &rusqlite::Connection,
+    artifact_ids: Option<Vec<i64>>,
+    spell_ids: Option<Vec<i64>>,
+) -> Result<Vec<i64>, AppError> {
+    if artifact_ids.is_none() && spell_ids.is_none() {
+        let mut stmt = conn.prepare("SELECT id FROM artifact ORDER BY id")?;
+        let ids = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+        return Ok(ids);
+    }
+
+    let mut ids = artifact_ids.unwrap_or_default();
+    for spell_id in spell_ids.unwrap_or_default() {
+        match primary_artifact_for_spell(conn, spell_id) {
+            Ok(artifact_id) if !ids.contains(&artifact_id) => ids.push(artifact_id),
+            Ok(_) => {}
+            Err(AppError::NotFound(reason)) => warn!(spell_id, %reason, "Skipping reparse"),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(ids)
+}
+
+/// Splits per-artifact reparse results into the shared batch shape, keyed by artifact id.
+fn reparse_batch_result(results: Vec<ReparseArtifactResult>) -> BatchResult<ReparseArtifactResult> {
+    BatchResult::from_outcomes(results.into_iter().map(|mut result| {
+        let artifact_id = result.artifact_id;
+        match result.error.take() {
+            Some(error) => (artifact_id, Err(error)),
+            None => (artifact_id, Ok(result)),
+        }
+    }))
+}
+
+/// The parsed spell for each source file of an `import` parse result, keyed by
+/// [`normalize_key`]. A file holding several spells maps to its first one, as
+/// [`reparse_artifact`] reparses it; files that failed to parse map to the conflict reason.
+fn parsed_spells_by_path(
+    result: &serde_json::Value,
+) -> HashMap<String, Result<SpellDetail, String>> {
+    let mut parsed_by_path = HashMap::new();
+    let spells = result
+        .get("spells")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for spell in spells {
+        let Some(path) = spell.get("_source_file").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let key = normalize_key(path);
+        parsed_by_path.entry(key).or_insert_with(|| {
+            serde_json::from_value::<SpellDetail>(spell)
+                .map_err(|e| format!("Failed to parse sidecar response: {}", e))
+        });
+    }
+    let conflicts: Vec<ParseConflict> =
+        serde_json::from_value(result.get("conflicts").cloned().unwrap_or(json!([])))
+            .unwrap_or_default();
+    for conflict in conflicts {
+        parsed_by_path
+            .entry(normalize_key(&conflict.path))
+            .or_insert(Err(conflict.reason));
+    }
+    parsed_by_path
+}
+
+/// Reparses many artifacts with a single sidecar call. `spell_ids` reparse each spell
+/// from its primary artifact; when both id lists are `None`, every artifact in the
+/// library is reparsed. Emits `reparse-progress` events.
+#[tauri::command]
+pub async fn reparse_artifacts(
+    window: Window,
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    artifact_ids: Option<Vec<i64>>,
+    spell_ids: Option<Vec<i64>>,
+    preserve_edited: Option<bool>,
+    force: Option<bool>,
+) -> Result<BatchResult<ReparseArtifactResult>, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let preserve_edited = preserve_edited.unwrap_or(true);
+    let force = force.unwrap_or(false);
+    let pool = state.inner().clone();
+
+    let plan = {
+        let pool = pool.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = pool.get()?;
+            let ids = reparse_artifact_ids_with_conn(&conn, artifact_ids, spell_ids)?;
+            Ok::<Vec<ReparsePlanItem>, AppError>(plan_artifact_reparse(&conn, &ids, force))
+        })
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))??
+    };
+
+    let paths: Vec<String> = plan
+        .iter()
+        .filter_map(|(_, resolved)| resolved.as_ref().ok().map(|(_, path)| path.clone()))
+        .collect();
+
+    let mut parsed_by_path: HashMap<String, Result<SpellDetail, String>> = HashMap::new();
+    if !paths.is_empty() {
+        let files: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+        match parse_import_files(&files).await {
+            Ok(result) => parsed_by_path = parsed_spells_by_path(&result),
+            Err(e) => {
+                let reason = e.to_string();
+                for path in &paths {
+                    parsed_by_path.insert(normalize_key(path), Err(reason.clone()));
+                }
+            }
+        }
+    }
+
+    let results = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        Ok::<Vec<ReparseArtifactResult>, AppError>(apply_artifact_reparse_batch(
+            &conn,
+            plan,
+            &parsed_by_path,
+            preserve_edited,
+            &mut |current, total| {
+                let _ = window.emit(
+                    "reparse-progress",
+                    json!({ "current": current, "total": total }),
+                );
+            },
+        ))
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(reparse_batch_result(results))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::spells::apply_spell_update_with_conn;
+    use crate::commands::vault::{
+        optimize_vault_with_root, VaultMaintenanceState, VaultTestEnvGuard,
+    };
+    use crate::models::canonical_spell::{CanonicalSpell, SourceRef};
+    use rusqlite::{params, Connection};
+
+    fn minimal_spell_json(name: &str) -> String {
+        format!(
+            r#"{{"name":"{}","tradition":"ARCANE","level":1,"description":"X","school":"Abjuration"}}"#,
+            name
+        )
+    }
+
+    fn payload_with_exact_bytes(target_bytes: usize) -> String {
         let prefix = "{\"name\":\"Sized\",\"tradition\":\"ARCANE\",\"level\":1,\"description\":\"X\",\"school\":\"Abjuration\",\"artifacts\":[{\"id\":1,\"type\":\"source\",\"path\":\"";
         let suffix = "\",\"hash\":\"artifact-hash\"}]}";
         let path_len = target_bytes
@@ -3139,6 +5292,42 @@ mod tests {
         assert!(!validate_source_ref_url("file:///etc/passwd"));
     }
 
+    #[test]
+    fn test_sanitize_import_filename_keeps_unicode_names() {
+        assert_eq!(
+            sanitize_import_filename("Féerie.md"),
+            ("Féerie.md".to_string(), false)
+        );
+        // Decomposed input is NFC-normalized without counting as sanitization.
+        assert_eq!(
+            sanitize_import_filename("Fe\u{301}erie.md"),
+            ("Féerie.md".to_string(), false)
+        );
+        assert_eq!(
+            sanitize_import_filename("魔法の矢.md"),
+            ("魔法の矢.md".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn test_sanitize_import_filename_blocks_traversal_and_control_chars() {
+        let (traversal, changed) = sanitize_import_filename("../../etc/passwd");
+        assert!(changed);
+        assert!(!traversal.contains('/'));
+        assert!(!traversal.starts_with('.'));
+        assert_eq!(traversal, "___.._etc_passwd");
+
+        let (windows, changed) = sanitize_import_filename("..\\secret.md");
+        assert!(changed);
+        assert_eq!(windows, "___secret.md");
+
+        assert_eq!(sanitize_import_filename(".."), ("__".to_string(), true));
+
+        let (control, changed) = sanitize_import_filename("bad\u{0}name\n.md");
+        assert!(changed);
+        assert_eq!(control, "bad_name_.md");
+    }
+
     #[test]
     fn test_sanitize_url_for_display() {
         assert_eq!(
@@ -3441,6 +5630,7 @@ mod tests {
             &maintenance_state,
             preview.spells,
             None,
+            false,
         )
         .expect("import should accept exact-boundary spell");
 
@@ -3640,451 +5830,1593 @@ mod tests {
             },
         ];
 
-        let (merged_tags, merged_json) =
-            merge_canonical_metadata(canonical_json, &incoming_tags, &incoming_refs).unwrap();
+        let (merged_tags, merged_json) =
+            merge_canonical_metadata(canonical_json, &incoming_tags, &incoming_refs).unwrap();
+
+        assert_eq!(
+            merged_tags.unwrap(),
+            r#"["existing","new"]"#,
+            "Tags merged and sorted"
+        );
+        let merged_spell: CanonicalSpell = serde_json::from_str(&merged_json).unwrap();
+        assert_eq!(
+            merged_spell.source_refs.len(),
+            2,
+            "SourceRefs merged (B1 was dupe, B2 added)"
+        );
+        assert_eq!(merged_spell.source_refs[0].book, "B1");
+        assert_eq!(
+            merged_spell.source_refs[1].url.as_deref(),
+            Some("https://b.com")
+        );
+    }
+
+    #[test]
+    fn test_import_alias_support() {
+        let h1 = "a".repeat(64);
+        let base_spell = CanonicalSpell {
+            name: "N1".into(),
+            tradition: "ARCANE".into(),
+            level: 1,
+            description: "D1".into(),
+            school: Some("Abjuration".into()),
+            version: "2.0.0".into(),
+            ..Default::default()
+        };
+
+        // 1. id
+        let mut s1 = base_spell.clone();
+        s1.id = Some(h1.clone());
+        let json_id = serde_json::to_string(&s1).unwrap();
+
+        // 2. content_hash
+        let mut val_hash = serde_json::to_value(&base_spell).unwrap();
+        val_hash
+            .as_object_mut()
+            .unwrap()
+            .insert("content_hash".into(), h1.clone().into());
+        let json_hash = serde_json::to_string(&val_hash).unwrap();
+
+        // 3. contentHash
+        let mut val_camel = serde_json::to_value(&base_spell).unwrap();
+        val_camel
+            .as_object_mut()
+            .unwrap()
+            .insert("contentHash".into(), h1.clone().into());
+        let json_camel = serde_json::to_string(&val_camel).unwrap();
+
+        let res_id =
+            tauri::async_runtime::block_on(preview_import_spell_json(json_id, None)).unwrap();
+        let res_hash =
+            tauri::async_runtime::block_on(preview_import_spell_json(json_hash, None)).unwrap();
+        let res_camel =
+            tauri::async_runtime::block_on(preview_import_spell_json(json_camel, None)).unwrap();
+
+        assert!(
+            res_id.failures.is_empty(),
+            "id failed: {:?}",
+            res_id.failures
+        );
+        assert!(
+            res_hash.failures.is_empty(),
+            "hash failed: {:?}",
+            res_hash.failures
+        );
+        assert!(
+            res_camel.failures.is_empty(),
+            "camel failed: {:?}",
+            res_camel.failures
+        );
+
+        assert_eq!(
+            res_id.spells[0].spell.id,
+            Some(res_id.spells[0].content_hash.clone())
+        );
+        assert_eq!(
+            res_hash.spells[0].spell.id,
+            Some(res_hash.spells[0].content_hash.clone())
+        );
+        assert_eq!(
+            res_camel.spells[0].spell.id,
+            Some(res_camel.spells[0].content_hash.clone())
+        );
+    }
+
+    #[test]
+    fn test_apply_import_conflict_same_name_different_level_different_hash() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_import_apply_test_db();
+        let existing_spell = test_spell("Mirror Veil", 1, "Existing spell");
+        let incoming_spell = test_spell("Mirror Veil", 5, "Incoming spell with different content");
+        let existing_hash = test_hash(&existing_spell);
+        let incoming_hash = test_hash(&incoming_spell);
+
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
+
+        let result = apply_import_spell_json_impl(
+            &conn,
+            vec![preview_item_for_test(incoming_spell.clone())],
+            None,
+        )
+        .expect("apply import should succeed");
+
+        assert_eq!(result.imported_count, 0, "conflict should block insert");
+        assert_eq!(result.conflicts.len(), 1, "one conflict should be emitted");
+        assert_eq!(result.conflicts[0].existing_id, 1);
+        assert_eq!(result.conflicts[0].incoming_name, incoming_spell.name);
+        assert_eq!(
+            result.conflicts[0].incoming_content_hash, incoming_hash,
+            "conflict should track incoming hash"
+        );
+    }
+
+    #[test]
+    fn test_apply_import_materializes_vault_file_after_commit() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_import_apply_test_db();
+        create_artifact_table_for_get_spell(&conn);
+        let incoming_spell = test_spell("Vault Import", 4, "Imported into vault storage");
+        let stored_hash = incoming_spell.compute_hash().expect("hash");
+
+        let result = apply_import_spell_json_impl(
+            &conn,
+            vec![PreviewSpellJsonItem {
+                spell: incoming_spell,
+                content_hash: stored_hash.clone(),
+                warnings: vec![],
+            }],
+            None,
+        )
+        .expect("apply import should succeed");
+
+        assert_eq!(result.imported_count, 1, "spell should be inserted");
+        assert!(
+            temp_dir
+                .path()
+                .join("spells")
+                .join(format!("{stored_hash}.json"))
+                .exists(),
+            "json import should write the canonical spell file into the vault"
+        );
+    }
+
+    #[test]
+    fn test_apply_import_db_commit_before_vault_write() {
+        // DB is committed first; vault write is a best-effort follow-up.
+        // If vault write fails, the error is surfaced but the DB row remains committed.
+        let long_root = std::env::temp_dir()
+            .join("spellbook-import")
+            .join("a".repeat(240));
+        let _env = VaultTestEnvGuard::with_root(long_root.clone()).expect("set isolated vault env");
+
+        let conn = setup_import_apply_test_db();
+        create_artifact_table_for_get_spell(&conn);
+        let incoming_item = preview_item_for_test(test_spell(
+            "Vault Import Failure",
+            4,
+            "DB committed even when vault write fails",
+        ));
+
+        let err = apply_import_spell_json_impl(&conn, vec![incoming_item], None)
+            .expect_err("vault write failure should surface an error");
+        assert!(err.to_string().contains("Windows path length"));
+
+        // DB row should be committed even though vault file write failed
+        let spell_count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM spell", [], |row| row.get(0))
+            .expect("query spell count");
+        assert_eq!(
+            spell_count, 1,
+            "DB transaction is committed before vault write; spell row must be present"
+        );
+    }
+
+    #[test]
+    fn test_manual_gc_is_blocked_while_import_guard_is_active() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_import_apply_test_db();
+        let maintenance_state = VaultMaintenanceState::default();
+        let _guard = maintenance_state
+            .start_import()
+            .expect("import guard should be acquired");
+
+        let err = optimize_vault_with_root(&conn, temp_dir.path(), Some(&maintenance_state))
+            .expect_err("manual gc should be rejected during active import");
+        assert!(
+            err.to_string().contains("import"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_run_with_import_maintenance_blocks_manual_gc_for_legacy_flows() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_import_apply_test_db();
+        let maintenance_state = VaultMaintenanceState::default();
+
+        let err = run_with_import_maintenance(&maintenance_state, || {
+            let _ = optimize_vault_with_root(&conn, temp_dir.path(), Some(&maintenance_state))?;
+            Ok::<(), AppError>(())
+        })
+        .expect_err("legacy import wrapper should block manual gc during active import");
+
+        assert!(
+            err.to_string().contains("import"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_run_post_import_gc_if_needed_removes_orphans_for_legacy_imports() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_import_apply_test_db();
+        let live_spell = test_spell("Legacy Import Live", 2, "Live spell");
+        let live_hash = test_hash(&live_spell);
+        insert_spell_for_apply_test(&conn, 1, &live_spell, &live_hash);
+
+        let orphan_hash = "l".repeat(64);
+        let spells_dir = temp_dir.path().join("spells");
+        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
+        std::fs::write(
+            spells_dir.join(format!("{live_hash}.json")),
+            serde_json::to_string(&live_spell).expect("serialize live spell"),
+        )
+        .expect("write live spell file");
+        std::fs::write(
+            spells_dir.join(format!("{orphan_hash}.json")),
+            r#"{"name":"Orphan","tradition":"ARCANE","level":1,"description":"Orphan","school":"Abjuration"}"#,
+        )
+        .expect("write orphan file");
+
+        run_post_import_gc_if_needed(&conn, temp_dir.path(), 1).expect("post-import gc should run");
+
+        assert!(spells_dir.join(format!("{live_hash}.json")).exists());
+        assert!(!spells_dir.join(format!("{orphan_hash}.json")).exists());
+    }
+
+    #[test]
+    fn test_run_post_import_gc_if_needed_skips_gc_when_no_legacy_mutation() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_import_apply_test_db();
+        let orphan_hash = "m".repeat(64);
+        let spells_dir = temp_dir.path().join("spells");
+        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
+        std::fs::write(
+            spells_dir.join(format!("{orphan_hash}.json")),
+            r#"{"name":"Orphan","tradition":"ARCANE","level":1,"description":"Orphan","school":"Abjuration"}"#,
+        )
+        .expect("write orphan file");
+
+        run_post_import_gc_if_needed(&conn, temp_dir.path(), 0)
+            .expect("post-import gc should be skipped cleanly");
+
+        assert!(
+            spells_dir.join(format!("{orphan_hash}.json")).exists(),
+            "gc should not run when no legacy mutations were applied"
+        );
+    }
+
+    #[test]
+    fn test_resolve_import_conflicts_with_conn_and_root_overwrite_cascades_hash_references() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+
+        let existing_spell = test_spell("Legacy Resolve", 2, "Original description");
+        let updated_spell = test_spell("Legacy Resolve", 2, "Updated description");
+        let old_hash = test_hash(&existing_spell);
+        let new_hash = test_hash(&updated_spell);
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &old_hash);
+        conn.execute(
+            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 1, ?)",
+            params![old_hash.clone()],
+        )
+        .expect("seed character_class_spell hash reference");
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
+             VALUES (1, 1, 'pdf', 'legacy-resolve.pdf', 'artifact-hash', '2026-01-01T00:00:00Z', ?)",
+            params![old_hash.clone()],
+        )
+        .expect("seed artifact hash reference");
+
+        let spells_dir = temp_dir.path().join("spells");
+        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
+        std::fs::write(
+            spells_dir.join(format!("{old_hash}.json")),
+            serde_json::to_string(&existing_spell).expect("serialize existing spell"),
+        )
+        .expect("write existing spell file");
+
+        let result = resolve_import_conflicts_with_conn_and_root(
+            &conn,
+            temp_dir.path(),
+            vec![ImportConflictResolution {
+                action: "overwrite".to_string(),
+                existing_id: 1,
+                spell: Some(spell_update_for_test(1, &updated_spell)),
+                artifact: None,
+                queue_id: None,
+            }],
+        )
+        .expect("conflict resolution should succeed");
+
+        assert_eq!(result.resolved.len(), 1);
+        let ccs_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM character_class_spell WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query character_class_spell hash");
+        assert_eq!(
+            ccs_hash, new_hash,
+            "legacy overwrite should cascade hash references in character_class_spell"
+        );
+        let artifact_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM artifact WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query artifact hash");
+        assert_eq!(
+            artifact_hash, new_hash,
+            "legacy overwrite should cascade hash references in artifact rows"
+        );
+        assert!(
+            spells_dir.join(format!("{new_hash}.json")).exists(),
+            "updated spell should still have a live vault file"
+        );
+        assert!(
+            spells_dir.join(format!("{old_hash}.json")).exists(),
+            "helper-level resolution should leave old hash cleanup to the command-level post-import GC"
+        );
+    }
+
+    #[test]
+    fn test_queued_import_conflict_survives_and_resolves_from_queue() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let root = _temp_dir.path().to_path_buf();
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+        conn.execute_batch(include_str!(
+            "../../../../../db/migrations/0024_import_conflict_queue.sql"
+        ))
+        .expect("create conflict queue");
+
+        let existing_spell = test_spell("Queued Conflict", 2, "Original description");
+        let incoming_spell = test_spell("Queued Conflict", 2, "Incoming description");
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &test_hash(&existing_spell));
+        let existing = get_spell_from_conn(&conn, 1)
+            .expect("load existing")
+            .expect("existing spell");
+        let mut incoming = existing.clone();
+        incoming.id = None;
+        incoming.description = incoming_spell.description.clone();
+
+        // The import reports a conflict and queues it; the dialog is then closed.
+        let queued = enqueue_import_conflicts_with_conn(
+            &conn,
+            &[
+                ImportConflict::Parse {
+                    path: "broken.md".to_string(),
+                    reason: "unreadable".to_string(),
+                },
+                ImportConflict::Spell {
+                    existing: Box::new(existing),
+                    incoming: Box::new(incoming),
+                    fields: vec![],
+                    artifact: None,
+                },
+            ],
+        )
+        .expect("enqueue conflicts");
+        assert_eq!(queued, 1, "parse conflicts are not queued");
+
+        // A later session lists the queue and resolves by queue id alone.
+        let pending = list_pending_conflicts_with_conn(&conn).expect("list queue");
+        assert_eq!(pending.len(), 1);
+        assert!(matches!(pending[0].conflict, ImportConflict::Spell { .. }));
+
+        let result = resolve_import_conflicts_with_conn_and_root(
+            &conn,
+            &root,
+            vec![ImportConflictResolution {
+                action: "overwrite".to_string(),
+                existing_id: 1,
+                spell: None,
+                artifact: None,
+                queue_id: Some(pending[0].id),
+            }],
+        )
+        .expect("resolve queued conflict");
+        assert_eq!(result.resolved, vec!["Queued Conflict".to_string()]);
+
+        let description: String = conn
+            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query description");
+        assert_eq!(description, "Incoming description");
+        assert!(list_pending_conflicts_with_conn(&conn)
+            .expect("list queue")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_persisted_chunk_conflicts_are_queued_once_per_incoming_version() {
+        let vault = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = Connection::open_in_memory().expect("open vault db");
+        crate::db::migrations::load_migrations(&conn).expect("migrate vault db");
+        let chunk = |description: &str| {
+            json!({
+                "spells": [{
+                    "name": "Queued Spell",
+                    "school": "Evocation",
+                    "level": 2,
+                    "description": description,
+                }],
+                "artifacts": [],
+                "conflicts": [],
+            })
+        };
+        let queue_len = |conn: &Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM import_conflict_queue", [], |row| {
+                row.get(0)
+            })
+            .expect("count queue")
+        };
+
+        apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("Original text"),
+            false,
+            false,
+            &[],
+            true,
+        )
+        .expect("initial import");
+        assert_eq!(queue_len(&conn), 0);
+
+        // The conflict is queued by the chunk itself, not by a later step of the command.
+        let result = apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("Revised text"),
+            false,
+            false,
+            &[],
+            true,
+        )
+        .expect("conflicting import");
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(queue_len(&conn), 1);
+
+        // Re-importing the same file does not queue the conflict again.
+        apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("Revised text"),
+            false,
+            false,
+            &[],
+            true,
+        )
+        .expect("repeated import");
+        assert_eq!(queue_len(&conn), 1);
+        assert_eq!(
+            enqueue_import_conflicts_with_conn(&conn, &result.conflicts).expect("re-enqueue"),
+            0
+        );
+
+        // Different incoming content for the same spell is a separate conflict.
+        apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("Another revision"),
+            false,
+            false,
+            &[],
+            true,
+        )
+        .expect("second conflicting import");
+        assert_eq!(queue_len(&conn), 2);
+    }
+
+    #[test]
+    fn test_upsert_import_artifact_persists_spell_content_hash_when_available() {
+        let conn = setup_import_apply_test_db();
+        create_hash_reference_tables(&conn);
+        let expected_hash = "a".repeat(64);
+
+        upsert_import_artifact(
+            &conn,
+            1,
+            &expected_hash,
+            &ImportArtifact {
+                r#type: "pdf".to_string(),
+                path: "spell.pdf".to_string(),
+                hash: "artifact-hash".to_string(),
+                imported_at: "2026-01-01T00:00:00Z".to_string(),
+            },
+        )
+        .expect("upsert artifact");
+
+        let stored_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM artifact WHERE spell_id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query artifact spell_content_hash");
+        assert_eq!(stored_hash, expected_hash);
+    }
+
+    #[test]
+    fn test_resolve_import_conflicts_with_conn_and_root_rolls_back_batch_on_error() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+
+        let existing_spell = test_spell("Legacy Partial Resolve", 4, "Original description");
+        let updated_spell = test_spell("Legacy Partial Resolve", 4, "Updated description");
+        let old_hash = test_hash(&existing_spell);
+        let new_hash = test_hash(&updated_spell);
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &old_hash);
+        conn.execute(
+            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 1, ?)",
+            params![old_hash.clone()],
+        )
+        .expect("seed character_class_spell hash reference");
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
+             VALUES (1, 1, 'pdf', 'legacy-partial.pdf', 'artifact-hash', '2026-01-01T00:00:00Z', ?)",
+            params![old_hash.clone()],
+        )
+        .expect("seed artifact hash reference");
+
+        let spells_dir = temp_dir.path().join("spells");
+        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
+        std::fs::write(
+            spells_dir.join(format!("{old_hash}.json")),
+            serde_json::to_string(&existing_spell).expect("serialize existing spell"),
+        )
+        .expect("write existing spell file");
+
+        let err = resolve_import_conflicts_with_conn_and_root(
+            &conn,
+            temp_dir.path(),
+            vec![
+                ImportConflictResolution {
+                    action: "overwrite".to_string(),
+                    existing_id: 1,
+                    spell: Some(spell_update_for_test(1, &updated_spell)),
+                    artifact: None,
+                    queue_id: None,
+                },
+                ImportConflictResolution {
+                    action: "bogus".to_string(),
+                    existing_id: 1,
+                    spell: None,
+                    artifact: None,
+                    queue_id: None,
+                },
+            ],
+        )
+        .expect_err("helper should still return the later validation error");
+
+        assert!(
+            err.to_string()
+                .contains("Unknown conflict resolution action"),
+            "unexpected error: {err}"
+        );
+        let stored_hash: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query stored spell hash");
+        assert_eq!(
+            stored_hash, old_hash,
+            "failed legacy resolution batch must roll back spell row mutations"
+        );
+        let ccs_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM character_class_spell WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query character_class_spell hash after rollback");
+        assert_eq!(
+            ccs_hash, old_hash,
+            "failed legacy resolution batch must roll back character_class_spell cascades"
+        );
+        let artifact_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM artifact WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query artifact hash after rollback");
+        assert_eq!(
+            artifact_hash, old_hash,
+            "failed legacy resolution batch must roll back artifact cascades"
+        );
+        assert!(
+            !spells_dir.join(format!("{new_hash}.json")).exists(),
+            "failed legacy resolution batch must not materialize a new vault file"
+        );
+        assert!(
+            spells_dir.join(format!("{old_hash}.json")).exists(),
+            "helper-level resolution should leave old hash cleanup to the command-level post-import GC"
+        );
+    }
+
+    #[test]
+    fn test_resolve_import_conflicts_with_conn_and_root_rolls_back_when_vault_write_fails() {
+        let long_root = std::env::temp_dir()
+            .join("spellbook-legacy-import")
+            .join("b".repeat(400));
+        let _env = VaultTestEnvGuard::with_root(long_root.clone()).expect("set isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+
+        let existing_spell = test_spell("Legacy Resolve Failure", 4, "Original description");
+        let updated_spell = test_spell("Legacy Resolve Failure", 4, "Updated description");
+        let old_hash = test_hash(&existing_spell);
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &old_hash);
+        conn.execute(
+            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 1, ?)",
+            params![old_hash.clone()],
+        )
+        .expect("seed character_class_spell hash reference");
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
+             VALUES (1, 1, 'pdf', 'legacy-resolve-failure.pdf', 'artifact-hash', '2026-01-01T00:00:00Z', ?)",
+            params![old_hash.clone()],
+        )
+        .expect("seed artifact hash reference");
+        let err = resolve_import_conflicts_with_conn_and_root(
+            &conn,
+            &long_root,
+            vec![ImportConflictResolution {
+                action: "overwrite".to_string(),
+                existing_id: 1,
+                spell: Some(spell_update_for_test(1, &updated_spell)),
+                artifact: None,
+                queue_id: None,
+            }],
+        )
+        .expect_err("vault write failure should roll back conflict resolution");
+        assert!(err.to_string().contains("Windows path length"));
+
+        let stored_description: String = conn
+            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query spell description");
+        let stored_hash: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query spell hash");
+        let class_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM character_class_spell WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query character_class_spell hash");
+        let artifact_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM artifact WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query artifact hash");
+
+        assert_eq!(stored_description, "Original description");
+        assert_eq!(stored_hash, old_hash);
+        assert_eq!(class_hash, old_hash);
+        assert_eq!(artifact_hash, old_hash);
+    }
+
+    #[test]
+    fn test_run_legacy_import_chunk_transaction_rolls_back_partial_mutations() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+        let existing_spell = test_spell("Legacy Chunk Rollback", 3, "Original description");
+        let existing_hash = test_hash(&existing_spell);
+        let updated_spell = test_spell("Legacy Chunk Rollback", 3, "Updated description");
+        let updated_hash = test_hash(&updated_spell);
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
+
+        let err = run_legacy_import_chunk_transaction(&conn, |conn| {
+            conn.execute(
+                "UPDATE spell SET description = ?, canonical_data = ?, content_hash = ? WHERE id = 1",
+                params![
+                    updated_spell.description,
+                    serde_json::to_string(&updated_spell).expect("serialize updated spell"),
+                    updated_hash
+                ],
+            )?;
+            Err::<(), AppError>(AppError::Validation("boom".to_string()))
+        })
+        .expect_err("transaction helper should return the operation error");
+
+        assert!(err.to_string().contains("boom"), "unexpected error: {err}");
+
+        let stored_hash: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query stored hash");
+        assert_eq!(
+            stored_hash, existing_hash,
+            "failed chunk should roll back to the original live hash"
+        );
+    }
+
+    #[test]
+    fn test_successful_import_triggers_post_import_gc() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+        let maintenance_state = VaultMaintenanceState::default();
+        let orphan_hash = "y".repeat(64);
+        let spells_dir = temp_dir.path().join("spells");
+        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
+        std::fs::write(
+            spells_dir.join(format!("{orphan_hash}.json")),
+            r#"{"name":"Orphan","tradition":"ARCANE","level":1,"description":"Orphan","school":"Abjuration"}"#,
+        )
+        .expect("write orphan file");
+        let imported_item = preview_item_for_test(test_spell("GC Trigger", 2, "Should trigger GC"));
+
+        let result = apply_import_spell_json_with_maintenance(
+            &conn,
+            temp_dir.path(),
+            &maintenance_state,
+            vec![imported_item],
+            None,
+            false,
+        )
+        .expect("import should succeed");
 
-        assert_eq!(
-            merged_tags.unwrap(),
-            "existing, new",
-            "Tags merged and sorted"
-        );
-        let merged_spell: CanonicalSpell = serde_json::from_str(&merged_json).unwrap();
-        assert_eq!(
-            merged_spell.source_refs.len(),
-            2,
-            "SourceRefs merged (B1 was dupe, B2 added)"
-        );
-        assert_eq!(merged_spell.source_refs[0].book, "B1");
-        assert_eq!(
-            merged_spell.source_refs[1].url.as_deref(),
-            Some("https://b.com")
+        assert_eq!(result.imported_count, 1);
+        assert!(
+            !spells_dir.join(format!("{orphan_hash}.json")).exists(),
+            "post-import gc should remove orphaned spell files"
         );
     }
 
     #[test]
-    fn test_import_alias_support() {
-        let h1 = "a".repeat(64);
-        let base_spell = CanonicalSpell {
-            name: "N1".into(),
-            tradition: "ARCANE".into(),
-            level: 1,
-            description: "D1".into(),
-            school: Some("Abjuration".into()),
-            version: "2.0.0".into(),
-            ..Default::default()
-        };
+    fn test_compressed_bundle_export_reimports_with_unchanged_hashes() {
+        use crate::commands::export::export_spell_bundle_json_impl;
+        use crate::utils::compression::write_export_json;
 
-        // 1. id
-        let mut s1 = base_spell.clone();
-        s1.id = Some(h1.clone());
-        let json_id = serde_json::to_string(&s1).unwrap();
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let maintenance_state = VaultMaintenanceState::default();
+        let hashes = |conn: &Connection| -> Vec<String> {
+            let mut stmt = conn
+                .prepare("SELECT content_hash FROM spell ORDER BY content_hash")
+                .unwrap();
+            stmt.query_map([], |row| row.get(0))
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
 
-        // 2. content_hash
-        let mut val_hash = serde_json::to_value(&base_spell).unwrap();
-        val_hash
-            .as_object_mut()
+        let source = Connection::open_in_memory().expect("open source db");
+        crate::db::migrations::load_migrations(&source).expect("migrate source db");
+        apply_import_spell_json_with_maintenance(
+            &source,
+            temp_dir.path(),
+            &maintenance_state,
+            vec![
+                preview_item_for_test(test_spell("Packed Shield", 1, "Wards the caster")),
+                preview_item_for_test(test_spell("Packed Sleep", 1, "Puts foes to sleep")),
+            ],
+            None,
+            false,
+        )
+        .expect("seed source spells");
+        let ids: Vec<i64> = source
+            .prepare("SELECT id FROM spell ORDER BY id")
             .unwrap()
-            .insert("content_hash".into(), h1.clone().into());
-        let json_hash = serde_json::to_string(&val_hash).unwrap();
-
-        // 3. contentHash
-        let mut val_camel = serde_json::to_value(&base_spell).unwrap();
-        val_camel
-            .as_object_mut()
+            .query_map([], |row| row.get(0))
             .unwrap()
-            .insert("contentHash".into(), h1.clone().into());
-        let json_camel = serde_json::to_string(&val_camel).unwrap();
-
-        let res_id =
-            tauri::async_runtime::block_on(preview_import_spell_json(json_id, None)).unwrap();
-        let res_hash =
-            tauri::async_runtime::block_on(preview_import_spell_json(json_hash, None)).unwrap();
-        let res_camel =
-            tauri::async_runtime::block_on(preview_import_spell_json(json_camel, None)).unwrap();
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let json = export_spell_bundle_json_impl(&source, ids, true).expect("export bundle");
+        let export_dir = tempfile::tempdir().expect("export dir");
+        let gz = write_export_json(export_dir.path(), "bundle", &json, true).expect("write gz");
 
-        assert!(
-            res_id.failures.is_empty(),
-            "id failed: {:?}",
-            res_id.failures
-        );
-        assert!(
-            res_hash.failures.is_empty(),
-            "hash failed: {:?}",
-            res_hash.failures
-        );
-        assert!(
-            res_camel.failures.is_empty(),
-            "camel failed: {:?}",
-            res_camel.failures
-        );
+        let payload = read_export_json(&gz).expect("read gz bundle");
+        let preview = tauri::async_runtime::block_on(preview_import_spell_json(payload, None))
+            .expect("preview compressed bundle");
+        assert!(preview.failures.is_empty());
+        let target = Connection::open_in_memory().expect("open target db");
+        crate::db::migrations::load_migrations(&target).expect("migrate target db");
+        let result = apply_import_spell_json_with_maintenance(
+            &target,
+            temp_dir.path(),
+            &maintenance_state,
+            preview.spells,
+            None,
+            false,
+        )
+        .expect("import compressed bundle");
 
-        assert_eq!(
-            res_id.spells[0].spell.id,
-            Some(res_id.spells[0].content_hash.clone())
-        );
-        assert_eq!(
-            res_hash.spells[0].spell.id,
-            Some(res_hash.spells[0].content_hash.clone())
-        );
-        assert_eq!(
-            res_camel.spells[0].spell.id,
-            Some(res_camel.spells[0].content_hash.clone())
-        );
+        assert_eq!(result.imported_count, 2);
+        assert_eq!(hashes(&target), hashes(&source));
     }
 
     #[test]
-    fn test_apply_import_conflict_same_name_different_level_different_hash() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_hash_id_import_gives_two_fresh_vaults_matching_logical_ids() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let maintenance_state = VaultMaintenanceState::default();
+        let bundle = || {
+            vec![
+                preview_item_for_test(test_spell("Shared Shield", 1, "Wards")),
+                preview_item_for_test(test_spell("Shared Sleep", 1, "Slumbers")),
+            ]
+        };
+        let logical_ids = |conn: &Connection| -> Vec<(i64, Option<String>)> {
+            let mut stmt = conn
+                .prepare(
+                    "SELECT id, logical_id FROM spell WHERE name LIKE 'Shared %' ORDER BY name",
+                )
+                .unwrap();
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
 
-        let conn = setup_import_apply_test_db();
-        let existing_spell = test_spell("Mirror Veil", 1, "Existing spell");
-        let incoming_spell = test_spell("Mirror Veil", 5, "Incoming spell with different content");
-        let existing_hash = test_hash(&existing_spell);
-        let incoming_hash = test_hash(&incoming_spell);
+        let mut vaults = Vec::new();
+        for local_spells in [0, 2] {
+            let conn = Connection::open_in_memory().expect("open vault db");
+            crate::db::migrations::load_migrations(&conn).expect("migrate vault db");
+            // Local-only spells shift the autoincrement ids the bundle rows receive.
+            let local: Vec<_> = (0..local_spells)
+                .map(|n| preview_item_for_test(test_spell(&format!("Local {n}"), 2, "Local")))
+                .collect();
+            apply_import_spell_json_with_maintenance(
+                &conn,
+                temp_dir.path(),
+                &maintenance_state,
+                local,
+                None,
+                false,
+            )
+            .expect("seed local spells");
+            apply_import_spell_json_with_maintenance(
+                &conn,
+                temp_dir.path(),
+                &maintenance_state,
+                bundle(),
+                None,
+                true,
+            )
+            .expect("import bundle with hash ids");
+            vaults.push(logical_ids(&conn));
+        }
 
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
+        let expected: Vec<Option<String>> = bundle()
+            .into_iter()
+            .map(|item| Some(item.content_hash))
+            .collect();
+        let ids =
+            |rows: &[(i64, Option<String>)]| rows.iter().map(|r| r.1.clone()).collect::<Vec<_>>();
+        assert_ne!(
+            vaults[0][0].0, vaults[1][0].0,
+            "local ids diverge between vaults"
+        );
+        assert_eq!(ids(&vaults[0]), expected);
+        assert_eq!(ids(&vaults[1]), expected);
+    }
 
-        let result = apply_import_spell_json_impl(
+    #[test]
+    fn test_hash_id_import_updates_the_row_sharing_a_logical_id() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let maintenance_state = VaultMaintenanceState::default();
+        let conn = Connection::open_in_memory().expect("open vault db");
+        crate::db::migrations::load_migrations(&conn).expect("migrate vault db");
+        let logical_id = "5".repeat(64);
+        let synced = |description: &str| {
+            let mut spell = test_spell("Synced Shield", 1, description);
+            spell.logical_id = Some(logical_id.clone());
+            preview_item_for_test(spell)
+        };
+
+        apply_import_spell_json_with_maintenance(
             &conn,
-            vec![preview_item_for_test(incoming_spell.clone())],
+            temp_dir.path(),
+            &maintenance_state,
+            vec![synced("Wards")],
             None,
+            true,
         )
-        .expect("apply import should succeed");
+        .expect("first sync");
+        let result = apply_import_spell_json_with_maintenance(
+            &conn,
+            temp_dir.path(),
+            &maintenance_state,
+            vec![synced("Wards better")],
+            None,
+            true,
+        )
+        .expect("second sync");
+        assert_eq!(result.imported_count, 0);
+        assert!(result.conflicts.is_empty());
 
-        assert_eq!(result.imported_count, 0, "conflict should block insert");
-        assert_eq!(result.conflicts.len(), 1, "one conflict should be emitted");
-        assert_eq!(result.conflicts[0].existing_id, 1);
-        assert_eq!(result.conflicts[0].incoming_name, incoming_spell.name);
+        let rows: Vec<(String, Option<String>)> = conn
+            .prepare("SELECT description, logical_id FROM spell WHERE name = 'Synced Shield'")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
         assert_eq!(
-            result.conflicts[0].incoming_content_hash, incoming_hash,
-            "conflict should track incoming hash"
+            rows,
+            vec![("Wards better".to_string(), Some(logical_id.clone()))]
         );
+
+        // The same logical id arriving with content another row already holds is reported.
+        apply_import_spell_json_with_maintenance(
+            &conn,
+            temp_dir.path(),
+            &maintenance_state,
+            vec![preview_item_for_test(test_spell(
+                "Other Shield",
+                1,
+                "Wards",
+            ))],
+            None,
+            false,
+        )
+        .expect("seed colliding content");
+        let mut colliding = test_spell("Other Shield", 1, "Wards");
+        colliding.logical_id = Some(logical_id.clone());
+        let result = apply_import_spell_json_with_maintenance(
+            &conn,
+            temp_dir.path(),
+            &maintenance_state,
+            vec![preview_item_for_test(colliding)],
+            None,
+            true,
+        )
+        .expect("colliding sync");
+        assert_eq!(result.warnings.len(), 1, "{:?}", result.warnings);
+        assert!(result.warnings[0].contains(&logical_id));
     }
 
     #[test]
-    fn test_apply_import_materializes_vault_file_after_commit() {
+    fn test_ndjson_export_round_trips_through_streaming_import() {
         let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let maintenance_state = VaultMaintenanceState::default();
+        let open_db = || {
+            let conn = setup_import_apply_test_db();
+            create_change_log_table(&conn);
+            create_hash_reference_tables(&conn);
+            conn.execute_batch(include_str!(
+                "../../../../../db/migrations/0020_canonical_cache.sql"
+            ))
+            .expect("create canonical_cache");
+            conn
+        };
 
-        let conn = setup_import_apply_test_db();
-        create_artifact_table_for_get_spell(&conn);
-        let incoming_spell = test_spell("Vault Import", 4, "Imported into vault storage");
-        let stored_hash = incoming_spell.compute_hash().expect("hash");
+        let mut seed = String::new();
+        for i in 0..60 {
+            let spell = test_spell(&format!("Streamed Spell {i:02}"), i % 9 + 1, "Streams");
+            seed.push_str(&serde_json::to_string(&spell).expect("serialize seed spell"));
+            seed.push('\n');
+        }
+        seed.push_str("\n{not json}\n");
+        // Longer than the line cap: dropped unbuffered and reported, the rest still imports.
+        seed.push_str(&format!("\"{}\"\n", "x".repeat(8 * 1024)));
+        let last = test_spell("Streamed Spell After Cap", 1, "Streams");
+        seed.push_str(&serde_json::to_string(&last).expect("serialize last spell"));
+
+        let source = open_db();
+        let seeded = import_ndjson_from_reader(
+            &source,
+            temp_dir.path(),
+            &maintenance_state,
+            seed.as_bytes(),
+            SourceRefUrlPolicy::default(),
+            7,
+            4 * 1024,
+        )
+        .expect("seed import");
+        assert_eq!(seeded.imported_count, 61);
+        assert_eq!(seeded.imported_ids.len(), 61);
+        assert_eq!(seeded.failure_count, 2);
+        assert_eq!(seeded.failures[0].spell_name, "line 62");
+        assert_eq!(seeded.failures[1].spell_name, "line 63");
+        assert!(seeded.failures[1].reason.contains("exceeds"));
+        source
+            .execute(
+                "DELETE FROM spell WHERE name = 'Streamed Spell After Cap'",
+                [],
+            )
+            .expect("drop capped-test spell");
 
-        let result = apply_import_spell_json_impl(
-            &conn,
-            vec![PreviewSpellJsonItem {
-                spell: incoming_spell,
-                content_hash: stored_hash.clone(),
-                warnings: vec![],
-            }],
+        let path = temp_dir.path().join("library.ndjson");
+        let written = crate::commands::export::write_spells_ndjson(
+            &source,
             None,
+            fs::File::create(&path).expect("create ndjson file"),
         )
-        .expect("apply import should succeed");
+        .expect("export ndjson");
+        assert_eq!(written, 60);
+        let exported = fs::read_to_string(&path).expect("read ndjson file");
+        assert_eq!(exported.lines().count(), 60);
+
+        let target = open_db();
+        let result = import_ndjson_from_reader(
+            &target,
+            temp_dir.path(),
+            &maintenance_state,
+            std::io::BufReader::new(fs::File::open(&path).expect("open ndjson file")),
+            SourceRefUrlPolicy::default(),
+            7,
+            4 * 1024,
+        )
+        .expect("reimport ndjson");
+        assert_eq!(result.imported_count, 60);
+        assert!(result.failures.is_empty());
 
-        assert_eq!(result.imported_count, 1, "spell should be inserted");
-        assert!(
-            temp_dir
-                .path()
-                .join("spells")
-                .join(format!("{stored_hash}.json"))
-                .exists(),
-            "json import should write the canonical spell file into the vault"
+        let mut reexported = Vec::new();
+        crate::commands::export::write_spells_ndjson(&target, None, &mut reexported)
+            .expect("re-export ndjson");
+        assert_eq!(
+            String::from_utf8(reexported).expect("utf8 ndjson"),
+            exported
         );
     }
 
     #[test]
-    fn test_apply_import_db_commit_before_vault_write() {
-        // DB is committed first; vault write is a best-effort follow-up.
-        // If vault write fails, the error is surfaced but the DB row remains committed.
-        let long_root = std::env::temp_dir()
-            .join("spellbook-import")
-            .join("a".repeat(240));
-        let _env = VaultTestEnvGuard::with_root(long_root.clone()).expect("set isolated vault env");
+    fn test_import_conflict_does_not_trigger_post_import_gc() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
 
         let conn = setup_import_apply_test_db();
         create_artifact_table_for_get_spell(&conn);
-        let incoming_item = preview_item_for_test(test_spell(
-            "Vault Import Failure",
-            4,
-            "DB committed even when vault write fails",
-        ));
+        let maintenance_state = VaultMaintenanceState::default();
+        let existing_spell = test_spell("Conflict Spell", 1, "Existing");
+        let existing_hash = test_hash(&existing_spell);
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
 
-        let err = apply_import_spell_json_impl(&conn, vec![incoming_item], None)
-            .expect_err("vault write failure should surface an error");
-        assert!(err.to_string().contains("Windows path length"));
+        let orphan_hash = "2".repeat(64);
+        let spells_dir = temp_dir.path().join("spells");
+        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
+        std::fs::write(
+            spells_dir.join(format!("{orphan_hash}.json")),
+            r#"{"name":"Orphan","tradition":"ARCANE","level":1,"description":"Orphan","school":"Abjuration"}"#,
+        )
+        .expect("write orphan file");
+        let incoming_item =
+            preview_item_for_test(test_spell("Conflict Spell", 3, "Incoming conflict"));
 
-        // DB row should be committed even though vault file write failed
-        let spell_count: i64 = conn
-            .query_row("SELECT COUNT(*) FROM spell", [], |row| row.get(0))
-            .expect("query spell count");
-        assert_eq!(
-            spell_count, 1,
-            "DB transaction is committed before vault write; spell row must be present"
+        let result = apply_import_spell_json_with_maintenance(
+            &conn,
+            temp_dir.path(),
+            &maintenance_state,
+            vec![incoming_item],
+            None,
+            false,
+        )
+        .expect("conflict-only import should return result");
+
+        assert_eq!(result.imported_count, 0);
+        assert_eq!(result.conflicts.len(), 1);
+        assert!(
+            spells_dir.join(format!("{orphan_hash}.json")).exists(),
+            "conflict-only import should not trigger post-import gc"
         );
     }
 
     #[test]
-    fn test_manual_gc_is_blocked_while_import_guard_is_active() {
+    fn test_replace_only_import_triggers_post_import_gc() {
         let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
 
         let conn = setup_import_apply_test_db();
         let maintenance_state = VaultMaintenanceState::default();
-        let _guard = maintenance_state
-            .start_import()
-            .expect("import guard should be acquired");
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
 
-        let err = optimize_vault_with_root(&conn, temp_dir.path(), Some(&maintenance_state))
-            .expect_err("manual gc should be rejected during active import");
+        let existing_spell = test_spell("Replace GC", 4, "Original description");
+        let existing_hash = test_hash(&existing_spell);
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
+
+        let spells_dir = temp_dir.path().join("spells");
+        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
+        std::fs::write(
+            spells_dir.join(format!("{existing_hash}.json")),
+            serde_json::to_string(&existing_spell).expect("serialize existing spell"),
+        )
+        .expect("write existing spell file");
+
+        let incoming_item =
+            preview_item_for_test(test_spell("Replace GC", 4, "Updated description"));
+        let new_hash = incoming_item.content_hash.clone();
+        let resolve_options = ImportSpellJsonResolveOptions {
+            resolutions: vec![ImportSpellJsonConflictResolution {
+                existing_id: 1,
+                incoming_content_hash: new_hash.clone(),
+                action: "replace_with_new".to_string(),
+            }],
+            default_action: None,
+        };
+
+        let result = apply_import_spell_json_with_maintenance(
+            &conn,
+            temp_dir.path(),
+            &maintenance_state,
+            vec![incoming_item],
+            Some(resolve_options),
+            false,
+        )
+        .expect("replace import should succeed");
+
+        assert_eq!(
+            result.imported_count, 0,
+            "replace should not count as a new insert"
+        );
+        assert_eq!(
+            result
+                .conflicts_resolved
+                .as_ref()
+                .expect("resolved counts")
+                .replace_count,
+            1
+        );
         assert!(
-            err.to_string().contains("import"),
-            "unexpected error: {err}"
+            !spells_dir.join(format!("{existing_hash}.json")).exists(),
+            "replace-only imports should still trigger post-import GC"
+        );
+        assert!(
+            spells_dir.join(format!("{new_hash}.json")).exists(),
+            "new hash file should remain after replace"
         );
     }
 
     #[test]
-    fn test_run_with_import_maintenance_blocks_manual_gc_for_legacy_flows() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-
-        let conn = setup_import_apply_test_db();
-        let maintenance_state = VaultMaintenanceState::default();
+    fn test_preview_import_rewrites_tampered_id_to_recomputed_hash() {
+        let mut incoming_spell = test_spell("Tampered Id", 2, "Import payload");
+        incoming_spell.id = Some("x".repeat(64));
+        let payload = serde_json::to_string(&incoming_spell).expect("serialize incoming spell");
 
-        let err = run_with_import_maintenance(&maintenance_state, || {
-            let _ = optimize_vault_with_root(&conn, temp_dir.path(), Some(&maintenance_state))?;
-            Ok::<(), AppError>(())
-        })
-        .expect_err("legacy import wrapper should block manual gc during active import");
+        let preview = tauri::async_runtime::block_on(preview_import_spell_json(payload, None))
+            .expect("preview import");
 
-        assert!(
-            err.to_string().contains("import"),
-            "unexpected error: {err}"
+        assert_eq!(preview.spells.len(), 1);
+        let stored_spell = &preview.spells[0].spell;
+        assert_eq!(
+            stored_spell.id.as_deref(),
+            Some(preview.spells[0].content_hash.as_str()),
+            "preview items should carry the trusted recomputed content hash in spell.id"
         );
     }
 
     #[test]
-    fn test_run_post_import_gc_if_needed_removes_orphans_for_legacy_imports() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_apply_import_same_hash_dedups_before_name_conflict() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
 
         let conn = setup_import_apply_test_db();
-        let live_spell = test_spell("Legacy Import Live", 2, "Live spell");
-        let live_hash = test_hash(&live_spell);
-        insert_spell_for_apply_test(&conn, 1, &live_spell, &live_hash);
+        let existing_spell = test_spell("Storm Cage", 1, "Existing spell");
+        let mut incoming_spell = test_spell("Storm Cage", 1, "Existing spell");
+        incoming_spell.source_refs = vec![SourceRef {
+            system: Some("AD&D 2e".to_string()),
+            book: "PHB".to_string(),
+            page: Some(serde_json::json!("42")),
+            url: None,
+            note: None,
+        }];
+        let shared_hash = test_hash(&existing_spell);
+        let incoming_item = preview_item_for_test(incoming_spell);
 
-        let orphan_hash = "l".repeat(64);
-        let spells_dir = temp_dir.path().join("spells");
-        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
-        std::fs::write(
-            spells_dir.join(format!("{live_hash}.json")),
-            serde_json::to_string(&live_spell).expect("serialize live spell"),
-        )
-        .expect("write live spell file");
-        std::fs::write(
-            spells_dir.join(format!("{orphan_hash}.json")),
-            r#"{"name":"Orphan","tradition":"ARCANE","level":1,"description":"Orphan","school":"Abjuration"}"#,
-        )
-        .expect("write orphan file");
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &shared_hash);
 
-        run_post_import_gc_if_needed(&conn, temp_dir.path(), 1).expect("post-import gc should run");
+        let result = apply_import_spell_json_impl(&conn, vec![incoming_item], None)
+            .expect("apply import should succeed");
 
-        assert!(spells_dir.join(format!("{live_hash}.json")).exists());
-        assert!(!spells_dir.join(format!("{orphan_hash}.json")).exists());
+        assert!(
+            result.conflicts.is_empty(),
+            "hash match should dedup before conflict detection"
+        );
+        assert_eq!(result.imported_count, 0, "no new rows should be inserted");
+        assert_eq!(
+            result.duplicates_skipped.total, 1,
+            "existing hash should be counted as duplicate handling"
+        );
+        let spell_count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM spell", [], |row| row.get(0))
+            .expect("count spells");
+        assert_eq!(spell_count, 1, "dedup should keep only one spell row");
     }
 
     #[test]
-    fn test_run_post_import_gc_if_needed_skips_gc_when_no_legacy_mutation() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_apply_import_dedup_counters_track_merged_vs_no_change() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
 
         let conn = setup_import_apply_test_db();
-        let orphan_hash = "m".repeat(64);
-        let spells_dir = temp_dir.path().join("spells");
-        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
-        std::fs::write(
-            spells_dir.join(format!("{orphan_hash}.json")),
-            r#"{"name":"Orphan","tradition":"ARCANE","level":1,"description":"Orphan","school":"Abjuration"}"#,
-        )
-        .expect("write orphan file");
+        let existing_spell = test_spell("Echo Ward", 2, "Existing spell");
+        let shared_hash = test_hash(&existing_spell);
 
-        run_post_import_gc_if_needed(&conn, temp_dir.path(), 0)
-            .expect("post-import gc should be skipped cleanly");
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &shared_hash);
 
-        assert!(
-            spells_dir.join(format!("{orphan_hash}.json")).exists(),
-            "gc should not run when no legacy mutations were applied"
+        let mut first_incoming = test_spell("Echo Ward", 2, "Existing spell");
+        first_incoming.source_refs = vec![SourceRef {
+            system: Some("AD&D 2e".to_string()),
+            book: "Tome of Magic".to_string(),
+            page: Some(serde_json::json!("55")),
+            url: None,
+            note: None,
+        }];
+        let mut second_incoming = test_spell("Echo Ward", 2, "Existing spell");
+        second_incoming.source_refs = first_incoming.source_refs.clone();
+        let first_item = preview_item_for_test(first_incoming);
+        let second_item = preview_item_for_test(second_incoming);
+
+        let result = apply_import_spell_json_impl(&conn, vec![first_item, second_item], None)
+            .expect("apply import should succeed");
+
+        assert_eq!(
+            result.imported_count, 0,
+            "dedup path should not insert rows"
         );
+        assert_eq!(result.duplicates_skipped.total, 2);
+        assert_eq!(result.duplicates_skipped.merged_count, 1);
+        assert_eq!(result.duplicates_skipped.no_change_count, 1);
     }
 
     #[test]
-    fn test_resolve_import_conflicts_with_conn_and_root_overwrite_cascades_hash_references() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_apply_import_conflict_resolution_branches_keep_replace_keep_both() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_import_apply_test_db();
         create_change_log_table(&conn);
         create_hash_reference_tables(&conn);
 
-        let existing_spell = test_spell("Legacy Resolve", 2, "Original description");
-        let updated_spell = test_spell("Legacy Resolve", 2, "Updated description");
-        let old_hash = test_hash(&existing_spell);
-        let new_hash = test_hash(&updated_spell);
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &old_hash);
+        let keep_existing_spell = test_spell("Aegis Shell", 1, "Keep me");
+        let replace_existing_spell = test_spell("Moon Lance", 3, "Old replace target");
+        let keep_both_existing_spell = test_spell("Twin Flame", 4, "Original twin");
+        let keep_existing_hash = test_hash(&keep_existing_spell);
+        let replace_old_hash = test_hash(&replace_existing_spell);
+        let keep_both_existing_hash = test_hash(&keep_both_existing_spell);
+
+        insert_spell_for_apply_test(&conn, 1, &keep_existing_spell, &keep_existing_hash);
+        insert_spell_for_apply_test(&conn, 2, &replace_existing_spell, &replace_old_hash);
+        insert_spell_for_apply_test(
+            &conn,
+            3,
+            &keep_both_existing_spell,
+            &keep_both_existing_hash,
+        );
         conn.execute(
-            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 1, ?)",
-            params![old_hash.clone()],
+            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 2, ?)",
+            params![replace_old_hash],
         )
         .expect("seed character_class_spell hash reference");
         conn.execute(
             "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
-             VALUES (1, 1, 'pdf', 'legacy-resolve.pdf', 'artifact-hash', '2026-01-01T00:00:00Z', ?)",
-            params![old_hash.clone()],
+             VALUES (1, 2, 'pdf', 'moon-lance.pdf', 'artifact-hash', '2026-01-01T00:00:00Z', ?)",
+            params![replace_old_hash],
         )
         .expect("seed artifact hash reference");
 
-        let spells_dir = temp_dir.path().join("spells");
-        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
-        std::fs::write(
-            spells_dir.join(format!("{old_hash}.json")),
-            serde_json::to_string(&existing_spell).expect("serialize existing spell"),
-        )
-        .expect("write existing spell file");
+        let keep_existing_incoming = test_spell("Aegis Shell", 1, "Incoming but skipped");
+        let replace_incoming = test_spell("Moon Lance", 3, "Replacement description");
+        let keep_both_incoming = test_spell("Twin Flame", 4, "Imported twin");
+        let keep_existing_item = preview_item_for_test(keep_existing_incoming);
+        let replace_item = preview_item_for_test(replace_incoming.clone());
+        let keep_both_item = preview_item_for_test(keep_both_incoming.clone());
+        let keep_existing_incoming_hash = keep_existing_item.content_hash.clone();
+        let _replace_new_hash_prev = replace_item.content_hash.clone();
+        let _keep_both_new_hash_prev = keep_both_item.content_hash.clone();
+        let mut renamed_keep_both_spell = keep_both_item.spell.clone();
+        renamed_keep_both_spell.name = "Twin Flame (1)".to_string();
+        let renamed_keep_both_hash = test_hash(&renamed_keep_both_spell);
+        let replace_new_hash = replace_incoming.compute_hash().expect("replace spell hash");
+        let mut keep_both_spell_with_suffix = keep_both_incoming.clone();
+        keep_both_spell_with_suffix.name = "Twin Flame (1)".to_string();
+        let _keep_both_new_hash = keep_both_spell_with_suffix
+            .compute_hash()
+            .expect("keep_both spell hash");
+
+        let resolve_options = ImportSpellJsonResolveOptions {
+            resolutions: vec![
+                ImportSpellJsonConflictResolution {
+                    existing_id: 1,
+                    incoming_content_hash: keep_existing_incoming_hash.clone(),
+                    action: "keep_existing".to_string(),
+                },
+                ImportSpellJsonConflictResolution {
+                    existing_id: 2,
+                    incoming_content_hash: replace_new_hash.clone(),
+                    action: "replace_with_new".to_string(),
+                },
+                ImportSpellJsonConflictResolution {
+                    existing_id: 3,
+                    incoming_content_hash: keep_both_item.content_hash.clone(),
+                    action: "keep_both".to_string(),
+                },
+            ],
+            default_action: None,
+        };
 
-        let result = resolve_import_conflicts_with_conn_and_root(
+        let result = apply_import_spell_json_impl(
             &conn,
-            temp_dir.path(),
-            vec![ImportConflictResolution {
-                action: "overwrite".to_string(),
-                existing_id: 1,
-                spell: Some(spell_update_for_test(1, &updated_spell)),
-                artifact: None,
-            }],
+            vec![keep_existing_item, replace_item, keep_both_item],
+            Some(resolve_options),
         )
-        .expect("conflict resolution should succeed");
+        .expect("apply import should succeed");
 
-        assert_eq!(result.resolved.len(), 1);
+        assert!(
+            result.conflicts.is_empty(),
+            "all conflicts should be resolved"
+        );
+        assert_eq!(result.imported_count, 1, "keep_both should insert one row");
+
+        let resolved = result
+            .conflicts_resolved
+            .expect("resolution counters should be present");
+        assert_eq!(resolved.keep_existing_count, 1);
+        assert_eq!(resolved.replace_count, 1);
+        assert_eq!(resolved.keep_both_count, 1);
+
+        let kept_hash: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query keep_existing hash");
+        assert_eq!(
+            kept_hash, keep_existing_hash,
+            "keep_existing should not mutate row"
+        );
+
+        let (replaced_hash, replaced_description): (String, String) = conn
+            .query_row(
+                "SELECT content_hash, description FROM spell WHERE id = 2",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query replaced row");
+        assert_eq!(replaced_hash, replace_new_hash);
+        assert_eq!(replaced_description, "Replacement description");
         let ccs_hash: String = conn
             .query_row(
                 "SELECT spell_content_hash FROM character_class_spell WHERE id = 1",
                 [],
                 |row| row.get(0),
             )
-            .expect("query character_class_spell hash");
-        assert_eq!(
-            ccs_hash, new_hash,
-            "legacy overwrite should cascade hash references in character_class_spell"
-        );
+            .expect("query character_class_spell hash after replace");
+        assert_eq!(ccs_hash, replace_new_hash);
         let artifact_hash: String = conn
             .query_row(
                 "SELECT spell_content_hash FROM artifact WHERE id = 1",
                 [],
                 |row| row.get(0),
             )
-            .expect("query artifact hash");
-        assert_eq!(
-            artifact_hash, new_hash,
-            "legacy overwrite should cascade hash references in artifact rows"
-        );
-        assert!(
-            spells_dir.join(format!("{new_hash}.json")).exists(),
-            "updated spell should still have a live vault file"
+            .expect("query artifact hash after replace");
+        assert_eq!(artifact_hash, replace_new_hash);
+
+        let keep_both_name: String = conn
+            .query_row(
+                "SELECT name FROM spell WHERE content_hash = ?",
+                params![renamed_keep_both_hash],
+                |row| row.get(0),
+            )
+            .expect("query keep_both row");
+        assert_eq!(keep_both_name, "Twin Flame (1)");
+    }
+
+    #[test]
+    fn test_keep_both_suffix_is_name_global_across_levels() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+
+        let base_spell = test_spell("Cross Fire", 1, "Original");
+        let existing_suffixed_other_level = test_spell("Cross Fire (1)", 9, "Higher level variant");
+        let base_hash = test_hash(&base_spell);
+        let existing_suffixed_hash = test_hash(&existing_suffixed_other_level);
+        insert_spell_for_apply_test(&conn, 1, &base_spell, &base_hash);
+        insert_spell_for_apply_test(
+            &conn,
+            2,
+            &existing_suffixed_other_level,
+            &existing_suffixed_hash,
         );
-        assert!(
-            spells_dir.join(format!("{old_hash}.json")).exists(),
-            "helper-level resolution should leave old hash cleanup to the command-level post-import GC"
+
+        let incoming_item = preview_item_for_test(test_spell("Cross Fire", 3, "Incoming variant"));
+        let mut renamed_incoming_spell = incoming_item.spell.clone();
+        renamed_incoming_spell.name = "Cross Fire (2)".to_string();
+        let renamed_hash = test_hash(&renamed_incoming_spell);
+
+        let incoming = test_spell("Cross Fire", 3, "Incoming variant");
+        let mut incoming_suffixed = incoming.clone();
+        incoming_suffixed.name = "Cross Fire (2)".to_string();
+        let _new_hash = incoming_suffixed
+            .compute_hash()
+            .expect("keep_both stored hash");
+        let resolve_options = ImportSpellJsonResolveOptions {
+            resolutions: vec![ImportSpellJsonConflictResolution {
+                existing_id: 1,
+                incoming_content_hash: incoming_item.content_hash.clone(), // Use original incoming hash to match conflict resolution
+                action: "keep_both".to_string(),
+            }],
+            default_action: None,
+        };
+
+        let result =
+            apply_import_spell_json_impl(&conn, vec![incoming_item], Some(resolve_options))
+                .expect("apply import should succeed");
+
+        assert_eq!(result.imported_count, 1, "keep_both should insert one row");
+        let inserted_name: String = conn
+            .query_row(
+                "SELECT name FROM spell WHERE content_hash = ?",
+                params![renamed_hash],
+                |row| row.get(0),
+            )
+            .expect("query inserted keep_both spell");
+        assert_eq!(
+            inserted_name, "Cross Fire (2)",
+            "suffix should increment globally by name even when (1) exists at another level"
         );
     }
 
     #[test]
-    fn test_upsert_import_artifact_persists_spell_content_hash_when_available() {
+    fn test_keep_both_rewrites_stored_id_to_renamed_hash() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_import_apply_test_db();
-        create_hash_reference_tables(&conn);
-        let expected_hash = "a".repeat(64);
+        let existing_spell = test_spell("Keep Both Id", 3, "Existing spell");
+        let existing_hash = test_hash(&existing_spell);
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
 
-        upsert_import_artifact(
-            &conn,
-            1,
-            &expected_hash,
-            &ImportArtifact {
-                r#type: "pdf".to_string(),
-                path: "spell.pdf".to_string(),
-                hash: "artifact-hash".to_string(),
-                imported_at: "2026-01-01T00:00:00Z".to_string(),
-            },
-        )
-        .expect("upsert artifact");
+        let incoming_item =
+            preview_item_for_test(test_spell("Keep Both Id", 4, "Incoming variant"));
+        let resolve_options = ImportSpellJsonResolveOptions {
+            resolutions: vec![ImportSpellJsonConflictResolution {
+                existing_id: 1,
+                incoming_content_hash: incoming_item.content_hash.clone(),
+                action: "keep_both".to_string(),
+            }],
+            default_action: None,
+        };
 
-        let stored_hash: String = conn
+        let result =
+            apply_import_spell_json_impl(&conn, vec![incoming_item], Some(resolve_options))
+                .expect("apply import should succeed");
+
+        assert_eq!(result.imported_count, 1);
+        let (stored_json, stored_hash): (String, String) = conn
             .query_row(
-                "SELECT spell_content_hash FROM artifact WHERE spell_id = 1",
+                "SELECT canonical_data, content_hash FROM spell WHERE name = 'Keep Both Id (1)'",
                 [],
-                |row| row.get(0),
+                |row| Ok((row.get(0)?, row.get(1)?)),
             )
-            .expect("query artifact spell_content_hash");
-        assert_eq!(stored_hash, expected_hash);
+            .expect("query keep_both canonical json");
+        let stored_spell: CanonicalSpell =
+            serde_json::from_str(&stored_json).expect("deserialize keep_both spell");
+
+        // Ensure the computed hash matches the one stored in `content_hash`
+        let recomputed_hash = stored_spell.compute_hash().expect("compute keep_both hash");
+        assert_eq!(
+            stored_hash,
+            recomputed_hash,
+            "keep_both should compute the renamed spell hash matching the recomputed DB content_hash"
+        );
     }
 
     #[test]
-    fn test_resolve_import_conflicts_with_conn_and_root_rolls_back_batch_on_error() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_apply_import_replace_failure_rolls_back_item_and_keeps_other_rows() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_import_apply_test_db();
         create_change_log_table(&conn);
         create_hash_reference_tables(&conn);
 
-        let existing_spell = test_spell("Legacy Partial Resolve", 4, "Original description");
-        let updated_spell = test_spell("Legacy Partial Resolve", 4, "Updated description");
-        let old_hash = test_hash(&existing_spell);
-        let new_hash = test_hash(&updated_spell);
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &old_hash);
+        let replace_existing_spell = test_spell("Rune Chain", 5, "Old rune chain");
+        let keep_both_existing_spell = test_spell("Solar Knot", 2, "Original knot");
+
+        let replace_old_hash = "n".repeat(64);
+        let keep_both_old_hash = "o".repeat(64);
+        let _keep_both_new_hash = "q".repeat(64);
+
+        let replace_incoming = test_spell("Rune Chain", 5, "Incoming replace should rollback");
+        let replace_new_hash = replace_incoming
+            .compute_hash()
+            .expect("replace incoming hash");
+
+        insert_spell_for_apply_test(&conn, 1, &replace_existing_spell, &replace_old_hash);
+        insert_spell_for_apply_test(&conn, 2, &keep_both_existing_spell, &keep_both_old_hash);
+        insert_spell_for_apply_test(&conn, 3, &replace_incoming.clone(), &replace_new_hash);
         conn.execute(
             "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 1, ?)",
-            params![old_hash.clone()],
+            params![replace_old_hash.clone()],
         )
-        .expect("seed character_class_spell hash reference");
+        .expect("seed old character_class_spell hash reference");
         conn.execute(
             "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
-             VALUES (1, 1, 'pdf', 'legacy-partial.pdf', 'artifact-hash', '2026-01-01T00:00:00Z', ?)",
-            params![old_hash.clone()],
-        )
-        .expect("seed artifact hash reference");
-
-        let spells_dir = temp_dir.path().join("spells");
-        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
-        std::fs::write(
-            spells_dir.join(format!("{old_hash}.json")),
-            serde_json::to_string(&existing_spell).expect("serialize existing spell"),
+             VALUES (1, 1, 'pdf', 'dummy.pdf', 'dummyhash', '2026-01-01', ?)",
+            params![replace_old_hash.clone()],
         )
-        .expect("write existing spell file");
-
-        let err = resolve_import_conflicts_with_conn_and_root(
-            &conn,
-            temp_dir.path(),
-            vec![
-                ImportConflictResolution {
-                    action: "overwrite".to_string(),
-                    existing_id: 1,
-                    spell: Some(spell_update_for_test(1, &updated_spell)),
-                    artifact: None,
+        .expect("seed old artifact hash reference");
+        let replace_item = preview_item_for_test(test_spell(
+            "Rune Chain",
+            5,
+            "Incoming replace should rollback",
+        ));
+        let keep_both_item =
+            preview_item_for_test(test_spell("Solar Knot", 2, "Incoming keep both"));
+        let replace_new_hash = replace_item.content_hash.clone();
+        let keep_both_new_hash = keep_both_item.content_hash.clone();
+        conn.execute(
+            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (2, 99, ?)",
+            params![replace_new_hash.clone()],
+        )
+        .expect("seed conflicting character_class_spell hash reference");
+
+        let _keep_both_incoming = test_spell("Solar Knot", 2, "Incoming keep both");
+
+        let resolve_options = ImportSpellJsonResolveOptions {
+            resolutions: vec![
+                ImportSpellJsonConflictResolution {
+                    existing_id: 2,
+                    incoming_content_hash: keep_both_new_hash.clone(),
+                    action: "keep_both".to_string(),
                 },
-                ImportConflictResolution {
-                    action: "bogus".to_string(),
+                ImportSpellJsonConflictResolution {
                     existing_id: 1,
-                    spell: None,
-                    artifact: None,
+                    incoming_content_hash: replace_new_hash.clone(),
+                    action: "replace_with_new".to_string(),
                 },
             ],
+            default_action: None,
+        };
+
+        let result = apply_import_spell_json_impl(
+            &conn,
+            vec![keep_both_item, replace_item],
+            Some(resolve_options),
         )
-        .expect_err("helper should still return the later validation error");
+        .expect("apply import should return per-item failures, not top-level error");
 
-        assert!(
-            err.to_string()
-                .contains("Unknown conflict resolution action"),
-            "unexpected error: {err}"
+        assert_eq!(result.imported_count, 1, "keep_both should still commit");
+        assert_eq!(result.failures.len(), 1, "replace item should fail");
+        assert_eq!(result.failures[0].spell_name, "Rune Chain");
+
+        let (final_hash, final_description): (String, String) = conn
+            .query_row(
+                "SELECT content_hash, description FROM spell WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query replace row after failure");
+        assert_eq!(
+            final_hash, replace_old_hash,
+            "failed replace must rollback hash"
         );
-        let stored_hash: String = conn
-            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
-                row.get(0)
-            })
-            .expect("query stored spell hash");
         assert_eq!(
-            stored_hash, old_hash,
-            "failed legacy resolution batch must roll back spell row mutations"
+            final_description, "Old rune chain",
+            "failed replace must rollback updated fields"
         );
-        let ccs_hash: String = conn
+        let ccs_rolled_back_hash: String = conn
             .query_row(
                 "SELECT spell_content_hash FROM character_class_spell WHERE id = 1",
                 [],
@@ -4092,10 +7424,10 @@ mod tests {
             )
             .expect("query character_class_spell hash after rollback");
         assert_eq!(
-            ccs_hash, old_hash,
-            "failed legacy resolution batch must roll back character_class_spell cascades"
+            ccs_rolled_back_hash, replace_old_hash,
+            "failed replace must rollback cascaded character_class_spell hash update"
         );
-        let artifact_hash: String = conn
+        let artifact_rolled_back_hash: String = conn
             .query_row(
                 "SELECT spell_content_hash FROM artifact WHERE id = 1",
                 [],
@@ -4103,907 +7435,1619 @@ mod tests {
             )
             .expect("query artifact hash after rollback");
         assert_eq!(
-            artifact_hash, old_hash,
-            "failed legacy resolution batch must roll back artifact cascades"
-        );
-        assert!(
-            !spells_dir.join(format!("{new_hash}.json")).exists(),
-            "failed legacy resolution batch must not materialize a new vault file"
-        );
-        assert!(
-            spells_dir.join(format!("{old_hash}.json")).exists(),
-            "helper-level resolution should leave old hash cleanup to the command-level post-import GC"
+            artifact_rolled_back_hash, replace_old_hash,
+            "failed replace must rollback cascaded artifact hash update"
         );
+
+        let keep_both_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM spell WHERE name = 'Solar Knot (1)'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query keep_both inserted row");
+        assert_eq!(keep_both_count, 1, "other savepoint commits should remain");
     }
 
     #[test]
-    fn test_resolve_import_conflicts_with_conn_and_root_rolls_back_when_vault_write_fails() {
-        let long_root = std::env::temp_dir()
-            .join("spellbook-legacy-import")
-            .join("b".repeat(400));
-        let _env = VaultTestEnvGuard::with_root(long_root.clone()).expect("set isolated vault env");
+    fn test_apply_import_conflict_deterministic_when_name_has_multiple_rows() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
         let conn = setup_import_apply_test_db();
-        create_change_log_table(&conn);
-        create_hash_reference_tables(&conn);
+        let existing_a = test_spell("Twin Sigil", 1, "Existing row A");
+        let existing_b = test_spell("Twin Sigil", 4, "Existing row B");
+        let incoming_spell = test_spell("Twin Sigil", 7, "Incoming row");
+        let hash_a = test_hash(&existing_a);
+        let hash_b = test_hash(&existing_b);
 
-        let existing_spell = test_spell("Legacy Resolve Failure", 4, "Original description");
-        let updated_spell = test_spell("Legacy Resolve Failure", 4, "Updated description");
-        let old_hash = test_hash(&existing_spell);
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &old_hash);
-        conn.execute(
-            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 1, ?)",
-            params![old_hash.clone()],
-        )
-        .expect("seed character_class_spell hash reference");
-        conn.execute(
-            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
-             VALUES (1, 1, 'pdf', 'legacy-resolve-failure.pdf', 'artifact-hash', '2026-01-01T00:00:00Z', ?)",
-            params![old_hash.clone()],
-        )
-        .expect("seed artifact hash reference");
-        let err = resolve_import_conflicts_with_conn_and_root(
-            &conn,
-            &long_root,
-            vec![ImportConflictResolution {
-                action: "overwrite".to_string(),
-                existing_id: 1,
-                spell: Some(spell_update_for_test(1, &updated_spell)),
-                artifact: None,
-            }],
-        )
-        .expect_err("vault write failure should roll back conflict resolution");
-        assert!(err.to_string().contains("Windows path length"));
+        insert_spell_for_apply_test(&conn, 1, &existing_a, &hash_a);
+        insert_spell_for_apply_test(&conn, 2, &existing_b, &hash_b);
 
-        let stored_description: String = conn
-            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
-                row.get(0)
-            })
-            .expect("query spell description");
-        let stored_hash: String = conn
-            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
-                row.get(0)
-            })
-            .expect("query spell hash");
-        let class_hash: String = conn
-            .query_row(
-                "SELECT spell_content_hash FROM character_class_spell WHERE id = 1",
-                [],
-                |row| row.get(0),
-            )
-            .expect("query character_class_spell hash");
-        let artifact_hash: String = conn
-            .query_row(
-                "SELECT spell_content_hash FROM artifact WHERE id = 1",
-                [],
-                |row| row.get(0),
-            )
-            .expect("query artifact hash");
+        let result =
+            apply_import_spell_json_impl(&conn, vec![preview_item_for_test(incoming_spell)], None)
+                .expect("apply import should succeed");
 
-        assert_eq!(stored_description, "Original description");
-        assert_eq!(stored_hash, old_hash);
-        assert_eq!(class_hash, old_hash);
-        assert_eq!(artifact_hash, old_hash);
+        assert_eq!(result.imported_count, 0, "conflict should block insert");
+        assert_eq!(result.conflicts.len(), 1, "one conflict should be emitted");
+        assert_eq!(
+            result.conflicts[0].existing_id, 1,
+            "name-only conflict should deterministically select the lowest id row"
+        );
     }
 
     #[test]
-    fn test_run_legacy_import_chunk_transaction_rolls_back_partial_mutations() {
+    fn test_replace_with_new_collision_error_includes_conflicting_spell_name_and_hash() {
         let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
         let conn = setup_import_apply_test_db();
-        create_change_log_table(&conn);
-        create_hash_reference_tables(&conn);
-        let existing_spell = test_spell("Legacy Chunk Rollback", 3, "Original description");
-        let existing_hash = test_hash(&existing_spell);
-        let updated_spell = test_spell("Legacy Chunk Rollback", 3, "Updated description");
-        let updated_hash = test_hash(&updated_spell);
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
+        let existing_spell = test_spell("Mirror Ward", 2, "Existing target spell");
+        let conflicting_spell = test_spell("Prismatic Net", 6, "Spell already using incoming hash");
+        let incoming_spell = test_spell("Mirror Ward", 2, "Incoming replacement spell");
+        let _old_hash = test_hash(&existing_spell);
+        let _collision_hash = test_hash(&conflicting_spell);
+        let collision_hash = conflicting_spell
+            .compute_hash()
+            .expect("conflicting spell hash");
+        let old_hash = "0".repeat(64);
+
+        insert_spell_for_apply_test(&conn, 1, &existing_spell, &old_hash);
+        insert_spell_for_apply_test(&conn, 2, &conflicting_spell, &collision_hash);
+
+        let mut item = preview_item_for_test(incoming_spell);
+        item.content_hash = collision_hash.clone();
+        let incoming_spell = conflicting_spell.clone();
+        let item = PreviewSpellJsonItem {
+            spell: incoming_spell,
+            content_hash: collision_hash.clone(),
+            warnings: vec![],
+        };
+
+        let err = replace_with_new_impl(&conn, 1, Some(&old_hash), &collision_hash, &item)
+            .expect_err("replace should fail when incoming hash belongs to another spell row");
+        let msg = err.to_string();
+        assert!(
+            msg.contains("Prismatic Net"),
+            "error should include conflicting spell name, got: {msg}"
+        );
+        assert!(
+            msg.contains(&collision_hash),
+            "error should include conflicting hash, got: {msg}"
+        );
+        assert!(
+            msg.contains("This imported version already exists"),
+            "error should include explicit already-exists guidance, got: {msg}"
+        );
+        assert!(
+            msg.contains("Keep Existing"),
+            "error should suggest Keep Existing action, got: {msg}"
+        );
+        assert!(
+            msg.contains("Keep Both"),
+            "error should suggest Keep Both action, got: {msg}"
+        );
+    }
+
+    // -----------------------------------------------------------------------
+    // resolve_artifact_spell_id tests
+    // -----------------------------------------------------------------------
+
+    fn setup_resolve_artifact_db() -> Connection {
+        let conn = Connection::open_in_memory().expect("open in-memory db");
+        conn.execute_batch(
+            "CREATE TABLE spell (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL DEFAULT '',
+                level INTEGER NOT NULL DEFAULT 1,
+                description TEXT NOT NULL DEFAULT '',
+                content_hash TEXT
+            );
+            CREATE TABLE artifact (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                spell_id INTEGER,
+                spell_content_hash TEXT,
+                path TEXT NOT NULL,
+                type TEXT NOT NULL DEFAULT 'source',
+                hash TEXT NOT NULL DEFAULT 'h',
+                imported_at TEXT NOT NULL DEFAULT '2026-01-01T00:00:00Z'
+            );",
+        )
+        .expect("create test schema");
+        conn
+    }
+
+    #[test]
+    fn test_resolve_artifact_spell_id_by_hash() {
+        let conn = setup_resolve_artifact_db();
+        conn.execute(
+            "INSERT INTO spell (id, content_hash) VALUES (42, 'hash-xyz')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, spell_content_hash, path) VALUES (1, NULL, 'hash-xyz', 'a.md')",
+            [],
+        )
+        .expect("insert artifact with hash only");
 
-        let err = run_legacy_import_chunk_transaction(&conn, |conn| {
-            conn.execute(
-                "UPDATE spell SET description = ?, canonical_data = ?, content_hash = ? WHERE id = 1",
-                params![
-                    updated_spell.description,
-                    serde_json::to_string(&updated_spell).expect("serialize updated spell"),
-                    updated_hash
-                ],
-            )?;
-            Err::<(), AppError>(AppError::Validation("boom".to_string()))
-        })
-        .expect_err("transaction helper should return the operation error");
+        let (spell_id, path) = resolve_artifact_spell_id(&conn, 1).expect("resolve ok");
+        assert_eq!(spell_id, 42, "should resolve spell_id from hash");
+        assert_eq!(path, "a.md");
+    }
 
-        assert!(err.to_string().contains("boom"), "unexpected error: {err}");
+    #[test]
+    fn test_resolve_artifact_spell_id_hash_fallback_to_spell_id() {
+        // Hash set but no matching spell row → fall back to spell_id column
+        let conn = setup_resolve_artifact_db();
+        conn.execute(
+            "INSERT INTO spell (id, content_hash) VALUES (7, 'different-hash')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, spell_content_hash, path) VALUES (1, 7, 'stale-hash', 'b.md')",
+            [],
+        )
+        .expect("insert artifact with stale hash but valid spell_id");
 
-        let stored_hash: String = conn
-            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
-                row.get(0)
-            })
-            .expect("query stored hash");
+        let (spell_id, path) = resolve_artifact_spell_id(&conn, 1).expect("resolve ok");
         assert_eq!(
-            stored_hash, existing_hash,
-            "failed chunk should roll back to the original live hash"
+            spell_id, 7,
+            "should fall back to spell_id when hash not found"
         );
+        assert_eq!(path, "b.md");
     }
 
     #[test]
-    fn test_successful_import_triggers_post_import_gc() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-
-        let conn = setup_import_apply_test_db();
-        create_change_log_table(&conn);
-        create_hash_reference_tables(&conn);
-        let maintenance_state = VaultMaintenanceState::default();
-        let orphan_hash = "y".repeat(64);
-        let spells_dir = temp_dir.path().join("spells");
-        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
-        std::fs::write(
-            spells_dir.join(format!("{orphan_hash}.json")),
-            r#"{"name":"Orphan","tradition":"ARCANE","level":1,"description":"Orphan","school":"Abjuration"}"#,
-        )
-        .expect("write orphan file");
-        let imported_item = preview_item_for_test(test_spell("GC Trigger", 2, "Should trigger GC"));
-
-        let result = apply_import_spell_json_with_maintenance(
-            &conn,
-            temp_dir.path(),
-            &maintenance_state,
-            vec![imported_item],
-            None,
+    fn test_resolve_artifact_spell_id_not_found_returns_error() {
+        // Neither hash nor spell_id resolves to an existing spell
+        let conn = setup_resolve_artifact_db();
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, spell_content_hash, path) VALUES (1, NULL, 'orphan-hash', 'c.md')",
+            [],
         )
-        .expect("import should succeed");
+        .expect("insert orphaned artifact");
 
-        assert_eq!(result.imported_count, 1);
+        let result = resolve_artifact_spell_id(&conn, 1);
         assert!(
-            !spells_dir.join(format!("{orphan_hash}.json")).exists(),
-            "post-import gc should remove orphaned spell files"
+            matches!(result, Err(AppError::NotFound(_))),
+            "should return NotFound when spell is gone"
         );
     }
 
     #[test]
-    fn test_import_conflict_does_not_trigger_post_import_gc() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-
-        let conn = setup_import_apply_test_db();
-        create_artifact_table_for_get_spell(&conn);
-        let maintenance_state = VaultMaintenanceState::default();
-        let existing_spell = test_spell("Conflict Spell", 1, "Existing");
-        let existing_hash = test_hash(&existing_spell);
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
-
-        let orphan_hash = "2".repeat(64);
-        let spells_dir = temp_dir.path().join("spells");
-        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
-        std::fs::write(
-            spells_dir.join(format!("{orphan_hash}.json")),
-            r#"{"name":"Orphan","tradition":"ARCANE","level":1,"description":"Orphan","school":"Abjuration"}"#,
+    fn test_resolve_artifact_spell_id_legacy_no_hash_column() {
+        // Old schema without spell_content_hash column — uses spell_id directly
+        let conn = Connection::open_in_memory().expect("open in-memory db");
+        conn.execute_batch(
+            "CREATE TABLE spell (
+                id INTEGER PRIMARY KEY,
+                content_hash TEXT
+            );
+            CREATE TABLE artifact (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                spell_id INTEGER,
+                path TEXT NOT NULL
+            );",
         )
-        .expect("write orphan file");
-        let incoming_item =
-            preview_item_for_test(test_spell("Conflict Spell", 3, "Incoming conflict"));
-
-        let result = apply_import_spell_json_with_maintenance(
-            &conn,
-            temp_dir.path(),
-            &maintenance_state,
-            vec![incoming_item],
-            None,
+        .expect("create legacy schema");
+        conn.execute("INSERT INTO spell (id, content_hash) VALUES (5, 'h')", [])
+            .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, path) VALUES (1, 5, 'legacy.md')",
+            [],
         )
-        .expect("conflict-only import should return result");
+        .expect("insert legacy artifact");
 
-        assert_eq!(result.imported_count, 0);
-        assert_eq!(result.conflicts.len(), 1);
-        assert!(
-            spells_dir.join(format!("{orphan_hash}.json")).exists(),
-            "conflict-only import should not trigger post-import gc"
-        );
+        let (spell_id, path) = resolve_artifact_spell_id(&conn, 1).expect("resolve ok");
+        assert_eq!(spell_id, 5);
+        assert_eq!(path, "legacy.md");
     }
 
     #[test]
-    fn test_replace_only_import_triggers_post_import_gc() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_collect_import_directory_files_filters_by_extension_and_size() {
+        let temp_dir = tempfile::tempdir().expect("create temp dir");
+        let root = temp_dir.path();
+        std::fs::write(root.join("magic_missile.md"), "# Magic Missile").unwrap();
+        std::fs::write(root.join("Sleep.MD"), "# Sleep").unwrap();
+        std::fs::write(root.join("notes.txt"), "not a spell").unwrap();
+        std::fs::write(root.join("huge.md"), "x".repeat(64)).unwrap();
+        std::fs::create_dir(root.join("nested")).unwrap();
+        std::fs::write(root.join("nested").join("shield.md"), "# Shield").unwrap();
+
+        let (paths, warnings) = collect_import_directory_files(root, false, 32).unwrap();
+        let names: Vec<String> = paths
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["Sleep.MD", "magic_missile.md"]);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("huge.md"), "{:?}", warnings);
 
-        let conn = setup_import_apply_test_db();
-        let maintenance_state = VaultMaintenanceState::default();
-        create_change_log_table(&conn);
-        create_hash_reference_tables(&conn);
+        let (paths, _) = collect_import_directory_files(root, true, 32).unwrap();
+        assert_eq!(paths.len(), 3);
+        assert!(paths.iter().any(|p| p.ends_with("nested/shield.md")));
 
-        let existing_spell = test_spell("Replace GC", 4, "Original description");
-        let existing_hash = test_hash(&existing_spell);
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
+        let missing = collect_import_directory_files(&root.join("absent"), true, 32);
+        assert!(matches!(missing, Err(AppError::Validation(_))));
+        let file = collect_import_directory_files(&root.join("notes.txt"), true, 32);
+        assert!(matches!(file, Err(AppError::Validation(_))));
+    }
 
-        let spells_dir = temp_dir.path().join("spells");
-        std::fs::create_dir_all(&spells_dir).expect("create spells dir");
+    #[test]
+    fn test_import_directory_hands_files_to_parser_in_place_and_imports_them() {
+        let vault = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let source_dir = tempfile::tempdir().expect("create source dir");
         std::fs::write(
-            spells_dir.join(format!("{existing_hash}.json")),
-            serde_json::to_string(&existing_spell).expect("serialize existing spell"),
+            source_dir.path().join("magic_missile.md"),
+            "# Magic Missile",
         )
-        .expect("write existing spell file");
-
-        let incoming_item =
-            preview_item_for_test(test_spell("Replace GC", 4, "Updated description"));
-        let new_hash = incoming_item.content_hash.clone();
-        let resolve_options = ImportSpellJsonResolveOptions {
-            resolutions: vec![ImportSpellJsonConflictResolution {
-                existing_id: 1,
-                incoming_content_hash: new_hash.clone(),
-                action: "replace_with_new".to_string(),
-            }],
-            default_action: None,
-        };
+        .unwrap();
+        std::fs::write(source_dir.path().join("sleep.md"), "# Sleep").unwrap();
+        let conn = Connection::open_in_memory().expect("open vault db");
+        crate::db::migrations::load_migrations(&conn).expect("migrate vault db");
+        let maintenance_state = VaultMaintenanceState::default();
 
-        let result = apply_import_spell_json_with_maintenance(
+        let mut parsed_paths = vec![];
+        // Stands in for the sidecar's `import` response for each chunk of paths.
+        let result = import_directory_with_conn(
             &conn,
-            temp_dir.path(),
+            vault.path(),
             &maintenance_state,
-            vec![incoming_item],
-            Some(resolve_options),
+            source_dir.path(),
+            false,
+            |chunk| {
+                parsed_paths.extend(chunk.iter().cloned());
+                let spells: Vec<_> = chunk
+                    .iter()
+                    .map(|path| {
+                        let stem = path.file_stem().unwrap().to_string_lossy();
+                        json!({
+                            "name": stem.replace('_', " "),
+                            "school": "Evocation",
+                            "level": 1,
+                            "description": format!("Parsed from {stem}"),
+                            "_source_file": path.to_string_lossy(),
+                        })
+                    })
+                    .collect();
+                let artifacts: Vec<_> = chunk
+                    .iter()
+                    .map(|path| {
+                        json!({
+                            "type": "source",
+                            "path": path.to_string_lossy(),
+                            "hash": "0".repeat(64),
+                            "importedAt": "2026-01-01T00:00:00Z",
+                        })
+                    })
+                    .collect();
+                Ok(json!({"spells": spells, "artifacts": artifacts, "conflicts": []}))
+            },
         )
-        .expect("replace import should succeed");
+        .expect("import directory");
 
         assert_eq!(
-            result.imported_count, 0,
-            "replace should not count as a new insert"
-        );
-        assert_eq!(
-            result
-                .conflicts_resolved
-                .as_ref()
-                .expect("resolved counts")
-                .replace_count,
-            1
-        );
-        assert!(
-            !spells_dir.join(format!("{existing_hash}.json")).exists(),
-            "replace-only imports should still trigger post-import GC"
+            parsed_paths,
+            vec![
+                source_dir.path().join("magic_missile.md"),
+                source_dir.path().join("sleep.md"),
+            ],
+            "the parser gets the user's files, not copies under imports/"
         );
+        assert!(!vault.path().join("imports").exists());
+        assert_eq!(result.spells.len(), 2);
+        let names: Vec<String> = conn
+            .prepare("SELECT name FROM spell ORDER BY name")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(names, vec!["magic missile", "sleep"]);
+        let artifact_paths: i64 = conn
+            .query_row("SELECT COUNT(*) FROM artifact", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(artifact_paths, 2);
         assert!(
-            spells_dir.join(format!("{new_hash}.json")).exists(),
-            "new hash file should remain after replace"
+            maintenance_state.start_import().is_ok(),
+            "the import guard is released after the directory import"
         );
     }
 
     #[test]
-    fn test_preview_import_rewrites_tampered_id_to_recomputed_hash() {
-        let mut incoming_spell = test_spell("Tampered Id", 2, "Import payload");
-        incoming_spell.id = Some("x".repeat(64));
-        let payload = serde_json::to_string(&incoming_spell).expect("serialize incoming spell");
-
-        let preview = tauri::async_runtime::block_on(preview_import_spell_json(payload, None))
-            .expect("preview import");
+    fn test_reparse_by_spell_id_prefers_primary_artifact() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let mut conn = setup_import_apply_test_db();
+        create_hash_reference_tables(&conn);
+        conn.execute(
+            "ALTER TABLE artifact ADD COLUMN is_primary INTEGER NOT NULL DEFAULT 0",
+            [],
+        )
+        .expect("add is_primary column");
+
+        let spell = test_spell("Two Source Spell", 2, "Original text");
+        let hash = test_hash(&spell);
+        insert_spell_for_apply_test(&conn, 1, &spell, &hash);
+
+        let text_path = temp_dir.path().join("original.md");
+        let scan_path = temp_dir.path().join("scan.pdf");
+        std::fs::write(&text_path, "# Two Source Spell").expect("write text artifact");
+        std::fs::write(&scan_path, "%PDF").expect("write scan artifact");
+        let text_path = text_path.to_string_lossy().to_string();
+        let scan_path = scan_path.to_string_lossy().to_string();
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
+             VALUES (1, 1, 'md', ?1, 'h1', '2026-01-01T00:00:00Z', ?3),
+                    (2, 1, 'pdf', ?2, 'h2', '2026-01-02T00:00:00Z', ?3)",
+            params![text_path, scan_path, hash],
+        )
+        .expect("seed artifacts");
 
-        assert_eq!(preview.spells.len(), 1);
-        let stored_spell = &preview.spells[0].spell;
+        // Without a primary, the oldest artifact is the reparse source.
         assert_eq!(
-            stored_spell.id.as_deref(),
-            Some(preview.spells[0].content_hash.as_str()),
-            "preview items should carry the trusted recomputed content hash in spell.id"
+            resolve_reparse_artifact_id(&conn, None, Some(1)).unwrap(),
+            1
         );
-    }
-
-    #[test]
-    fn test_apply_import_same_hash_dedups_before_name_conflict() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-
-        let conn = setup_import_apply_test_db();
-        let existing_spell = test_spell("Storm Cage", 1, "Existing spell");
-        let mut incoming_spell = test_spell("Storm Cage", 1, "Existing spell");
-        incoming_spell.source_refs = vec![SourceRef {
-            system: Some("AD&D 2e".to_string()),
-            book: "PHB".to_string(),
-            page: Some(serde_json::json!("42")),
-            url: None,
-            note: None,
-        }];
-        let shared_hash = test_hash(&existing_spell);
-        let incoming_item = preview_item_for_test(incoming_spell);
-
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &shared_hash);
 
-        let result = apply_import_spell_json_impl(&conn, vec![incoming_item], None)
-            .expect("apply import should succeed");
-
-        assert!(
-            result.conflicts.is_empty(),
-            "hash match should dedup before conflict detection"
+        set_primary_artifact_with_conn(&mut conn, 2).expect("mark scan primary");
+        assert_eq!(
+            resolve_reparse_artifact_id(&conn, None, Some(1)).unwrap(),
+            2
         );
-        assert_eq!(result.imported_count, 0, "no new rows should be inserted");
         assert_eq!(
-            result.duplicates_skipped.total, 1,
-            "existing hash should be counted as duplicate handling"
+            resolve_reparse_artifact_id(&conn, Some(1), Some(1)).unwrap(),
+            1
         );
-        let spell_count: i64 = conn
-            .query_row("SELECT COUNT(*) FROM spell", [], |row| row.get(0))
-            .expect("count spells");
-        assert_eq!(spell_count, 1, "dedup should keep only one spell row");
+
+        let ids = reparse_artifact_ids_with_conn(&conn, None, Some(vec![1])).unwrap();
+        assert_eq!(ids, vec![2]);
+        let plan = plan_artifact_reparse(&conn, &ids, false);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(plan[0].1.as_ref().unwrap(), &(1, scan_path.clone()));
+
+        let loaded = get_spell_from_conn(&conn, 1).unwrap().unwrap();
+        let artifacts = loaded.artifacts.unwrap();
+        assert_eq!(artifacts[0].id, 2);
+        assert!(artifacts[0].is_primary);
+        assert!(!artifacts[1].is_primary);
+
+        set_primary_artifact_with_conn(&mut conn, 1).expect("switch primary back");
+        let primary_flags: Vec<(i64, i64)> = conn
+            .prepare("SELECT id, is_primary FROM artifact ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(primary_flags, vec![(1, 1), (2, 0)]);
+
+        assert!(matches!(
+            set_primary_artifact_with_conn(&mut conn, 99),
+            Err(AppError::NotFound(_))
+        ));
     }
 
     #[test]
-    fn test_apply_import_dedup_counters_track_merged_vs_no_change() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-
+    fn test_reparse_artifact_batch_continues_past_missing_file() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_import_apply_test_db();
-        let existing_spell = test_spell("Echo Ward", 2, "Existing spell");
-        let shared_hash = test_hash(&existing_spell);
-
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &shared_hash);
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
 
-        let mut first_incoming = test_spell("Echo Ward", 2, "Existing spell");
-        first_incoming.source_refs = vec![SourceRef {
-            system: Some("AD&D 2e".to_string()),
-            book: "Tome of Magic".to_string(),
-            page: Some(serde_json::json!("55")),
-            url: None,
-            note: None,
-        }];
-        let mut second_incoming = test_spell("Echo Ward", 2, "Existing spell");
-        second_incoming.source_refs = first_incoming.source_refs.clone();
-        let first_item = preview_item_for_test(first_incoming);
-        let second_item = preview_item_for_test(second_incoming);
+        let present = test_spell("Present Spell", 1, "Old description");
+        let missing = test_spell("Missing Spell", 1, "Untouched description");
+        insert_spell_for_apply_test(&conn, 1, &present, &test_hash(&present));
+        insert_spell_for_apply_test(&conn, 2, &missing, &test_hash(&missing));
+
+        let present_path = temp_dir.path().join("present.md");
+        std::fs::write(&present_path, "# Present Spell").expect("write artifact file");
+        let present_path = present_path.to_string_lossy().to_string();
+        let missing_path = temp_dir
+            .path()
+            .join("missing.md")
+            .to_string_lossy()
+            .to_string();
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at)
+             VALUES (1, 1, 'md', ?, 'h1', '2026-01-01T00:00:00Z'),
+                    (2, 2, 'md', ?, 'h2', '2026-01-01T00:00:00Z')",
+            params![present_path, missing_path],
+        )
+        .expect("seed artifacts");
+
+        let plan = plan_artifact_reparse(&conn, &[1, 2], false);
+        let mut parsed_by_path = HashMap::new();
+        parsed_by_path.insert(
+            normalize_key(&present_path),
+            Ok(SpellDetail {
+                name: "Present Spell".into(),
+                school: Some("Abjuration".into()),
+                level: 1,
+                description: "New description".into(),
+                ..Default::default()
+            }),
+        );
 
-        let result = apply_import_spell_json_impl(&conn, vec![first_item, second_item], None)
-            .expect("apply import should succeed");
+        let mut progress_calls = vec![];
+        let results =
+            apply_artifact_reparse_batch(&conn, plan, &parsed_by_path, true, &mut |c, t| {
+                progress_calls.push((c, t))
+            });
 
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].artifact_id, 1);
+        assert_eq!(results[0].spell_id, Some(1));
+        assert!(results[0].error.is_none(), "{:?}", results[0].error);
+        assert!(results[0]
+            .changed_fields
+            .contains(&"description".to_string()));
+
+        assert_eq!(results[1].artifact_id, 2);
+        assert!(results[1]
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("no longer exists")));
+        assert_eq!(progress_calls, vec![(1, 2), (2, 2)]);
+
+        let descriptions: Vec<String> = conn
+            .prepare("SELECT description FROM spell ORDER BY id")
+            .expect("prepare")
+            .query_map([], |row| row.get(0))
+            .expect("query")
+            .collect::<Result<_, _>>()
+            .expect("collect");
         assert_eq!(
-            result.imported_count, 0,
-            "dedup path should not insert rows"
+            descriptions,
+            vec!["New description", "Untouched description"]
         );
-        assert_eq!(result.duplicates_skipped.total, 2);
-        assert_eq!(result.duplicates_skipped.merged_count, 1);
-        assert_eq!(result.duplicates_skipped.no_change_count, 1);
     }
 
     #[test]
-    fn test_apply_import_conflict_resolution_branches_keep_replace_keep_both() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_reparse_batch_result_reports_failed_item_alongside_successes() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_import_apply_test_db();
         create_change_log_table(&conn);
         create_hash_reference_tables(&conn);
 
-        let keep_existing_spell = test_spell("Aegis Shell", 1, "Keep me");
-        let replace_existing_spell = test_spell("Moon Lance", 3, "Old replace target");
-        let keep_both_existing_spell = test_spell("Twin Flame", 4, "Original twin");
-        let keep_existing_hash = test_hash(&keep_existing_spell);
-        let replace_old_hash = test_hash(&replace_existing_spell);
-        let keep_both_existing_hash = test_hash(&keep_both_existing_spell);
-
-        insert_spell_for_apply_test(&conn, 1, &keep_existing_spell, &keep_existing_hash);
-        insert_spell_for_apply_test(&conn, 2, &replace_existing_spell, &replace_old_hash);
-        insert_spell_for_apply_test(
-            &conn,
-            3,
-            &keep_both_existing_spell,
-            &keep_both_existing_hash,
-        );
-        conn.execute(
-            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 2, ?)",
-            params![replace_old_hash],
-        )
-        .expect("seed character_class_spell hash reference");
+        let present = test_spell("Present Spell", 1, "Old description");
+        let missing = test_spell("Missing Spell", 1, "Untouched description");
+        insert_spell_for_apply_test(&conn, 1, &present, &test_hash(&present));
+        insert_spell_for_apply_test(&conn, 2, &missing, &test_hash(&missing));
+
+        let present_path = temp_dir.path().join("present.md");
+        std::fs::write(&present_path, "# Present Spell").expect("write artifact file");
+        let present_path = present_path.to_string_lossy().to_string();
+        let missing_path = temp_dir
+            .path()
+            .join("missing.md")
+            .to_string_lossy()
+            .to_string();
         conn.execute(
-            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
-             VALUES (1, 2, 'pdf', 'moon-lance.pdf', 'artifact-hash', '2026-01-01T00:00:00Z', ?)",
-            params![replace_old_hash],
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at)
+             VALUES (1, 1, 'md', ?, 'h1', '2026-01-01T00:00:00Z'),
+                    (2, 2, 'md', ?, 'h2', '2026-01-01T00:00:00Z')",
+            params![present_path, missing_path],
         )
-        .expect("seed artifact hash reference");
+        .expect("seed artifacts");
+
+        let plan = plan_artifact_reparse(&conn, &[1, 2], false);
+        let mut parsed_by_path = HashMap::new();
+        parsed_by_path.insert(
+            normalize_key(&present_path),
+            Ok(SpellDetail {
+                name: "Present Spell".into(),
+                school: Some("Abjuration".into()),
+                level: 1,
+                description: "New description".into(),
+                ..Default::default()
+            }),
+        );
 
-        let keep_existing_incoming = test_spell("Aegis Shell", 1, "Incoming but skipped");
-        let replace_incoming = test_spell("Moon Lance", 3, "Replacement description");
-        let keep_both_incoming = test_spell("Twin Flame", 4, "Imported twin");
-        let keep_existing_item = preview_item_for_test(keep_existing_incoming);
-        let replace_item = preview_item_for_test(replace_incoming.clone());
-        let keep_both_item = preview_item_for_test(keep_both_incoming.clone());
-        let keep_existing_incoming_hash = keep_existing_item.content_hash.clone();
-        let _replace_new_hash_prev = replace_item.content_hash.clone();
-        let _keep_both_new_hash_prev = keep_both_item.content_hash.clone();
-        let mut renamed_keep_both_spell = keep_both_item.spell.clone();
-        renamed_keep_both_spell.name = "Twin Flame (1)".to_string();
-        let renamed_keep_both_hash = test_hash(&renamed_keep_both_spell);
-        let replace_new_hash = replace_incoming.compute_hash().expect("replace spell hash");
-        let mut keep_both_spell_with_suffix = keep_both_incoming.clone();
-        keep_both_spell_with_suffix.name = "Twin Flame (1)".to_string();
-        let _keep_both_new_hash = keep_both_spell_with_suffix
-            .compute_hash()
-            .expect("keep_both spell hash");
+        let batch = reparse_batch_result(apply_artifact_reparse_batch(
+            &conn,
+            plan,
+            &parsed_by_path,
+            true,
+            &mut |_, _| {},
+        ));
 
-        let resolve_options = ImportSpellJsonResolveOptions {
-            resolutions: vec![
-                ImportSpellJsonConflictResolution {
-                    existing_id: 1,
-                    incoming_content_hash: keep_existing_incoming_hash.clone(),
-                    action: "keep_existing".to_string(),
-                },
-                ImportSpellJsonConflictResolution {
-                    existing_id: 2,
-                    incoming_content_hash: replace_new_hash.clone(),
-                    action: "replace_with_new".to_string(),
-                },
-                ImportSpellJsonConflictResolution {
-                    existing_id: 3,
-                    incoming_content_hash: keep_both_item.content_hash.clone(),
-                    action: "keep_both".to_string(),
-                },
-            ],
-            default_action: None,
+        assert_eq!(batch.succeeded.len(), 1);
+        assert_eq!(batch.succeeded[0].artifact_id, 1);
+        assert!(batch.succeeded[0].error.is_none());
+        assert_eq!(batch.failed.len(), 1);
+        assert_eq!(batch.failed[0].input_id, 2);
+        assert!(batch.failed[0].error.contains("no longer exists"));
+    }
+
+    #[test]
+    fn test_build_conflict_fields_ignores_class_list_order() {
+        let existing = SpellDetail {
+            id: Some(1),
+            name: "Shield".into(),
+            school: Some("Abjuration".into()),
+            class_list: Some("Wizard, Bard".into()),
+            level: 1,
+            description: "A shimmering barrier.".into(),
+            ..Default::default()
+        };
+        let mut incoming = ImportSpell {
+            name: "Shield".into(),
+            school: Some("Abjuration".into()),
+            sphere: None,
+            class_list: Some("Bard,  Wizard".into()),
+            level: 1,
+            range: None,
+            components: None,
+            material_components: None,
+            casting_time: None,
+            duration: None,
+            area: None,
+            saving_throw: None,
+            damage: None,
+            magic_resistance: None,
+            reversible: None,
+            reverse_name: None,
+            description: "A shimmering barrier.".into(),
+            tags: None,
+            source: None,
+            edition: None,
+            author: None,
+            license: None,
+            source_file: None,
+            is_quest_spell: 0,
+            is_cantrip: 0,
+            schema_version: None,
+            class_levels: None,
+            canonical_data: None,
         };
 
-        let result = apply_import_spell_json_impl(
-            &conn,
-            vec![keep_existing_item, replace_item, keep_both_item],
-            Some(resolve_options),
-        )
-        .expect("apply import should succeed");
+        assert!(build_conflict_fields(&existing, &incoming).is_empty());
 
-        assert!(
-            result.conflicts.is_empty(),
-            "all conflicts should be resolved"
-        );
-        assert_eq!(result.imported_count, 1, "keep_both should insert one row");
+        incoming.level = 2;
+        let fields = build_conflict_fields(&existing, &incoming);
+        let names: Vec<&str> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(names, vec!["level"]);
+    }
 
-        let resolved = result
-            .conflicts_resolved
-            .expect("resolution counters should be present");
-        assert_eq!(resolved.keep_existing_count, 1);
-        assert_eq!(resolved.replace_count, 1);
-        assert_eq!(resolved.keep_both_count, 1);
+    #[test]
+    fn test_validate_preview_reports_schema_problems() {
+        let preview = |name: &str, school: Option<&str>| -> PreviewSpell {
+            serde_json::from_value(json!({
+                "name": name,
+                "level": 1,
+                "school": school,
+                "description": "X",
+                "_confidence": {},
+                "_source_file": "batch.md",
+            }))
+            .expect("preview spell")
+        };
+        let spells = vec![
+            preview("Valid Spell", Some("Evocation")),
+            preview("No Tradition", None),
+        ];
 
-        let kept_hash: String = conn
-            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
-                row.get(0)
-            })
-            .expect("query keep_existing hash");
+        let results = validate_preview_spells(&spells);
+        assert_eq!(results.len(), 2);
         assert_eq!(
-            kept_hash, keep_existing_hash,
-            "keep_existing should not mutate row"
+            results[0],
+            PreviewValidation {
+                name: "Valid Spell".into(),
+                valid: true,
+                error: None,
+            }
         );
-
-        let (replaced_hash, replaced_description): (String, String) = conn
-            .query_row(
-                "SELECT content_hash, description FROM spell WHERE id = 2",
-                [],
-                |row| Ok((row.get(0)?, row.get(1)?)),
-            )
-            .expect("query replaced row");
-        assert_eq!(replaced_hash, replace_new_hash);
-        assert_eq!(replaced_description, "Replacement description");
-        let ccs_hash: String = conn
-            .query_row(
-                "SELECT spell_content_hash FROM character_class_spell WHERE id = 1",
-                [],
-                |row| row.get(0),
-            )
-            .expect("query character_class_spell hash after replace");
-        assert_eq!(ccs_hash, replace_new_hash);
-        let artifact_hash: String = conn
-            .query_row(
-                "SELECT spell_content_hash FROM artifact WHERE id = 1",
-                [],
-                |row| row.get(0),
-            )
-            .expect("query artifact hash after replace");
-        assert_eq!(artifact_hash, replace_new_hash);
-
-        let keep_both_name: String = conn
-            .query_row(
-                "SELECT name FROM spell WHERE content_hash = ?",
-                params![renamed_keep_both_hash],
-                |row| row.get(0),
-            )
-            .expect("query keep_both row");
-        assert_eq!(keep_both_name, "Twin Flame (1)");
+        assert_eq!(results[1].name, "No Tradition");
+        assert!(!results[1].valid);
+        assert!(results[1]
+            .error
+            .as_deref()
+            .is_some_and(|e| e.contains("Must have a School")));
     }
 
     #[test]
-    fn test_keep_both_suffix_is_name_global_across_levels() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-        let conn = setup_import_apply_test_db();
+    fn test_preview_confidence_stats_aggregates_mixed_previews() {
+        let preview = |name: &str, confidence: serde_json::Value| -> PreviewSpell {
+            serde_json::from_value(json!({
+                "name": name,
+                "level": 1,
+                "description": "X",
+                "_confidence": confidence,
+                "_source_file": "batch.md",
+            }))
+            .expect("preview spell")
+        };
+        let spells = vec![
+            preview("Clean", json!({"name": 1.0, "level": 1.0})),
+            preview("Shaky", json!({"name": 0.9, "level": 0.3})),
+            preview("Decent", json!({"name": 0.8, "level": 0.6})),
+            preview("Unscored", json!({})),
+        ];
 
-        let base_spell = test_spell("Cross Fire", 1, "Original");
-        let existing_suffixed_other_level = test_spell("Cross Fire (1)", 9, "Higher level variant");
-        let base_hash = test_hash(&base_spell);
-        let existing_suffixed_hash = test_hash(&existing_suffixed_other_level);
-        insert_spell_for_apply_test(&conn, 1, &base_spell, &base_hash);
-        insert_spell_for_apply_test(
-            &conn,
-            2,
-            &existing_suffixed_other_level,
-            &existing_suffixed_hash,
-        );
+        let stats = preview_confidence_stats(&spells, 0.5);
+        assert!((stats.mean_confidence - 0.7666667).abs() < 1e-4);
+        assert_eq!(stats.low_confidence_count, 1);
+        assert_eq!(stats.fully_parsed_count, 1);
 
-        let incoming_item = preview_item_for_test(test_spell("Cross Fire", 3, "Incoming variant"));
-        let mut renamed_incoming_spell = incoming_item.spell.clone();
-        renamed_incoming_spell.name = "Cross Fire (2)".to_string();
-        let renamed_hash = test_hash(&renamed_incoming_spell);
+        let strict = preview_confidence_stats(&spells, 0.7);
+        assert_eq!(strict.low_confidence_count, 2);
 
-        let incoming = test_spell("Cross Fire", 3, "Incoming variant");
-        let mut incoming_suffixed = incoming.clone();
-        incoming_suffixed.name = "Cross Fire (2)".to_string();
-        let _new_hash = incoming_suffixed
-            .compute_hash()
-            .expect("keep_both stored hash");
-        let resolve_options = ImportSpellJsonResolveOptions {
-            resolutions: vec![ImportSpellJsonConflictResolution {
-                existing_id: 1,
-                incoming_content_hash: incoming_item.content_hash.clone(), // Use original incoming hash to match conflict resolution
-                action: "keep_both".to_string(),
-            }],
-            default_action: None,
+        assert_eq!(
+            preview_confidence_stats(&[], 0.5),
+            PreviewConfidenceStats::default()
+        );
+    }
+
+    #[test]
+    fn test_apply_import_tags_merges_batch_label() {
+        let spell = |name: &str, tags: Option<&str>| -> ImportSpell {
+            serde_json::from_value(json!({
+                "name": name,
+                "level": 1,
+                "description": "X",
+                "tags": tags,
+            }))
+            .expect("import spell")
         };
+        let mut spells = vec![
+            spell("Fresh Spell", None),
+            spell("Updated Spell", Some("Necromancy, Evil")),
+        ];
 
-        let result =
-            apply_import_spell_json_impl(&conn, vec![incoming_item], Some(resolve_options))
-                .expect("apply import should succeed");
+        apply_import_tags(&mut spells, &["Homebrew".to_string(), "Evil".to_string()]);
 
-        assert_eq!(result.imported_count, 1, "keep_both should insert one row");
-        let inserted_name: String = conn
-            .query_row(
-                "SELECT name FROM spell WHERE content_hash = ?",
-                params![renamed_hash],
-                |row| row.get(0),
-            )
-            .expect("query inserted keep_both spell");
+        assert_eq!(spells[0].tags.as_deref(), Some(r#"["Evil","Homebrew"]"#));
         assert_eq!(
-            inserted_name, "Cross Fire (2)",
-            "suffix should increment globally by name even when (1) exists at another level"
+            spells[1].tags.as_deref(),
+            Some(r#"["Evil","Homebrew","Necromancy"]"#)
         );
+
+        let mut untouched = vec![spell("Plain Spell", Some("Fire"))];
+        apply_import_tags(&mut untouched, &[]);
+        assert_eq!(untouched[0].tags.as_deref(), Some("Fire"));
     }
 
     #[test]
-    fn test_keep_both_rewrites_stored_id_to_renamed_hash() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-        let conn = setup_import_apply_test_db();
-        let existing_spell = test_spell("Keep Both Id", 3, "Existing spell");
-        let existing_hash = test_hash(&existing_spell);
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &existing_hash);
-
-        let incoming_item =
-            preview_item_for_test(test_spell("Keep Both Id", 4, "Incoming variant"));
-        let resolve_options = ImportSpellJsonResolveOptions {
-            resolutions: vec![ImportSpellJsonConflictResolution {
-                existing_id: 1,
-                incoming_content_hash: incoming_item.content_hash.clone(),
-                action: "keep_both".to_string(),
-            }],
-            default_action: None,
+    fn test_overwrite_import_keeps_existing_tags_alongside_batch_labels() {
+        let vault = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = Connection::open_in_memory().expect("open vault db");
+        crate::db::migrations::load_migrations(&conn).expect("migrate vault db");
+        let chunk = |description: &str, tags: Option<&str>| {
+            json!({
+                "spells": [{
+                    "name": "Tagged Spell",
+                    "school": "Evocation",
+                    "level": 2,
+                    "description": description,
+                    "tags": tags,
+                }],
+                "artifacts": [],
+                "conflicts": [],
+            })
         };
 
-        let result =
-            apply_import_spell_json_impl(&conn, vec![incoming_item], Some(resolve_options))
-                .expect("apply import should succeed");
+        apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("Original text", Some("Fire, Favorite")),
+            false,
+            false,
+            &[],
+            false,
+        )
+        .expect("initial import");
+        apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("Revised text", None),
+            true,
+            false,
+            &["Homebrew".to_string()],
+            false,
+        )
+        .expect("overwrite import");
 
-        assert_eq!(result.imported_count, 1);
-        let (stored_json, stored_hash): (String, String) = conn
+        let (description, tags): (String, String) = conn
             .query_row(
-                "SELECT canonical_data, content_hash FROM spell WHERE name = 'Keep Both Id (1)'",
+                "SELECT description, tags FROM spell WHERE name = 'Tagged Spell'",
                 [],
                 |row| Ok((row.get(0)?, row.get(1)?)),
             )
-            .expect("query keep_both canonical json");
-        let stored_spell: CanonicalSpell =
-            serde_json::from_str(&stored_json).expect("deserialize keep_both spell");
+            .expect("overwritten spell");
+        assert_eq!(description, "Revised text");
+        assert_eq!(tags, r#"["Favorite","Fire","Homebrew"]"#);
+
+        // Without batch labels an overwrite takes the incoming tags, so tags can be removed.
+        apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("Trimmed text", Some("Fire")),
+            true,
+            false,
+            &[],
+            false,
+        )
+        .expect("plain overwrite import");
+        let tags: String = conn
+            .query_row(
+                "SELECT tags FROM spell WHERE name = 'Tagged Spell'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("re-overwritten spell");
+        assert_eq!(parse_list_column(&tags), vec!["Fire".to_string()]);
+    }
+
+    #[test]
+    fn test_every_import_path_stores_class_list_and_tags_as_json_arrays() {
+        let vault = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = Connection::open_in_memory().expect("open vault db");
+        crate::db::migrations::load_migrations(&conn).expect("migrate vault db");
+        let stored_lists = |name: &str| -> (String, String) {
+            conn.query_row(
+                "SELECT class_list, tags FROM spell WHERE name = ?",
+                [name],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap_or_else(|e| panic!("stored lists for {name}: {e}"))
+        };
+        let expected = (
+            r#"["Cleric","Wizard"]"#.to_string(),
+            r#"["AoE","Fire"]"#.to_string(),
+        );
+        let legacy_spell = |name: &str, description: &str| {
+            json!({
+                "name": name,
+                "school": "Evocation",
+                "level": 2,
+                "description": description,
+                "classList": "Wizard, Cleric, Wizard",
+                "tags": "Fire, AoE",
+            })
+        };
+
+        // Canonical JSON import.
+        let mut canonical = test_spell("Json Path", 2, "Canonical import");
+        canonical.class_list = vec!["Wizard".to_string(), "Cleric".to_string()];
+        canonical.tags = vec!["Fire".to_string(), "AoE".to_string()];
+        let result = apply_import_spell_json_impl(
+            &conn,
+            vec![preview_item_for_test(canonical.clone())],
+            None,
+        )
+        .expect("json import");
+        assert_eq!(result.imported_count, 1);
+        assert_eq!(stored_lists("Json Path"), expected);
+        let detail =
+            get_spell_from_conn(&conn, result.imported_spells[0].id.expect("json spell id"))
+                .expect("load json spell")
+                .expect("json spell exists");
+        let changes = diff_canonical_vs_detail(&detail, &canonical);
+        assert!(
+            changes
+                .iter()
+                .all(|(field, _, _)| field != "class_list" && field != "tags"),
+            "JSON-stored lists should not diff against the same canonical lists: {changes:?}"
+        );
+
+        // Legacy file chunk: insert, then overwrite through the conflict update path.
+        for (description, allow_overwrite) in [("Chunk insert", false), ("Chunk overwrite", true)] {
+            apply_import_file_chunk_with_conn(
+                &conn,
+                vault.path(),
+                json!({
+                    "spells": [legacy_spell("Chunk Path", description)],
+                    "artifacts": [],
+                    "conflicts": [],
+                }),
+                allow_overwrite,
+                false,
+                &[],
+                false,
+            )
+            .expect("chunk import");
+            assert_eq!(stored_lists("Chunk Path"), expected, "{description}");
+        }
+
+        // Override import.
+        let overrides: Vec<ImportSpell> =
+            serde_json::from_value(json!([legacy_spell("Override Path", "Override import")]))
+                .expect("deserialize override spell");
+        run_legacy_import_chunk_with_vault_writes(&conn, vault.path(), |conn| {
+            import_override_spells_with_conn(conn, &overrides, true, false, &[], &HashMap::new())
+        })
+        .expect("override import");
+        assert_eq!(stored_lists("Override Path"), expected);
+
+        // Import plan: insert, then update.
+        for (description, action) in [("Plan insert", "insert"), ("Plan update", "update")] {
+            let plan: Vec<ImportPlanItem> = serde_json::from_value(json!([{
+                "spell": legacy_spell("Plan Path", description),
+                "action": action,
+            }]))
+            .expect("deserialize plan item");
+            run_legacy_import_chunk_with_vault_writes(&conn, vault.path(), |conn| {
+                apply_import_plan_with_conn(conn, &plan, false)
+            })
+            .expect("apply plan");
+            assert_eq!(stored_lists("Plan Path"), expected, "{description}");
+        }
+    }
+
+    #[test]
+    fn test_extract_class_levels_splits_levels_from_class_list() {
+        let mut spells: Vec<ImportSpell> = vec![
+            serde_json::from_value(json!({
+                "name": "Split Spell",
+                "level": 3,
+                "description": "X",
+                "classList": "Wizard 3, Cleric (4), Bard",
+            }))
+            .expect("import spell"),
+            serde_json::from_value(json!({
+                "name": "Plain Spell",
+                "level": 1,
+                "description": "X",
+                "classList": "Wizard, Priest",
+            }))
+            .expect("import spell"),
+        ];
+
+        extract_class_levels(&mut spells);
 
-        // Ensure the computed hash matches the one stored in `content_hash`
-        let recomputed_hash = stored_spell.compute_hash().expect("compute keep_both hash");
         assert_eq!(
-            stored_hash,
-            recomputed_hash,
-            "keep_both should compute the renamed spell hash matching the recomputed DB content_hash"
+            spells[0].class_list.as_deref(),
+            Some(r#"["Bard","Cleric","Wizard"]"#)
         );
+        let levels = spells[0].class_levels.as_ref().expect("class levels");
+        assert_eq!(levels.get("Wizard"), Some(&3));
+        assert_eq!(levels.get("Cleric"), Some(&4));
+        assert_eq!(levels.len(), 2);
+
+        assert_eq!(spells[1].class_list.as_deref(), Some("Wizard, Priest"));
+        assert!(spells[1].class_levels.is_none());
     }
 
     #[test]
-    fn test_apply_import_replace_failure_rolls_back_item_and_keeps_other_rows() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_reparse_reports_level_change() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_import_apply_test_db();
         create_change_log_table(&conn);
         create_hash_reference_tables(&conn);
 
-        let replace_existing_spell = test_spell("Rune Chain", 5, "Old rune chain");
-        let keep_both_existing_spell = test_spell("Solar Knot", 2, "Original knot");
+        let original = test_spell("Leveled Spell", 2, "Same description");
+        insert_spell_for_apply_test(&conn, 1, &original, &test_hash(&original));
+        let artifact_path = temp_dir.path().join("leveled.md");
+        std::fs::write(&artifact_path, "# Leveled Spell").expect("write artifact file");
+        let artifact_path = artifact_path.to_string_lossy().to_string();
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at)
+             VALUES (1, 1, 'md', ?, 'h1', '2026-01-01T00:00:00Z')",
+            params![artifact_path],
+        )
+        .expect("seed artifact");
 
-        let replace_old_hash = "n".repeat(64);
-        let keep_both_old_hash = "o".repeat(64);
-        let _keep_both_new_hash = "q".repeat(64);
+        let parsed = SpellDetail {
+            name: "Leveled Spell".into(),
+            school: Some("Abjuration".into()),
+            level: 3,
+            description: "Same description".into(),
+            ..Default::default()
+        };
+        let changes =
+            apply_artifact_reparse_with_conn(&conn, 1, 1, &parsed, true).expect("reparse");
+        let level_change = changes
+            .iter()
+            .find(|change| change.field == "level")
+            .expect("level change reported");
+        assert_eq!(
+            level_change,
+            &ReparseFieldChange {
+                field: "level".into(),
+                old: "2".into(),
+                new: "3".into(),
+            }
+        );
+        assert!(!changes.iter().any(|change| change.field == "description"));
 
-        let replace_incoming = test_spell("Rune Chain", 5, "Incoming replace should rollback");
-        let replace_new_hash = replace_incoming
-            .compute_hash()
-            .expect("replace incoming hash");
+        let mut parsed_by_path = HashMap::new();
+        parsed_by_path.insert(
+            normalize_key(&artifact_path),
+            Ok(SpellDetail { level: 4, ..parsed }),
+        );
+        let plan = plan_artifact_reparse(&conn, &[1], false);
+        let results =
+            apply_artifact_reparse_batch(&conn, plan, &parsed_by_path, true, &mut |_, _| {});
+        let level_change = results[0]
+            .changes
+            .iter()
+            .find(|change| change.field == "level")
+            .expect("batch result reports level change");
+        assert_eq!(
+            (level_change.old.as_str(), level_change.new.as_str()),
+            ("3", "4")
+        );
+        assert!(results[0].changed_fields.contains(&"level".to_string()));
+    }
 
-        insert_spell_for_apply_test(&conn, 1, &replace_existing_spell, &replace_old_hash);
-        insert_spell_for_apply_test(&conn, 2, &keep_both_existing_spell, &keep_both_old_hash);
-        insert_spell_for_apply_test(&conn, 3, &replace_incoming.clone(), &replace_new_hash);
-        conn.execute(
-            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (1, 1, ?)",
-            params![replace_old_hash.clone()],
-        )
-        .expect("seed old character_class_spell hash reference");
-        conn.execute(
-            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at, spell_content_hash)
-             VALUES (1, 1, 'pdf', 'dummy.pdf', 'dummyhash', '2026-01-01', ?)",
-            params![replace_old_hash.clone()],
-        )
-        .expect("seed old artifact hash reference");
-        let replace_item = preview_item_for_test(test_spell(
-            "Rune Chain",
-            5,
-            "Incoming replace should rollback",
-        ));
-        let keep_both_item =
-            preview_item_for_test(test_spell("Solar Knot", 2, "Incoming keep both"));
-        let replace_new_hash = replace_item.content_hash.clone();
-        let keep_both_new_hash = keep_both_item.content_hash.clone();
+    #[test]
+    fn test_reparse_preserves_user_edited_fields() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        conn.execute_batch("ALTER TABLE change_log ADD COLUMN actor TEXT DEFAULT 'local';")
+            .expect("add actor column");
+        create_hash_reference_tables(&conn);
+
+        let original = test_spell("Edited Spell", 1, "Imported description");
+        insert_spell_for_apply_test(&conn, 1, &original, &test_hash(&original));
+        let artifact_path = temp_dir.path().join("edited.md");
+        std::fs::write(&artifact_path, "# Edited Spell").expect("write artifact file");
         conn.execute(
-            "INSERT INTO character_class_spell (id, spell_id, spell_content_hash) VALUES (2, 99, ?)",
-            params![replace_new_hash.clone()],
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at)
+             VALUES (1, 1, 'md', ?, 'h1', '2026-01-01T00:00:00Z')",
+            params![artifact_path.to_string_lossy()],
         )
-        .expect("seed conflicting character_class_spell hash reference");
+        .expect("seed artifact");
 
-        let _keep_both_incoming = test_spell("Solar Knot", 2, "Incoming keep both");
+        let mut user_edit = spell_update_for_test(1, &original);
+        user_edit.description = "Hand-corrected description".into();
+        apply_spell_update_with_conn(&conn, &user_edit).expect("user edit");
 
-        let resolve_options = ImportSpellJsonResolveOptions {
-            resolutions: vec![
-                ImportSpellJsonConflictResolution {
-                    existing_id: 2,
-                    incoming_content_hash: keep_both_new_hash.clone(),
-                    action: "keep_both".to_string(),
-                },
-                ImportSpellJsonConflictResolution {
-                    existing_id: 1,
-                    incoming_content_hash: replace_new_hash.clone(),
-                    action: "replace_with_new".to_string(),
-                },
-            ],
-            default_action: None,
+        let parsed = SpellDetail {
+            name: "Edited Spell".into(),
+            school: Some("Evocation".into()),
+            level: 1,
+            description: "Freshly parsed description".into(),
+            ..Default::default()
         };
+        let changed: Vec<String> = apply_artifact_reparse_with_conn(&conn, 1, 1, &parsed, true)
+            .expect("reparse")
+            .into_iter()
+            .map(|change| change.field)
+            .collect();
+        assert!(changed.contains(&"school".to_string()));
+        assert!(!changed.contains(&"description".to_string()));
 
-        let result = apply_import_spell_json_impl(
-            &conn,
-            vec![keep_both_item, replace_item],
-            Some(resolve_options),
-        )
-        .expect("apply import should return per-item failures, not top-level error");
-
-        assert_eq!(result.imported_count, 1, "keep_both should still commit");
-        assert_eq!(result.failures.len(), 1, "replace item should fail");
-        assert_eq!(result.failures[0].spell_name, "Rune Chain");
-
-        let (final_hash, final_description): (String, String) = conn
+        let (school, description): (String, String) = conn
             .query_row(
-                "SELECT content_hash, description FROM spell WHERE id = 1",
+                "SELECT school, description FROM spell WHERE id = 1",
                 [],
                 |row| Ok((row.get(0)?, row.get(1)?)),
             )
-            .expect("query replace row after failure");
-        assert_eq!(
-            final_hash, replace_old_hash,
-            "failed replace must rollback hash"
-        );
-        assert_eq!(
-            final_description, "Old rune chain",
-            "failed replace must rollback updated fields"
-        );
-        let ccs_rolled_back_hash: String = conn
+            .expect("query spell");
+        assert_eq!(school, "Evocation");
+        assert_eq!(description, "Hand-corrected description");
+
+        // A second reparse still honors the earlier user edit.
+        apply_artifact_reparse_with_conn(&conn, 1, 1, &parsed, true).expect("reparse again");
+        let description: String = conn
+            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query description");
+        assert_eq!(description, "Hand-corrected description");
+
+        apply_artifact_reparse_with_conn(&conn, 1, 1, &parsed, false).expect("full reparse");
+        let description: String = conn
+            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query description");
+        assert_eq!(description, "Freshly parsed description");
+    }
+
+    #[test]
+    fn test_reparse_refreshes_fields_last_written_by_an_import() {
+        let vault = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = Connection::open_in_memory().expect("open vault db");
+        crate::db::migrations::load_migrations(&conn).expect("migrate vault db");
+        let chunk = |description: &str, range: &str| {
+            json!({
+                "spells": [{
+                    "name": "Reimported Spell",
+                    "level": 1,
+                    "school": "Abjuration",
+                    "range": range,
+                    "description": description,
+                }],
+                "artifacts": [],
+                "conflicts": [],
+            })
+        };
+        apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("First import", "10 yards"),
+            false,
+            false,
+            &[],
+            false,
+        )
+        .expect("initial import");
+        apply_import_file_chunk_with_conn(
+            &conn,
+            vault.path(),
+            chunk("Second import", "20 yards"),
+            true,
+            false,
+            &[],
+            false,
+        )
+        .expect("overwrite import");
+        let spell_id: i64 = conn
             .query_row(
-                "SELECT spell_content_hash FROM character_class_spell WHERE id = 1",
+                "SELECT id FROM spell WHERE name = 'Reimported Spell'",
                 [],
                 |row| row.get(0),
             )
-            .expect("query character_class_spell hash after rollback");
+            .expect("imported spell");
+        let import_fields: HashSet<String> = conn
+            .prepare("SELECT field FROM change_log WHERE spell_id = ? AND actor = 'import'")
+            .unwrap()
+            .query_map([spell_id], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .expect("import rows");
+        assert!(import_fields.contains("description"));
+        assert!(import_fields.contains("range"));
+
+        let mut user_edit = spell_detail_to_update(
+            &get_spell_from_conn(&conn, spell_id).unwrap().unwrap(),
+            spell_id,
+        );
+        user_edit.range = Some("30 yards".into());
+        apply_spell_update_with_conn(&conn, &user_edit).expect("user edit");
+        let artifact_path = vault.path().join("reimported.md");
+        std::fs::write(&artifact_path, "# Reimported Spell").expect("write artifact file");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at)
+             VALUES (?, 'md', ?, 'h1', '2026-01-01T00:00:00Z')",
+            params![spell_id, artifact_path.to_string_lossy()],
+        )
+        .expect("seed artifact");
+        let artifact_id = conn.last_insert_rowid();
+
+        let parsed = SpellDetail {
+            name: "Reimported Spell".into(),
+            school: Some("Abjuration".into()),
+            level: 1,
+            range: Some("40 yards".into()),
+            description: "Reparsed description".into(),
+            ..Default::default()
+        };
+        apply_artifact_reparse_with_conn(&conn, artifact_id, spell_id, &parsed, true)
+            .expect("reparse");
+
+        let (range, description): (String, String) = conn
+            .query_row(
+                "SELECT range, description FROM spell WHERE id = ?",
+                [spell_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query spell");
+        assert_eq!(range, "30 yards");
+        assert_eq!(description, "Reparsed description");
+        let reparse_fields: HashSet<String> = conn
+            .prepare("SELECT field FROM change_log WHERE spell_id = ? AND actor = 'reparse'")
+            .unwrap()
+            .query_map([spell_id], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .expect("reparse rows");
+        assert!(reparse_fields.contains("description"));
+        assert!(!reparse_fields.contains("range"));
+    }
+
+    #[test]
+    fn test_parsed_spells_by_path_keeps_first_spell_of_multi_spell_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let spell = |name: &str| SpellDetail {
+            name: name.into(),
+            level: 1,
+            description: "Desc".into(),
+            ..Default::default()
+        };
+        let path = dir.path().join("pair.json");
+        fs::write(
+            &path,
+            serde_json::to_string(&vec![spell("First"), spell("Second")]).expect("serialize"),
+        )
+        .expect("write export");
+        let missing = dir.path().join("missing.json");
+
+        let parsed =
+            parsed_spells_by_path(&parse_native_json_files(&[path.clone(), missing.clone()]));
+
+        assert_eq!(parsed.len(), 2);
+        let first = parsed[&normalize_key(&path.to_string_lossy())]
+            .as_ref()
+            .expect("parsed spell");
+        assert_eq!(first.name, "First");
         assert_eq!(
-            ccs_rolled_back_hash, replace_old_hash,
-            "failed replace must rollback cascaded character_class_spell hash update"
+            parsed[&normalize_key(&missing.to_string_lossy())]
+                .as_ref()
+                .err()
+                .map(String::as_str),
+            Some("missing")
         );
-        let artifact_rolled_back_hash: String = conn
+    }
+
+    #[test]
+    fn test_native_json_import_round_trips_exported_spells() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let exported = vec![SpellDetail {
+            name: "Fireball".into(),
+            school: Some("Evocation".into()),
+            class_list: Some("Wizard".into()),
+            level: 3,
+            range: Some("10 yds. + 10 yds./level".into()),
+            components: Some("V, S, M".into()),
+            material_components: Some("a tiny ball of bat guano and sulfur".into()),
+            duration: Some("Instantaneous".into()),
+            description: "A burst of flame.".into(),
+            source: Some("PHB".into()),
+            reversible: Some(0),
+            schema_version: Some(CURRENT_SCHEMA_VERSION),
+            ..Default::default()
+        }];
+        let export_path = dir.path().join("spellbook_export.json");
+        fs::write(
+            &export_path,
+            serde_json::to_string_pretty(&exported).expect("serialize export"),
+        )
+        .expect("write export");
+        let malformed_path = dir.path().join("broken.json");
+        fs::write(&malformed_path, "[{\"name\": ").expect("write malformed");
+
+        let result = parse_native_json_files(&[export_path.clone(), malformed_path.clone()]);
+
+        let spells: Vec<PreviewSpell> =
+            serde_json::from_value(result["spells"].clone()).expect("preview spells");
+        assert_eq!(spells.len(), 1);
+        let spell = &spells[0];
+        let original = &exported[0];
+        assert_eq!(spell.name, original.name);
+        assert_eq!(spell.level, original.level);
+        assert_eq!(spell.school, original.school);
+        assert_eq!(spell.class_list, original.class_list);
+        assert_eq!(spell.range, original.range);
+        assert_eq!(spell.components, original.components);
+        assert_eq!(spell.material_components, original.material_components);
+        assert_eq!(spell.duration, original.duration);
+        assert_eq!(spell.description, original.description);
+        assert_eq!(spell.source, original.source);
+        assert_eq!(spell.source_file, export_path.to_string_lossy());
+        assert!(spell.confidence.values().all(|v| *v == 1.0));
+        assert!(!spell.confidence.contains_key("area"));
+
+        let imported: Vec<ImportSpell> =
+            serde_json::from_value(result["spells"].clone()).expect("import spells");
+        assert_eq!(imported[0].name, "Fireball");
+        assert_eq!(imported[0].source.as_deref(), Some("PHB"));
+
+        let artifacts: Vec<ImportArtifact> =
+            serde_json::from_value(result["artifacts"].clone()).expect("artifacts");
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(artifacts[0].r#type, "json");
+
+        let conflicts: Vec<ParseConflict> =
+            serde_json::from_value(result["conflicts"].clone()).expect("conflicts");
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].path, malformed_path.to_string_lossy());
+        assert!(conflicts[0].reason.starts_with("invalid_json"));
+    }
+
+    #[test]
+    fn test_native_json_import_prefers_canonical_spells() {
+        let mut canonical = CanonicalSpell::new(
+            "Cure Light Wounds".into(),
+            1,
+            "DIVINE".into(),
+            "Heals 1d8.".into(),
+        );
+        canonical.sphere = Some("Healing".into());
+        canonical.components = Some(crate::models::canonical_spell::SpellComponents {
+            verbal: true,
+            somatic: true,
+            material: false,
+            focus: false,
+            divine_focus: false,
+            experience: false,
+        });
+        let payload = serde_json::to_string(&vec![canonical.clone()]).expect("serialize canonical");
+
+        let details = parse_native_json_spells(&payload).expect("parse canonical export");
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].name, "Cure Light Wounds");
+        assert_eq!(details[0].sphere.as_deref(), Some("Healing"));
+        assert_eq!(details[0].components.as_deref(), Some("V, S"));
+        assert!(details[0].canonical_data.is_some());
+
+        let (_, expected_hash, _) =
+            finalize_canonical_spell(canonical).expect("finalize canonical");
+        let (_, kept_hash, _) =
+            canonicalize_import_spell(&details[0], import_spell_to_detail(&details[0], None))
+                .expect("canonicalize carried spell");
+        assert_eq!(kept_hash, expected_hash);
+
+        let mut edited = details[0].clone();
+        edited.description = "Heals 2d8.".into();
+        let (fallback, _, _) =
+            canonicalize_import_spell(&edited, import_spell_to_detail(&edited, None))
+                .expect("canonicalize edited spell");
+        assert_eq!(fallback.description, "Heals 2d8.");
+    }
+
+    #[test]
+    fn test_apply_import_plan_applies_each_action() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+        conn.execute("ALTER TABLE spell ADD COLUMN needs_review INTEGER", [])
+            .expect("add needs_review column");
+        let updated_seed = test_spell("Plan Update", 2, "Old text");
+        insert_spell_for_apply_test(&conn, 1, &updated_seed, &test_hash(&updated_seed));
+        let skipped_seed = test_spell("Plan Skip", 1, "Keep me");
+        let skipped_hash = test_hash(&skipped_seed);
+        insert_spell_for_apply_test(&conn, 2, &skipped_seed, &skipped_hash);
+
+        let item = |name: &str, level: i64, description: &str, action: &str| -> ImportPlanItem {
+            serde_json::from_value(serde_json::json!({
+                "spell": {
+                    "name": name,
+                    "school": "Abjuration",
+                    "level": level,
+                    "description": description,
+                    "reversible": 0,
+                },
+                "action": action,
+            }))
+            .expect("deserialize plan item")
+        };
+        let mut insert = item("Plan Insert", 3, "Brand new", "insert");
+        insert.artifact = Some(ImportArtifact {
+            r#type: "json".to_string(),
+            path: "plan.json".to_string(),
+            hash: "a1".to_string(),
+            imported_at: "2026-01-01T00:00:00Z".to_string(),
+        });
+        let plan = vec![
+            insert,
+            item("Plan Update", 2, "New text", "update"),
+            item("Plan Skip", 1, "Should not land", "skip"),
+        ];
+
+        let result = run_legacy_import_chunk_with_vault_writes(&conn, temp_dir.path(), |conn| {
+            apply_import_plan_with_conn(conn, &plan, false)
+        })
+        .expect("apply plan");
+
+        assert_eq!(result.spells.len(), 2);
+        assert_eq!(result.skipped, vec!["Plan Skip".to_string()]);
+
+        let (inserted_id, inserted_description): (i64, String) = conn
             .query_row(
-                "SELECT spell_content_hash FROM artifact WHERE id = 1",
+                "SELECT id, description FROM spell WHERE name = 'Plan Insert'",
                 [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("inserted spell exists");
+        assert_eq!(inserted_description, "Brand new");
+        let artifact_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM artifact WHERE spell_id = ? AND path = 'plan.json'",
+                [inserted_id],
                 |row| row.get(0),
             )
-            .expect("query artifact hash after rollback");
-        assert_eq!(
-            artifact_rolled_back_hash, replace_old_hash,
-            "failed replace must rollback cascaded artifact hash update"
-        );
+            .expect("count artifacts");
+        assert_eq!(artifact_count, 1);
 
-        let keep_both_count: i64 = conn
+        let updated_description: String = conn
+            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query updated spell");
+        assert_eq!(updated_description, "New text");
+
+        let (skipped_description, stored_skip_hash): (String, String) = conn
             .query_row(
-                "SELECT COUNT(*) FROM spell WHERE name = 'Solar Knot (1)'",
+                "SELECT description, content_hash FROM spell WHERE id = 2",
                 [],
-                |row| row.get(0),
+                |row| Ok((row.get(0)?, row.get(1)?)),
             )
-            .expect("query keep_both inserted row");
-        assert_eq!(keep_both_count, 1, "other savepoint commits should remain");
+            .expect("query skipped spell");
+        assert_eq!(skipped_description, "Keep me");
+        assert_eq!(stored_skip_hash, skipped_hash);
+
+        let missing = vec![item("Plan Missing", 4, "Nothing to update", "update")];
+        let err = run_legacy_import_chunk_with_vault_writes(&conn, temp_dir.path(), |conn| {
+            apply_import_plan_with_conn(conn, &missing, false)
+        })
+        .expect_err("update without a target should fail");
+        assert!(matches!(err, AppError::NotFound(_)));
     }
 
     #[test]
-    fn test_apply_import_conflict_deterministic_when_name_has_multiple_rows() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-
+    fn test_apply_import_plan_skips_duplicate_inserts_and_locked_updates() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_import_apply_test_db();
-        let existing_a = test_spell("Twin Sigil", 1, "Existing row A");
-        let existing_b = test_spell("Twin Sigil", 4, "Existing row B");
-        let incoming_spell = test_spell("Twin Sigil", 7, "Incoming row");
-        let hash_a = test_hash(&existing_a);
-        let hash_b = test_hash(&existing_b);
-
-        insert_spell_for_apply_test(&conn, 1, &existing_a, &hash_a);
-        insert_spell_for_apply_test(&conn, 2, &existing_b, &hash_b);
-
-        let result =
-            apply_import_spell_json_impl(&conn, vec![preview_item_for_test(incoming_spell)], None)
-                .expect("apply import should succeed");
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+        conn.execute_batch(
+            "ALTER TABLE spell ADD COLUMN needs_review INTEGER;
+             ALTER TABLE spell ADD COLUMN locked INTEGER NOT NULL DEFAULT 0;",
+        )
+        .expect("add needs_review and locked columns");
+        let locked_seed = test_spell("Plan Locked", 2, "Curated text");
+        insert_spell_for_apply_test(&conn, 1, &locked_seed, &test_hash(&locked_seed));
+        crate::commands::spells::set_spell_locked_with_conn(&conn, 1, true).expect("lock spell");
+
+        let item = |name: &str, description: &str, action: &str| -> ImportPlanItem {
+            serde_json::from_value(serde_json::json!({
+                "spell": {
+                    "name": name,
+                    "school": "Abjuration",
+                    "level": 2,
+                    "description": description,
+                    "reversible": 0,
+                },
+                "action": action,
+            }))
+            .expect("deserialize plan item")
+        };
+        let plan = vec![
+            item("Plan Twin", "Same words", "insert"),
+            item("Plan Twin", "Same words", "insert"),
+            item("Plan Locked", "Overwritten text", "update"),
+        ];
 
-        assert_eq!(result.imported_count, 0, "conflict should block insert");
-        assert_eq!(result.conflicts.len(), 1, "one conflict should be emitted");
+        let result = run_legacy_import_chunk_with_vault_writes(&conn, temp_dir.path(), |conn| {
+            apply_import_plan_with_conn(conn, &plan, false)
+        })
+        .expect("apply plan");
+        assert_eq!(result.spells.len(), 1);
         assert_eq!(
-            result.conflicts[0].existing_id, 1,
-            "name-only conflict should deterministically select the lowest id row"
+            result.skipped,
+            vec!["Plan Twin".to_string(), "Plan Locked".to_string()]
         );
+        assert_eq!(result.warnings.len(), 2);
+        let twins: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM spell WHERE name = 'Plan Twin'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("count twins");
+        assert_eq!(twins, 1, "identical content must not be inserted twice");
+        let locked_description = |conn: &Connection| -> String {
+            conn.query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query locked spell")
+        };
+        assert_eq!(locked_description(&conn), "Curated text");
+
+        let forced = vec![item("Plan Locked", "Overwritten text", "update")];
+        run_legacy_import_chunk_with_vault_writes(&conn, temp_dir.path(), |conn| {
+            apply_import_plan_with_conn(conn, &forced, true)
+        })
+        .expect("apply forced plan");
+        assert_eq!(locked_description(&conn), "Overwritten text");
     }
 
     #[test]
-    fn test_replace_with_new_collision_error_includes_conflicting_spell_name_and_hash() {
-        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
-
+    fn test_overwrite_import_skips_locked_spell_unless_forced() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_import_apply_test_db();
-        let existing_spell = test_spell("Mirror Ward", 2, "Existing target spell");
-        let conflicting_spell = test_spell("Prismatic Net", 6, "Spell already using incoming hash");
-        let incoming_spell = test_spell("Mirror Ward", 2, "Incoming replacement spell");
-        let _old_hash = test_hash(&existing_spell);
-        let _collision_hash = test_hash(&conflicting_spell);
-        let collision_hash = conflicting_spell
-            .compute_hash()
-            .expect("conflicting spell hash");
-        let old_hash = "0".repeat(64);
-
-        insert_spell_for_apply_test(&conn, 1, &existing_spell, &old_hash);
-        insert_spell_for_apply_test(&conn, 2, &conflicting_spell, &collision_hash);
-
-        let mut item = preview_item_for_test(incoming_spell);
-        item.content_hash = collision_hash.clone();
-        let incoming_spell = conflicting_spell.clone();
-        let item = PreviewSpellJsonItem {
-            spell: incoming_spell,
-            content_hash: collision_hash.clone(),
-            warnings: vec![],
-        };
-
-        let err = replace_with_new_impl(&conn, 1, Some(&old_hash), &collision_hash, &item)
-            .expect_err("replace should fail when incoming hash belongs to another spell row");
-        let msg = err.to_string();
-        assert!(
-            msg.contains("Prismatic Net"),
-            "error should include conflicting spell name, got: {msg}"
-        );
-        assert!(
-            msg.contains(&collision_hash),
-            "error should include conflicting hash, got: {msg}"
-        );
-        assert!(
-            msg.contains("This imported version already exists"),
-            "error should include explicit already-exists guidance, got: {msg}"
-        );
-        assert!(
-            msg.contains("Keep Existing"),
-            "error should suggest Keep Existing action, got: {msg}"
-        );
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+        conn.execute_batch(
+            "ALTER TABLE spell ADD COLUMN needs_review INTEGER;
+             ALTER TABLE spell ADD COLUMN locked INTEGER NOT NULL DEFAULT 0;",
+        )
+        .expect("add needs_review and locked columns");
+        let seed = test_spell("Curated Ward", 2, "Hand-perfected text");
+        let seed_hash = test_hash(&seed);
+        insert_spell_for_apply_test(&conn, 1, &seed, &seed_hash);
+        crate::commands::spells::set_spell_locked_with_conn(&conn, 1, true).expect("lock spell");
+
+        let incoming: Vec<ImportSpell> = serde_json::from_value(serde_json::json!([{
+            "name": "Curated Ward",
+            "school": "Abjuration",
+            "level": 2,
+            "description": "Freshly parsed text",
+            "reversible": 0,
+        }]))
+        .expect("deserialize import spell");
+        let no_artifacts = HashMap::new();
+
+        let result = run_legacy_import_chunk_with_vault_writes(&conn, temp_dir.path(), |conn| {
+            import_override_spells_with_conn(conn, &incoming, true, false, &[], &no_artifacts)
+        })
+        .expect("overwrite import");
+        assert!(result.spells.is_empty());
+        assert_eq!(result.skipped, vec!["Curated Ward".to_string()]);
+        assert_eq!(result.warnings.len(), 1);
         assert!(
-            msg.contains("Keep Both"),
-            "error should suggest Keep Both action, got: {msg}"
+            result.warnings[0].contains("locked"),
+            "{:?}",
+            result.warnings
         );
-    }
-
-    // -----------------------------------------------------------------------
-    // resolve_artifact_spell_id tests
-    // -----------------------------------------------------------------------
+        let (description, stored_hash): (String, String) = conn
+            .query_row(
+                "SELECT description, content_hash FROM spell WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query locked spell");
+        assert_eq!(description, "Hand-perfected text");
+        assert_eq!(stored_hash, seed_hash);
 
-    fn setup_resolve_artifact_db() -> Connection {
-        let conn = Connection::open_in_memory().expect("open in-memory db");
-        conn.execute_batch(
-            "CREATE TABLE spell (
-                id INTEGER PRIMARY KEY,
-                name TEXT NOT NULL DEFAULT '',
-                level INTEGER NOT NULL DEFAULT 1,
-                description TEXT NOT NULL DEFAULT '',
-                content_hash TEXT
-            );
-            CREATE TABLE artifact (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                spell_id INTEGER,
-                spell_content_hash TEXT,
-                path TEXT NOT NULL,
-                type TEXT NOT NULL DEFAULT 'source',
-                hash TEXT NOT NULL DEFAULT 'h',
-                imported_at TEXT NOT NULL DEFAULT '2026-01-01T00:00:00Z'
-            );",
-        )
-        .expect("create test schema");
-        conn
+        let forced = run_legacy_import_chunk_with_vault_writes(&conn, temp_dir.path(), |conn| {
+            import_override_spells_with_conn(conn, &incoming, true, true, &[], &no_artifacts)
+        })
+        .expect("forced overwrite import");
+        assert_eq!(forced.spells.len(), 1);
+        assert!(forced.skipped.is_empty());
+        let description: String = conn
+            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query forced spell");
+        assert_eq!(description, "Freshly parsed text");
+        assert!(is_spell_locked_with_conn(&conn, 1).unwrap());
     }
 
     #[test]
-    fn test_resolve_artifact_spell_id_by_hash() {
-        let conn = setup_resolve_artifact_db();
-        conn.execute(
-            "INSERT INTO spell (id, content_hash) VALUES (42, 'hash-xyz')",
-            [],
-        )
-        .expect("insert spell");
-        conn.execute(
-            "INSERT INTO artifact (id, spell_id, spell_content_hash, path) VALUES (1, NULL, 'hash-xyz', 'a.md')",
-            [],
-        )
-        .expect("insert artifact with hash only");
+    fn test_import_with_unparseable_range_lands_in_review_queue() {
+        use crate::commands::spells::{clear_review_flag_with_conn, list_needs_review_with_conn};
 
-        let (spell_id, path) = resolve_artifact_spell_id(&conn, 1).expect("resolve ok");
-        assert_eq!(spell_id, 42, "should resolve spell_id from hash");
-        assert_eq!(path, "a.md");
-    }
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+        conn.execute("ALTER TABLE spell ADD COLUMN needs_review INTEGER", [])
+            .expect("add needs_review column");
 
-    #[test]
-    fn test_resolve_artifact_spell_id_hash_fallback_to_spell_id() {
-        // Hash set but no matching spell row → fall back to spell_id column
-        let conn = setup_resolve_artifact_db();
-        conn.execute(
-            "INSERT INTO spell (id, content_hash) VALUES (7, 'different-hash')",
-            [],
-        )
-        .expect("insert spell");
-        conn.execute(
-            "INSERT INTO artifact (id, spell_id, spell_content_hash, path) VALUES (1, 7, 'stale-hash', 'b.md')",
-            [],
-        )
-        .expect("insert artifact with stale hash but valid spell_id");
+        let incoming: Vec<ImportSpell> = serde_json::from_value(serde_json::json!([
+            {
+                "name": "Odd Range",
+                "school": "Evocation",
+                "level": 1,
+                "range": "xyz gibberish 123",
+                "duration": "1 round/level",
+                "description": "Desc",
+            },
+            {
+                "name": "Plain Range",
+                "school": "Evocation",
+                "level": 1,
+                "range": "10 yards",
+                "duration": "1 round/level",
+                "description": "Desc",
+            }
+        ]))
+        .expect("deserialize import spells");
+        let no_artifacts = HashMap::new();
 
-        let (spell_id, path) = resolve_artifact_spell_id(&conn, 1).expect("resolve ok");
-        assert_eq!(
-            spell_id, 7,
-            "should fall back to spell_id when hash not found"
-        );
-        assert_eq!(path, "b.md");
+        let result = run_legacy_import_chunk_with_vault_writes(&conn, temp_dir.path(), |conn| {
+            import_override_spells_with_conn(conn, &incoming, true, false, &[], &no_artifacts)
+        })
+        .expect("import spells");
+        assert_eq!(result.spells.len(), 2);
+
+        let queue = list_needs_review_with_conn(&conn).expect("list review queue");
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].name, "Odd Range");
+        assert_eq!(queue[0].fallback_fields, vec!["range".to_string()]);
+
+        clear_review_flag_with_conn(&conn, queue[0].id).expect("clear flag");
+        assert!(list_needs_review_with_conn(&conn)
+            .expect("list review queue")
+            .is_empty());
+        assert!(matches!(
+            clear_review_flag_with_conn(&conn, 999),
+            Err(AppError::NotFound(_))
+        ));
     }
 
     #[test]
-    fn test_resolve_artifact_spell_id_not_found_returns_error() {
-        // Neither hash nor spell_id resolves to an existing spell
-        let conn = setup_resolve_artifact_db();
-        conn.execute(
-            "INSERT INTO artifact (id, spell_id, spell_content_hash, path) VALUES (1, NULL, 'orphan-hash', 'c.md')",
-            [],
+    fn test_override_import_writes_reverse_name() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_change_log_table(&conn);
+        create_hash_reference_tables(&conn);
+        conn.execute_batch(
+            "ALTER TABLE spell ADD COLUMN needs_review INTEGER;
+             ALTER TABLE spell ADD COLUMN reverse_name TEXT;",
         )
-        .expect("insert orphaned artifact");
-
-        let result = resolve_artifact_spell_id(&conn, 1);
-        assert!(
-            matches!(result, Err(AppError::NotFound(_))),
-            "should return NotFound when spell is gone"
-        );
+        .expect("add needs_review and reverse_name columns");
+
+        let incoming: Vec<ImportSpell> = serde_json::from_value(serde_json::json!([{
+            "name": "Cure Light Wounds",
+            "school": "Necromancy",
+            "level": 1,
+            "description": "Heals 1d8 points of damage.",
+            "reversible": 1,
+            "reverseName": "Cause Light Wounds",
+        }]))
+        .expect("deserialize import spell");
+        let no_artifacts = HashMap::new();
+
+        let result = run_legacy_import_chunk_with_vault_writes(&conn, temp_dir.path(), |conn| {
+            import_override_spells_with_conn(conn, &incoming, true, false, &[], &no_artifacts)
+        })
+        .expect("override import");
+        assert_eq!(result.spells.len(), 1);
+        let reverse_name: Option<String> = conn
+            .query_row(
+                "SELECT reverse_name FROM spell WHERE name = 'Cure Light Wounds'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query reverse name");
+        assert_eq!(reverse_name.as_deref(), Some("Cause Light Wounds"));
     }
 
     #[test]
-    fn test_resolve_artifact_spell_id_legacy_no_hash_column() {
-        // Old schema without spell_content_hash column — uses spell_id directly
-        let conn = Connection::open_in_memory().expect("open in-memory db");
-        conn.execute_batch(
-            "CREATE TABLE spell (
-                id INTEGER PRIMARY KEY,
-                content_hash TEXT
-            );
-            CREATE TABLE artifact (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                spell_id INTEGER,
-                path TEXT NOT NULL
-            );",
-        )
-        .expect("create legacy schema");
-        conn.execute("INSERT INTO spell (id, content_hash) VALUES (5, 'h')", [])
-            .expect("insert spell");
+    fn test_diff_spell_against_source_reports_edited_fields() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_import_apply_test_db();
+        create_hash_reference_tables(&conn);
+
         conn.execute(
-            "INSERT INTO artifact (id, spell_id, path) VALUES (1, 5, 'legacy.md')",
+            "INSERT INTO spell (id, name, school, level, description, tags, reversible,
+                                is_quest_spell, is_cantrip)
+             VALUES (1, 'Shield', 'Evocation', 1, 'Edited by hand.', 'house-rule', 0, 0, 0)",
             [],
         )
-        .expect("insert legacy artifact");
+        .expect("seed spell");
+        assert!(matches!(
+            primary_artifact_path_with_conn(&conn, 1),
+            Err(AppError::NotFound(_))
+        ));
 
-        let (spell_id, path) = resolve_artifact_spell_id(&conn, 1).expect("resolve ok");
-        assert_eq!(spell_id, 5);
-        assert_eq!(path, "legacy.md");
+        let source = vec![SpellDetail {
+            name: "Shield".into(),
+            school: Some("Evocation".into()),
+            level: 1,
+            description: "An invisible barrier.".into(),
+            reversible: Some(0),
+            ..Default::default()
+        }];
+        let artifact_path = temp_dir.path().join("shield.json");
+        fs::write(&artifact_path, serde_json::to_string(&source).unwrap())
+            .expect("write artifact file");
+        conn.execute(
+            "INSERT INTO artifact (id, spell_id, type, path, hash, imported_at)
+             VALUES (1, 1, 'json', ?, 'h1', '2026-01-01T00:00:00Z')",
+            params![artifact_path.to_string_lossy()],
+        )
+        .expect("seed artifact");
+
+        let path = primary_artifact_path_with_conn(&conn, 1).expect("primary artifact");
+        let parsed = parse_native_json_spells(&fs::read_to_string(path).unwrap())
+            .expect("parse artifact")
+            .remove(0);
+        let diffs =
+            diff_spell_against_parsed_with_conn(&conn, 1, &import_spell_to_detail(&parsed, None))
+                .expect("diff");
+
+        let fields: Vec<&str> = diffs.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, vec!["description", "tags"]);
+        assert_eq!(diffs[0].old, Some(json!("An invisible barrier.")));
+        assert_eq!(diffs[0].new, Some(json!("Edited by hand.")));
+        assert_eq!(diffs[1].old, None);
+
+        let unchanged: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM spell WHERE description = 'Edited by hand.'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(unchanged, 1);
     }
 }
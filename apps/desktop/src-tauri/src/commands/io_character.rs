@@ -1,13 +1,17 @@
-use crate::commands::spells::canonicalize_spell_detail;
+use crate::commands::export::resolve_output_dir;
+use crate::commands::spells::{canonicalize_spell_detail, SpellCache};
+use crate::commands::vault::VaultMaintenanceState;
 use crate::db::Pool;
 use crate::error::AppError;
 use crate::models::{
-    BundleClass, BundleClassSpell, Character, CharacterAbilities, CharacterBundle, CharacterClass,
-    SpellDetail,
+    BundleClass, BundleClassSpell, BundleSpellbookEntry, Character, CharacterAbilities,
+    CharacterBundle, CharacterClass, SpellDetail,
 };
+use crate::utils::compression::{read_export_json, write_export_json};
 use rusqlite::params;
 use rusqlite::OptionalExtension;
 use sha2::{Digest, Sha256};
+use std::path::Path;
 use std::sync::Arc;
 use tauri::State;
 
@@ -94,69 +98,29 @@ fn fetch_character_bundle(
         }
 
         let query = if use_hash {
-            "SELECT s.id, s.name, s.level, s.school, s.sphere, s.range, s.components,
-                    s.material_components, s.casting_time, s.duration, s.area, s.saving_throw,
-                    s.reversible, s.description, s.tags, s.source, s.edition, s.author,
-                    s.license, s.is_quest_spell, s.is_cantrip, s.class_list,
-                    s.damage, s.magic_resistance, s.schema_version,
-                    ccs.list_type, ccs.notes
+            format!(
+                "SELECT {BUNDLE_SPELL_COLUMNS}, ccs.list_type, ccs.notes
              FROM character_class_spell ccs
              JOIN spell s ON
                 (ccs.spell_content_hash IS NOT NULL AND s.content_hash = ccs.spell_content_hash)
                 OR (ccs.spell_content_hash IS NULL AND s.id = ccs.spell_id)
              WHERE ccs.character_class_id = ?"
+            )
         } else {
-            "SELECT s.id, s.name, s.level, s.school, s.sphere, s.range, s.components,
-                    s.material_components, s.casting_time, s.duration, s.area, s.saving_throw,
-                    s.reversible, s.description, s.tags, s.source, s.edition, s.author,
-                    s.license, s.is_quest_spell, s.is_cantrip, s.class_list,
-                    s.damage, s.magic_resistance, s.schema_version,
-                    ccs.list_type, ccs.notes
+            format!(
+                "SELECT {BUNDLE_SPELL_COLUMNS}, ccs.list_type, ccs.notes
              FROM character_class_spell ccs
              JOIN spell s ON s.id = ccs.spell_id
              WHERE ccs.character_class_id = ?"
+            )
         };
-        let mut stmt = conn.prepare(query)?;
+        let mut stmt = conn.prepare(&query)?;
 
         let spell_rows = stmt.query_map(params![class_data.id], |row| {
-            let spell = SpellDetail {
-                id: None,
-                name: row.get(1)?,
-                level: row.get(2)?,
-                school: row.get(3)?,
-                sphere: row.get(4)?,
-                range: row.get(5)?,
-                components: row.get(6)?,
-                material_components: row.get(7)?,
-                casting_time: row.get(8)?,
-                duration: row.get(9)?,
-                area: row.get(10)?,
-                saving_throw: row.get(11)?,
-                reversible: row.get(12)?,
-                description: row.get(13)?,
-                tags: row.get(14)?,
-                source: row.get(15)?,
-                edition: row.get(16)?,
-                author: row.get(17)?,
-                license: row.get(18)?,
-                is_quest_spell: row.get(19)?,
-                is_cantrip: row.get(20)?,
-                class_list: row.get(21)?,
-                damage: row.get(22)?,
-                magic_resistance: row.get(23)?,
-                schema_version: row.get(24)?,
-                artifacts: None,
-                canonical_data: None,
-                content_hash: None,
-                ..Default::default()
-            };
-            let list_type: String = row.get(25)?;
-            let notes: Option<String> = row.get(26)?;
-
             Ok(BundleClassSpell {
-                spell,
-                list_type,
-                notes,
+                spell: bundle_spell_from_row(row)?,
+                list_type: row.get(26)?,
+                notes: row.get(27)?,
             })
         })?;
 
@@ -186,9 +150,78 @@ fn fetch_character_bundle(
         updated_at: character.updated_at,
         abilities,
         classes,
+        spellbook: vec![],
+    })
+}
+
+/// Spell columns shared by the bundle queries, in `SELECT` order (indices 0..=25).
+const BUNDLE_SPELL_COLUMNS: &str =
+    "s.id, s.name, s.level, s.school, s.sphere, s.range, s.components,
+    s.material_components, s.casting_time, s.duration, s.area, s.saving_throw,
+    s.reversible, s.description, s.tags, s.source, s.edition, s.author,
+    s.license, s.is_quest_spell, s.is_cantrip, s.class_list,
+    s.damage, s.magic_resistance, s.schema_version, s.content_hash";
+
+// Reads the bundle spell columns (see `BUNDLE_SPELL_COLUMNS`), keeping the stored hash.
+fn bundle_spell_from_row(row: &rusqlite::Row) -> rusqlite::Result<SpellDetail> {
+    Ok(SpellDetail {
+        id: None,
+        name: row.get(1)?,
+        level: row.get(2)?,
+        school: row.get(3)?,
+        sphere: row.get(4)?,
+        range: row.get(5)?,
+        components: row.get(6)?,
+        material_components: row.get(7)?,
+        casting_time: row.get(8)?,
+        duration: row.get(9)?,
+        area: row.get(10)?,
+        saving_throw: row.get(11)?,
+        reversible: row.get(12)?,
+        description: row.get(13)?,
+        tags: row.get(14)?,
+        source: row.get(15)?,
+        edition: row.get(16)?,
+        author: row.get(17)?,
+        license: row.get(18)?,
+        is_quest_spell: row.get(19)?,
+        is_cantrip: row.get(20)?,
+        class_list: row.get(21)?,
+        damage: row.get(22)?,
+        magic_resistance: row.get(23)?,
+        schema_version: row.get(24)?,
+        artifacts: None,
+        canonical_data: None,
+        content_hash: row.get(25)?,
+        ..Default::default()
     })
 }
 
+// The character's legacy `spellbook` rows (prepared/known flags and notes per spell).
+fn fetch_spellbook_entries(
+    conn: &rusqlite::Connection,
+    character_id: i64,
+) -> Result<Vec<BundleSpellbookEntry>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {BUNDLE_SPELL_COLUMNS}, sb.prepared, sb.known, sb.notes
+         FROM spellbook sb
+         JOIN spell s ON s.id = sb.spell_id
+         WHERE sb.character_id = ?
+         ORDER BY s.name, s.id"
+    ))?;
+    let entries = stmt
+        .query_map(params![character_id], |row| {
+            Ok(BundleSpellbookEntry {
+                spell: bundle_spell_from_row(row)?,
+                prepared: row.get::<_, Option<i64>>(26)?.unwrap_or(0),
+                known: row.get::<_, Option<i64>>(27)?.unwrap_or(1),
+                notes: row.get(28)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(entries)
+}
+
 #[tauri::command]
 pub async fn export_character_bundle(
     state: State<'_, Arc<Pool>>,
@@ -205,6 +238,38 @@ pub async fn export_character_bundle(
     Ok(bundle)
 }
 
+/// Writes one character bundle to the exports dir (or `output_dir`) as `.json`, or
+/// `.json.gz` with `compress`, and returns its path.
+#[tauri::command]
+pub async fn export_character_bundle_file(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    character_id: i64,
+    compress: Option<bool>,
+    output_dir: Option<String>,
+) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let bundle = fetch_character_bundle(&conn, character_id)?;
+        let json = serde_json::to_string_pretty(&bundle)
+            .map_err(|e| AppError::Export(format!("JSON serialization error: {}", e)))?;
+        let path = write_export_json(
+            &resolve_output_dir(&app, output_dir.as_deref(), "exports")?,
+            &format!(
+                "character_{}_{}",
+                sanitize_filename(&bundle.name).replace(' ', "_"),
+                chrono::Utc::now().format("%Y%m%dT%H%M%S%3f")
+            ),
+            &json,
+            compress.unwrap_or(false),
+        )?;
+        Ok::<String, AppError>(path.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
 #[tauri::command]
 pub async fn export_character_markdown_zip(
     state: State<'_, Arc<Pool>>,
@@ -349,36 +414,7 @@ fn import_character_bundle_logic(
         let class_id = tx.last_insert_rowid();
 
         for spell_entry in class.spells {
-            let s = &spell_entry.spell;
-            let spell_id: Option<i64> = tx
-                .query_row(
-                    "SELECT id FROM spell WHERE name = ? AND level = ? AND IFNULL(source, '') = ?",
-                    params![s.name, s.level, s.source.as_deref().unwrap_or("")],
-                    |row| row.get(0),
-                )
-                .optional()?;
-
-            let final_spell_id = if let Some(sid) = spell_id {
-                sid
-            } else {
-                let (canonical, hash, json) = canonicalize_spell_detail(s.clone())?;
-                tx.execute(
-                    "INSERT INTO spell (name, level, school, sphere, range, components, material_components,
-                                        casting_time, duration, area, saving_throw, damage, magic_resistance,
-                                        reversible, description, tags, source, edition, author, license,
-                                        is_quest_spell, is_cantrip, class_list, canonical_data, content_hash,
-                                        schema_version)
-                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-                    params![
-                        s.name, s.level, s.school, s.sphere, s.range, s.components, s.material_components,
-                        s.casting_time, s.duration, s.area, s.saving_throw, s.damage, s.magic_resistance,
-                        s.reversible, s.description,
-                        s.tags, s.source, s.edition, s.author, s.license, s.is_quest_spell, s.is_cantrip, s.class_list,
-                        json, hash, canonical.schema_version
-                    ],
-                )?;
-                tx.last_insert_rowid()
-            };
+            let final_spell_id = resolve_bundle_spell_id(tx, &spell_entry.spell)?;
 
             if crate::db::table_has_column(tx, "character_class_spell", "spell_content_hash") {
                 let spell_content_hash: Option<String> = tx
@@ -403,9 +439,87 @@ fn import_character_bundle_logic(
         }
     }
 
+    // 4. Legacy spellbook rows
+    for entry in bundle.spellbook {
+        let spell_id = resolve_bundle_spell_id(tx, &entry.spell)?;
+        tx.execute(
+            "INSERT OR REPLACE INTO spellbook (character_id, spell_id, prepared, known, notes) VALUES (?, ?, ?, ?, ?)",
+            params![character_id, spell_id, entry.prepared, entry.known, entry.notes],
+        )?;
+    }
+
     Ok(character_id)
 }
 
+// Library id for a bundle spell: by content hash when the bundle carries one, then by
+// name/level/source, else the spell is imported.
+fn resolve_bundle_spell_id(tx: &rusqlite::Transaction, s: &SpellDetail) -> Result<i64, AppError> {
+    // Prefer the content hash when the bundle carries one; fall back to name/level/source.
+    let spell_id: Option<i64> = match s.content_hash.as_deref() {
+        Some(hash) => tx
+            .query_row(
+                "SELECT id FROM spell WHERE content_hash = ?",
+                params![hash],
+                |row| row.get(0),
+            )
+            .optional()?,
+        None => None,
+    };
+    let spell_id: Option<i64> = match spell_id {
+        Some(id) => Some(id),
+        None => tx
+            .query_row(
+                "SELECT id FROM spell WHERE name = ? AND level = ? AND IFNULL(source, '') = ?",
+                params![s.name, s.level, s.source.as_deref().unwrap_or("")],
+                |row| row.get(0),
+            )
+            .optional()?,
+    };
+
+    if let Some(sid) = spell_id {
+        return Ok(sid);
+    }
+
+    let (canonical, hash, json) = canonicalize_spell_detail(s.clone())?;
+    tx.execute(
+        "INSERT INTO spell (name, level, school, sphere, range, components, material_components,
+                            casting_time, duration, area, saving_throw, damage, magic_resistance,
+                            reversible, description, tags, source, edition, author, license,
+                            is_quest_spell, is_cantrip, class_list, canonical_data, content_hash,
+                            schema_version)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        params![
+            s.name,
+            s.level,
+            s.school,
+            s.sphere,
+            s.range,
+            s.components,
+            s.material_components,
+            s.casting_time,
+            s.duration,
+            s.area,
+            s.saving_throw,
+            s.damage,
+            s.magic_resistance,
+            s.reversible,
+            s.description,
+            s.tags,
+            s.source,
+            s.edition,
+            s.author,
+            s.license,
+            s.is_quest_spell,
+            s.is_cantrip,
+            s.class_list,
+            json,
+            hash,
+            canonical.schema_version
+        ],
+    )?;
+    Ok(tx.last_insert_rowid())
+}
+
 fn record_import_artifact(
     tx: &rusqlite::Transaction,
     hash: &str,
@@ -456,6 +570,22 @@ pub async fn import_character_bundle(
     Ok(id)
 }
 
+/// Imports a bundle written by `export_character_bundle_file`; `.json.gz` files (or any
+/// gzip payload) are decompressed transparently.
+#[tauri::command]
+pub async fn import_character_bundle_file(
+    state: State<'_, Arc<Pool>>,
+    path: String,
+    options: ImportOptions,
+) -> Result<i64, AppError> {
+    let json = tokio::task::spawn_blocking(move || read_export_json(Path::new(&path)))
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))??;
+    let bundle: CharacterBundle = serde_json::from_str(&json)
+        .map_err(|e| AppError::Import(format!("Character bundle parse error: {}", e)))?;
+    import_character_bundle(state, bundle, options).await
+}
+
 #[tauri::command]
 pub async fn preview_character_markdown_zip(bytes: Vec<u8>) -> Result<CharacterBundle, AppError> {
     let preview_res = tokio::task::spawn_blocking(move || {
@@ -512,6 +642,139 @@ pub async fn import_character_markdown_zip(
     Ok(id)
 }
 
+const CHARACTERS_ARCHIVE_ENTRY: &str = "characters.json";
+
+// Every character as a bundle, with its legacy spellbook rows, for the archive. Spells
+// carry their stored content hash so the archive re-links by hash on import; a spell
+// without one is warned about and re-linked by name/level/source instead.
+fn fetch_all_character_bundles(
+    conn: &rusqlite::Connection,
+) -> Result<Vec<CharacterBundle>, AppError> {
+    let mut stmt = conn.prepare("SELECT id FROM \"character\" ORDER BY id")?;
+    let ids = stmt
+        .query_map([], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut bundles = Vec::with_capacity(ids.len());
+    for id in ids {
+        let mut bundle = fetch_character_bundle(conn, id)?;
+        bundle.spellbook = fetch_spellbook_entries(conn, id)?;
+        let spells = bundle
+            .classes
+            .iter()
+            .flat_map(|class| class.spells.iter().map(|entry| &entry.spell))
+            .chain(bundle.spellbook.iter().map(|entry| &entry.spell));
+        for spell in spells.filter(|spell| spell.content_hash.is_none()) {
+            eprintln!(
+                "WARNING: Spell '{}' of character '{}' has no content hash; it will be re-linked by name on import.",
+                spell.name, bundle.name
+            );
+        }
+        bundles.push(bundle);
+    }
+    Ok(bundles)
+}
+
+fn write_character_bundles_zip(bundles: &[CharacterBundle], path: &Path) -> Result<(), AppError> {
+    use std::io::Write;
+    use zip::write::{FileOptions, ZipWriter};
+
+    let json = serde_json::to_string_pretty(bundles)
+        .map_err(|e| AppError::Export(format!("JSON serialization error: {}", e)))?;
+    let mut zip = ZipWriter::new(std::fs::File::create(path)?);
+    let options = FileOptions::<()>::default().compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(CHARACTERS_ARCHIVE_ENTRY, options)
+        .map_err(|e| AppError::Export(format!("Zip error: {}", e)))?;
+    zip.write_all(json.as_bytes())?;
+    zip.finish()
+        .map_err(|e| AppError::Export(format!("Zip finish error: {}", e)))?;
+    Ok(())
+}
+
+fn read_character_bundles_zip(bytes: &[u8]) -> Result<Vec<CharacterBundle>, AppError> {
+    use std::io::Read;
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .map_err(|e| AppError::Import(format!("Zip error: {}", e)))?;
+    let mut file = archive
+        .by_name(CHARACTERS_ARCHIVE_ENTRY)
+        .map_err(|_| AppError::Import(format!("{} not found in ZIP", CHARACTERS_ARCHIVE_ENTRY)))?;
+    let mut json = String::new();
+    file.read_to_string(&mut json)?;
+    serde_json::from_str(&json)
+        .map_err(|e| AppError::Import(format!("Character archive parse error: {}", e)))
+}
+
+// Upserts every bundle by character name in one transaction; returns the character ids.
+fn import_character_bundles_with_conn(
+    conn: &mut rusqlite::Connection,
+    bundles: Vec<CharacterBundle>,
+    archive_hash: &str,
+) -> Result<Vec<i64>, AppError> {
+    let tx = conn.transaction()?;
+    let mut ids = Vec::with_capacity(bundles.len());
+    for bundle in bundles {
+        let name = bundle.name.clone();
+        ids.push(import_character_bundle_logic(
+            &tx,
+            bundle,
+            ImportOptions { overwrite: true },
+        )?);
+        record_import_artifact(&tx, archive_hash, &name)?;
+    }
+    tx.commit()?;
+    Ok(ids)
+}
+
+/// Writes every character, with its class spell lists and the referenced spells, to one
+/// zip of `CharacterBundle`s in the exports dir (or `output_dir`) and returns its path.
+#[tauri::command]
+pub async fn export_all_characters(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    output_dir: Option<String>,
+) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let bundles = fetch_all_character_bundles(&conn)?;
+
+        let output_dir = resolve_output_dir(&app, output_dir.as_deref(), "exports")?;
+        let path = output_dir.join(format!(
+            "characters_{}.zip",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%3f")
+        ));
+        write_character_bundles_zip(&bundles, &path)?;
+        Ok::<String, AppError>(path.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Restores an `export_all_characters` archive, upserting characters by name and linking
+/// spells by content hash. Spells missing from the library are imported alongside.
+#[tauri::command]
+pub async fn import_all_characters(
+    state: State<'_, Arc<Pool>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    path: String,
+) -> Result<Vec<i64>, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let _import_guard = maintenance_state.start_import()?;
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let bytes = std::fs::read(&path)?;
+        let hash = hex::encode(Sha256::digest(&bytes));
+        let bundles = read_character_bundles_zip(&bytes)?;
+
+        let mut conn = pool.get()?;
+        import_character_bundles_with_conn(&mut conn, bundles, &hash)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -585,6 +848,14 @@ mod tests {
                 notes TEXT,
                 spell_content_hash TEXT
             );
+            CREATE TABLE spellbook (
+                character_id INTEGER,
+                spell_id INTEGER,
+                prepared INTEGER DEFAULT 0,
+                known INTEGER DEFAULT 1,
+                notes TEXT,
+                PRIMARY KEY(character_id, spell_id)
+            );
             CREATE TABLE artifact (
                 id INTEGER PRIMARY KEY,
                 type TEXT NOT NULL,
@@ -627,6 +898,7 @@ mod tests {
                     notes: Some("combat staple".to_string()),
                 }],
             }],
+            spellbook: vec![],
         }
     }
 
@@ -710,4 +982,108 @@ mod tests {
             .expect("count hashes");
         assert_eq!(count_with_hash, 1);
     }
+
+    #[test]
+    fn test_export_and_import_all_characters_round_trip_shared_spell() {
+        let source = setup_bundle_db();
+        let hash = "stored-shield-hash";
+        source
+            .execute_batch(&format!(
+                "INSERT INTO \"character\" (id, name, type, com_enabled) VALUES (1, 'Elminster', 'PC', 0);
+                 INSERT INTO \"character\" (id, name, type, com_enabled) VALUES (2, 'Khelben', 'PC', 0);
+                 INSERT INTO character_class (id, character_id, class_name, level) VALUES (10, 1, 'Mage', 9);
+                 INSERT INTO character_class (id, character_id, class_name, level) VALUES (20, 2, 'Mage', 7);
+                 INSERT INTO spell (id, name, level, description, school, source, content_hash)
+                 VALUES (5, 'Shield', 1, 'An invisible barrier.', 'Evocation', 'PHB', '{hash}');
+                 INSERT INTO character_class_spell (character_class_id, spell_id, list_type, spell_content_hash)
+                 VALUES (10, 5, 'KNOWN', '{hash}');
+                 INSERT INTO character_class_spell (character_class_id, spell_id, list_type, spell_content_hash)
+                 VALUES (20, 5, 'PREPARED', '{hash}');
+                 INSERT INTO spell (id, name, level, description, school, source)
+                 VALUES (6, 'Light', 1, 'A glowing orb.', 'Alteration', 'PHB');
+                 INSERT INTO spellbook (character_id, spell_id, prepared, known, notes)
+                 VALUES (1, 6, 1, 1, 'legacy note');"
+            ))
+            .expect("seed characters");
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("characters.zip");
+        let bundles = fetch_all_character_bundles(&source).expect("fetch bundles");
+        assert_eq!(
+            bundles[0].classes[0].spells[0]
+                .spell
+                .content_hash
+                .as_deref(),
+            Some(hash),
+            "stored hash is exported"
+        );
+        assert_eq!(bundles[0].spellbook.len(), 1);
+        assert_eq!(
+            bundles[0].spellbook[0].spell.content_hash, None,
+            "a spell without a stored hash is exported with a warning, not an error"
+        );
+        write_character_bundles_zip(&bundles, &path).expect("write archive");
+
+        let restored = read_character_bundles_zip(&std::fs::read(&path).expect("read archive"))
+            .expect("parse archive");
+        assert_eq!(restored.len(), 2);
+
+        let mut target = setup_bundle_db();
+        let ids = import_character_bundles_with_conn(&mut target, restored, "archive-hash")
+            .expect("import archive");
+        assert_eq!(ids.len(), 2);
+
+        let spell_count: i64 = target
+            .query_row("SELECT COUNT(*) FROM spell", [], |row| row.get(0))
+            .expect("count spells");
+        assert_eq!(spell_count, 2, "shared spell must be imported once");
+
+        let spellbook: (String, i64, i64, Option<String>) = target
+            .query_row(
+                "SELECT s.name, sb.prepared, sb.known, sb.notes
+                 FROM spellbook sb
+                 JOIN spell s ON s.id = sb.spell_id
+                 JOIN \"character\" c ON c.id = sb.character_id
+                 WHERE c.name = 'Elminster'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .expect("restored spellbook row");
+        assert_eq!(
+            spellbook,
+            ("Light".to_string(), 1, 1, Some("legacy note".to_string()))
+        );
+
+        let shield_hash: Option<String> = target
+            .query_row(
+                "SELECT content_hash FROM spell WHERE name = 'Shield'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("imported shield hash");
+        let links: Vec<(String, String, Option<String>)> = target
+            .prepare(
+                "SELECT c.name, ccs.list_type, ccs.spell_content_hash
+                 FROM character_class_spell ccs
+                 JOIN character_class cc ON cc.id = ccs.character_class_id
+                 JOIN \"character\" c ON c.id = cc.character_id
+                 ORDER BY c.name",
+            )
+            .expect("prepare links")
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .expect("query links")
+            .collect::<Result<_, _>>()
+            .expect("collect links");
+        assert_eq!(
+            links,
+            vec![
+                (
+                    "Elminster".to_string(),
+                    "KNOWN".to_string(),
+                    shield_hash.clone()
+                ),
+                ("Khelben".to_string(), "PREPARED".to_string(), shield_hash),
+            ]
+        );
+    }
 }
@@ -2,6 +2,7 @@ pub mod characters;
 pub mod export;
 pub mod import;
 pub mod search;
+pub mod settings;
 pub mod spells;
 pub mod vault;
 
@@ -9,6 +10,7 @@ pub use characters::*;
 pub use export::*;
 pub use import::*;
 pub use search::*;
+pub use settings::*;
 pub use spells::*;
 pub use vault::*;
 pub mod io_character;
@@ -1,14 +1,31 @@
-use crate::db::Pool;
+use crate::commands::settings::get_setting_with_conn;
+use crate::commands::spells::{
+    description_preview, description_preview_sql, get_spell_from_conn,
+    DEFAULT_DESCRIPTION_PREVIEW_CHARS,
+};
+use crate::commands::vault::VaultMaintenanceState;
+use crate::db::{Pool, VecMode};
 use crate::error::AppError;
+use crate::models::canonical_spell::{normalize_string, parse_list_column, NormalizationMode};
 use crate::models::{
-    ChatResponse, Facets, SavedSearch, SavedSearchPayload, SearchFilters, SpellSummary,
+    ChatResponse, ClassFacetCount, Facets, FtsConsistency, RangeKind, RangeSpec, ReembedSummary,
+    SavedSearch, SavedSearchPayload, SearchFilters, SearchRebuildSummary, SemanticSearchResults,
+    SpellDetail, SpellSummary, TagUsage, UnembeddedSpells, VecModeStatus,
 };
 use crate::sidecar::call_sidecar;
+use crate::utils::spell_parser::SpellParser;
 use rusqlite::params;
 use rusqlite::Connection;
+use rusqlite::OptionalExtension;
 use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tauri::State;
+use tauri::{Emitter, State, Window};
+use tracing::warn;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 // ---------------------------------------------------------------------------
 // FTS5 query builder — two-tier search
@@ -138,6 +155,27 @@ fn build_fts_query(raw_query: &str) -> String {
     wrap_as_fts_phrase(trimmed)
 }
 
+/// Builds the FTS5 MATCH expression for a whole-word search: every token becomes its
+/// own quoted phrase, so each word must match a complete indexed token but the words
+/// need not be adjacent. Boolean operators behave as in advanced mode; a malformed
+/// operator expression treats the operators as literal words.
+fn build_whole_word_fts_query(raw_query: &str) -> String {
+    let tokens: Vec<&str> = raw_query.split_whitespace().collect();
+    if tokens.is_empty() {
+        return String::new();
+    }
+    if tokens.iter().any(|&t| is_fts_operator(t)) {
+        if let Some(advanced) = try_build_advanced_fts_query(&tokens) {
+            return advanced;
+        }
+    }
+    tokens
+        .iter()
+        .map(|token| wrap_as_fts_phrase(token))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 // ---------------------------------------------------------------------------
 // Search command utilities
 // ---------------------------------------------------------------------------
@@ -162,45 +200,114 @@ fn collect_facet_entries(conn: &Connection, sql: &str) -> Result<Vec<String>, Ap
     let mut all_entries = std::collections::BTreeSet::new();
     for row in rows {
         if let Some(s) = row? {
-            for part in s.split(',') {
-                let trimmed = part.trim();
-                if !trimmed.is_empty() {
-                    all_entries.insert(trimmed.to_string());
-                }
-            }
+            // List columns may be stored as JSON arrays or legacy comma-separated text.
+            all_entries.extend(parse_list_column(&s));
         }
     }
-    Ok(all_entries.into_iter().collect())
+    let mut entries: Vec<String> = all_entries.into_iter().collect();
+    entries.sort_by_cached_key(|entry| (facet_sort_key(entry), entry.clone()));
+    Ok(entries)
 }
 
+/// Collation key for facet values: diacritics are folded and case is ignored so
+/// "Évocation" files next to "Evocation" instead of after "Z". The displayed value is
+/// left untouched; ties fall back to the original string.
+fn facet_sort_key(value: &str) -> String {
+    value
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Largest result set `export_search_results` will export in one call. Exceeding it is an
+/// error rather than a silent truncation.
+pub(crate) const EXPORT_SEARCH_RESULT_CAP: usize = 10_000;
+
 fn search_keyword_with_conn(
     conn: &Connection,
     query: &str,
     filters: Option<SearchFilters>,
 ) -> Result<Vec<SpellSummary>, AppError> {
-    let has_text_query = !query.trim().is_empty();
-    let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+    search_keyword_with_conn_limit(
+        conn,
+        query,
+        filters,
+        SEARCH_RESULT_LIMIT,
+        DEFAULT_DESCRIPTION_PREVIEW_CHARS,
+        false,
+    )
+}
 
-    // When a text query is present we JOIN spell_fts so that bm25() is available
-    // for relevance ordering. The `s.` prefix avoids ambiguity on columns that
-    // exist in both `spell` and `spell_fts` (e.g. `tags`, `source`).
-    let mut sql = if has_text_query {
-        params.push(Box::new(build_fts_query(query)));
-        "SELECT s.id, s.name, s.school, s.sphere, s.level, s.class_list, s.components, \
-         s.duration, s.source, s.is_quest_spell, s.is_cantrip, s.tags \
-         FROM spell s JOIN spell_fts ON spell_fts.rowid = s.id \
-         WHERE spell_fts MATCH ?"
-            .to_string()
-    } else {
-        "SELECT id, name, school, sphere, level, class_list, components, duration, source, \
-         is_quest_spell, is_cantrip, tags FROM spell WHERE 1=1"
-            .to_string()
-    };
+/// Ids of every spell matching `query`/`filters`, in search order, ignoring the page limit.
+/// `operation` names the bulk action (e.g. "exporting") in the over-cap error.
+pub(crate) fn search_result_ids_capped(
+    conn: &Connection,
+    query: &str,
+    filters: Option<SearchFilters>,
+    operation: &str,
+) -> Result<Vec<i64>, AppError> {
+    let spells = search_keyword_with_conn_limit(
+        conn,
+        query,
+        filters,
+        EXPORT_SEARCH_RESULT_CAP + 1,
+        0,
+        false,
+    )?;
+    if spells.len() > EXPORT_SEARCH_RESULT_CAP {
+        return Err(AppError::Validation(format!(
+            "Search matches more than {} spells; narrow the filters before {}",
+            EXPORT_SEARCH_RESULT_CAP, operation
+        )));
+    }
+    Ok(spells.into_iter().map(|spell| spell.id).collect())
+}
 
-    // When joining with spell_fts, qualify all spell column references with `s.`
-    // to avoid ambiguous-column errors while keeping user input bound through `?`.
-    let col = if has_text_query { "s." } else { "" };
+/// Id of one spell chosen uniformly at random among those matching `filters`, or `None`
+/// when nothing matches.
+fn random_spell_id_with_conn(
+    conn: &Connection,
+    filters: Option<SearchFilters>,
+) -> Result<Option<i64>, AppError> {
+    let mut sql = "SELECT id FROM spell WHERE 1=1".to_string();
+    let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
+    push_search_filter_clauses(&mut sql, &mut params, "", filters);
+    sql.push_str(" ORDER BY RANDOM() LIMIT 1");
+    Ok(conn
+        .query_row(&sql, rusqlite::params_from_iter(params.iter()), |row| {
+            row.get(0)
+        })
+        .optional()?)
+}
 
+/// A random spell matching `filters` (e.g. for a scroll drop), or `None` when no spell
+/// matches.
+#[tauri::command]
+pub async fn get_random_spell(
+    state: State<'_, Arc<Pool>>,
+    filters: Option<SearchFilters>,
+) -> Result<Option<SpellDetail>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        match random_spell_id_with_conn(&conn, filters)? {
+            Some(id) => get_spell_from_conn(&conn, id),
+            None => Ok(None),
+        }
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Appends the `AND ...` clauses for `filters` to `sql`, binding values into `params`.
+/// `col` prefixes every spell column (e.g. `"s."` when joined with `spell_fts`).
+fn push_search_filter_clauses(
+    sql: &mut String,
+    params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    col: &str,
+    filters: Option<SearchFilters>,
+) {
     if let Some(f) = filters {
         if let Some(schools) = f.schools {
             let nonempty_schools: Vec<_> = schools.iter().filter(|s| !s.is_empty()).collect();
@@ -281,14 +388,92 @@ fn search_keyword_with_conn(
                 sql.push_str(&format!(" AND {}is_cantrip = 1", col));
             }
         }
+
+        if let Some(verified) = f.verified {
+            sql.push_str(&format!(" AND {}verified = ?", col));
+            params.push(Box::new(verified as i64));
+        }
+
+        let has_material =
+            format!("({col}material_components IS NOT NULL AND {col}material_components != '')");
+        if let Some(wanted) = f.has_material {
+            if wanted {
+                sql.push_str(&format!(" AND {}", has_material));
+            } else {
+                sql.push_str(&format!(" AND NOT {}", has_material));
+            }
+        }
+
+        if let Some(max_cost) = f.max_material_cost {
+            // Total cost is the sum of the parsed `gpValue`s in canonical_data; NULL when
+            // no component has one (or canonical_data is missing or malformed).
+            let cost = format!(
+                "(SELECT SUM(json_extract(m.value, '$.gpValue')) FROM json_each(\
+                 CASE WHEN json_valid({col}canonical_data) THEN {col}canonical_data END, \
+                 '$.material_components') m)"
+            );
+            sql.push_str(&format!(
+                " AND (CASE WHEN {cost} IS NOT NULL THEN {cost} <= ? WHEN {has_material} THEN ? ELSE 1 END)"
+            ));
+            params.push(Box::new(max_cost));
+            params.push(Box::new(
+                f.include_unknown_material_cost.unwrap_or(false) as i64
+            ));
+        }
     }
+}
 
+fn search_keyword_with_conn_limit(
+    conn: &Connection,
+    query: &str,
+    filters: Option<SearchFilters>,
+    limit: usize,
+    preview_chars: usize,
+    whole_word: bool,
+) -> Result<Vec<SpellSummary>, AppError> {
+    // Stored text is NFC-normalized on write; normalize the query the same way so
+    // decomposed input (e + U+0301) matches composed content (é).
+    let query = normalize_string(query, NormalizationMode::Exact);
+    let has_text_query = !query.is_empty();
+    let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    // When a text query is present we JOIN spell_fts so that bm25() is available
+    // for relevance ordering. The `s.` prefix avoids ambiguity on columns that
+    // exist in both `spell` and `spell_fts` (e.g. `tags`, `source`).
+    let mut sql = if has_text_query {
+        params.push(Box::new(if whole_word {
+            build_whole_word_fts_query(&query)
+        } else {
+            build_fts_query(&query)
+        }));
+        format!(
+            "SELECT s.id, s.name, s.school, s.sphere, s.level, s.class_list, s.components, \
+             s.duration, s.source, s.is_quest_spell, s.is_cantrip, s.tags, {} \
+             FROM spell s JOIN spell_fts ON spell_fts.rowid = s.id \
+             WHERE spell_fts MATCH ?",
+            description_preview_sql("s.description", preview_chars)
+        )
+    } else {
+        format!(
+            "SELECT id, name, school, sphere, level, class_list, components, duration, source, \
+             is_quest_spell, is_cantrip, tags, {} FROM spell WHERE 1=1",
+            description_preview_sql("description", preview_chars)
+        )
+    };
+
+    // When joining with spell_fts, qualify all spell column references with `s.`
+    // to avoid ambiguous-column errors while keeping user input bound through `?`.
+    let col = if has_text_query { "s." } else { "" };
+
+    push_search_filter_clauses(&mut sql, &mut params, col, filters);
+
+    // The id tiebreak keeps equal scores/names in a stable order across identical queries.
     if has_text_query {
         sql.push_str(&format!(
-            " ORDER BY bm25(spell_fts) ASC LIMIT {SEARCH_RESULT_LIMIT}"
+            " ORDER BY bm25(spell_fts) ASC, s.id ASC LIMIT {limit}"
         ));
     } else {
-        sql.push_str(&format!(" ORDER BY name ASC LIMIT {SEARCH_RESULT_LIMIT}"));
+        sql.push_str(&format!(" ORDER BY name ASC, id ASC LIMIT {limit}"));
     }
 
     let mut stmt = conn.prepare(&sql)?;
@@ -306,6 +491,9 @@ fn search_keyword_with_conn(
             is_quest_spell: row.get(9)?,
             is_cantrip: row.get(10)?,
             tags: row.get(11)?,
+            description_preview: row
+                .get::<_, Option<String>>(12)?
+                .and_then(|text| description_preview(&text, preview_chars)),
         })
     })?;
 
@@ -316,16 +504,154 @@ fn search_keyword_with_conn(
     Ok(spells)
 }
 
+// ---------------------------------------------------------------------------
+// Range sorting
+// ---------------------------------------------------------------------------
+
+/// Sort key for perception-bounded and location-bounded ranges (sight, hearing,
+/// same room/structure/dungeon level). Larger than any realistic numeric range.
+const RANGE_SORT_PERCEPTION_FEET: f64 = 1_000_000.0;
+
+/// Sort key for ranges with no practical distance limit (wilderness, same plane,
+/// interplanar, domain, unlimited). Sorts after perception ranges.
+const RANGE_SORT_UNBOUNDED_FEET: f64 = 1_000_000_000.0;
+
+/// Places a range on a single feet scale for ordering:
+/// personal/touch = 0, numeric distances via [`RangeSpec::in_feet`], perception and
+/// location kinds = [`RANGE_SORT_PERCEPTION_FEET`], unbounded kinds =
+/// [`RANGE_SORT_UNBOUNDED_FEET`]. Per-level distances are ranked at caster level 1.
+/// Returns `None` for `special` (unparseable) ranges.
+fn range_sort_feet(spec: &RangeSpec) -> Option<f64> {
+    match spec.kind {
+        RangeKind::Personal | RangeKind::Touch => Some(0.0),
+        RangeKind::Distance | RangeKind::DistanceLos | RangeKind::DistanceLoe => {
+            spec.in_feet().or_else(|| {
+                let distance = spec.distance.as_ref()?;
+                let at_first_level =
+                    distance.value.unwrap_or(0.0) + distance.per_level.unwrap_or(0.0);
+                Some(at_first_level * spec.unit?.feet_per_unit())
+            })
+        }
+        RangeKind::Los
+        | RangeKind::Loe
+        | RangeKind::Sight
+        | RangeKind::Hearing
+        | RangeKind::Voice
+        | RangeKind::Senses
+        | RangeKind::SameRoom
+        | RangeKind::SameStructure
+        | RangeKind::SameDungeonLevel => Some(RANGE_SORT_PERCEPTION_FEET),
+        RangeKind::Wilderness
+        | RangeKind::SamePlane
+        | RangeKind::Interplanar
+        | RangeKind::AnywhereOnPlane
+        | RangeKind::Domain
+        | RangeKind::Unlimited => Some(RANGE_SORT_UNBOUNDED_FEET),
+        RangeKind::Special => None,
+    }
+}
+
+/// Reorders an already-fetched result page by range ascending (name breaks ties).
+/// The range comes from `canonical_data` when present, else the legacy `range` text
+/// is parsed. Unparseable ranges sort last.
+pub(crate) fn sort_spells_by_range(
+    conn: &Connection,
+    spells: &mut [SpellSummary],
+) -> Result<(), AppError> {
+    if spells.is_empty() {
+        return Ok(());
+    }
+
+    let placeholders = vec!["?"; spells.len()].join(", ");
+    let sql = format!(
+        "SELECT id, range, canonical_data FROM spell WHERE id IN ({})",
+        placeholders
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(
+        rusqlite::params_from_iter(spells.iter().map(|s| s.id)),
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        },
+    )?;
+
+    let parser = SpellParser::new();
+    let mut keys: HashMap<i64, Option<f64>> = HashMap::new();
+    for row in rows {
+        let (id, range, canonical_data) = row?;
+        let spec = canonical_data
+            .as_deref()
+            .and_then(|data| serde_json::from_str::<serde_json::Value>(data).ok())
+            .and_then(|value| value.get("range").cloned())
+            .and_then(|range| serde_json::from_value::<RangeSpec>(range).ok())
+            .or_else(|| {
+                range
+                    .as_deref()
+                    .filter(|r| !r.trim().is_empty())
+                    .map(|r| parser.parse_range(r))
+            });
+        keys.insert(id, spec.as_ref().and_then(range_sort_feet));
+    }
+
+    spells.sort_by(|a, b| {
+        let ka = keys.get(&a.id).copied().flatten();
+        let kb = keys.get(&b.id).copied().flatten();
+        match (ka, kb) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+        .then_with(|| a.name.cmp(&b.name))
+        .then_with(|| a.id.cmp(&b.id))
+    });
+    Ok(())
+}
+
+/// Applies an optional `sort_by` override to a result page. `None` keeps the
+/// query's own ordering (relevance or name).
+pub(crate) fn apply_sort_by(
+    conn: &Connection,
+    spells: &mut [SpellSummary],
+    sort_by: Option<&str>,
+) -> Result<(), AppError> {
+    match sort_by {
+        None | Some("") => Ok(()),
+        Some("range") => sort_spells_by_range(conn, spells),
+        Some(other) => Err(AppError::Validation(format!(
+            "Unsupported sort_by '{}'",
+            other
+        ))),
+    }
+}
+
 #[tauri::command]
 pub async fn search_keyword(
     state: State<'_, Arc<Pool>>,
     query: String,
     filters: Option<SearchFilters>,
+    sort_by: Option<String>,
+    description_preview_chars: Option<usize>,
+    whole_word: Option<bool>,
 ) -> Result<Vec<SpellSummary>, AppError> {
     let pool = state.inner().clone();
+    let preview_chars = description_preview_chars.unwrap_or(DEFAULT_DESCRIPTION_PREVIEW_CHARS);
     let result = tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        search_keyword_with_conn(&conn, &query, filters)
+        let mut spells = search_keyword_with_conn_limit(
+            &conn,
+            &query,
+            filters,
+            SEARCH_RESULT_LIMIT,
+            preview_chars,
+            whole_word.unwrap_or(false),
+        )?;
+        apply_sort_by(&conn, &mut spells, sort_by.as_deref())?;
+        Ok::<Vec<SpellSummary>, AppError>(spells)
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
@@ -333,64 +659,562 @@ pub async fn search_keyword(
     Ok(result)
 }
 
+/// Number of spells matching `filters`, using the same WHERE clauses as keyword search.
+fn get_spell_count_with_conn(
+    conn: &Connection,
+    filters: Option<SearchFilters>,
+) -> Result<i64, AppError> {
+    let mut sql = String::from("SELECT COUNT(*) FROM spell WHERE 1=1");
+    let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+    push_search_filter_clauses(&mut sql, &mut params, "", filters);
+    Ok(
+        conn.query_row(&sql, rusqlite::params_from_iter(params.iter()), |row| {
+            row.get(0)
+        })?,
+    )
+}
+
+#[tauri::command]
+pub async fn get_spell_count(
+    state: State<'_, Arc<Pool>>,
+    filters: Option<SearchFilters>,
+) -> Result<i64, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_spell_count_with_conn(&conn, filters)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[tauri::command]
+pub fn get_vec_mode(vec_mode: State<'_, VecMode>) -> VecModeStatus {
+    let mode = *vec_mode.inner();
+    VecModeStatus {
+        mode,
+        degraded: mode.is_degraded(),
+    }
+}
+
+/// Nearest spells to `vector` in `spell_vec`; requires sqlite-vec (`VecMode::Vec0`).
+fn search_semantic_vectors_with_conn(
+    conn: &Connection,
+    vector: &[f32],
+) -> Result<Vec<SpellSummary>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT s.id, s.name, s.school, s.sphere, s.level, s.class_list, s.components, s.duration,
+                s.source, s.is_quest_spell, s.is_cantrip, s.tags, vec_distance_cosine(v.v, ?) as distance,
+                {}
+         FROM spell_vec v
+         JOIN spell s ON s.id = v.rowid
+         ORDER BY distance ASC, s.id ASC
+         LIMIT 50",
+        description_preview_sql("s.description", DEFAULT_DESCRIPTION_PREVIEW_CHARS)
+    ))?;
+
+    let vec_json = serde_json::to_string(vector).unwrap();
+    let rows = stmt.query_map([vec_json], |row| {
+        Ok(SpellSummary {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            school: row.get(2)?,
+            sphere: row.get(3)?,
+            level: row.get(4)?,
+            class_list: row.get(5)?,
+            components: row.get(6)?,
+            duration: row.get(7)?,
+            source: row.get(8)?,
+            is_quest_spell: row.get(9)?,
+            is_cantrip: row.get(10)?,
+            tags: row.get(11)?,
+            description_preview: row
+                .get::<_, Option<String>>(13)?
+                .and_then(|text| description_preview(&text, DEFAULT_DESCRIPTION_PREVIEW_CHARS)),
+        })
+    })?;
+
+    let mut spells = vec![];
+    for spell in rows {
+        spells.push(spell?);
+    }
+    Ok(spells)
+}
+
+/// Runs semantic search in `vec_mode`. Under blob fallback the distance function is
+/// unavailable, so the query goes to keyword search and the result is marked degraded;
+/// otherwise `vector` (the query's embedding) is required.
+fn search_semantic_with_conn(
+    conn: &Connection,
+    vec_mode: VecMode,
+    query: &str,
+    vector: Option<&[f32]>,
+) -> Result<SemanticSearchResults, AppError> {
+    let degraded = vec_mode.is_degraded();
+    let spells = if degraded {
+        search_keyword_with_conn(conn, query, None)?
+    } else {
+        let vector = vector.ok_or_else(|| AppError::Sidecar("Empty embedding returned".into()))?;
+        search_semantic_vectors_with_conn(conn, vector)?
+    };
+    Ok(SemanticSearchResults {
+        mode: vec_mode,
+        degraded,
+        spells,
+    })
+}
+
+/// Semantic search over `spell_vec`. Without sqlite-vec (`VecMode::BlobFallback`) this
+/// degrades to keyword search on the same query and says so in the response.
 #[tauri::command]
 pub async fn search_semantic(
     state: State<'_, Arc<Pool>>,
+    vec_mode: State<'_, VecMode>,
     query: String,
-) -> Result<Vec<SpellSummary>, AppError> {
-    let embedding_resp = call_sidecar("embed", json!({"text": query})).await?;
-    let vector: Vec<f32> = serde_json::from_value(
-        embedding_resp
-            .get("embedding")
-            .cloned()
-            .unwrap_or(json!([])),
+) -> Result<SemanticSearchResults, AppError> {
+    let vec_mode = *vec_mode.inner();
+    let vector = if vec_mode.is_degraded() {
+        warn!("search_semantic: sqlite-vec unavailable; using keyword search");
+        None
+    } else {
+        let embedding_resp = call_sidecar("embed", json!({"text": query})).await?;
+        let vector: Vec<f32> = serde_json::from_value(
+            embedding_resp
+                .get("embedding")
+                .cloned()
+                .unwrap_or(json!([])),
+        )
+        .map_err(|e| AppError::Sidecar(format!("Failed to parse embedding: {}", e)))?;
+        if vector.is_empty() {
+            return Err(AppError::Sidecar("Empty embedding returned".into()));
+        }
+        Some(vector)
+    };
+
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        search_semantic_with_conn(&conn, vec_mode, &query, vector.as_deref())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Spells sent to the sidecar per `embed` call during [`reembed_all_spells`].
+const REEMBED_BATCH_SIZE: usize = 32;
+
+/// Cancellation flag for a running [`reembed_all_spells`]; checked between batches.
+#[derive(Debug, Default)]
+pub struct ReembedState {
+    cancel_requested: AtomicBool,
+}
+
+impl ReembedState {
+    pub fn request_cancel(&self) {
+        self.cancel_requested.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether a cancel was requested and clears the flag.
+    pub fn take_cancel(&self) -> bool {
+        self.cancel_requested.swap(false, Ordering::SeqCst)
+    }
+}
+
+/// Settings key holding the template spells are rendered through before embedding.
+pub const EMBEDDING_TEMPLATE_SETTING: &str = "embedding_template";
+
+/// Template used when no `embedding_template` setting is stored: name and description.
+pub const DEFAULT_EMBEDDING_TEMPLATE: &str = "{name}\n{description}";
+
+/// SHA-256 hex of the text a spell is embedded from. Hashing the rendered text means a
+/// template change invalidates every stored `embedding_hash`.
+pub fn embedding_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+/// Spell fields available to an embedding template as `{name}`, `{school}`, etc.
+#[derive(Debug, Default)]
+struct EmbeddingFields {
+    name: String,
+    school: String,
+    sphere: String,
+    level: i64,
+    class_list: String,
+    tags: String,
+    description: String,
+}
+
+/// Substitutes `{name}`, `{school}`, `{sphere}`, `{level}`, `{class_list}`, `{tags}`
+/// and `{description}`; list columns are joined with ", ". Unknown placeholders are
+/// left as written.
+fn render_embedding_text(template: &str, fields: &EmbeddingFields) -> String {
+    let join_list = |raw: &str| parse_list_column(raw).join(", ");
+    template
+        .replace("{name}", &fields.name)
+        .replace("{school}", &fields.school)
+        .replace("{sphere}", &fields.sphere)
+        .replace("{level}", &fields.level.to_string())
+        .replace("{class_list}", &join_list(&fields.class_list))
+        .replace("{tags}", &join_list(&fields.tags))
+        .replace("{description}", &fields.description)
+}
+
+/// The stored `embedding_template` setting, or [`DEFAULT_EMBEDDING_TEMPLATE`] when unset
+/// (or before migration 0026 created the settings table).
+fn embedding_template_with_conn(conn: &Connection) -> Result<String, AppError> {
+    if !crate::db::table_has_column(conn, "settings", "value") {
+        return Ok(DEFAULT_EMBEDDING_TEMPLATE.to_string());
+    }
+    match get_setting_with_conn(conn, EMBEDDING_TEMPLATE_SETTING)? {
+        None => Ok(DEFAULT_EMBEDDING_TEMPLATE.to_string()),
+        Some(serde_json::Value::String(template)) if !template.trim().is_empty() => Ok(template),
+        Some(other) => Err(AppError::Validation(format!(
+            "Setting '{}' must be a non-empty string, got {}",
+            EMBEDDING_TEMPLATE_SETTING, other
+        ))),
+    }
+}
+
+/// A spell whose `spell_vec` row is missing or was built from different text.
+#[derive(Debug, Clone)]
+struct PendingEmbedding {
+    id: i64,
+    text: String,
+    hash: String,
+}
+
+/// Spells that need a (re)embed, in id order, plus the count already up to date.
+/// A spell is up to date when it has a `spell_vec` row and its `embedding_hash`
+/// matches its text rendered through the current embedding template.
+fn pending_embeddings_with_conn(
+    conn: &Connection,
+) -> Result<(Vec<PendingEmbedding>, usize), AppError> {
+    let template = embedding_template_with_conn(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.embedding_hash, v.rowid IS NOT NULL, s.name, s.school, s.sphere,
+                s.level, s.class_list, s.tags, s.description
+         FROM spell s
+         LEFT JOIN spell_vec v ON v.rowid = s.id
+         ORDER BY s.id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        let text = |idx: usize| -> rusqlite::Result<String> {
+            Ok(row.get::<_, Option<String>>(idx)?.unwrap_or_default())
+        };
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, Option<String>>(1)?,
+            row.get::<_, bool>(2)?,
+            EmbeddingFields {
+                name: text(3)?,
+                school: text(4)?,
+                sphere: text(5)?,
+                level: row.get::<_, Option<i64>>(6)?.unwrap_or_default(),
+                class_list: text(7)?,
+                tags: text(8)?,
+                description: text(9)?,
+            },
+        ))
+    })?;
+
+    let mut pending = Vec::new();
+    let mut skipped = 0;
+    for row in rows {
+        let (id, stored_hash, has_vector, fields) = row?;
+        let text = render_embedding_text(&template, &fields);
+        let hash = embedding_hash(&text);
+        if has_vector && stored_hash.as_deref() == Some(hash.as_str()) {
+            skipped += 1;
+            continue;
+        }
+        pending.push(PendingEmbedding { id, text, hash });
+    }
+    Ok((pending, skipped))
+}
+
+/// Writes one batch of vectors and their hashes in a single transaction, so each
+/// completed batch is a checkpoint a cancelled run can resume from.
+fn store_embeddings_with_conn(
+    conn: &mut Connection,
+    batch: &[PendingEmbedding],
+    vectors: &[Vec<f32>],
+) -> Result<(), AppError> {
+    if batch.len() != vectors.len() {
+        return Err(AppError::Sidecar(format!(
+            "Expected {} embeddings, got {}",
+            batch.len(),
+            vectors.len()
+        )));
+    }
+    let tx = conn.transaction()?;
+    for (spell, vector) in batch.iter().zip(vectors) {
+        let vec_json = serde_json::to_string(vector).unwrap();
+        // vec0 tables reject INSERT OR REPLACE, so drop the old row first.
+        tx.execute("DELETE FROM spell_vec WHERE rowid = ?", [spell.id])?;
+        tx.execute(
+            "INSERT INTO spell_vec (rowid, v) VALUES (?, ?)",
+            params![spell.id, vec_json],
+        )?;
+        tx.execute(
+            "UPDATE spell SET embedding_hash = ? WHERE id = ?",
+            params![spell.hash, spell.id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Embeds `pending` in batches through `embed` (the sidecar's `embed` call in the app),
+/// committing each batch. Stops early, returning `cancelled = true`, when `reembed` has a
+/// cancel request between batches. `on_batch(done, total)` is called after every
+/// committed batch.
+async fn embed_pending_spells<F, Fut>(
+    pool: &Arc<Pool>,
+    pending: &[PendingEmbedding],
+    reembed: &ReembedState,
+    embed: &mut F,
+    mut on_batch: impl FnMut(usize, usize),
+) -> Result<(usize, bool), AppError>
+where
+    F: FnMut(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<Vec<f32>>, AppError>>,
+{
+    let mut embedded = 0;
+    for batch in pending.chunks(REEMBED_BATCH_SIZE) {
+        if reembed.take_cancel() {
+            return Ok((embedded, true));
+        }
+
+        let texts = batch.iter().map(|spell| spell.text.clone()).collect();
+        let vectors = embed(texts).await?;
+
+        let pool = pool.clone();
+        let batch = batch.to_vec();
+        let batch_len = batch.len();
+        tokio::task::spawn_blocking(move || {
+            let mut conn = pool.get()?;
+            store_embeddings_with_conn(&mut conn, &batch, &vectors)
+        })
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))??;
+        embedded += batch_len;
+        on_batch(embedded, pending.len());
+    }
+    Ok((embedded, false))
+}
+
+/// Sends `texts` to the sidecar's `embed` method and returns one vector per text.
+async fn embed_texts_with_sidecar(texts: Vec<String>) -> Result<Vec<Vec<f32>>, AppError> {
+    let response = call_sidecar("embed", json!({ "texts": texts })).await?;
+    serde_json::from_value(response.get("vectors").cloned().unwrap_or(json!([])))
+        .map_err(|e| AppError::Sidecar(format!("Failed to parse embeddings: {}", e)))
+}
+
+/// Body of [`reembed_all_spells`]. Holds the search-rebuild maintenance guard for the
+/// whole run and refuses under `VecMode::BlobFallback`, where stored vectors are never
+/// searched.
+async fn reembed_all_spells_with_state<F, Fut>(
+    pool: &Arc<Pool>,
+    vec_mode: VecMode,
+    maintenance_state: &VaultMaintenanceState,
+    reembed: &ReembedState,
+    mut embed: F,
+) -> Result<ReembedSummary, AppError>
+where
+    F: FnMut(Vec<String>) -> Fut,
+    Fut: std::future::Future<Output = Result<Vec<Vec<f32>>, AppError>>,
+{
+    if vec_mode.is_degraded() {
+        return Err(AppError::Search(
+            "Semantic search is unavailable without sqlite-vec; spells were not re-embedded"
+                .to_string(),
+        ));
+    }
+    let _guard = maintenance_state.start_search_rebuild()?;
+    // A cancel issued before this run started must not stop it.
+    reembed.take_cancel();
+
+    let conn_pool = pool.clone();
+    let (pending, skipped) = tokio::task::spawn_blocking(move || {
+        let conn = conn_pool.get()?;
+        pending_embeddings_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    let (embedded, cancelled) =
+        embed_pending_spells(pool, &pending, reembed, &mut embed, |_, _| {}).await?;
+    if cancelled {
+        warn!(embedded, "reembed_all_spells: cancelled");
+    }
+    Ok(ReembedSummary {
+        embedded,
+        skipped,
+        remaining: pending.len() - embedded,
+        cancelled,
+    })
+}
+
+/// Rebuilds `spell_vec` through the sidecar, skipping spells whose embedding hash
+/// still matches. Progress is committed per batch, so after [`cancel_reembed`] (or a
+/// crash) running this again picks up where it stopped.
+#[tauri::command]
+pub async fn reembed_all_spells(
+    state: State<'_, Arc<Pool>>,
+    vec_mode: State<'_, VecMode>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    reembed: State<'_, Arc<ReembedState>>,
+) -> Result<ReembedSummary, AppError> {
+    reembed_all_spells_with_state(
+        state.inner(),
+        *vec_mode.inner(),
+        maintenance_state.inner(),
+        reembed.inner(),
+        embed_texts_with_sidecar,
     )
-    .map_err(|e| AppError::Sidecar(format!("Failed to parse embedding: {}", e)))?;
+    .await
+}
+
+/// Asks a running [`reembed_all_spells`] to stop after its current batch.
+#[tauri::command]
+pub fn cancel_reembed(reembed: State<'_, Arc<ReembedState>>) {
+    reembed.request_cancel();
+}
+
+/// Spells with no `spell_vec` row, by name then id.
+fn list_spells_without_embeddings_with_conn(
+    conn: &Connection,
+) -> Result<Vec<SpellSummary>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT s.id, s.name, s.school, s.sphere, s.level, s.class_list, s.components,
+                s.duration, s.source, s.is_quest_spell, s.is_cantrip, s.tags, {}
+         FROM spell s
+         LEFT JOIN spell_vec v ON v.rowid = s.id
+         WHERE v.rowid IS NULL
+         ORDER BY s.name ASC, s.id ASC",
+        description_preview_sql("s.description", DEFAULT_DESCRIPTION_PREVIEW_CHARS)
+    ))?;
+    let rows = stmt.query_map([], |row| {
+        Ok(SpellSummary {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            school: row.get(2)?,
+            sphere: row.get(3)?,
+            level: row.get(4)?,
+            class_list: row.get(5)?,
+            components: row.get(6)?,
+            duration: row.get(7)?,
+            source: row.get(8)?,
+            is_quest_spell: row.get(9)?,
+            is_cantrip: row.get(10)?,
+            tags: row.get(11)?,
+            description_preview: row
+                .get::<_, Option<String>>(12)?
+                .and_then(|text| description_preview(&text, DEFAULT_DESCRIPTION_PREVIEW_CHARS)),
+        })
+    })?;
+
+    let mut spells = vec![];
+    for spell in rows {
+        spells.push(spell?);
+    }
+    Ok(spells)
+}
 
-    if vector.is_empty() {
-        return Err(AppError::Sidecar("Empty embedding returned".into()));
+/// Spells not yet covered by semantic search, so the UI can offer to embed them.
+#[tauri::command]
+pub async fn list_spells_without_embeddings(
+    state: State<'_, Arc<Pool>>,
+    vec_mode: State<'_, VecMode>,
+) -> Result<UnembeddedSpells, AppError> {
+    let vec_mode = *vec_mode.inner();
+    if vec_mode.is_degraded() {
+        return Ok(UnembeddedSpells {
+            vec_mode,
+            degraded: true,
+            spells: vec![],
+        });
     }
 
     let pool = state.inner().clone();
-    let result = tokio::task::spawn_blocking(move || {
+    let spells = tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT s.id, s.name, s.school, s.sphere, s.level, s.class_list, s.components, s.duration,
-                    s.source, s.is_quest_spell, s.is_cantrip, s.tags, vec_distance_cosine(v.v, ?) as distance
-             FROM spell_vec v
-             JOIN spell s ON s.id = v.rowid
-             ORDER BY distance ASC
-             LIMIT 50",
-        )?;
+        list_spells_without_embeddings_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+    Ok(UnembeddedSpells {
+        vec_mode,
+        degraded: false,
+        spells,
+    })
+}
 
-        let vec_json = serde_json::to_string(&vector).unwrap();
-        let rows = stmt.query_map([vec_json], |row| {
-            Ok(SpellSummary {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                school: row.get(2)?,
-                sphere: row.get(3)?,
-                level: row.get(4)?,
-                class_list: row.get(5)?,
-                components: row.get(6)?,
-                duration: row.get(7)?,
-                source: row.get(8)?,
-                is_quest_spell: row.get(9)?,
-                is_cantrip: row.get(10)?,
-                tags: row.get(11)?,
-            })
-        })?;
+/// Every tag with the number of spells using it, most used first, then alphabetically.
+fn get_tags_with_usage_with_conn(conn: &Connection) -> Result<Vec<TagUsage>, AppError> {
+    let mut stmt = conn.prepare("SELECT tags FROM spell")?;
+    let rows = stmt.query_map([], |row| row.get::<_, Option<String>>(0))?;
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for row in rows {
+        if let Some(tags) = row? {
+            // parse_list_column dedups, so a spell counts once per tag.
+            for tag in parse_list_column(&tags) {
+                *counts.entry(tag).or_insert(0) += 1;
+            }
+        }
+    }
+    let mut usage: Vec<TagUsage> = counts
+        .into_iter()
+        .map(|(tag, count)| TagUsage { tag, count })
+        .collect();
+    usage.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+    Ok(usage)
+}
+
+#[tauri::command]
+pub async fn get_tags_with_usage(state: State<'_, Arc<Pool>>) -> Result<Vec<TagUsage>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_tags_with_usage_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Every class with the number of spells listing it, most common first; ties are ordered
+/// like facet values.
+fn get_class_facet_counts_with_conn(conn: &Connection) -> Result<Vec<ClassFacetCount>, AppError> {
+    let mut stmt = conn.prepare("SELECT class_list FROM spell")?;
+    let rows = stmt.query_map([], |row| row.get::<_, Option<String>>(0))?;
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for row in rows {
+        if let Some(class_list) = row? {
+            // JSON arrays and legacy comma-separated text both go through parse_list_column.
+            for class in parse_list_column(&class_list) {
+                *counts.entry(class).or_insert(0) += 1;
+            }
+        }
+    }
+    let mut classes: Vec<ClassFacetCount> = counts
+        .into_iter()
+        .map(|(class, count)| ClassFacetCount { class, count })
+        .collect();
+    classes.sort_by_cached_key(|c| (-c.count, facet_sort_key(&c.class), c.class.clone()));
+    Ok(classes)
+}
 
-        let mut spells = vec![];
-        for spell in rows {
-            spells.push(spell?);
-        }
-        Ok::<Vec<SpellSummary>, AppError>(spells)
+#[tauri::command]
+pub async fn get_class_facet_counts(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<ClassFacetCount>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_class_facet_counts_with_conn(&conn)
     })
     .await
-    .map_err(|e| AppError::Unknown(e.to_string()))??;
-
-    Ok(result)
+    .map_err(|e| AppError::Unknown(e.to_string()))?
 }
 
 #[tauri::command]
@@ -496,6 +1320,156 @@ pub async fn delete_saved_search(state: State<'_, Arc<Pool>>, id: i64) -> Result
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// FTS consistency check / rebuild
+// ---------------------------------------------------------------------------
+
+/// Repopulates `spell_fts` from `spell`. Mirrors the repopulation INSERT in
+/// migration 0014 (FTS5 `'rebuild'` cannot be used because the canonical_*
+/// columns do not exist on the content table); keep the two in sync.
+const REBUILD_SPELL_FTS_SQL: &str = r#"
+INSERT INTO spell_fts(spell_fts) VALUES('delete-all');
+INSERT INTO spell_fts(rowid, name, description, material_components, tags, source, author,
+    canonical_range_text, canonical_duration_text, canonical_area_text, canonical_casting_time_text,
+    canonical_saving_throw_text, canonical_damage_text, canonical_mr_text, canonical_xp_text)
+SELECT id, name, description, material_components, tags, source, author,
+    json_extract(canonical_data, '$.range.text'),
+    json_extract(canonical_data, '$.duration.text'),
+    json_extract(canonical_data, '$.area.text'),
+    COALESCE(json_extract(canonical_data, '$.casting_time.text'), '') || ' ' || COALESCE(json_extract(canonical_data, '$.casting_time.raw_legacy_value'), ''),
+    COALESCE(json_extract(canonical_data, '$.saving_throw.raw_legacy_value'), '') || ' ' || COALESCE(json_extract(canonical_data, '$.saving_throw.notes'), ''),
+    COALESCE(json_extract(canonical_data, '$.damage.source_text'), '') || ' ' || COALESCE(json_extract(canonical_data, '$.damage.notes'), '') || ' ' || COALESCE(json_extract(canonical_data, '$.damage.dm_guidance'), ''),
+    COALESCE(json_extract(canonical_data, '$.magic_resistance.source_text'), '') || ' ' || COALESCE(json_extract(canonical_data, '$.magic_resistance.notes'), '') || ' ' || COALESCE(json_extract(canonical_data, '$.magic_resistance.special_rule'), ''),
+    COALESCE(json_extract(canonical_data, '$.experience_cost.source_text'), '') || ' ' || COALESCE(json_extract(canonical_data, '$.experience_cost.notes'), '') || ' ' || COALESCE(json_extract(canonical_data, '$.experience_cost.dm_guidance'), '')
+FROM spell;
+"#;
+
+/// Compares the `spell` row count with the number of documents in `spell_fts`.
+pub(crate) fn check_fts_consistency_with_conn(
+    conn: &Connection,
+) -> Result<FtsConsistency, AppError> {
+    let spell_count: i64 = conn.query_row("SELECT COUNT(*) FROM spell", [], |row| row.get(0))?;
+    // COUNT(*) on spell_fts would read through to the content table, which lacks the
+    // canonical_* columns; spell_fts_docsize holds one row per indexed document.
+    let fts_count: i64 = conn.query_row("SELECT COUNT(*) FROM spell_fts_docsize", [], |row| {
+        row.get(0)
+    })?;
+    Ok(FtsConsistency {
+        spell_count,
+        fts_count,
+        consistent: spell_count == fts_count,
+    })
+}
+
+/// Clears and repopulates `spell_fts` in one transaction, then re-checks consistency.
+pub(crate) fn rebuild_spell_fts_with_conn(
+    conn: &mut Connection,
+) -> Result<FtsConsistency, AppError> {
+    let tx = conn.transaction()?;
+    tx.execute_batch(REBUILD_SPELL_FTS_SQL)?;
+    tx.commit()?;
+    check_fts_consistency_with_conn(conn)
+}
+
+#[tauri::command]
+pub async fn check_fts_consistency(
+    state: State<'_, Arc<Pool>>,
+) -> Result<FtsConsistency, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        check_fts_consistency_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn rebuild_spell_fts(state: State<'_, Arc<Pool>>) -> Result<FtsConsistency, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get()?;
+        rebuild_spell_fts_with_conn(&mut conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+/// FTS half of [`rebuild_search_indexes`]: rebuilds `spell_fts`, then (when `with_vectors`)
+/// lists the spells whose vectors are missing or stale.
+fn prepare_search_rebuild_with_conn(
+    conn: &mut Connection,
+    with_vectors: bool,
+) -> Result<(FtsConsistency, Vec<PendingEmbedding>), AppError> {
+    let fts = rebuild_spell_fts_with_conn(conn)?;
+    let pending = if with_vectors {
+        pending_embeddings_with_conn(conn)?.0
+    } else {
+        vec![]
+    };
+    Ok((fts, pending))
+}
+
+/// One-step repair for everything search-related, e.g. after a restore: rebuilds
+/// `spell_fts`, then re-embeds spells with missing or stale vectors when sqlite-vec is
+/// available. Holds the vault maintenance lock throughout and emits
+/// `search-rebuild-progress` events (`{ stage, current, total }`).
+#[tauri::command]
+pub async fn rebuild_search_indexes(
+    window: Window,
+    state: State<'_, Arc<Pool>>,
+    vec_mode: State<'_, VecMode>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    reembed: State<'_, Arc<ReembedState>>,
+) -> Result<SearchRebuildSummary, AppError> {
+    let _guard = maintenance_state.start_search_rebuild()?;
+    let vec_mode = *vec_mode.inner();
+    let with_vectors = !vec_mode.is_degraded();
+    reembed.take_cancel();
+
+    let _ = window.emit(
+        "search-rebuild-progress",
+        json!({ "stage": "fts", "current": 0, "total": 1 }),
+    );
+    let pool = state.inner().clone();
+    let (fts, pending) = tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get()?;
+        prepare_search_rebuild_with_conn(&mut conn, with_vectors)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+    let _ = window.emit(
+        "search-rebuild-progress",
+        json!({ "stage": "fts", "current": 1, "total": 1 }),
+    );
+
+    let (vectors_rebuilt, _cancelled) = embed_pending_spells(
+        state.inner(),
+        &pending,
+        reembed.inner(),
+        &mut embed_texts_with_sidecar,
+        |current, total| {
+            let _ = window.emit(
+                "search-rebuild-progress",
+                json!({ "stage": "vectors", "current": current, "total": total }),
+            );
+        },
+    )
+    .await?;
+
+    Ok(SearchRebuildSummary {
+        fts_rebuilt: true,
+        fts_count: fts.fts_count,
+        fts_consistent: fts.consistent,
+        vectors_rebuilt,
+        vec_mode,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use rusqlite::Connection;
@@ -869,6 +1843,7 @@ mod tests {
                 class_list       TEXT DEFAULT '',
                 components       TEXT DEFAULT '',
                 duration         TEXT DEFAULT '',
+                range            TEXT DEFAULT '',
                 is_quest_spell   INTEGER DEFAULT 0,
                 is_cantrip       INTEGER DEFAULT 0,
                 canonical_data   TEXT
@@ -899,6 +1874,53 @@ mod tests {
             .collect()
     }
 
+    #[test]
+    fn test_search_ties_break_by_id_deterministically() {
+        let conn = setup_search_db();
+        insert_spell(&conn, 7, "Twin Bolt", "A bolt of force");
+        insert_spell(&conn, 3, "Twin Bolt", "A bolt of force");
+        insert_spell(&conn, 5, "Twin Bolt", "A bolt of force");
+
+        for _ in 0..5 {
+            assert_eq!(search_ids(&conn, "bolt"), vec![3, 5, 7]);
+            assert_eq!(search_ids(&conn, ""), vec![3, 5, 7]);
+        }
+    }
+
+    #[test]
+    fn test_whole_word_search_matches_complete_tokens_only() {
+        use super::{build_whole_word_fts_query, search_keyword_with_conn_limit};
+        let conn = setup_search_db();
+        insert_spell(&conn, 1, "Fire Shield", "Wreathes the caster in fire");
+        insert_spell(&conn, 2, "Fireball", "A fireball bursts at the target");
+        insert_spell(
+            &conn,
+            3,
+            "Flame Arrow",
+            "Arrows of fire strike the shield bearer",
+        );
+
+        assert_eq!(
+            build_whole_word_fts_query("fire shield"),
+            "\"fire\" \"shield\""
+        );
+
+        let whole_word_ids = |query: &str| -> Vec<i64> {
+            let mut ids: Vec<i64> =
+                search_keyword_with_conn_limit(&conn, query, None, 100, 0, true)
+                    .unwrap()
+                    .into_iter()
+                    .map(|s| s.id)
+                    .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(whole_word_ids("fire"), vec![1, 3]);
+        // Each word must appear, but not as one adjacent phrase.
+        assert_eq!(whole_word_ids("shield fire"), vec![1, 3]);
+        assert!(search_ids(&conn, "shield fire").is_empty());
+    }
+
     /// Verification plan test 2: a single-token query must match spells whose
     /// name/description contains that token and NOT match unrelated spells.
     #[test]
@@ -1017,15 +2039,7 @@ mod tests {
 
         let filters = SearchFilters {
             schools: Some(vec!["Evocation".to_string()]),
-            spheres: None,
-            level_min: None,
-            level_max: None,
-            class_list: None,
-            source: None,
-            components: None,
-            tags: None,
-            is_quest_spell: None,
-            is_cantrip: None,
+            ..Default::default()
         };
 
         let results = search_keyword_with_conn(&conn, "fire", Some(filters)).unwrap();
@@ -1041,6 +2055,386 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_search_matches_nfc_content_with_decomposed_query() {
+        let conn = setup_search_db();
+        insert_spell(&conn, 1, "Fianc\u{e9}", "A spell named for a betrothed");
+
+        assert_eq!(search_ids(&conn, "Fiance\u{301}"), vec![1]);
+        assert_eq!(search_ids(&conn, "Fianc\u{e9}"), vec![1]);
+    }
+
+    #[test]
+    fn test_get_tags_with_usage_counts_overlapping_tags() {
+        use super::get_tags_with_usage_with_conn;
+
+        let conn = setup_search_db();
+        conn.execute_batch(
+            r#"INSERT INTO spell (id, name, tags) VALUES (1, 'A', '["Fire","Underdark"]');
+               INSERT INTO spell (id, name, tags) VALUES (2, 'B', 'Fire, AoE');
+               INSERT INTO spell (id, name, tags) VALUES (3, 'C', '["AoE","Fire"]');
+               INSERT INTO spell (id, name, tags) VALUES (4, 'D', NULL);"#,
+        )
+        .unwrap();
+
+        let usage: Vec<(String, i64)> = get_tags_with_usage_with_conn(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|u| (u.tag, u.count))
+            .collect();
+        assert_eq!(
+            usage,
+            vec![
+                ("Fire".to_string(), 3),
+                ("AoE".to_string(), 2),
+                ("Underdark".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_random_spell_respects_filters() {
+        use super::random_spell_id_with_conn;
+        use crate::models::SearchFilters;
+
+        let conn = setup_search_db();
+        conn.execute_batch(
+            "INSERT INTO spell (id, name, level) VALUES (1, 'Sleep', 1), (2, 'Light', 1),
+                 (3, 'Fireball', 3), (4, 'Lightning Bolt', 3), (5, 'Wish', 9);",
+        )
+        .unwrap();
+        let level = |min, max| {
+            Some(SearchFilters {
+                level_min: Some(min),
+                level_max: Some(max),
+                ..Default::default()
+            })
+        };
+
+        for _ in 0..20 {
+            let id = random_spell_id_with_conn(&conn, level(3, 3))
+                .unwrap()
+                .expect("a level 3 spell");
+            assert!([3, 4].contains(&id), "got {id}");
+        }
+        assert!(random_spell_id_with_conn(&conn, None).unwrap().is_some());
+        assert_eq!(random_spell_id_with_conn(&conn, level(5, 7)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_get_class_facet_counts_tallies_both_storage_formats() {
+        use super::get_class_facet_counts_with_conn;
+
+        let conn = setup_search_db();
+        conn.execute_batch(
+            r#"INSERT INTO spell (id, name, class_list) VALUES (1, 'A', '["Wizard","Cleric"]');
+               INSERT INTO spell (id, name, class_list) VALUES (2, 'B', 'Wizard, Druid');
+               INSERT INTO spell (id, name, class_list) VALUES (3, 'C', '["Wizard"]');
+               INSERT INTO spell (id, name, class_list) VALUES (4, 'D', 'Cleric');
+               INSERT INTO spell (id, name, class_list) VALUES (5, 'E', NULL);"#,
+        )
+        .unwrap();
+
+        let counts: Vec<(String, i64)> = get_class_facet_counts_with_conn(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.class, c.count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("Wizard".to_string(), 3),
+                ("Cleric".to_string(), 2),
+                ("Druid".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_spells_without_embeddings_lists_only_missing_vectors() {
+        use super::list_spells_without_embeddings_with_conn;
+        let conn = setup_search_db();
+        conn.execute_batch("CREATE TABLE spell_vec (rowid INTEGER PRIMARY KEY, v BLOB);")
+            .unwrap();
+        insert_spell(&conn, 1, "Magic Missile", "Darts of force.");
+        insert_spell(&conn, 2, "Fireball", "A burst of flame.");
+        conn.execute(
+            "INSERT INTO spell_vec (rowid, v) VALUES (1, '[0.5,0.5]')",
+            [],
+        )
+        .unwrap();
+
+        let missing = list_spells_without_embeddings_with_conn(&conn).unwrap();
+        assert_eq!(missing.iter().map(|s| s.id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(missing[0].name, "Fireball");
+    }
+
+    #[test]
+    fn test_semantic_search_reports_keyword_fallback_under_blob_mode() {
+        use super::search_semantic_with_conn;
+        use crate::db::VecMode;
+        let conn = setup_search_db();
+        insert_spell(&conn, 1, "Magic Missile", "Darts of force.");
+        insert_spell(&conn, 2, "Fireball", "A burst of flame.");
+
+        let results =
+            search_semantic_with_conn(&conn, VecMode::BlobFallback, "flame", None).unwrap();
+        assert_eq!(results.mode, VecMode::BlobFallback);
+        assert!(results.degraded);
+        assert_eq!(
+            results.spells.iter().map(|s| s.id).collect::<Vec<_>>(),
+            vec![2]
+        );
+
+        let err = search_semantic_with_conn(&conn, VecMode::Vec0, "flame", None)
+            .expect_err("vec0 search needs an embedding");
+        assert!(matches!(err, crate::error::AppError::Sidecar(_)));
+    }
+
+    #[test]
+    fn test_embedding_template_renders_fields_and_invalidates_hashes() {
+        use super::{
+            pending_embeddings_with_conn, render_embedding_text, store_embeddings_with_conn,
+            EmbeddingFields, EMBEDDING_TEMPLATE_SETTING,
+        };
+        let fields = EmbeddingFields {
+            name: "Fireball".into(),
+            school: "Evocation".into(),
+            level: 3,
+            class_list: r#"["Wizard","Sorcerer"]"#.into(),
+            tags: "fire, area".into(),
+            description: "A burst of flame.".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            render_embedding_text("{name}\n{school}\n{tags}\n{description}", &fields),
+            "Fireball\nEvocation\narea, fire\nA burst of flame."
+        );
+        assert_eq!(
+            render_embedding_text("{name} (L{level}, {class_list}) {unknown}", &fields),
+            "Fireball (L3, Sorcerer, Wizard) {unknown}"
+        );
+
+        let mut conn = setup_search_db();
+        conn.execute_batch(
+            "ALTER TABLE spell ADD COLUMN embedding_hash TEXT;
+             CREATE TABLE spell_vec (rowid INTEGER PRIMARY KEY, v BLOB);",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!(
+            "../../../../../db/migrations/0026_settings.sql"
+        ))
+        .unwrap();
+        insert_spell(&conn, 1, "Fireball", "A burst of flame.");
+        conn.execute("UPDATE spell SET school = 'Evocation' WHERE id = 1", [])
+            .unwrap();
+        let (pending, _) = pending_embeddings_with_conn(&conn).unwrap();
+        assert_eq!(pending[0].text, "Fireball\nA burst of flame.");
+        store_embeddings_with_conn(&mut conn, &pending, &[vec![0.5; 4]]).unwrap();
+
+        crate::commands::settings::set_setting_with_conn(
+            &conn,
+            EMBEDDING_TEMPLATE_SETTING,
+            &serde_json::json!("{name} [{school}]\n{description}"),
+        )
+        .unwrap();
+        let (pending, skipped) = pending_embeddings_with_conn(&conn).unwrap();
+        assert_eq!(skipped, 0, "a new template invalidates stored hashes");
+        assert_eq!(pending[0].text, "Fireball [Evocation]\nA burst of flame.");
+    }
+
+    #[test]
+    fn test_reembed_resumes_after_cancel_and_skips_embedded_spells() {
+        use super::{pending_embeddings_with_conn, store_embeddings_with_conn, ReembedState};
+        let mut conn = setup_search_db();
+        conn.execute_batch(
+            "ALTER TABLE spell ADD COLUMN embedding_hash TEXT;
+             CREATE TABLE spell_vec (rowid INTEGER PRIMARY KEY, v BLOB);",
+        )
+        .unwrap();
+        insert_spell(&conn, 1, "Magic Missile", "Darts of force.");
+        insert_spell(&conn, 2, "Fireball", "A burst of flame.");
+        insert_spell(&conn, 3, "Sleep", "Creatures fall asleep.");
+
+        // First run embeds one batch, then is cancelled before the next.
+        let state = ReembedState::default();
+        let (pending, skipped) = pending_embeddings_with_conn(&conn).unwrap();
+        assert_eq!(pending.len(), 3);
+        assert_eq!(skipped, 0);
+        store_embeddings_with_conn(&mut conn, &pending[..1], &[vec![0.5; 4]]).unwrap();
+        state.request_cancel();
+        assert!(state.take_cancel());
+        assert!(!state.take_cancel(), "taking the cancel clears it");
+
+        // Re-run: the embedded spell is skipped, only the remainder is pending.
+        let (pending, skipped) = pending_embeddings_with_conn(&conn).unwrap();
+        assert_eq!(skipped, 1);
+        assert_eq!(pending.iter().map(|p| p.id).collect::<Vec<_>>(), vec![2, 3]);
+        let vectors = vec![vec![0.25; 4]; pending.len()];
+        store_embeddings_with_conn(&mut conn, &pending, &vectors).unwrap();
+        let (pending, skipped) = pending_embeddings_with_conn(&conn).unwrap();
+        assert!(pending.is_empty());
+        assert_eq!(skipped, 3);
+
+        // Editing a description invalidates that spell's stored hash.
+        conn.execute(
+            "UPDATE spell SET description = 'A bigger burst of flame.' WHERE id = 2",
+            [],
+        )
+        .unwrap();
+        let (pending, _) = pending_embeddings_with_conn(&conn).unwrap();
+        assert_eq!(pending.iter().map(|p| p.id).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(pending[0].text, "Fireball\nA bigger burst of flame.");
+
+        let vec_rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM spell_vec", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(vec_rows, 3);
+    }
+
+    #[test]
+    fn test_reembed_all_spells_holds_guard_respects_vec_mode_and_resumes() {
+        use super::{reembed_all_spells_with_state, ReembedState, REEMBED_BATCH_SIZE};
+        use crate::commands::vault::{VaultMaintenanceState, VaultTestEnvGuard};
+        use crate::db::VecMode;
+        use crate::error::AppError;
+        use std::sync::Arc;
+
+        let _vault = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let pool = Arc::new(crate::db::pool::init_db(None, false).expect("init db pool"));
+        let spell_count = REEMBED_BATCH_SIZE + 8;
+        {
+            let conn = pool.get().unwrap();
+            for n in 0..spell_count {
+                conn.execute(
+                    "INSERT INTO spell (name, level, description) VALUES (?, 1, 'Embed me')",
+                    [format!("Embedded Spell {n}")],
+                )
+                .unwrap();
+            }
+        }
+        let maintenance_state = VaultMaintenanceState::default();
+        let reembed = ReembedState::default();
+        let mut batches = Vec::new();
+        // Stands in for the sidecar; asks for a cancel while the first batch is in flight.
+        let mut embed = |texts: Vec<String>| {
+            batches.push(texts.len());
+            reembed.request_cancel();
+            let vectors = vec![vec![0.1_f32; 384]; texts.len()];
+            async move { Ok::<_, AppError>(vectors) }
+        };
+
+        let degraded = tauri::async_runtime::block_on(reembed_all_spells_with_state(
+            &pool,
+            VecMode::BlobFallback,
+            &maintenance_state,
+            &reembed,
+            &mut embed,
+        ));
+        assert!(matches!(degraded, Err(AppError::Search(_))));
+
+        let import_guard = maintenance_state.start_import().expect("start import");
+        let busy = tauri::async_runtime::block_on(reembed_all_spells_with_state(
+            &pool,
+            VecMode::Vec0,
+            &maintenance_state,
+            &reembed,
+            &mut embed,
+        ));
+        assert!(matches!(busy, Err(AppError::Validation(_))));
+        drop(import_guard);
+
+        let first = tauri::async_runtime::block_on(reembed_all_spells_with_state(
+            &pool,
+            VecMode::Vec0,
+            &maintenance_state,
+            &reembed,
+            &mut embed,
+        ))
+        .expect("first run");
+        assert!(first.cancelled);
+        assert_eq!(first.embedded, REEMBED_BATCH_SIZE);
+        assert_eq!(first.remaining, 8);
+
+        let second = tauri::async_runtime::block_on(reembed_all_spells_with_state(
+            &pool,
+            VecMode::Vec0,
+            &maintenance_state,
+            &reembed,
+            &mut embed,
+        ))
+        .expect("resumed run");
+        assert!(!second.cancelled);
+        assert_eq!(second.embedded, 8);
+        assert_eq!(second.skipped, REEMBED_BATCH_SIZE);
+        assert_eq!(second.remaining, 0);
+        assert_eq!(batches, vec![REEMBED_BATCH_SIZE, 8]);
+        maintenance_state
+            .start_search_rebuild()
+            .expect("guard released after the run");
+    }
+
+    #[test]
+    fn test_material_filters_and_cost_ceiling() {
+        use super::search_result_ids_capped;
+        use crate::models::SearchFilters;
+        let conn = setup_search_db();
+        conn.execute_batch(
+            r#"INSERT INTO spell (id, name, material_components, canonical_data)
+                   VALUES (1, 'No Materials', '', '{}');
+               INSERT INTO spell (id, name, material_components, canonical_data)
+                   VALUES (2, 'Cheap', 'a feather',
+                           '{"material_components":[{"name":"feather","gpValue":1}]}');
+               INSERT INTO spell (id, name, material_components, canonical_data)
+                   VALUES (3, 'Expensive', 'ruby dust',
+                           '{"material_components":[{"name":"ruby dust","gpValue":100},{"name":"pearl","gpValue":400}]}');
+               INSERT INTO spell (id, name, material_components, canonical_data)
+                   VALUES (4, 'Unknown Cost', 'bat guano', NULL);"#,
+        )
+        .unwrap();
+        let filters = |has_material, max_material_cost, include_unknown_material_cost| {
+            Some(SearchFilters {
+                has_material,
+                max_material_cost,
+                include_unknown_material_cost,
+                ..Default::default()
+            })
+        };
+        let ids = |f| {
+            let mut ids = search_result_ids_capped(&conn, "", f, "exporting").unwrap();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(ids(filters(Some(true), None, None)), vec![2, 3, 4]);
+        assert_eq!(ids(filters(Some(false), None, None)), vec![1]);
+        assert_eq!(ids(filters(None, Some(100.0), None)), vec![1, 2]);
+        assert_eq!(ids(filters(None, Some(100.0), Some(true))), vec![1, 2, 4]);
+        assert_eq!(ids(filters(None, Some(500.0), None)), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_get_spell_count_matches_rows_and_school_filter() {
+        use super::get_spell_count_with_conn;
+        use crate::models::SearchFilters;
+
+        let conn = setup_search_db();
+        conn.execute_batch(
+            "INSERT INTO spell (id, name, school) VALUES (1, 'Fireball', 'Evocation');
+             INSERT INTO spell (id, name, school) VALUES (2, 'Lightning Bolt', 'Evocation');
+             INSERT INTO spell (id, name, school) VALUES (3, 'Sleep', 'Enchantment');",
+        )
+        .unwrap();
+
+        assert_eq!(get_spell_count_with_conn(&conn, None).unwrap(), 3);
+
+        let filters = SearchFilters {
+            schools: Some(vec!["Evocation".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(get_spell_count_with_conn(&conn, Some(filters)).unwrap(), 2);
+    }
+
     #[test]
     fn test_search_malicious_fts_payload_does_not_mutate_spell_table() {
         let conn = setup_search_db();
@@ -1100,16 +2494,8 @@ mod tests {
         .unwrap();
 
         let filters = SearchFilters {
-            schools: None,
-            spheres: None,
-            level_min: None,
-            level_max: None,
-            class_list: None,
             source: Some("Guild_Archive%Vol\\1".to_string()),
-            components: None,
-            tags: None,
-            is_quest_spell: None,
-            is_cantrip: None,
+            ..Default::default()
         };
 
         let ids: Vec<i64> = search_keyword_with_conn(&conn, "", Some(filters))
@@ -1175,16 +2561,8 @@ mod tests {
         .unwrap();
 
         let filters = SearchFilters {
-            schools: None,
-            spheres: None,
-            level_min: None,
-            level_max: None,
-            class_list: None,
             source: Some("Guild_Archive%Vol\\1".to_string()),
-            components: None,
-            tags: None,
-            is_quest_spell: None,
-            is_cantrip: None,
+            ..Default::default()
         };
 
         let ids: Vec<i64> = search_keyword_with_conn(&conn, "fire AND rune", Some(filters))
@@ -1242,14 +2620,7 @@ mod tests {
         let filters = SearchFilters {
             schools: Some(vec![String::new(), "Evocation".to_string()]),
             spheres: Some(vec![String::new(), "Combat".to_string()]),
-            level_min: None,
-            level_max: None,
-            class_list: None,
-            source: None,
-            components: None,
-            tags: None,
-            is_quest_spell: None,
-            is_cantrip: None,
+            ..Default::default()
         };
 
         let ids: Vec<i64> = search_keyword_with_conn(&conn, "fire", Some(filters))
@@ -1330,6 +2701,140 @@ mod tests {
             "text-query results must match the direct bm25-ranked ordering for the same MATCH term"
         );
     }
+
+    /// Facet collection must yield the same entries whether list columns are stored as
+    /// legacy comma-separated text or as normalized JSON arrays.
+    #[test]
+    fn test_collect_facet_entries_handles_json_arrays_and_comma_lists() {
+        use super::collect_facet_entries;
+
+        let legacy = setup_search_db();
+        legacy
+            .execute_batch(
+                "INSERT INTO spell (id, name, class_list, tags) VALUES (1, 'A', 'Wizard, Bard', 'Fire, AoE');
+                 INSERT INTO spell (id, name, class_list, tags) VALUES (2, 'B', 'Cleric', 'Fire');",
+            )
+            .unwrap();
+        let normalized = setup_search_db();
+        normalized
+            .execute_batch(
+                r#"INSERT INTO spell (id, name, class_list, tags) VALUES (1, 'A', '["Bard","Wizard"]', '["AoE","Fire"]');
+                   INSERT INTO spell (id, name, class_list, tags) VALUES (2, 'B', '["Cleric"]', '["Fire"]');"#,
+            )
+            .unwrap();
+
+        for sql in ["SELECT class_list FROM spell", "SELECT tags FROM spell"] {
+            let legacy_entries = collect_facet_entries(&legacy, sql).unwrap();
+            let normalized_entries = collect_facet_entries(&normalized, sql).unwrap();
+            assert_eq!(
+                legacy_entries, normalized_entries,
+                "facet entries for `{sql}` must not depend on list storage format"
+            );
+        }
+        assert_eq!(
+            collect_facet_entries(&normalized, "SELECT class_list FROM spell").unwrap(),
+            vec!["Bard", "Cleric", "Wizard"]
+        );
+    }
+
+    #[test]
+    fn test_collect_facet_entries_folds_diacritics_when_sorting() {
+        use super::collect_facet_entries;
+
+        let conn = setup_search_db();
+        conn.execute_batch(
+            "INSERT INTO spell (id, name, school) VALUES (1, 'A', 'Zzz');
+             INSERT INTO spell (id, name, school) VALUES (2, 'B', 'Évocation');
+             INSERT INTO spell (id, name, school) VALUES (3, 'C', 'Evocation');
+             INSERT INTO spell (id, name, school) VALUES (4, 'D', 'Abjuration');
+             INSERT INTO spell (id, name, school) VALUES (5, 'E', 'Illusion');",
+        )
+        .unwrap();
+
+        assert_eq!(
+            collect_facet_entries(&conn, "SELECT school FROM spell").unwrap(),
+            vec!["Abjuration", "Evocation", "Évocation", "Illusion", "Zzz"]
+        );
+    }
+
+    /// Range sort puts touch before numeric distances and perception ranges after
+    /// them; unparseable ranges sort last.
+    #[test]
+    fn test_sort_by_range_orders_touch_then_distance_then_sight() {
+        use super::{apply_sort_by, search_keyword_with_conn};
+
+        let conn = setup_search_db();
+        conn.execute_batch(
+            "INSERT INTO spell (id, name, range) VALUES (1, 'Alpha', 'Sight');
+             INSERT INTO spell (id, name, range) VALUES (2, 'Bravo', 'xyz gibberish 123');
+             INSERT INTO spell (id, name, range) VALUES (3, 'Charlie', '60 ft.');
+             INSERT INTO spell (id, name, range) VALUES (4, 'Delta', 'Touch');
+             INSERT INTO spell (id, name, range) VALUES (5, 'Echo', '10 yards');",
+        )
+        .unwrap();
+
+        let mut spells = search_keyword_with_conn(&conn, "", None).unwrap();
+        apply_sort_by(&conn, &mut spells, Some("range")).unwrap();
+        let names: Vec<&str> = spells.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Delta", "Echo", "Charlie", "Alpha", "Bravo"]);
+
+        assert!(apply_sort_by(&conn, &mut spells, Some("bogus")).is_err());
+    }
+
+    #[test]
+    fn test_prepare_search_rebuild_repairs_restored_db_and_lists_missing_vectors() {
+        use super::prepare_search_rebuild_with_conn;
+
+        let mut conn = setup_search_db();
+        conn.execute_batch(
+            "ALTER TABLE spell ADD COLUMN embedding_hash TEXT;
+             CREATE TABLE spell_vec (rowid INTEGER PRIMARY KEY, v BLOB);",
+        )
+        .unwrap();
+        insert_spell(&conn, 1, "Fireball", "A blazing orb of fire");
+        insert_spell(&conn, 2, "Sleep", "Creatures fall into a slumber");
+        // A restored database can arrive with an empty FTS index.
+        conn.execute("DELETE FROM spell_fts_docsize", []).unwrap();
+
+        let (fts, pending) = prepare_search_rebuild_with_conn(&mut conn, true).unwrap();
+        assert!(fts.consistent);
+        assert_eq!(fts.fts_count, 2);
+        assert_eq!(fts_rowids(&conn, "slumber"), vec![2]);
+        assert_eq!(pending.iter().map(|p| p.id).collect::<Vec<_>>(), vec![1, 2]);
+
+        let (_, pending) = prepare_search_rebuild_with_conn(&mut conn, false).unwrap();
+        assert!(pending.is_empty(), "degraded vec mode skips re-embedding");
+    }
+
+    #[test]
+    fn test_fts_consistency_detects_missing_row_and_rebuild_restores_it() {
+        use super::{check_fts_consistency_with_conn, rebuild_spell_fts_with_conn};
+
+        let mut conn = setup_search_db();
+        conn.execute_batch(
+            "INSERT INTO spell (id, name, description, canonical_data) VALUES \
+             (1, 'Fireball', 'A blazing orb of fire', '{}'), \
+             (2, 'Sleep', 'Creatures fall into a slumber', '{}');",
+        )
+        .unwrap();
+
+        let before = check_fts_consistency_with_conn(&conn).unwrap();
+        assert!(before.consistent);
+        assert_eq!(before.spell_count, 2);
+
+        // Simulate index drift by dropping one FTS document directly.
+        conn.execute("DELETE FROM spell_fts_docsize WHERE id = 2", [])
+            .unwrap();
+        let drifted = check_fts_consistency_with_conn(&conn).unwrap();
+        assert!(!drifted.consistent);
+        assert_eq!(drifted.spell_count, 2);
+        assert_eq!(drifted.fts_count, 1);
+
+        let rebuilt = rebuild_spell_fts_with_conn(&mut conn).unwrap();
+        assert!(rebuilt.consistent);
+        assert_eq!(rebuilt.fts_count, 2);
+        assert_eq!(fts_rowids(&conn, "slumber"), vec![2]);
+    }
 }
 
 #[tauri::command]
@@ -0,0 +1,272 @@
+use crate::commands::export::resolve_output_dir;
+use crate::db::Pool;
+use crate::error::AppError;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::State;
+
+/// Version of the settings file written by `export_settings`.
+const SETTINGS_FILE_VERSION: i64 = 1;
+
+/// Settings file envelope. Keys are snake_case like the bundle export.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SettingsFile {
+    format_version: i64,
+    #[serde(default)]
+    exported_at: Option<String>,
+    settings: BTreeMap<String, Value>,
+}
+
+fn validate_setting_key(key: &str) -> Result<&str, AppError> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(AppError::Validation("Setting key cannot be empty".into()));
+    }
+    Ok(key)
+}
+
+fn parse_setting_value(key: &str, raw: &str) -> Result<Value, AppError> {
+    serde_json::from_str(raw)
+        .map_err(|e| AppError::Validation(format!("Setting '{key}' is not valid JSON: {e}")))
+}
+
+pub(crate) fn get_setting_with_conn(
+    conn: &Connection,
+    key: &str,
+) -> Result<Option<Value>, AppError> {
+    let key = validate_setting_key(key)?;
+    let raw: Option<String> = conn
+        .query_row("SELECT value FROM settings WHERE key = ?", [key], |row| {
+            row.get(0)
+        })
+        .optional()?;
+    raw.map(|raw| parse_setting_value(key, &raw)).transpose()
+}
+
+pub(crate) fn set_setting_with_conn(
+    conn: &Connection,
+    key: &str,
+    value: &Value,
+) -> Result<(), AppError> {
+    let key = validate_setting_key(key)?;
+    let raw = serde_json::to_string(value).map_err(|e| AppError::Unknown(e.to_string()))?;
+    conn.execute(
+        "INSERT INTO settings (key, value) VALUES (?, ?)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![key, raw],
+    )?;
+    Ok(())
+}
+
+pub(crate) fn get_all_settings_with_conn(
+    conn: &Connection,
+) -> Result<HashMap<String, Value>, AppError> {
+    let mut stmt = conn.prepare("SELECT key, value FROM settings")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    let mut settings = HashMap::new();
+    for row in rows {
+        let (key, raw) = row?;
+        let value = parse_setting_value(&key, &raw)?;
+        settings.insert(key, value);
+    }
+    Ok(settings)
+}
+
+/// Writes every setting to `settings_<timestamp>.json` in `dir` and returns its path.
+pub(crate) fn export_settings_with_conn(
+    conn: &Connection,
+    dir: &Path,
+) -> Result<PathBuf, AppError> {
+    let file = SettingsFile {
+        format_version: SETTINGS_FILE_VERSION,
+        exported_at: Some(Utc::now().to_rfc3339()),
+        settings: get_all_settings_with_conn(conn)?.into_iter().collect(),
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| AppError::Export(e.to_string()))?;
+    let path = dir.join(format!(
+        "settings_{}.json",
+        Utc::now().format("%Y%m%dT%H%M%S%3f")
+    ));
+    fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Merges a settings file into the `settings` table in one transaction: keys in the
+/// file overwrite stored values, all other stored keys are kept. Keys this version does
+/// not know about are imported as-is. Returns the number of keys written.
+pub(crate) fn import_settings_with_conn(
+    conn: &mut Connection,
+    path: &Path,
+) -> Result<usize, AppError> {
+    let raw = fs::read_to_string(path)?;
+    let file: SettingsFile = serde_json::from_str(&raw)
+        .map_err(|e| AppError::Import(format!("Invalid settings file: {e}")))?;
+    if file.format_version > SETTINGS_FILE_VERSION {
+        return Err(AppError::Import(format!(
+            "Settings file version {} is newer than supported version {}",
+            file.format_version, SETTINGS_FILE_VERSION
+        )));
+    }
+    let tx = conn.transaction()?;
+    for (key, value) in &file.settings {
+        set_setting_with_conn(&tx, key, value)?;
+    }
+    tx.commit()?;
+    Ok(file.settings.len())
+}
+
+/// Returns the stored JSON value for `key`, or `None` when it has never been set.
+#[tauri::command]
+pub async fn get_setting(
+    state: State<'_, Arc<Pool>>,
+    key: String,
+) -> Result<Option<Value>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_setting_with_conn(&conn, &key)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Stores `value` (any JSON) under `key`, replacing a previous value.
+#[tauri::command]
+pub async fn set_setting(
+    state: State<'_, Arc<Pool>>,
+    key: String,
+    value: Value,
+) -> Result<(), AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        set_setting_with_conn(&conn, &key, &value)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn get_all_settings(
+    state: State<'_, Arc<Pool>>,
+) -> Result<HashMap<String, Value>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_all_settings_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Exports all settings to a JSON file under `output_dir` (default `exports/`).
+#[tauri::command]
+pub async fn export_settings(
+    app: tauri::AppHandle,
+    state: State<'_, Arc<Pool>>,
+    output_dir: Option<String>,
+) -> Result<String, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let dir = resolve_output_dir(&app, output_dir.as_deref(), "exports")?;
+        let path = export_settings_with_conn(&conn, &dir)?;
+        Ok(path.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Merges settings from a file written by `export_settings`; see
+/// [`import_settings_with_conn`].
+#[tauri::command]
+pub async fn import_settings(state: State<'_, Arc<Pool>>, path: String) -> Result<usize, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get()?;
+        import_settings_with_conn(&mut conn, Path::new(&path))
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn setup_settings_db() -> Connection {
+        let conn = Connection::open_in_memory().expect("open in-memory sqlite");
+        conn.execute_batch(include_str!(
+            "../../../../../db/migrations/0026_settings.sql"
+        ))
+        .expect("create settings table");
+        conn
+    }
+
+    #[test]
+    fn test_settings_round_trip_structured_json() {
+        let conn = setup_settings_db();
+        assert_eq!(get_setting_with_conn(&conn, "conversion").unwrap(), None);
+
+        let config = json!({"unit": "metric", "round_to": 5, "aliases": ["ft", "yd"]});
+        set_setting_with_conn(&conn, "conversion", &config).unwrap();
+        set_setting_with_conn(&conn, "page_size", &json!(50)).unwrap();
+        set_setting_with_conn(&conn, "page_size", &json!(25)).unwrap();
+
+        assert_eq!(
+            get_setting_with_conn(&conn, "conversion").unwrap(),
+            Some(config.clone())
+        );
+        let all = get_all_settings_with_conn(&conn).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all["conversion"], config);
+        assert_eq!(all["page_size"], json!(25));
+        assert!(matches!(
+            set_setting_with_conn(&conn, "  ", &json!(true)),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_settings_export_import_round_trip_merges() {
+        let mut conn = setup_settings_db();
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        set_setting_with_conn(&conn, "conversion", &json!({"unit": "metric"})).unwrap();
+        set_setting_with_conn(&conn, "future.unknown_key", &json!([1, 2, 3])).unwrap();
+
+        let path = export_settings_with_conn(&conn, temp_dir.path()).expect("export settings");
+        let exported: Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).expect("valid json");
+        assert_eq!(exported["format_version"], json!(SETTINGS_FILE_VERSION));
+
+        conn.execute("DELETE FROM settings", []).unwrap();
+        set_setting_with_conn(&conn, "conversion", &json!({"unit": "imperial"})).unwrap();
+        set_setting_with_conn(&conn, "local_only", &json!(true)).unwrap();
+
+        assert_eq!(import_settings_with_conn(&mut conn, &path).unwrap(), 2);
+        let all = get_all_settings_with_conn(&conn).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all["conversion"], json!({"unit": "metric"}));
+        assert_eq!(all["future.unknown_key"], json!([1, 2, 3]));
+        assert_eq!(
+            all["local_only"],
+            json!(true),
+            "keys absent from the file are kept"
+        );
+
+        let bad = temp_dir.path().join("bad.json");
+        fs::write(&bad, "{\"settings\": 5}").unwrap();
+        assert!(matches!(
+            import_settings_with_conn(&mut conn, &bad),
+            Err(AppError::Import(_))
+        ));
+    }
+}
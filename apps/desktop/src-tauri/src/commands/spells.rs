@@ -1,10 +1,18 @@
+use crate::commands::search::{apply_sort_by, search_result_ids_capped};
 use crate::commands::vault::export_spell_to_vault_by_hash;
-use crate::db::Pool;
+use crate::db::{cascade_spell_content_hash_refs, Pool};
 use crate::error::AppError;
-use crate::models::canonical_spell::CanonicalSpell;
+use crate::models::canonical_spell::{
+    match_schema_case, normalize_string, parse_list_column, schema_enum_for_field, CanonicalSpell,
+    CastingTimeUnit, NormalizationMode, CURRENT_SCHEMA_VERSION,
+};
 use crate::models::{
-    MaterialComponentSpec, SpellArtifact, SpellComponents, SpellCreate, SpellDetail, SpellSummary,
-    SpellUpdate,
+    AreaKind, BatchResult, ContentHashRepairResult, DataQualityReport, DuplicateSpellGroup,
+    DurationKind, FieldValidation, LevelCount, MagicResistanceKind, MaterialComponentSpec,
+    RangeKind, RecentChangesPage, ResolvedDamage, ResolvedSpell, ResolvedValue, SchemaVersionCount,
+    SearchFilters, SourceUsage, SpellArtifact, SpellChange, SpellComponents, SpellCreate,
+    SpellDetail, SpellReviewItem, SpellScalar, SpellSummary, SpellTemplate, SpellUpdate,
+    SpellValidationResult, TagNormalizationSummary,
 };
 use crate::utils::migration_manager;
 use crate::utils::spell_parser::SpellParser;
@@ -12,7 +20,8 @@ use chrono::Utc;
 use rusqlite::params;
 use rusqlite::{Connection, OptionalExtension};
 use serde_json::Value;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
 use tauri::State;
 
 fn validate_spell_fields(name: &str, level: i64, description: &str) -> Result<(), AppError> {
@@ -75,87 +84,175 @@ pub(crate) fn validate_epic_and_quest_spells(
     Ok(())
 }
 
+/// Normalizes a `class_list`/`tags` column to its canonical storage form: a sorted,
+/// deduplicated JSON-array string. Accepts JSON arrays or comma-separated text.
+/// Returns `None` when the input has no non-empty entries.
+pub(crate) fn normalize_list_column(value: &Option<String>) -> Option<String> {
+    let items = parse_list_column(value.as_deref()?);
+    if items.is_empty() {
+        return None;
+    }
+    serde_json::to_string(&items).ok()
+}
+
+const SPELL_DETAIL_COLUMNS: &str = "id, name, school, sphere, class_list, level, range, components,
+    material_components, casting_time, duration, area, saving_throw, reversible,
+    description, tags, source, edition, author, license, is_quest_spell, is_cantrip,
+    damage, magic_resistance, schema_version, canonical_data, content_hash";
+
+/// Maps a row selected with [`SPELL_DETAIL_COLUMNS`] to a `SpellDetail` (artifacts not loaded).
+fn spell_detail_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SpellDetail> {
+    let canonical_data_str: Option<String> = row.get(25)?;
+
+    // Try to parse canonical_data to populate structured specs
+    let mut range_spec = None;
+    let mut components_spec = None;
+    let mut material_components_spec = None;
+    let mut casting_time_spec = None;
+    let mut duration_spec = None;
+    let mut area_spec = None;
+    let mut saving_throw_spec = None;
+    let mut damage_spec = None;
+    let mut magic_resistance_spec = None;
+    let mut material_has_cost = false;
+
+    if let Some(json_str) = &canonical_data_str {
+        if let Ok(canon) = serde_json::from_str::<CanonicalSpell>(json_str) {
+            material_has_cost = canon.material_has_cost();
+            range_spec = canon.range;
+            components_spec = canon.components;
+            material_components_spec = canon.material_components;
+            casting_time_spec = canon.casting_time;
+            duration_spec = canon.duration;
+            area_spec = canon.area;
+            saving_throw_spec = canon.saving_throw;
+            damage_spec = canon.damage;
+            magic_resistance_spec = canon.magic_resistance;
+        }
+    }
+
+    Ok(SpellDetail {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        school: row.get(2)?,
+        sphere: row.get(3)?,
+        class_list: row.get(4)?,
+        level: row.get(5)?,
+        range: row.get(6)?,
+        components: row.get(7)?,
+        material_components: row.get(8)?,
+        casting_time: row.get(9)?,
+        duration: row.get(10)?,
+        area: row.get(11)?,
+        saving_throw: row.get(12)?,
+        reversible: row.get(13)?,
+        description: row.get(14)?,
+        tags: row.get(15)?,
+        source: row.get(16)?,
+        edition: row.get(17)?,
+        author: row.get(18)?,
+        license: row.get(19)?,
+        is_quest_spell: row.get(20)?,
+        is_cantrip: row.get(21)?,
+        damage: row.get(22)?,
+        magic_resistance: row.get(23)?,
+        schema_version: row.get(24)?,
+        artifacts: None,
+        canonical_data: canonical_data_str,
+        content_hash: row.get(26)?,
+        reverse_name: None,
+        created_at: None,
+        updated_at: None,
+        material_has_cost,
+        range_spec,
+        components_spec,
+        material_components_spec,
+        casting_time_spec,
+        duration_spec,
+        area_spec,
+        saving_throw_spec,
+        damage_spec,
+        magic_resistance_spec,
+    })
+}
+
+/// Writes `reverse_name` for spell `id`: `None` leaves it alone and a blank name clears
+/// it. A no-op on schemas without the column (before migration 0029).
+pub(crate) fn set_spell_reverse_name_with_conn(
+    conn: &Connection,
+    id: i64,
+    reverse_name: Option<&str>,
+) -> Result<(), AppError> {
+    let Some(reverse_name) = reverse_name else {
+        return Ok(());
+    };
+    if !crate::db::table_has_column(conn, "spell", "reverse_name") {
+        return Ok(());
+    }
+    let reverse_name = Some(reverse_name.trim()).filter(|name| !name.is_empty());
+    conn.execute(
+        "UPDATE spell SET reverse_name = ? WHERE id = ?",
+        params![reverse_name, id],
+    )?;
+    Ok(())
+}
+
+/// The timestamp and reverse-name columns to select after [`SPELL_DETAIL_COLUMNS`], with
+/// `NULL` standing in for any the schema lacks. One `pragma_table_info` read per query
+/// rather than one probe per column.
+fn spell_optional_columns(conn: &Connection) -> Result<String, AppError> {
+    let mut stmt = conn.prepare("SELECT name FROM pragma_table_info('spell')")?;
+    let present = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<HashSet<_>, _>>()?;
+    Ok(["created_at", "updated_at", "reverse_name"]
+        .iter()
+        .map(|column| {
+            if present.contains(*column) {
+                *column
+            } else {
+                "NULL"
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", "))
+}
+
+/// Maps a row selected with [`SPELL_DETAIL_COLUMNS`] followed by [`spell_optional_columns`].
+fn spell_detail_with_optional_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SpellDetail> {
+    let mut spell = spell_detail_from_row(row)?;
+    spell.created_at = row.get(27)?;
+    spell.updated_at = row.get(28)?;
+    spell.reverse_name = row.get(29)?;
+    Ok(spell)
+}
+
 pub fn get_spell_from_conn(conn: &Connection, id: i64) -> Result<Option<SpellDetail>, AppError> {
+    let optional_columns = spell_optional_columns(conn)?;
     let mut spell: SpellDetail = conn
         .query_row(
-            "SELECT id, name, school, sphere, class_list, level, range, components,
-                material_components, casting_time, duration, area, saving_throw, reversible,
-                description, tags, source, edition, author, license, is_quest_spell, is_cantrip,
-                damage, magic_resistance, schema_version, canonical_data, content_hash
-         FROM spell WHERE id = ?",
+            &format!("SELECT {SPELL_DETAIL_COLUMNS}, {optional_columns} FROM spell WHERE id = ?"),
             [id],
-            |row| {
-                let canonical_data_str: Option<String> = row.get(25)?;
-
-                // Try to parse canonical_data to populate structured specs
-                let mut range_spec = None;
-                let mut components_spec = None;
-                let mut material_components_spec = None;
-                let mut casting_time_spec = None;
-                let mut duration_spec = None;
-                let mut area_spec = None;
-                let mut saving_throw_spec = None;
-                let mut damage_spec = None;
-                let mut magic_resistance_spec = None;
-
-                if let Some(json_str) = &canonical_data_str {
-                    if let Ok(canon) = serde_json::from_str::<CanonicalSpell>(json_str) {
-                        range_spec = canon.range;
-                        components_spec = canon.components;
-                        material_components_spec = canon.material_components;
-                        casting_time_spec = canon.casting_time;
-                        duration_spec = canon.duration;
-                        area_spec = canon.area;
-                        saving_throw_spec = canon.saving_throw;
-                        damage_spec = canon.damage;
-                        magic_resistance_spec = canon.magic_resistance;
-                    }
-                }
-
-                Ok(SpellDetail {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    school: row.get(2)?,
-                    sphere: row.get(3)?,
-                    class_list: row.get(4)?,
-                    level: row.get(5)?,
-                    range: row.get(6)?,
-                    components: row.get(7)?,
-                    material_components: row.get(8)?,
-                    casting_time: row.get(9)?,
-                    duration: row.get(10)?,
-                    area: row.get(11)?,
-                    saving_throw: row.get(12)?,
-                    reversible: row.get(13)?,
-                    description: row.get(14)?,
-                    tags: row.get(15)?,
-                    source: row.get(16)?,
-                    edition: row.get(17)?,
-                    author: row.get(18)?,
-                    license: row.get(19)?,
-                    is_quest_spell: row.get(20)?,
-                    is_cantrip: row.get(21)?,
-                    damage: row.get(22)?,
-                    magic_resistance: row.get(23)?,
-                    schema_version: row.get(24)?,
-                    artifacts: None,
-                    canonical_data: canonical_data_str,
-                    content_hash: row.get(26)?,
-                    range_spec,
-                    components_spec,
-                    material_components_spec,
-                    casting_time_spec,
-                    duration_spec,
-                    area_spec,
-                    saving_throw_spec,
-                    damage_spec,
-                    magic_resistance_spec,
-                })
-            },
+            spell_detail_with_optional_from_row,
         )
         .optional()?
         .ok_or_else(|| AppError::NotFound("Spell not found".into()))?;
 
+    spell.artifacts = Some(load_spell_artifacts_with_conn(
+        conn,
+        spell.id,
+        spell.content_hash.as_deref(),
+    )?);
+
+    Ok(Some(spell))
+}
+
+/// Artifacts of one spell, primary first.
+fn load_spell_artifacts_with_conn(
+    conn: &Connection,
+    spell_id: Option<i64>,
+    content_hash: Option<&str>,
+) -> Result<Vec<SpellArtifact>, AppError> {
     // Hash-first: load artifacts by spell content hash; fallback to spell_id only when
     // spell_content_hash IS NULL (migration-period legacy). Exclude rows whose spell_id
     // matches but spell_content_hash belongs to a different spell.
@@ -163,7 +260,7 @@ pub fn get_spell_from_conn(conn: &Connection, id: i64) -> Result<Option<SpellDet
     // When spell_id is dropped: keep only spell_content_hash = ? branch; remove legacy OR and (sid, None) arm.
     let artifact_has_hash_column =
         crate::db::table_has_column(conn, "artifact", "spell_content_hash");
-    let artifacts: Vec<SpellArtifact> = match (spell.id, spell.content_hash.as_deref()) {
+    let artifacts: Vec<SpellArtifact> = match (spell_id, content_hash) {
         (Some(sid), Some(h)) => {
             if artifact_has_hash_column {
                 let mut stmt = conn.prepare(
@@ -181,6 +278,8 @@ pub fn get_spell_from_conn(conn: &Connection, id: i64) -> Result<Option<SpellDet
                         hash: row.get(4)?,
                         imported_at: row.get(5)?,
                         spell_content_hash: row.get(6)?,
+                        is_primary: false,
+                        exists: None,
                     })
                 })?;
                 rows.collect::<Result<Vec<_>, _>>()?
@@ -197,6 +296,8 @@ pub fn get_spell_from_conn(conn: &Connection, id: i64) -> Result<Option<SpellDet
                         hash: row.get(4)?,
                         imported_at: row.get(5)?,
                         spell_content_hash: None,
+                        is_primary: false,
+                        exists: None,
                     })
                 })?;
                 rows.collect::<Result<Vec<_>, _>>()?
@@ -215,6 +316,8 @@ pub fn get_spell_from_conn(conn: &Connection, id: i64) -> Result<Option<SpellDet
                     hash: row.get(4)?,
                     imported_at: row.get(5)?,
                     spell_content_hash: None,
+                    is_primary: false,
+                    exists: None,
                 })
             })?;
             rows.collect::<Result<Vec<_>, _>>()?
@@ -222,9 +325,364 @@ pub fn get_spell_from_conn(conn: &Connection, id: i64) -> Result<Option<SpellDet
         (None, _) => vec![],
     };
 
-    spell.artifacts = Some(artifacts);
+    let mut artifacts = artifacts;
+    let primary = primary_artifact_ids(conn, &artifacts)?;
+    order_artifacts_primary_first(&mut artifacts, &primary);
+    Ok(artifacts)
+}
 
-    Ok(Some(spell))
+/// Just the artifact list of spell `spell_id`, each flagged with whether its file is
+/// still on disk. Skips the rest of the spell payload.
+pub(crate) fn get_spell_artifacts_with_conn(
+    conn: &Connection,
+    spell_id: i64,
+) -> Result<Vec<SpellArtifact>, AppError> {
+    let content_hash: Option<String> = conn
+        .query_row(
+            "SELECT content_hash FROM spell WHERE id = ?",
+            [spell_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Spell {} not found", spell_id)))?;
+    let mut artifacts =
+        load_spell_artifacts_with_conn(conn, Some(spell_id), content_hash.as_deref())?;
+    for artifact in &mut artifacts {
+        artifact.exists = Some(std::path::Path::new(&artifact.path).exists());
+    }
+    Ok(artifacts)
+}
+
+#[tauri::command]
+pub async fn get_spell_artifacts(
+    state: State<'_, Arc<Pool>>,
+    spell_id: i64,
+) -> Result<Vec<SpellArtifact>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_spell_artifacts_with_conn(&conn, spell_id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+fn resolve_value(
+    field: &str,
+    text: Option<String>,
+    scalar: Option<&SpellScalar>,
+    unit: Option<&str>,
+    caster_level: i32,
+) -> ResolvedValue {
+    ResolvedValue {
+        field: field.to_string(),
+        text,
+        formula: scalar.map(SpellScalar::to_text),
+        value: scalar.and_then(|s| s.evaluate_at(caster_level)),
+        unit: unit.map(str::to_string),
+    }
+}
+
+/// Evaluates the range, duration, area and damage scalars of `spell` at `caster_level`.
+/// Fields without a scalar pass through as text only.
+pub(crate) fn resolve_spell_at_level(
+    spell_id: i64,
+    spell: &CanonicalSpell,
+    caster_level: i32,
+) -> ResolvedSpell {
+    let range = spell.range.as_ref().map(|range| {
+        resolve_value(
+            "distance",
+            range.text.clone(),
+            range.distance.as_ref(),
+            range.unit.as_ref().map(|u| u.to_text()),
+            caster_level,
+        )
+    });
+
+    let duration = spell
+        .duration
+        .as_ref()
+        .map(|duration| match &duration.duration {
+            Some(scalar) => resolve_value(
+                "duration",
+                duration.text.clone(),
+                Some(scalar),
+                duration.unit.as_ref().map(|u| u.to_text()),
+                caster_level,
+            ),
+            None => resolve_value(
+                "uses",
+                duration.text.clone(),
+                duration.uses.as_ref(),
+                None,
+                caster_level,
+            ),
+        });
+
+    let area = spell
+        .area
+        .as_ref()
+        .map(|area| {
+            let linear = area.shape_unit.map(|u| u.to_text());
+            let measured = area.unit.map(|u| u.to_text());
+            [
+                ("radius", &area.radius, linear),
+                ("diameter", &area.diameter, linear),
+                ("length", &area.length, linear),
+                ("width", &area.width, linear),
+                ("height", &area.height, linear),
+                ("thickness", &area.thickness, linear),
+                ("edge", &area.edge, linear),
+                ("surface_area", &area.surface_area, measured),
+                ("volume", &area.volume, measured),
+                (
+                    "tile_count",
+                    &area.tile_count,
+                    area.tile_unit.map(|u| u.to_text()),
+                ),
+                (
+                    "count",
+                    &area.count,
+                    area.count_subject.map(|s| s.to_text()),
+                ),
+            ]
+            .into_iter()
+            .filter_map(|(field, scalar, unit)| {
+                scalar.as_ref().map(|scalar| {
+                    resolve_value(field, area.text.clone(), Some(scalar), unit, caster_level)
+                })
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+
+    let damage = spell
+        .damage
+        .as_ref()
+        .and_then(|damage| damage.parts.as_ref())
+        .map(|parts| {
+            parts
+                .iter()
+                .map(|part| ResolvedDamage {
+                    label: part.label.clone(),
+                    damage_type: part.damage_type,
+                    base: part.base.to_text(),
+                    dice: part.dice_at_level(caster_level).to_text(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    ResolvedSpell {
+        spell_id,
+        name: spell.name.clone(),
+        caster_level,
+        range,
+        duration,
+        area,
+        damage,
+    }
+}
+
+pub(crate) fn render_spell_at_level_with_conn(
+    conn: &Connection,
+    spell_id: i64,
+    caster_level: i32,
+) -> Result<ResolvedSpell, AppError> {
+    if caster_level < 1 {
+        return Err(AppError::Validation(format!(
+            "Caster level must be at least 1, got {}",
+            caster_level
+        )));
+    }
+    let detail = get_spell_from_conn(conn, spell_id)?
+        .ok_or_else(|| AppError::NotFound(format!("Spell {} not found", spell_id)))?;
+    let (canonical, _, _) = canonicalize_spell_detail(detail)?;
+    Ok(resolve_spell_at_level(spell_id, &canonical, caster_level))
+}
+
+#[tauri::command]
+pub async fn render_spell_at_level(
+    state: State<'_, Arc<Pool>>,
+    spell_id: i64,
+    caster_level: i32,
+) -> Result<ResolvedSpell, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        render_spell_at_level_with_conn(&conn, spell_id, caster_level)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Ids among `artifacts` flagged `is_primary` (empty before migration 0019).
+fn primary_artifact_ids(
+    conn: &Connection,
+    artifacts: &[SpellArtifact],
+) -> Result<HashSet<i64>, AppError> {
+    if artifacts.is_empty() || !crate::db::table_has_column(conn, "artifact", "is_primary") {
+        return Ok(HashSet::new());
+    }
+    let placeholders = vec!["?"; artifacts.len()].join(", ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id FROM artifact WHERE is_primary = 1 AND id IN ({placeholders})"
+    ))?;
+    let rows = stmt.query_map(
+        rusqlite::params_from_iter(artifacts.iter().map(|a| a.id)),
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(rows.collect::<Result<HashSet<_>, _>>()?)
+}
+
+/// Marks primary artifacts and moves them to the front, keeping the rest in load order.
+fn order_artifacts_primary_first(artifacts: &mut [SpellArtifact], primary: &HashSet<i64>) {
+    for artifact in artifacts.iter_mut() {
+        artifact.is_primary = primary.contains(&artifact.id);
+    }
+    artifacts.sort_by_key(|a| !a.is_primary);
+}
+
+/// Batch form of [`get_spell_from_conn`]: one spell query plus one artifact query for all ids.
+/// Results follow the order of `ids` (first occurrence wins); missing ids are omitted.
+/// Artifacts attach with the same hash-first / legacy `spell_id` rules as the single fetch.
+pub(crate) fn get_spells_batch_with_conn(
+    conn: &Connection,
+    ids: &[i64],
+) -> Result<Vec<SpellDetail>, AppError> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    let optional_columns = spell_optional_columns(conn)?;
+    let mut spells: Vec<SpellDetail> = {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {SPELL_DETAIL_COLUMNS}, {optional_columns} FROM spell WHERE id IN ({placeholders})"
+        ))?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(ids.iter()),
+            spell_detail_with_optional_from_row,
+        )?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let by_id: HashMap<i64, usize> = spells
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| s.id.map(|id| (id, i)))
+        .collect();
+    let by_hash: HashMap<String, usize> = spells
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| s.content_hash.clone().map(|h| (h, i)))
+        .collect();
+    let spell_ids: Vec<i64> = by_id.keys().copied().collect();
+
+    let mut artifacts_by_spell: Vec<Vec<SpellArtifact>> = vec![vec![]; spells.len()];
+    if !spell_ids.is_empty() {
+        let id_placeholders = vec!["?"; spell_ids.len()].join(", ");
+        if crate::db::table_has_column(conn, "artifact", "spell_content_hash") {
+            let hashes: Vec<&String> = by_hash.keys().collect();
+            let mut sql = format!(
+                "SELECT id, spell_id, type, path, hash, imported_at, spell_content_hash FROM artifact
+                 WHERE spell_id IN ({id_placeholders})"
+            );
+            if !hashes.is_empty() {
+                sql.push_str(&format!(
+                    " OR spell_content_hash IN ({})",
+                    vec!["?"; hashes.len()].join(", ")
+                ));
+            }
+            sql.push_str(" ORDER BY id");
+
+            let mut params: Vec<&dyn rusqlite::ToSql> = vec![];
+            params.extend(spell_ids.iter().map(|id| id as &dyn rusqlite::ToSql));
+            params.extend(hashes.iter().map(|h| *h as &dyn rusqlite::ToSql));
+
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt.query_map(params.as_slice(), |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, Option<i64>>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                ))
+            })?;
+            for row in rows {
+                let (id, artifact_spell_id, r#type, path, hash, imported_at, spell_content_hash) =
+                    row?;
+                // Hash-first: a hashed artifact belongs to the spell carrying that hash.
+                // Legacy: match by spell_id when the artifact has no hash, or the spell has none.
+                let target = match spell_content_hash.as_deref() {
+                    Some(h) if by_hash.contains_key(h) => by_hash.get(h).copied(),
+                    _ => artifact_spell_id
+                        .and_then(|sid| by_id.get(&sid).copied())
+                        .filter(|&i| {
+                            spell_content_hash.is_none() || spells[i].content_hash.is_none()
+                        }),
+                };
+                if let Some(i) = target {
+                    let spell_id = artifact_spell_id.or(spells[i].id).unwrap_or_default();
+                    artifacts_by_spell[i].push(SpellArtifact {
+                        id,
+                        spell_id,
+                        r#type,
+                        path,
+                        hash,
+                        imported_at,
+                        spell_content_hash,
+                        is_primary: false,
+                        exists: None,
+                    });
+                }
+            }
+        } else {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT id, spell_id, type, path, hash, imported_at FROM artifact
+                 WHERE spell_id IN ({id_placeholders}) ORDER BY id"
+            ))?;
+            let rows = stmt.query_map(rusqlite::params_from_iter(spell_ids.iter()), |row| {
+                Ok(SpellArtifact {
+                    id: row.get(0)?,
+                    spell_id: row.get(1)?,
+                    r#type: row.get(2)?,
+                    path: row.get(3)?,
+                    hash: row.get(4)?,
+                    imported_at: row.get(5)?,
+                    spell_content_hash: None,
+                    is_primary: false,
+                    exists: None,
+                })
+            })?;
+            for artifact in rows {
+                let artifact = artifact?;
+                if let Some(&i) = by_id.get(&artifact.spell_id) {
+                    artifacts_by_spell[i].push(artifact);
+                }
+            }
+        }
+    }
+
+    let all_artifacts: Vec<SpellArtifact> = artifacts_by_spell.iter().flatten().cloned().collect();
+    let primary = primary_artifact_ids(conn, &all_artifacts)?;
+    for (spell, mut artifacts) in spells.iter_mut().zip(artifacts_by_spell) {
+        order_artifacts_primary_first(&mut artifacts, &primary);
+        spell.artifacts = Some(artifacts);
+    }
+
+    let mut slots: Vec<Option<SpellDetail>> = spells.into_iter().map(Some).collect();
+    let mut ordered = Vec::with_capacity(slots.len());
+    for id in ids {
+        if let Some(spell) = by_id.get(id).and_then(|&i| slots[i].take()) {
+            ordered.push(spell);
+        }
+    }
+    Ok(ordered)
 }
 
 pub(crate) fn diff_spells(old: &SpellDetail, new: &SpellUpdate) -> Vec<(String, String, String)> {
@@ -247,11 +705,13 @@ pub(crate) fn diff_spells(old: &SpellDetail, new: &SpellUpdate) -> Vec<(String,
             new.sphere.clone().unwrap_or_default(),
         ));
     }
-    if old.class_list != new.class_list {
+    let old_class_list = normalize_list_column(&old.class_list);
+    let new_class_list = normalize_list_column(&new.class_list);
+    if old_class_list != new_class_list {
         changes.push((
             "class_list".into(),
-            old.class_list.clone().unwrap_or_default(),
-            new.class_list.clone().unwrap_or_default(),
+            old_class_list.unwrap_or_default(),
+            new_class_list.unwrap_or_default(),
         ));
     }
     if old.level != new.level {
@@ -332,6 +792,17 @@ pub(crate) fn diff_spells(old: &SpellDetail, new: &SpellUpdate) -> Vec<(String,
         }
         _ => {}
     }
+    if let Some(new_reverse) = &new.reverse_name {
+        let new_reverse = new_reverse.trim();
+        let old_reverse = old.reverse_name.as_deref().unwrap_or_default();
+        if old_reverse != new_reverse {
+            changes.push((
+                "reverse_name".into(),
+                old_reverse.to_string(),
+                new_reverse.to_string(),
+            ));
+        }
+    }
     if old.description != new.description {
         changes.push((
             "description".into(),
@@ -339,11 +810,13 @@ pub(crate) fn diff_spells(old: &SpellDetail, new: &SpellUpdate) -> Vec<(String,
             new.description.clone(),
         ));
     }
-    if old.tags != new.tags {
+    let old_tags = normalize_list_column(&old.tags);
+    let new_tags = normalize_list_column(&new.tags);
+    if old_tags != new_tags {
         changes.push((
             "tags".into(),
-            old.tags.clone().unwrap_or_default(),
-            new.tags.clone().unwrap_or_default(),
+            old_tags.unwrap_or_default(),
+            new_tags.unwrap_or_default(),
         ));
     }
     if old.source != new.source {
@@ -385,34 +858,7 @@ pub(crate) fn diff_spells(old: &SpellDetail, new: &SpellUpdate) -> Vec<(String,
     changes
 }
 
-fn cascade_spell_content_hash_refs(
-    conn: &Connection,
-    old_hash: Option<&str>,
-    new_hash: &str,
-) -> Result<(), AppError> {
-    let Some(old_hash) = old_hash else {
-        return Ok(());
-    };
-    if old_hash == new_hash {
-        return Ok(());
-    }
-
-    if crate::db::table_has_column(conn, "character_class_spell", "spell_content_hash") {
-        conn.execute(
-            "UPDATE character_class_spell SET spell_content_hash = ? WHERE spell_content_hash = ?",
-            params![new_hash, old_hash],
-        )?;
-    }
-    if crate::db::table_has_column(conn, "artifact", "spell_content_hash") {
-        conn.execute(
-            "UPDATE artifact SET spell_content_hash = ? WHERE spell_content_hash = ?",
-            params![new_hash, old_hash],
-        )?;
-    }
-    Ok(())
-}
-
-fn spell_detail_to_update(spell: &SpellDetail, id: i64) -> SpellUpdate {
+pub(crate) fn spell_detail_to_update(spell: &SpellDetail, id: i64) -> SpellUpdate {
     SpellUpdate {
         id,
         name: spell.name.clone(),
@@ -430,6 +876,7 @@ fn spell_detail_to_update(spell: &SpellDetail, id: i64) -> SpellUpdate {
         damage: spell.damage.clone(),
         magic_resistance: spell.magic_resistance.clone(),
         reversible: spell.reversible,
+        reverse_name: spell.reverse_name.clone(),
         description: spell.description.clone(),
         tags: spell.tags.clone(),
         source: spell.source.clone(),
@@ -481,8 +928,15 @@ where
 pub fn canonicalize_spell_detail(
     detail: SpellDetail,
 ) -> Result<(CanonicalSpell, String, String), AppError> {
-    let mut canonical = CanonicalSpell::try_from(detail).map_err(AppError::Validation)?;
+    let canonical = CanonicalSpell::try_from(detail).map_err(AppError::Validation)?;
+    finalize_canonical_spell(canonical)
+}
 
+/// Normalizes, validates and hashes an already-built `canonical`, returning it with its
+/// `id` set to the hash, the hash, and the JSON stored in `canonical_data`.
+pub(crate) fn finalize_canonical_spell(
+    mut canonical: CanonicalSpell,
+) -> Result<(CanonicalSpell, String, String), AppError> {
     // Normalize BEFORE hashing/serializing to ensure the stored data is clean
     let res = canonical.normalize(None);
     if res.notes_truncated {
@@ -517,24 +971,178 @@ pub fn canonicalize_spell_detail(
     Ok((canonical, hash, json))
 }
 
+/// `change_log` actor for edits made in the app.
+pub(crate) const USER_CHANGE_LOG_ACTOR: &str = "local";
+
+/// `change_log` actor for rows written by an import (including conflict resolution).
+pub(crate) const IMPORT_CHANGE_LOG_ACTOR: &str = "import";
+
+/// Records `changes` in `change_log` under `actor`. Schemas without an `actor` column
+/// (older test fixtures) fall back to the column default.
 pub(crate) fn log_changes(
     conn: &Connection,
     spell_id: i64,
     changes: Vec<(String, String, String)>,
+    actor: &str,
 ) -> Result<(), AppError> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let has_actor = crate::db::table_has_column(conn, "change_log", "actor");
     for (field, old_val, new_val) in changes {
-        conn.execute(
-            "INSERT INTO change_log (spell_id, field, old_value, new_value) VALUES (?, ?, ?, ?)",
-            params![spell_id, field, old_val, new_val],
-        )?;
+        if has_actor {
+            conn.execute(
+                "INSERT INTO change_log (spell_id, field, old_value, new_value, actor)
+                 VALUES (?, ?, ?, ?, ?)",
+                params![spell_id, field, old_val, new_val, actor],
+            )?;
+        } else {
+            conn.execute(
+                "INSERT INTO change_log (spell_id, field, old_value, new_value) VALUES (?, ?, ?, ?)",
+                params![spell_id, field, old_val, new_val],
+            )?;
+        }
     }
     Ok(())
 }
 
+/// Name reported for `change_log` rows whose spell no longer exists.
+const DELETED_SPELL_PLACEHOLDER: &str = "(deleted spell)";
+
+/// Default page size for `list_recent_changes`.
+const DEFAULT_RECENT_CHANGES_LIMIT: i64 = 50;
+
+pub(crate) fn list_recent_changes_with_conn(
+    conn: &Connection,
+    limit: i64,
+    offset: i64,
+) -> Result<RecentChangesPage, AppError> {
+    load_change_page(conn, None, limit, offset)
+}
+
+/// `list_recent_changes` restricted to rows written by `actor` (`local` for user edits,
+/// `import`, `reparse`, ...). Rows logged before actors were recorded count as `local`.
+pub(crate) fn list_changes_by_actor_with_conn(
+    conn: &Connection,
+    actor: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<RecentChangesPage, AppError> {
+    let actor = actor.trim();
+    if actor.is_empty() {
+        return Err(AppError::Validation("actor must not be empty".to_string()));
+    }
+    load_change_page(conn, Some(actor), limit, offset)
+}
+
+/// Newest-first page of `change_log` joined with spell names, optionally for one actor.
+fn load_change_page(
+    conn: &Connection,
+    actor: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<RecentChangesPage, AppError> {
+    let total: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM change_log WHERE ?1 IS NULL OR IFNULL(actor, 'local') = ?1",
+        [actor],
+        |row| row.get(0),
+    )?;
+    let mut stmt = conn.prepare(
+        "SELECT cl.id, cl.spell_id, COALESCE(s.name, ?1), cl.changed_at, cl.field,
+                cl.old_value, cl.new_value
+         FROM change_log cl
+         LEFT JOIN spell s ON s.id = cl.spell_id
+         WHERE ?2 IS NULL OR IFNULL(cl.actor, 'local') = ?2
+         ORDER BY cl.changed_at DESC, cl.id DESC
+         LIMIT ?3 OFFSET ?4",
+    )?;
+    let rows = stmt.query_map(
+        params![
+            DELETED_SPELL_PLACEHOLDER,
+            actor,
+            limit.max(0),
+            offset.max(0)
+        ],
+        |row| {
+            Ok(SpellChange {
+                id: row.get(0)?,
+                spell_id: row.get(1)?,
+                spell_name: row.get(2)?,
+                changed_at: row.get(3)?,
+                field: row.get(4)?,
+                old_value: row.get(5)?,
+                new_value: row.get(6)?,
+            })
+        },
+    )?;
+    Ok(RecentChangesPage {
+        changes: rows.collect::<Result<Vec<_>, _>>()?,
+        total,
+    })
+}
+
+/// Newest-first `change_log` entries across every spell, `limit` per page.
+#[tauri::command]
+pub async fn list_recent_changes(
+    state: State<'_, Arc<Pool>>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<RecentChangesPage, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        list_recent_changes_with_conn(
+            &conn,
+            limit.unwrap_or(DEFAULT_RECENT_CHANGES_LIMIT),
+            offset.unwrap_or(0),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Newest-first `change_log` entries written by `actor`, `limit` per page.
+#[tauri::command]
+pub async fn list_changes_by_actor(
+    state: State<'_, Arc<Pool>>,
+    actor: String,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<RecentChangesPage, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        list_changes_by_actor_with_conn(
+            &conn,
+            &actor,
+            limit.unwrap_or(DEFAULT_RECENT_CHANGES_LIMIT),
+            offset.unwrap_or(0),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
 pub fn apply_spell_update_with_conn(
     conn: &Connection,
     spell: &SpellUpdate,
 ) -> Result<i64, AppError> {
+    apply_spell_update_with_actor(conn, spell, USER_CHANGE_LOG_ACTOR)
+}
+
+/// [`apply_spell_update_with_conn`] with the resulting `change_log` rows attributed to
+/// `actor`.
+pub(crate) fn apply_spell_update_with_actor(
+    conn: &Connection,
+    spell: &SpellUpdate,
+    actor: &str,
+) -> Result<i64, AppError> {
+    let normalized = SpellUpdate {
+        class_list: normalize_list_column(&spell.class_list),
+        tags: normalize_list_column(&spell.tags),
+        ..spell.clone()
+    };
+    let spell = &normalized;
     run_in_savepoint(conn, "spell_update_write", || {
         validate_spell_fields(&spell.name, spell.level, &spell.description)?;
         validate_epic_and_quest_spells(
@@ -546,7 +1154,7 @@ pub fn apply_spell_update_with_conn(
 
         let old_hash = if let Some(old_spell) = get_spell_from_conn(conn, spell.id)? {
             let changes = diff_spells(&old_spell, spell);
-            log_changes(conn, spell.id, changes)?;
+            log_changes(conn, spell.id, changes, actor)?;
             old_spell.content_hash
         } else {
             None
@@ -569,6 +1177,7 @@ pub fn apply_spell_update_with_conn(
             damage: spell.damage.clone(),
             magic_resistance: spell.magic_resistance.clone(),
             reversible: spell.reversible,
+            reverse_name: spell.reverse_name.clone(),
             description: spell.description.clone(),
             tags: spell.tags.clone(),
             source: spell.source.clone(),
@@ -581,6 +1190,9 @@ pub fn apply_spell_update_with_conn(
             artifacts: None,
             canonical_data: None,
             content_hash: None,
+            created_at: None,
+            updated_at: None,
+            material_has_cost: false,
             range_spec: spell.range_spec.clone(),
             components_spec: spell.components_spec.clone(),
             material_components_spec: spell.material_components_spec.clone(),
@@ -631,6 +1243,7 @@ pub fn apply_spell_update_with_conn(
                 spell.id,
             ],
         )?;
+        set_spell_reverse_name_with_conn(conn, spell.id, spell.reverse_name.as_deref())?;
         cascade_spell_content_hash_refs(conn, old_hash.as_deref(), &hash)?;
 
         migration_manager::sync_check_spell(conn, spell.id);
@@ -683,15 +1296,111 @@ fn parsed_to_camel_value<T: serde::Serialize>(t: &T) -> Result<Value, AppError>
     Ok(v)
 }
 
+const SPELL_CACHE_CAPACITY: usize = 128;
+
+#[derive(Debug, Default)]
+struct SpellCacheEntries {
+    spells: HashMap<i64, SpellDetail>,
+    /// Least recently used id first.
+    order: VecDeque<i64>,
+    generation: u64,
+}
+
+/// Small LRU of `get_spell` results. Any command that writes spells or artifacts holds a
+/// `SpellCacheWriteGuard`, which drops every entry when the command finishes, so a hit
+/// always matches a fresh read.
+#[derive(Debug, Default)]
+pub struct SpellCache {
+    entries: Mutex<SpellCacheEntries>,
+}
+
+#[derive(Debug)]
+pub struct SpellCacheWriteGuard<'a> {
+    cache: &'a SpellCache,
+}
+
+impl SpellCache {
+    fn lock(&self) -> MutexGuard<'_, SpellCacheEntries> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn get(&self, id: i64) -> Option<SpellDetail> {
+        let mut entries = self.lock();
+        let spell = entries.spells.get(&id).cloned()?;
+        entries.order.retain(|cached| *cached != id);
+        entries.order.push_back(id);
+        Some(spell)
+    }
+
+    fn generation(&self) -> u64 {
+        self.lock().generation
+    }
+
+    /// Stores `spell` unless a write invalidated the cache after `generation` was read.
+    fn insert(&self, id: i64, spell: SpellDetail, generation: u64) {
+        let mut entries = self.lock();
+        if entries.generation != generation {
+            return;
+        }
+        entries.order.retain(|cached| *cached != id);
+        entries.order.push_back(id);
+        entries.spells.insert(id, spell);
+        while entries.order.len() > SPELL_CACHE_CAPACITY {
+            if let Some(evicted) = entries.order.pop_front() {
+                entries.spells.remove(&evicted);
+            }
+        }
+    }
+
+    pub fn invalidate_all(&self) {
+        let mut entries = self.lock();
+        entries.spells.clear();
+        entries.order.clear();
+        entries.generation = entries.generation.wrapping_add(1);
+    }
+
+    /// Invalidates now and again when the guard drops, covering reads that race the write.
+    pub fn start_write(&self) -> SpellCacheWriteGuard<'_> {
+        self.invalidate_all();
+        SpellCacheWriteGuard { cache: self }
+    }
+}
+
+impl Drop for SpellCacheWriteGuard<'_> {
+    fn drop(&mut self) {
+        self.cache.invalidate_all();
+    }
+}
+
+pub(crate) fn get_spell_cached_with_conn(
+    conn: &Connection,
+    cache: &SpellCache,
+    id: i64,
+) -> Result<Option<SpellDetail>, AppError> {
+    if let Some(spell) = cache.get(id) {
+        return Ok(Some(spell));
+    }
+    let generation = cache.generation();
+    let spell = get_spell_from_conn(conn, id)?;
+    if let Some(spell) = &spell {
+        cache.insert(id, spell.clone(), generation);
+    }
+    Ok(spell)
+}
+
 #[tauri::command]
 pub async fn get_spell(
     state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
     id: i64,
 ) -> Result<Option<SpellDetail>, AppError> {
     let pool = state.inner().clone();
+    let spell_cache = spell_cache.inner().clone();
     let result = tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        get_spell_from_conn(&conn, id)
+        get_spell_cached_with_conn(&conn, &spell_cache, id)
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))??;
@@ -700,10 +1409,26 @@ pub async fn get_spell(
 }
 
 #[tauri::command]
-pub async fn parse_spell_range(legacy: String) -> Result<Value, AppError> {
-    let parser = SpellParser::new();
-    let spec = parser.parse_range(&legacy);
-    parsed_to_camel_value(&spec)
+pub async fn get_spells_batch(
+    state: State<'_, Arc<Pool>>,
+    ids: Vec<i64>,
+) -> Result<Vec<SpellDetail>, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_spells_batch_with_conn(&conn, &ids)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn parse_spell_range(legacy: String) -> Result<Value, AppError> {
+    let parser = SpellParser::new();
+    let spec = parser.parse_range(&legacy);
+    parsed_to_camel_value(&spec)
 }
 
 #[tauri::command]
@@ -744,6 +1469,11 @@ pub fn parse_spell_components(legacy: String) -> Result<Value, AppError> {
     parsed_to_camel_value(&spec)
 }
 
+#[tauri::command]
+pub fn canonicalize_components(input: String) -> String {
+    SpellParser::new().canonicalize_components(&input)
+}
+
 #[tauri::command]
 pub fn extract_materials_from_components_line(legacy: String) -> Result<Value, AppError> {
     let parser = SpellParser::new();
@@ -786,36 +1516,110 @@ pub fn parse_spell_components_with_migration(
     parsed_to_camel_value(&result)
 }
 
+/// Default length of `SpellSummary::description_preview`, in characters.
+pub(crate) const DEFAULT_DESCRIPTION_PREVIEW_CHARS: usize = 160;
+
+/// SQL expression selecting just enough of `column` to build a preview of `max_chars`
+/// (one extra character tells whether the text was cut). `NULL` when previews are off.
+pub(crate) fn description_preview_sql(column: &str, max_chars: usize) -> String {
+    if max_chars == 0 {
+        "NULL".to_string()
+    } else {
+        format!("substr({column}, 1, {})", max_chars + 1)
+    }
+}
+
+/// Collapses whitespace and cuts `text` to at most `max_chars` characters, backing off to
+/// the last word boundary and appending an ellipsis when anything was dropped.
+/// `text` may itself be a prefix from [`description_preview_sql`].
+pub(crate) fn description_preview(text: &str, max_chars: usize) -> Option<String> {
+    if max_chars == 0 {
+        return None;
+    }
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    let source_was_cut = text.chars().count() > max_chars;
+    if !source_was_cut && collapsed.chars().count() <= max_chars {
+        return Some(collapsed);
+    }
+
+    let mut head: String = collapsed.chars().take(max_chars).collect();
+    // When whitespace runs make `collapsed` fit but `text` was itself cut short, the
+    // word at its end may continue past the prefix; only trailing whitespace proves not.
+    let splits_word = match collapsed.chars().nth(max_chars) {
+        Some(next) => !next.is_whitespace(),
+        None => source_was_cut && !text.ends_with(char::is_whitespace),
+    };
+    if splits_word {
+        if let Some(boundary) = head.rfind(' ') {
+            head.truncate(boundary);
+        }
+    }
+    let head = head.trim_end_matches(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | ':'));
+    Some(format!("{head}…"))
+}
+
+/// Columns read by [`spell_summary_from_row`], which expects the description preview
+/// expression right after them.
+const SPELL_SUMMARY_COLUMNS: &str = "id, name, school, sphere, level, class_list, components, duration, source, is_quest_spell, is_cantrip, tags";
+
+fn spell_summary_from_row(
+    row: &rusqlite::Row<'_>,
+    preview_chars: usize,
+) -> rusqlite::Result<SpellSummary> {
+    Ok(SpellSummary {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        school: row.get(2)?,
+        sphere: row.get(3)?,
+        level: row.get(4)?,
+        class_list: row.get(5)?,
+        components: row.get(6)?,
+        duration: row.get(7)?,
+        source: row.get(8)?,
+        is_quest_spell: row.get(9)?,
+        is_cantrip: row.get(10)?,
+        tags: row.get(11)?,
+        description_preview: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|text| description_preview(&text, preview_chars)),
+    })
+}
+
+/// Every spell as a summary row, ordered by name. Works against any spellbook database.
+/// `preview_chars` sets the description preview length (0 disables it).
+pub(crate) fn list_spell_summaries_with_conn(
+    conn: &Connection,
+    preview_chars: usize,
+) -> Result<Vec<SpellSummary>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SPELL_SUMMARY_COLUMNS}, {}
+         FROM spell ORDER BY name ASC",
+        description_preview_sql("description", preview_chars)
+    ))?;
+    let rows = stmt.query_map([], |row| spell_summary_from_row(row, preview_chars))?;
+
+    let mut spells = vec![];
+    for spell in rows {
+        spells.push(spell?);
+    }
+    Ok(spells)
+}
+
 #[tauri::command]
-pub async fn list_spells(state: State<'_, Arc<Pool>>) -> Result<Vec<SpellSummary>, AppError> {
+pub async fn list_spells(
+    state: State<'_, Arc<Pool>>,
+    sort_by: Option<String>,
+    description_preview_chars: Option<usize>,
+) -> Result<Vec<SpellSummary>, AppError> {
     let pool = state.inner().clone();
+    let preview_chars = description_preview_chars.unwrap_or(DEFAULT_DESCRIPTION_PREVIEW_CHARS);
     let result = tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, name, school, sphere, level, class_list, components, duration, source, is_quest_spell, is_cantrip, tags
-             FROM spell ORDER BY name ASC",
-        )?;
-        let rows = stmt.query_map([], |row| {
-            Ok(SpellSummary {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                school: row.get(2)?,
-                sphere: row.get(3)?,
-                level: row.get(4)?,
-                class_list: row.get(5)?,
-                components: row.get(6)?,
-                duration: row.get(7)?,
-                source: row.get(8)?,
-                is_quest_spell: row.get(9)?,
-                is_cantrip: row.get(10)?,
-                tags: row.get(11)?,
-            })
-        })?;
-
-        let mut spells = vec![];
-        for spell in rows {
-            spells.push(spell?);
-        }
+        let mut spells = list_spell_summaries_with_conn(&conn, preview_chars)?;
+        apply_sort_by(&conn, &mut spells, sort_by.as_deref())?;
         Ok::<Vec<SpellSummary>, AppError>(spells)
     })
     .await
@@ -824,1043 +1628,4114 @@ pub async fn list_spells(state: State<'_, Arc<Pool>>) -> Result<Vec<SpellSummary
     Ok(result)
 }
 
+/// Replaces the per-class level overrides for one spell. Class names match case-insensitively.
+pub(crate) fn replace_class_spell_levels(
+    conn: &Connection,
+    spell_id: i64,
+    levels: &HashMap<String, i64>,
+) -> Result<(), AppError> {
+    conn.execute(
+        "DELETE FROM class_spell_level WHERE spell_id = ?",
+        params![spell_id],
+    )?;
+    for (class, level) in levels {
+        let class = class.trim();
+        if class.is_empty() {
+            continue;
+        }
+        conn.execute(
+            "INSERT OR REPLACE INTO class_spell_level (spell_id, class, level) VALUES (?, ?, ?)",
+            params![spell_id, class, level],
+        )?;
+    }
+    Ok(())
+}
+
+/// Level of `spell_id` for `class`, falling back to `spell.level` when no override exists.
+pub(crate) fn get_class_spell_level_with_conn(
+    conn: &Connection,
+    spell_id: i64,
+    class: &str,
+) -> Result<i64, AppError> {
+    conn.query_row(
+        "SELECT COALESCE(
+             (SELECT csl.level FROM class_spell_level csl
+              WHERE csl.spell_id = s.id AND csl.class = ?2),
+             s.level)
+         FROM spell s WHERE s.id = ?1",
+        params![spell_id, class.trim()],
+        |row| row.get(0),
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Spell id {} not found", spell_id)))
+}
+
 #[tauri::command]
-pub async fn create_spell(
+pub async fn get_class_spell_level(
     state: State<'_, Arc<Pool>>,
-    spell: SpellCreate,
+    spell_id: i64,
+    class: String,
 ) -> Result<i64, AppError> {
     let pool = state.inner().clone();
-    let result = tokio::task::spawn_blocking(move || {
-        validate_spell_fields(&spell.name, spell.level, &spell.description)?;
-        validate_epic_and_quest_spells(
-            spell.level,
-            &spell.class_list,
-            spell.is_quest_spell != 0,
-            spell.is_cantrip != 0,
-        )?;
-
-        let detail = SpellDetail {
-            id: None,
-            name: spell.name.clone(),
-            school: spell.school.clone(),
-            sphere: spell.sphere.clone(),
-            class_list: spell.class_list.clone(),
-            level: spell.level,
-            range: spell.range.clone(),
-            components: spell.components.clone(),
-            material_components: spell.material_components.clone(),
-            casting_time: spell.casting_time.clone(),
-            duration: spell.duration.clone(),
-            area: spell.area.clone(),
-            saving_throw: spell.saving_throw.clone(),
-            damage: spell.damage.clone(),
-            magic_resistance: spell.magic_resistance.clone(),
-            reversible: spell.reversible,
-            description: spell.description.clone(),
-            tags: spell.tags.clone(),
-            source: spell.source.clone(),
-            edition: spell.edition.clone(),
-            author: spell.author.clone(),
-            license: spell.license.clone(),
-            is_quest_spell: spell.is_quest_spell,
-            is_cantrip: spell.is_cantrip,
-            schema_version: None,
-            artifacts: None,
-            canonical_data: None,
-            content_hash: None,
-            range_spec: spell.range_spec.clone(),
-            components_spec: spell.components_spec.clone(),
-            material_components_spec: spell.material_components_spec.clone(),
-            casting_time_spec: spell.casting_time_spec.clone(),
-            duration_spec: spell.duration_spec.clone(),
-            area_spec: spell.area_spec.clone(),
-            saving_throw_spec: spell.saving_throw_spec.clone(),
-            damage_spec: spell.damage_spec.clone(),
-            magic_resistance_spec: spell.magic_resistance_spec.clone(),
-        };
-        let (canonical, hash, json) = canonicalize_spell_detail(detail)?;
-
+    tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        run_in_savepoint(&conn, "spell_create_write", || {
-            conn.execute(
-                "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
-                 material_components, casting_time, duration, area, saving_throw, damage,
-                 magic_resistance, reversible, description, tags, source, edition, author,
-                 license, is_quest_spell, is_cantrip, canonical_data, content_hash,
-                 schema_version)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-                params![
-                    spell.name,
-                    spell.school,
-                    spell.sphere,
-                    spell.class_list,
-                    spell.level,
-                    spell.range,
-                    spell.components,
-                    spell.material_components,
-                    spell.casting_time,
-                    spell.duration,
-                    spell.area,
-                    spell.saving_throw,
-                    spell.damage,
-                    spell.magic_resistance,
-                    spell.reversible.unwrap_or(0),
-                    spell.description,
-                    spell.tags,
-                    spell.source,
-                    spell.edition,
-                    spell.author,
-                    spell.license,
-                    spell.is_quest_spell,
-                    spell.is_cantrip,
-                    json,
-                    hash,
-                    canonical.schema_version,
-                ],
-            )?;
-            let spell_id = conn.last_insert_rowid();
-            export_spell_to_vault_by_hash(&conn, &hash)?;
-            Ok::<i64, AppError>(spell_id)
-        })
+        get_class_spell_level_with_conn(&conn, spell_id, &class)
     })
     .await
-    .map_err(|e| AppError::Unknown(e.to_string()))??;
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
 
-    Ok(result)
+/// Spells whose class list includes `class`, with `level` replaced by the class-specific
+/// level where one is recorded. Ordered by that level, then name.
+pub(crate) fn list_spells_by_class_with_conn(
+    conn: &Connection,
+    class: &str,
+    preview_chars: usize,
+) -> Result<Vec<SpellSummary>, AppError> {
+    let class = class.trim();
+    let mut stmt = conn.prepare("SELECT spell_id, level FROM class_spell_level WHERE class = ?")?;
+    let overrides = stmt
+        .query_map(params![class], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+        })?
+        .collect::<Result<HashMap<i64, i64>, _>>()?;
+
+    let mut spells: Vec<SpellSummary> = list_spell_summaries_with_conn(conn, preview_chars)?
+        .into_iter()
+        .filter(|spell| {
+            overrides.contains_key(&spell.id)
+                || spell.class_list.as_deref().is_some_and(|list| {
+                    parse_list_column(list)
+                        .iter()
+                        .any(|entry| entry.eq_ignore_ascii_case(class))
+                })
+        })
+        .map(|mut spell| {
+            if let Some(level) = overrides.get(&spell.id) {
+                spell.level = *level;
+            }
+            spell
+        })
+        .collect();
+    spells.sort_by(|a, b| a.level.cmp(&b.level).then_with(|| a.name.cmp(&b.name)));
+    Ok(spells)
 }
 
 #[tauri::command]
-pub async fn update_spell(
+pub async fn list_spells_by_class(
     state: State<'_, Arc<Pool>>,
-    spell: SpellUpdate,
-) -> Result<i64, AppError> {
+    class: String,
+    description_preview_chars: Option<usize>,
+) -> Result<Vec<SpellSummary>, AppError> {
     let pool = state.inner().clone();
-    let result = tokio::task::spawn_blocking(move || {
+    let preview_chars = description_preview_chars.unwrap_or(DEFAULT_DESCRIPTION_PREVIEW_CHARS);
+    tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        apply_spell_update_with_conn(&conn, &spell)
+        list_spells_by_class_with_conn(&conn, &class, preview_chars)
     })
     .await
-    .map_err(|e| AppError::Unknown(e.to_string()))??;
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
 
-    Ok(result)
+/// Spells whose `source` equals `source` exactly (ignoring case and surrounding
+/// whitespace), ordered by level, then name. Unlike the search filter this does not
+/// match on substrings, so "PHB" does not pull in "PHB Errata".
+pub(crate) fn get_spells_by_source_with_conn(
+    conn: &Connection,
+    source: &str,
+    preview_chars: usize,
+) -> Result<Vec<SpellSummary>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SPELL_SUMMARY_COLUMNS}, {}
+         FROM spell WHERE TRIM(source) = ?1 COLLATE NOCASE ORDER BY level, name",
+        description_preview_sql("description", preview_chars)
+    ))?;
+    let spells = stmt
+        .query_map([source.trim()], |row| {
+            spell_summary_from_row(row, preview_chars)
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(spells)
 }
 
 #[tauri::command]
-pub async fn delete_spell(state: State<'_, Arc<Pool>>, id: i64) -> Result<(), AppError> {
+pub async fn get_spells_by_source(
+    state: State<'_, Arc<Pool>>,
+    source: String,
+    description_preview_chars: Option<usize>,
+) -> Result<Vec<SpellSummary>, AppError> {
     let pool = state.inner().clone();
+    let preview_chars = description_preview_chars.unwrap_or(DEFAULT_DESCRIPTION_PREVIEW_CHARS);
     tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
-        conn.execute("DELETE FROM spell WHERE id = ?", [id])?;
-        Ok::<(), AppError>(())
+        get_spells_by_source_with_conn(&conn, &source, preview_chars)
     })
     .await
-    .map_err(|e| AppError::Unknown(e.to_string()))??;
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
 
-    Ok(())
+/// Every non-empty source with its spell count, alphabetical. Sources differing only
+/// in case or surrounding whitespace are counted together under the first spelling seen.
+pub(crate) fn list_sources_with_counts_with_conn(
+    conn: &Connection,
+) -> Result<Vec<SourceUsage>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT TRIM(source), COUNT(*) FROM spell
+         WHERE source IS NOT NULL AND TRIM(source) <> ''
+         GROUP BY TRIM(source) COLLATE NOCASE
+         ORDER BY TRIM(source) COLLATE NOCASE",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(SourceUsage {
+            source: row.get(0)?,
+            count: row.get(1)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
 }
 
 #[tauri::command]
-pub async fn upsert_spell(
+pub async fn list_sources_with_counts(
     state: State<'_, Arc<Pool>>,
-    spell: SpellDetail,
-) -> Result<i64, AppError> {
+) -> Result<Vec<SourceUsage>, AppError> {
     let pool = state.inner().clone();
-    let result = tokio::task::spawn_blocking(move || {
-        validate_spell_fields(&spell.name, spell.level, &spell.description)?;
-        validate_epic_and_quest_spells(
-            spell.level,
-            &spell.class_list,
-            spell.is_quest_spell != 0,
-            spell.is_cantrip != 0,
-        )?;
-
+    tokio::task::spawn_blocking(move || {
         let conn = pool.get()?;
+        list_sources_with_counts_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
 
-        let (canonical, hash, json) = canonicalize_spell_detail(spell.clone())?;
+/// Spell counts per level from the lowest level present to the highest, with levels that
+/// have no spells filled in as zero. Empty when the library is.
+pub(crate) fn get_level_histogram_with_conn(
+    conn: &Connection,
+) -> Result<Vec<LevelCount>, AppError> {
+    let mut stmt =
+        conn.prepare("SELECT level, COUNT(*) FROM spell GROUP BY level ORDER BY level")?;
+    let counts = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    let (Some(&min), Some(&max)) = (counts.keys().min(), counts.keys().max()) else {
+        return Ok(Vec::new());
+    };
+    Ok((min..=max)
+        .map(|level| LevelCount {
+            level,
+            count: counts.get(&level).copied().unwrap_or(0),
+        })
+        .collect())
+}
 
-        let spell_id = if let Some(id) = spell.id {
-            let update = spell_detail_to_update(&spell, id);
-            apply_spell_update_with_conn(&conn, &update)?;
-            id
-        } else {
-            run_in_savepoint(&conn, "spell_upsert_insert_write", || {
-                conn.execute(
-                    "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
-                     material_components, casting_time, duration, area, saving_throw, damage,
-                     magic_resistance, reversible, description, tags, source, edition, author,
-                     license, is_quest_spell, is_cantrip, canonical_data, content_hash,
-                     schema_version)
-                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-                    params![
-                        spell.name,
-                        spell.school,
-                        spell.sphere,
-                        spell.class_list,
-                        spell.level,
-                        spell.range,
-                        spell.components,
-                        spell.material_components,
-                        spell.casting_time,
-                        spell.duration,
-                        spell.area,
-                        spell.saving_throw,
-                        spell.damage,
-                        spell.magic_resistance,
-                        spell.reversible.unwrap_or(0),
-                        spell.description,
-                        spell.tags,
-                        spell.source,
-                        spell.edition,
-                        spell.author,
-                        spell.license,
-                        spell.is_quest_spell,
-                        spell.is_cantrip,
-                        json,
-                        hash,
-                        canonical.schema_version,
-                    ],
-                )?;
-                let id = conn.last_insert_rowid();
-                export_spell_to_vault_by_hash(&conn, &hash)?;
-                Ok::<i64, AppError>(id)
-            })?
-        };
-        migration_manager::sync_check_spell(&conn, spell_id);
-        Ok::<i64, AppError>(spell_id)
+#[tauri::command]
+pub async fn get_level_histogram(state: State<'_, Arc<Pool>>) -> Result<Vec<LevelCount>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_level_histogram_with_conn(&conn)
     })
     .await
-    .map_err(|e| AppError::Unknown(e.to_string()))??;
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
 
-    Ok(result)
+pub(crate) fn list_spells_by_schema_version_with_conn(
+    conn: &Connection,
+) -> Result<Vec<SchemaVersionCount>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(schema_version, 0) AS version, COUNT(*) FROM spell
+         GROUP BY version ORDER BY version",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(SchemaVersionCount {
+            version: row.get(0)?,
+            count: row.get(1)?,
+        })
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::commands::vault::VaultTestEnvGuard;
-    use rusqlite::Connection;
+/// Ids of spells stored below `CURRENT_SCHEMA_VERSION` (or with no version at all), the
+/// set a schema migration step needs to re-validate.
+pub(crate) fn list_outdated_schema_spells_with_conn(
+    conn: &Connection,
+) -> Result<Vec<i64>, AppError> {
+    let mut stmt =
+        conn.prepare("SELECT id FROM spell WHERE COALESCE(schema_version, 0) < ? ORDER BY id")?;
+    let rows = stmt.query_map([CURRENT_SCHEMA_VERSION], |row| row.get(0))?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
 
-    fn repeated_text(len: usize) -> String {
-        "x".repeat(len)
-    }
+#[tauri::command]
+pub async fn list_spells_by_schema_version(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<SchemaVersionCount>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        list_spells_by_schema_version_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
 
-    #[test]
-    fn test_get_spell_from_conn_loads_artifact_by_content_hash_when_hash_set() {
-        let conn = setup_get_spell_artifact_test_db();
-        conn.execute(
-            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
-            [],
-        )
-        .expect("insert spell");
+#[tauri::command]
+pub async fn list_outdated_schema_spells(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<i64>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        list_outdated_schema_spells_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn create_spell(
+    state: State<'_, Arc<Pool>>,
+    spell: SpellCreate,
+) -> Result<i64, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        create_spell_with_conn(&conn, spell)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+pub(crate) fn create_spell_with_conn(
+    conn: &Connection,
+    spell: SpellCreate,
+) -> Result<i64, AppError> {
+    let spell = SpellCreate {
+        class_list: normalize_list_column(&spell.class_list),
+        tags: normalize_list_column(&spell.tags),
+        ..spell
+    };
+    validate_spell_fields(&spell.name, spell.level, &spell.description)?;
+    validate_epic_and_quest_spells(
+        spell.level,
+        &spell.class_list,
+        spell.is_quest_spell != 0,
+        spell.is_cantrip != 0,
+    )?;
+
+    let detail = SpellDetail {
+        id: None,
+        name: spell.name.clone(),
+        school: spell.school.clone(),
+        sphere: spell.sphere.clone(),
+        class_list: spell.class_list.clone(),
+        level: spell.level,
+        range: spell.range.clone(),
+        components: spell.components.clone(),
+        material_components: spell.material_components.clone(),
+        casting_time: spell.casting_time.clone(),
+        duration: spell.duration.clone(),
+        area: spell.area.clone(),
+        saving_throw: spell.saving_throw.clone(),
+        damage: spell.damage.clone(),
+        magic_resistance: spell.magic_resistance.clone(),
+        reversible: spell.reversible,
+        reverse_name: spell.reverse_name.clone(),
+        description: spell.description.clone(),
+        tags: spell.tags.clone(),
+        source: spell.source.clone(),
+        edition: spell.edition.clone(),
+        author: spell.author.clone(),
+        license: spell.license.clone(),
+        is_quest_spell: spell.is_quest_spell,
+        is_cantrip: spell.is_cantrip,
+        schema_version: None,
+        artifacts: None,
+        canonical_data: None,
+        content_hash: None,
+        created_at: None,
+        updated_at: None,
+        material_has_cost: false,
+        range_spec: spell.range_spec.clone(),
+        components_spec: spell.components_spec.clone(),
+        material_components_spec: spell.material_components_spec.clone(),
+        casting_time_spec: spell.casting_time_spec.clone(),
+        duration_spec: spell.duration_spec.clone(),
+        area_spec: spell.area_spec.clone(),
+        saving_throw_spec: spell.saving_throw_spec.clone(),
+        damage_spec: spell.damage_spec.clone(),
+        magic_resistance_spec: spell.magic_resistance_spec.clone(),
+    };
+    let (canonical, hash, json) = canonicalize_spell_detail(detail)?;
+
+    run_in_savepoint(conn, "spell_create_write", || {
+        conn.execute(
+            "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
+             material_components, casting_time, duration, area, saving_throw, damage,
+             magic_resistance, reversible, description, tags, source, edition, author,
+             license, is_quest_spell, is_cantrip, canonical_data, content_hash,
+             schema_version)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                spell.name,
+                spell.school,
+                spell.sphere,
+                spell.class_list,
+                spell.level,
+                spell.range,
+                spell.components,
+                spell.material_components,
+                spell.casting_time,
+                spell.duration,
+                spell.area,
+                spell.saving_throw,
+                spell.damage,
+                spell.magic_resistance,
+                spell.reversible.unwrap_or(0),
+                spell.description,
+                spell.tags,
+                spell.source,
+                spell.edition,
+                spell.author,
+                spell.license,
+                spell.is_quest_spell,
+                spell.is_cantrip,
+                json,
+                hash,
+                canonical.schema_version,
+            ],
+        )?;
+        let spell_id = conn.last_insert_rowid();
+        set_spell_reverse_name_with_conn(conn, spell_id, spell.reverse_name.as_deref())?;
+        export_spell_to_vault_by_hash(conn, &hash)?;
+        Ok::<i64, AppError>(spell_id)
+    })
+}
+
+#[tauri::command]
+pub async fn update_spell(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    spell: SpellUpdate,
+) -> Result<i64, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        apply_spell_update_with_conn(&conn, &spell)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn delete_spell(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    id: i64,
+) -> Result<(), AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        conn.execute("DELETE FROM spell WHERE id = ?", [id])?;
+        Ok::<(), AppError>(())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(())
+}
+
+/// Adds (or with `remove`, strips) `tags` on every spell matching the search, in one
+/// savepoint. Tags compare case-insensitively; spells whose tags would not change are not
+/// rewritten. Returns the number of spells updated.
+pub(crate) fn retag_spells_by_filter_with_conn(
+    conn: &Connection,
+    query: &str,
+    filters: Option<SearchFilters>,
+    tags: &[String],
+    remove: bool,
+) -> Result<usize, AppError> {
+    let tags: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    if tags.is_empty() {
+        return Err(AppError::Validation("At least one tag is required".into()));
+    }
+    let ids = search_result_ids_capped(
+        conn,
+        query,
+        filters,
+        if remove { "untagging" } else { "tagging" },
+    )?;
+
+    run_in_savepoint(conn, "spell_bulk_tag", || {
+        let mut affected = 0;
+        for id in ids {
+            let Some(spell) = get_spell_from_conn(conn, id)? else {
+                continue;
+            };
+            let current = spell
+                .tags
+                .as_deref()
+                .map(parse_list_column)
+                .unwrap_or_default();
+            let has_tag = |list: &[String], tag: &str| {
+                list.iter()
+                    .any(|existing| existing.eq_ignore_ascii_case(tag))
+            };
+            let next: Vec<String> = if remove {
+                current
+                    .iter()
+                    .filter(|existing| !has_tag(&tags, existing))
+                    .cloned()
+                    .collect()
+            } else {
+                let mut next = current.clone();
+                for tag in &tags {
+                    if !has_tag(&next, tag) {
+                        next.push(tag.clone());
+                    }
+                }
+                next
+            };
+            if next.len() == current.len() {
+                continue;
+            }
+
+            let mut update = spell_detail_to_update(&spell, id);
+            update.tags = normalize_list_column(&serde_json::to_string(&next).ok());
+            apply_spell_update_with_conn(conn, &update)?;
+            affected += 1;
+        }
+        Ok(affected)
+    })
+}
+
+#[tauri::command]
+pub async fn tag_spells_by_filter(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    query: String,
+    filters: Option<SearchFilters>,
+    tags: Vec<String>,
+) -> Result<usize, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        retag_spells_by_filter_with_conn(&conn, &query, filters, &tags, false)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn untag_spells_by_filter(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    query: String,
+    filters: Option<SearchFilters>,
+    tags: Vec<String>,
+) -> Result<usize, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        retag_spells_by_filter_with_conn(&conn, &query, filters, &tags, true)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Folds tags that differ only by case into one display form across the library and
+/// rewrites every spell's `tags` as a JSON array. An entry in `preferred` fixes the display
+/// casing for its tag; otherwise the first spelling seen (by spell id) wins. Updates go
+/// through `apply_spell_update_with_conn`, so each rewrite is logged in `change_log`.
+pub(crate) fn normalize_all_tags_with_conn(
+    conn: &Connection,
+    preferred: &[String],
+) -> Result<TagNormalizationSummary, AppError> {
+    let mut stmt = conn.prepare("SELECT id, tags FROM spell WHERE tags IS NOT NULL ORDER BY id")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut display: HashMap<String, String> = HashMap::new();
+    for tag in preferred
+        .iter()
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+    {
+        display
+            .entry(tag.to_lowercase())
+            .or_insert_with(|| tag.to_string());
+    }
+    let mut spellings: HashSet<String> = HashSet::new();
+    for (_, tags) in &rows {
+        for tag in parse_list_column(tags) {
+            display
+                .entry(tag.to_lowercase())
+                .or_insert_with(|| tag.clone());
+            spellings.insert(tag);
+        }
+    }
+    let variants_collapsed = spellings
+        .iter()
+        .filter(|tag| display.get(&tag.to_lowercase()) != Some(*tag))
+        .count();
+
+    run_in_savepoint(conn, "spell_normalize_tags", || {
+        let mut spells_updated = 0;
+        for (id, tags) in &rows {
+            let next: Vec<String> = parse_list_column(tags)
+                .iter()
+                .map(|tag| display[&tag.to_lowercase()].clone())
+                .collect();
+            let next = normalize_list_column(&serde_json::to_string(&next).ok());
+            if next.as_deref() == Some(tags.as_str()) {
+                continue;
+            }
+            let Some(spell) = get_spell_from_conn(conn, *id)? else {
+                continue;
+            };
+            let mut update = spell_detail_to_update(&spell, *id);
+            update.tags = next;
+            apply_spell_update_with_conn(conn, &update)?;
+            spells_updated += 1;
+        }
+        Ok(TagNormalizationSummary {
+            spells_updated,
+            variants_collapsed,
+        })
+    })
+}
+
+#[tauri::command]
+pub async fn normalize_all_tags(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    preferred: Option<Vec<String>>,
+) -> Result<TagNormalizationSummary, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        normalize_all_tags_with_conn(&conn, &preferred.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Inserts `spell` when it has no `id`, otherwise updates that row in place.
+/// Local-only flags (`verified`, `locked`) are never written here.
+pub(crate) fn upsert_spell_with_conn(
+    conn: &Connection,
+    spell: SpellDetail,
+) -> Result<i64, AppError> {
+    let spell = SpellDetail {
+        class_list: normalize_list_column(&spell.class_list),
+        tags: normalize_list_column(&spell.tags),
+        ..spell
+    };
+    validate_spell_fields(&spell.name, spell.level, &spell.description)?;
+    validate_epic_and_quest_spells(
+        spell.level,
+        &spell.class_list,
+        spell.is_quest_spell != 0,
+        spell.is_cantrip != 0,
+    )?;
+
+    let (canonical, hash, json) = canonicalize_spell_detail(spell.clone())?;
+
+    let spell_id = if let Some(id) = spell.id {
+        let update = spell_detail_to_update(&spell, id);
+        apply_spell_update_with_conn(conn, &update)?;
+        id
+    } else {
+        run_in_savepoint(conn, "spell_upsert_insert_write", || {
+            conn.execute(
+                "INSERT INTO spell (name, school, sphere, class_list, level, range, components,
+                 material_components, casting_time, duration, area, saving_throw, damage,
+                 magic_resistance, reversible, description, tags, source, edition, author,
+                 license, is_quest_spell, is_cantrip, canonical_data, content_hash,
+                 schema_version)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    spell.name,
+                    spell.school,
+                    spell.sphere,
+                    spell.class_list,
+                    spell.level,
+                    spell.range,
+                    spell.components,
+                    spell.material_components,
+                    spell.casting_time,
+                    spell.duration,
+                    spell.area,
+                    spell.saving_throw,
+                    spell.damage,
+                    spell.magic_resistance,
+                    spell.reversible.unwrap_or(0),
+                    spell.description,
+                    spell.tags,
+                    spell.source,
+                    spell.edition,
+                    spell.author,
+                    spell.license,
+                    spell.is_quest_spell,
+                    spell.is_cantrip,
+                    json,
+                    hash,
+                    canonical.schema_version,
+                ],
+            )?;
+            let id = conn.last_insert_rowid();
+            set_spell_reverse_name_with_conn(conn, id, spell.reverse_name.as_deref())?;
+            export_spell_to_vault_by_hash(conn, &hash)?;
+            Ok::<i64, AppError>(id)
+        })?
+    };
+    Ok(spell_id)
+}
+
+/// Upserts `spell` keyed on its sync `logical_id`: a local row with that id is updated
+/// in place (keeping its row id and local-only flags), otherwise a new row is inserted
+/// and stamped with `logical_id`. Any `spell.id` supplied by the caller is ignored. A
+/// locked match is left alone unless `force` is set; that returns `None` and records the
+/// spell in `skipped`/`warnings`.
+pub(crate) fn upsert_by_logical_id_with_conn(
+    conn: &Connection,
+    spell: SpellDetail,
+    logical_id: &str,
+    force: bool,
+    skipped: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) -> Result<Option<i64>, AppError> {
+    let logical_id = logical_id.trim();
+    if logical_id.is_empty() {
+        return Err(AppError::Validation(
+            "logical_id must not be empty".to_string(),
+        ));
+    }
+    run_in_savepoint(conn, "spell_upsert_logical_id", || {
+        let existing: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM spell WHERE logical_id = ?",
+                [logical_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(id) = existing {
+            if skip_locked_overwrite(conn, id, &spell.name, force, skipped, warnings)? {
+                return Ok(None);
+            }
+        }
+        let spell_id = upsert_spell_with_conn(
+            conn,
+            SpellDetail {
+                id: existing,
+                ..spell
+            },
+        )?;
+        if existing.is_none() {
+            conn.execute(
+                "UPDATE spell SET logical_id = ? WHERE id = ?",
+                params![logical_id, spell_id],
+            )?;
+        }
+        Ok(Some(spell_id))
+    })
+}
+
+#[tauri::command]
+pub async fn upsert_spell(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    spell: SpellDetail,
+) -> Result<i64, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let spell_id = upsert_spell_with_conn(&conn, spell)?;
+        migration_manager::sync_check_spell(&conn, spell_id);
+        Ok::<i64, AppError>(spell_id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn upsert_by_logical_id(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    spell: SpellDetail,
+    logical_id: String,
+    force: Option<bool>,
+) -> Result<i64, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let mut skipped = Vec::new();
+        let mut warnings = Vec::new();
+        let spell_id = upsert_by_logical_id_with_conn(
+            &conn,
+            spell,
+            &logical_id,
+            force.unwrap_or(false),
+            &mut skipped,
+            &mut warnings,
+        )?
+        .ok_or_else(|| AppError::Validation(warnings.join(" ")))?;
+        migration_manager::sync_check_spell(&conn, spell_id);
+        Ok::<i64, AppError>(spell_id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+/// Rewrites legacy comma-separated `class_list`/`tags` rows into the canonical JSON-array
+/// form. Only the flat columns change; `canonical_data` and `content_hash` are untouched.
+/// Returns the number of rows rewritten.
+pub(crate) fn normalize_spell_list_columns_with_conn(conn: &Connection) -> Result<usize, AppError> {
+    run_in_savepoint(conn, "spell_list_normalize", || {
+        let rows: Vec<(i64, Option<String>, Option<String>)> = {
+            let mut stmt = conn.prepare("SELECT id, class_list, tags FROM spell")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut updated = 0;
+        for (id, class_list, tags) in rows {
+            let new_class_list = normalize_list_column(&class_list);
+            let new_tags = normalize_list_column(&tags);
+            if new_class_list != class_list || new_tags != tags {
+                conn.execute(
+                    "UPDATE spell SET class_list = ?, tags = ? WHERE id = ?",
+                    params![new_class_list, new_tags, id],
+                )?;
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    })
+}
+
+#[tauri::command]
+pub async fn normalize_spell_list_columns(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+) -> Result<usize, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        normalize_spell_list_columns_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+/// Upper-cases single-letter component codes ("v, s, m" -> "V, S, M"), leaving any
+/// longer entry as written.
+fn normalize_components_casing(components: &str) -> String {
+    components
+        .split(',')
+        .map(|part| {
+            let part = part.trim();
+            if part.chars().count() == 1 {
+                part.to_uppercase()
+            } else {
+                part.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The flat columns scanned by the data-quality pass.
+struct QualityRow {
+    id: i64,
+    name: String,
+    school: Option<String>,
+    components: Option<String>,
+    class_list: Option<String>,
+    tags: Option<String>,
+}
+
+/// `(field, old, new, issue)` for each normalization `row` needs.
+fn data_quality_fixes(row: &QualityRow) -> Vec<(&'static str, String, String, String)> {
+    let mut fixes = Vec::new();
+    let trimmed = row.name.trim();
+    if trimmed != row.name {
+        fixes.push((
+            "name",
+            row.name.clone(),
+            trimmed.to_string(),
+            "name has surrounding whitespace".to_string(),
+        ));
+    }
+    // Casing warnings compare against the whitespace-normalized value, so stray spacing
+    // alone is not reported as a case problem.
+    if let Some(school) = row.school.as_deref().filter(|s| !s.trim().is_empty()) {
+        let spaced = normalize_string(school, NormalizationMode::Structured);
+        let cased = match_schema_case(school);
+        if cased != spaced && cased.to_lowercase() == spaced.to_lowercase() {
+            fixes.push((
+                "school",
+                school.to_string(),
+                cased,
+                "school not title-case".to_string(),
+            ));
+        }
+    }
+    if let Some(components) = row.components.as_deref().filter(|s| !s.trim().is_empty()) {
+        let spaced = components
+            .split(',')
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let cased = normalize_components_casing(components);
+        if cased != spaced {
+            fixes.push((
+                "components",
+                components.to_string(),
+                cased,
+                "components not in canonical casing".to_string(),
+            ));
+        }
+    }
+    for (field, value) in [("class_list", &row.class_list), ("tags", &row.tags)] {
+        let normalized = normalize_list_column(value);
+        if normalized != *value {
+            fixes.push((
+                field,
+                value.clone().unwrap_or_default(),
+                normalized.unwrap_or_default(),
+                format!("{} not JSON array", field),
+            ));
+        }
+    }
+    fixes
+}
+
+fn load_quality_rows(conn: &Connection, ids: Option<&[i64]>) -> Result<Vec<QualityRow>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, school, components, class_list, tags FROM spell ORDER BY id")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(QualityRow {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            school: row.get(2)?,
+            components: row.get(3)?,
+            class_list: row.get(4)?,
+            tags: row.get(5)?,
+        })
+    })?;
+    let wanted: Option<HashSet<i64>> = ids.map(|ids| ids.iter().copied().collect());
+    let mut out = Vec::new();
+    for row in rows {
+        let row = row?;
+        if wanted.as_ref().is_none_or(|set| set.contains(&row.id)) {
+            out.push(row);
+        }
+    }
+    Ok(out)
+}
+
+/// Lists spells whose stored flat columns need normalizing. Read-only.
+pub(crate) fn scan_data_quality_with_conn(
+    conn: &Connection,
+) -> Result<Vec<DataQualityReport>, AppError> {
+    Ok(load_quality_rows(conn, None)?
+        .iter()
+        .filter_map(|row| {
+            let issues: Vec<String> = data_quality_fixes(row)
+                .into_iter()
+                .map(|(_, _, _, issue)| issue)
+                .collect();
+            (!issues.is_empty()).then_some(DataQualityReport { id: row.id, issues })
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn scan_data_quality(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<DataQualityReport>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        scan_data_quality_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Lists ids of spells whose `reverse_name` is broken: it names the spell itself, or it
+/// names another spell whose own `reverse_name` does not point back. Names are matched
+/// trimmed and case-insensitively; a reverse that names no stored spell is not flagged.
+pub(crate) fn validate_reverse_links_with_conn(conn: &Connection) -> Result<Vec<i64>, AppError> {
+    let mut stmt = conn.prepare("SELECT id, name, reverse_name FROM spell ORDER BY id")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let normalize = |name: &str| name.trim().to_lowercase();
+    let mut reverses_by_name: HashMap<String, Vec<Option<String>>> = HashMap::new();
+    for (_, name, reverse_name) in &rows {
+        reverses_by_name
+            .entry(normalize(name))
+            .or_default()
+            .push(reverse_name.as_deref().map(normalize));
+    }
+
+    Ok(rows
+        .iter()
+        .filter_map(|(id, name, reverse_name)| {
+            let own = normalize(name);
+            let target = normalize(reverse_name.as_deref()?);
+            if target.is_empty() {
+                return None;
+            }
+            if target == own {
+                return Some(*id);
+            }
+            let target_reverses = reverses_by_name.get(&target)?;
+            let reciprocated = target_reverses
+                .iter()
+                .any(|back| back.as_deref() == Some(own.as_str()));
+            (!reciprocated).then_some(*id)
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn validate_reverse_links(state: State<'_, Arc<Pool>>) -> Result<Vec<i64>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        validate_reverse_links_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Validates every stored spell against the canonical schema. Spells with `canonical_data`
+/// are checked as stored; older rows are canonicalized from their flat columns first.
+pub(crate) fn validate_all_spells_with_conn(
+    conn: &Connection,
+) -> Result<Vec<SpellValidationResult>, AppError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SPELL_DETAIL_COLUMNS} FROM spell ORDER BY id"
+    ))?;
+    let spells = stmt
+        .query_map([], spell_detail_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(spells
+        .into_iter()
+        .map(|spell| {
+            let canonical = match spell.canonical_data.as_deref() {
+                Some(data) => serde_json::from_str::<CanonicalSpell>(data)
+                    .map_err(|e| format!("canonical_data is not valid JSON: {e}")),
+                None => CanonicalSpell::try_from(spell.clone()),
+            };
+            let error = canonical
+                .and_then(|canonical| canonical.validate())
+                .err()
+                .map(|e| e.trim_end().to_string());
+            SpellValidationResult {
+                id: spell.id.unwrap_or_default(),
+                name: spell.name,
+                valid: error.is_none(),
+                error,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn validate_all_spells(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<SpellValidationResult>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        validate_all_spells_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Applies the [`scan_data_quality_with_conn`] normalizations to `ids`, logging each
+/// changed field. As with [`normalize_spell_list_columns_with_conn`], only the flat
+/// columns change. Returns the number of spells rewritten.
+pub(crate) fn fix_data_quality_with_conn(
+    conn: &Connection,
+    ids: &[i64],
+) -> Result<usize, AppError> {
+    run_in_savepoint(conn, "spell_data_quality_fix", || {
+        let mut updated = 0;
+        for row in load_quality_rows(conn, Some(ids))? {
+            let fixes = data_quality_fixes(&row);
+            if fixes.is_empty() {
+                continue;
+            }
+            let mut changes = Vec::new();
+            for (field, old_val, new_val, _) in fixes {
+                // An empty normalized list is stored as NULL, matching normalize_list_column.
+                let stored = (!new_val.is_empty()).then_some(new_val.as_str());
+                conn.execute(
+                    &format!("UPDATE spell SET {} = ? WHERE id = ?", field),
+                    params![stored, row.id],
+                )?;
+                changes.push((field.to_string(), old_val, new_val));
+            }
+            log_changes(conn, row.id, changes, USER_CHANGE_LOG_ACTOR)?;
+            updated += 1;
+        }
+        Ok(updated)
+    })
+}
+
+#[tauri::command]
+pub async fn fix_data_quality(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+    ids: Vec<i64>,
+) -> Result<usize, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        fix_data_quality_with_conn(&conn, &ids)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Groups spells by `content_hash` (NULL hashes ignored), keeping only groups with more
+/// than one member. The partial unique index `idx_spell_content_hash` prevents new
+/// duplicates, so in practice this surfaces rows from databases where that index is absent.
+pub(crate) fn find_duplicate_spells_with_conn(
+    conn: &Connection,
+) -> Result<Vec<DuplicateSpellGroup>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, school, sphere, level, class_list, components, duration, source,
+                is_quest_spell, is_cantrip, tags, content_hash
+         FROM spell
+         WHERE content_hash IN (
+             SELECT content_hash FROM spell
+             WHERE content_hash IS NOT NULL
+             GROUP BY content_hash HAVING COUNT(*) > 1
+         )
+         ORDER BY content_hash ASC, id ASC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(12)?,
+            SpellSummary {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                school: row.get(2)?,
+                sphere: row.get(3)?,
+                level: row.get(4)?,
+                class_list: row.get(5)?,
+                components: row.get(6)?,
+                duration: row.get(7)?,
+                source: row.get(8)?,
+                is_quest_spell: row.get(9)?,
+                is_cantrip: row.get(10)?,
+                tags: row.get(11)?,
+                description_preview: None,
+            },
+        ))
+    })?;
+
+    let mut groups: Vec<DuplicateSpellGroup> = vec![];
+    for row in rows {
+        let (hash, spell) = row?;
+        match groups.last_mut() {
+            Some(group) if group.hash == hash => group.spells.push(spell),
+            _ => groups.push(DuplicateSpellGroup {
+                hash,
+                spells: vec![spell],
+            }),
+        }
+    }
+    Ok(groups)
+}
+
+#[tauri::command]
+pub async fn find_duplicate_spells(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<DuplicateSpellGroup>, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        find_duplicate_spells_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+/// Spells whose stored `content_hash` disagrees with the hash recomputed from their current
+/// columns (as a save would canonicalize them), as `(id, canonical, recomputed hash,
+/// canonical JSON)` in id order. Rows without a stored hash are left to the hash backfill,
+/// and rows that no longer canonicalize are skipped.
+fn content_hash_mismatches(
+    conn: &Connection,
+) -> Result<Vec<(i64, CanonicalSpell, String, String)>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT id, content_hash FROM spell WHERE content_hash IS NOT NULL ORDER BY id")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut mismatches = Vec::new();
+    for (id, stored_hash) in rows {
+        let Some(detail) = get_spell_from_conn(conn, id)? else {
+            continue;
+        };
+        let Ok((canonical, hash, json)) = canonicalize_spell_detail(detail) else {
+            continue;
+        };
+        if hash != stored_hash {
+            mismatches.push((id, canonical, hash, json));
+        }
+    }
+    Ok(mismatches)
+}
+
+/// Ids of spells whose stored `content_hash` has drifted from their canonical content.
+/// Read-only; see `repair_content_hashes_with_conn` to fix them.
+pub(crate) fn verify_content_hashes_with_conn(conn: &Connection) -> Result<Vec<i64>, AppError> {
+    Ok(content_hash_mismatches(conn)?
+        .into_iter()
+        .map(|(id, _, _, _)| id)
+        .collect())
+}
+
+#[tauri::command]
+pub async fn verify_content_hashes(state: State<'_, Arc<Pool>>) -> Result<Vec<i64>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        verify_content_hashes_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Rewrites every drifted `content_hash` and `canonical_data` to the recomputed values,
+/// cascading hash references and refreshing the vault file. A spell whose correct hash
+/// already belongs to another row is a duplicate, not drift: it is reported in `skipped`
+/// and left for `find_duplicate_spells`.
+pub(crate) fn repair_content_hashes_with_conn(
+    conn: &Connection,
+) -> Result<ContentHashRepairResult, AppError> {
+    run_in_savepoint(conn, "spell_repair_content_hashes", || {
+        let mut result = ContentHashRepairResult::default();
+        for (id, _, hash, json) in content_hash_mismatches(conn)? {
+            let holder: Option<i64> = conn
+                .query_row(
+                    "SELECT id FROM spell WHERE content_hash = ? AND id != ?",
+                    params![hash, id],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if let Some(holder) = holder {
+                result.skipped.push(id);
+                result.warnings.push(format!(
+                    "Spell {} not repaired: its content hash already belongs to spell {}.",
+                    id, holder
+                ));
+                continue;
+            }
+            let old_hash: Option<String> =
+                conn.query_row("SELECT content_hash FROM spell WHERE id = ?", [id], |row| {
+                    row.get(0)
+                })?;
+            conn.execute(
+                "UPDATE spell SET canonical_data = ?, content_hash = ? WHERE id = ?",
+                params![json, hash, id],
+            )?;
+            cascade_spell_content_hash_refs(conn, old_hash.as_deref(), &hash)?;
+            export_spell_to_vault_by_hash(conn, &hash)?;
+            result.repaired.push(id);
+        }
+        Ok(result)
+    })
+}
+
+#[tauri::command]
+pub async fn repair_content_hashes(
+    state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<SpellCache>>,
+) -> Result<ContentHashRepairResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        repair_content_hashes_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Mechanical fields whose canonical spec fell back to the `special` or `unknown` kind, i.e.
+/// the parser could not structure the legacy text. An unknown magic resistance whose source
+/// is absent or a "no MR" sentinel ("0", "None") is not a fallback.
+pub(crate) fn fallback_mechanic_fields(spell: &CanonicalSpell) -> Vec<String> {
+    let mut fields = vec![];
+    if spell
+        .range
+        .as_ref()
+        .is_some_and(|r| r.kind == RangeKind::Special)
+    {
+        fields.push("range".to_string());
+    }
+    if spell
+        .casting_time
+        .as_ref()
+        .is_some_and(|ct| ct.unit == CastingTimeUnit::Special)
+    {
+        fields.push("casting_time".to_string());
+    }
+    if spell
+        .duration
+        .as_ref()
+        .is_some_and(|d| d.kind == DurationKind::Special)
+    {
+        fields.push("duration".to_string());
+    }
+    if spell
+        .area
+        .as_ref()
+        .is_some_and(|a| a.kind == AreaKind::Special)
+    {
+        fields.push("area".to_string());
+    }
+    if spell.magic_resistance.as_ref().is_some_and(|mr| {
+        mr.kind == MagicResistanceKind::Unknown
+            && mr.source_text.as_deref().is_some_and(|text| {
+                let text = text.trim();
+                !text.is_empty() && text != "0" && !text.eq_ignore_ascii_case("none")
+            })
+    }) {
+        fields.push("magic_resistance".to_string());
+    }
+    fields
+}
+
+/// Sets or clears `needs_review` for an imported spell based on its parse fallbacks.
+pub(crate) fn flag_needs_review_with_conn(
+    conn: &Connection,
+    spell_id: i64,
+    spell: &CanonicalSpell,
+) -> Result<bool, AppError> {
+    let needs_review = !fallback_mechanic_fields(spell).is_empty();
+    conn.execute(
+        "UPDATE spell SET needs_review = ? WHERE id = ?",
+        params![needs_review as i64, spell_id],
+    )?;
+    Ok(needs_review)
+}
+
+pub(crate) fn list_needs_review_with_conn(
+    conn: &Connection,
+) -> Result<Vec<SpellReviewItem>, AppError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, level, source, canonical_data FROM spell
+         WHERE needs_review = 1 ORDER BY name ASC, level ASC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, Option<String>>(3)?,
+            row.get::<_, Option<String>>(4)?,
+        ))
+    })?;
+
+    let mut items = vec![];
+    for row in rows {
+        let (id, name, level, source, canonical_data) = row?;
+        let fallback_fields = canonical_data
+            .as_deref()
+            .and_then(|data| serde_json::from_str::<CanonicalSpell>(data).ok())
+            .map(|spell| fallback_mechanic_fields(&spell))
+            .unwrap_or_default();
+        items.push(SpellReviewItem {
+            id,
+            name,
+            level,
+            source,
+            fallback_fields,
+        });
+    }
+    Ok(items)
+}
+
+#[tauri::command]
+pub async fn list_needs_review(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<SpellReviewItem>, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        list_needs_review_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+pub(crate) fn clear_review_flag_with_conn(conn: &Connection, id: i64) -> Result<(), AppError> {
+    let updated = conn.execute("UPDATE spell SET needs_review = 0 WHERE id = ?", [id])?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Spell {} not found", id)));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn clear_review_flag(state: State<'_, Arc<Pool>>, id: i64) -> Result<(), AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        clear_review_flag_with_conn(&conn, id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(())
+}
+
+/// Sets or clears the GM `verified` flag. Spell edits never touch this column, so the
+/// flag survives [`apply_spell_update_with_conn`].
+pub(crate) fn set_spell_verified_with_conn(
+    conn: &Connection,
+    id: i64,
+    verified: bool,
+) -> Result<(), AppError> {
+    let updated = conn.execute(
+        "UPDATE spell SET verified = ? WHERE id = ?",
+        params![verified as i64, id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Spell {} not found", id)));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_spell_verified(
+    state: State<'_, Arc<Pool>>,
+    id: i64,
+    verified: bool,
+) -> Result<(), AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        set_spell_verified_with_conn(&conn, id, verified)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Sets or clears the `locked` flag. Like `verified`, spell edits never touch it; a locked
+/// spell is skipped by reparse and overwrite imports unless they are forced.
+pub(crate) fn set_spell_locked_with_conn(
+    conn: &Connection,
+    id: i64,
+    locked: bool,
+) -> Result<(), AppError> {
+    let updated = conn.execute(
+        "UPDATE spell SET locked = ? WHERE id = ?",
+        params![locked as i64, id],
+    )?;
+    if updated == 0 {
+        return Err(AppError::NotFound(format!("Spell {} not found", id)));
+    }
+    Ok(())
+}
+
+/// Whether spell `id` is locked. False for unknown ids and for schemas without the column.
+pub(crate) fn is_spell_locked_with_conn(conn: &Connection, id: i64) -> Result<bool, AppError> {
+    if !crate::db::table_has_column(conn, "spell", "locked") {
+        return Ok(false);
+    }
+    let locked: Option<i64> = conn
+        .query_row("SELECT locked FROM spell WHERE id = ?", [id], |row| {
+            row.get(0)
+        })
+        .optional()?;
+    Ok(locked.unwrap_or(0) != 0)
+}
+
+/// Skips an overwrite of locked spell `id` unless `force` is set, recording the spell in
+/// `skipped` and `warnings`. Returns whether the caller should skip it.
+pub(crate) fn skip_locked_overwrite(
+    conn: &Connection,
+    id: i64,
+    name: &str,
+    force: bool,
+    skipped: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) -> Result<bool, AppError> {
+    if force || !is_spell_locked_with_conn(conn, id)? {
+        return Ok(false);
+    }
+    skipped.push(name.to_string());
+    warnings.push(format!(
+        "Skipped locked spell '{}'; unlock it or import with force to overwrite.",
+        name
+    ));
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn set_spell_locked(
+    state: State<'_, Arc<Pool>>,
+    id: i64,
+    locked: bool,
+) -> Result<(), AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        set_spell_locked_with_conn(&conn, id, locked)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Marks every spell in `ids` verified in one transaction; any unknown id rolls the
+/// whole batch back. Returns the number of spells updated.
+pub(crate) fn bulk_verify_with_conn(conn: &mut Connection, ids: &[i64]) -> Result<usize, AppError> {
+    let tx = conn.transaction()?;
+    let mut updated = 0;
+    for &id in ids {
+        set_spell_verified_with_conn(&tx, id, true)?;
+        updated += 1;
+    }
+    tx.commit()?;
+    Ok(updated)
+}
+
+/// Atomic: either every id is reported verified or, after a rollback, every id failed.
+#[tauri::command]
+pub async fn bulk_verify(
+    state: State<'_, Arc<Pool>>,
+    ids: Vec<i64>,
+) -> Result<BatchResult<i64>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = pool.get()?;
+        let outcome = bulk_verify_with_conn(&mut conn, &ids).map(|_| ids.clone());
+        Ok(BatchResult::all_or_nothing(&ids, outcome))
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Summaries of spells not yet verified, ordered by name.
+pub(crate) fn list_unverified_with_conn(
+    conn: &Connection,
+    preview_chars: usize,
+) -> Result<Vec<SpellSummary>, AppError> {
+    let mut stmt = conn.prepare("SELECT id FROM spell WHERE verified = 0")?;
+    let unverified = stmt
+        .query_map([], |row| row.get::<_, i64>(0))?
+        .collect::<Result<HashSet<i64>, _>>()?;
+    Ok(list_spell_summaries_with_conn(conn, preview_chars)?
+        .into_iter()
+        .filter(|spell| unverified.contains(&spell.id))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn list_unverified(
+    state: State<'_, Arc<Pool>>,
+    description_preview_chars: Option<usize>,
+) -> Result<Vec<SpellSummary>, AppError> {
+    let pool = state.inner().clone();
+    let preview_chars = description_preview_chars.unwrap_or(DEFAULT_DESCRIPTION_PREVIEW_CHARS);
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        list_unverified_with_conn(&conn, preview_chars)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// SpellDetail keys that identify a concrete spell row and never belong in a template.
+const TEMPLATE_EXCLUDED_KEYS: &[&str] = &[
+    "id",
+    "artifacts",
+    "canonicalData",
+    "contentHash",
+    "schemaVersion",
+    "createdAt",
+    "updatedAt",
+    "materialHasCost",
+];
+
+/// Reduces a spell to the fields it actually defines: nulls, empty strings and
+/// row identity are dropped so instantiation leaves them empty.
+fn spell_template_partial(spell: &SpellDetail) -> Result<Value, AppError> {
+    let Value::Object(fields) =
+        serde_json::to_value(spell).map_err(|e| AppError::Unknown(e.to_string()))?
+    else {
+        return Err(AppError::Unknown(
+            "spell did not serialize to an object".into(),
+        ));
+    };
+    let partial = fields
+        .into_iter()
+        .filter(|(key, value)| {
+            !TEMPLATE_EXCLUDED_KEYS.contains(&key.as_str())
+                && !value.is_null()
+                && !matches!(value, Value::String(s) if s.trim().is_empty())
+        })
+        .collect();
+    Ok(Value::Object(partial))
+}
+
+pub(crate) fn save_spell_template_with_conn(
+    conn: &Connection,
+    name: &str,
+    spell: &SpellDetail,
+) -> Result<i64, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Template name cannot be empty".into()));
+    }
+    let partial = spell_template_partial(spell)?;
+    conn.execute(
+        "INSERT INTO spell_template (name, partial_json) VALUES (?, ?)",
+        params![name, partial.to_string()],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub(crate) fn list_spell_templates_with_conn(
+    conn: &Connection,
+) -> Result<Vec<SpellTemplate>, AppError> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, partial_json FROM spell_template ORDER BY name ASC, id ASC")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+
+    let mut templates = vec![];
+    for row in rows {
+        let (id, name, partial_json) = row?;
+        let partial_json = serde_json::from_str(&partial_json).map_err(|e| {
+            AppError::Validation(format!("Template {} has invalid JSON: {}", id, e))
+        })?;
+        templates.push(SpellTemplate {
+            id,
+            name,
+            partial_json,
+        });
+    }
+    Ok(templates)
+}
+
+/// Layers the template's stored fields and then `overrides` (camelCase SpellCreate keys)
+/// over an empty spell and inserts the result through the normal create path.
+pub(crate) fn create_spell_from_template_with_conn(
+    conn: &Connection,
+    template_id: i64,
+    overrides: Option<Value>,
+) -> Result<i64, AppError> {
+    let partial_json: String = conn
+        .query_row(
+            "SELECT partial_json FROM spell_template WHERE id = ?",
+            [template_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Spell template {} not found", template_id)))?;
+    let partial: Value = serde_json::from_str(&partial_json).map_err(|e| {
+        AppError::Validation(format!("Template {} has invalid JSON: {}", template_id, e))
+    })?;
+
+    let mut merged = serde_json::to_value(SpellCreate::default())
+        .map_err(|e| AppError::Unknown(e.to_string()))?;
+    for layer in [Some(partial), overrides].into_iter().flatten() {
+        match layer {
+            Value::Object(fields) => {
+                if let Value::Object(target) = &mut merged {
+                    target.extend(fields);
+                }
+            }
+            Value::Null => {}
+            _ => {
+                return Err(AppError::Validation(
+                    "Template fields and overrides must be JSON objects".into(),
+                ))
+            }
+        }
+    }
+
+    let spell: SpellCreate = serde_json::from_value(merged)
+        .map_err(|e| AppError::Validation(format!("Invalid template override: {}", e)))?;
+    create_spell_with_conn(conn, spell)
+}
+
+#[tauri::command]
+pub async fn list_spell_templates(
+    state: State<'_, Arc<Pool>>,
+) -> Result<Vec<SpellTemplate>, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        list_spell_templates_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn save_spell_template(
+    state: State<'_, Arc<Pool>>,
+    name: String,
+    spell: SpellDetail,
+) -> Result<i64, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        save_spell_template_with_conn(&conn, &name, &spell)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn create_spell_from_template(
+    state: State<'_, Arc<Pool>>,
+    template_id: i64,
+    overrides: Option<Value>,
+) -> Result<i64, AppError> {
+    let pool = state.inner().clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        create_spell_from_template_with_conn(&conn, template_id, overrides)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))??;
+
+    Ok(result)
+}
+
+const MAX_FIELD_SUGGESTIONS: usize = 5;
+
+/// Case-insensitive Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Checks a single field value against the schema enum for `field`.
+///
+/// Fields without an enum always validate. For enum fields, the value must match
+/// exactly; otherwise the closest allowed values are returned as suggestions,
+/// case-insensitive matches first.
+pub(crate) fn validate_spell_field_value(
+    field: &str,
+    value: &str,
+) -> Result<FieldValidation, AppError> {
+    let allowed = schema_enum_for_field(field)
+        .ok_or_else(|| AppError::Validation(format!("Unknown spell field: {field}")))?;
+    let Some(allowed) = allowed else {
+        return Ok(FieldValidation {
+            valid: true,
+            suggestions: Vec::new(),
+        });
+    };
+
+    let value = value.trim();
+    if allowed.iter().any(|candidate| candidate == value) {
+        return Ok(FieldValidation {
+            valid: true,
+            suggestions: Vec::new(),
+        });
+    }
+
+    let needle = value.to_lowercase();
+    let threshold = (needle.chars().count() / 3).max(2);
+    let mut ranked: Vec<(usize, usize, &String)> = allowed
+        .iter()
+        .filter_map(|candidate| {
+            let lower = candidate.to_lowercase();
+            let rank = if lower == needle {
+                0
+            } else if !needle.is_empty() && lower.starts_with(&needle) {
+                1
+            } else if !needle.is_empty() && lower.contains(&needle) {
+                2
+            } else {
+                3
+            };
+            let distance = edit_distance(&needle, &lower);
+            (rank < 3 || distance <= threshold).then_some((rank, distance, candidate))
+        })
+        .collect();
+    ranked.sort_by(|a, b| (a.0, a.1, a.2).cmp(&(b.0, b.1, b.2)));
+
+    Ok(FieldValidation {
+        valid: false,
+        suggestions: ranked
+            .into_iter()
+            .take(MAX_FIELD_SUGGESTIONS)
+            .map(|(_, _, candidate)| candidate.clone())
+            .collect(),
+    })
+}
+
+#[tauri::command]
+pub async fn validate_spell_field(
+    field: String,
+    value: String,
+) -> Result<FieldValidation, AppError> {
+    validate_spell_field_value(&field, &value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::vault::VaultTestEnvGuard;
+    use rusqlite::Connection;
+
+    fn repeated_text(len: usize) -> String {
+        "x".repeat(len)
+    }
+
+    #[test]
+    fn test_get_spell_from_conn_loads_artifact_by_content_hash_when_hash_set() {
+        let conn = setup_get_spell_artifact_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (NULL, 'source', 'a.md', 'ah', '2026-01-01T00:00:00Z', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert artifact keyed by hash only");
+        let detail = get_spell_from_conn(&conn, 1)
+            .expect("get spell")
+            .expect("some");
+        let artifacts = detail.artifacts.expect("artifacts loaded");
+        assert_eq!(
+            artifacts.len(),
+            1,
+            "artifact with spell_content_hash=spell hash must be loaded"
+        );
+        assert_eq!(artifacts[0].path, "a.md");
+    }
+
+    #[test]
+    fn test_get_spells_batch_preserves_order_and_attaches_artifacts() {
+        let conn = setup_get_spell_artifact_test_db();
+        conn.execute_batch(
+            r#"
+            INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'One', 1, 'Desc', 'hash-1');
+            INSERT INTO spell (id, name, level, description, content_hash) VALUES (2, 'Two', 1, 'Desc', 'hash-2');
+            INSERT INTO spell (id, name, level, description, content_hash) VALUES (3, 'Three', 1, 'Desc', NULL);
+            INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
+            VALUES (NULL, 'source', 'one.md', 'a1', '2026-01-01T00:00:00Z', 'hash-1');
+            INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
+            VALUES (2, 'source', 'two.md', 'a2', '2026-01-01T00:00:00Z', NULL);
+            INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
+            VALUES (3, 'source', 'three.md', 'a3', '2026-01-01T00:00:00Z', NULL);
+            INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
+            VALUES (1, 'source', 'stale.md', 'a4', '2026-01-01T00:00:00Z', 'other-hash');
+            "#,
+        )
+        .expect("seed spells and artifacts");
+
+        let spells = get_spells_batch_with_conn(&conn, &[3, 99, 1, 2]).expect("batch fetch");
+        let ids: Vec<Option<i64>> = spells.iter().map(|s| s.id).collect();
+        assert_eq!(
+            ids,
+            vec![Some(3), Some(1), Some(2)],
+            "input order, missing id omitted"
+        );
+
+        let paths: Vec<Vec<&str>> = spells
+            .iter()
+            .map(|s| {
+                s.artifacts
+                    .as_ref()
+                    .expect("artifacts loaded")
+                    .iter()
+                    .map(|a| a.path.as_str())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            vec![vec!["three.md"], vec!["one.md"], vec!["two.md"]],
+            "each artifact attaches to its own spell; stale hash refs are excluded"
+        );
+        assert_eq!(spells[1].artifacts.as_ref().unwrap()[0].spell_id, 1);
+    }
+
+    #[test]
+    fn test_get_spells_batch_loads_timestamps_like_single_fetch() {
+        let conn = setup_get_spell_artifact_test_db();
+        conn.execute_batch(
+            r#"
+            ALTER TABLE spell ADD COLUMN created_at TEXT;
+            INSERT INTO spell (id, name, level, description, created_at, updated_at)
+            VALUES (1, 'One', 1, 'Desc', '2026-01-01T00:00:00Z', '2026-02-01T00:00:00Z');
+            "#,
+        )
+        .expect("seed spell with timestamps");
+
+        let single = get_spell_from_conn(&conn, 1)
+            .expect("get spell")
+            .expect("some");
+        let batch = get_spells_batch_with_conn(&conn, &[1]).expect("batch fetch");
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].created_at.as_deref(), Some("2026-01-01T00:00:00Z"));
+        assert_eq!(batch[0].updated_at.as_deref(), Some("2026-02-01T00:00:00Z"));
+        assert_eq!(batch[0].created_at, single.created_at);
+        assert_eq!(batch[0].updated_at, single.updated_at);
+        assert_eq!(
+            batch[0].reverse_name, None,
+            "schemas without reverse_name still read"
+        );
+    }
+
+    #[test]
+    fn test_get_spell_from_conn_excludes_artifact_when_spell_id_matches_but_hash_different() {
+        let conn = setup_get_spell_artifact_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (1, 'source', 'wrong.md', 'ah', '2026-01-01T00:00:00Z', 'other-spell-hash')",
+            [],
+        )
+        .expect("insert artifact with stale spell_id but different spell_content_hash");
+        let detail = get_spell_from_conn(&conn, 1)
+            .expect("get spell")
+            .expect("some");
+        let artifacts = detail.artifacts.unwrap_or_default();
+        assert!(artifacts.is_empty(), "artifact whose spell_content_hash belongs to another spell must not leak into this spell");
+    }
+
+    #[test]
+    fn test_get_spell_from_conn_legacy_fallback_loads_artifact_when_hash_null() {
+        let conn = setup_get_spell_artifact_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (1, 'source', 'legacy.md', 'ah', '2026-01-01T00:00:00Z', NULL)",
+            [],
+        )
+        .expect("insert legacy artifact with spell_id only");
+        let detail = get_spell_from_conn(&conn, 1)
+            .expect("get spell")
+            .expect("some");
+        let artifacts = detail.artifacts.expect("artifacts loaded");
+        assert_eq!(
+            artifacts.len(),
+            1,
+            "legacy artifact with spell_content_hash NULL but spell_id match must be loaded"
+        );
+        assert_eq!(artifacts[0].path, "legacy.md");
+    }
+
+    #[test]
+    fn test_get_spell_from_conn_legacy_artifact_schema_without_hash_column() {
+        let conn = Connection::open_in_memory().expect("open in-memory sqlite");
+        conn.execute_batch(
+            r#"
+            CREATE TABLE spell (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                school TEXT,
+                sphere TEXT,
+                class_list TEXT,
+                level INTEGER NOT NULL,
+                range TEXT,
+                components TEXT,
+                material_components TEXT,
+                casting_time TEXT,
+                duration TEXT,
+                area TEXT,
+                saving_throw TEXT,
+                reversible INTEGER,
+                description TEXT NOT NULL,
+                tags TEXT,
+                source TEXT,
+                edition TEXT,
+                author TEXT,
+                license TEXT,
+                is_quest_spell INTEGER NOT NULL DEFAULT 0,
+                is_cantrip INTEGER NOT NULL DEFAULT 0,
+                damage TEXT,
+                magic_resistance TEXT,
+                schema_version INTEGER,
+                updated_at TEXT,
+                canonical_data TEXT,
+                content_hash TEXT
+            );
+            CREATE TABLE artifact (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                spell_id INTEGER,
+                type TEXT NOT NULL,
+                path TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                imported_at TEXT NOT NULL
+            );
+            "#,
+        )
+        .expect("create legacy spell+artifact test schema");
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at) VALUES (1, 'source', 'legacy-schema.md', 'ah', '2026-01-01T00:00:00Z')",
+            [],
+        )
+        .expect("insert legacy artifact row");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at) VALUES (1, 'errata', 'legacy-schema-2.md', 'bh', '2026-01-01T00:00:01Z')",
+            [],
+        )
+        .expect("insert second legacy artifact row");
+
+        let detail = get_spell_from_conn(&conn, 1)
+            .expect("get spell")
+            .expect("some");
+        let artifacts = detail.artifacts.expect("artifacts loaded");
+        assert_eq!(
+            artifacts.len(),
+            2,
+            "legacy artifact schema should still load all matching rows by spell_id fallback"
+        );
+        assert_eq!(artifacts[0].path, "legacy-schema.md");
+        assert_eq!(artifacts[1].path, "legacy-schema-2.md");
+    }
+
+    #[test]
+    fn test_get_spell_artifacts_flags_missing_files() {
+        let conn = setup_get_spell_artifact_test_db();
+        let dir = tempfile::tempdir().expect("temp dir");
+        let present = dir.path().join("present.md");
+        std::fs::write(&present, "# Test").expect("write artifact file");
+        let missing = dir.path().join("missing.md");
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
+             VALUES (1, 'source', ?1, 'ah', '2026-01-01T00:00:00Z', 'spell-hash-1'),
+                    (1, 'errata', ?2, 'bh', '2026-01-01T00:00:01Z', 'spell-hash-1')",
+            params![
+                present.to_string_lossy().to_string(),
+                missing.to_string_lossy().to_string()
+            ],
+        )
+        .expect("insert artifacts");
+
+        let artifacts = get_spell_artifacts_with_conn(&conn, 1).expect("list artifacts");
+        let flags: Vec<(String, Option<bool>)> = artifacts
+            .into_iter()
+            .map(|a| (a.r#type, a.exists))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("source".to_string(), Some(true)),
+                ("errata".to_string(), Some(false))
+            ]
+        );
+        assert!(matches!(
+            get_spell_artifacts_with_conn(&conn, 99),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_spell_at_level_evaluates_scaling_fields() {
+        let detail = SpellDetail {
+            name: "Flame Lance".into(),
+            level: 3,
+            description: "A lance of fire.".into(),
+            school: Some("Evocation".into()),
+            range: Some("10 + 5/level yards".into()),
+            duration: Some("1 round/level".into()),
+            damage: Some("1d6/level (max 10d6) fire damage".into()),
+            ..Default::default()
+        };
+        let (mut canonical, _, _) = canonicalize_spell_detail(detail).expect("canonicalize");
+
+        let at_three = resolve_spell_at_level(7, &canonical, 3);
+        let range = at_three.range.expect("range resolved");
+        assert_eq!(range.formula.as_deref(), Some("10+5/level"));
+        assert_eq!(range.value, Some(25.0));
+        assert_eq!(range.unit.as_deref(), Some("yd"));
+        assert_eq!(at_three.duration.as_ref().unwrap().value, Some(3.0));
+        assert_eq!(at_three.damage[0].dice, "3d6");
+
+        let at_twelve = resolve_spell_at_level(7, &canonical, 12);
+        assert_eq!(at_twelve.damage[0].dice, "10d6", "damage stops at max 10d6");
+
+        // Capped duration: at most 5 rounds however high the caster.
+        canonical
+            .duration
+            .as_mut()
+            .and_then(|d| d.duration.as_mut())
+            .expect("duration scalar")
+            .cap_value = Some(5.0);
+        let capped = resolve_spell_at_level(7, &canonical, 12).duration.unwrap();
+        assert_eq!(capped.formula.as_deref(), Some("1/level"));
+        assert_eq!(capped.value, Some(5.0));
+        assert_eq!(capped.unit.as_deref(), Some("round"));
+        assert_eq!(
+            resolve_spell_at_level(7, &canonical, 4)
+                .duration
+                .unwrap()
+                .value,
+            Some(4.0)
+        );
+    }
+
+    #[test]
+    fn test_get_spell_from_conn_artifact_exposes_spell_content_hash() {
+        let conn = setup_get_spell_artifact_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (NULL, 'source', 'a.md', 'ah', '2026-01-01T00:00:00Z', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert artifact with spell_content_hash set");
+
+        let detail = get_spell_from_conn(&conn, 1)
+            .expect("get spell")
+            .expect("some");
+        let artifact = &detail.artifacts.expect("loaded")[0];
+        assert_eq!(
+            artifact.spell_content_hash,
+            Some("spell-hash-1".to_string()),
+            "artifact should expose spell_content_hash when column is populated"
+        );
+    }
+
+    #[test]
+    fn test_get_spell_from_conn_artifact_null_spell_content_hash_for_legacy() {
+        let conn = setup_get_spell_artifact_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            [],
+        )
+        .expect("insert spell");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (1, 'source', 'legacy.md', 'ah', '2026-01-01T00:00:00Z', NULL)",
+            [],
+        )
+        .expect("insert legacy artifact with null spell_content_hash");
+
+        let detail = get_spell_from_conn(&conn, 1)
+            .expect("get spell")
+            .expect("some");
+        let artifact = &detail.artifacts.expect("loaded")[0];
+        assert_eq!(
+            artifact.spell_content_hash, None,
+            "legacy artifact (NULL spell_content_hash) should return None"
+        );
+    }
+
+    #[test]
+    fn test_validate_cantrip_level() {
+        // Cantrip must be level 0
+        assert!(validate_epic_and_quest_spells(0, &None, false, true).is_ok());
+        assert!(validate_epic_and_quest_spells(1, &None, false, true).is_err());
+    }
+
+    #[test]
+    fn test_validate_quest_spell_restrictions() {
+        // Quest spells must be level 8 (Quest level in this app)
+        assert!(validate_epic_and_quest_spells(8, &Some("Priest".into()), true, false).is_ok());
+        assert!(validate_epic_and_quest_spells(10, &Some("Priest".into()), true, false).is_err());
+        assert!(validate_epic_and_quest_spells(1, &Some("Priest".into()), true, false).is_err());
+
+        // Quest spells must be for Divine classes
+        assert!(validate_epic_and_quest_spells(8, &Some("Priest".into()), true, false).is_ok());
+        assert!(validate_epic_and_quest_spells(8, &Some("Cleric".into()), true, false).is_ok());
+        assert!(validate_epic_and_quest_spells(8, &Some("Druid".into()), true, false).is_ok());
+        assert!(validate_epic_and_quest_spells(8, &Some("Paladin".into()), true, false).is_ok());
+        assert!(validate_epic_and_quest_spells(8, &Some("Ranger".into()), true, false).is_ok());
+
+        // Not a divine class
+        assert!(validate_epic_and_quest_spells(8, &Some("Wizard".into()), true, false).is_err());
+        assert!(validate_epic_and_quest_spells(8, &Some("Fighter".into()), true, false).is_err());
+    }
+
+    #[test]
+    fn test_validate_epic_spell_restrictions() {
+        // Spells > 9 must be for Arcane classes (Wizard/Mage)
+        assert!(validate_epic_and_quest_spells(10, &Some("Wizard".into()), false, false).is_ok());
+        assert!(validate_epic_and_quest_spells(10, &Some("Mage".into()), false, false).is_ok());
+        assert!(validate_epic_and_quest_spells(10, &Some("Priest".into()), false, false).is_err());
+    }
+
+    #[test]
+    fn test_canonicalize_spell_detail_rejects_mutually_exclusive_school_and_sphere() {
+        let detail = SpellDetail {
+            name: "Mutually Exclusive".to_string(),
+            level: 1,
+            description: "Invalid direct-write payload".to_string(),
+            school: Some("Abjuration".to_string()),
+            sphere: Some("Combat".to_string()),
+            ..Default::default()
+        };
+
+        let err = canonicalize_spell_detail(detail).expect_err(
+            "direct writes should reject school/sphere combinations that import rejects",
+        );
+
+        assert!(
+            err.to_string().contains("mutually exclusive"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_spell_detail_rejects_oversized_direct_write_text_fields() {
+        let cases = [
+            (
+                "name",
+                SpellDetail {
+                    name: repeated_text(257),
+                    level: 1,
+                    description: "Valid description".to_string(),
+                    school: Some("Abjuration".to_string()),
+                    ..Default::default()
+                },
+                "/name",
+            ),
+            (
+                "description",
+                SpellDetail {
+                    name: "Oversized Description".to_string(),
+                    level: 1,
+                    description: repeated_text(16_385),
+                    school: Some("Abjuration".to_string()),
+                    ..Default::default()
+                },
+                "/description",
+            ),
+            (
+                "source",
+                SpellDetail {
+                    name: "Oversized Source".to_string(),
+                    level: 1,
+                    description: "Valid description".to_string(),
+                    school: Some("Abjuration".to_string()),
+                    source: Some(repeated_text(513)),
+                    ..Default::default()
+                },
+                "/source_refs/0/book",
+            ),
+            (
+                "author",
+                SpellDetail {
+                    name: "Oversized Author".to_string(),
+                    level: 1,
+                    description: "Valid description".to_string(),
+                    school: Some("Abjuration".to_string()),
+                    author: Some(repeated_text(257)),
+                    ..Default::default()
+                },
+                "/author",
+            ),
+        ];
+
+        for (field_name, detail, expected_path) in cases {
+            let err = canonicalize_spell_detail(detail)
+                .expect_err("oversized direct-write fields should be rejected");
+            let message = err.to_string();
+            assert!(
+                message.contains(expected_path),
+                "expected {field_name} error to mention {expected_path}, got: {message}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_canonicalize_spell_detail_accepts_exact_boundary_direct_write_text_fields() {
+        let detail = SpellDetail {
+            name: repeated_text(256),
+            level: 3,
+            description: repeated_text(16_384),
+            school: Some("Abjuration".to_string()),
+            source: Some(repeated_text(512)),
+            author: Some(repeated_text(256)),
+            ..Default::default()
+        };
+
+        let (canonical, hash, _json) = canonicalize_spell_detail(detail)
+            .expect("exact-boundary direct-write spell should validate");
+
+        assert_eq!(canonical.name.len(), 256);
+        assert_eq!(canonical.description.len(), 16_384);
+        assert_eq!(canonical.author.as_deref().map(str::len), Some(256));
+        assert_eq!(
+            canonical.source_refs.len(),
+            1,
+            "direct source should map into a single source_ref"
+        );
+        assert_eq!(canonical.source_refs[0].book.len(), 512);
+        assert_eq!(
+            hash.len(),
+            64,
+            "hash should still be computed for valid boundary input"
+        );
+    }
+
+    /// Minimal schema for get_spell_from_conn artifact read-path tests (spell + artifact only).
+    fn setup_get_spell_artifact_test_db() -> Connection {
+        let conn = Connection::open_in_memory().expect("open in-memory sqlite");
+        conn.execute_batch(
+            r#"
+            CREATE TABLE spell (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                school TEXT,
+                sphere TEXT,
+                class_list TEXT,
+                level INTEGER NOT NULL,
+                range TEXT,
+                components TEXT,
+                material_components TEXT,
+                casting_time TEXT,
+                duration TEXT,
+                area TEXT,
+                saving_throw TEXT,
+                reversible INTEGER,
+                description TEXT NOT NULL,
+                tags TEXT,
+                source TEXT,
+                edition TEXT,
+                author TEXT,
+                license TEXT,
+                is_quest_spell INTEGER NOT NULL DEFAULT 0,
+                is_cantrip INTEGER NOT NULL DEFAULT 0,
+                damage TEXT,
+                magic_resistance TEXT,
+                schema_version INTEGER,
+                updated_at TEXT,
+                canonical_data TEXT,
+                content_hash TEXT
+            );
+            CREATE TABLE artifact (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                spell_id INTEGER,
+                type TEXT NOT NULL,
+                path TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                imported_at TEXT NOT NULL,
+                spell_content_hash TEXT
+            );
+            "#,
+        )
+        .expect("create spell+artifact test schema");
+        conn
+    }
+
+    fn setup_spell_update_test_db() -> Connection {
+        let conn = Connection::open_in_memory().expect("open in-memory sqlite");
+        conn.execute_batch(
+            r#"
+            CREATE TABLE spell (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                school TEXT,
+                sphere TEXT,
+                class_list TEXT,
+                level INTEGER NOT NULL,
+                range TEXT,
+                components TEXT,
+                material_components TEXT,
+                casting_time TEXT,
+                duration TEXT,
+                area TEXT,
+                saving_throw TEXT,
+                reversible INTEGER,
+                description TEXT NOT NULL,
+                tags TEXT,
+                source TEXT,
+                edition TEXT,
+                author TEXT,
+                license TEXT,
+                is_quest_spell INTEGER NOT NULL DEFAULT 0,
+                is_cantrip INTEGER NOT NULL DEFAULT 0,
+                damage TEXT,
+                magic_resistance TEXT,
+                schema_version INTEGER,
+                updated_at TEXT,
+                canonical_data TEXT,
+                content_hash TEXT,
+                needs_review INTEGER NOT NULL DEFAULT 0,
+                verified INTEGER NOT NULL DEFAULT 0,
+                locked INTEGER NOT NULL DEFAULT 0,
+                logical_id TEXT,
+                reverse_name TEXT
+            );
+            CREATE TABLE change_log (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                spell_id INTEGER NOT NULL,
+                field TEXT NOT NULL,
+                old_value TEXT,
+                new_value TEXT
+            );
+            CREATE TABLE artifact (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                spell_id INTEGER NOT NULL,
+                type TEXT NOT NULL,
+                path TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                imported_at TEXT NOT NULL,
+                spell_content_hash TEXT
+            );
+            CREATE TABLE character_class_spell (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                spell_id INTEGER NOT NULL,
+                character_class_id INTEGER NOT NULL,
+                spell_content_hash TEXT
+            );
+            CREATE TABLE spell_template (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                partial_json TEXT NOT NULL
+            );
+            "#,
+        )
+        .expect("create spell test schema");
+        conn
+    }
+
+    #[test]
+    fn test_get_spell_cache_is_invalidated_by_spell_update() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_spell_update_test_db();
+        let cache = SpellCache::default();
+
+        let initial = SpellDetail {
+            name: "Cached Spell".to_string(),
+            level: 2,
+            description: "Before edit".to_string(),
+            school: Some("Alteration".to_string()),
+            ..Default::default()
+        };
+        let (canonical, hash, json) =
+            canonicalize_spell_detail(initial.clone()).expect("canonicalize spell");
+        conn.execute(
+            "INSERT INTO spell (id, name, school, level, description, schema_version, canonical_data, content_hash)
+             VALUES (1, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                initial.name,
+                initial.school,
+                initial.level,
+                initial.description,
+                canonical.schema_version,
+                json,
+                hash
+            ],
+        )
+        .expect("seed spell");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
+             VALUES (1, 'source', '/tmp/cached.md', 'abc', '2024-01-01T00:00:00Z', ?)",
+            [&hash],
+        )
+        .expect("seed artifact");
+
+        let first = get_spell_cached_with_conn(&conn, &cache, 1)
+            .expect("first read")
+            .expect("spell exists");
+        let hit = get_spell_cached_with_conn(&conn, &cache, 1)
+            .expect("cached read")
+            .expect("spell exists");
+        let fresh = get_spell_from_conn(&conn, 1)
+            .expect("fresh read")
+            .expect("spell exists");
+        assert_eq!(
+            serde_json::to_string(&hit).unwrap(),
+            serde_json::to_string(&fresh).unwrap()
+        );
+        assert_eq!(first.artifacts.as_ref().map(Vec::len), Some(1));
+
+        {
+            let _cache_guard = cache.start_write();
+            let update = SpellUpdate {
+                id: 1,
+                name: "Cached Spell".to_string(),
+                level: 2,
+                description: "After edit".to_string(),
+                school: Some("Alteration".to_string()),
+                ..Default::default()
+            };
+            apply_spell_update_with_conn(&conn, &update).expect("update spell");
+        }
+
+        let after = get_spell_cached_with_conn(&conn, &cache, 1)
+            .expect("post-edit read")
+            .expect("spell exists");
+        assert_eq!(after.description, "After edit");
+        assert_ne!(after.content_hash, first.content_hash);
+    }
+
+    #[test]
+    fn test_create_spell_from_template_merges_overrides() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_spell_update_test_db();
+
+        let template_spell = SpellDetail {
+            name: String::new(),
+            level: 3,
+            school: Some("Evocation".to_string()),
+            damage: Some("1d6 per level".to_string()),
+            description: "A burst of raw force.".to_string(),
+            range: Some(String::new()),
+            created_at: Some("2026-01-01T00:00:00Z".to_string()),
+            updated_at: Some("2026-02-01T00:00:00Z".to_string()),
+            material_has_cost: true,
+            ..Default::default()
+        };
+        let template_id =
+            save_spell_template_with_conn(&conn, "Blank evocation damage spell", &template_spell)
+                .expect("save template");
+
+        let templates = list_spell_templates_with_conn(&conn).expect("list templates");
+        assert_eq!(templates.len(), 1);
+        let stored = templates[0].partial_json.as_object().unwrap();
+        assert_eq!(stored["school"], "Evocation");
+        assert!(!stored.contains_key("name"));
+        assert!(!stored.contains_key("range"));
+        assert!(!stored.contains_key("duration"));
+        for key in ["createdAt", "updatedAt", "materialHasCost"] {
+            assert!(
+                !stored.contains_key(key),
+                "row metadata {key} must be stripped"
+            );
+        }
+
+        let spell_id = create_spell_from_template_with_conn(
+            &conn,
+            template_id,
+            Some(serde_json::json!({"name": "Force Burst"})),
+        )
+        .expect("instantiate template");
+
+        let created = get_spell_from_conn(&conn, spell_id)
+            .expect("load created spell")
+            .expect("created spell exists");
+        assert_eq!(created.name, "Force Burst");
+        assert_eq!(created.level, 3);
+        assert_eq!(created.school.as_deref(), Some("Evocation"));
+        assert_eq!(created.damage.as_deref(), Some("1d6 per level"));
+        assert_eq!(created.description, "A burst of raw force.");
+        assert_eq!(created.range, None);
+        assert_eq!(created.duration, None);
+        assert!(created.content_hash.is_some());
+    }
+
+    #[test]
+    fn test_spell_summary_description_preview_truncates_at_word_boundary() {
+        let conn = setup_spell_update_test_db();
+        let long = "A glowing missile of magical energy darts forth from the caster's \
+                    fingertip and unerringly strikes its target, dealing damage.";
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description) VALUES (1, 'Magic Missile', 1, ?), \
+             (2, 'Light', 1, 'Creates   light.')",
+            [long],
+        )
+        .unwrap();
+
+        let summaries = list_spell_summaries_with_conn(&conn, 40).unwrap();
+        let light = summaries.iter().find(|s| s.id == 2).unwrap();
+        assert_eq!(light.description_preview.as_deref(), Some("Creates light."));
+
+        let missile = summaries.iter().find(|s| s.id == 1).unwrap();
+        let preview = missile.description_preview.as_deref().unwrap();
+        assert_eq!(preview, "A glowing missile of magical energy…");
+        assert!(preview.chars().count() <= 41);
+
+        assert_eq!(
+            description_preview("one two three", 7).as_deref(),
+            Some("one two…")
+        );
+        assert_eq!(description_preview(long, 0), None);
+        assert!(list_spell_summaries_with_conn(&conn, 0)
+            .unwrap()
+            .iter()
+            .all(|s| s.description_preview.is_none()));
+    }
+
+    #[test]
+    fn test_description_preview_backs_off_when_whitespace_runs_hide_a_cut() {
+        let conn = setup_spell_update_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description) VALUES \
+             (1, 'Sleep', 1, 'alpha    betamax    gamma'), \
+             (2, 'Light', 1, 'alpha    beta     gamma')",
+            [],
+        )
+        .unwrap();
+
+        // The SQL prefix of 13 characters collapses to 10, ending mid-word in "betamax".
+        let summaries = list_spell_summaries_with_conn(&conn, 12).unwrap();
+        let sleep = summaries.iter().find(|s| s.id == 1).unwrap();
+        assert_eq!(sleep.description_preview.as_deref(), Some("alpha…"));
+
+        // A prefix ending in whitespace shows its last word was complete.
+        let summaries = list_spell_summaries_with_conn(&conn, 13).unwrap();
+        let light = summaries.iter().find(|s| s.id == 2).unwrap();
+        assert_eq!(light.description_preview.as_deref(), Some("alpha beta…"));
+    }
+
+    #[test]
+    fn test_validate_spell_field_value_checks_schema_enums() {
+        let valid = validate_spell_field_value("school", "Evocation").expect("known field");
+        assert!(valid.valid);
+        assert!(valid.suggestions.is_empty());
+
+        let miscased = validate_spell_field_value("school", "evocation").expect("known field");
+        assert!(!miscased.valid);
+        assert_eq!(
+            miscased.suggestions.first().map(String::as_str),
+            Some("Evocation")
+        );
+
+        let typo = validate_spell_field_value("range.kind", "tuch").expect("nested field");
+        assert!(!typo.valid);
+        assert!(typo.suggestions.iter().any(|s| s == "touch"));
+
+        let free_text =
+            validate_spell_field_value("description", "anything goes").expect("known field");
+        assert!(free_text.valid);
+        assert!(free_text.suggestions.is_empty());
+
+        assert!(matches!(
+            validate_spell_field_value("not_a_field", "x"),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_apply_spell_update_with_conn_persists_vault_file() {
+        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_spell_update_test_db();
+        let initial_detail = SpellDetail {
+            id: Some(1),
+            name: "Vaulted Spell".to_string(),
+            level: 3,
+            description: "Original description".to_string(),
+            school: Some("Abjuration".to_string()),
+            ..Default::default()
+        };
+        let (canonical, hash, json) =
+            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
         conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (NULL, 'source', 'a.md', 'ah', '2026-01-01T00:00:00Z', 'spell-hash-1')",
-            [],
+            "INSERT INTO spell (
+                id, name, school, sphere, class_list, level, range, components, material_components,
+                casting_time, duration, area, saving_throw, reversible, description, tags, source,
+                edition, author, license, is_quest_spell, is_cantrip, damage, magic_resistance,
+                schema_version, canonical_data, content_hash
+             ) VALUES (
+                1, ?, ?, NULL, NULL, ?, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 0, ?, NULL, NULL,
+                NULL, NULL, NULL, 0, 0, NULL, NULL, ?, ?, ?
+             )",
+            rusqlite::params![
+                initial_detail.name,
+                initial_detail.school,
+                initial_detail.level,
+                initial_detail.description,
+                canonical.schema_version,
+                json,
+                hash,
+            ],
+        )
+        .expect("seed spell row");
+
+        let update = SpellUpdate {
+            id: 1,
+            name: "Vaulted Spell".to_string(),
+            level: 3,
+            description: "Updated description".to_string(),
+            school: Some("Abjuration".to_string()),
+            is_quest_spell: 0,
+            is_cantrip: 0,
+            ..Default::default()
+        };
+
+        apply_spell_update_with_conn(&conn, &update).expect("update spell");
+
+        let updated_hash: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query updated hash");
+        let vault_file = temp_dir
+            .path()
+            .join("spells")
+            .join(format!("{updated_hash}.json"));
+        assert!(
+            vault_file.exists(),
+            "spell update should materialize the corresponding vault file"
+        );
+    }
+
+    #[test]
+    fn test_get_spell_from_conn_reports_material_has_cost() {
+        let conn = setup_spell_update_test_db();
+        for (id, material) in [(1, "a 5,000gp diamond"), (2, "a pinch of sulfur")] {
+            let detail = SpellDetail {
+                id: Some(id),
+                name: format!("Material Spell {id}"),
+                level: 3,
+                description: "Uses a material.".to_string(),
+                school: Some("Evocation".to_string()),
+                components: Some("V, S, M".to_string()),
+                material_components: Some(material.to_string()),
+                ..Default::default()
+            };
+            let (_, hash, json) = canonicalize_spell_detail(detail.clone()).expect("canonicalize");
+            conn.execute(
+                "INSERT INTO spell (id, name, school, level, description, components,
+                                    material_components, canonical_data, content_hash)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![
+                    id,
+                    detail.name,
+                    detail.school,
+                    detail.level,
+                    detail.description,
+                    detail.components,
+                    detail.material_components,
+                    json,
+                    hash,
+                ],
+            )
+            .expect("seed spell row");
+        }
+
+        let load = |id| get_spell_from_conn(&conn, id).unwrap().unwrap();
+        assert!(load(1).material_has_cost);
+        assert!(!load(2).material_has_cost);
+    }
+
+    #[test]
+    fn test_get_spell_from_conn_returns_updated_at_after_edit() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_spell_update_test_db();
+        let initial_detail = SpellDetail {
+            id: Some(1),
+            name: "Timestamped Spell".to_string(),
+            level: 2,
+            description: "Original description".to_string(),
+            school: Some("Evocation".to_string()),
+            ..Default::default()
+        };
+        let (_, hash, json) =
+            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
+        conn.execute(
+            "INSERT INTO spell (id, name, school, level, description, canonical_data, content_hash)
+             VALUES (1, ?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                initial_detail.name,
+                initial_detail.school,
+                initial_detail.level,
+                initial_detail.description,
+                json,
+                hash,
+            ],
+        )
+        .expect("seed spell row");
+
+        let before = get_spell_from_conn(&conn, 1)
+            .expect("load spell")
+            .expect("spell exists");
+        assert!(before.updated_at.is_none());
+
+        let update = SpellUpdate {
+            id: 1,
+            name: "Timestamped Spell".to_string(),
+            level: 2,
+            description: "Edited description".to_string(),
+            school: Some("Evocation".to_string()),
+            ..Default::default()
+        };
+        apply_spell_update_with_conn(&conn, &update).expect("update spell");
+
+        let after = get_spell_from_conn(&conn, 1)
+            .expect("load spell")
+            .expect("spell exists");
+        assert!(after.updated_at.as_deref().is_some_and(|ts| !ts.is_empty()));
+
+        // Timestamps stay out of the canonical form.
+        let (_, rehash, _) = canonicalize_spell_detail(after.clone()).expect("canonicalize");
+        let mut without_timestamps = after;
+        without_timestamps.updated_at = None;
+        without_timestamps.created_at = None;
+        let (_, plain_hash, _) =
+            canonicalize_spell_detail(without_timestamps).expect("canonicalize");
+        assert_eq!(rehash, plain_hash);
+    }
+
+    #[test]
+    fn test_apply_spell_update_with_conn_cascades_hash_references() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_spell_update_test_db();
+        let initial_detail = SpellDetail {
+            id: Some(1),
+            name: "Cascade Spell".to_string(),
+            level: 3,
+            description: "Original description".to_string(),
+            school: Some("Abjuration".to_string()),
+            ..Default::default()
+        };
+        let (canonical, old_hash, json) =
+            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
+        conn.execute(
+            "INSERT INTO spell (
+                id, name, school, sphere, class_list, level, range, components, material_components,
+                casting_time, duration, area, saving_throw, reversible, description, tags, source,
+                edition, author, license, is_quest_spell, is_cantrip, damage, magic_resistance,
+                schema_version, canonical_data, content_hash
+             ) VALUES (
+                1, ?, ?, NULL, NULL, ?, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 0, ?, NULL, NULL,
+                NULL, NULL, NULL, 0, 0, NULL, NULL, ?, ?, ?
+             )",
+            rusqlite::params![
+                initial_detail.name,
+                initial_detail.school,
+                initial_detail.level,
+                initial_detail.description,
+                canonical.schema_version,
+                json,
+                old_hash,
+            ],
+        )
+        .expect("seed spell row");
+        conn.execute(
+            "INSERT INTO character_class_spell (spell_id, character_class_id, spell_content_hash)
+             VALUES (1, 7, ?1)",
+            [old_hash.as_str()],
+        )
+        .expect("seed character class reference");
+        conn.execute(
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
+             VALUES (1, 'source', 'spells/cascade.md', 'artifact-hash', '2026-03-08T00:00:00Z', ?1)",
+            [old_hash.as_str()],
+        )
+        .expect("seed artifact reference");
+
+        let update = SpellUpdate {
+            id: 1,
+            name: "Cascade Spell".to_string(),
+            level: 3,
+            description: "Updated description".to_string(),
+            school: Some("Abjuration".to_string()),
+            is_quest_spell: 0,
+            is_cantrip: 0,
+            ..Default::default()
+        };
+
+        apply_spell_update_with_conn(&conn, &update).expect("update spell");
+
+        let new_hash: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query updated hash");
+        assert_ne!(new_hash, old_hash, "test update should produce a new hash");
+
+        let class_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM character_class_spell WHERE spell_id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query class hash");
+        let artifact_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM artifact WHERE spell_id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query artifact hash");
+
+        assert_eq!(class_hash, new_hash);
+        assert_eq!(artifact_hash, new_hash);
+    }
+
+    #[test]
+    fn test_run_in_savepoint_rolls_back_and_releases_on_error() {
+        let conn = Connection::open_in_memory().expect("open db");
+        conn.execute("CREATE TABLE test_tx (value TEXT NOT NULL)", [])
+            .expect("create test table");
+
+        let err = run_in_savepoint(&conn, "test_tx_write", || {
+            conn.execute("INSERT INTO test_tx (value) VALUES ('pending')", [])?;
+            Err::<(), AppError>(AppError::Validation("boom".to_string()))
+        })
+        .expect_err("savepoint should bubble error");
+        assert!(err.to_string().contains("boom"));
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM test_tx", [], |row| row.get(0))
+            .expect("query row count");
+        assert_eq!(count, 0, "savepoint should roll back failed writes");
+
+        run_in_savepoint(&conn, "test_tx_write", || {
+            conn.execute("INSERT INTO test_tx (value) VALUES ('committed')", [])?;
+            Ok::<(), AppError>(())
+        })
+        .expect("savepoint should be reusable after rollback");
+
+        let stored: String = conn
+            .query_row("SELECT value FROM test_tx", [], |row| row.get(0))
+            .expect("query committed value");
+        assert_eq!(stored, "committed");
+    }
+
+    #[test]
+    fn test_apply_spell_update_with_conn_rolls_back_on_vault_export_failure() {
+        let long_root = std::env::temp_dir()
+            .join("spellbook-backend")
+            .join("a".repeat(240));
+        let _env = VaultTestEnvGuard::with_root(long_root.clone()).expect("set isolated vault env");
+
+        let conn = setup_spell_update_test_db();
+        let initial_detail = SpellDetail {
+            id: Some(1),
+            name: "Rollback Spell".to_string(),
+            level: 3,
+            description: "Original description".to_string(),
+            school: Some("Abjuration".to_string()),
+            ..Default::default()
+        };
+        let (canonical, old_hash, json) =
+            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
+        conn.execute(
+            "INSERT INTO spell (
+                id, name, school, sphere, class_list, level, range, components, material_components,
+                casting_time, duration, area, saving_throw, reversible, description, tags, source,
+                edition, author, license, is_quest_spell, is_cantrip, damage, magic_resistance,
+                schema_version, canonical_data, content_hash
+             ) VALUES (
+                1, ?, ?, NULL, NULL, ?, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 0, ?, NULL, NULL,
+                NULL, NULL, NULL, 0, 0, NULL, NULL, ?, ?, ?
+             )",
+            rusqlite::params![
+                initial_detail.name,
+                initial_detail.school,
+                initial_detail.level,
+                initial_detail.description,
+                canonical.schema_version,
+                json,
+                old_hash,
+            ],
         )
-        .expect("insert artifact keyed by hash only");
-        let detail = get_spell_from_conn(&conn, 1)
-            .expect("get spell")
-            .expect("some");
-        let artifacts = detail.artifacts.expect("artifacts loaded");
-        assert_eq!(
-            artifacts.len(),
-            1,
-            "artifact with spell_content_hash=spell hash must be loaded"
-        );
-        assert_eq!(artifacts[0].path, "a.md");
-    }
-
-    #[test]
-    fn test_get_spell_from_conn_excludes_artifact_when_spell_id_matches_but_hash_different() {
-        let conn = setup_get_spell_artifact_test_db();
+        .expect("seed spell row");
         conn.execute(
-            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
-            [],
+            "INSERT INTO character_class_spell (spell_id, character_class_id, spell_content_hash)
+             VALUES (1, 7, ?1)",
+            [old_hash.as_str()],
         )
-        .expect("insert spell");
+        .expect("seed character class reference");
         conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (1, 'source', 'wrong.md', 'ah', '2026-01-01T00:00:00Z', 'other-spell-hash')",
-            [],
+            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
+             VALUES (1, 'source', 'spells/rollback.md', 'artifact-hash', '2026-03-08T00:00:00Z', ?1)",
+            [old_hash.as_str()],
         )
-        .expect("insert artifact with stale spell_id but different spell_content_hash");
-        let detail = get_spell_from_conn(&conn, 1)
-            .expect("get spell")
-            .expect("some");
-        let artifacts = detail.artifacts.unwrap_or_default();
-        assert!(artifacts.is_empty(), "artifact whose spell_content_hash belongs to another spell must not leak into this spell");
+        .expect("seed artifact reference");
+
+        let update = SpellUpdate {
+            id: 1,
+            name: "Rollback Spell".to_string(),
+            level: 3,
+            description: "Updated description".to_string(),
+            school: Some("Abjuration".to_string()),
+            is_quest_spell: 0,
+            is_cantrip: 0,
+            ..Default::default()
+        };
+
+        let err =
+            apply_spell_update_with_conn(&conn, &update).expect_err("vault export should fail");
+        assert!(err.to_string().contains("Windows path length"));
+
+        let stored_description: String = conn
+            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query rolled back description");
+        let stored_hash: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query rolled back hash");
+        let class_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM character_class_spell WHERE spell_id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query rolled back class hash");
+        let artifact_hash: String = conn
+            .query_row(
+                "SELECT spell_content_hash FROM artifact WHERE spell_id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query rolled back artifact hash");
+        let change_log_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM change_log WHERE spell_id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query change log count");
+
+        assert_eq!(stored_description, "Original description");
+        assert_eq!(stored_hash, old_hash);
+        assert_eq!(class_hash, old_hash);
+        assert_eq!(artifact_hash, old_hash);
+        assert_eq!(change_log_count, 0);
     }
 
     #[test]
-    fn test_get_spell_from_conn_legacy_fallback_loads_artifact_when_hash_null() {
-        let conn = setup_get_spell_artifact_test_db();
+    fn test_apply_spell_update_with_conn_rejects_oversized_direct_write_source() {
+        let conn = setup_spell_update_test_db();
+        let initial_detail = SpellDetail {
+            id: Some(1),
+            name: "Oversized Source Update".to_string(),
+            level: 2,
+            description: "Original description".to_string(),
+            school: Some("Abjuration".to_string()),
+            ..Default::default()
+        };
+        let (canonical, hash, json) =
+            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
         conn.execute(
-            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
-            [],
+            "INSERT INTO spell (
+                id, name, school, sphere, class_list, level, range, components, material_components,
+                casting_time, duration, area, saving_throw, reversible, description, tags, source,
+                edition, author, license, is_quest_spell, is_cantrip, damage, magic_resistance,
+                schema_version, canonical_data, content_hash
+             ) VALUES (
+                1, ?, ?, NULL, NULL, ?, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 0, ?, NULL, NULL,
+                NULL, NULL, NULL, 0, 0, NULL, NULL, ?, ?, ?
+             )",
+            rusqlite::params![
+                initial_detail.name,
+                initial_detail.school,
+                initial_detail.level,
+                initial_detail.description,
+                canonical.schema_version,
+                json,
+                hash,
+            ],
         )
-        .expect("insert spell");
+        .expect("seed spell row");
+
+        let update = SpellUpdate {
+            id: 1,
+            name: "Oversized Source Update".to_string(),
+            level: 2,
+            description: "Original description".to_string(),
+            school: Some("Abjuration".to_string()),
+            source: Some(repeated_text(513)),
+            is_quest_spell: 0,
+            is_cantrip: 0,
+            ..Default::default()
+        };
+
+        let err = apply_spell_update_with_conn(&conn, &update)
+            .expect_err("oversized update source should be rejected");
+        assert!(
+            err.to_string().contains("/source_refs/0/book"),
+            "unexpected error: {err}"
+        );
+
+        let stored_source: Option<String> = conn
+            .query_row("SELECT source FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("query source after rejected update");
+        let change_log_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM change_log WHERE spell_id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("query change log count");
+
+        assert_eq!(
+            stored_source, None,
+            "rejected update must not persist source text"
+        );
+        assert_eq!(
+            change_log_count, 0,
+            "rejected update must roll back change log writes"
+        );
+    }
+
+    #[test]
+    fn test_normalize_list_column_produces_sorted_json_array() {
+        assert_eq!(
+            normalize_list_column(&Some("Wizard, Bard, Wizard".into())),
+            Some(r#"["Bard","Wizard"]"#.to_string())
+        );
+        assert_eq!(
+            normalize_list_column(&Some(r#"["Wizard", " Bard "]"#.into())),
+            Some(r#"["Bard","Wizard"]"#.to_string())
+        );
+        assert_eq!(normalize_list_column(&Some(" , ".into())), None);
+        assert_eq!(normalize_list_column(&Some("[]".into())), None);
+        assert_eq!(normalize_list_column(&None), None);
+    }
+
+    #[test]
+    fn test_apply_spell_update_with_conn_normalizes_list_columns() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_spell_update_test_db();
         conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (1, 'source', 'legacy.md', 'ah', '2026-01-01T00:00:00Z', NULL)",
+            "INSERT INTO spell (id, name, level, description, school, class_list, tags)
+             VALUES (1, 'List Spell', 1, 'Desc', 'Abjuration', 'Wizard, Bard', 'Fire')",
             [],
         )
-        .expect("insert legacy artifact with spell_id only");
-        let detail = get_spell_from_conn(&conn, 1)
-            .expect("get spell")
-            .expect("some");
-        let artifacts = detail.artifacts.expect("artifacts loaded");
+        .expect("seed spell row");
+
+        let update = SpellUpdate {
+            id: 1,
+            name: "List Spell".to_string(),
+            level: 1,
+            description: "Desc".to_string(),
+            school: Some("Abjuration".to_string()),
+            class_list: Some(r#"["Wizard","Bard"]"#.to_string()),
+            tags: Some("Fire, AoE".to_string()),
+            ..Default::default()
+        };
+        apply_spell_update_with_conn(&conn, &update).expect("update spell");
+
+        let (class_list, tags): (String, String) = conn
+            .query_row(
+                "SELECT class_list, tags FROM spell WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query list columns");
+        assert_eq!(class_list, r#"["Bard","Wizard"]"#);
+        assert_eq!(tags, r#"["AoE","Fire"]"#);
+
+        let logged_fields: Vec<String> = conn
+            .prepare("SELECT field FROM change_log WHERE spell_id = 1")
+            .expect("prepare change log query")
+            .query_map([], |row| row.get(0))
+            .expect("query change log")
+            .collect::<Result<_, _>>()
+            .expect("collect change log");
         assert_eq!(
-            artifacts.len(),
-            1,
-            "legacy artifact with spell_content_hash NULL but spell_id match must be loaded"
+            logged_fields,
+            vec!["tags".to_string()],
+            "a format-only class_list change must not be logged"
         );
-        assert_eq!(artifacts[0].path, "legacy.md");
     }
 
     #[test]
-    fn test_get_spell_from_conn_legacy_artifact_schema_without_hash_column() {
-        let conn = Connection::open_in_memory().expect("open in-memory sqlite");
+    fn test_normalize_spell_list_columns_backfills_mixed_rows() {
+        let conn = setup_spell_update_test_db();
         conn.execute_batch(
             r#"
-            CREATE TABLE spell (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                school TEXT,
-                sphere TEXT,
-                class_list TEXT,
-                level INTEGER NOT NULL,
-                range TEXT,
-                components TEXT,
-                material_components TEXT,
-                casting_time TEXT,
-                duration TEXT,
-                area TEXT,
-                saving_throw TEXT,
-                reversible INTEGER,
-                description TEXT NOT NULL,
-                tags TEXT,
-                source TEXT,
-                edition TEXT,
-                author TEXT,
-                license TEXT,
-                is_quest_spell INTEGER NOT NULL DEFAULT 0,
-                is_cantrip INTEGER NOT NULL DEFAULT 0,
-                damage TEXT,
-                magic_resistance TEXT,
-                schema_version INTEGER,
-                updated_at TEXT,
-                canonical_data TEXT,
-                content_hash TEXT
-            );
-            CREATE TABLE artifact (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                spell_id INTEGER,
-                type TEXT NOT NULL,
-                path TEXT NOT NULL,
-                hash TEXT NOT NULL,
-                imported_at TEXT NOT NULL
-            );
-            "#,
-        )
-        .expect("create legacy spell+artifact test schema");
-        conn.execute(
-            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
-            [],
+            INSERT INTO spell (id, name, level, description, class_list, tags)
+            VALUES (1, 'Comma', 1, 'Desc', 'Wizard, Bard', 'Fire, AoE');
+            INSERT INTO spell (id, name, level, description, class_list, tags)
+            VALUES (2, 'Json', 1, 'Desc', '["Bard","Wizard"]', '["AoE","Fire"]');
+            INSERT INTO spell (id, name, level, description, class_list, tags)
+            VALUES (3, 'Unsorted Json', 1, 'Desc', '["Wizard","Bard"]', NULL);
+            "#,
         )
-        .expect("insert spell");
+        .expect("seed mixed list rows");
+
+        let updated = normalize_spell_list_columns_with_conn(&conn).expect("backfill lists");
+        assert_eq!(updated, 2, "already-canonical rows must be left alone");
+
+        let mut stmt = conn
+            .prepare("SELECT class_list, tags FROM spell ORDER BY id")
+            .expect("prepare");
+        let rows: Vec<(Option<String>, Option<String>)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .expect("query")
+            .collect::<Result<_, _>>()
+            .expect("collect");
+        for (class_list, _) in &rows {
+            assert_eq!(class_list.as_deref(), Some(r#"["Bard","Wizard"]"#));
+        }
+        assert_eq!(rows[0].1.as_deref(), Some(r#"["AoE","Fire"]"#));
+        assert_eq!(rows[1].1.as_deref(), Some(r#"["AoE","Fire"]"#));
+        assert_eq!(rows[2].1, None);
+    }
+
+    #[test]
+    fn test_fallback_mechanic_fields_flags_unknown_magic_resistance() {
+        use crate::models::MagicResistanceSpec;
+
+        let mut spell = CanonicalSpell::new("Odd MR".into(), 1, "ARCANE".into(), "Desc".into());
+        spell.magic_resistance = Some(MagicResistanceSpec {
+            kind: MagicResistanceKind::Unknown,
+            source_text: Some("see table".into()),
+            ..Default::default()
+        });
+        assert_eq!(
+            fallback_mechanic_fields(&spell),
+            vec!["magic_resistance".to_string()]
+        );
+
+        spell.magic_resistance = Some(MagicResistanceSpec {
+            kind: MagicResistanceKind::Unknown,
+            source_text: Some("None".into()),
+            ..Default::default()
+        });
+        assert!(fallback_mechanic_fields(&spell).is_empty());
+    }
+
+    #[test]
+    fn test_fallback_mechanic_fields_flags_special_casting_time() {
+        use crate::models::canonical_spell::SpellCastingTime;
+
+        let mut spell =
+            CanonicalSpell::new("Odd Casting".into(), 1, "ARCANE".into(), "Desc".into());
+        spell.casting_time = Some(SpellCastingTime {
+            text: "until the next full moon".into(),
+            unit: CastingTimeUnit::Special,
+            ..Default::default()
+        });
+        assert_eq!(
+            fallback_mechanic_fields(&spell),
+            vec!["casting_time".to_string()]
+        );
+
+        spell.casting_time = Some(SpellCastingTime {
+            text: "1 round".into(),
+            unit: CastingTimeUnit::Round,
+            base_value: Some(1.0),
+            ..Default::default()
+        });
+        assert!(fallback_mechanic_fields(&spell).is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicate_spells_groups_identical_content() {
+        let conn = setup_spell_update_test_db();
+        let detail = |name: &str, description: &str| SpellDetail {
+            name: name.into(),
+            school: Some("Evocation".into()),
+            level: 3,
+            description: description.into(),
+            ..Default::default()
+        };
+        let (_, dup_hash, dup_json) =
+            canonicalize_spell_detail(detail("Fireball", "A burst of flame"))
+                .expect("canonicalize");
+        let (_, other_hash, other_json) =
+            canonicalize_spell_detail(detail("Lightning Bolt", "A stroke of lightning"))
+                .expect("canonicalize");
+        for (name, description, json, hash) in [
+            ("Fireball", "A burst of flame", &dup_json, &dup_hash),
+            ("Fireball", "A burst of flame", &dup_json, &dup_hash),
+            (
+                "Lightning Bolt",
+                "A stroke of lightning",
+                &other_json,
+                &other_hash,
+            ),
+        ] {
+            conn.execute(
+                "INSERT INTO spell (name, school, level, description, canonical_data, content_hash)
+                 VALUES (?, 'Evocation', 3, ?, ?, ?)",
+                params![name, description, json, hash],
+            )
+            .expect("insert spell");
+        }
         conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at) VALUES (1, 'source', 'legacy-schema.md', 'ah', '2026-01-01T00:00:00Z')",
+            "INSERT INTO spell (name, level, description, content_hash) VALUES ('Legacy', 1, 'x', NULL),
+             ('Legacy', 1, 'x', NULL)",
             [],
         )
-        .expect("insert legacy artifact row");
+        .expect("insert unhashed spells");
+
+        let groups = find_duplicate_spells_with_conn(&conn).expect("find duplicates");
+        assert_eq!(groups.len(), 1, "only the identical pair forms a group");
+        assert_eq!(groups[0].hash, dup_hash);
+        let ids: Vec<i64> = groups[0].spells.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_repair_content_hashes_fixes_corrupted_hash() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_spell_update_test_db();
+        for (name, description) in [("Sleep", "Slumber"), ("Light", "Glow")] {
+            let (_, hash, json) = canonicalize_spell_detail(SpellDetail {
+                name: name.into(),
+                school: Some("Enchantment".into()),
+                level: 1,
+                description: description.into(),
+                ..Default::default()
+            })
+            .expect("canonicalize");
+            conn.execute(
+                "INSERT INTO spell (name, school, level, description, canonical_data, content_hash)
+                 VALUES (?, 'Enchantment', 1, ?, ?, ?)",
+                params![name, description, json, hash],
+            )
+            .expect("insert spell");
+        }
+        assert!(verify_content_hashes_with_conn(&conn)
+            .expect("verify")
+            .is_empty());
+
+        let correct: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 2", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
         conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at) VALUES (1, 'errata', 'legacy-schema-2.md', 'bh', '2026-01-01T00:00:01Z')",
-            [],
+            "UPDATE spell SET content_hash = ? WHERE id = 2",
+            ["f".repeat(64)],
         )
-        .expect("insert second legacy artifact row");
+        .unwrap();
 
-        let detail = get_spell_from_conn(&conn, 1)
-            .expect("get spell")
-            .expect("some");
-        let artifacts = detail.artifacts.expect("artifacts loaded");
         assert_eq!(
-            artifacts.len(),
-            2,
-            "legacy artifact schema should still load all matching rows by spell_id fallback"
+            verify_content_hashes_with_conn(&conn).expect("verify"),
+            vec![2]
         );
-        assert_eq!(artifacts[0].path, "legacy-schema.md");
-        assert_eq!(artifacts[1].path, "legacy-schema-2.md");
+        let repair = repair_content_hashes_with_conn(&conn).expect("repair");
+        assert_eq!(repair.repaired, vec![2]);
+        assert!(repair.skipped.is_empty());
+
+        let (stored, json): (String, String) = conn
+            .query_row(
+                "SELECT content_hash, canonical_data FROM spell WHERE id = 2",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(stored, correct);
+        let canonical: CanonicalSpell = serde_json::from_str(&json).unwrap();
+        assert_eq!(canonical.id.as_deref(), Some(correct.as_str()));
+        assert!(verify_content_hashes_with_conn(&conn)
+            .expect("verify")
+            .is_empty());
     }
 
     #[test]
-    fn test_get_spell_from_conn_artifact_exposes_spell_content_hash() {
-        let conn = setup_get_spell_artifact_test_db();
+    fn test_repair_content_hashes_detects_column_edits_and_reports_taken_hashes() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_spell_update_test_db();
+        for (name, description) in [("Sleep", "Slumber"), ("Sleep", "Deep slumber")] {
+            let (_, hash, json) = canonicalize_spell_detail(SpellDetail {
+                name: name.into(),
+                school: Some("Enchantment".into()),
+                level: 1,
+                description: description.into(),
+                ..Default::default()
+            })
+            .expect("canonicalize");
+            conn.execute(
+                "INSERT INTO spell (name, school, level, description, canonical_data, content_hash)
+                 VALUES (?, 'Enchantment', 1, ?, ?, ?)",
+                params![name, description, json, hash],
+            )
+            .expect("insert spell");
+        }
+        let first_hash: String = conn
+            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+
+        // A direct column edit leaves canonical_data and content_hash behind.
         conn.execute(
-            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            "UPDATE spell SET description = 'Heavy slumber' WHERE id = 1",
             [],
         )
-        .expect("insert spell");
+        .unwrap();
+        // The same edit on the second row makes it a duplicate of the repaired first row.
         conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (NULL, 'source', 'a.md', 'ah', '2026-01-01T00:00:00Z', 'spell-hash-1')",
+            "UPDATE spell SET description = 'Heavy slumber' WHERE id = 2",
             [],
         )
-        .expect("insert artifact with spell_content_hash set");
+        .unwrap();
+        assert_eq!(
+            verify_content_hashes_with_conn(&conn).expect("verify"),
+            vec![1, 2]
+        );
 
-        let detail = get_spell_from_conn(&conn, 1)
-            .expect("get spell")
-            .expect("some");
-        let artifact = &detail.artifacts.expect("loaded")[0];
+        let repair = repair_content_hashes_with_conn(&conn).expect("repair");
+        assert_eq!(repair.repaired, vec![1]);
+        assert_eq!(repair.skipped, vec![2]);
+        assert_eq!(repair.warnings.len(), 1);
+        assert!(
+            repair.warnings[0].contains("spell 1"),
+            "{:?}",
+            repair.warnings
+        );
+
+        let (repaired_hash, json): (String, String) = conn
+            .query_row(
+                "SELECT content_hash, canonical_data FROM spell WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_ne!(repaired_hash, first_hash);
+        let canonical: CanonicalSpell = serde_json::from_str(&json).unwrap();
+        assert_eq!(canonical.description, "Heavy slumber");
         assert_eq!(
-            artifact.spell_content_hash,
-            Some("spell-hash-1".to_string()),
-            "artifact should expose spell_content_hash when column is populated"
+            verify_content_hashes_with_conn(&conn).expect("verify"),
+            vec![2]
         );
     }
 
     #[test]
-    fn test_get_spell_from_conn_artifact_null_spell_content_hash_for_legacy() {
-        let conn = setup_get_spell_artifact_test_db();
+    fn test_class_spell_level_overrides_and_falls_back() {
+        let conn = setup_spell_update_test_db();
+        conn.execute_batch(include_str!(
+            "../../../../../db/migrations/0021_class_spell_level.sql"
+        ))
+        .unwrap();
         conn.execute(
-            "INSERT INTO spell (id, name, level, description, content_hash) VALUES (1, 'Test', 1, 'Desc', 'spell-hash-1')",
+            "INSERT INTO spell (id, name, level, description, class_list)
+             VALUES (1, 'Dispel Magic', 3, 'X', 'Wizard, Cleric'),
+                    (2, 'Light', 1, 'X', 'Wizard, Cleric')",
             [],
         )
-        .expect("insert spell");
-        conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash) VALUES (1, 'source', 'legacy.md', 'ah', '2026-01-01T00:00:00Z', NULL)",
-            [],
+        .unwrap();
+        replace_class_spell_levels(
+            &conn,
+            1,
+            &HashMap::from([("Wizard".to_string(), 3), ("Cleric".to_string(), 4)]),
         )
-        .expect("insert legacy artifact with null spell_content_hash");
+        .unwrap();
 
-        let detail = get_spell_from_conn(&conn, 1)
-            .expect("get spell")
-            .expect("some");
-        let artifact = &detail.artifacts.expect("loaded")[0];
         assert_eq!(
-            artifact.spell_content_hash, None,
-            "legacy artifact (NULL spell_content_hash) should return None"
+            get_class_spell_level_with_conn(&conn, 1, "Wizard").unwrap(),
+            3
         );
-    }
+        assert_eq!(
+            get_class_spell_level_with_conn(&conn, 1, "cleric").unwrap(),
+            4
+        );
+        assert_eq!(
+            get_class_spell_level_with_conn(&conn, 1, "Druid").unwrap(),
+            3
+        );
+        assert_eq!(
+            get_class_spell_level_with_conn(&conn, 2, "Cleric").unwrap(),
+            1
+        );
+        assert!(matches!(
+            get_class_spell_level_with_conn(&conn, 99, "Cleric"),
+            Err(AppError::NotFound(_))
+        ));
 
-    #[test]
-    fn test_validate_cantrip_level() {
-        // Cantrip must be level 0
-        assert!(validate_epic_and_quest_spells(0, &None, false, true).is_ok());
-        assert!(validate_epic_and_quest_spells(1, &None, false, true).is_err());
+        let cleric = list_spells_by_class_with_conn(&conn, "Cleric", 0).unwrap();
+        let levels: Vec<(&str, i64)> = cleric.iter().map(|s| (s.name.as_str(), s.level)).collect();
+        assert_eq!(levels, vec![("Light", 1), ("Dispel Magic", 4)]);
     }
 
     #[test]
-    fn test_validate_quest_spell_restrictions() {
-        // Quest spells must be level 8 (Quest level in this app)
-        assert!(validate_epic_and_quest_spells(8, &Some("Priest".into()), true, false).is_ok());
-        assert!(validate_epic_and_quest_spells(10, &Some("Priest".into()), true, false).is_err());
-        assert!(validate_epic_and_quest_spells(1, &Some("Priest".into()), true, false).is_err());
-
-        // Quest spells must be for Divine classes
-        assert!(validate_epic_and_quest_spells(8, &Some("Priest".into()), true, false).is_ok());
-        assert!(validate_epic_and_quest_spells(8, &Some("Cleric".into()), true, false).is_ok());
-        assert!(validate_epic_and_quest_spells(8, &Some("Druid".into()), true, false).is_ok());
-        assert!(validate_epic_and_quest_spells(8, &Some("Paladin".into()), true, false).is_ok());
-        assert!(validate_epic_and_quest_spells(8, &Some("Ranger".into()), true, false).is_ok());
+    fn test_get_spells_by_source_matches_exactly_and_counts_sources() {
+        let conn = setup_spell_update_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, source)
+             VALUES (1, 'Sleep', 1, 'X', 'PHB'),
+                    (2, 'Fireball', 3, 'X', 'phb '),
+                    (3, 'Alarm', 1, 'X', 'PHB'),
+                    (4, 'Nahal''s Reckless Dweomer', 1, 'X', 'PHB Errata'),
+                    (5, 'Unsourced', 1, 'X', NULL)",
+            [],
+        )
+        .unwrap();
 
-        // Not a divine class
-        assert!(validate_epic_and_quest_spells(8, &Some("Wizard".into()), true, false).is_err());
-        assert!(validate_epic_and_quest_spells(8, &Some("Fighter".into()), true, false).is_err());
-    }
+        let phb = get_spells_by_source_with_conn(&conn, "Phb", 0).unwrap();
+        let names: Vec<&str> = phb.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Alarm", "Sleep", "Fireball"]);
 
-    #[test]
-    fn test_validate_epic_spell_restrictions() {
-        // Spells > 9 must be for Arcane classes (Wizard/Mage)
-        assert!(validate_epic_and_quest_spells(10, &Some("Wizard".into()), false, false).is_ok());
-        assert!(validate_epic_and_quest_spells(10, &Some("Mage".into()), false, false).is_ok());
-        assert!(validate_epic_and_quest_spells(10, &Some("Priest".into()), false, false).is_err());
+        let counts: Vec<(String, i64)> = list_sources_with_counts_with_conn(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|u| (u.source, u.count))
+            .collect();
+        assert_eq!(counts.len(), 2);
+        assert!(counts[0].0.eq_ignore_ascii_case("PHB"));
+        assert_eq!(counts[0].1, 3);
+        assert_eq!(counts[1], ("PHB Errata".to_string(), 1));
     }
 
     #[test]
-    fn test_canonicalize_spell_detail_rejects_mutually_exclusive_school_and_sphere() {
-        let detail = SpellDetail {
-            name: "Mutually Exclusive".to_string(),
-            level: 1,
-            description: "Invalid direct-write payload".to_string(),
-            school: Some("Abjuration".to_string()),
-            sphere: Some("Combat".to_string()),
-            ..Default::default()
-        };
+    fn test_level_histogram_fills_gaps_with_zero() {
+        let conn = setup_spell_update_test_db();
+        assert!(get_level_histogram_with_conn(&conn).unwrap().is_empty());
 
-        let err = canonicalize_spell_detail(detail).expect_err(
-            "direct writes should reject school/sphere combinations that import rejects",
-        );
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description)
+             VALUES (1, 'Sleep', 1, 'X'), (2, 'Light', 1, 'X'), (3, 'Fireball', 3, 'X')",
+            [],
+        )
+        .unwrap();
 
-        assert!(
-            err.to_string().contains("mutually exclusive"),
-            "unexpected error: {err}"
-        );
+        let histogram: Vec<(i64, i64)> = get_level_histogram_with_conn(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.level, c.count))
+            .collect();
+        assert_eq!(histogram, vec![(1, 2), (2, 0), (3, 1)]);
     }
 
     #[test]
-    fn test_canonicalize_spell_detail_rejects_oversized_direct_write_text_fields() {
-        let cases = [
-            (
-                "name",
-                SpellDetail {
-                    name: repeated_text(257),
-                    level: 1,
-                    description: "Valid description".to_string(),
-                    school: Some("Abjuration".to_string()),
-                    ..Default::default()
-                },
-                "/name",
-            ),
-            (
-                "description",
-                SpellDetail {
-                    name: "Oversized Description".to_string(),
-                    level: 1,
-                    description: repeated_text(16_385),
-                    school: Some("Abjuration".to_string()),
-                    ..Default::default()
-                },
-                "/description",
-            ),
-            (
-                "source",
-                SpellDetail {
-                    name: "Oversized Source".to_string(),
-                    level: 1,
-                    description: "Valid description".to_string(),
-                    school: Some("Abjuration".to_string()),
-                    source: Some(repeated_text(513)),
-                    ..Default::default()
-                },
-                "/source_refs/0/book",
-            ),
-            (
-                "author",
-                SpellDetail {
-                    name: "Oversized Author".to_string(),
-                    level: 1,
-                    description: "Valid description".to_string(),
-                    school: Some("Abjuration".to_string()),
-                    author: Some(repeated_text(257)),
-                    ..Default::default()
-                },
-                "/author",
-            ),
-        ];
+    fn test_list_changes_by_actor_filters_user_and_reparse_edits() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::load_migrations(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description) VALUES (1, 'Sleep', 1, 'X')",
+            [],
+        )
+        .unwrap();
+        log_changes(
+            &conn,
+            1,
+            vec![("range".into(), "30 yards".into(), "60 yards".into())],
+            USER_CHANGE_LOG_ACTOR,
+        )
+        .unwrap();
+        log_changes(
+            &conn,
+            1,
+            vec![(
+                "duration".into(),
+                "5 rounds".into(),
+                "5 rounds/level".into(),
+            )],
+            USER_CHANGE_LOG_ACTOR,
+        )
+        .unwrap();
+        conn.execute(
+            "UPDATE change_log SET actor = 'reparse' WHERE field = 'duration'",
+            [],
+        )
+        .unwrap();
 
-        for (field_name, detail, expected_path) in cases {
-            let err = canonicalize_spell_detail(detail)
-                .expect_err("oversized direct-write fields should be rejected");
-            let message = err.to_string();
-            assert!(
-                message.contains(expected_path),
-                "expected {field_name} error to mention {expected_path}, got: {message}"
-            );
-        }
-    }
+        let user = list_changes_by_actor_with_conn(&conn, "local", 10, 0).unwrap();
+        assert_eq!(user.total, 1);
+        assert_eq!(user.changes.len(), 1);
+        assert_eq!(user.changes[0].field.as_deref(), Some("range"));
+        assert_eq!(user.changes[0].spell_name, "Sleep");
 
-    #[test]
-    fn test_canonicalize_spell_detail_accepts_exact_boundary_direct_write_text_fields() {
-        let detail = SpellDetail {
-            name: repeated_text(256),
-            level: 3,
-            description: repeated_text(16_384),
-            school: Some("Abjuration".to_string()),
-            source: Some(repeated_text(512)),
-            author: Some(repeated_text(256)),
-            ..Default::default()
-        };
+        let reparse = list_changes_by_actor_with_conn(&conn, " reparse ", 10, 0).unwrap();
+        assert_eq!(reparse.total, 1);
+        assert_eq!(reparse.changes[0].field.as_deref(), Some("duration"));
 
-        let (canonical, hash, _json) = canonicalize_spell_detail(detail)
-            .expect("exact-boundary direct-write spell should validate");
+        assert!(matches!(
+            list_changes_by_actor_with_conn(&conn, "  ", 10, 0),
+            Err(AppError::Validation(_))
+        ));
+    }
 
-        assert_eq!(canonical.name.len(), 256);
-        assert_eq!(canonical.description.len(), 16_384);
-        assert_eq!(canonical.author.as_deref().map(str::len), Some(256));
-        assert_eq!(
-            canonical.source_refs.len(),
+    #[test]
+    fn test_list_recent_changes_is_newest_first_with_names() {
+        let conn = Connection::open_in_memory().unwrap();
+        crate::db::migrations::load_migrations(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description)
+             VALUES (1, 'Sleep', 1, 'X'), (2, 'Fireball', 3, 'X'), (3, 'Gone', 2, 'X')",
+            [],
+        )
+        .unwrap();
+        log_changes(
+            &conn,
             1,
-            "direct source should map into a single source_ref"
-        );
-        assert_eq!(canonical.source_refs[0].book.len(), 512);
+            vec![("range".into(), "30 yards".into(), "60 yards".into())],
+            USER_CHANGE_LOG_ACTOR,
+        )
+        .unwrap();
+        log_changes(
+            &conn,
+            2,
+            vec![("level".into(), "3".into(), "4".into())],
+            USER_CHANGE_LOG_ACTOR,
+        )
+        .unwrap();
+        log_changes(
+            &conn,
+            3,
+            vec![("name".into(), "Old".into(), "Gone".into())],
+            USER_CHANGE_LOG_ACTOR,
+        )
+        .unwrap();
+        conn.execute_batch(
+            "UPDATE change_log SET changed_at = '2026-01-01T00:00:00Z' WHERE spell_id = 1;
+             UPDATE change_log SET changed_at = '2026-01-02T00:00:00Z' WHERE spell_id = 2;
+             UPDATE change_log SET changed_at = '2025-12-31T00:00:00Z' WHERE spell_id = 3;
+             -- Orphan the third row the way pre-FK databases left them.
+             PRAGMA foreign_keys = OFF;
+             DELETE FROM spell WHERE id = 3;",
+        )
+        .unwrap();
+
+        let page = list_recent_changes_with_conn(&conn, 2, 0).unwrap();
+        assert_eq!(page.total, 3);
+        let names: Vec<(&str, Option<&str>)> = page
+            .changes
+            .iter()
+            .map(|c| (c.spell_name.as_str(), c.field.as_deref()))
+            .collect();
         assert_eq!(
-            hash.len(),
-            64,
-            "hash should still be computed for valid boundary input"
+            names,
+            vec![("Fireball", Some("level")), ("Sleep", Some("range"))]
         );
-    }
 
-    /// Minimal schema for get_spell_from_conn artifact read-path tests (spell + artifact only).
-    fn setup_get_spell_artifact_test_db() -> Connection {
-        let conn = Connection::open_in_memory().expect("open in-memory sqlite");
-        conn.execute_batch(
-            r#"
-            CREATE TABLE spell (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                school TEXT,
-                sphere TEXT,
-                class_list TEXT,
-                level INTEGER NOT NULL,
-                range TEXT,
-                components TEXT,
-                material_components TEXT,
-                casting_time TEXT,
-                duration TEXT,
-                area TEXT,
-                saving_throw TEXT,
-                reversible INTEGER,
-                description TEXT NOT NULL,
-                tags TEXT,
-                source TEXT,
-                edition TEXT,
-                author TEXT,
-                license TEXT,
-                is_quest_spell INTEGER NOT NULL DEFAULT 0,
-                is_cantrip INTEGER NOT NULL DEFAULT 0,
-                damage TEXT,
-                magic_resistance TEXT,
-                schema_version INTEGER,
-                updated_at TEXT,
-                canonical_data TEXT,
-                content_hash TEXT
-            );
-            CREATE TABLE artifact (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                spell_id INTEGER,
-                type TEXT NOT NULL,
-                path TEXT NOT NULL,
-                hash TEXT NOT NULL,
-                imported_at TEXT NOT NULL,
-                spell_content_hash TEXT
-            );
-            "#,
-        )
-        .expect("create spell+artifact test schema");
-        conn
+        let rest = list_recent_changes_with_conn(&conn, 2, 2).unwrap();
+        assert_eq!(rest.changes.len(), 1);
+        assert_eq!(rest.changes[0].spell_id, Some(3));
+        assert_eq!(rest.changes[0].spell_name, DELETED_SPELL_PLACEHOLDER);
     }
 
-    fn setup_spell_update_test_db() -> Connection {
-        let conn = Connection::open_in_memory().expect("open in-memory sqlite");
-        conn.execute_batch(
-            r#"
-            CREATE TABLE spell (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                school TEXT,
-                sphere TEXT,
-                class_list TEXT,
-                level INTEGER NOT NULL,
-                range TEXT,
-                components TEXT,
-                material_components TEXT,
-                casting_time TEXT,
-                duration TEXT,
-                area TEXT,
-                saving_throw TEXT,
-                reversible INTEGER,
-                description TEXT NOT NULL,
-                tags TEXT,
-                source TEXT,
-                edition TEXT,
-                author TEXT,
-                license TEXT,
-                is_quest_spell INTEGER NOT NULL DEFAULT 0,
-                is_cantrip INTEGER NOT NULL DEFAULT 0,
-                damage TEXT,
-                magic_resistance TEXT,
-                schema_version INTEGER,
-                updated_at TEXT,
-                canonical_data TEXT,
-                content_hash TEXT
-            );
-            CREATE TABLE change_log (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                spell_id INTEGER NOT NULL,
-                field TEXT NOT NULL,
-                old_value TEXT,
-                new_value TEXT
-            );
-            CREATE TABLE artifact (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                spell_id INTEGER NOT NULL,
-                type TEXT NOT NULL,
-                path TEXT NOT NULL,
-                hash TEXT NOT NULL,
-                imported_at TEXT NOT NULL,
-                spell_content_hash TEXT
-            );
-            CREATE TABLE character_class_spell (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                spell_id INTEGER NOT NULL,
-                character_class_id INTEGER NOT NULL,
-                spell_content_hash TEXT
-            );
-            "#,
+    #[test]
+    fn test_outdated_schema_spells_lists_versions_below_current() {
+        let conn = setup_spell_update_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, schema_version)
+             VALUES (1, 'Sleep', 1, 'X', 1), (2, 'Light', 1, 'X', 0),
+                    (3, 'Fireball', 3, 'X', ?), (4, 'Web', 2, 'X', NULL)",
+            [CURRENT_SCHEMA_VERSION],
         )
-        .expect("create spell test schema");
-        conn
+        .unwrap();
+
+        assert_eq!(
+            list_outdated_schema_spells_with_conn(&conn).unwrap(),
+            vec![1, 2, 4]
+        );
+        let by_version: Vec<(i64, i64)> = list_spells_by_schema_version_with_conn(&conn)
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.version, c.count))
+            .collect();
+        assert_eq!(
+            by_version,
+            vec![(0, 2), (1, 1), (CURRENT_SCHEMA_VERSION, 1)]
+        );
     }
 
     #[test]
-    fn test_apply_spell_update_with_conn_persists_vault_file() {
-        let temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+    fn test_verified_flag_toggles_and_survives_spell_update() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
 
-        let conn = setup_spell_update_test_db();
-        let initial_detail = SpellDetail {
-            id: Some(1),
-            name: "Vaulted Spell".to_string(),
-            level: 3,
-            description: "Original description".to_string(),
-            school: Some("Abjuration".to_string()),
-            ..Default::default()
-        };
-        let (canonical, hash, json) =
-            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
+        let mut conn = setup_spell_update_test_db();
         conn.execute(
-            "INSERT INTO spell (
-                id, name, school, sphere, class_list, level, range, components, material_components,
-                casting_time, duration, area, saving_throw, reversible, description, tags, source,
-                edition, author, license, is_quest_spell, is_cantrip, damage, magic_resistance,
-                schema_version, canonical_data, content_hash
-             ) VALUES (
-                1, ?, ?, NULL, NULL, ?, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 0, ?, NULL, NULL,
-                NULL, NULL, NULL, 0, 0, NULL, NULL, ?, ?, ?
-             )",
-            rusqlite::params![
-                initial_detail.name,
-                initial_detail.school,
-                initial_detail.level,
-                initial_detail.description,
-                canonical.schema_version,
-                json,
-                hash,
-            ],
+            "INSERT INTO spell (id, name, level, description, school)
+             VALUES (1, 'Sleep', 1, 'Desc', 'Enchantment'),
+                    (2, 'Light', 1, 'Desc', 'Alteration'),
+                    (3, 'Fireball', 3, 'Desc', 'Evocation')",
+            [],
         )
-        .expect("seed spell row");
+        .expect("seed spell rows");
+        let unverified_ids = |conn: &Connection| -> Vec<i64> {
+            list_unverified_with_conn(conn, 0)
+                .expect("list unverified")
+                .into_iter()
+                .map(|s| s.id)
+                .collect()
+        };
+
+        set_spell_verified_with_conn(&conn, 1, true).expect("verify spell");
+        assert_eq!(unverified_ids(&conn), vec![3, 2]);
+        set_spell_verified_with_conn(&conn, 1, false).expect("unverify spell");
+        assert_eq!(unverified_ids(&conn), vec![3, 2, 1]);
+
+        assert!(matches!(
+            bulk_verify_with_conn(&mut conn, &[1, 99]),
+            Err(AppError::NotFound(_))
+        ));
+        assert_eq!(unverified_ids(&conn).len(), 3, "failed batch rolls back");
+        assert_eq!(bulk_verify_with_conn(&mut conn, &[1, 2]).unwrap(), 2);
+        assert_eq!(unverified_ids(&conn), vec![3]);
 
         let update = SpellUpdate {
             id: 1,
-            name: "Vaulted Spell".to_string(),
-            level: 3,
-            description: "Updated description".to_string(),
-            school: Some("Abjuration".to_string()),
-            is_quest_spell: 0,
-            is_cantrip: 0,
+            name: "Sleep".to_string(),
+            level: 1,
+            description: "Edited description".to_string(),
+            school: Some("Enchantment".to_string()),
             ..Default::default()
         };
-
         apply_spell_update_with_conn(&conn, &update).expect("update spell");
-
-        let updated_hash: String = conn
-            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
+        let verified: i64 = conn
+            .query_row("SELECT verified FROM spell WHERE id = 1", [], |row| {
                 row.get(0)
             })
-            .expect("query updated hash");
-        let vault_file = temp_dir
-            .path()
-            .join("spells")
-            .join(format!("{updated_hash}.json"));
-        assert!(
-            vault_file.exists(),
-            "spell update should materialize the corresponding vault file"
-        );
+            .expect("query verified");
+        assert_eq!(verified, 1, "editing a spell must not reset verified");
     }
 
     #[test]
-    fn test_apply_spell_update_with_conn_cascades_hash_references() {
+    fn test_locked_flag_toggles_and_survives_spell_update() {
         let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
 
         let conn = setup_spell_update_test_db();
-        let initial_detail = SpellDetail {
-            id: Some(1),
-            name: "Cascade Spell".to_string(),
-            level: 3,
-            description: "Original description".to_string(),
-            school: Some("Abjuration".to_string()),
-            ..Default::default()
-        };
-        let (canonical, old_hash, json) =
-            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
         conn.execute(
-            "INSERT INTO spell (
-                id, name, school, sphere, class_list, level, range, components, material_components,
-                casting_time, duration, area, saving_throw, reversible, description, tags, source,
-                edition, author, license, is_quest_spell, is_cantrip, damage, magic_resistance,
-                schema_version, canonical_data, content_hash
-             ) VALUES (
-                1, ?, ?, NULL, NULL, ?, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 0, ?, NULL, NULL,
-                NULL, NULL, NULL, 0, 0, NULL, NULL, ?, ?, ?
-             )",
-            rusqlite::params![
-                initial_detail.name,
-                initial_detail.school,
-                initial_detail.level,
-                initial_detail.description,
-                canonical.schema_version,
-                json,
-                old_hash,
-            ],
+            "INSERT INTO spell (id, name, level, description, school)
+             VALUES (1, 'Sleep', 1, 'Desc', 'Enchantment')",
+            [],
         )
         .expect("seed spell row");
-        conn.execute(
-            "INSERT INTO character_class_spell (spell_id, character_class_id, spell_content_hash)
-             VALUES (1, 7, ?1)",
-            [old_hash.as_str()],
-        )
-        .expect("seed character class reference");
-        conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
-             VALUES (1, 'source', 'spells/cascade.md', 'artifact-hash', '2026-03-08T00:00:00Z', ?1)",
-            [old_hash.as_str()],
-        )
-        .expect("seed artifact reference");
+
+        assert!(!is_spell_locked_with_conn(&conn, 1).unwrap());
+        set_spell_locked_with_conn(&conn, 1, true).expect("lock spell");
+        assert!(is_spell_locked_with_conn(&conn, 1).unwrap());
+        assert!(matches!(
+            set_spell_locked_with_conn(&conn, 99, true),
+            Err(AppError::NotFound(_))
+        ));
+        assert!(!is_spell_locked_with_conn(&conn, 99).unwrap());
 
         let update = SpellUpdate {
             id: 1,
-            name: "Cascade Spell".to_string(),
-            level: 3,
-            description: "Updated description".to_string(),
-            school: Some("Abjuration".to_string()),
-            is_quest_spell: 0,
-            is_cantrip: 0,
+            name: "Sleep".to_string(),
+            level: 1,
+            description: "Edited description".to_string(),
+            school: Some("Enchantment".to_string()),
             ..Default::default()
         };
-
         apply_spell_update_with_conn(&conn, &update).expect("update spell");
+        assert!(
+            is_spell_locked_with_conn(&conn, 1).unwrap(),
+            "editing a spell must not clear locked"
+        );
 
-        let new_hash: String = conn
-            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
-                row.get(0)
-            })
-            .expect("query updated hash");
-        assert_ne!(new_hash, old_hash, "test update should produce a new hash");
+        set_spell_locked_with_conn(&conn, 1, false).expect("unlock spell");
+        assert!(!is_spell_locked_with_conn(&conn, 1).unwrap());
+    }
 
-        let class_hash: String = conn
-            .query_row(
-                "SELECT spell_content_hash FROM character_class_spell WHERE spell_id = 1",
-                [],
-                |row| row.get(0),
-            )
-            .expect("query class hash");
-        let artifact_hash: String = conn
-            .query_row(
-                "SELECT spell_content_hash FROM artifact WHERE spell_id = 1",
-                [],
-                |row| row.get(0),
-            )
-            .expect("query artifact hash");
+    #[test]
+    fn test_validate_reverse_links_flags_self_reference_and_unreciprocated() {
+        let conn = setup_spell_update_test_db();
+        conn.execute_batch(
+            r#"
+            INSERT INTO spell (id, name, level, description, reverse_name) VALUES
+                (1, 'Cure Light Wounds', 1, 'd', 'cause light wounds'),
+                (2, 'Cause Light Wounds', 1, 'd', ' Cure Light Wounds '),
+                (3, 'Light', 1, 'd', 'Light'),
+                (4, 'Bless', 1, 'd', 'Curse'),
+                (5, 'Curse', 1, 'd', 'Cure Light Wounds'),
+                (6, 'Enlarge', 1, 'd', 'Reduce');
+            "#,
+        )
+        .expect("seed reverse links");
 
-        assert_eq!(class_hash, new_hash);
-        assert_eq!(artifact_hash, new_hash);
+        let flagged = validate_reverse_links_with_conn(&conn).expect("validate reverse links");
+        assert_eq!(flagged, vec![3, 4, 5]);
     }
 
     #[test]
-    fn test_run_in_savepoint_rolls_back_and_releases_on_error() {
-        let conn = Connection::open_in_memory().expect("open db");
-        conn.execute("CREATE TABLE test_tx (value TEXT NOT NULL)", [])
-            .expect("create test table");
+    fn test_reverse_name_round_trips_through_create_update_and_validation() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        let conn = setup_spell_update_test_db();
 
-        let err = run_in_savepoint(&conn, "test_tx_write", || {
-            conn.execute("INSERT INTO test_tx (value) VALUES ('pending')", [])?;
-            Err::<(), AppError>(AppError::Validation("boom".to_string()))
-        })
-        .expect_err("savepoint should bubble error");
-        assert!(err.to_string().contains("boom"));
+        let light_id = create_spell_with_conn(
+            &conn,
+            SpellCreate {
+                name: "Light".to_string(),
+                level: 1,
+                description: "A glowing orb.".to_string(),
+                school: Some("Alteration".to_string()),
+                reversible: Some(1),
+                reverse_name: Some(" Light ".to_string()),
+                ..Default::default()
+            },
+        )
+        .expect("create light");
+        let light = get_spell_from_conn(&conn, light_id)
+            .expect("read light")
+            .expect("light exists");
+        assert_eq!(light.reverse_name.as_deref(), Some("Light"));
+        assert_eq!(
+            validate_reverse_links_with_conn(&conn).expect("validate"),
+            vec![light_id],
+            "a reverse naming the spell itself is flagged"
+        );
 
-        let count: i64 = conn
-            .query_row("SELECT COUNT(*) FROM test_tx", [], |row| row.get(0))
-            .expect("query row count");
-        assert_eq!(count, 0, "savepoint should roll back failed writes");
+        let mut update = spell_detail_to_update(&light, light_id);
+        update.reverse_name = Some("Darkness".to_string());
+        apply_spell_update_with_conn(&conn, &update).expect("set reverse name");
+        let updated = get_spell_from_conn(&conn, light_id)
+            .expect("read light")
+            .expect("light exists");
+        assert_eq!(updated.reverse_name.as_deref(), Some("Darkness"));
 
-        run_in_savepoint(&conn, "test_tx_write", || {
-            conn.execute("INSERT INTO test_tx (value) VALUES ('committed')", [])?;
-            Ok::<(), AppError>(())
-        })
-        .expect("savepoint should be reusable after rollback");
+        update.reverse_name = None;
+        update.description = "A brighter orb.".to_string();
+        apply_spell_update_with_conn(&conn, &update).expect("edit without reverse name");
+        let kept = get_spell_from_conn(&conn, light_id)
+            .expect("read light")
+            .expect("light exists");
+        assert_eq!(kept.reverse_name.as_deref(), Some("Darkness"));
 
-        let stored: String = conn
-            .query_row("SELECT value FROM test_tx", [], |row| row.get(0))
-            .expect("query committed value");
-        assert_eq!(stored, "committed");
+        update.reverse_name = Some("  ".to_string());
+        apply_spell_update_with_conn(&conn, &update).expect("clear reverse name");
+        let cleared = get_spell_from_conn(&conn, light_id)
+            .expect("read light")
+            .expect("light exists");
+        assert_eq!(cleared.reverse_name, None);
     }
 
     #[test]
-    fn test_apply_spell_update_with_conn_rolls_back_on_vault_export_failure() {
-        let long_root = std::env::temp_dir()
-            .join("spellbook-backend")
-            .join("a".repeat(240));
-        let _env = VaultTestEnvGuard::with_root(long_root.clone()).expect("set isolated vault env");
-
+    fn test_upsert_by_logical_id_updates_in_place_and_preserves_flags() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
         let conn = setup_spell_update_test_db();
-        let initial_detail = SpellDetail {
-            id: Some(1),
-            name: "Rollback Spell".to_string(),
-            level: 3,
-            description: "Original description".to_string(),
-            school: Some("Abjuration".to_string()),
+
+        let bundled = SpellDetail {
+            name: "Magic Missile".to_string(),
+            level: 1,
+            description: "Original text".to_string(),
+            school: Some("Evocation".to_string()),
             ..Default::default()
         };
-        let (canonical, old_hash, json) =
-            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
-        conn.execute(
-            "INSERT INTO spell (
-                id, name, school, sphere, class_list, level, range, components, material_components,
-                casting_time, duration, area, saving_throw, reversible, description, tags, source,
-                edition, author, license, is_quest_spell, is_cantrip, damage, magic_resistance,
-                schema_version, canonical_data, content_hash
-             ) VALUES (
-                1, ?, ?, NULL, NULL, ?, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 0, ?, NULL, NULL,
-                NULL, NULL, NULL, 0, 0, NULL, NULL, ?, ?, ?
-             )",
-            rusqlite::params![
-                initial_detail.name,
-                initial_detail.school,
-                initial_detail.level,
-                initial_detail.description,
-                canonical.schema_version,
-                json,
-                old_hash,
-            ],
-        )
-        .expect("seed spell row");
-        conn.execute(
-            "INSERT INTO character_class_spell (spell_id, character_class_id, spell_content_hash)
-             VALUES (1, 7, ?1)",
-            [old_hash.as_str()],
-        )
-        .expect("seed character class reference");
-        conn.execute(
-            "INSERT INTO artifact (spell_id, type, path, hash, imported_at, spell_content_hash)
-             VALUES (1, 'source', 'spells/rollback.md', 'artifact-hash', '2026-03-08T00:00:00Z', ?1)",
-            [old_hash.as_str()],
+        let mut skipped = Vec::new();
+        let mut warnings = Vec::new();
+        let first_id = upsert_by_logical_id_with_conn(
+            &conn,
+            bundled.clone(),
+            "mm-001",
+            false,
+            &mut skipped,
+            &mut warnings,
         )
-        .expect("seed artifact reference");
+        .expect("first upsert inserts")
+        .expect("new spell is inserted");
+        set_spell_verified_with_conn(&conn, first_id, true).expect("verify spell");
+        set_spell_locked_with_conn(&conn, first_id, true).expect("lock spell");
 
-        let update = SpellUpdate {
-            id: 1,
-            name: "Rollback Spell".to_string(),
-            level: 3,
-            description: "Updated description".to_string(),
-            school: Some("Abjuration".to_string()),
-            is_quest_spell: 0,
-            is_cantrip: 0,
-            ..Default::default()
+        let resynced = SpellDetail {
+            id: Some(999),
+            description: "Synced text".to_string(),
+            ..bundled
         };
+        let blocked = upsert_by_logical_id_with_conn(
+            &conn,
+            resynced.clone(),
+            "mm-001",
+            false,
+            &mut skipped,
+            &mut warnings,
+        )
+        .expect("locked upsert runs");
+        assert_eq!(blocked, None, "locked spell must not be overwritten");
+        assert_eq!(skipped, vec!["Magic Missile".to_string()]);
+        assert_eq!(warnings.len(), 1);
+        let description: String = conn
+            .query_row("SELECT description FROM spell", [], |row| row.get(0))
+            .expect("read description");
+        assert_eq!(description, "Original text");
 
-        let err =
-            apply_spell_update_with_conn(&conn, &update).expect_err("vault export should fail");
-        assert!(err.to_string().contains("Windows path length"));
+        let second_id = upsert_by_logical_id_with_conn(
+            &conn,
+            resynced,
+            " mm-001 ",
+            true,
+            &mut skipped,
+            &mut warnings,
+        )
+        .expect("forced upsert updates");
+        assert_eq!(second_id, Some(first_id));
 
-        let stored_description: String = conn
-            .query_row("SELECT description FROM spell WHERE id = 1", [], |row| {
-                row.get(0)
-            })
-            .expect("query rolled back description");
-        let stored_hash: String = conn
-            .query_row("SELECT content_hash FROM spell WHERE id = 1", [], |row| {
-                row.get(0)
-            })
-            .expect("query rolled back hash");
-        let class_hash: String = conn
-            .query_row(
-                "SELECT spell_content_hash FROM character_class_spell WHERE spell_id = 1",
+        let (rows, description, verified, locked, logical_id): (i64, String, i64, i64, String) =
+            conn.query_row(
+                "SELECT COUNT(*) OVER (), description, verified, locked, logical_id FROM spell",
                 [],
-                |row| row.get(0),
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                    ))
+                },
             )
-            .expect("query rolled back class hash");
-        let artifact_hash: String = conn
+            .expect("read spell row");
+        assert_eq!(rows, 1, "same logical_id must not duplicate the spell");
+        assert_eq!(description, "Synced text");
+        assert_eq!(verified, 1, "upsert must keep the local verified flag");
+        assert_eq!(locked, 1, "upsert must keep the local locked flag");
+        assert_eq!(logical_id, "mm-001");
+
+        assert!(matches!(
+            upsert_by_logical_id_with_conn(
+                &conn,
+                SpellDetail::default(),
+                "  ",
+                false,
+                &mut skipped,
+                &mut warnings,
+            ),
+            Err(AppError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_data_quality_scan_detects_then_fix_repairs() {
+        let conn = setup_spell_update_test_db();
+        conn.execute_batch(
+            r#"
+            INSERT INTO spell (id, name, level, description, school, components, tags)
+            VALUES (1, '  Sleep ', 1, 'Desc', 'enchantment', 'v, s, M', 'Sleep, Charm');
+            INSERT INTO spell (id, name, level, description, school, components, tags)
+            VALUES (2, 'Light', 1, 'Desc', 'Alteration', 'V, M', '["Light"]');
+            "#,
+        )
+        .expect("seed spell rows");
+
+        let report = scan_data_quality_with_conn(&conn).expect("scan");
+        assert_eq!(report.len(), 1, "clean rows are not reported");
+        assert_eq!(report[0].id, 1);
+        assert_eq!(
+            report[0].issues,
+            vec![
+                "name has surrounding whitespace",
+                "school not title-case",
+                "components not in canonical casing",
+                "tags not JSON array",
+            ]
+        );
+        let name: String = conn
+            .query_row("SELECT name FROM spell WHERE id = 1", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(name, "  Sleep ", "scanning must not modify rows");
+
+        assert_eq!(fix_data_quality_with_conn(&conn, &[1, 2]).unwrap(), 1);
+        let (name, school, components, tags): (String, String, String, String) = conn
             .query_row(
-                "SELECT spell_content_hash FROM artifact WHERE spell_id = 1",
+                "SELECT name, school, components, tags FROM spell WHERE id = 1",
                 [],
-                |row| row.get(0),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
             )
-            .expect("query rolled back artifact hash");
-        let change_log_count: i64 = conn
+            .unwrap();
+        assert_eq!(name, "Sleep");
+        assert_eq!(school, "Enchantment");
+        assert_eq!(components, "V, S, M");
+        assert_eq!(tags, r#"["Charm","Sleep"]"#);
+        assert!(scan_data_quality_with_conn(&conn).unwrap().is_empty());
+
+        let logged: i64 = conn
             .query_row(
                 "SELECT COUNT(*) FROM change_log WHERE spell_id = 1",
                 [],
                 |row| row.get(0),
             )
-            .expect("query change log count");
-
-        assert_eq!(stored_description, "Original description");
-        assert_eq!(stored_hash, old_hash);
-        assert_eq!(class_hash, old_hash);
-        assert_eq!(artifact_hash, old_hash);
-        assert_eq!(change_log_count, 0);
+            .unwrap();
+        assert_eq!(logged, 4);
     }
 
     #[test]
-    fn test_apply_spell_update_with_conn_rejects_oversized_direct_write_source() {
+    fn test_data_quality_scan_ignores_spacing_only_casing_differences() {
         let conn = setup_spell_update_test_db();
-        let initial_detail = SpellDetail {
-            id: Some(1),
-            name: "Oversized Source Update".to_string(),
-            level: 2,
-            description: "Original description".to_string(),
-            school: Some("Abjuration".to_string()),
-            ..Default::default()
-        };
-        let (canonical, hash, json) =
-            canonicalize_spell_detail(initial_detail.clone()).expect("canonicalize initial spell");
-        conn.execute(
-            "INSERT INTO spell (
-                id, name, school, sphere, class_list, level, range, components, material_components,
-                casting_time, duration, area, saving_throw, reversible, description, tags, source,
-                edition, author, license, is_quest_spell, is_cantrip, damage, magic_resistance,
-                schema_version, canonical_data, content_hash
-             ) VALUES (
-                1, ?, ?, NULL, NULL, ?, NULL, NULL, NULL, NULL, NULL, NULL, NULL, 0, ?, NULL, NULL,
-                NULL, NULL, NULL, 0, 0, NULL, NULL, ?, ?, ?
-             )",
-            rusqlite::params![
-                initial_detail.name,
-                initial_detail.school,
-                initial_detail.level,
-                initial_detail.description,
-                canonical.schema_version,
-                json,
-                hash,
-            ],
+        conn.execute_batch(
+            r#"
+            INSERT INTO spell (id, name, level, description, school, components)
+            VALUES (1, 'Shield', 1, 'Desc', ' Evocation ', 'V,S');
+            INSERT INTO spell (id, name, level, description, school, components)
+            VALUES (2, 'Haste', 3, 'Desc', ' alteration', 'V,s');
+            "#,
         )
-        .expect("seed spell row");
-
-        let update = SpellUpdate {
-            id: 1,
-            name: "Oversized Source Update".to_string(),
-            level: 2,
-            description: "Original description".to_string(),
-            school: Some("Abjuration".to_string()),
-            source: Some(repeated_text(513)),
-            is_quest_spell: 0,
-            is_cantrip: 0,
-            ..Default::default()
-        };
+        .expect("seed spell rows");
 
-        let err = apply_spell_update_with_conn(&conn, &update)
-            .expect_err("oversized update source should be rejected");
-        assert!(
-            err.to_string().contains("/source_refs/0/book"),
-            "unexpected error: {err}"
+        let report = scan_data_quality_with_conn(&conn).expect("scan");
+        assert_eq!(report.len(), 1, "spacing alone is not a casing issue");
+        assert_eq!(report[0].id, 2);
+        assert_eq!(
+            report[0].issues,
+            vec![
+                "school not title-case",
+                "components not in canonical casing"
+            ]
         );
+    }
 
-        let stored_source: Option<String> = conn
-            .query_row("SELECT source FROM spell WHERE id = 1", [], |row| {
+    #[test]
+    fn test_normalize_all_tags_collapses_case_variants() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_spell_update_test_db();
+        conn.execute(
+            r#"INSERT INTO spell (id, name, level, description, school, tags)
+             VALUES (1, 'Fireball', 3, 'Desc', 'Evocation', 'Fire, Area'),
+                    (2, 'Burning Hands', 1, 'Desc', 'Alteration', '["fire"]'),
+                    (3, 'Sleep', 1, 'Desc', 'Enchantment', '["area","Sleep"]')"#,
+            [],
+        )
+        .expect("seed spell rows");
+        let tags_of = |id: i64| -> Option<String> {
+            conn.query_row("SELECT tags FROM spell WHERE id = ?", [id], |row| {
                 row.get(0)
             })
-            .expect("query source after rejected update");
-        let change_log_count: i64 = conn
+            .expect("query tags")
+        };
+
+        let summary = normalize_all_tags_with_conn(&conn, &[]).expect("normalize tags");
+        assert_eq!(
+            summary,
+            TagNormalizationSummary {
+                spells_updated: 3,
+                variants_collapsed: 2,
+            }
+        );
+        assert_eq!(tags_of(1).as_deref(), Some(r#"["Area","Fire"]"#));
+        assert_eq!(tags_of(2).as_deref(), Some(r#"["Fire"]"#));
+        assert_eq!(tags_of(3).as_deref(), Some(r#"["Area","Sleep"]"#));
+        let logged: i64 = conn
             .query_row(
-                "SELECT COUNT(*) FROM change_log WHERE spell_id = 1",
+                "SELECT COUNT(*) FROM change_log WHERE field = 'tags'",
                 [],
                 |row| row.get(0),
             )
-            .expect("query change log count");
+            .unwrap();
+        // Spell 1 only changed storage form, which the change log does not record.
+        assert_eq!(logged, 2);
 
-        assert_eq!(
-            stored_source, None,
-            "rejected update must not persist source text"
-        );
-        assert_eq!(
-            change_log_count, 0,
-            "rejected update must roll back change log writes"
-        );
+        let again = normalize_all_tags_with_conn(&conn, &["fire".to_string()])
+            .expect("normalize with preferred casing");
+        assert_eq!(again.spells_updated, 2);
+        assert_eq!(tags_of(2).as_deref(), Some(r#"["fire"]"#));
+    }
+
+    #[test]
+    fn test_tag_spells_by_filter_only_tags_matching_spells() {
+        let _temp_dir = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+
+        let conn = setup_spell_update_test_db();
+        conn.execute(
+            "INSERT INTO spell (id, name, level, description, school, tags)
+             VALUES (1, 'Light', 1, 'Desc', 'Alteration', 'Utility'),
+                    (2, 'Sleep', 1, 'Desc', 'Enchantment', NULL),
+                    (3, 'Fireball', 3, 'Desc', 'Evocation', NULL)",
+            [],
+        )
+        .expect("seed spell rows");
+        let level_one = || SearchFilters {
+            level_min: Some(1),
+            level_max: Some(1),
+            ..Default::default()
+        };
+        let tags_of = |id: i64| -> Option<String> {
+            conn.query_row("SELECT tags FROM spell WHERE id = ?", [id], |row| {
+                row.get(0)
+            })
+            .expect("query tags")
+        };
+
+        let affected = retag_spells_by_filter_with_conn(
+            &conn,
+            "",
+            Some(level_one()),
+            &["Underdark".to_string()],
+            false,
+        )
+        .expect("tag by filter");
+        assert_eq!(affected, 2);
+        assert_eq!(tags_of(1).as_deref(), Some(r#"["Underdark","Utility"]"#));
+        assert_eq!(tags_of(2).as_deref(), Some(r#"["Underdark"]"#));
+        assert_eq!(tags_of(3), None);
+
+        let removed = retag_spells_by_filter_with_conn(
+            &conn,
+            "",
+            Some(level_one()),
+            &["underdark".to_string()],
+            true,
+        )
+        .expect("untag by filter");
+        assert_eq!(removed, 2);
+        assert_eq!(tags_of(1).as_deref(), Some(r#"["Utility"]"#));
+        assert_eq!(tags_of(2), None);
     }
 }
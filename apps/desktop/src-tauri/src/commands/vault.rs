@@ -1,7 +1,14 @@
+use crate::commands::spells::{
+    create_spell_with_conn, get_spell_from_conn, list_spell_summaries_with_conn, SpellCache,
+    DEFAULT_DESCRIPTION_PREVIEW_CHARS,
+};
+use crate::db::{IntegrityCheck, VecMode};
 use crate::error::AppError;
 use crate::models::canonical_spell::CanonicalSpell;
+use crate::models::{ConversionConfig, SpellCreate, SpellDetail, SpellSummary};
+use chrono::{DateTime, Utc};
 use dirs::data_dir as system_data_dir;
-use rusqlite::OptionalExtension;
+use rusqlite::{OpenFlags, OptionalExtension};
 use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::Write;
@@ -64,6 +71,8 @@ fn persist_tempfile_replace(
 pub struct VaultSettings {
     pub integrity_check_on_open: bool,
     pub import_source_ref_url_policy: String,
+    /// House-rule time/distance conversions used by `in_rounds_with` and friends.
+    pub conversion: ConversionConfig,
 }
 
 impl Default for VaultSettings {
@@ -71,6 +80,7 @@ impl Default for VaultSettings {
         Self {
             integrity_check_on_open: true,
             import_source_ref_url_policy: IMPORT_SOURCE_REF_URL_POLICY_DROP_REF.to_string(),
+            conversion: ConversionConfig::default(),
         }
     }
 }
@@ -97,6 +107,11 @@ fn load_vault_settings_from_root(root: &Path) -> Result<VaultSettings, AppError>
     Ok(settings)
 }
 
+/// House-rule conversions from the vault settings, for display paths outside this module.
+pub(crate) fn load_conversion_config(root: &Path) -> Result<ConversionConfig, AppError> {
+    Ok(load_vault_settings_from_root(root)?.conversion)
+}
+
 fn write_vault_settings_in_root(root: &Path, settings: &VaultSettings) -> Result<(), AppError> {
     let target_path = vault_settings_path_in_root(root);
     validate_windows_path_length(&target_path)?;
@@ -259,6 +274,24 @@ fn add_directory_to_backup_archive(
     Ok(())
 }
 
+/// Rejects archive entry names that could escape the extraction directory (zip slip).
+fn validate_archive_entry_path(entry_name: &str) -> Result<(), AppError> {
+    for component in Path::new(entry_name).components() {
+        match component {
+            std::path::Component::ParentDir
+            | std::path::Component::RootDir
+            | std::path::Component::Prefix(_) => {
+                return Err(AppError::Validation(format!(
+                    "Invalid zip entry path: {}",
+                    entry_name
+                )));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 fn restore_supporting_files_from_archive(
     archive: &mut ZipArchive<File>,
     staging_dir: &Path,
@@ -275,21 +308,8 @@ fn restore_supporting_files_from_archive(
             continue;
         }
 
-        // Validate to prevent zip slip
+        validate_archive_entry_path(&entry_name)?;
         let entry_path = Path::new(&entry_name);
-        for component in entry_path.components() {
-            match component {
-                std::path::Component::ParentDir
-                | std::path::Component::RootDir
-                | std::path::Component::Prefix(_) => {
-                    return Err(AppError::Validation(format!(
-                        "Invalid zip entry path: {}",
-                        entry_name
-                    )));
-                }
-                _ => {}
-            }
-        }
 
         let output_path = staging_dir.join(entry_path);
         // Ensure path is rooted in staging_dir
@@ -330,6 +350,29 @@ enum VaultMaintenancePhase {
     Idle,
     Import,
     Gc,
+    Backup,
+    Restore,
+    SearchRebuild,
+}
+
+impl VaultMaintenancePhase {
+    fn describe(self) -> &'static str {
+        match self {
+            VaultMaintenancePhase::Idle => "no operation",
+            VaultMaintenancePhase::Import => "an import",
+            VaultMaintenancePhase::Gc => "vault optimization",
+            VaultMaintenancePhase::Backup => "a vault backup",
+            VaultMaintenancePhase::Restore => "a vault restore",
+            VaultMaintenancePhase::SearchRebuild => "a search index rebuild",
+        }
+    }
+
+    fn busy_message(self) -> String {
+        format!(
+            "Another vault operation is in progress ({}). Try again when it finishes.",
+            self.describe()
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -342,6 +385,13 @@ pub struct VaultGcGuard<'a> {
     state: &'a VaultMaintenanceState,
 }
 
+/// Holds the maintenance state in `Backup` or `Restore` until dropped.
+#[derive(Debug)]
+pub struct VaultOperationGuard<'a> {
+    state: &'a VaultMaintenanceState,
+    phase: VaultMaintenancePhase,
+}
+
 #[cfg(test)]
 pub(crate) fn vault_env_lock() -> &'static Mutex<()> {
     use std::sync::OnceLock;
@@ -439,6 +489,11 @@ impl VaultMaintenanceState {
                     "Vault optimization is currently in progress.".to_string(),
                 ));
             }
+            busy @ (VaultMaintenancePhase::Backup
+            | VaultMaintenancePhase::Restore
+            | VaultMaintenancePhase::SearchRebuild) => {
+                return Err(AppError::Import(busy.busy_message()));
+            }
         }
         drop(phase);
         Ok(VaultImportGuard { state: self })
@@ -463,10 +518,49 @@ impl VaultMaintenanceState {
                     "Vault optimization is already in progress.".to_string(),
                 ));
             }
+            busy @ (VaultMaintenancePhase::Backup
+            | VaultMaintenancePhase::Restore
+            | VaultMaintenancePhase::SearchRebuild) => {
+                return Err(AppError::Validation(busy.busy_message()));
+            }
         }
         drop(phase);
         Ok(VaultGcGuard { state: self })
     }
+
+    /// Serializes `backup_vault` against imports, optimization, and restores.
+    pub fn start_backup(&self) -> Result<VaultOperationGuard<'_>, AppError> {
+        self.start_operation(VaultMaintenancePhase::Backup)
+    }
+
+    /// Serializes `restore_vault` against imports, optimization, and backups.
+    pub fn start_restore(&self) -> Result<VaultOperationGuard<'_>, AppError> {
+        self.start_operation(VaultMaintenancePhase::Restore)
+    }
+
+    /// Serializes `rebuild_search_indexes` against the other vault operations.
+    pub fn start_search_rebuild(&self) -> Result<VaultOperationGuard<'_>, AppError> {
+        self.start_operation(VaultMaintenancePhase::SearchRebuild)
+    }
+
+    fn start_operation(
+        &self,
+        next: VaultMaintenancePhase,
+    ) -> Result<VaultOperationGuard<'_>, AppError> {
+        let mut phase = self
+            .phase
+            .lock()
+            .map_err(|_| AppError::Unknown("Vault maintenance state is poisoned".to_string()))?;
+        if *phase != VaultMaintenancePhase::Idle {
+            return Err(AppError::Validation(phase.busy_message()));
+        }
+        *phase = next;
+        drop(phase);
+        Ok(VaultOperationGuard {
+            state: self,
+            phase: next,
+        })
+    }
 }
 
 impl Drop for VaultImportGuard<'_> {
@@ -509,6 +603,16 @@ impl Drop for VaultGcGuard<'_> {
     }
 }
 
+impl Drop for VaultOperationGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut phase) = self.state.phase.lock() {
+            if *phase == self.phase {
+                *phase = VaultMaintenancePhase::Idle;
+            }
+        }
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
 #[serde(crate = "serde")]
 #[serde(rename_all = "camelCase")]
@@ -539,6 +643,48 @@ pub struct VaultGcSummary {
     pub integrity: VaultIntegritySummary,
 }
 
+/// Location and health of the library database. `integrity` is the check run at
+/// startup; when it is not ok the UI should advise restoring a backup.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseInfo {
+    pub path: String,
+    pub size_bytes: u64,
+    pub wal_present: bool,
+    pub schema_version: i64,
+    pub vec_mode: VecMode,
+    pub integrity: IntegrityCheck,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct BackupRecord {
+    pub id: i64,
+    pub path: String,
+    pub created_at: String,
+    pub size: i64,
+    pub kind: String,
+}
+
+/// What a `restore_vault` dry run found in a backup. `problems` lists everything that would
+/// make a real restore fail or bring back bad data; `valid` is true when it is empty.
+/// `verified_hash_count` counts spell files whose content matches their hash filename.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreDryRunReport {
+    pub valid: bool,
+    pub entry_count: usize,
+    pub spell_file_count: usize,
+    pub verified_hash_count: usize,
+    pub has_settings: bool,
+    pub schema_version: Option<i64>,
+    pub database_integrity: Option<IntegrityCheck>,
+    pub problems: Vec<String>,
+}
+
 fn record_unrecoverable(
     summary: &mut VaultIntegritySummary,
     content_hash: &str,
@@ -746,6 +892,73 @@ pub(crate) fn run_vault_gc_with_root(
     Ok(summary)
 }
 
+pub(crate) fn record_backup_with_conn(
+    conn: &rusqlite::Connection,
+    path: &str,
+    size: i64,
+    kind: &str,
+    created_at: DateTime<Utc>,
+) -> Result<BackupRecord, AppError> {
+    let created_at = created_at.to_rfc3339();
+    conn.execute(
+        "INSERT INTO backup_history (path, created_at, size, kind) VALUES (?, ?, ?, ?)",
+        rusqlite::params![path, created_at, size, kind],
+    )?;
+    Ok(BackupRecord {
+        id: conn.last_insert_rowid(),
+        path: path.to_string(),
+        created_at,
+        size,
+        kind: kind.to_string(),
+    })
+}
+
+pub(crate) fn get_last_backup_with_conn(
+    conn: &rusqlite::Connection,
+) -> Result<Option<BackupRecord>, AppError> {
+    Ok(conn
+        .query_row(
+            "SELECT id, path, created_at, size, kind FROM backup_history
+             ORDER BY created_at DESC, id DESC LIMIT 1",
+            [],
+            |row| {
+                Ok(BackupRecord {
+                    id: row.get(0)?,
+                    path: row.get(1)?,
+                    created_at: row.get(2)?,
+                    size: row.get(3)?,
+                    kind: row.get(4)?,
+                })
+            },
+        )
+        .optional()?)
+}
+
+/// Returns true when no backup has been recorded or the most recent one is at
+/// least `interval_days` old as of `now`.
+pub(crate) fn should_backup_with_conn(
+    conn: &rusqlite::Connection,
+    interval_days: i64,
+    now: DateTime<Utc>,
+) -> Result<bool, AppError> {
+    if interval_days < 0 {
+        return Err(AppError::Validation(
+            "interval_days must not be negative".to_string(),
+        ));
+    }
+    let Some(last) = get_last_backup_with_conn(conn)? else {
+        return Ok(true);
+    };
+    let last_at = match DateTime::parse_from_rfc3339(&last.created_at) {
+        Ok(value) => value.with_timezone(&Utc),
+        Err(e) => {
+            warn!(created_at = %last.created_at, error = %e, "Unparseable backup timestamp");
+            return Ok(true);
+        }
+    };
+    Ok(now - last_at >= chrono::Duration::days(interval_days))
+}
+
 pub(crate) fn optimize_vault_with_root(
     conn: &rusqlite::Connection,
     root: &Path,
@@ -795,6 +1008,31 @@ pub async fn set_import_source_ref_url_policy(policy: String) -> Result<VaultSet
     .map_err(|e| AppError::Unknown(e.to_string()))?
 }
 
+#[tauri::command]
+pub async fn get_conversion_config() -> Result<ConversionConfig, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let root = app_data_dir()?;
+        load_conversion_config(&root)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn set_conversion_config(config: ConversionConfig) -> Result<VaultSettings, AppError> {
+    tokio::task::spawn_blocking(move || {
+        config.validate().map_err(AppError::Validation)?;
+
+        let root = app_data_dir()?;
+        let mut settings = load_vault_settings_from_root(&root)?;
+        settings.conversion = config;
+        write_vault_settings_in_root(&root, &settings)?;
+        Ok::<VaultSettings, AppError>(settings)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
 #[tauri::command]
 pub async fn run_vault_integrity_check(
     state: State<'_, Arc<crate::db::pool::Pool>>,
@@ -809,6 +1047,34 @@ pub async fn run_vault_integrity_check(
     .map_err(|e| AppError::Unknown(e.to_string()))?
 }
 
+#[tauri::command]
+pub async fn get_database_info(
+    state: State<'_, Arc<crate::db::pool::Pool>>,
+    vec_mode: State<'_, VecMode>,
+    integrity: State<'_, IntegrityCheck>,
+) -> Result<DatabaseInfo, AppError> {
+    let pool = state.inner().clone();
+    let vec_mode = *vec_mode.inner();
+    let integrity = integrity.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let db_path = app_data_dir()?.join("spellbook.sqlite3");
+        let mut wal_path = db_path.clone().into_os_string();
+        wal_path.push("-wal");
+        let schema_version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        Ok::<DatabaseInfo, AppError>(DatabaseInfo {
+            path: db_path.display().to_string(),
+            size_bytes: fs::metadata(&db_path).map(|m| m.len()).unwrap_or(0),
+            wal_present: PathBuf::from(wal_path).exists(),
+            schema_version,
+            vec_mode,
+            integrity,
+        })
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
 #[tauri::command]
 pub async fn optimize_vault(
     state: State<'_, Arc<crate::db::pool::Pool>>,
@@ -825,72 +1091,117 @@ pub async fn optimize_vault(
     .map_err(|e| AppError::Unknown(e.to_string()))?
 }
 
-#[tauri::command]
-pub async fn backup_vault(
-    pool: tauri::State<'_, std::sync::Arc<crate::db::pool::Pool>>,
-    destination_path: String,
+/// Body of [`backup_vault`]: zips a backup-API snapshot of `conn` with the vault settings
+/// and spell files under `data_dir`, then records it in `backup_history`. Holds the
+/// maintenance state in `Backup` so it cannot overlap an import, optimization or restore.
+pub(crate) fn backup_vault_with_conn(
+    conn: &rusqlite::Connection,
+    data_dir: &Path,
+    maintenance_state: &VaultMaintenanceState,
+    destination_path: &str,
 ) -> Result<String, AppError> {
-    let pool = pool.inner().clone();
-    tokio::task::spawn_blocking(move || {
-        use rusqlite::backup::Backup;
-        use std::time::Duration;
+    use rusqlite::backup::Backup;
+    use std::time::Duration;
 
-        let data_dir = app_data_dir()?;
-        let dest_path = PathBuf::from(&destination_path);
+    let _backup_guard = maintenance_state.start_backup()?;
+    let dest_path = PathBuf::from(destination_path);
 
-        // Ensure parent directory exists
-        if let Some(parent) = dest_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
+    // Ensure parent directory exists
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
 
-        // Create a temporary file for the database backup
-        let temp_db = tempfile::NamedTempFile::new()
-            .map_err(|e| AppError::Unknown(format!("Failed to create temp file: {}", e)))?;
-        let temp_db_path = temp_db.path();
+    // Create a temporary file for the database backup
+    let temp_db = tempfile::NamedTempFile::new()
+        .map_err(|e| AppError::Unknown(format!("Failed to create temp file: {}", e)))?;
+    let temp_db_path = temp_db.path();
 
-        // Use SQLite's backup API to safely backup the database
-        {
-            let src_conn = pool.get()?;
-            let mut dst_conn = rusqlite::Connection::open(temp_db_path)
-                .map_err(|e| AppError::Unknown(format!("Failed to open temp db: {}", e)))?;
+    // Use SQLite's backup API to safely backup the database
+    {
+        let mut dst_conn = rusqlite::Connection::open(temp_db_path)
+            .map_err(|e| AppError::Unknown(format!("Failed to open temp db: {}", e)))?;
 
-            let backup = Backup::new(&src_conn, &mut dst_conn)
-                .map_err(|e| AppError::Unknown(format!("Failed to init backup: {}", e)))?;
+        let backup = Backup::new(conn, &mut dst_conn)
+            .map_err(|e| AppError::Unknown(format!("Failed to init backup: {}", e)))?;
 
-            backup
-                .run_to_completion(5, Duration::from_millis(250), None)
-                .map_err(|e| AppError::Unknown(format!("Failed to backup database: {}", e)))?;
-        }
+        backup
+            .run_to_completion(5, Duration::from_millis(250), None)
+            .map_err(|e| AppError::Unknown(format!("Failed to backup database: {}", e)))?;
+    }
 
-        // Create ZIP archive
-        let file = File::create(&dest_path)
-            .map_err(|e| AppError::Unknown(format!("Failed to create backup file: {}", e)))?;
+    // Create ZIP archive
+    let file = File::create(&dest_path)
+        .map_err(|e| AppError::Unknown(format!("Failed to create backup file: {}", e)))?;
 
-        let mut zip = ZipWriter::new(file);
-        let options = SimpleFileOptions::default()
-            .compression_method(zip::CompressionMethod::Deflated)
-            .unix_permissions(0o644);
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
 
-        // Add the backed-up database to the ZIP
-        zip.start_file("spellbook.sqlite3", options)
-            .map_err(|e| AppError::Unknown(format!("Failed to start zip entry: {}", e)))?;
+    // Add the backed-up database to the ZIP
+    zip.start_file("spellbook.sqlite3", options)
+        .map_err(|e| AppError::Unknown(format!("Failed to start zip entry: {}", e)))?;
 
-        let mut temp_db_file = File::open(temp_db_path)
-            .map_err(|e| AppError::Unknown(format!("Failed to open temp db: {}", e)))?;
-        std::io::copy(&mut temp_db_file, &mut zip)
-            .map_err(|e| AppError::Unknown(format!("Failed to write db to zip: {}", e)))?;
+    let mut temp_db_file = File::open(temp_db_path)
+        .map_err(|e| AppError::Unknown(format!("Failed to open temp db: {}", e)))?;
+    std::io::copy(&mut temp_db_file, &mut zip)
+        .map_err(|e| AppError::Unknown(format!("Failed to write db to zip: {}", e)))?;
 
-        let settings_path = vault_settings_path_in_root(&data_dir);
-        if settings_path.exists() {
-            add_file_to_backup_archive(&mut zip, options, "vault-settings.json", &settings_path)?;
-        }
-        add_directory_to_backup_archive(&mut zip, options, &data_dir, &data_dir.join("spells"))?;
+    let settings_path = vault_settings_path_in_root(data_dir);
+    if settings_path.exists() {
+        add_file_to_backup_archive(&mut zip, options, "vault-settings.json", &settings_path)?;
+    }
+    add_directory_to_backup_archive(&mut zip, options, data_dir, &data_dir.join("spells"))?;
 
-        // Finalize the ZIP archive
-        zip.finish()
-            .map_err(|e| AppError::Unknown(format!("Failed to finalize zip: {}", e)))?;
+    // Finalize the ZIP archive
+    zip.finish()
+        .map_err(|e| AppError::Unknown(format!("Failed to finalize zip: {}", e)))?;
 
-        Ok::<String, AppError>(destination_path)
+    let size = fs::metadata(&dest_path)?.len() as i64;
+    record_backup_with_conn(conn, destination_path, size, "manual", Utc::now())?;
+
+    Ok(destination_path.to_string())
+}
+
+#[tauri::command]
+pub async fn backup_vault(
+    pool: tauri::State<'_, std::sync::Arc<crate::db::pool::Pool>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
+    destination_path: String,
+) -> Result<String, AppError> {
+    let pool = pool.inner().clone();
+    let maintenance_state = maintenance_state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        let data_dir = app_data_dir()?;
+        backup_vault_with_conn(&conn, &data_dir, &maintenance_state, &destination_path)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn get_last_backup(
+    state: State<'_, Arc<crate::db::pool::Pool>>,
+) -> Result<Option<BackupRecord>, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        get_last_backup_with_conn(&conn)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+#[tauri::command]
+pub async fn should_backup(
+    state: State<'_, Arc<crate::db::pool::Pool>>,
+    interval_days: i64,
+) -> Result<bool, AppError> {
+    let pool = state.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        should_backup_with_conn(&conn, interval_days, Utc::now())
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))?
@@ -899,19 +1210,177 @@ pub async fn backup_vault(
 #[tauri::command]
 pub async fn restore_vault(
     pool: tauri::State<'_, std::sync::Arc<crate::db::pool::Pool>>,
+    spell_cache: tauri::State<'_, std::sync::Arc<SpellCache>>,
+    maintenance_state: State<'_, Arc<VaultMaintenanceState>>,
     backup_path: String,
     allow_overwrite: bool,
-) -> Result<(), AppError> {
+    dry_run: Option<bool>,
+) -> Result<Option<RestoreDryRunReport>, AppError> {
+    if dry_run.unwrap_or(false) {
+        return tokio::task::spawn_blocking(move || {
+            dry_run_restore_vault_impl(&PathBuf::from(&backup_path)).map(Some)
+        })
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))?;
+    }
+
+    let _cache_guard = spell_cache.start_write();
     let pool = pool.inner().clone();
+    let maintenance_state = maintenance_state.inner().clone();
     tokio::task::spawn_blocking(move || {
         let backup_file = PathBuf::from(&backup_path);
         let data_dir = app_data_dir()?;
-        restore_vault_impl(pool, &data_dir, &backup_file, allow_overwrite)
+        restore_vault_with_state(
+            pool,
+            &data_dir,
+            &maintenance_state,
+            &backup_file,
+            allow_overwrite,
+        )
+        .map(|_| None)
     })
     .await
     .map_err(|e| AppError::Unknown(e.to_string()))?
 }
 
+fn is_content_hash(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Checks that `backup_file` would restore cleanly without touching the live vault: every
+/// entry path is safe, spell files named by a content hash hold content with that hash,
+/// and the sqlite snapshot opens from a temp copy and passes `PRAGMA integrity_check`.
+/// Problems with the backup are collected in the report rather than returned as errors.
+pub(crate) fn dry_run_restore_vault_impl(
+    backup_file: &Path,
+) -> Result<RestoreDryRunReport, AppError> {
+    if !backup_file.exists() {
+        return Err(AppError::NotFound(format!(
+            "Backup file not found: {}",
+            backup_file.display()
+        )));
+    }
+
+    let file = File::open(backup_file)
+        .map_err(|e| AppError::Unknown(format!("Failed to open backup file: {}", e)))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| AppError::Unknown(format!("Failed to read zip archive: {}", e)))?;
+
+    let mut report = RestoreDryRunReport {
+        valid: false,
+        entry_count: archive.len(),
+        spell_file_count: 0,
+        verified_hash_count: 0,
+        has_settings: false,
+        schema_version: None,
+        database_integrity: None,
+        problems: Vec::new(),
+    };
+
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| AppError::Unknown(format!("Failed to read zip entry: {}", e)))?;
+        let entry_name = entry.name().to_string();
+        if let Err(e) = validate_archive_entry_path(&entry_name) {
+            report.problems.push(e.to_string());
+            continue;
+        }
+        if entry_name == "vault-settings.json" {
+            report.has_settings = true;
+            continue;
+        }
+        let Some(file_name) = entry_name.strip_prefix("spells/") else {
+            continue;
+        };
+        if entry.is_dir() || file_name.is_empty() {
+            continue;
+        }
+        report.spell_file_count += 1;
+        let Some(content_hash) = file_name
+            .strip_suffix(".json")
+            .filter(|h| is_content_hash(h))
+        else {
+            continue;
+        };
+        let mut json = String::new();
+        let verified = std::io::Read::read_to_string(&mut entry, &mut json)
+            .map_err(|e| AppError::Validation(format!("Failed to read spell file: {e}")))
+            .and_then(|_| verify_vault_spell_json(content_hash, &json));
+        match verified {
+            Ok(_) => report.verified_hash_count += 1,
+            Err(e) => report.problems.push(format!("{}: {}", entry_name, e)),
+        }
+    }
+
+    let temp_db = tempfile::NamedTempFile::new()
+        .map_err(|e| AppError::Unknown(format!("Failed to create temp file: {}", e)))?;
+    match archive.by_name("spellbook.sqlite3") {
+        Ok(mut db_file) => {
+            let mut temp_file = File::create(temp_db.path())
+                .map_err(|e| AppError::Unknown(format!("Failed to create temp db: {}", e)))?;
+            std::io::copy(&mut db_file, &mut temp_file)
+                .map_err(|e| AppError::Unknown(format!("Failed to extract db to temp: {}", e)))?;
+            drop(temp_file);
+
+            let checked = (|| -> Result<(i64, IntegrityCheck), rusqlite::Error> {
+                // Read-write on the temp copy: FTS5 tables write while checking their index.
+                let conn = rusqlite::Connection::open_with_flags(
+                    temp_db.path(),
+                    OpenFlags::SQLITE_OPEN_READ_WRITE,
+                )?;
+                let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+                let rows = stmt
+                    .query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<Result<Vec<String>, _>>()?;
+                let ok = rows.len() == 1 && rows[0].eq_ignore_ascii_case("ok");
+                let schema_version = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+                Ok((
+                    schema_version,
+                    IntegrityCheck {
+                        ok,
+                        messages: if ok { vec![] } else { rows },
+                    },
+                ))
+            })();
+            match checked {
+                Ok((schema_version, integrity)) => {
+                    if !integrity.ok {
+                        report.problems.push(format!(
+                            "Database integrity check failed: {}",
+                            integrity.messages.join("; ")
+                        ));
+                    }
+                    report.schema_version = Some(schema_version);
+                    report.database_integrity = Some(integrity);
+                }
+                Err(e) => report
+                    .problems
+                    .push(format!("Database snapshot could not be opened: {}", e)),
+            }
+        }
+        Err(e) => report
+            .problems
+            .push(format!("Database not found in backup: {}", e)),
+    }
+
+    report.valid = report.problems.is_empty();
+    Ok(report)
+}
+
+/// [`restore_vault_impl`] holding the maintenance state in `Restore`, so a restore cannot
+/// overlap an import, optimization or backup.
+pub(crate) fn restore_vault_with_state(
+    pool: std::sync::Arc<crate::db::pool::Pool>,
+    data_dir: &Path,
+    maintenance_state: &VaultMaintenanceState,
+    backup_file: &Path,
+    allow_overwrite: bool,
+) -> Result<(), AppError> {
+    let _restore_guard = maintenance_state.start_restore()?;
+    restore_vault_impl(pool, data_dir, backup_file, allow_overwrite)
+}
+
 pub(crate) fn restore_vault_impl(
     pool: std::sync::Arc<crate::db::pool::Pool>,
     data_dir: &Path,
@@ -1071,6 +1540,215 @@ pub(crate) fn restore_vault_impl(
     Ok(())
 }
 
+#[tauri::command]
+pub async fn restore_vault_to(backup_path: String, target_dir: String) -> Result<String, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let data_dir = app_data_dir()?;
+        let db_path = restore_vault_to_impl(
+            &data_dir,
+            &PathBuf::from(&backup_path),
+            &PathBuf::from(&target_dir),
+        )?;
+        Ok(db_path.to_string_lossy().into_owned())
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+/// Extracts a backup into `target_dir` for side-by-side comparison; the live vault at
+/// `live_root` is never written. Returns the path of the restored sqlite file.
+pub(crate) fn restore_vault_to_impl(
+    live_root: &Path,
+    backup_file: &Path,
+    target_dir: &Path,
+) -> Result<PathBuf, AppError> {
+    if !backup_file.exists() {
+        return Err(AppError::NotFound(format!(
+            "Backup file not found: {}",
+            backup_file.display()
+        )));
+    }
+
+    fs::create_dir_all(target_dir)?;
+    let target_dir = target_dir.canonicalize()?;
+    let live_root = live_root
+        .canonicalize()
+        .unwrap_or_else(|_| live_root.to_path_buf());
+    if target_dir == live_root {
+        return Err(AppError::Validation(
+            "Target directory is the live vault; use restore_vault to overwrite it.".to_string(),
+        ));
+    }
+
+    let db_path = target_dir.join("spellbook.sqlite3");
+    if db_path.exists() {
+        return Err(AppError::Validation(format!(
+            "Target directory already contains a database: {}",
+            db_path.display()
+        )));
+    }
+
+    let file = File::open(backup_file)
+        .map_err(|e| AppError::Unknown(format!("Failed to open backup file: {}", e)))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| AppError::Unknown(format!("Failed to read zip archive: {}", e)))?;
+
+    {
+        let mut db_file = archive
+            .by_name("spellbook.sqlite3")
+            .map_err(|e| AppError::Unknown(format!("Database not found in backup: {}", e)))?;
+        let mut output = File::create(&db_path)
+            .map_err(|e| AppError::Unknown(format!("Failed to create restored db: {}", e)))?;
+        std::io::copy(&mut db_file, &mut output)
+            .map_err(|e| AppError::Unknown(format!("Failed to extract db: {}", e)))?;
+    }
+
+    restore_supporting_files_from_archive(&mut archive, &target_dir)?;
+    Ok(db_path)
+}
+
+fn open_external_db(path: &Path) -> Result<rusqlite::Connection, AppError> {
+    if !path.exists() {
+        return Err(AppError::NotFound(format!(
+            "Database not found: {}",
+            path.display()
+        )));
+    }
+    Ok(rusqlite::Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?)
+}
+
+pub(crate) fn list_spells_in_external_db_impl(path: &Path) -> Result<Vec<SpellSummary>, AppError> {
+    let conn = open_external_db(path)?;
+    list_spell_summaries_with_conn(&conn, DEFAULT_DESCRIPTION_PREVIEW_CHARS)
+}
+
+#[tauri::command]
+pub async fn list_spells_in_external_db(path: String) -> Result<Vec<SpellSummary>, AppError> {
+    tokio::task::spawn_blocking(move || list_spells_in_external_db_impl(&PathBuf::from(&path)))
+        .await
+        .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
+fn spell_create_from_detail(detail: SpellDetail) -> SpellCreate {
+    SpellCreate {
+        name: detail.name,
+        school: detail.school,
+        sphere: detail.sphere,
+        class_list: detail.class_list,
+        level: detail.level,
+        range: detail.range,
+        components: detail.components,
+        material_components: detail.material_components,
+        casting_time: detail.casting_time,
+        duration: detail.duration,
+        area: detail.area,
+        saving_throw: detail.saving_throw,
+        damage: detail.damage,
+        magic_resistance: detail.magic_resistance,
+        reversible: detail.reversible,
+        reverse_name: detail.reverse_name,
+        description: detail.description,
+        tags: detail.tags,
+        source: detail.source,
+        edition: detail.edition,
+        author: detail.author,
+        license: detail.license,
+        is_quest_spell: detail.is_quest_spell,
+        is_cantrip: detail.is_cantrip,
+        range_spec: detail.range_spec,
+        components_spec: detail.components_spec,
+        material_components_spec: detail.material_components_spec,
+        casting_time_spec: detail.casting_time_spec,
+        duration_spec: detail.duration_spec,
+        area_spec: detail.area_spec,
+        saving_throw_spec: detail.saving_throw_spec,
+        damage_spec: detail.damage_spec,
+        magic_resistance_spec: detail.magic_resistance_spec,
+    }
+}
+
+/// Copies one spell from another spellbook database into `conn`. The spell is matched by
+/// its `logical_id` first, so an edited copy of a spell is not duplicated, then by content
+/// hash; on a match the existing id is returned instead. A new copy keeps the source's
+/// `logical_id`.
+pub(crate) fn copy_spell_from_external_with_conn(
+    conn: &rusqlite::Connection,
+    external_path: &Path,
+    spell_id: i64,
+) -> Result<i64, AppError> {
+    let external = open_external_db(external_path)?;
+    let detail = get_spell_from_conn(&external, spell_id)?.ok_or_else(|| {
+        AppError::NotFound(format!(
+            "Spell {} not found in {}",
+            spell_id,
+            external_path.display()
+        ))
+    })?;
+    let logical_id: Option<String> =
+        if crate::db::table_has_column(&external, "spell", "logical_id") {
+            external.query_row(
+                "SELECT logical_id FROM spell WHERE id = ?",
+                [spell_id],
+                |row| row.get(0),
+            )?
+        } else {
+            None
+        };
+    let local_has_logical_id = crate::db::table_has_column(conn, "spell", "logical_id");
+
+    if let Some(logical_id) = logical_id.as_deref().filter(|_| local_has_logical_id) {
+        let existing: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM spell WHERE logical_id = ?",
+                [logical_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(id) = existing {
+            return Ok(id);
+        }
+    }
+    if let Some(hash) = &detail.content_hash {
+        let existing: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM spell WHERE content_hash = ?",
+                [hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(id) = existing {
+            return Ok(id);
+        }
+    }
+
+    let id = create_spell_with_conn(conn, spell_create_from_detail(detail))?;
+    if let Some(logical_id) = logical_id.filter(|_| local_has_logical_id) {
+        conn.execute(
+            "UPDATE spell SET logical_id = ? WHERE id = ?",
+            rusqlite::params![logical_id, id],
+        )?;
+    }
+    Ok(id)
+}
+
+#[tauri::command]
+pub async fn copy_spell_from_external(
+    pool: tauri::State<'_, std::sync::Arc<crate::db::pool::Pool>>,
+    path: String,
+    spell_id: i64,
+) -> Result<i64, AppError> {
+    let pool = pool.inner().clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = pool.get()?;
+        copy_spell_from_external_with_conn(&conn, &PathBuf::from(&path), spell_id)
+    })
+    .await
+    .map_err(|e| AppError::Unknown(e.to_string()))?
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1166,6 +1844,10 @@ mod tests {
         let expected = VaultSettings {
             integrity_check_on_open: false,
             import_source_ref_url_policy: IMPORT_SOURCE_REF_URL_POLICY_REJECT_SPELL.to_string(),
+            conversion: ConversionConfig {
+                rounds_per_turn: 6,
+                ..Default::default()
+            },
         };
 
         write_vault_settings_in_root(temp_dir.path(), &expected).expect("write settings");
@@ -1208,6 +1890,45 @@ mod tests {
             .contains("Invalid import.sourceRefUrlPolicy"));
     }
 
+    #[test]
+    fn test_should_backup_respects_interval_since_last_backup() {
+        let conn = Connection::open_in_memory().expect("open in-memory sqlite");
+        conn.execute_batch(include_str!(
+            "../../../../../db/migrations/0027_backup_history.sql"
+        ))
+        .expect("create backup_history table");
+
+        let now = Utc::now();
+        assert!(get_last_backup_with_conn(&conn).expect("empty").is_none());
+        assert!(should_backup_with_conn(&conn, 7, now).expect("no backups yet"));
+
+        let older = record_backup_with_conn(
+            &conn,
+            "/tmp/old.zip",
+            10,
+            "manual",
+            now - chrono::Duration::days(30),
+        )
+        .expect("record old backup");
+        let recorded = record_backup_with_conn(&conn, "/tmp/backup.zip", 2048, "manual", now)
+            .expect("record backup");
+        assert_ne!(older.id, recorded.id);
+
+        let last = get_last_backup_with_conn(&conn)
+            .expect("query last")
+            .expect("last backup");
+        assert_eq!(last, recorded);
+        assert_eq!(last.size, 2048);
+
+        assert!(!should_backup_with_conn(&conn, 7, now + chrono::Duration::days(3)).unwrap());
+        assert!(should_backup_with_conn(&conn, 7, now + chrono::Duration::days(7)).unwrap());
+        assert!(should_backup_with_conn(&conn, 0, now).unwrap());
+        assert!(matches!(
+            should_backup_with_conn(&conn, -1, now),
+            Err(AppError::Validation(_))
+        ));
+    }
+
     #[test]
     fn test_backup_helpers_include_spell_files_and_settings() {
         let temp_dir = tempfile::tempdir().expect("temp dir");
@@ -1315,6 +2036,7 @@ mod tests {
             &VaultSettings {
                 integrity_check_on_open: true,
                 import_source_ref_url_policy: IMPORT_SOURCE_REF_URL_POLICY_DROP_REF.to_string(),
+                ..Default::default()
             },
         )
         .expect("write initial settings");
@@ -1323,6 +2045,7 @@ mod tests {
             &VaultSettings {
                 integrity_check_on_open: false,
                 import_source_ref_url_policy: IMPORT_SOURCE_REF_URL_POLICY_REJECT_SPELL.to_string(),
+                ..Default::default()
             },
         )
         .expect("overwrite existing settings");
@@ -1467,6 +2190,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_maintenance_state_rejects_concurrent_heavy_operations() {
+        let maintenance_state = Arc::new(VaultMaintenanceState::default());
+        let import_guard = maintenance_state.start_import().expect("start import");
+
+        // A second heavy operation from another thread is rejected, not queued.
+        let backup_err = std::thread::scope(|scope| {
+            scope
+                .spawn(|| maintenance_state.start_backup().map(|_| ()))
+                .join()
+                .expect("backup thread")
+        })
+        .expect_err("backup should not start while import is active");
+        assert!(
+            backup_err
+                .to_string()
+                .contains("Another vault operation is in progress (an import)"),
+            "unexpected error: {backup_err}"
+        );
+
+        drop(import_guard);
+        let backup_guard = maintenance_state.start_backup().expect("start backup");
+        for err in [
+            maintenance_state.start_import().map(|_| ()).unwrap_err(),
+            maintenance_state.start_gc().map(|_| ()).unwrap_err(),
+            maintenance_state.start_restore().map(|_| ()).unwrap_err(),
+        ] {
+            assert!(
+                err.to_string().contains("a vault backup"),
+                "unexpected error: {err}"
+            );
+        }
+
+        drop(backup_guard);
+        maintenance_state
+            .start_restore()
+            .expect("restore starts once backup finishes");
+    }
+
+    #[test]
+    fn test_backup_and_restore_with_state_are_rejected_during_import() {
+        let vault = VaultTestEnvGuard::new_temp().expect("create isolated vault env");
+        std::fs::create_dir_all(vault.path().join("spells")).expect("spells dir");
+        let pool = Arc::new(crate::db::pool::init_db(None, false).expect("init db pool"));
+        let conn = pool.get().expect("pool connection");
+        let maintenance_state = VaultMaintenanceState::default();
+        let destination = vault.path().join("backups").join("during-import.zip");
+        let destination_str = destination.to_string_lossy().into_owned();
+
+        let import_guard = maintenance_state.start_import().expect("start import");
+        let backup_err =
+            backup_vault_with_conn(&conn, vault.path(), &maintenance_state, &destination_str)
+                .expect_err("backup should not run during an import");
+        assert!(
+            backup_err.to_string().contains("(an import)"),
+            "{backup_err}"
+        );
+        assert!(!destination.exists(), "a rejected backup writes nothing");
+        let restore_err = restore_vault_with_state(
+            pool.clone(),
+            vault.path(),
+            &maintenance_state,
+            &destination,
+            true,
+        )
+        .expect_err("restore should not run during an import");
+        assert!(
+            restore_err.to_string().contains("(an import)"),
+            "{restore_err}"
+        );
+
+        drop(import_guard);
+        backup_vault_with_conn(&conn, vault.path(), &maintenance_state, &destination_str)
+            .expect("backup runs once the import finishes");
+        assert!(destination.exists());
+        let last = get_last_backup_with_conn(&conn)
+            .expect("query last backup")
+            .expect("backup recorded");
+        assert_eq!(last.path, destination_str);
+    }
+
     #[test]
     fn test_verify_vault_spell_json_recomputes_canonical_hash_not_raw_bytes() {
         let mut spell = sample_spell();
@@ -1879,6 +2683,209 @@ mod tests {
         assert!(!data_dir.join("vault-settings.json.old").exists());
     }
 
+    #[test]
+    fn test_restore_vault_dry_run_validates_backup_without_touching_live_vault() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let live_dir = temp_dir.path().join("live");
+        let _env = VaultTestEnvGuard::with_root(live_dir.clone()).expect("set isolated vault env");
+        std::fs::create_dir_all(live_dir.join("spells")).expect("create live vault");
+        std::fs::write(live_dir.join("spells").join("live-spell.json"), "live")
+            .expect("live spell");
+        std::fs::write(live_dir.join("vault-settings.json"), "live settings")
+            .expect("live settings");
+
+        let source_db = temp_dir.path().join("source.sqlite3");
+        let schema_version: i64 = {
+            let conn = Connection::open(&source_db).expect("open source db");
+            crate::db::migrations::load_migrations(&conn).expect("migrate source db");
+            conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+                .expect("read schema version")
+        };
+        let spell = sample_spell();
+        let hash = spell.compute_hash().expect("hash");
+
+        let backup_path = temp_dir.path().join("backup.zip");
+        let file = File::create(&backup_path).expect("create backup archive");
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default();
+        add_file_to_backup_archive(&mut zip, options, "spellbook.sqlite3", &source_db)
+            .expect("archive db");
+        zip.start_file(format!("spells/{hash}.json"), options)
+            .expect("start spell entry");
+        zip.write_all(serde_json::to_string(&spell).unwrap().as_bytes())
+            .expect("write spell entry");
+        zip.start_file("vault-settings.json", options)
+            .expect("start settings entry");
+        zip.write_all(b"{}").expect("write settings entry");
+        zip.finish().expect("finish archive");
+
+        let report = dry_run_restore_vault_impl(&backup_path).expect("dry run");
+
+        assert!(report.valid, "problems: {:?}", report.problems);
+        assert_eq!(report.entry_count, 3);
+        assert_eq!(report.spell_file_count, 1);
+        assert_eq!(report.verified_hash_count, 1);
+        assert!(report.has_settings);
+        assert_eq!(report.schema_version, Some(schema_version));
+        assert!(report.database_integrity.expect("integrity checked").ok);
+
+        let mut live_entries: Vec<_> = std::fs::read_dir(&live_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        live_entries.sort();
+        assert_eq!(
+            live_entries,
+            vec![
+                OsString::from("spells"),
+                OsString::from("vault-settings.json")
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(live_dir.join("vault-settings.json")).unwrap(),
+            "live settings"
+        );
+        let live_spells: Vec<_> = std::fs::read_dir(live_dir.join("spells"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(live_spells, vec![OsString::from("live-spell.json")]);
+    }
+
+    #[test]
+    fn test_restore_vault_to_extracts_backup_without_touching_live_vault() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let live_dir = temp_dir.path().join("live");
+        let _env = VaultTestEnvGuard::with_root(live_dir.clone()).expect("set isolated vault env");
+        std::fs::create_dir_all(live_dir.join("spells")).expect("create live vault");
+        std::fs::write(live_dir.join("spells").join("live-spell.json"), "live")
+            .expect("live spell");
+
+        let source_db = temp_dir.path().join("source.sqlite3");
+        {
+            let conn = Connection::open(&source_db).expect("open source db");
+            crate::db::migrations::load_migrations(&conn).expect("migrate source db");
+            conn.execute(
+                "INSERT INTO spell (name, level, description, school) VALUES ('Backed Up Bolt', 3, 'Zap', 'Evocation')",
+                [],
+            )
+            .expect("seed backup spell");
+        }
+        let backup_path = temp_dir.path().join("backup.zip");
+        let file = File::create(&backup_path).expect("create backup archive");
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default();
+        add_file_to_backup_archive(&mut zip, options, "spellbook.sqlite3", &source_db)
+            .expect("archive db");
+        zip.start_file("spells/backup-spell.json", options)
+            .expect("start spell entry");
+        zip.write_all(b"backup").expect("write spell entry");
+        zip.finish().expect("finish archive");
+
+        let target_dir = temp_dir.path().join("compare");
+        let db_path =
+            restore_vault_to_impl(&live_dir, &backup_path, &target_dir).expect("restore to dir");
+
+        assert_eq!(
+            db_path,
+            target_dir.canonicalize().unwrap().join("spellbook.sqlite3")
+        );
+        assert!(target_dir.join("spells").join("backup-spell.json").exists());
+
+        let spells = list_spells_in_external_db_impl(&db_path).expect("list external spells");
+        assert_eq!(spells.len(), 1);
+        assert_eq!(spells[0].name, "Backed Up Bolt");
+
+        let live_entries: Vec<_> = std::fs::read_dir(live_dir.join("spells"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(live_entries, vec![OsString::from("live-spell.json")]);
+        assert!(!live_dir.join("spellbook.sqlite3").exists());
+
+        let err = restore_vault_to_impl(&live_dir, &backup_path, &live_dir)
+            .expect_err("restoring over the live vault must be refused");
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
+    #[test]
+    fn test_copy_spell_from_external_matches_edited_spell_by_logical_id() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let _env = VaultTestEnvGuard::with_root(temp_dir.path().join("vault"))
+            .expect("set isolated vault env");
+        let migrated = |name: &str| {
+            let path = temp_dir.path().join(name);
+            let conn = Connection::open(&path).expect("open db");
+            crate::db::migrations::load_migrations(&conn).expect("migrate db");
+            (path, conn)
+        };
+        let (source_path, source) = migrated("source.sqlite3");
+        let (_, local) = migrated("local.sqlite3");
+
+        let create = |description: &str| SpellCreate {
+            name: "Shared Bolt".into(),
+            level: 3,
+            school: Some("Evocation".into()),
+            description: description.into(),
+            ..Default::default()
+        };
+        let source_id = create_spell_with_conn(&source, create("Original")).expect("seed source");
+        source
+            .execute(
+                "UPDATE spell SET logical_id = 'shared-bolt' WHERE id = ?",
+                [source_id],
+            )
+            .expect("stamp logical id");
+
+        let copied = copy_spell_from_external_with_conn(&local, &source_path, source_id)
+            .expect("first copy");
+        let logical_id: Option<String> = local
+            .query_row(
+                "SELECT logical_id FROM spell WHERE id = ?",
+                [copied],
+                |row| row.get(0),
+            )
+            .expect("read logical id");
+        assert_eq!(logical_id.as_deref(), Some("shared-bolt"));
+
+        // Editing the source changes its content hash but not its logical identity.
+        let before: String = source
+            .query_row(
+                "SELECT content_hash FROM spell WHERE id = ?",
+                [source_id],
+                |row| row.get(0),
+            )
+            .expect("read hash");
+        crate::commands::spells::apply_spell_update_with_conn(
+            &source,
+            &crate::models::SpellUpdate {
+                id: source_id,
+                name: "Shared Bolt".into(),
+                level: 3,
+                school: Some("Evocation".into()),
+                description: "Edited".into(),
+                ..Default::default()
+            },
+        )
+        .expect("edit source");
+        let after: String = source
+            .query_row(
+                "SELECT content_hash FROM spell WHERE id = ?",
+                [source_id],
+                |row| row.get(0),
+            )
+            .expect("read hash");
+        assert_ne!(before, after);
+
+        let recopied = copy_spell_from_external_with_conn(&local, &source_path, source_id)
+            .expect("second copy");
+        assert_eq!(recopied, copied, "an edited spell matches by logical_id");
+        let count: i64 = local
+            .query_row("SELECT COUNT(*) FROM spell", [], |row| row.get(0))
+            .expect("count spells");
+        assert_eq!(count, 1);
+    }
+
     #[test]
     #[ignore]
     fn test_bench_vault_gc_10000() {
@@ -1,4 +1,7 @@
 use crate::error::AppError;
+use crate::models::canonical_spell::CanonicalSpell;
+use crate::models::spell::SpellDetail;
+use crate::utils::spell_parser::SpellParser;
 use rusqlite::Connection;
 use tracing::{info, warn};
 
@@ -32,6 +35,448 @@ fn apply_hash_reference_columns_migration(conn: &Connection) -> Result<(), AppEr
     Ok(())
 }
 
+/// Applies migration 0016: `spell.needs_review` flag for the import review queue.
+///
+/// As with 0015, the column is added here only when missing; the SQL file just
+/// creates the partial index over flagged rows.
+fn apply_needs_review_migration(conn: &Connection) -> Result<(), AppError> {
+    if !crate::db::table_has_column(conn, "spell", "needs_review") {
+        conn.execute(
+            "ALTER TABLE spell ADD COLUMN needs_review INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    let sql = include_str!("../../../../../db/migrations/0016_add_needs_review.sql");
+    conn.execute_batch(sql)?;
+    Ok(())
+}
+
+/// Applies migration 0019: `artifact.is_primary`, the artifact a reparse by spell id reads,
+/// indexed per spell so the primary lookup skips secondary artifacts.
+fn apply_artifact_primary_migration(conn: &Connection) -> Result<(), AppError> {
+    if !crate::db::table_has_column(conn, "artifact", "is_primary") {
+        conn.execute(
+            "ALTER TABLE artifact ADD COLUMN is_primary INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    let sql = include_str!("../../../../../db/migrations/0019_artifact_is_primary.sql");
+    conn.execute_batch(sql)?;
+    Ok(())
+}
+
+/// Applies migration 0022, which has no SQL file: `spell.embedding_hash` for resumable
+/// reembeds. Pending spells are found by comparing each row's hash against its rendered
+/// text, so the column is not indexed.
+fn apply_embedding_hash_migration(conn: &Connection) -> Result<(), AppError> {
+    if !crate::db::table_has_column(conn, "spell", "embedding_hash") {
+        conn.execute("ALTER TABLE spell ADD COLUMN embedding_hash TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Applies migration 0023: `spell.verified` marks spells a GM has reviewed; the index
+/// serves the "unverified" queue.
+fn apply_spell_verified_migration(conn: &Connection) -> Result<(), AppError> {
+    if !crate::db::table_has_column(conn, "spell", "verified") {
+        conn.execute(
+            "ALTER TABLE spell ADD COLUMN verified INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    let sql = include_str!("../../../../../db/migrations/0023_spell_verified.sql");
+    conn.execute_batch(sql)?;
+    Ok(())
+}
+
+/// Applies migration 0025: `spell.logical_id`, the cross-vault identity stamped by
+/// `import_with_hash_ids` imports. Unlike `spell.id` it is the same in every vault that
+/// imported the same bundle.
+fn apply_spell_logical_id_migration(conn: &Connection) -> Result<(), AppError> {
+    if !crate::db::table_has_column(conn, "spell", "logical_id") {
+        conn.execute("ALTER TABLE spell ADD COLUMN logical_id TEXT", [])?;
+    }
+
+    let sql = include_str!("../../../../../db/migrations/0025_spell_logical_id.sql");
+    conn.execute_batch(sql)?;
+    Ok(())
+}
+
+/// Applies migration 0028: `spell.locked` shields hand-curated spells from reparse and
+/// overwrite imports, which look locked rows up by id before writing.
+fn apply_spell_locked_migration(conn: &Connection) -> Result<(), AppError> {
+    if !crate::db::table_has_column(conn, "spell", "locked") {
+        conn.execute(
+            "ALTER TABLE spell ADD COLUMN locked INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+
+    let sql = include_str!("../../../../../db/migrations/0028_spell_locked.sql");
+    conn.execute_batch(sql)?;
+    Ok(())
+}
+
+/// Applies migration 0029: `spell.reverse_name`, the name of a reversible spell's reversed
+/// form (e.g. *Cure Light Wounds* / *Cause Light Wounds*), indexed for lookup by that name.
+fn apply_spell_reverse_name_migration(conn: &Connection) -> Result<(), AppError> {
+    if !crate::db::table_has_column(conn, "spell", "reverse_name") {
+        conn.execute("ALTER TABLE spell ADD COLUMN reverse_name TEXT", [])?;
+    }
+
+    let sql = include_str!("../../../../../db/migrations/0029_spell_reverse_name.sql");
+    conn.execute_batch(sql)?;
+    Ok(())
+}
+
+/// Applies migration 0031, which has no SQL file: re-derives the material components of
+/// spells whose material text has a thousands separator ("a 5,000 gp diamond"). The parser
+/// used to split such text at the comma into two components, so the stored canonical data
+/// and content hash of those spells are stale. A spell whose corrected hash already belongs
+/// to another row is a duplicate and is left for `find_duplicate_spells`.
+fn apply_material_thousands_separator_rehash(conn: &Connection) -> Result<(), AppError> {
+    if !["material_components", "components", "canonical_data"]
+        .iter()
+        .all(|column| crate::db::table_has_column(conn, "spell", column))
+    {
+        return Ok(());
+    }
+    let mut stmt = conn.prepare(
+        "SELECT id, material_components, components, canonical_data, content_hash FROM spell
+         WHERE canonical_data IS NOT NULL
+           AND (material_components GLOB '*[0-9],[0-9][0-9][0-9]*'
+                OR components GLOB '*[0-9],[0-9][0-9][0-9]*')",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let parser = SpellParser::new();
+    for (id, material_components, components, canonical_data, old_hash) in rows {
+        let Ok(mut canonical) = serde_json::from_str::<CanonicalSpell>(&canonical_data) else {
+            warn!(
+                spell_id = id,
+                "canonical_data unreadable; material components not re-derived"
+            );
+            continue;
+        };
+        // Same precedence as `CanonicalSpell::try_from(SpellDetail)`.
+        let materials = material_components
+            .as_deref()
+            .filter(|s| !s.is_empty())
+            .map(|s| parser.parse_material_components(s))
+            .or_else(|| {
+                components
+                    .as_deref()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| parser.extract_materials_from_components_line(s))
+                    .filter(|extracted| !extracted.is_empty())
+            });
+        if materials.is_none() {
+            continue;
+        }
+        let stored = canonical.clone();
+        canonical.material_components = materials;
+        canonical.normalize(None);
+        if canonical == stored {
+            continue;
+        }
+        let hash = match canonical.compute_hash() {
+            Ok(hash) => hash,
+            Err(e) => {
+                warn!(spell_id = id, error = %e, "re-derived material components do not hash");
+                continue;
+            }
+        };
+        let taken: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM spell WHERE content_hash = ? AND id != ?)",
+            rusqlite::params![hash, id],
+            |row| row.get(0),
+        )?;
+        if taken {
+            warn!(
+                spell_id = id,
+                "re-derived content hash belongs to another spell"
+            );
+            continue;
+        }
+        canonical.id = Some(hash.clone());
+        let json = serde_json::to_string(&canonical)
+            .map_err(|e| AppError::Validation(format!("JSON error: {}", e)))?;
+        conn.execute(
+            "UPDATE spell SET canonical_data = ?, content_hash = ? WHERE id = ?",
+            rusqlite::params![json, hash, id],
+        )?;
+        crate::db::cascade_spell_content_hash_refs(conn, old_hash.as_deref(), &hash)?;
+    }
+    Ok(())
+}
+
+/// A canonical field that a parser-change migration re-derives from its legacy text column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReparsedField {
+    Range,
+    Duration,
+    CastingTime,
+    Area,
+    ClassList,
+    Tags,
+    SavingThrow,
+}
+
+impl ReparsedField {
+    fn copy(self, from: &CanonicalSpell, to: &mut CanonicalSpell) {
+        match self {
+            ReparsedField::Range => to.range = from.range.clone(),
+            ReparsedField::Duration => to.duration = from.duration.clone(),
+            ReparsedField::CastingTime => to.casting_time = from.casting_time.clone(),
+            ReparsedField::Area => to.area = from.area.clone(),
+            ReparsedField::ClassList => to.class_list = from.class_list.clone(),
+            ReparsedField::Tags => to.tags = from.tags.clone(),
+            ReparsedField::SavingThrow => to.saving_throw = from.saving_throw.clone(),
+        }
+    }
+}
+
+/// Re-parses `field` from the legacy text columns of spells matching `filter` (an SQL
+/// condition on `spell`) and rewrites their canonical data and content hash, cascading hash
+/// references. Shared by the migrations that follow a parser change, like 0031 does for
+/// material components. Everything else in the stored canonical data is kept. A spell whose
+/// re-derived hash already belongs to another row is a duplicate and is left for
+/// `find_duplicate_spells`. Returns the number of rehashed spells.
+fn rehash_reparsed_field(
+    conn: &Connection,
+    filter: &str,
+    field: ReparsedField,
+) -> Result<usize, AppError> {
+    let columns = [
+        "canonical_data",
+        "range",
+        "area",
+        "duration",
+        "casting_time",
+        "class_list",
+        "tags",
+        "saving_throw",
+    ];
+    if !columns
+        .iter()
+        .all(|column| crate::db::table_has_column(conn, "spell", column))
+    {
+        return Ok(0);
+    }
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, range, area, duration, casting_time, class_list, tags, saving_throw,
+                canonical_data, content_hash
+         FROM spell
+         WHERE canonical_data IS NOT NULL AND ({})",
+        filter
+    ))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                SpellDetail {
+                    range: row.get(1)?,
+                    area: row.get(2)?,
+                    duration: row.get(3)?,
+                    casting_time: row.get(4)?,
+                    class_list: row.get(5)?,
+                    tags: row.get(6)?,
+                    saving_throw: row.get(7)?,
+                    ..Default::default()
+                },
+                row.get::<_, String>(8)?,
+                row.get::<_, Option<String>>(9)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut rehashed = 0;
+    for (id, legacy, canonical_data, old_hash) in rows {
+        let Ok(mut canonical) = serde_json::from_str::<CanonicalSpell>(&canonical_data) else {
+            warn!(
+                spell_id = id,
+                ?field,
+                "canonical_data unreadable; field not re-derived"
+            );
+            continue;
+        };
+        let detail = SpellDetail {
+            name: canonical.name.clone(),
+            level: canonical.level,
+            description: canonical.description.clone(),
+            school: canonical.school.clone(),
+            sphere: canonical.sphere.clone(),
+            ..legacy
+        };
+        let fresh = match CanonicalSpell::try_from(detail) {
+            Ok(fresh) => fresh,
+            Err(e) => {
+                warn!(spell_id = id, ?field, error = %e, "legacy columns do not canonicalize");
+                continue;
+            }
+        };
+        field.copy(&fresh, &mut canonical);
+        canonical.normalize(None);
+        let hash = match canonical.compute_hash() {
+            Ok(hash) => hash,
+            Err(e) => {
+                warn!(spell_id = id, ?field, error = %e, "re-derived field does not hash");
+                continue;
+            }
+        };
+        if old_hash.as_deref() == Some(hash.as_str()) {
+            continue;
+        }
+        let taken: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM spell WHERE content_hash = ? AND id != ?)",
+            rusqlite::params![hash, id],
+            |row| row.get(0),
+        )?;
+        if taken {
+            warn!(
+                spell_id = id,
+                ?field,
+                "re-derived content hash belongs to another spell"
+            );
+            continue;
+        }
+        canonical.id = Some(hash.clone());
+        let json = serde_json::to_string(&canonical)
+            .map_err(|e| AppError::Validation(format!("JSON error: {}", e)))?;
+        conn.execute(
+            "UPDATE spell SET canonical_data = ?, content_hash = ? WHERE id = ?",
+            rusqlite::params![json, hash, id],
+        )?;
+        crate::db::cascade_spell_content_hash_refs(conn, old_hash.as_deref(), &hash)?;
+        rehashed += 1;
+    }
+    Ok(rehashed)
+}
+
+/// Applies migration 0032, which has no SQL file: re-derives the duration of spells whose
+/// instant or permanent duration has a trailing clause ("Instantaneous (see text)",
+/// "Instantaneous; effects last 1 round"). The parser now keeps that clause in `notes` and a
+/// lingering time in `duration`, and canonical schema v3 allows both on instant durations.
+fn apply_instant_duration_notes_rehash(conn: &Connection) -> Result<(), AppError> {
+    let rehashed = rehash_reparsed_field(
+        conn,
+        "(trim(duration) LIKE 'instant%' OR trim(duration) LIKE 'permanent%')
+         AND (duration LIKE '%;%' OR trim(duration) LIKE '%)')",
+        ReparsedField::Duration,
+    )?;
+    info!(rehashed, "re-derived instant and permanent duration notes");
+    Ok(())
+}
+
+/// Applies migration 0033, which has no SQL file: re-derives the fields whose parsing
+/// changed after their spells were stored, each only on rows whose legacy text takes the
+/// new path:
+/// - class list and tags stored as JSON arrays, which used to be split at their commas;
+/// - "0" ranges on area spells (caster origin) and "X or Y" dual ranges;
+/// - special ranges with an embedded distance or keyword ("Special (100 yards)");
+/// - base-plus-per-level usage durations and "N rounds, then M rounds" phased durations;
+/// - action, reaction, bonus and free casting times;
+/// - base-plus-per-level counted areas and "all <subjects> within N radius" areas;
+/// - counted areas with a containing radius ("up to 3 creatures within 30 ft.");
+/// - per-level and hyphenated tile areas ("1 10-ft. square/level");
+/// - repeated saving throws ("Save vs. spell each round (3 rounds)").
+fn apply_parser_changes_rehash(conn: &Connection) -> Result<(), AppError> {
+    const REPARSES: &[(ReparsedField, &str)] = &[
+        (ReparsedField::ClassList, "class_list LIKE '[%'"),
+        (ReparsedField::Tags, "tags LIKE '[%'"),
+        (
+            ReparsedField::Range,
+            "(trim(range) = '0' AND trim(COALESCE(area, '')) != '') OR range LIKE '% or %'",
+        ),
+        (ReparsedField::Range, "trim(range) LIKE '%(%)'"),
+        (
+            ReparsedField::Duration,
+            "duration LIKE '%+%' OR duration LIKE '% plus %' OR duration LIKE '%then%'",
+        ),
+        (
+            ReparsedField::CastingTime,
+            "casting_time LIKE '%action%' OR trim(casting_time) LIKE 'free'
+             OR trim(casting_time) LIKE 'bonus'",
+        ),
+        (
+            ReparsedField::Area,
+            "area LIKE '%+%' OR area LIKE '% plus %' OR trim(area) LIKE 'all %radius'",
+        ),
+        (ReparsedField::Area, "area LIKE '% within %'"),
+        (
+            ReparsedField::Area,
+            "(area LIKE '%square%' OR area LIKE '%hex%' OR area LIKE '%room%'
+              OR area LIKE '%floor%')
+             AND (area LIKE '%/%level%' OR area GLOB '*[0-9]-*')",
+        ),
+        (
+            ReparsedField::SavingThrow,
+            "saving_throw LIKE '%each round%' OR saving_throw LIKE '%each turn%'
+             OR saving_throw LIKE '%twice%' OR saving_throw LIKE '%thrice%'
+             OR saving_throw LIKE '% times%' OR saving_throw LIKE '% saves%'
+             OR saving_throw LIKE '%saving throws%'",
+        ),
+    ];
+    for (field, filter) in REPARSES {
+        let rehashed = rehash_reparsed_field(conn, filter, *field)?;
+        info!(rehashed, ?field, "re-derived field after parser change");
+    }
+    Ok(())
+}
+
+/// How `spell_vec` is backed on this install.
+///
+/// `BlobFallback` means migration 0001 ran without sqlite-vec and created a plain blob
+/// table, or the `vec0` module is not loaded on the init connection; vector distance
+/// functions are unavailable and semantic features degrade.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VecMode {
+    Vec0,
+    BlobFallback,
+}
+
+impl VecMode {
+    pub fn is_degraded(self) -> bool {
+        self == VecMode::BlobFallback
+    }
+}
+
+/// Determines the [`VecMode`] for `conn` after migrations have run: `Vec0` only when
+/// `spell_vec` is a vec0 virtual table and the extension answers `vec_version()`.
+pub fn detect_vec_mode(conn: &Connection) -> VecMode {
+    let table_sql: Option<String> = conn
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE name = 'spell_vec'",
+            [],
+            |row| row.get(0),
+        )
+        .ok();
+    let is_vec0_table = table_sql.is_some_and(|sql| sql.to_lowercase().contains("using vec0"));
+    let vec0_loaded = conn
+        .query_row("SELECT vec_version()", [], |row| row.get::<_, String>(0))
+        .is_ok();
+
+    if is_vec0_table && vec0_loaded {
+        VecMode::Vec0
+    } else {
+        VecMode::BlobFallback
+    }
+}
+
 pub fn load_migrations(conn: &Connection) -> Result<(), AppError> {
     let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
     info!(version, "DB migration start");
@@ -151,7 +596,124 @@ pub fn load_migrations(conn: &Connection) -> Result<(), AppError> {
         conn.execute("PRAGMA user_version = 15", [])?;
     }
 
-    info!(version = 15, "DB migration complete");
+    if version < 16 {
+        info!("Applying migration 0016");
+        apply_needs_review_migration(conn)?;
+        conn.execute("PRAGMA user_version = 16", [])?;
+    }
+
+    if version < 17 {
+        info!("Applying migration 0017");
+        let sql = include_str!("../../../../../db/migrations/0017_character_change_log.sql");
+        conn.execute_batch(sql)?;
+        conn.execute("PRAGMA user_version = 17", [])?;
+    }
+
+    if version < 18 {
+        info!("Applying migration 0018");
+        let sql = include_str!("../../../../../db/migrations/0018_spell_template.sql");
+        conn.execute_batch(sql)?;
+        conn.execute("PRAGMA user_version = 18", [])?;
+    }
+
+    if version < 19 {
+        info!("Applying migration 0019");
+        apply_artifact_primary_migration(conn)?;
+        conn.execute("PRAGMA user_version = 19", [])?;
+    }
+
+    if version < 20 {
+        info!("Applying migration 0020");
+        let sql = include_str!("../../../../../db/migrations/0020_canonical_cache.sql");
+        conn.execute_batch(sql)?;
+        conn.execute("PRAGMA user_version = 20", [])?;
+    }
+
+    if version < 21 {
+        info!("Applying migration 0021");
+        let sql = include_str!("../../../../../db/migrations/0021_class_spell_level.sql");
+        conn.execute_batch(sql)?;
+        conn.execute("PRAGMA user_version = 21", [])?;
+    }
+
+    if version < 22 {
+        info!("Applying migration 0022");
+        apply_embedding_hash_migration(conn)?;
+        conn.execute("PRAGMA user_version = 22", [])?;
+    }
+
+    if version < 23 {
+        info!("Applying migration 0023");
+        apply_spell_verified_migration(conn)?;
+        conn.execute("PRAGMA user_version = 23", [])?;
+    }
+
+    if version < 24 {
+        info!("Applying migration 0024");
+        let sql = include_str!("../../../../../db/migrations/0024_import_conflict_queue.sql");
+        conn.execute_batch(sql)?;
+        conn.execute("PRAGMA user_version = 24", [])?;
+    }
+
+    if version < 25 {
+        info!("Applying migration 0025");
+        apply_spell_logical_id_migration(conn)?;
+        conn.execute("PRAGMA user_version = 25", [])?;
+    }
+
+    if version < 26 {
+        info!("Applying migration 0026");
+        let sql = include_str!("../../../../../db/migrations/0026_settings.sql");
+        conn.execute_batch(sql)?;
+        conn.execute("PRAGMA user_version = 26", [])?;
+    }
+
+    if version < 27 {
+        info!("Applying migration 0027");
+        let sql = include_str!("../../../../../db/migrations/0027_backup_history.sql");
+        conn.execute_batch(sql)?;
+        conn.execute("PRAGMA user_version = 27", [])?;
+    }
+
+    if version < 28 {
+        info!("Applying migration 0028");
+        apply_spell_locked_migration(conn)?;
+        conn.execute("PRAGMA user_version = 28", [])?;
+    }
+
+    if version < 29 {
+        info!("Applying migration 0029");
+        apply_spell_reverse_name_migration(conn)?;
+        conn.execute("PRAGMA user_version = 29", [])?;
+    }
+
+    if version < 30 {
+        info!("Applying migration 0030");
+        let sql =
+            include_str!("../../../../../db/migrations/0030_canonical_cache_invalidation.sql");
+        conn.execute_batch(sql)?;
+        conn.execute("PRAGMA user_version = 30", [])?;
+    }
+
+    if version < 31 {
+        info!("Applying migration 0031");
+        apply_material_thousands_separator_rehash(conn)?;
+        conn.execute("PRAGMA user_version = 31", [])?;
+    }
+
+    if version < 32 {
+        info!("Applying migration 0032");
+        apply_instant_duration_notes_rehash(conn)?;
+        conn.execute("PRAGMA user_version = 32", [])?;
+    }
+
+    if version < 33 {
+        info!("Applying migration 0033");
+        apply_parser_changes_rehash(conn)?;
+        conn.execute("PRAGMA user_version = 33", [])?;
+    }
+
+    info!(version = 33, "DB migration complete");
 
     Ok(())
 }
@@ -160,6 +722,31 @@ pub fn load_migrations(conn: &Connection) -> Result<(), AppError> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_detect_vec_mode_reports_blob_fallback_without_vec0() {
+        // In-memory connections never load sqlite-vec, so migration 0001 takes the
+        // blob-table fallback path.
+        let conn = Connection::open_in_memory().expect("open db");
+        load_migrations(&conn).expect("load migrations");
+
+        let table_sql: String = conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE name = 'spell_vec'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("spell_vec exists");
+        assert!(!table_sql.contains("vec0"), "{table_sql}");
+
+        let mode = detect_vec_mode(&conn);
+        assert_eq!(mode, VecMode::BlobFallback);
+        assert!(mode.is_degraded());
+        assert_eq!(
+            serde_json::to_value(mode).unwrap(),
+            serde_json::json!("blob_fallback")
+        );
+    }
+
     #[test]
     fn test_migration_0015_creates_task_5_index_names() {
         let conn = Connection::open_in_memory().expect("open db");
@@ -199,7 +786,7 @@ mod tests {
             .query_row("PRAGMA user_version", [], |row| row.get(0))
             .expect("query user_version");
 
-        assert_eq!(version, 15);
+        assert_eq!(version, 33);
         assert!(crate::db::table_has_column(
             &conn,
             "character_class_spell",
@@ -224,6 +811,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_load_migrations_adds_needs_review_column() {
+        let conn = Connection::open_in_memory().expect("open db");
+
+        load_migrations(&conn).expect("load migrations");
+
+        assert!(crate::db::table_has_column(&conn, "spell", "needs_review"));
+        let index_exists = conn
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type='index' AND name='idx_spell_needs_review'",
+                [],
+                |row| row.get::<_, i32>(0),
+            )
+            .is_ok();
+        assert!(
+            index_exists,
+            "idx_spell_needs_review must exist after migration 0016"
+        );
+    }
+
     #[test]
     fn test_migration_0015_backfills_existing_hash_references() {
         let conn = Connection::open_in_memory().expect("open db");
@@ -481,7 +1088,7 @@ mod tests {
         let version: i32 = conn
             .query_row("PRAGMA user_version", [], |row| row.get(0))
             .expect("query user_version");
-        assert_eq!(version, 15);
+        assert_eq!(version, 33);
         assert!(crate::db::table_has_column(
             &conn,
             "character_class_spell",
@@ -494,6 +1101,425 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_migration_0031_rehashes_thousands_separated_materials() {
+        use crate::models::spell::SpellDetail;
+
+        let conn = Connection::open_in_memory().expect("open db");
+        load_migrations(&conn).expect("load migrations");
+
+        let detail = SpellDetail {
+            name: "Raise Dead".into(),
+            sphere: Some("Necromantic".into()),
+            level: 5,
+            components: Some("V, S, M".into()),
+            material_components: Some("a 5,000 gp diamond".into()),
+            description: "Returns the dead to life.".into(),
+            ..Default::default()
+        };
+        let mut fixed = CanonicalSpell::try_from(detail).expect("canonicalize");
+        fixed.normalize(None);
+        // What the comma-splitting parser stored before the fix.
+        let mut stale = fixed.clone();
+        stale.material_components = Some(
+            SpellParser::new()
+                .parse_material_components("a 5")
+                .into_iter()
+                .chain(SpellParser::new().parse_material_components("000 gp diamond"))
+                .collect(),
+        );
+        stale.normalize(None);
+        let stale_hash = stale.compute_hash().expect("stale hash");
+        stale.id = Some(stale_hash.clone());
+        conn.execute(
+            "INSERT INTO spell (id, name, level, sphere, components, material_components,
+                                description, canonical_data, content_hash)
+             VALUES (1, 'Raise Dead', 5, 'Necromantic', 'V, S, M', 'a 5,000 gp diamond',
+                     'Returns the dead to life.', ?, ?)",
+            rusqlite::params![serde_json::to_string(&stale).unwrap(), stale_hash],
+        )
+        .expect("insert stale spell");
+        conn.execute(
+            "INSERT INTO artifact (type, hash, spell_id, spell_content_hash)
+             VALUES ('md', 'artifact-hash', 1, ?)",
+            [&stale_hash],
+        )
+        .expect("insert artifact");
+        conn.execute("PRAGMA user_version = 30", [])
+            .expect("rewind user_version");
+
+        load_migrations(&conn).expect("apply migration 0031");
+
+        let (canonical_data, content_hash): (String, String) = conn
+            .query_row(
+                "SELECT canonical_data, content_hash FROM spell WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query spell");
+        let canonical: CanonicalSpell = serde_json::from_str(&canonical_data).unwrap();
+        assert_eq!(canonical.material_components, fixed.material_components);
+        assert!(canonical.material_has_cost());
+        assert_eq!(content_hash, fixed.compute_hash().unwrap());
+        assert_eq!(canonical.id.as_deref(), Some(content_hash.as_str()));
+        let artifact_hash: String = conn
+            .query_row("SELECT spell_content_hash FROM artifact", [], |row| {
+                row.get(0)
+            })
+            .expect("query artifact");
+        assert_eq!(artifact_hash, content_hash);
+    }
+
+    #[test]
+    fn test_migration_0032_rehashes_instant_duration_notes() {
+        use crate::models::duration_spec::{DurationKind, DurationSpec};
+
+        let conn = Connection::open_in_memory().expect("open db");
+        load_migrations(&conn).expect("load migrations");
+
+        let detail = SpellDetail {
+            name: "Magic Missile".into(),
+            school: Some("Evocation".into()),
+            level: 1,
+            duration: Some("Instantaneous; effects last 1 round".into()),
+            description: "Darts of force.".into(),
+            ..Default::default()
+        };
+        let mut fixed = CanonicalSpell::try_from(detail).expect("canonicalize");
+        fixed.normalize(None);
+        // What the parser stored before trailing clauses were split off.
+        let mut stale = fixed.clone();
+        stale.duration = Some(DurationSpec {
+            kind: DurationKind::Special,
+            raw_legacy_value: Some("Instantaneous; effects last 1 round".into()),
+            ..Default::default()
+        });
+        stale.schema_version = 2;
+        stale.normalize(None);
+        let stale_hash = stale.compute_hash().expect("stale hash");
+        stale.id = Some(stale_hash.clone());
+        conn.execute(
+            "INSERT INTO spell (id, name, level, school, duration, description, canonical_data,
+                                content_hash)
+             VALUES (1, 'Magic Missile', 1, 'Evocation', 'Instantaneous; effects last 1 round',
+                     'Darts of force.', ?, ?)",
+            rusqlite::params![serde_json::to_string(&stale).unwrap(), stale_hash],
+        )
+        .expect("insert stale spell");
+        conn.execute(
+            "INSERT INTO artifact (type, hash, spell_id, spell_content_hash)
+             VALUES ('md', 'artifact-hash', 1, ?)",
+            [&stale_hash],
+        )
+        .expect("insert artifact");
+        conn.execute("PRAGMA user_version = 31", [])
+            .expect("rewind user_version");
+
+        load_migrations(&conn).expect("apply migration 0032");
+
+        let (canonical_data, content_hash): (String, String) = conn
+            .query_row(
+                "SELECT canonical_data, content_hash FROM spell WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query spell");
+        let canonical: CanonicalSpell = serde_json::from_str(&canonical_data).unwrap();
+        let duration = canonical.duration.as_ref().expect("duration");
+        assert_eq!(duration.kind, DurationKind::Instant);
+        assert_eq!(duration.notes.as_deref(), Some("effects last 1 round"));
+        assert_eq!(canonical.duration, fixed.duration);
+        assert_eq!(canonical.schema_version, 3);
+        assert_eq!(content_hash, fixed.compute_hash().unwrap());
+        let artifact_hash: String = conn
+            .query_row("SELECT spell_content_hash FROM artifact", [], |row| {
+                row.get(0)
+            })
+            .expect("query artifact");
+        assert_eq!(artifact_hash, content_hash);
+    }
+
+    #[test]
+    fn test_migration_0033_rehashes_fields_changed_by_the_parser() {
+        use crate::models::canonical_spell::{CastingTimeUnit, SpellCastingTime};
+        use crate::models::range_spec::{RangeKind, RangeSpec};
+
+        let conn = Connection::open_in_memory().expect("open db");
+        load_migrations(&conn).expect("load migrations");
+
+        let insert = |id: i64, detail: &SpellDetail, stale: &CanonicalSpell| -> String {
+            let hash = stale.compute_hash().expect("stale hash");
+            let mut stored = stale.clone();
+            stored.id = Some(hash.clone());
+            conn.execute(
+                "INSERT INTO spell (id, name, level, school, range, casting_time, class_list,
+                                    description, canonical_data, content_hash)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                rusqlite::params![
+                    id,
+                    detail.name,
+                    detail.level,
+                    detail.school,
+                    detail.range,
+                    detail.casting_time,
+                    detail.class_list,
+                    detail.description,
+                    serde_json::to_string(&stored).unwrap(),
+                    hash
+                ],
+            )
+            .expect("insert spell");
+            hash
+        };
+
+        let grasp = SpellDetail {
+            name: "Shocking Grasp".into(),
+            school: Some("Evocation".into()),
+            level: 1,
+            range: Some("Touch or 30 ft.".into()),
+            casting_time: Some("1 action".into()),
+            class_list: Some(r#"["Wizard","Bard"]"#.into()),
+            description: "A jolt.".into(),
+            ..Default::default()
+        };
+        let mut fixed = CanonicalSpell::try_from(grasp.clone()).expect("canonicalize");
+        fixed.normalize(None);
+        // What the parsers stored before the changes.
+        let mut stale = fixed.clone();
+        stale.range = Some(RangeSpec {
+            kind: RangeKind::Special,
+            raw_legacy_value: Some("Touch or 30 ft.".into()),
+            ..Default::default()
+        });
+        stale.casting_time = Some(SpellCastingTime {
+            text: "1 action".into(),
+            unit: CastingTimeUnit::Special,
+            base_value: Some(1.0),
+            raw_legacy_value: Some("1 action".into()),
+            ..Default::default()
+        });
+        stale.class_list = vec![r#"["Wizard""#.into(), r#""Bard"]"#.into()];
+        stale.normalize(None);
+        insert(1, &grasp, &stale);
+
+        let sleep = SpellDetail {
+            name: "Sleep".into(),
+            school: Some("Enchantment".into()),
+            level: 1,
+            range: Some("30 yards".into()),
+            casting_time: Some("1 round".into()),
+            class_list: Some("Wizard".into()),
+            description: "Slumber.".into(),
+            ..Default::default()
+        };
+        let mut current = CanonicalSpell::try_from(sleep.clone()).expect("canonicalize");
+        current.normalize(None);
+        let current_hash = insert(2, &sleep, &current);
+
+        conn.execute("PRAGMA user_version = 32", [])
+            .expect("rewind user_version");
+        load_migrations(&conn).expect("apply migration 0033");
+
+        let stored = |id: i64| -> (CanonicalSpell, String) {
+            conn.query_row(
+                "SELECT canonical_data, content_hash FROM spell WHERE id = ?",
+                [id],
+                |row| {
+                    Ok((
+                        serde_json::from_str(&row.get::<_, String>(0)?).unwrap(),
+                        row.get(1)?,
+                    ))
+                },
+            )
+            .expect("query spell")
+        };
+        let (canonical, hash) = stored(1);
+        assert_eq!(canonical.range, fixed.range);
+        assert_eq!(
+            canonical.casting_time.as_ref().map(|ct| ct.unit),
+            Some(CastingTimeUnit::Action)
+        );
+        assert_eq!(canonical.class_list, vec!["Bard", "Wizard"]);
+        assert_eq!(hash, fixed.compute_hash().unwrap());
+        assert_eq!(canonical.id.as_deref(), Some(hash.as_str()));
+
+        assert_eq!(
+            stored(2).1,
+            current_hash,
+            "untouched spells keep their hash"
+        );
+    }
+
+    /// Stores `detail` with the canonical data `stale` makes of its current parse, as a row
+    /// written before a parser change, then reruns migration 0033. Returns the current parse
+    /// and the canonical data and hash stored afterwards.
+    fn rerun_0033_on_stale(
+        detail: SpellDetail,
+        stale: impl FnOnce(&mut CanonicalSpell),
+    ) -> (CanonicalSpell, CanonicalSpell, String) {
+        let conn = Connection::open_in_memory().expect("open db");
+        load_migrations(&conn).expect("load migrations");
+
+        let mut fixed = CanonicalSpell::try_from(detail.clone()).expect("canonicalize");
+        fixed.normalize(None);
+        let mut stored = fixed.clone();
+        stale(&mut stored);
+        stored.normalize(None);
+        let stale_hash = stored.compute_hash().expect("stale hash");
+        stored.id = Some(stale_hash.clone());
+        conn.execute(
+            "INSERT INTO spell (id, name, level, school, range, area, saving_throw, description,
+                                canonical_data, content_hash)
+             VALUES (1, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                detail.name,
+                detail.level,
+                detail.school,
+                detail.range,
+                detail.area,
+                detail.saving_throw,
+                detail.description,
+                serde_json::to_string(&stored).unwrap(),
+                stale_hash
+            ],
+        )
+        .expect("insert spell");
+
+        conn.execute("PRAGMA user_version = 32", [])
+            .expect("rewind user_version");
+        load_migrations(&conn).expect("apply migration 0033");
+
+        let (canonical, hash): (String, String) = conn
+            .query_row(
+                "SELECT canonical_data, content_hash FROM spell WHERE id = 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("query spell");
+        assert_ne!(hash, stale_hash, "the stale row is rehashed");
+        (fixed, serde_json::from_str(&canonical).unwrap(), hash)
+    }
+
+    #[test]
+    fn test_migration_0033_rehashes_counted_areas_within_a_radius() {
+        use crate::models::area_spec::{AreaKind, AreaSpec};
+
+        let (fixed, canonical, hash) = rerun_0033_on_stale(
+            SpellDetail {
+                name: "Hold Person".into(),
+                school: Some("Enchantment".into()),
+                level: 2,
+                area: Some("up to 3 creatures within 30 ft.".into()),
+                description: "Paralysis.".into(),
+                ..Default::default()
+            },
+            |stale| {
+                stale.area = Some(AreaSpec {
+                    kind: AreaKind::Special,
+                    raw_legacy_value: Some("up to 3 creatures within 30 ft.".into()),
+                    ..Default::default()
+                })
+            },
+        );
+        assert_eq!(canonical.area, fixed.area);
+        assert!(canonical.area.as_ref().is_some_and(|a| a.radius.is_some()));
+        assert_eq!(hash, fixed.compute_hash().unwrap());
+    }
+
+    #[test]
+    fn test_migration_0033_rehashes_per_level_and_hyphenated_tile_areas() {
+        use crate::models::area_spec::{AreaKind, AreaSpec};
+        use crate::models::scalar::ScalarMode;
+
+        let (fixed, canonical, hash) = rerun_0033_on_stale(
+            SpellDetail {
+                name: "Wall of Stone".into(),
+                school: Some("Evocation".into()),
+                level: 5,
+                area: Some("1 10-ft. square/level".into()),
+                description: "A wall.".into(),
+                ..Default::default()
+            },
+            |stale| {
+                stale.area = Some(AreaSpec {
+                    kind: AreaKind::Special,
+                    raw_legacy_value: Some("1 10-ft. square/level".into()),
+                    ..Default::default()
+                })
+            },
+        );
+        assert_eq!(canonical.area, fixed.area);
+        assert_eq!(
+            canonical
+                .area
+                .as_ref()
+                .and_then(|a| a.tile_count.as_ref())
+                .map(|count| count.mode.clone()),
+            Some(ScalarMode::PerLevel)
+        );
+        assert_eq!(hash, fixed.compute_hash().unwrap());
+    }
+
+    #[test]
+    fn test_migration_0033_rehashes_special_ranges_with_embedded_text() {
+        use crate::models::range_spec::{RangeKind, RangeSpec};
+
+        let (fixed, canonical, hash) = rerun_0033_on_stale(
+            SpellDetail {
+                name: "Contact Other Plane".into(),
+                school: Some("Divination".into()),
+                level: 5,
+                range: Some("Special (100 yards)".into()),
+                description: "Questions.".into(),
+                ..Default::default()
+            },
+            |stale| {
+                stale.range = Some(RangeSpec {
+                    kind: RangeKind::Special,
+                    text: Some("Special (100 yards)".into()),
+                    raw_legacy_value: Some("Special (100 yards)".into()),
+                    ..Default::default()
+                })
+            },
+        );
+        assert_eq!(canonical.range, fixed.range);
+        assert!(canonical
+            .range
+            .as_ref()
+            .is_some_and(|r| r.distance.is_some()));
+        assert_eq!(hash, fixed.compute_hash().unwrap());
+    }
+
+    #[test]
+    fn test_migration_0033_rehashes_repeated_saving_throws() {
+        use crate::models::saving_throw::SavingThrowKind;
+
+        let (fixed, canonical, hash) = rerun_0033_on_stale(
+            SpellDetail {
+                name: "Hold Monster".into(),
+                school: Some("Enchantment".into()),
+                level: 5,
+                saving_throw: Some("Save vs. spell each round (3 rounds)".into()),
+                description: "Paralysis.".into(),
+                ..Default::default()
+            },
+            |stale| {
+                // Before the change only the first save was kept.
+                if let Some(save) = stale.saving_throw.as_mut() {
+                    save.kind = SavingThrowKind::Single;
+                    save.single = save.multiple.as_ref().and_then(|m| m.first().cloned());
+                    save.multiple = None;
+                }
+            },
+        );
+        assert_eq!(canonical.saving_throw, fixed.saving_throw);
+        assert_eq!(
+            canonical.saving_throw.as_ref().map(|save| &save.kind),
+            Some(&SavingThrowKind::Multiple)
+        );
+        assert_eq!(hash, fixed.compute_hash().unwrap());
+    }
+
     /// Benchmarks migration 0014 FTS rebuild with 10k spells; must complete in < 60s.
     #[test]
     #[ignore]
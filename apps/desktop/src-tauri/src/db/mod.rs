@@ -2,5 +2,9 @@ pub mod migrations;
 pub mod pool;
 pub mod utils;
 
-pub use pool::{app_data_dir, init_db, Pool};
-pub use utils::table_has_column;
+pub use migrations::{detect_vec_mode, VecMode};
+pub use pool::{
+    app_data_dir, check_database_integrity, init_db, init_db_with_status, init_db_with_vec_mode,
+    IntegrityCheck, Pool,
+};
+pub use utils::{cascade_spell_content_hash_refs, table_has_column};
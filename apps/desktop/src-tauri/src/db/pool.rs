@@ -1,3 +1,4 @@
+use super::migrations::VecMode;
 use crate::error::AppError;
 use dirs::data_dir as system_data_dir;
 use r2d2_sqlite::SqliteConnectionManager;
@@ -110,15 +111,72 @@ fn try_load_sqlite_vec(conn: &Connection, data_dir: &Path) {
     let _ = conn.load_extension_disable();
 }
 
+/// Outcome of `PRAGMA quick_check` run when the database is opened. `messages` holds the
+/// problems SQLite reported and is empty when the database is consistent.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityCheck {
+    pub ok: bool,
+    pub messages: Vec<String>,
+}
+
+/// Runs `PRAGMA quick_check` on `conn`. Nothing is repaired or deleted; a failed check
+/// is only reported.
+pub fn check_database_integrity(conn: &Connection) -> Result<IntegrityCheck, AppError> {
+    let mut stmt = conn.prepare("PRAGMA quick_check")?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    let ok = rows.len() == 1 && rows[0].eq_ignore_ascii_case("ok");
+    Ok(IntegrityCheck {
+        ok,
+        messages: if ok { vec![] } else { rows },
+    })
+}
+
 pub fn init_db(resource_dir: Option<&Path>, run_backfill: bool) -> Result<Pool, AppError> {
+    init_db_with_vec_mode(resource_dir, run_backfill).map(|(pool, _)| pool)
+}
+
+/// Like [`init_db`], but also reports whether `spell_vec` is backed by sqlite-vec.
+/// The mode is detected on the connection that loaded the extension.
+pub fn init_db_with_vec_mode(
+    resource_dir: Option<&Path>,
+    run_backfill: bool,
+) -> Result<(Pool, VecMode), AppError> {
+    init_db_with_status(resource_dir, run_backfill).map(|(pool, vec_mode, _)| (pool, vec_mode))
+}
+
+/// Like [`init_db_with_vec_mode`], but also returns the startup [`IntegrityCheck`]. A
+/// crash mid-write can leave `-wal`/`-shm` files behind; the check runs before migrations
+/// so a damaged database is flagged for the UI (which can advise a restore) instead of
+/// silently opened.
+pub fn init_db_with_status(
+    resource_dir: Option<&Path>,
+    run_backfill: bool,
+) -> Result<(Pool, VecMode, IntegrityCheck), AppError> {
     let data_dir = app_data_dir()?;
     let _ = install_sqlite_vec_if_needed(&data_dir, resource_dir)?;
     let db_path = data_dir.join("spellbook.sqlite3");
     let manager = SqliteConnectionManager::file(&db_path);
     let pool = r2d2::Pool::new(manager)?;
-    {
+    let (vec_mode, integrity) = {
         let conn = pool.get()?;
         conn.execute_batch("PRAGMA foreign_keys=ON;")?;
+        let integrity = match check_database_integrity(&conn) {
+            Ok(check) => check,
+            Err(e) => IntegrityCheck {
+                ok: false,
+                messages: vec![e.to_string()],
+            },
+        };
+        if !integrity.ok {
+            error!(
+                db_path = %db_path.display(),
+                problems = ?integrity.messages,
+                "DATABASE INTEGRITY CHECK FAILED; the library may be damaged, consider restoring a backup"
+            );
+        }
         try_load_sqlite_vec(&conn, &data_dir);
         super::migrations::load_migrations(&conn)?;
         if run_backfill {
@@ -126,6 +184,37 @@ pub fn init_db(resource_dir: Option<&Path>, run_backfill: bool) -> Result<Pool,
                 error!(error = %e, "Hash backfill failed");
             }
         }
+        (super::migrations::detect_vec_mode(&conn), integrity)
+    };
+    if vec_mode.is_degraded() {
+        warn!("sqlite-vec: spell_vec is blob-backed; semantic search is degraded");
+    }
+    Ok((pool, vec_mode, integrity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integrity_check_passes_on_known_good_db() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let conn = Connection::open(temp_dir.path().join("good.sqlite3")).expect("open db");
+        conn.execute_batch(
+            "PRAGMA journal_mode=WAL;
+             CREATE TABLE spell (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+             CREATE INDEX idx_spell_name ON spell(name);
+             INSERT INTO spell (name) VALUES ('Sleep'), ('Light');",
+        )
+        .expect("seed db");
+
+        let check = check_database_integrity(&conn).expect("run quick_check");
+        assert_eq!(
+            check,
+            IntegrityCheck {
+                ok: true,
+                messages: vec![],
+            }
+        );
     }
-    Ok(pool)
 }
@@ -1,4 +1,4 @@
-use rusqlite::Connection;
+use rusqlite::{params, Connection};
 
 /// Returns true if `column` exists on `table` in the given SQLite connection.
 /// Used during the schema-migration transition period (migration 0015) where
@@ -10,3 +10,32 @@ pub fn table_has_column(conn: &Connection, table: &str, column: &str) -> bool {
     );
     conn.query_row(&sql, [column], |_| Ok(())).is_ok()
 }
+
+/// Repoints the `spell_content_hash` references in `character_class_spell` and `artifact`
+/// from `old_hash` to `new_hash` after a spell's content hash changes.
+pub fn cascade_spell_content_hash_refs(
+    conn: &Connection,
+    old_hash: Option<&str>,
+    new_hash: &str,
+) -> rusqlite::Result<()> {
+    let Some(old_hash) = old_hash else {
+        return Ok(());
+    };
+    if old_hash == new_hash {
+        return Ok(());
+    }
+
+    if table_has_column(conn, "character_class_spell", "spell_content_hash") {
+        conn.execute(
+            "UPDATE character_class_spell SET spell_content_hash = ? WHERE spell_content_hash = ?",
+            params![new_hash, old_hash],
+        )?;
+    }
+    if table_has_column(conn, "artifact", "spell_content_hash") {
+        conn.execute(
+            "UPDATE artifact SET spell_content_hash = ? WHERE spell_content_hash = ?",
+            params![new_hash, old_hash],
+        )?;
+    }
+    Ok(())
+}
@@ -19,6 +19,9 @@ pub enum AppError {
     #[error("Sidecar error: {0}")]
     Sidecar(String),
 
+    #[error("Sidecar unavailable: {0}")]
+    SidecarUnavailable(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -5,9 +5,10 @@ pub mod models;
 pub mod sidecar;
 pub mod utils;
 
+use commands::spells::SpellCache;
 use commands::vault::VaultMaintenanceState;
 use commands::*;
-use db::init_db;
+use db::init_db_with_status;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::Manager;
@@ -31,33 +32,73 @@ pub fn run() {
                 .map(PathBuf::from)
                 .or_else(|| app.path().resource_dir().ok());
 
-            let pool = init_db(resource_dir.as_deref(), true)
+            let (pool, vec_mode, integrity) = init_db_with_status(resource_dir.as_deref(), true)
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
             app.manage(Arc::new(pool));
+            app.manage(vec_mode);
+            app.manage(integrity);
             app.manage(Arc::new(VaultMaintenanceState::default()));
+            app.manage(Arc::new(SpellCache::default()));
+            app.manage(Arc::new(ReembedState::default()));
             Ok(())
         })
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .invoke_handler(tauri::generate_handler![
             get_spell,
+            get_spell_artifacts,
+            render_spell_at_level,
+            get_spells_batch,
             parse_spell_range,
             parse_spell_duration,
             parse_spell_casting_time,
             parse_spell_area,
             parse_spell_damage,
             parse_spell_components,
+            canonicalize_components,
             parse_spell_components_with_migration,
             parse_spell_material_components,
             extract_materials_from_components_line,
             list_spells,
+            list_spells_by_class,
+            get_spells_by_source,
+            list_sources_with_counts,
+            get_level_histogram,
+            list_spells_by_schema_version,
+            list_outdated_schema_spells,
+            get_class_spell_level,
             create_spell,
             update_spell,
             delete_spell,
+            tag_spells_by_filter,
+            untag_spells_by_filter,
+            normalize_all_tags,
             upsert_spell,
+            upsert_by_logical_id,
+            normalize_spell_list_columns,
+            scan_data_quality,
+            validate_reverse_links,
+            validate_all_spells,
+            fix_data_quality,
+            list_needs_review,
+            clear_review_flag,
+            set_spell_verified,
+            set_spell_locked,
+            bulk_verify,
+            list_unverified,
+            list_spell_templates,
+            save_spell_template,
+            create_spell_from_template,
+            validate_spell_field,
+            find_duplicate_spells,
+            verify_content_hashes,
+            repair_content_hashes,
             list_characters,
             create_character,
             update_character_details,
+            get_character_history,
+            list_recent_changes,
+            list_changes_by_actor,
             delete_character,
             get_character,
             get_character_abilities,
@@ -81,43 +122,95 @@ pub fn run() {
             #[cfg(debug_assertions)]
             test_seed_character_with_orphan_spell,
             get_character_spellbook,
+            find_orphaned_spellbook_entries,
+            prune_orphaned_spellbook_entries,
+            copy_spellbook_entry,
+            learn_spell,
+            learn_spell_by_name,
             update_character_spell,
             search_keyword,
+            get_random_spell,
+            get_spell_count,
             search_semantic,
+            reembed_all_spells,
+            cancel_reembed,
+            get_vec_mode,
+            list_spells_without_embeddings,
+            check_fts_consistency,
+            rebuild_spell_fts,
+            rebuild_search_indexes,
             list_facets,
+            get_tags_with_usage,
+            get_class_facet_counts,
             save_search,
             list_saved_searches,
             delete_saved_search,
             chat_answer,
             preview_import,
+            validate_preview,
             preview_import_spell_json,
             import_spell_json,
+            import_spell_json_file,
+            import_ndjson,
             resolve_import_spell_json,
             import_files,
+            apply_import_plan,
+            import_directory,
+            list_pending_conflicts,
             resolve_import_conflicts,
             reparse_artifact,
+            reparse_artifacts,
+            diff_spell_against_source,
+            set_primary_artifact,
             export_spells,
+            export_search_results,
+            export_spell_comparison,
+            export_spell_index,
+            export_validation_report,
+            refresh_canonical_cache,
+            get_canonical_json,
             export_spell_as_json,
             export_spell_bundle_json,
+            export_spell_bundle_file,
+            export_ndjson,
             print_spell,
             print_spellbook,
+            get_printable_spellbook,
             backup_vault,
+            get_last_backup,
+            should_backup,
             restore_vault,
+            restore_vault_to,
+            list_spells_in_external_db,
+            copy_spell_from_external,
             get_vault_settings,
             run_vault_integrity_check,
+            get_database_info,
             set_import_source_ref_url_policy,
+            get_conversion_config,
+            set_conversion_config,
             set_vault_integrity_check_on_open,
             optimize_vault,
+            get_setting,
+            set_setting,
+            get_all_settings,
+            export_settings,
+            import_settings,
             export_character_bundle,
+            export_character_bundle_file,
             export_character_markdown_zip,
             import_character_bundle,
+            import_character_bundle_file,
             preview_character_markdown_zip,
             import_character_markdown_zip,
+            export_all_characters,
+            import_all_characters,
             export_character_sheet,
             export_character_spellbook_pack,
             search_characters,
             // Prerequisite for ecosystem hash integration (Migration 0015, hash-based import/export).
             crate::models::canonical_spell::migrate_all_spells_to_v2,
+            crate::sidecar::client::sidecar_status,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -371,6 +371,20 @@ pub struct AreaSpec {
 }
 
 impl AreaSpec {
+    /// Edge length in feet of one square tile: the parsed tile size when given in
+    /// feet or yards, otherwise the house-rule `feet_per_square`.
+    pub fn tile_edge_in_feet(&self, config: &crate::models::ConversionConfig) -> Option<f64> {
+        if self.kind != AreaKind::Tiles || self.tile_unit != Some(TileUnit::Square) {
+            return None;
+        }
+        let parsed = match (self.length.as_ref(), self.unit) {
+            (Some(length), Some(AreaUnit::Ft)) => length.fixed_value(),
+            (Some(length), Some(AreaUnit::Yd)) => length.fixed_value().map(|v| v * 3.0),
+            _ => None,
+        };
+        Some(parsed.unwrap_or_else(|| f64::from(config.feet_per_square)))
+    }
+
     pub fn normalize(&mut self) {
         if let Some(n) = &mut self.notes {
             *n = crate::models::canonical_spell::normalize_string(
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+/// One input a batch command could not process, keyed by the id the caller passed in.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct BatchFailure {
+    pub input_id: i64,
+    pub error: String,
+}
+
+/// Shared return shape for batch commands. Non-atomic commands report each input in
+/// either list; atomic commands either succeed for every input or fail them all.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResult<T> {
+    pub succeeded: Vec<T>,
+    pub failed: Vec<BatchFailure>,
+}
+
+impl<T> Default for BatchResult<T> {
+    fn default() -> Self {
+        Self {
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<T> BatchResult<T> {
+    /// Collects per-input outcomes from a non-atomic batch, preserving input order.
+    pub fn from_outcomes(outcomes: impl IntoIterator<Item = (i64, Result<T, String>)>) -> Self {
+        let mut batch = Self::default();
+        for (input_id, outcome) in outcomes {
+            match outcome {
+                Ok(value) => batch.succeeded.push(value),
+                Err(error) => batch.failed.push(BatchFailure { input_id, error }),
+            }
+        }
+        batch
+    }
+
+    /// Shapes an atomic batch: on error every input is reported failed with that error,
+    /// since the whole batch was rolled back.
+    pub fn all_or_nothing<E: std::fmt::Display>(
+        input_ids: &[i64],
+        outcome: Result<Vec<T>, E>,
+    ) -> Self {
+        match outcome {
+            Ok(succeeded) => Self {
+                succeeded,
+                failed: Vec::new(),
+            },
+            Err(error) => {
+                let error = error.to_string();
+                Self {
+                    succeeded: Vec::new(),
+                    failed: input_ids
+                        .iter()
+                        .map(|&input_id| BatchFailure {
+                            input_id,
+                            error: error.clone(),
+                        })
+                        .collect(),
+                }
+            }
+        }
+    }
+}
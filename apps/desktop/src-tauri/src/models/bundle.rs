@@ -18,6 +18,9 @@ pub struct CharacterBundle {
     pub updated_at: Option<String>,
     pub abilities: Option<CharacterAbilities>,
     pub classes: Vec<BundleClass>,
+    /// Legacy per-character `spellbook` rows; empty in bundles written before they were kept.
+    #[serde(default)]
+    pub spellbook: Vec<BundleSpellbookEntry>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -39,6 +42,16 @@ pub struct BundleClassSpell {
     pub notes: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSpellbookEntry {
+    pub spell: SpellDetail,
+    pub prepared: i64,
+    pub known: i64,
+    pub notes: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(crate = "serde")]
 #[serde(rename_all = "camelCase")]
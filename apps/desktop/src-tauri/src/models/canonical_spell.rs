@@ -21,7 +21,7 @@ use std::fmt::Write as _;
 use std::sync::Arc;
 use tauri::{Emitter, State, Window};
 
-pub const CURRENT_SCHEMA_VERSION: i64 = 2;
+pub const CURRENT_SCHEMA_VERSION: i64 = 3;
 
 /// Format version for hash-based spell bundles (JSON import/export). Used by Task 2
 /// import/export to identify bundle structure; increment when the bundle envelope changes.
@@ -38,6 +38,9 @@ pub const SOURCE_REF_BOOK_MAX_CHARS: usize = 512;
 pub const SOURCE_REF_NOTE_MAX_CHARS: usize = 2_048;
 pub const SOURCE_REF_URL_MAX_CHARS: usize = 2_048;
 
+/// Range note added when a legacy "0" range accompanies an area effect.
+pub const AREA_ORIGINATES_AT_CASTER_NOTE: &str = "area originates at caster";
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 #[serde(deny_unknown_fields)]
@@ -65,18 +68,23 @@ pub enum CastingTimeUnit {
     Hour,
     #[serde(alias = "MINUTE", alias = "Minute")]
     Minute,
-    /// Deserialization-only: 5e unit removed from schema in v2. Task 0.1 (v1→v2 migration):
-    /// `migrate_to_v2()` remaps this to `Special` and preserves the original text in `raw_legacy_value`.
+    /// Reaction-style casting time ("1 action"). v1 rows carrying it are still remapped to
+    /// `Special` by `migrate_to_v2()`, which preserves the original text in `raw_legacy_value`.
     #[serde(alias = "ACTION", alias = "Action")]
     Action,
     /// Deserialization-only: 5e unit removed from schema in v2. Task 0.1 (v1→v2 migration):
     /// `migrate_to_v2()` remaps this to `Special` and preserves the original text in `raw_legacy_value`.
     #[serde(alias = "BONUS_ACTION", alias = "BonusAction")]
     BonusAction,
-    /// Deserialization-only: 5e unit removed from schema in v2. Task 0.1 (v1→v2 migration):
-    /// `migrate_to_v2()` remaps this to `Special` and preserves the original text in `raw_legacy_value`.
+    /// Reaction-style casting time ("reaction"); remapped like `Action` for v1 rows.
     #[serde(alias = "REACTION", alias = "Reaction")]
     Reaction,
+    /// Reaction-style casting time ("bonus action").
+    #[serde(alias = "BONUS", alias = "Bonus")]
+    Bonus,
+    /// Reaction-style casting time ("free action").
+    #[serde(alias = "FREE", alias = "Free")]
+    Free,
     #[serde(alias = "SPECIAL", alias = "Special")]
     Special,
     #[serde(alias = "INSTANTANEOUS", alias = "Instantaneous")]
@@ -179,6 +187,16 @@ fn default_one() -> f64 {
     1.0
 }
 
+/// One top-level field whose canonical value differs between two spells; `None` when the
+/// field is absent (pruned) on that side.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CanonicalFieldDiff {
+    pub field: String,
+    pub old: Option<serde_json::Value>,
+    pub new: Option<serde_json::Value>,
+}
+
 /// Root spell type. Serializes as snake_case for canonical hashing (§2.6). Deserializes from
 /// both snake_case and camelCase (aliases below) for JSON import/export and frontend IPC.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
@@ -240,6 +258,10 @@ pub struct CanonicalSpell {
     pub version: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<String>,
+    /// Cross-vault identity from `spell.logical_id`; carried by bundle export so a
+    /// spell keeps it after edits change its content hash.
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "logicalId")]
+    pub logical_id: Option<String>,
 
     #[serde(
         default,
@@ -317,6 +339,7 @@ impl CanonicalSpell {
             author: None,
             version: default_version(),
             license: None,
+            logical_id: None,
             is_quest_spell: Some(0),
             is_cantrip: Some((level == 0) as i64),
             schema_version: CURRENT_SCHEMA_VERSION,
@@ -326,6 +349,39 @@ impl CanonicalSpell {
         }
     }
 
+    /// Whether any parsed material component has a gp value above zero, for a quick
+    /// "costs gold" indicator without walking `material_components` in the UI.
+    pub fn material_has_cost(&self) -> bool {
+        self.material_components
+            .iter()
+            .flatten()
+            .any(|m| m.gp_value.is_some_and(|gp| gp > 0.0))
+    }
+
+    /// Top-level fields whose canonical JSON differs from `other`'s, in key order. Only
+    /// hash-relevant content is compared, so metadata such as `source_refs` never shows up.
+    pub fn diff(&self, other: &Self) -> Result<Vec<CanonicalFieldDiff>, String> {
+        let parse = |spell: &Self| -> Result<serde_json::Map<String, serde_json::Value>, String> {
+            match serde_json::from_str(&spell.to_canonical_json()?) {
+                Ok(serde_json::Value::Object(obj)) => Ok(obj),
+                Ok(_) => Err("canonical JSON is not an object".to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        };
+        let old = parse(self)?;
+        let new = parse(other)?;
+        let fields: std::collections::BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        Ok(fields
+            .into_iter()
+            .filter(|field| old.get(*field) != new.get(*field))
+            .map(|field| CanonicalFieldDiff {
+                field: field.clone(),
+                old: old.get(field).cloned(),
+                new: new.get(field).cloned(),
+            })
+            .collect())
+    }
+
     pub fn to_canonical_json(&self) -> Result<String, String> {
         let mut clone = self.clone();
         // Heavy Normalization (includes sorting/deduplication of arrays and materialization of defaults)
@@ -376,6 +432,7 @@ fn prune_metadata_recursive(value: &mut serde_json::Value, is_root: bool) {
                     "edition",
                     "author",
                     "license",
+                    "logical_id",
                     "schema_version",
                     "created_at",
                     "updated_at",
@@ -670,6 +727,93 @@ fn set_schema_max_length(schema: &mut serde_json::Value, pointer: &str, max_leng
     );
 }
 
+/// Embedded spell schema as raw JSON, parsed once. Used for field-level lookups
+/// (e.g. enum values for editor validation) rather than full-document validation.
+fn embedded_spell_schema() -> &'static serde_json::Value {
+    use std::sync::OnceLock;
+    static RAW_SCHEMA: OnceLock<serde_json::Value> = OnceLock::new();
+
+    RAW_SCHEMA.get_or_init(|| {
+        const SCHEMA_STR: &str = include_str!("../../schemas/spell.schema.json");
+        serde_json::from_str::<serde_json::Value>(SCHEMA_STR)
+            .expect("Invalid embedded schema definition")
+    })
+}
+
+/// Resolves a local `$ref` (`#/$defs/...`) against the embedded schema.
+fn resolve_schema_ref<'a>(
+    root: &'a serde_json::Value,
+    node: &'a serde_json::Value,
+) -> &'a serde_json::Value {
+    match node
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix('#'))
+        .and_then(|pointer| root.pointer(pointer))
+    {
+        Some(target) => target,
+        None => node,
+    }
+}
+
+/// Finds the schema node for a named child property, looking through `$ref`,
+/// array `items`, and `oneOf`/`anyOf`/`allOf` branches.
+fn schema_child_property<'a>(
+    root: &'a serde_json::Value,
+    node: &'a serde_json::Value,
+    key: &str,
+) -> Option<&'a serde_json::Value> {
+    let node = resolve_schema_ref(root, node);
+    if let Some(child) = node.get("properties").and_then(|p| p.get(key)) {
+        return Some(child);
+    }
+    if let Some(items) = node.get("items") {
+        if let Some(child) = schema_child_property(root, items, key) {
+            return Some(child);
+        }
+    }
+    ["oneOf", "anyOf", "allOf"]
+        .iter()
+        .filter_map(|combinator| node.get(*combinator).and_then(|v| v.as_array()))
+        .flatten()
+        .find_map(|branch| schema_child_property(root, branch, key))
+}
+
+/// Looks up the allowed enum values for a spell field in the embedded schema.
+///
+/// `field` is a dot path such as `school` or `range.kind`; camelCase segments are
+/// accepted. Returns `None` when the path does not exist in the schema,
+/// `Some(None)` for fields without an enum (free text / numeric), and
+/// `Some(Some(values))` otherwise. Non-string enum values are stringified.
+pub(crate) fn schema_enum_for_field(field: &str) -> Option<Option<Vec<String>>> {
+    let root = embedded_spell_schema();
+    let mut node = root;
+    for segment in field.split('.') {
+        let key = camel_to_snake(segment.trim());
+        if key.is_empty() {
+            return None;
+        }
+        node = schema_child_property(root, node, &key)?;
+    }
+
+    let mut node = resolve_schema_ref(root, node);
+    if node.get("enum").is_none() {
+        if let Some(items) = node.get("items") {
+            node = resolve_schema_ref(root, items);
+        }
+    }
+
+    Some(node.get("enum").and_then(|e| e.as_array()).map(|values| {
+        values
+            .iter()
+            .map(|v| match v {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect()
+    }))
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MigrationFailure {
@@ -702,12 +846,12 @@ impl CanonicalSpell {
     /// (2) remaps 5e casting-time units (Action/BonusAction/Reaction) to `Special` and preserves
     /// the original text in `raw_legacy_value`.
     ///
-    /// Stamps `schema_version = CURRENT_SCHEMA_VERSION` on success.
+    /// Stamps `schema_version = 2` on success; `migrate_to_v3()` runs next.
     /// `db_id` is optional; when provided (e.g. bulk migration), truncation sets `truncated_spell_id`.
     /// See "Migration Plan / Schema version compatibility" in design.md.
     fn migrate_to_v2(&mut self, db_id: Option<i64>) -> MigrateV2Result {
         let mut result = MigrateV2Result::default();
-        if self.schema_version >= CURRENT_SCHEMA_VERSION {
+        if self.schema_version >= 2 {
             return result;
         }
 
@@ -771,11 +915,23 @@ impl CanonicalSpell {
         result
     }
 
+    /// Migrates a spell from schema version 2 to 3. v3 only loosens the schema: casting
+    /// times gain the `action`, `reaction`, `bonus` and `free` units, and an `instant`
+    /// duration may carry `notes` and a lingering `unit`/`duration`. Every v2 spell is
+    /// therefore valid v3 and only the version is stamped; stored rows whose parse output
+    /// changed are re-derived by the database rehash migrations.
+    fn migrate_to_v3(&mut self) {
+        if self.schema_version >= 3 {
+            return;
+        }
+        self.schema_version = 3;
+    }
+
     /// Recursively normalizes all string and number fields for deterministic hashing.
     /// Also sorts and deduplicates unordered arrays.
     ///
     /// Order of operations (matches canonical-serialization contract: Materialize → Sanitize → … → Prune):
-    /// schema version migration (migrate_to_v2 if needed); string sanitization and sub-spec normalization;
+    /// schema version migration (migrate_to_v2/migrate_to_v3 if needed); string sanitization and sub-spec normalization;
     /// tradition-consistent clearing; default materialization and pruning; component materialization and
     /// pruning; array sort/dedup (subschools/descriptors with casing normalization).
     ///
@@ -783,9 +939,12 @@ impl CanonicalSpell {
     pub fn normalize(&mut self, db_id: Option<i64>) -> MigrateV2Result {
         let mut migrate_result = MigrateV2Result::default();
         // Schema Migration: Run before heavy normalization so v1 fields can be preserved/moved.
-        if self.schema_version < CURRENT_SCHEMA_VERSION {
+        if self.schema_version < 2 {
             migrate_result = self.migrate_to_v2(db_id);
         }
+        if self.schema_version < 3 {
+            self.migrate_to_v3();
+        }
 
         self.name = normalize_string(&self.name, NormalizationMode::Structured);
         self.tradition =
@@ -914,12 +1073,13 @@ impl CanonicalSpell {
             }
         }
 
-        // migrate_to_v2() (called above) is the sole migration path and stamps schema_version
-        // = CURRENT_SCHEMA_VERSION on every spell that needed upgrading. Spells that arrived at
-        // >= CURRENT are left unchanged. Either way, the version must be >= CURRENT here.
+        // migrate_to_v2() and migrate_to_v3() (called above) are the sole migration path and
+        // stamp schema_version = CURRENT_SCHEMA_VERSION on every spell that needed upgrading.
+        // Spells that arrived at >= CURRENT are left unchanged. Either way, the version must be
+        // >= CURRENT here.
         debug_assert!(
             self.schema_version >= CURRENT_SCHEMA_VERSION,
-            "schema_version should be current or newer after migrate_to_v2() (got {})",
+            "schema_version should be current or newer after migration (got {})",
             self.schema_version
         );
 
@@ -1001,7 +1161,7 @@ pub(crate) fn normalize_string(s: &str, mode: NormalizationMode) -> String {
 }
 
 /// Matches a string against schema-defined enums case-insensitively, or falls back to Title Case.
-fn match_schema_case(s: &str) -> String {
+pub(crate) fn match_schema_case(s: &str) -> String {
     let normalized = normalize_string(s, NormalizationMode::Structured);
     let lower = normalized.to_lowercase();
 
@@ -1097,21 +1257,31 @@ pub(crate) fn clamp_precision(val: f64) -> f64 {
     (val * 1_000_000.0).round() / 1_000_000.0
 }
 
-/// Helper to parse comma-separated strings into sorted Vecs
+/// Splits a stored list column (`class_list`, `tags`) into trimmed, sorted, deduped entries.
+/// Accepts both the JSON-array form (`["Mage","Cleric"]`) and legacy comma-separated text.
+pub(crate) fn parse_list_column(s: &str) -> Vec<String> {
+    let trimmed = s.trim();
+    let items: Vec<String> = if trimmed.starts_with('[') {
+        match serde_json::from_str::<Vec<String>>(trimmed) {
+            Ok(items) => items,
+            Err(_) => trimmed.split(',').map(String::from).collect(),
+        }
+    } else {
+        trimmed.split(',').map(String::from).collect()
+    };
+    let mut vec: Vec<String> = items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect();
+    vec.sort();
+    vec.dedup();
+    vec
+}
+
+/// Helper to parse comma-separated strings (or JSON arrays) into sorted Vecs
 fn parse_comma_list(input: &Option<String>) -> Vec<String> {
-    input
-        .as_ref()
-        .map(|s| {
-            let mut vec: Vec<String> = s
-                .split(',')
-                .map(|item| item.trim().to_string())
-                .filter(|item| !item.is_empty())
-                .collect();
-            vec.sort();
-            vec.dedup();
-            vec
-        })
-        .unwrap_or_default()
+    input.as_deref().map(parse_list_column).unwrap_or_default()
 }
 
 fn normalized_optional_structured_field(value: Option<&str>) -> Option<String> {
@@ -1201,6 +1371,9 @@ impl TryFrom<crate::models::spell::SpellDetail> for CanonicalSpell {
 
         let parser = SpellParser::new();
 
+        let legacy_zero_range =
+            detail.range_spec.is_none() && detail.range.as_deref().map(str::trim) == Some("0");
+
         // Prioritize structured spec objects if provided by frontend
         spell.range = detail.range_spec.or_else(|| {
             detail
@@ -1234,6 +1407,25 @@ impl TryFrom<crate::models::spell::SpellDetail> for CanonicalSpell {
                 .and_then(|s| parser.parse_area(s))
         });
 
+        // A legacy "0" range on a spell with a real area means the effect originates at the
+        // caster, not that the caster is the only target. Keep Personal but say so.
+        if legacy_zero_range
+            && spell
+                .area
+                .as_ref()
+                .is_some_and(|a| a.kind != AreaKind::Point)
+        {
+            if let Some(range) = spell
+                .range
+                .as_mut()
+                .filter(|r| r.kind == RangeKind::Personal)
+            {
+                range
+                    .notes
+                    .get_or_insert_with(|| AREA_ORIGINATES_AT_CASTER_NOTE.to_string());
+            }
+        }
+
         // Damage parsing
         spell.damage = detail.damage_spec.or_else(|| {
             detail
@@ -1416,8 +1608,8 @@ mod tests {
     #[test]
     fn test_regression_casting_time_units() {
         // Fix: Bonus Action and Reaction must be accepted andNormalized
-        let units = vec!["bonus action", "reaction", "Bonus Actions"];
-        let expected = vec!["bonus_action", "reaction", "bonus_action"];
+        let units = ["bonus action", "reaction", "Bonus Actions"];
+        let expected = ["bonus_action", "reaction", "bonus_action"];
 
         for (u, exp) in units.iter().zip(expected.iter()) {
             let normalized = match_schema_case(u);
@@ -2184,6 +2376,67 @@ mod tests {
         assert_eq!(materials[0].is_consumed, Some(true));
     }
 
+    #[test]
+    fn test_from_spell_detail_flags_costly_material_components() {
+        use crate::models::spell::SpellDetail;
+
+        let costly = SpellDetail {
+            name: "Raise Dead".into(),
+            sphere: Some("Necromantic".into()),
+            level: 5,
+            components: Some("V, S, M".into()),
+            material_components: Some("a 5,000gp diamond".into()),
+            description: "Returns the dead to life.".into(),
+            ..Default::default()
+        };
+        let canon = CanonicalSpell::try_from(costly).unwrap();
+        let materials = canon.material_components.as_ref().unwrap();
+        assert_eq!(materials.len(), 1);
+        assert_eq!(materials[0].gp_value, Some(5000.0));
+        assert!(canon.material_has_cost());
+
+        let cheap = SpellDetail {
+            name: "Stinking Cloud".into(),
+            school: Some("Evocation".into()),
+            level: 2,
+            components: Some("V, S, M".into()),
+            material_components: Some("a pinch of sulfur".into()),
+            description: "A nauseating vapor.".into(),
+            ..Default::default()
+        };
+        let canon = CanonicalSpell::try_from(cheap).unwrap();
+        assert!(canon.material_components.is_some());
+        assert!(!canon.material_has_cost());
+    }
+
+    #[test]
+    fn test_from_spell_detail_zero_range_with_area_notes_caster_origin() {
+        use crate::models::spell::SpellDetail;
+
+        let detail = SpellDetail {
+            name: "Burning Hands".into(),
+            school: Some("Alteration".into()),
+            level: 1,
+            range: Some("0".into()),
+            area: Some("5 ft. cone".into()),
+            description: "A fan of flame.".into(),
+            ..Default::default()
+        };
+        let canon = CanonicalSpell::try_from(detail.clone()).unwrap();
+        let range = canon.range.expect("range parsed");
+        assert_eq!(range.kind, RangeKind::Personal);
+        assert_eq!(range.notes.as_deref(), Some(AREA_ORIGINATES_AT_CASTER_NOTE));
+
+        let self_only = SpellDetail {
+            area: None,
+            ..detail
+        };
+        let canon = CanonicalSpell::try_from(self_only).unwrap();
+        let range = canon.range.expect("range parsed");
+        assert_eq!(range.kind, RangeKind::Personal);
+        assert_eq!(range.notes, None, "no area means a plain self range");
+    }
+
     #[test]
     fn test_from_spell_detail_inference() {
         use crate::models::spell::SpellDetail;
@@ -2347,6 +2600,7 @@ mod tests {
             requires: None,
             anchor: None,
             region_unit: None,
+            alt_kind: None,
             notes: Some("Some  note".into()),
             raw_legacy_value: None,
         });
@@ -2456,6 +2710,7 @@ mod tests {
             requires: None,
             anchor: None,
             region_unit: None,
+            alt_kind: None,
             notes: None,
             raw_legacy_value: None,
         };
@@ -3097,6 +3352,7 @@ mod tests {
             requires: None,
             anchor: None,
             region_unit: None,
+            alt_kind: None,
             notes: None,
             raw_legacy_value: None,
         });
@@ -3347,11 +3603,11 @@ mod tests {
         assert_eq!(a.radius.unwrap().value.unwrap(), 20.0);
         assert_eq!(a.unit.unwrap(), AreaUnit::Ft);
 
-        // Casting Time — "1 action" is a 5e unit; parser remaps to Special at parse time
+        // Casting Time — "1 action" is a recognized reaction-style unit
         let ct = spell.casting_time.unwrap();
         assert_eq!(ct.base_value.unwrap_or(1.0), 1.0);
-        assert_eq!(ct.unit, CastingTimeUnit::Special);
-        assert_eq!(ct.raw_legacy_value.as_deref(), Some("1 action"));
+        assert_eq!(ct.unit, CastingTimeUnit::Action);
+        assert_eq!(ct.text, "1 action");
 
         // Components
         let c = spell.components.unwrap();
@@ -3797,7 +4053,7 @@ mod tests {
 
         spell.normalize(None); // Should trigger migrate_to_v2
 
-        assert_eq!(spell.schema_version, 2);
+        assert_eq!(spell.schema_version, CURRENT_SCHEMA_VERSION);
 
         let st = spell.saving_throw.as_ref().unwrap();
         assert!(st.legacy_dm_guidance.is_none());
@@ -3832,7 +4088,7 @@ mod tests {
 
         let res = spell.normalize(None);
         assert!(res.notes_truncated, "truncation flag should be set");
-        assert_eq!(spell.schema_version, 2);
+        assert_eq!(spell.schema_version, CURRENT_SCHEMA_VERSION);
         let notes_len = spell
             .saving_throw
             .as_ref()
@@ -3936,7 +4192,7 @@ mod tests {
         spell.schema_version = 0;
 
         let _ = spell.normalize(None);
-        assert_eq!(spell.schema_version, 2);
+        assert_eq!(spell.schema_version, CURRENT_SCHEMA_VERSION);
     }
 
     #[test]
@@ -3970,29 +4226,30 @@ mod tests {
         );
         spell.schema_version = 2;
         spell.casting_time = Some(SpellCastingTime {
-            unit: CastingTimeUnit::Round,
-            text: "1 round".into(),
+            unit: CastingTimeUnit::Action,
+            text: "1 action".into(),
             ..Default::default()
         });
 
+        // v2 -> v3 only stamps the version: the v1 action remap does not run again.
         let res = spell.normalize(None);
         assert!(!res.notes_truncated);
         assert_eq!(res.truncated_spell_id, None);
-        assert_eq!(spell.schema_version, 2);
+        assert_eq!(spell.schema_version, 3);
         assert_eq!(
             spell.casting_time.as_ref().unwrap().unit,
-            CastingTimeUnit::Round
+            CastingTimeUnit::Action
         );
     }
 
-    /// Priority D (TG5): Future schema_version (e.g. 3) is passed through without migration.
+    /// Priority D (TG5): Future schema_version (e.g. 4) is passed through without migration.
     #[test]
     fn test_migrate_v1_to_v2_passthrough_future_schema_version() {
         use crate::models::canonical_spell::{CastingTimeUnit, SpellCastingTime};
 
         let mut spell =
             CanonicalSpell::new("Future Version".into(), 1, "ARCANE".into(), "Desc".into());
-        spell.schema_version = 3;
+        spell.schema_version = CURRENT_SCHEMA_VERSION + 1;
         spell.casting_time = Some(SpellCastingTime {
             unit: CastingTimeUnit::Round,
             text: "1 round".into(),
@@ -4003,7 +4260,8 @@ mod tests {
         assert!(!res.notes_truncated);
         assert_eq!(res.truncated_spell_id, None);
         assert_eq!(
-            spell.schema_version, 3,
+            spell.schema_version,
+            CURRENT_SCHEMA_VERSION + 1,
             "future schema_version must be preserved"
         );
         assert_eq!(
@@ -4035,7 +4293,7 @@ mod tests {
 
         let _ = spell.normalize(None);
 
-        assert_eq!(spell.schema_version, 2);
+        assert_eq!(spell.schema_version, CURRENT_SCHEMA_VERSION);
         // Step 1: dm_guidance moved to notes
         let notes = spell
             .saving_throw
@@ -4094,8 +4352,8 @@ mod tests {
             |r| r.get(0),
         )?;
         assert_eq!(
-            schema_v2, 2,
-            "Successful spell must be updated to schema_version 2"
+            schema_v2, CURRENT_SCHEMA_VERSION,
+            "Successful spell must be updated to the current schema_version"
         );
 
         let has_hash: i64 = conn.query_row(
@@ -4419,7 +4677,7 @@ mod tests {
         assert!(
             st.notes
                 .as_ref()
-                .map_or(false, |n| n.contains("DM adjudicates this.")),
+                .is_some_and(|n| n.contains("DM adjudicates this.")),
             "notes must contain the migrated dm_guidance content"
         );
 
@@ -4661,7 +4919,9 @@ pub(crate) fn run_migration_batch_impl(
 pub async fn migrate_all_spells_to_v2(
     window: Window,
     state: State<'_, Arc<Pool>>,
+    spell_cache: State<'_, Arc<crate::commands::spells::SpellCache>>,
 ) -> Result<MigrationResult, AppError> {
+    let _cache_guard = spell_cache.start_write();
     let pool = state.inner().clone();
     tokio::task::spawn_blocking(move || {
         let mut conn = pool.get()?;
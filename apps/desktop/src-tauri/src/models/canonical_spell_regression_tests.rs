@@ -462,6 +462,7 @@ fn test_regression_range_text_word_boundaries() {
         requires: None,
         anchor: None,
         region_unit: None,
+        alt_kind: None,
         notes: None,
         raw_legacy_value: None,
     });
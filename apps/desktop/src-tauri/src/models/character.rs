@@ -15,6 +15,19 @@ pub struct Character {
     pub updated_at: Option<String>,
 }
 
+/// One audited edit to a character field, from `character_change_log`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterChange {
+    pub id: i64,
+    pub character_id: i64,
+    pub changed_at: Option<String>,
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(crate = "serde")]
 #[serde(rename_all = "camelCase")]
@@ -101,6 +114,15 @@ pub struct CharacterSpellbookEntry {
     pub available_upgrade_spell_id: Option<i64>,
 }
 
+/// A legacy `spellbook` row whose `spell_id` no longer exists in `spell`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedSpellbookEntry {
+    pub character_id: i64,
+    pub spell_id: i64,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PrintableCharacter {
@@ -116,6 +138,14 @@ pub struct PrintableCharacter {
     pub include_notes: bool,
 }
 
+/// Structured print dataset for a character's spellbook (what `print_spellbook` renders).
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintableSpellbook {
+    pub character: PrintableCharacter,
+    pub spells: Vec<PrintableSpellbookEntry>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PrintableSpell {
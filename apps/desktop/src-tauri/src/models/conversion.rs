@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// House-rule conversions between game time and distance units. Defaults match AD&D 2e
+/// (10 rounds per turn, 10 segments per round, 10-foot squares).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "serde")]
+#[serde(default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversionConfig {
+    pub rounds_per_turn: u32,
+    pub feet_per_square: u32,
+    pub segments_per_round: u32,
+}
+
+impl Default for ConversionConfig {
+    fn default() -> Self {
+        Self {
+            rounds_per_turn: 10,
+            feet_per_square: 10,
+            segments_per_round: 10,
+        }
+    }
+}
+
+impl ConversionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rounds_per_turn == 0 || self.feet_per_square == 0 || self.segments_per_round == 0 {
+            return Err("Conversion values must be greater than zero".to_string());
+        }
+        Ok(())
+    }
+}
@@ -314,6 +314,77 @@ pub struct DamagePart {
     pub notes: Option<String>,
 }
 
+impl DicePool {
+    /// Dice notation such as "3d6 + 2"; zero-count terms are omitted.
+    pub fn to_text(&self) -> String {
+        let dice: Vec<String> = self
+            .terms
+            .iter()
+            .filter(|term| term.count != 0)
+            .map(|term| match term.per_die_modifier {
+                0 => format!("{}d{}", term.count, term.sides),
+                m => format!("{}d{}{:+}/die", term.count, term.sides, m),
+            })
+            .collect();
+        if dice.is_empty() {
+            return self.flat_modifier.to_string();
+        }
+        let dice = dice.join(" + ");
+        match self.flat_modifier {
+            0 => dice,
+            m if m > 0 => format!("{} + {}", dice, m),
+            m => format!("{} - {}", dice, -m),
+        }
+    }
+}
+
+impl DamagePart {
+    /// Base dice after applying every caster-level scaling rule at `caster_level`.
+    /// Rules driven by anything else (target HD, choice, ...) are left unapplied.
+    pub fn dice_at_level(&self, caster_level: i32) -> DicePool {
+        let rules: Vec<&ScalingRule> = self
+            .scaling
+            .iter()
+            .flatten()
+            .filter(|rule| rule.driver == ScalingDriver::CasterLevel)
+            .collect();
+
+        let mut pool = rules
+            .iter()
+            .filter(|rule| rule.kind == ScalingKind::SetBaseByLevelBand)
+            .flat_map(|rule| rule.level_bands.iter().flatten())
+            .find(|band| (band.min..=band.max).contains(&caster_level))
+            .map(|band| band.base.clone())
+            .unwrap_or_else(|| self.base.clone());
+
+        for rule in rules {
+            let mut steps = caster_level.max(0) / rule.step.max(1);
+            if let Some(max_steps) = rule.max_steps {
+                steps = steps.min(max_steps);
+            }
+            match rule.kind {
+                ScalingKind::AddDicePerStep => {
+                    if let Some(inc) = &rule.dice_increment {
+                        match pool.terms.iter_mut().find(|t| t.sides == inc.sides) {
+                            Some(term) => term.count += inc.count * steps,
+                            None => pool.terms.push(DiceTerm {
+                                count: inc.count * steps,
+                                ..inc.clone()
+                            }),
+                        }
+                    }
+                    pool.flat_modifier += rule.flat_increment.unwrap_or(0) * steps;
+                }
+                ScalingKind::AddFlatPerStep => {
+                    pool.flat_modifier += rule.flat_increment.unwrap_or(0) * steps;
+                }
+                ScalingKind::SetBaseByLevelBand => {}
+            }
+        }
+        pool
+    }
+}
+
 fn default_mr_interaction() -> MrInteraction {
     MrInteraction::Normal
 }
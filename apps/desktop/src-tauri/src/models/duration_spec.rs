@@ -1,3 +1,4 @@
+use crate::models::conversion::ConversionConfig;
 use crate::models::scalar::SpellScalar;
 use serde::{Deserialize, Serialize};
 
@@ -126,7 +127,46 @@ impl Default for DurationSpec {
     }
 }
 
+impl DurationUnit {
+    /// Rounds per unit, using 2e timekeeping: 10 segments = 1 round = 1 minute,
+    /// 1 turn = 10 rounds, 1 hour = 60 rounds. Months are 30 days and years 365 days.
+    pub fn rounds_per_unit(&self) -> f64 {
+        self.rounds_per_unit_with(&ConversionConfig::default())
+    }
+
+    /// Rounds per unit under the table's `ConversionConfig` (segments and turns vary).
+    pub fn rounds_per_unit_with(&self, config: &ConversionConfig) -> f64 {
+        match self {
+            DurationUnit::Segment => 1.0 / f64::from(config.segments_per_round),
+            DurationUnit::Round => 1.0,
+            DurationUnit::Turn => f64::from(config.rounds_per_turn),
+            DurationUnit::Minute => 1.0,
+            DurationUnit::Hour => 60.0,
+            DurationUnit::Day => 1_440.0,
+            DurationUnit::Week => 10_080.0,
+            DurationUnit::Month => 43_200.0,
+            DurationUnit::Year => 525_600.0,
+        }
+    }
+}
+
 impl DurationSpec {
+    /// Duration converted to rounds for comparison across units.
+    ///
+    /// Returns `None` unless the kind is `time` with a unit and a fixed (non per-level) value.
+    pub fn in_rounds(&self) -> Option<f64> {
+        self.in_rounds_with(&ConversionConfig::default())
+    }
+
+    /// Like [`DurationSpec::in_rounds`], using house-rule conversions.
+    pub fn in_rounds_with(&self, config: &ConversionConfig) -> Option<f64> {
+        if self.kind != DurationKind::Time {
+            return None;
+        }
+        let value = self.duration.as_ref()?.fixed_value()?;
+        Some(value * self.unit.as_ref()?.rounds_per_unit_with(config))
+    }
+
     pub fn normalize(&mut self) {
         if let Some(c) = &mut self.condition {
             *c = crate::models::canonical_spell::normalize_string(
@@ -182,3 +222,55 @@ impl DurationSpec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_in_rounds_converts_turns_and_hours() {
+        let turns = DurationSpec {
+            kind: DurationKind::Time,
+            unit: Some(DurationUnit::Turn),
+            duration: Some(SpellScalar::fixed(3.0)),
+            ..Default::default()
+        };
+        assert_eq!(turns.in_rounds(), Some(30.0));
+
+        let hours = DurationSpec {
+            unit: Some(DurationUnit::Hour),
+            duration: Some(SpellScalar::fixed(1.0)),
+            ..turns.clone()
+        };
+        assert_eq!(hours.in_rounds(), Some(60.0));
+
+        let per_level = DurationSpec {
+            duration: Some(SpellScalar::per_level(1.0)),
+            ..turns
+        };
+        assert_eq!(per_level.in_rounds(), None);
+
+        let permanent = DurationSpec {
+            kind: DurationKind::Permanent,
+            ..Default::default()
+        };
+        assert_eq!(permanent.in_rounds(), None);
+    }
+
+    #[test]
+    fn test_duration_in_rounds_with_house_rule_turn_length() {
+        let one_turn = DurationSpec {
+            kind: DurationKind::Time,
+            unit: Some(DurationUnit::Turn),
+            duration: Some(SpellScalar::fixed(1.0)),
+            ..Default::default()
+        };
+        let six_round_turns = ConversionConfig {
+            rounds_per_turn: 6,
+            ..Default::default()
+        };
+
+        assert_eq!(one_turn.in_rounds(), Some(10.0));
+        assert_eq!(one_turn.in_rounds_with(&six_round_turns), Some(6.0));
+    }
+}
@@ -27,6 +27,12 @@ pub struct ImportSpell {
     pub damage: Option<String>,
     pub magic_resistance: Option<String>,
     pub reversible: Option<i64>,
+    #[serde(
+        default,
+        alias = "reverse_name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reverse_name: Option<String>,
     pub description: String,
     pub tags: Option<String>,
     pub source: Option<String>,
@@ -41,6 +47,21 @@ pub struct ImportSpell {
     pub is_cantrip: i64,
     #[serde(default, alias = "schema_version")]
     pub schema_version: Option<i64>,
+    /// Per-class level overrides (class name -> level) for spells whose level differs by class.
+    #[serde(
+        default,
+        alias = "class_levels",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub class_levels: Option<HashMap<String, i64>>,
+    /// The spell's canonical JSON when the import file carried one (our JSON exports).
+    /// Used as the stored form as long as the text fields above still match it.
+    #[serde(
+        default,
+        alias = "canonical_data",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub canonical_data: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -115,6 +136,44 @@ pub struct ImportConflictResolution {
     pub existing_id: i64,
     pub spell: Option<SpellUpdate>,
     pub artifact: Option<ImportArtifact>,
+    /// Id from `list_pending_conflicts`. When set, `spell` and `artifact` default to the
+    /// queued conflict's incoming values and the queue row is removed once resolved.
+    #[serde(default, alias = "queue_id")]
+    pub queue_id: Option<i64>,
+}
+
+/// What `apply_import_plan` should do with one reviewed spell.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub enum ImportPlanAction {
+    Insert,
+    Update,
+    Skip,
+}
+
+/// One user-approved entry of an import plan.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPlanItem {
+    pub spell: ImportSpell,
+    #[serde(default)]
+    pub artifact: Option<ImportArtifact>,
+    pub action: ImportPlanAction,
+    /// Row to overwrite for `Update`; falls back to the name/level/source match when omitted.
+    #[serde(default, alias = "existing_id")]
+    pub existing_id: Option<i64>,
+}
+
+/// An unresolved import conflict persisted in `import_conflict_queue`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedImportConflict {
+    pub id: i64,
+    pub conflict: ImportConflict,
+    pub queued_at: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -167,6 +226,13 @@ pub struct PreviewSpell {
     pub is_cantrip: i64,
     #[serde(default, alias = "schema_version")]
     pub schema_version: Option<i64>,
+    /// Carried through to `ImportSpell::canonical_data` when the spell is confirmed.
+    #[serde(
+        default,
+        alias = "canonical_data",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub canonical_data: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -176,6 +242,31 @@ pub struct PreviewResult {
     pub spells: Vec<PreviewSpell>,
     pub artifacts: Vec<ImportArtifact>,
     pub conflicts: Vec<ImportConflict>,
+    #[serde(default)]
+    pub stats: PreviewConfidenceStats,
+}
+
+/// Canonicalization + schema validation outcome for one previewed spell.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewValidation {
+    pub name: String,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// Batch-level parse quality across all previewed spells' `_confidence` maps.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewConfidenceStats {
+    /// Mean of per-spell average field confidence (spells without confidence data are skipped).
+    pub mean_confidence: f32,
+    /// Spells with at least one field below the low-confidence threshold.
+    pub low_confidence_count: usize,
+    /// Spells whose every field was parsed with full confidence.
+    pub fully_parsed_count: usize,
 }
 
 // --- JSON spell import (Task 2: hash-based import/export) ---
@@ -277,6 +368,38 @@ pub struct ImportSpellJsonFailure {
     pub reason: String,
 }
 
+/// Per-artifact outcome of a batch reparse: the spell it resolved to, which fields the
+/// reparse changed, or why that artifact was skipped.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ReparseArtifactResult {
+    pub artifact_id: i64,
+    pub spell_id: Option<i64>,
+    pub changed_fields: Vec<String>,
+    pub changes: Vec<ReparseFieldChange>,
+    pub error: Option<String>,
+}
+
+/// One field a reparse changed, with its flat-column values before and after.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ReparseFieldChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// Result of reparse_artifact: the updated spell and the fields the reparse changed.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ReparseResult {
+    pub spell: SpellDetail,
+    pub changes: Vec<ReparseFieldChange>,
+}
+
 /// Result of import_spell_json (apply phase): counts, conflict list or resolution counts, failures.
 #[derive(serde::Serialize, Debug, Clone)]
 #[serde(crate = "serde")]
@@ -294,3 +417,23 @@ pub struct ImportSpellJsonResult {
     pub failures: Vec<ImportSpellJsonFailure>,
     pub warnings: Vec<String>,
 }
+
+/// Result of import_ndjson: totals for the whole stream, the ids of imported rows, and at
+/// most `NDJSON_IMPORT_SAMPLE_LIMIT` conflicts, failures and warnings so memory stays flat
+/// however many lines the file has.
+#[derive(serde::Serialize, Debug, Clone, Default)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ImportNdjsonResult {
+    pub imported_count: usize,
+    pub imported_ids: Vec<i64>,
+    pub duplicates_skipped: DuplicatesSkipped,
+    pub conflict_count: usize,
+    pub conflicts: Vec<ImportSpellJsonConflict>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflicts_resolved: Option<ConflictsResolved>,
+    pub failure_count: usize,
+    pub failures: Vec<ImportSpellJsonFailure>,
+    pub warning_count: usize,
+    pub warnings: Vec<String>,
+}
@@ -19,6 +19,9 @@ pub mod canonical_spell_regression_tests;
 pub mod scalar;
 pub use scalar::*;
 
+pub mod conversion;
+pub use conversion::*;
+
 pub mod duration_spec;
 pub use duration_spec::*;
 
@@ -42,3 +45,6 @@ pub use saving_throw::*;
 
 pub mod material;
 pub use material::*;
+
+pub mod batch;
+pub use batch::*;
@@ -125,6 +125,9 @@ pub struct RangeSpec {
         alias = "region_unit"
     )]
     pub region_unit: Option<crate::models::area_spec::RegionUnit>,
+    /// Secondary keyword mode for "X or Y" ranges, e.g. `touch` in "Touch or 30 ft.".
+    #[serde(default, skip_serializing_if = "Option::is_none", alias = "alt_kind")]
+    pub alt_kind: Option<RangeKind>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
     /// Original legacy source text preserved as-is for auditability.
@@ -186,7 +189,41 @@ fn re_in_dot() -> &'static Regex {
     RE.get_or_init(|| Regex::new(r"\bin\.\b").unwrap())
 }
 
+impl RangeUnit {
+    pub fn to_text(&self) -> &'static str {
+        match self {
+            RangeUnit::Ft => "ft",
+            RangeUnit::Yd => "yd",
+            RangeUnit::Mi => "mi",
+            RangeUnit::Inch => "inch",
+        }
+    }
+
+    /// Feet per unit: 1 yd = 3 ft, 1 mi = 5280 ft, 1 inch = 1/12 ft.
+    pub fn feet_per_unit(&self) -> f64 {
+        match self {
+            RangeUnit::Ft => 1.0,
+            RangeUnit::Yd => 3.0,
+            RangeUnit::Mi => 5280.0,
+            RangeUnit::Inch => 1.0 / 12.0,
+        }
+    }
+}
+
 impl RangeSpec {
+    /// Distance converted to feet for comparison across units.
+    ///
+    /// Returns `None` for non-distance kinds (touch, sight, ...), a missing unit,
+    /// or a per-level distance that has no fixed magnitude.
+    pub fn in_feet(&self) -> Option<f64> {
+        match self.kind {
+            RangeKind::Distance | RangeKind::DistanceLos | RangeKind::DistanceLoe => {}
+            _ => return None,
+        }
+        let value = self.distance.as_ref()?.fixed_value()?;
+        Some(value * self.unit?.feet_per_unit())
+    }
+
     pub fn normalize(&mut self) {
         if let Some(reqs) = &mut self.requires {
             reqs.sort_by_key(|r| match r {
@@ -240,6 +277,7 @@ mod tests {
             requires: None,
             anchor: None,
             region_unit: None,
+            alt_kind: None,
             notes: None,
         };
         spec_yd.normalize();
@@ -254,6 +292,7 @@ mod tests {
             requires: None,
             anchor: None,
             region_unit: None,
+            alt_kind: None,
             notes: None,
         };
         spec_backyard.normalize();
@@ -272,6 +311,7 @@ mod tests {
             requires: None,
             anchor: None,
             region_unit: None,
+            alt_kind: None,
             notes: None,
         };
         spec_ft.normalize();
@@ -286,6 +326,7 @@ mod tests {
             requires: None,
             anchor: None,
             region_unit: None,
+            alt_kind: None,
             notes: None,
         };
         spec_footprint.normalize();
@@ -295,4 +336,39 @@ mod tests {
             "footprint must be unchanged"
         );
     }
+
+    fn distance_spec(value: f64, unit: RangeUnit) -> RangeSpec {
+        RangeSpec {
+            kind: RangeKind::Distance,
+            unit: Some(unit),
+            distance: Some(SpellScalar::fixed(value)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_range_in_feet_converts_yards_and_miles() {
+        assert_eq!(distance_spec(10.0, RangeUnit::Yd).in_feet(), Some(30.0));
+        assert_eq!(distance_spec(1.0, RangeUnit::Mi).in_feet(), Some(5280.0));
+        assert_eq!(distance_spec(30.0, RangeUnit::Ft).in_feet(), Some(30.0));
+        assert_eq!(
+            distance_spec(1.0, RangeUnit::Yd).in_feet(),
+            distance_spec(3.0, RangeUnit::Ft).in_feet()
+        );
+    }
+
+    #[test]
+    fn test_range_in_feet_none_for_touch_and_per_level() {
+        let touch = RangeSpec {
+            kind: RangeKind::Touch,
+            ..Default::default()
+        };
+        assert_eq!(touch.in_feet(), None);
+
+        let per_level = RangeSpec {
+            distance: Some(SpellScalar::per_level(10.0)),
+            ..distance_spec(0.0, RangeUnit::Yd)
+        };
+        assert_eq!(per_level.in_feet(), None);
+    }
 }
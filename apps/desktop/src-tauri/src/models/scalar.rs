@@ -75,6 +75,47 @@ impl SpellScalar {
         }
     }
 
+    /// The level-independent magnitude, or `None` for per-level scalars.
+    pub fn fixed_value(&self) -> Option<f64> {
+        match self.mode {
+            ScalarMode::Fixed => self.value,
+            ScalarMode::PerLevel => None,
+        }
+    }
+
+    /// Concrete magnitude at `caster_level`. Per-level scalars count levels within
+    /// `min_level..=max_level` (and no further than `cap_level`), then apply `rounding`
+    /// and clamp to `cap_value`. `None` when the scalar carries no magnitude at all.
+    pub fn evaluate_at(&self, caster_level: i32) -> Option<f64> {
+        let raw = match self.mode {
+            ScalarMode::Fixed => self.value?,
+            ScalarMode::PerLevel => {
+                let per_level = self.per_level?;
+                let mut level = caster_level;
+                if let Some(min) = self.min_level {
+                    level = level.max(min);
+                }
+                if let Some(max) = self.max_level {
+                    level = level.min(max);
+                }
+                if let Some(cap) = self.cap_level {
+                    level = level.min(cap);
+                }
+                self.value.unwrap_or(0.0) + per_level * f64::from(level)
+            }
+        };
+        let rounded = match self.rounding {
+            Some(ScalarRounding::Floor) => raw.floor(),
+            Some(ScalarRounding::Ceil) => raw.ceil(),
+            Some(ScalarRounding::Nearest) => raw.round(),
+            Some(ScalarRounding::None) | None => raw,
+        };
+        Some(match self.cap_value {
+            Some(cap) => rounded.min(cap),
+            None => rounded,
+        })
+    }
+
     pub fn to_text(&self) -> String {
         let value = self.value.unwrap_or(0.0);
         match self.mode {
@@ -1,6 +1,8 @@
+use super::SpellSummary;
+use crate::db::VecMode;
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default)]
 #[serde(rename_all = "camelCase")] // Standardize frontend communication
 pub struct SearchFilters {
     pub schools: Option<Vec<String>>,
@@ -14,6 +16,19 @@ pub struct SearchFilters {
     pub tags: Option<String>,
     pub is_quest_spell: Option<bool>,
     pub is_cantrip: Option<bool>,
+    /// `Some(true)` keeps only verified spells, `Some(false)` only unverified ones.
+    #[serde(default)]
+    pub verified: Option<bool>,
+    /// `Some(true)` keeps spells with material components, `Some(false)` those without.
+    #[serde(default)]
+    pub has_material: Option<bool>,
+    /// Drops spells whose summed material `gpValue` exceeds this many gp. Spells without
+    /// materials always pass; spells with materials but no parsed cost pass only when
+    /// `include_unknown_material_cost` is set.
+    #[serde(default)]
+    pub max_material_cost: Option<f64>,
+    #[serde(default)]
+    pub include_unknown_material_cost: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -52,6 +67,106 @@ pub struct Facets {
     pub tags: Vec<String>,
 }
 
+/// One class and the number of spells on its list.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassFacetCount {
+    pub class: String,
+    pub count: i64,
+}
+
+/// One tag and the number of spells carrying it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TagUsage {
+    pub tag: String,
+    pub count: i64,
+}
+
+/// One source book and the number of spells taken from it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceUsage {
+    pub source: String,
+    pub count: i64,
+}
+
+/// Number of spells at one level; `get_level_histogram` reports gaps as zero.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LevelCount {
+    pub level: i64,
+    pub count: i64,
+}
+
+/// Number of spells stored at one canonical schema version (NULL counts as 0).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVersionCount {
+    pub version: i64,
+    pub count: i64,
+}
+
+/// Outcome of `reembed_all_spells`: spells embedded this run, spells skipped because
+/// their stored embedding hash still matches, and how many were left when cancelled.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReembedSummary {
+    pub embedded: usize,
+    pub skipped: usize,
+    pub remaining: usize,
+    pub cancelled: bool,
+}
+
+/// Outcome of `rebuild_search_indexes`: the FTS index state after its rebuild and how
+/// many spells were re-embedded under `vec_mode`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchRebuildSummary {
+    pub fts_rebuilt: bool,
+    pub fts_count: i64,
+    pub fts_consistent: bool,
+    pub vectors_rebuilt: usize,
+    pub vec_mode: VecMode,
+}
+
+/// Row counts for `spell` versus its FTS index, used to detect a stale search index.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FtsConsistency {
+    pub spell_count: i64,
+    pub fts_count: i64,
+    pub consistent: bool,
+}
+
+/// Vector backend reported to the UI; `degraded` is true when semantic search falls back.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VecModeStatus {
+    pub mode: VecMode,
+    pub degraded: bool,
+}
+
+/// `search_semantic` results. `degraded` is true when `mode` is blob fallback and the
+/// spells came from keyword search instead of vector distance.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticSearchResults {
+    pub mode: VecMode,
+    pub degraded: bool,
+    pub spells: Vec<SpellSummary>,
+}
+
+/// Spells with no `spell_vec` row. Under blob fallback nothing is semantically
+/// searchable, so `spells` is left empty and `degraded` tells the UI why.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnembeddedSpells {
+    pub vec_mode: VecMode,
+    pub degraded: bool,
+    pub spells: Vec<SpellSummary>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ChatResponse {
     pub answer: String,
@@ -1,6 +1,6 @@
 // Imports handled by explicit paths in derives
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
 #[serde(crate = "serde")]
 #[serde(rename_all = "camelCase")]
 pub struct SpellSummary {
@@ -19,6 +19,9 @@ pub struct SpellSummary {
     #[serde(alias = "is_cantrip")]
     pub is_cantrip: i64,
     pub tags: Option<String>,
+    /// One-line teaser cut from the description; the full text is never sent in summaries.
+    #[serde(default, alias = "description_preview")]
+    pub description_preview: Option<String>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
@@ -45,6 +48,9 @@ pub struct SpellCreate {
     #[serde(alias = "magic_resistance")]
     pub magic_resistance: Option<String>,
     pub reversible: Option<i64>,
+    /// Name of the reversed form of a reversible spell; local metadata, not hashed.
+    #[serde(default, alias = "reverse_name")]
+    pub reverse_name: Option<String>,
     pub description: String,
     pub tags: Option<String>,
     pub source: Option<String>,
@@ -92,6 +98,9 @@ pub struct SpellUpdate {
     #[serde(alias = "magic_resistance")]
     pub magic_resistance: Option<String>,
     pub reversible: Option<i64>,
+    /// `None` leaves the stored reverse name alone; a blank name clears it.
+    #[serde(default, alias = "reverse_name")]
+    pub reverse_name: Option<String>,
     pub description: String,
     pub tags: Option<String>,
     pub source: Option<String>,
@@ -114,6 +123,112 @@ pub struct SpellUpdate {
     pub magic_resistance_spec: Option<crate::models::MagicResistanceSpec>,
 }
 
+/// Spells sharing one `content_hash`, i.e. exact content duplicates.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateSpellGroup {
+    pub hash: String,
+    pub spells: Vec<SpellSummary>,
+}
+
+/// Outcome of `repair_content_hashes`: ids whose hash was rewritten, and ids left alone
+/// because their correct hash already belongs to another spell.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ContentHashRepairResult {
+    pub repaired: Vec<i64>,
+    pub skipped: Vec<i64>,
+    pub warnings: Vec<String>,
+}
+
+/// A saved homebrew starting point; `partial_json` holds only the fields the template defines.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct SpellTemplate {
+    pub id: i64,
+    pub name: String,
+    pub partial_json: serde_json::Value,
+}
+
+/// Result of checking a single edited field against the spell schema's enum values.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct FieldValidation {
+    pub valid: bool,
+    pub suggestions: Vec<String>,
+}
+
+/// A spell in the import review queue, with the mechanical fields that fell back to `special`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct SpellReviewItem {
+    pub id: i64,
+    pub name: String,
+    pub level: i64,
+    pub source: Option<String>,
+    #[serde(alias = "fallback_fields")]
+    pub fallback_fields: Vec<String>,
+}
+
+/// Data-quality problems found on one stored spell by `scan_data_quality`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct DataQualityReport {
+    pub id: i64,
+    pub issues: Vec<String>,
+}
+
+/// Outcome of `normalize_all_tags`: spells whose `tags` were rewritten, and how many
+/// distinct spellings were folded into another tag's display form.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct TagNormalizationSummary {
+    pub spells_updated: usize,
+    pub variants_collapsed: usize,
+}
+
+/// One `change_log` row with the edited spell's name, for the library-wide activity feed.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct SpellChange {
+    pub id: i64,
+    pub spell_id: Option<i64>,
+    /// The spell's current name, or a placeholder when the spell has since been deleted.
+    pub spell_name: String,
+    pub changed_at: Option<String>,
+    pub field: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// A page of `list_recent_changes` plus the total number of logged changes.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct RecentChangesPage {
+    pub changes: Vec<SpellChange>,
+    pub total: i64,
+}
+
+/// Schema validation outcome for one stored spell, as reported by `validate_all_spells`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct SpellValidationResult {
+    pub id: i64,
+    pub name: String,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 #[serde(crate = "serde")]
 #[serde(rename_all = "camelCase")]
@@ -125,6 +240,12 @@ pub struct SpellArtifact {
     pub hash: String,
     pub imported_at: String,
     pub spell_content_hash: Option<String>,
+    /// True for the artifact used as the canonical source when reparsing by spell id.
+    #[serde(default)]
+    pub is_primary: bool,
+    /// Whether the file is still on disk; only checked by `get_spell_artifacts`.
+    #[serde(default)]
+    pub exists: Option<bool>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
@@ -152,6 +273,9 @@ pub struct SpellDetail {
     #[serde(alias = "magic_resistance")]
     pub magic_resistance: Option<String>,
     pub reversible: Option<i64>,
+    /// Name of the reversed form of a reversible spell; local metadata, not hashed.
+    #[serde(default, alias = "reverse_name")]
+    pub reverse_name: Option<String>,
     pub description: String,
     pub tags: Option<String>,
     pub source: Option<String>,
@@ -171,6 +295,15 @@ pub struct SpellDetail {
     /// Content-addressed hash (SHA-256) of canonical_data.
     #[serde(alias = "content_hash")]
     pub content_hash: Option<String>,
+    /// Row timestamps; metadata only, never part of the canonical form.
+    #[serde(default, alias = "created_at")]
+    pub created_at: Option<String>,
+    #[serde(default, alias = "updated_at")]
+    pub updated_at: Option<String>,
+    /// Whether a parsed material component has a gp value, for a "costs gold" indicator.
+    /// Derived from `canonical_data` on read; ignored on write.
+    #[serde(default, alias = "material_has_cost")]
+    pub material_has_cost: bool,
     // Structured Data Spec Objects
     pub range_spec: Option<crate::models::RangeSpec>,
     pub components_spec: Option<crate::models::SpellComponents>,
@@ -182,3 +315,41 @@ pub struct SpellDetail {
     pub damage_spec: Option<crate::models::SpellDamageSpec>,
     pub magic_resistance_spec: Option<crate::models::MagicResistanceSpec>,
 }
+
+/// One spell measurement resolved at a caster level. `formula` is the scalar as written
+/// (e.g. "10+5/level"); `value` is `None` for fields with no magnitude, such as Touch.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedValue {
+    pub field: String,
+    pub text: Option<String>,
+    pub formula: Option<String>,
+    pub value: Option<f64>,
+    pub unit: Option<String>,
+}
+
+/// One damage part with its base dice and the dice rolled at the requested level.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedDamage {
+    pub label: Option<String>,
+    pub damage_type: crate::models::DamageType,
+    pub base: String,
+    pub dice: String,
+}
+
+/// Result of render_spell_at_level: scaling fields evaluated for one caster level.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedSpell {
+    pub spell_id: i64,
+    pub name: String,
+    pub caster_level: i32,
+    pub range: Option<ResolvedValue>,
+    pub duration: Option<ResolvedValue>,
+    pub area: Vec<ResolvedValue>,
+    pub damage: Vec<ResolvedDamage>,
+}
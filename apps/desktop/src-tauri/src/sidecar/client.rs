@@ -5,20 +5,56 @@ use std::process::Stdio;
 use tokio::io::AsyncWriteExt;
 use tokio::process::Command;
 
-fn sidecar_path() -> Result<PathBuf, AppError> {
+/// User-facing reason returned by every sidecar call when the script cannot be located.
+pub const SIDECAR_UNAVAILABLE_MESSAGE: &str = "AI features require the Python sidecar; see setup";
+
+/// Availability of the Python sidecar script and where it was found.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(crate = "serde")]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarStatus {
+    pub available: bool,
+    pub path: Option<String>,
+    pub searched: Vec<String>,
+}
+
+fn sidecar_candidates() -> Vec<PathBuf> {
     let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let repo_root = manifest_dir.join("../../..");
-    let candidate = repo_root.join("services/ml/spellbook_sidecar.py");
-    if candidate.exists() {
-        return Ok(candidate);
+    let mut candidates = vec![repo_root.join("services/ml/spellbook_sidecar.py")];
+    if let Ok(cwd) = std::env::current_dir() {
+        candidates.push(cwd.join("services/ml/spellbook_sidecar.py"));
     }
-    let fallback = std::env::current_dir()
-        .map_err(AppError::Io)?
-        .join("services/ml/spellbook_sidecar.py");
-    if fallback.exists() {
-        return Ok(fallback);
+    candidates
+}
+
+fn resolve_sidecar_path(candidates: &[PathBuf]) -> Option<PathBuf> {
+    candidates
+        .iter()
+        .find(|candidate| candidate.exists())
+        .cloned()
+}
+
+fn sidecar_path() -> Result<PathBuf, AppError> {
+    resolve_sidecar_path(&sidecar_candidates())
+        .ok_or_else(|| AppError::SidecarUnavailable(SIDECAR_UNAVAILABLE_MESSAGE.into()))
+}
+
+pub(crate) fn sidecar_status_for(candidates: &[PathBuf]) -> SidecarStatus {
+    let path = resolve_sidecar_path(candidates);
+    SidecarStatus {
+        available: path.is_some(),
+        path: path.map(|p| p.to_string_lossy().into_owned()),
+        searched: candidates
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect(),
     }
-    Err(AppError::NotFound("spellbook_sidecar.py not found".into()))
+}
+
+#[tauri::command]
+pub async fn sidecar_status() -> Result<SidecarStatus, AppError> {
+    Ok(sidecar_status_for(&sidecar_candidates()))
 }
 
 fn python_command() -> PathBuf {
@@ -107,3 +143,32 @@ pub async fn call_sidecar(
 
     Err(AppError::Sidecar("No valid JSON-RPC response found".into()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_status_reports_unavailable_for_missing_script() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let missing = dir.path().join("services/ml/spellbook_sidecar.py");
+
+        let status = sidecar_status_for(std::slice::from_ref(&missing));
+
+        assert!(!status.available);
+        assert_eq!(status.path, None);
+        assert_eq!(
+            status.searched,
+            vec![missing.to_string_lossy().into_owned()]
+        );
+
+        std::fs::create_dir_all(missing.parent().unwrap()).unwrap();
+        std::fs::write(&missing, "").unwrap();
+        let status = sidecar_status_for(std::slice::from_ref(&missing));
+        assert!(status.available);
+        assert_eq!(
+            status.path.as_deref(),
+            Some(missing.to_string_lossy().as_ref())
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use crate::error::AppError;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Largest decoded payload an import accepts (100 MB), compressed or not.
+pub const MAX_IMPORT_PAYLOAD_BYTES: usize = 100 * 1024 * 1024;
+
+/// Writes `json` to `dir/<stem>.json`, or gzipped to `dir/<stem>.json.gz` when `compress`.
+pub fn write_export_json(
+    dir: &Path,
+    stem: &str,
+    json: &str,
+    compress: bool,
+) -> Result<PathBuf, AppError> {
+    std::fs::create_dir_all(dir)?;
+    if !compress {
+        let path = dir.join(format!("{stem}.json"));
+        std::fs::write(&path, json)?;
+        return Ok(path);
+    }
+    let path = dir.join(format!("{stem}.json.gz"));
+    let mut encoder = GzEncoder::new(std::fs::File::create(&path)?, Compression::default());
+    encoder.write_all(json.as_bytes())?;
+    encoder.finish()?;
+    Ok(path)
+}
+
+/// Decodes an export payload, transparently gunzipping it when it starts with the gzip magic.
+/// Payloads that decode to more than [`MAX_IMPORT_PAYLOAD_BYTES`] are rejected without
+/// inflating past the limit.
+pub fn decode_export_bytes(bytes: &[u8]) -> Result<String, AppError> {
+    let decoded = if bytes.starts_with(&GZIP_MAGIC) {
+        let mut decoded = Vec::new();
+        GzDecoder::new(bytes)
+            .take(MAX_IMPORT_PAYLOAD_BYTES as u64 + 1)
+            .read_to_end(&mut decoded)
+            .map_err(|e| AppError::Import(format!("Failed to decompress gzip export: {}", e)))?;
+        if decoded.len() > MAX_IMPORT_PAYLOAD_BYTES {
+            return Err(AppError::Import(
+                "Decompressed import payload exceeds 100 MB limit".to_string(),
+            ));
+        }
+        decoded
+    } else {
+        if bytes.len() > MAX_IMPORT_PAYLOAD_BYTES {
+            return Err(AppError::Import(format!(
+                "Import payload exceeds 100 MB limit ({} bytes)",
+                bytes.len()
+            )));
+        }
+        bytes.to_vec()
+    };
+    String::from_utf8(decoded)
+        .map_err(|e| AppError::Import(format!("Export file is not UTF-8 JSON: {}", e)))
+}
+
+/// Reads a `.json` or `.json.gz` export from disk.
+pub fn read_export_json(path: &Path) -> Result<String, AppError> {
+    let bytes = std::fs::read(path)?;
+    let is_gz = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"));
+    if is_gz && !bytes.starts_with(&GZIP_MAGIC) {
+        return Err(AppError::Import(format!(
+            "{} has a .gz extension but is not gzip data",
+            path.display()
+        )));
+    }
+    decode_export_bytes(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_export_bytes_rejects_gzip_bomb() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        let zeros = vec![0u8; 1024 * 1024];
+        for _ in 0..=MAX_IMPORT_PAYLOAD_BYTES / zeros.len() {
+            encoder.write_all(&zeros).unwrap();
+        }
+        let bomb = encoder.finish().unwrap();
+        assert!(bomb.len() < MAX_IMPORT_PAYLOAD_BYTES / 100);
+
+        assert!(matches!(
+            decode_export_bytes(&bomb),
+            Err(AppError::Import(message)) if message.contains("100 MB")
+        ));
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(br#"{"spells":[]}"#).unwrap();
+        assert_eq!(
+            decode_export_bytes(&encoder.finish().unwrap()).unwrap(),
+            r#"{"spells":[]}"#
+        );
+    }
+}
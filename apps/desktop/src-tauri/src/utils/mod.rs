@@ -1,3 +1,4 @@
+pub mod compression;
 pub mod migration_manager;
 pub mod parsers;
 pub mod spell_parser;
@@ -8,6 +8,9 @@ pub struct AreaParser {
     area_per_level_regex: Regex,
     area_multi_regex: Regex,
     area_count_regex: Regex,
+    area_count_scaling_regex: Regex,
+    area_within_regex: Regex,
+    area_all_radius_regex: Regex,
     area_volume_regex: Regex,
     area_tile_regex: Regex,
 }
@@ -30,8 +33,13 @@ impl AreaParser {
             ).unwrap(),
             area_multi_regex: Regex::new(r#"(?i)^(\d+(?:\.\d+)?)\s*(ft\.|ft|yards?|yd\.|mi|in\.|in|inches|'|")?\s*(?:by|x|×)\s*(\d+(?:\.\d+)?)\s*(ft\.|ft|yards?|yd\.|mi|in\.|in|inches|'|")?\s*(?:(?:by|x|×)\s*(\d+(?:\.\d+)?)\s*(ft\.|ft|yards?|yd\.|mi|in\.|in|inches|'|")?)?\s*([a-z\._-]+)$"#).unwrap(),
             area_count_regex: Regex::new(r#"(?i)^(?:up\s+to\s+)?(\d+(?:\.\d+)?|1)\s*(?:/level)?\s*(creatures?|targets?|enemies?|allies?|objects?|undead|structures?)(?:\s*/level)?$"#).unwrap(),
+            // Pattern: "1 creature + 1/level", "2 targets plus 1 target per level"
+            area_count_scaling_regex: Regex::new(r#"(?i)^(?:up\s+to\s+)?(\d+(?:\.\d+)?)\s*(creatures?|targets?|enemies?|allies?|objects?|undead|structures?)\s*(?:\+|plus)\s*(\d+(?:\.\d+)?)\s*(?:(?:creatures?|targets?|enemies?|allies?|objects?|undead|structures?)\s*)?(?:/\s*|per\s+)level$"#).unwrap(),
+            area_within_regex: Regex::new(r#"(?i)^(.+?)\s+within\s+(\d+(?:\.\d+)?)\s*(ft\.|ft|feet|foot|'|yards?|yd\.|yd|miles?|mi\.|mi)\.?$"#).unwrap(),
+            // Pattern: "all creatures within 20 ft. radius", "all allies in a 15-foot radius"
+            area_all_radius_regex: Regex::new(r#"(?i)^all\s+(creatures?|targets?|enemies?|allies?|objects?|undead|structures?)\s+(?:with)?in\s+(?:an?\s+)?(\d+(?:\.\d+)?)\s*-?\s*(ft\.|ft|feet|foot|'|yards?|yd\.|yd|miles?|mi\.|mi)\.?\s*radius$"#).unwrap(),
             area_volume_regex: Regex::new(r#"(?i)^(\d+(?:\.\d+)?)\s*(cubic|cu\.)\s*([a-z\.'"-]+)$"#).unwrap(),
-            area_tile_regex: Regex::new(r#"(?i)^(\d+)\s*(?:(\d+(?:\.\d+)?)\s*([a-z\.'"-]+)\s*)?(squares?|hexes?|rooms?|floors?)$"#).unwrap(),
+            area_tile_regex: Regex::new(r#"(?i)^(\d+)\s*(?:(\d+(?:\.\d+)?)\s*-?\s*([a-z\.'"]+)\s*)?(squares?|hexes?|rooms?|floors?)(\s*/\s*level)?$"#).unwrap(),
         }
     }
 
@@ -158,42 +166,95 @@ impl AreaParser {
                 }
             }
 
-            // 2. Count-based: "1 creature/level", "6 objects"
-            if let Some(caps) = self.area_count_regex.captures(&lower) {
-                let count_str = caps.get(1).map_or("1", |m| m.as_str());
-                let subject_str = caps.get(2).map_or("", |m| m.as_str());
-
-                let val = count_str.parse::<f64>().unwrap_or(1.0);
-                let is_per_level = lower.contains("/level");
+            // 2a. Every subject inside a radius: the shape is the radius, and `count` stays
+            // unset because "all" is unbounded.
+            if let Some(caps) = self.area_all_radius_regex.captures(&lower) {
+                let subject_str = caps.get(1).map_or("", |m| m.as_str());
+                let dist = caps
+                    .get(2)
+                    .map_or(0.0, |m| m.as_str().parse().unwrap_or(0.0));
+                let (u, su) = map_units(caps.get(3).map_or("", |m| m.as_str()));
+                return Some(AreaSpec {
+                    kind: AreaKind::RadiusCircle,
+                    unit: u,
+                    shape_unit: su,
+                    radius: Some(make_scalar(dist)),
+                    count_subject: map_count_subject(subject_str).1,
+                    notes: Some(format!("all {}", subject_str)),
+                    ..Default::default()
+                });
+            }
 
-                let scalar = if is_per_level {
-                    SpellScalar {
-                        mode: ScalarMode::PerLevel,
-                        per_level: Some(val),
-                        ..Default::default()
-                    }
+            // 2b. Count-based: "1 creature/level", "6 objects", "1 creature + 1/level",
+            // "up to 3 creatures within 30 ft."
+            // A trailing "within N unit" clause is split off so the containing radius is kept.
+            let (count_input, within) = match self.area_within_regex.captures(&lower) {
+                Some(caps) => (
+                    caps.get(1).map_or("", |m| m.as_str()).to_string(),
+                    Some((
+                        caps.get(2).map_or("", |m| m.as_str()).to_string(),
+                        caps.get(3).map_or("", |m| m.as_str()).to_string(),
+                    )),
+                ),
+                None => (lower.clone(), None),
+            };
+            let count_match =
+                if let Some(caps) = self.area_count_scaling_regex.captures(&count_input) {
+                    let base = caps
+                        .get(1)
+                        .map_or(1.0, |m| m.as_str().parse().unwrap_or(1.0));
+                    let per_level = caps
+                        .get(3)
+                        .map_or(1.0, |m| m.as_str().parse().unwrap_or(1.0));
+                    Some((
+                        SpellScalar {
+                            mode: ScalarMode::PerLevel,
+                            value: Some(base),
+                            per_level: Some(per_level),
+                            ..Default::default()
+                        },
+                        caps.get(2).map_or("", |m| m.as_str()).to_string(),
+                    ))
                 } else {
-                    make_scalar(val)
-                };
-
-                let (kind, subject) = match subject_str {
-                    "creature" | "creatures" | "target" | "targets" | "enemy" | "enemies"
-                    | "ally" | "allies" | "undead" => {
-                        (AreaKind::Creatures, Some(CountSubject::Creature))
-                    }
-                    "object" | "objects" => (AreaKind::Objects, Some(CountSubject::Object)),
-                    "structure" | "structures" => {
-                        (AreaKind::Objects, Some(CountSubject::Structure))
-                    }
-                    _ => (AreaKind::Creatures, None),
+                    self.area_count_regex.captures(&count_input).map(|caps| {
+                        let count_str = caps.get(1).map_or("1", |m| m.as_str());
+                        let val = count_str.parse::<f64>().unwrap_or(1.0);
+                        let scalar = if count_input.contains("/level") {
+                            SpellScalar {
+                                mode: ScalarMode::PerLevel,
+                                per_level: Some(val),
+                                ..Default::default()
+                            }
+                        } else {
+                            make_scalar(val)
+                        };
+                        (scalar, caps.get(2).map_or("", |m| m.as_str()).to_string())
+                    })
                 };
+            if let Some((scalar, subject_str)) = count_match {
+                let (kind, subject) = map_count_subject(&subject_str);
 
-                return Some(AreaSpec {
+                let mut spec = AreaSpec {
                     kind,
                     count: Some(scalar),
                     count_subject: subject,
                     ..Default::default()
-                });
+                };
+
+                if let Some((dist_str, unit_str)) = within {
+                    let (u, su) = map_units(&unit_str);
+                    let dist = dist_str.parse::<f64>().unwrap_or(0.0);
+                    spec.radius = Some(make_scalar(dist));
+                    spec.unit = u;
+                    spec.shape_unit = su;
+                    spec.notes = Some(format!(
+                        "within {} {}",
+                        crate::models::scalar::format_numeric(dist),
+                        su.map_or(unit_str.as_str(), |unit| unit.to_text())
+                    ));
+                }
+
+                return Some(spec);
             }
 
             // 3. Volume: "1000 cubic feet"
@@ -218,11 +279,20 @@ impl AreaParser {
                 }
             }
 
-            // 4. Tiles: "16 10ft. squares", "5 hexes"
+            // 4. Tiles: "16 10ft. squares", "5 hexes", "1 10-ft. square/level"
             if let Some(caps) = self.area_tile_regex.captures(&lower) {
                 let count = caps
                     .get(1)
                     .map_or(1.0, |m| m.as_str().parse().unwrap_or(1.0));
+                let tile_count = if caps.get(5).is_some() {
+                    SpellScalar {
+                        mode: ScalarMode::PerLevel,
+                        per_level: Some(count),
+                        ..Default::default()
+                    }
+                } else {
+                    make_scalar(count)
+                };
                 let size_str = caps.get(2).map_or("", |m| m.as_str());
                 let unit_str = caps.get(3).map_or("", |m| m.as_str());
                 let tile_kind = caps.get(4).map_or("", |m| m.as_str());
@@ -237,7 +307,7 @@ impl AreaParser {
 
                 return Some(AreaSpec {
                     kind: AreaKind::Tiles,
-                    tile_count: Some(make_scalar(count)),
+                    tile_count: Some(tile_count),
                     tile_unit,
                     length: if !size_str.is_empty() {
                         Some(make_scalar(size_str.parse().unwrap_or(0.0)))
@@ -400,6 +470,17 @@ impl AreaParser {
     }
 }
 
+/// Area kind and counted subject for a count noun such as "creatures" or "objects".
+fn map_count_subject(subject: &str) -> (AreaKind, Option<CountSubject>) {
+    match subject {
+        "creature" | "creatures" | "target" | "targets" | "enemy" | "enemies" | "ally"
+        | "allies" | "undead" => (AreaKind::Creatures, Some(CountSubject::Creature)),
+        "object" | "objects" => (AreaKind::Objects, Some(CountSubject::Object)),
+        "structure" | "structures" => (AreaKind::Objects, Some(CountSubject::Structure)),
+        _ => (AreaKind::Creatures, None),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -494,6 +575,80 @@ mod tests {
         assert_eq!(res3.count_subject, Some(CountSubject::Object));
     }
 
+    #[test]
+    fn test_parse_area_all_subjects_within_radius() {
+        let parser = AreaParser::new();
+
+        let res = parser.parse("all creatures within 20 ft. radius").unwrap();
+        assert_eq!(res.kind, AreaKind::RadiusCircle);
+        assert_eq!(res.radius.unwrap().value, Some(20.0));
+        assert_eq!(res.shape_unit, Some(AreaShapeUnit::Ft));
+        assert_eq!(res.count_subject, Some(CountSubject::Creature));
+        assert!(res.count.is_none(), "\"all\" leaves count unbounded");
+        assert_eq!(res.notes.as_deref(), Some("all creatures"));
+
+        let allies = parser.parse("All allies in a 15-foot radius").unwrap();
+        assert_eq!(allies.kind, AreaKind::RadiusCircle);
+        assert_eq!(allies.radius.unwrap().value, Some(15.0));
+        assert_eq!(allies.shape_unit, Some(AreaShapeUnit::Ft));
+        assert_eq!(allies.count_subject, Some(CountSubject::Creature));
+        assert!(allies.count.is_none());
+        assert_eq!(allies.text.as_deref(), Some("15 ft radius"));
+    }
+
+    #[test]
+    fn test_parse_area_count_base_plus_per_level() {
+        let parser = AreaParser::new();
+
+        let res = parser.parse("1 creature + 1/level").unwrap();
+        assert_eq!(res.kind, AreaKind::Creatures);
+        assert_eq!(res.count_subject, Some(CountSubject::Creature));
+        let count = res.count.unwrap();
+        assert_eq!(count.mode, ScalarMode::PerLevel);
+        assert_eq!(count.value, Some(1.0));
+        assert_eq!(count.per_level, Some(1.0));
+
+        let res2 = parser.parse("2 targets + 1 per level").unwrap();
+        assert_eq!(res2.count_subject, Some(CountSubject::Creature));
+        let count2 = res2.count.unwrap();
+        assert_eq!(count2.value, Some(2.0));
+        assert_eq!(count2.per_level, Some(1.0));
+
+        let fixed = parser.parse("6 objects").unwrap();
+        assert_eq!(fixed.kind, AreaKind::Objects);
+        let fixed_count = fixed.count.unwrap();
+        assert_eq!(fixed_count.mode, ScalarMode::Fixed);
+        assert_eq!(fixed_count.value, Some(6.0));
+        assert_eq!(fixed_count.per_level, None);
+
+        let per_level = parser.parse("1 creature/level").unwrap();
+        let per_level_count = per_level.count.unwrap();
+        assert_eq!(per_level_count.mode, ScalarMode::PerLevel);
+        assert_eq!(per_level_count.per_level, Some(1.0));
+        assert_eq!(per_level_count.value, None);
+    }
+
+    #[test]
+    fn test_parse_area_count_within_radius() {
+        let parser = AreaParser::new();
+
+        let res = parser.parse("up to 3 creatures within 30 ft.").unwrap();
+        assert_eq!(res.kind, AreaKind::Creatures);
+        assert_eq!(res.count.unwrap().value.unwrap(), 3.0);
+        assert_eq!(res.count_subject, Some(CountSubject::Creature));
+        assert_eq!(res.radius.unwrap().value.unwrap(), 30.0);
+        assert_eq!(res.unit, Some(AreaUnit::Ft));
+        assert_eq!(res.notes.as_deref(), Some("within 30 ft"));
+
+        let res2 = parser.parse("6 objects").unwrap();
+        assert_eq!(res2.kind, AreaKind::Objects);
+        assert_eq!(res2.count.unwrap().value.unwrap(), 6.0);
+        assert_eq!(res2.count_subject, Some(CountSubject::Object));
+        assert!(res2.radius.is_none());
+        assert!(res2.unit.is_none());
+        assert!(res2.notes.is_none());
+    }
+
     #[test]
     fn test_parse_area_volume_and_tiles() {
         let parser = AreaParser::new();
@@ -515,6 +670,28 @@ mod tests {
         assert_eq!(res3.tile_unit, Some(TileUnit::Hex));
     }
 
+    #[test]
+    fn test_parse_area_tiles_per_level_hyphenated_size() {
+        let parser = AreaParser::new();
+
+        let res = parser.parse("1 10-ft. square/level").unwrap();
+        assert_eq!(res.kind, AreaKind::Tiles);
+        assert_eq!(res.tile_unit, Some(TileUnit::Square));
+        let count = res.tile_count.unwrap();
+        assert_eq!(count.mode, ScalarMode::PerLevel);
+        assert_eq!(count.per_level, Some(1.0));
+        assert!(count.value.is_none());
+        assert_eq!(res.length.unwrap().value.unwrap(), 10.0);
+        assert_eq!(res.unit, Some(AreaUnit::Ft));
+
+        let hexes = parser.parse("5 hexes").unwrap();
+        assert_eq!(hexes.tile_count.unwrap().mode, ScalarMode::Fixed);
+
+        let squares = parser.parse("16 10ft. squares").unwrap();
+        assert_eq!(squares.tile_count.unwrap().value.unwrap(), 16.0);
+        assert_eq!(squares.unit, Some(AreaUnit::Ft));
+    }
+
     #[test]
     fn test_parse_area_point() {
         let parser = AreaParser::new();
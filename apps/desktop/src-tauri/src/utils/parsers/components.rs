@@ -1,6 +1,27 @@
 use crate::models::{CastingTimeUnit, MaterialComponentSpec, SpellCastingTime, SpellComponents};
 use regex::Regex;
 
+/// Maps reaction-style casting times to their unit. Checked most specific first, so
+/// "free action" is Free and "bonus action" is Bonus rather than Action.
+fn action_style_unit(lower: &str) -> Option<CastingTimeUnit> {
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let has = |word: &str| words.contains(&word);
+    if has("reaction") {
+        Some(CastingTimeUnit::Reaction)
+    } else if has("bonus") && (has("action") || words.len() == 1) {
+        Some(CastingTimeUnit::Bonus)
+    } else if has("free") && (has("action") || words.len() == 1) {
+        Some(CastingTimeUnit::Free)
+    } else if has("action") || has("actions") {
+        Some(CastingTimeUnit::Action)
+    } else {
+        None
+    }
+}
+
 pub struct ComponentsParser;
 
 impl Default for ComponentsParser {
@@ -33,33 +54,12 @@ impl ComponentsParser {
             .and_then(|s| s.parse::<f64>().ok())
             .unwrap_or(1.0);
 
-        // 5e units (Action, BonusAction, Reaction) are not valid for 1e/2e content.
-        // Per task 1.4: the parser must remap them to Special and always preserve the
-        // source text in raw_legacy_value so migration is not required to recover it.
-        if lower.contains("bonus action") {
+        // Reaction-style times get their own units; the count stays in base_value.
+        if let Some(unit) = action_style_unit(&lower) {
             return SpellCastingTime {
                 text: input_clean.to_string(),
-                unit: CastingTimeUnit::Special,
+                unit,
                 base_value: Some(base_val),
-                raw_legacy_value: Some(input_clean.to_string()),
-                ..Default::default()
-            };
-        }
-        if lower.contains("reaction") {
-            return SpellCastingTime {
-                text: input_clean.to_string(),
-                unit: CastingTimeUnit::Special,
-                base_value: Some(base_val),
-                raw_legacy_value: Some(input_clean.to_string()),
-                ..Default::default()
-            };
-        }
-        if lower.contains("action") {
-            return SpellCastingTime {
-                text: input_clean.to_string(),
-                unit: CastingTimeUnit::Special,
-                base_value: Some(base_val),
-                raw_legacy_value: Some(input_clean.to_string()),
                 ..Default::default()
             };
         }
@@ -202,6 +202,29 @@ impl ComponentsParser {
         }
     }
 
+    /// Renders present components as "V, S, M, F, DF, XP" in that fixed order.
+    pub fn render_components(components: &SpellComponents) -> String {
+        [
+            (components.verbal, "V"),
+            (components.somatic, "S"),
+            (components.material, "M"),
+            (components.focus, "F"),
+            (components.divine_focus, "DF"),
+            (components.experience, "XP"),
+        ]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, letter)| *letter)
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    /// Re-renders a free-form component string in canonical form, independent of the
+    /// input's ordering, casing, and delimiters.
+    pub fn canonicalize_components(&self, input: &str) -> String {
+        Self::render_components(&self.parse_components(input))
+    }
+
     pub fn parse_material_components(&self, input: &str) -> Vec<MaterialComponentSpec> {
         let input_clean = input.trim();
         if input_clean.is_empty() || input_clean == "None" || input_clean == "none" {
@@ -213,12 +236,19 @@ impl ComponentsParser {
         let mut parts = Vec::new();
         let mut current_part = String::new();
 
-        for part in raw_parts {
+        for (i, part) in raw_parts.iter().enumerate() {
             if !current_part.is_empty() {
                 current_part.push(',');
             }
             current_part.push_str(part);
-            if current_part.matches('(').count() == current_part.matches(')').count() {
+            // Keep thousands separators ("5,000 gp") inside one part.
+            let splits_number = current_part.ends_with(|c: char| c.is_ascii_digit())
+                && raw_parts
+                    .get(i + 1)
+                    .is_some_and(|next| next.chars().take_while(char::is_ascii_digit).count() == 3);
+            if !splits_number
+                && current_part.matches('(').count() == current_part.matches(')').count()
+            {
                 parts.push(current_part.trim().to_string());
                 current_part = String::new();
             }
@@ -228,7 +258,9 @@ impl ComponentsParser {
         }
 
         let mut results = Vec::new();
-        let gp_regex = Regex::new(r"(?i)(?:worth\s+)?(\d+(?:\.\d+)?)\s*gp").unwrap();
+        let gp_regex =
+            Regex::new(r"(?i)(?:worth\s+)?(\d{1,3}(?:,\d{3})+(?:\.\d+)?|\d+(?:\.\d+)?)\s*gp")
+                .unwrap();
         let consumed_regex = Regex::new(r"(?i)\b(consumed|expended|destroyed)\b").unwrap();
         // Remove entire parenthetical block containing gp value (handles "ruby (worth 1000 gp, consumed)")
         let paren_with_gp_regex =
@@ -245,7 +277,9 @@ impl ComponentsParser {
 
             // Extract GP value
             if let Some(caps) = gp_regex.captures(&p) {
-                gp_value = caps.get(1).and_then(|m| m.as_str().parse::<f64>().ok());
+                gp_value = caps
+                    .get(1)
+                    .and_then(|m| m.as_str().replace(',', "").parse::<f64>().ok());
                 // Remove the entire parenthetical block containing gp, OR just the gp match if not in parens
                 if paren_with_gp_regex.is_match(&name) {
                     name = paren_with_gp_regex.replace_all(&name, "").to_string();
@@ -343,9 +377,8 @@ mod tests {
         assert_eq!(res.unit, CastingTimeUnit::Round);
 
         let res2 = parser.parse_casting_time("1 action");
-        // Per task 1.4: 5e units are remapped to Special at parse time
-        assert_eq!(res2.unit, CastingTimeUnit::Special);
-        assert_eq!(res2.raw_legacy_value.as_deref(), Some("1 action"));
+        assert_eq!(res2.unit, CastingTimeUnit::Action);
+        assert_eq!(res2.text, "1 action");
     }
 
     /// Task 1.5: Empty casting time input must yield raw_legacy_value: None (default struct).
@@ -493,14 +526,57 @@ mod tests {
     fn test_parse_casting_time_bonus_reaction() {
         let parser = ComponentsParser::new();
 
-        // Per task 1.4: 5e units are remapped to Special at parse time
         let res = parser.parse_casting_time("1 bonus action");
-        assert_eq!(res.unit, CastingTimeUnit::Special);
-        assert_eq!(res.raw_legacy_value.as_deref(), Some("1 bonus action"));
+        assert_eq!(res.unit, CastingTimeUnit::Bonus);
+        assert_eq!(res.text, "1 bonus action");
+        assert_eq!(res.raw_legacy_value, None);
 
         let res2 = parser.parse_casting_time("1 reaction");
-        assert_eq!(res2.unit, CastingTimeUnit::Special);
-        assert_eq!(res2.raw_legacy_value.as_deref(), Some("1 reaction"));
+        assert_eq!(res2.unit, CastingTimeUnit::Reaction);
+        assert_eq!(res2.text, "1 reaction");
+    }
+
+    #[test]
+    fn test_parse_casting_time_action_style_units() {
+        let parser = ComponentsParser::new();
+
+        let res = parser.parse_casting_time("1 action");
+        assert_eq!(res.unit, CastingTimeUnit::Action);
+        assert_eq!(res.text, "1 action");
+        assert_eq!(res.base_value, Some(1.0));
+
+        let res = parser.parse_casting_time("reaction");
+        assert_eq!(res.unit, CastingTimeUnit::Reaction);
+        assert_eq!(res.text, "reaction");
+        assert_eq!(res.base_value, Some(1.0));
+
+        let res = parser.parse_casting_time("bonus action");
+        assert_eq!(res.unit, CastingTimeUnit::Bonus);
+        assert_eq!(res.base_value, Some(1.0));
+        assert_eq!(
+            parser.parse_casting_time("bonus").unit,
+            CastingTimeUnit::Bonus
+        );
+
+        assert_eq!(
+            parser.parse_casting_time("free action").unit,
+            CastingTimeUnit::Free
+        );
+        assert_eq!(
+            parser.parse_casting_time("Free").unit,
+            CastingTimeUnit::Free
+        );
+
+        let res = parser.parse_casting_time("2 actions");
+        assert_eq!(res.unit, CastingTimeUnit::Action);
+        assert_eq!(res.text, "2 actions");
+        assert_eq!(res.base_value, Some(2.0));
+
+        // Numeric + unit values are unaffected.
+        let res = parser.parse_casting_time("3 rounds");
+        assert_eq!(res.unit, CastingTimeUnit::Round);
+        assert_eq!(res.text, "3 rounds");
+        assert_eq!(res.base_value, Some(3.0));
     }
 
     #[test]
@@ -517,6 +593,27 @@ mod tests {
         assert_eq!(res3.unit, CastingTimeUnit::Segment);
     }
 
+    #[test]
+    fn test_canonicalize_components() {
+        let parser = ComponentsParser::new();
+        assert_eq!(
+            parser.canonicalize_components("verbal somatic material"),
+            "V, S, M"
+        );
+        assert_eq!(parser.canonicalize_components("m,s,v"), "V, S, M");
+        assert_eq!(
+            parser.canonicalize_components("S; Divine Focus; V"),
+            "V, S, DF"
+        );
+        assert_eq!(parser.canonicalize_components(""), "");
+
+        for canonical in ["V, S, M", "V, S, M, F, DF", "S", "V, DF"] {
+            let once = parser.canonicalize_components(canonical);
+            assert_eq!(once, canonical);
+            assert_eq!(parser.canonicalize_components(&once), once);
+        }
+    }
+
     #[test]
     fn test_parse_components_undelimited() {
         let parser = ComponentsParser::new();
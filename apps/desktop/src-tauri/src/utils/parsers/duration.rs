@@ -6,6 +6,9 @@ pub struct DurationParser {
     duration_simple_regex: Regex,
     duration_divisor_regex: Regex,
     duration_usage_regex: Regex,
+    duration_usage_scaling_regex: Regex,
+    lingering_time_regex: Regex,
+    duration_phased_regex: Regex,
 }
 
 impl Default for DurationParser {
@@ -21,6 +24,12 @@ impl DurationParser {
             duration_divisor_regex: Regex::new(r"(?i)^(\d+(?:\.\d+)?)\s*([a-z\.]+)\s*/\s*(\d+)\s*levels?$").unwrap(),
             // Pattern: "6 uses", "1 charge/level", "3 strikes"
             duration_usage_regex: Regex::new(r#"(?i)^(\d+(?:\.\d+)?)\s*(?:/level)?\s*(uses?|charges?|activations?|strikes?|discharges?)(?:\s*/level)?$"#).unwrap(),
+            // Pattern: "3 charges + 1/level", "2 uses plus 1 use/level"
+            duration_usage_scaling_regex: Regex::new(r#"(?i)^(\d+(?:\.\d+)?)\s*(uses?|charges?|activations?|strikes?|discharges?)\s*(?:\+|plus)\s*(\d+(?:\.\d+)?)\s*(?:(?:uses?|charges?|activations?|strikes?|discharges?)\s*)?/\s*level$"#).unwrap(),
+            // A concrete time inside a trailing note: "effects last 1 round"
+            lingering_time_regex: Regex::new(r"(?i)\b(\d+(?:\.\d+)?)\s*([a-z\.]+)").unwrap(),
+            // Pattern: "3 rounds, then 3 rounds weaker", "1 turn; then 2 turns at half strength"
+            duration_phased_regex: Regex::new(r"(?i)^(\d+(?:\.\d+)?)\s*([a-z\.]+)[^,;/]*?[,;]?\s*then\s+(\d+(?:\.\d+)?)\s*([a-z\.]+)(?:\s[^/]*)?$").unwrap(),
         }
     }
 
@@ -29,16 +38,25 @@ impl DurationParser {
         let mut res = (|| {
             let mut lower = input_clean.to_lowercase();
 
-            if lower == "instantaneous" || lower == "instant" {
+            // "Instantaneous (see text)", "Instantaneous; effects last 1 round"
+            let (head, trailing_note) = split_trailing_clause(input_clean);
+            let head_lower = head.to_lowercase();
+
+            if head_lower == "instantaneous" || head_lower == "instant" {
+                let lingering = trailing_note.and_then(|note| self.lingering_time(note));
                 return DurationSpec {
                     kind: DurationKind::Instant,
+                    unit: lingering.as_ref().map(|(u, _)| u.clone()),
+                    duration: lingering.map(|(_, scalar)| scalar),
+                    notes: trailing_note.map(str::to_string),
                     ..Default::default()
                 };
             }
 
-            if lower == "permanent" {
+            if head_lower == "permanent" {
                 return DurationSpec {
                     kind: DurationKind::Permanent,
+                    notes: trailing_note.map(str::to_string),
                     ..Default::default()
                 };
             }
@@ -118,6 +136,28 @@ impl DurationParser {
                 };
             }
 
+            // Handle Usage Limited with a base plus per-level increment "3 charges + 1/level"
+            if let Some(caps) = self.duration_usage_scaling_regex.captures(&lower) {
+                let base = caps
+                    .get(1)
+                    .map_or(0.0, |m| m.as_str().parse().unwrap_or(0.0));
+                let per_level = caps
+                    .get(3)
+                    .map_or(0.0, |m| m.as_str().parse().unwrap_or(0.0));
+
+                return DurationSpec {
+                    kind: DurationKind::UsageLimited,
+                    uses: Some(SpellScalar {
+                        mode: ScalarMode::PerLevel,
+                        value: Some(base),
+                        per_level: Some(per_level),
+                        ..Default::default()
+                    }),
+                    notes: caps.get(2).map(|m| m.as_str().to_string()),
+                    ..Default::default()
+                };
+            }
+
             // Handle Usage Limited "6 uses", "1 charge/level"
             if let Some(caps) = self.duration_usage_regex.captures(&lower) {
                 let val = caps
@@ -142,6 +182,20 @@ impl DurationParser {
                 return DurationSpec {
                     kind: DurationKind::UsageLimited,
                     uses: Some(scalar),
+                    notes: caps.get(2).map(|m| m.as_str().to_string()),
+                    ..Default::default()
+                };
+            }
+
+            // Phased: "3 rounds, then 3 rounds weaker" keeps the phasing in notes but
+            // carries the combined total when both phases share a unit.
+            if let Some(total) = self.phased_total(&lower) {
+                let (unit, scalar) = total;
+                return DurationSpec {
+                    kind: DurationKind::Special,
+                    unit: Some(unit),
+                    duration: Some(scalar),
+                    notes: Some(input_clean.to_string()),
                     ..Default::default()
                 };
             }
@@ -165,22 +219,6 @@ impl DurationParser {
                 }
             }
 
-            // Helper to map units
-            let map_unit = |u: &str| -> Option<DurationUnit> {
-                match u.to_lowercase().as_str() {
-                    "round" | "rounds" => Some(DurationUnit::Round),
-                    "turn" | "turns" => Some(DurationUnit::Turn),
-                    "minute" | "minutes" | "min" | "min." => Some(DurationUnit::Minute),
-                    "hour" | "hours" | "hr" | "hr." => Some(DurationUnit::Hour),
-                    "day" | "days" => Some(DurationUnit::Day),
-                    "week" | "weeks" => Some(DurationUnit::Week),
-                    "month" | "months" => Some(DurationUnit::Month),
-                    "year" | "years" => Some(DurationUnit::Year),
-                    "segment" | "segments" => Some(DurationUnit::Segment),
-                    _ => None,
-                }
-            };
-
             // Pattern 4: Per N Levels "1 round / 2 levels"
             if let Some(caps) = self.duration_divisor_regex.captures(target_str.trim()) {
                 let per_level = caps
@@ -191,7 +229,7 @@ impl DurationParser {
                     .get(3)
                     .map_or(1.0, |m| m.as_str().parse().unwrap_or(1.0));
 
-                let unit = map_unit(unit_raw);
+                let unit = map_duration_unit(unit_raw);
                 if let Some(u) = unit {
                     let adjusted_per_level = if divisor > 0.0 {
                         per_level / divisor
@@ -229,7 +267,7 @@ impl DurationParser {
                             .get(1)
                             .map_or(0.0, |m| m.as_str().parse().unwrap_or(0.0));
                         let unit_raw = caps.get(2).map_or("", |m| m.as_str());
-                        let unit = map_unit(unit_raw);
+                        let unit = map_duration_unit(unit_raw);
 
                         if let Some(u) = unit {
                             let scalar = SpellScalar {
@@ -259,7 +297,7 @@ impl DurationParser {
                     .get(1)
                     .map_or(0.0, |m| m.as_str().parse().unwrap_or(0.0));
                 let unit_raw = caps.get(2).map_or("", |m| m.as_str());
-                let unit = map_unit(unit_raw);
+                let unit = map_duration_unit(unit_raw);
 
                 if let Some(u) = unit {
                     let scalar = SpellScalar {
@@ -298,6 +336,75 @@ impl DurationParser {
         res.synthesize_text();
         res
     }
+
+    /// Sum of both phases of "N units, then M units ..." when the units match.
+    fn phased_total(&self, input: &str) -> Option<(DurationUnit, SpellScalar)> {
+        let caps = self.duration_phased_regex.captures(input)?;
+        let first_unit = map_duration_unit(caps.get(2)?.as_str())?;
+        let second_unit = map_duration_unit(caps.get(4)?.as_str())?;
+        if first_unit != second_unit {
+            return None;
+        }
+        let first: f64 = caps.get(1)?.as_str().parse().ok()?;
+        let second: f64 = caps.get(3)?.as_str().parse().ok()?;
+        Some((
+            first_unit,
+            SpellScalar {
+                mode: ScalarMode::Fixed,
+                value: Some(first + second),
+                ..Default::default()
+            },
+        ))
+    }
+
+    /// First fixed time (e.g. "1 round") mentioned in a note, for instant effects that linger.
+    fn lingering_time(&self, note: &str) -> Option<(DurationUnit, SpellScalar)> {
+        self.lingering_time_regex
+            .captures_iter(note)
+            .find_map(|caps| {
+                let unit = map_duration_unit(caps.get(2)?.as_str())?;
+                let value: f64 = caps.get(1)?.as_str().parse().ok()?;
+                Some((
+                    unit,
+                    SpellScalar {
+                        mode: ScalarMode::Fixed,
+                        value: Some(value),
+                        ..Default::default()
+                    },
+                ))
+            })
+    }
+}
+
+fn map_duration_unit(u: &str) -> Option<DurationUnit> {
+    match u.to_lowercase().as_str() {
+        "round" | "rounds" => Some(DurationUnit::Round),
+        "turn" | "turns" => Some(DurationUnit::Turn),
+        "minute" | "minutes" | "min" | "min." => Some(DurationUnit::Minute),
+        "hour" | "hours" | "hr" | "hr." => Some(DurationUnit::Hour),
+        "day" | "days" => Some(DurationUnit::Day),
+        "week" | "weeks" => Some(DurationUnit::Week),
+        "month" | "months" => Some(DurationUnit::Month),
+        "year" | "years" => Some(DurationUnit::Year),
+        "segment" | "segments" => Some(DurationUnit::Segment),
+        _ => None,
+    }
+}
+
+/// Splits "Instantaneous (see text)" / "Instantaneous; effects last 1 round" into the
+/// leading keyword and the trailing clause. Input without such a clause is returned whole.
+fn split_trailing_clause(input: &str) -> (&str, Option<&str>) {
+    if let Some((head, rest)) = input.split_once(';') {
+        let rest = rest.trim();
+        return (head.trim(), (!rest.is_empty()).then_some(rest));
+    }
+    if input.ends_with(')') {
+        if let Some(open) = input.find('(') {
+            let note = input[open + 1..input.len() - 1].trim();
+            return (input[..open].trim(), (!note.is_empty()).then_some(note));
+        }
+    }
+    (input, None)
 }
 
 #[cfg(test)]
@@ -314,6 +421,29 @@ mod tests {
         assert_eq!(res.unit, Some(DurationUnit::Round));
     }
 
+    #[test]
+    fn test_parse_duration_phased_same_unit_sums_total() {
+        let parser = DurationParser::new();
+        let res = parser.parse("3 rounds, then 3 rounds weaker");
+        assert_eq!(res.kind, DurationKind::Special);
+        assert_eq!(res.unit, Some(DurationUnit::Round));
+        assert_eq!(res.duration.unwrap().value, Some(6.0));
+        assert_eq!(res.notes.as_deref(), Some("3 rounds, then 3 rounds weaker"));
+    }
+
+    #[test]
+    fn test_parse_duration_phased_mismatched_units_stays_special() {
+        let parser = DurationParser::new();
+        let res = parser.parse("1 turn, then 2 rounds at half strength");
+        assert_eq!(res.kind, DurationKind::Special);
+        assert!(res.unit.is_none());
+        assert!(res.duration.is_none());
+        assert_eq!(
+            res.notes.as_deref(),
+            Some("1 turn, then 2 rounds at half strength")
+        );
+    }
+
     #[test]
     fn test_parse_duration_per_level() {
         let parser = DurationParser::new();
@@ -333,6 +463,28 @@ mod tests {
         assert_eq!(res2.kind, DurationKind::Permanent);
     }
 
+    #[test]
+    fn test_parse_duration_instant_with_parenthetical_note() {
+        let parser = DurationParser::new();
+        let res = parser.parse("Instantaneous (see text)");
+        assert_eq!(res.kind, DurationKind::Instant);
+        assert_eq!(res.notes.as_deref(), Some("see text"));
+        assert!(res.duration.is_none());
+        assert!(res.unit.is_none());
+    }
+
+    #[test]
+    fn test_parse_duration_instant_with_lingering_effect() {
+        let parser = DurationParser::new();
+        let res = parser.parse("Instantaneous; effects last 1 round");
+        assert_eq!(res.kind, DurationKind::Instant);
+        assert_eq!(res.notes.as_deref(), Some("effects last 1 round"));
+        assert_eq!(res.unit, Some(DurationUnit::Round));
+        let lingering = res.duration.unwrap();
+        assert_eq!(lingering.mode, ScalarMode::Fixed);
+        assert_eq!(lingering.value, Some(1.0));
+    }
+
     #[test]
     fn test_parse_duration_extended_keywords() {
         let parser = DurationParser::new();
@@ -360,6 +512,33 @@ mod tests {
         assert_eq!(res2.uses.unwrap().value.unwrap(), 3.0);
     }
 
+    #[test]
+    fn test_parse_duration_usage_base_plus_per_level() {
+        let parser = DurationParser::new();
+
+        let res = parser.parse("3 charges + 1/level");
+        assert_eq!(res.kind, DurationKind::UsageLimited);
+        assert_eq!(res.notes.as_deref(), Some("charges"));
+        let uses = res.uses.unwrap();
+        assert_eq!(uses.mode, ScalarMode::PerLevel);
+        assert_eq!(uses.value, Some(3.0));
+        assert_eq!(uses.per_level, Some(1.0));
+
+        let res2 = parser.parse("2 strikes plus 1 strike/level");
+        assert_eq!(res2.notes.as_deref(), Some("strikes"));
+        let uses2 = res2.uses.unwrap();
+        assert_eq!(uses2.value, Some(2.0));
+        assert_eq!(uses2.per_level, Some(1.0));
+
+        let fixed = parser.parse("6 uses");
+        assert_eq!(fixed.kind, DurationKind::UsageLimited);
+        assert_eq!(fixed.notes.as_deref(), Some("uses"));
+        let fixed_uses = fixed.uses.unwrap();
+        assert_eq!(fixed_uses.mode, ScalarMode::Fixed);
+        assert_eq!(fixed_uses.value, Some(6.0));
+        assert_eq!(fixed_uses.per_level, None);
+    }
+
     #[test]
     fn test_parse_duration_planar_conditional() {
         let parser = DurationParser::new();
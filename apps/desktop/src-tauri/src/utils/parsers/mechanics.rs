@@ -9,6 +9,9 @@ pub struct MechanicsParser {
     dice_term_regex: Regex,
     scaling_rule_regex: Regex,
     xp_regex: Regex,
+    each_round_regex: Regex,
+    round_count_regex: Regex,
+    repeat_count_regex: Regex,
 }
 
 impl Default for MechanicsParser {
@@ -48,6 +51,9 @@ impl MechanicsParser {
             dice_term_regex: Regex::new(r"(?P<count>\d+)?d(?P<sides>\d+)(?:\s*(?P<mod>[+-]\s*\d+))?").unwrap(),
             scaling_rule_regex: Regex::new(r"(?i)\/(?:per\s+)?(?P<step>\d+)?\s*(?P<unit>level|hd|caster level|spell level|target hd)(?:\s*\(max\s*(?P<max>[^)]+)\))?").unwrap(),
             xp_regex: Regex::new(r"(?i)(?P<val>\d+(?:,\d+)*)\s*xp").unwrap(),
+            each_round_regex: Regex::new(r"\beach\s+(?:round|turn)\b").unwrap(),
+            round_count_regex: Regex::new(r"\b(\d+)\s+(?:rounds?|turns?)\b").unwrap(),
+            repeat_count_regex: Regex::new(r"\b(twice|thrice|(\d+)\s+(?:times|saves|saving throws))\b").unwrap(),
         }
     }
 
@@ -322,6 +328,10 @@ impl MechanicsParser {
             };
         }
 
+        if let Some(spec) = self.parse_repeated_save(input_clean) {
+            return spec;
+        }
+
         // Split by delimiters to detect multiple saves
         // Delimiters: ";", " and ", " or ", " then " (case insensitive)
         let split_regex = Regex::new(r"(?i)(?:;| and | or |\s+then\s+)").unwrap();
@@ -364,6 +374,74 @@ impl MechanicsParser {
         }
     }
 
+    /// Detects iterative saves ("save each round (3 rounds)", "save twice"). A stated count
+    /// expands into that many ordered entries; an open-ended "each round" stays a single save
+    /// with per-round timing. Returns None for ordinary one-off saves.
+    fn parse_repeated_save(&self, input_clean: &str) -> Option<SavingThrowSpec> {
+        const MAX_REPEATED_SAVES: usize = 20;
+
+        let lower = input_clean.to_lowercase();
+        let (applies_to, timing, id_prefix, count) = if self.each_round_regex.is_match(&lower) {
+            let count = self
+                .round_count_regex
+                .captures(&lower)
+                .and_then(|caps| caps[1].parse::<usize>().ok());
+            (
+                SaveAppliesTo::EachRound,
+                SaveTiming::EndOfRound,
+                "round",
+                count,
+            )
+        } else if let Some(caps) = self.repeat_count_regex.captures(&lower) {
+            let count = match &caps[1] {
+                "twice" => Some(2),
+                "thrice" => Some(3),
+                _ => caps.get(2).and_then(|m| m.as_str().parse::<usize>().ok()),
+            };
+            (
+                SaveAppliesTo::EachApplication,
+                SaveTiming::OnEffect,
+                "save",
+                count,
+            )
+        } else {
+            return None;
+        };
+
+        let base = SingleSave {
+            applies_to,
+            timing,
+            ..self.parse_single_save_intern(input_clean)
+        };
+
+        match count {
+            Some(n) if (2..=MAX_REPEATED_SAVES).contains(&n) => {
+                let saves = (1..=n)
+                    .map(|i| SingleSave {
+                        id: Some(format!("{id_prefix}_{i}")),
+                        ..base.clone()
+                    })
+                    .collect();
+                Some(SavingThrowSpec {
+                    kind: SavingThrowKind::Multiple,
+                    single: None,
+                    multiple: Some(saves),
+                    raw_legacy_value: Some(input_clean.to_string()),
+                    legacy_dm_guidance: None,
+                    notes: None,
+                })
+            }
+            _ => Some(SavingThrowSpec {
+                kind: SavingThrowKind::Single,
+                single: Some(base),
+                multiple: None,
+                raw_legacy_value: Some(input_clean.to_string()),
+                legacy_dm_guidance: None,
+                notes: None,
+            }),
+        }
+    }
+
     fn parse_single_save_intern(&self, input: &str) -> SingleSave {
         let input_clean = input.trim();
         let mut result_kind = SaveResult::NoEffect; // Default for "Negates"
@@ -673,6 +751,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_saving_throw_repeated_each_round() {
+        let parser = MechanicsParser::new();
+
+        let res = parser.parse_saving_throw("Save vs. spell each round (3 rounds)");
+        assert_eq!(res.kind, SavingThrowKind::Multiple);
+        assert!(res.single.is_none());
+        let saves = res.multiple.unwrap();
+        assert_eq!(saves.len(), 3);
+        for (i, save) in saves.iter().enumerate() {
+            assert_eq!(
+                save.id.as_deref(),
+                Some(format!("round_{}", i + 1).as_str())
+            );
+            assert_eq!(save.save_type, SaveType::Spell);
+            assert_eq!(save.applies_to, SaveAppliesTo::EachRound);
+            assert_eq!(save.timing, SaveTiming::EndOfRound);
+        }
+
+        let twice = parser.parse_saving_throw("Save vs. poison twice");
+        let twice_saves = twice.multiple.unwrap();
+        assert_eq!(twice_saves.len(), 2);
+        assert_eq!(twice_saves[1].applies_to, SaveAppliesTo::EachApplication);
+        assert_eq!(twice_saves[1].save_vs, SaveVs::Poison);
+
+        let open_ended = parser.parse_saving_throw("Save negates each round");
+        assert_eq!(open_ended.kind, SavingThrowKind::Single);
+        assert_eq!(open_ended.single.unwrap().timing, SaveTiming::EndOfRound);
+
+        let single = parser.parse_saving_throw("Save negates");
+        assert_eq!(single.kind, SavingThrowKind::Single);
+        assert!(single.multiple.is_none());
+        let save = single.single.unwrap();
+        assert_eq!(save.on_success.result, SaveResult::NoEffect);
+        assert_eq!(save.applies_to, SaveAppliesTo::EachTarget);
+        assert_eq!(save.timing, SaveTiming::OnEffect);
+    }
+
     /// Task 1.5: Empty and "None" sentinel must yield raw_legacy_value: None.
     #[test]
     fn test_parse_saving_throw_empty_and_none_raw_legacy_value_none() {
@@ -45,6 +45,9 @@ impl RangeParser {
         if input_clean.is_empty() {
             return RangeSpec::default();
         }
+        if let Some(spec) = self.parse_dual_range(input_clean) {
+            return spec;
+        }
 
         let mut lower = input_clean.to_lowercase();
         let mut context = Vec::new();
@@ -440,15 +443,30 @@ impl RangeParser {
                 }
             }
 
-            matched_spec.unwrap_or_else(|| RangeSpec {
-                kind: RangeKind::Special,
-                text: Some(input_clean.to_string()),
-                raw_legacy_value: Some(input_clean.to_string()),
-                requires: requires.clone(),
-                anchor,
-                region_unit,
-                notes: None, // input_clean is already in .text
-                ..Default::default()
+            matched_spec.unwrap_or_else(|| {
+                let mut special = RangeSpec {
+                    kind: RangeKind::Special,
+                    text: Some(input_clean.to_string()),
+                    raw_legacy_value: Some(input_clean.to_string()),
+                    requires: requires.clone(),
+                    anchor,
+                    region_unit,
+                    notes: None, // input_clean is already in .text
+                    ..Default::default()
+                };
+                // "Special (100 yards)", "See text (touch)": keep Special but surface the
+                // embedded distance or keyword.
+                if let Some(embedded) = self.parse_embedded_parenthetical(input_clean) {
+                    match embedded.kind {
+                        RangeKind::Distance | RangeKind::DistanceLos | RangeKind::DistanceLoe => {
+                            special.distance = embedded.distance;
+                            special.unit = embedded.unit;
+                        }
+                        kind => special.alt_kind = Some(kind),
+                    }
+                    special.notes = Some(input_clean.to_string());
+                }
+                special
             })
         };
 
@@ -456,6 +474,52 @@ impl RangeParser {
         res.raw_legacy_value = Some(input_clean.to_string());
         res
     }
+
+    /// Parses a trailing parenthetical ("Special (100 yards)") on its own. Returns `None`
+    /// when there is none or it is not a recognizable range either.
+    fn parse_embedded_parenthetical(&self, input: &str) -> Option<RangeSpec> {
+        let inner = input.strip_suffix(')')?;
+        let (_, inner) = inner.rsplit_once('(')?;
+        let inner = inner.trim();
+        if inner.is_empty() {
+            return None;
+        }
+        let spec = self.parse(inner);
+        (spec.kind != RangeKind::Special).then_some(spec)
+    }
+
+    /// Parses "X or Y" ranges where at least one side is a keyword ("Touch or 30 ft.",
+    /// "Personal or touch"). The distance side (or the first keyword) becomes the
+    /// primary spec and the other keyword is recorded in `alt_kind`.
+    fn parse_dual_range(&self, input: &str) -> Option<RangeSpec> {
+        let lower = input.to_lowercase();
+        let (left, right) = lower.split_once(" or ")?;
+        if right.contains(" or ") {
+            return None;
+        }
+
+        let is_keyword = |spec: &RangeSpec| {
+            !matches!(
+                spec.kind,
+                RangeKind::Distance
+                    | RangeKind::DistanceLos
+                    | RangeKind::DistanceLoe
+                    | RangeKind::Special
+            )
+        };
+        let left_spec = self.parse(left);
+        let right_spec = self.parse(right);
+        let (mut primary, alt) = match (is_keyword(&left_spec), is_keyword(&right_spec)) {
+            (true, false) if right_spec.kind != RangeKind::Special => (right_spec, left_spec),
+            (_, true) if left_spec.kind != RangeKind::Special => (left_spec, right_spec),
+            _ => return None,
+        };
+
+        primary.alt_kind = Some(alt.kind);
+        primary.text = Some(input.to_string());
+        primary.raw_legacy_value = Some(input.to_string());
+        Some(primary)
+    }
 }
 
 #[cfg(test)]
@@ -634,4 +698,57 @@ mod tests {
         assert_eq!(res2.kind, RangeKind::Special);
         assert_eq!(res2.raw_legacy_value.as_ref().unwrap(), "Remote corner");
     }
+
+    #[test]
+    fn test_parse_range_special_with_embedded_distance() {
+        let parser = RangeParser::new();
+        let res = parser.parse("Special (100 yards)");
+        assert_eq!(res.kind, RangeKind::Special);
+        assert_eq!(res.unit, Some(RangeUnit::Yd));
+        assert_eq!(res.distance.as_ref().unwrap().value, Some(100.0));
+        assert_eq!(res.notes.as_deref(), Some("Special (100 yards)"));
+        assert_eq!(res.raw_legacy_value.as_deref(), Some("Special (100 yards)"));
+    }
+
+    #[test]
+    fn test_parse_range_special_with_embedded_keyword() {
+        let parser = RangeParser::new();
+        let res = parser.parse("See text (touch)");
+        assert_eq!(res.kind, RangeKind::Special);
+        assert_eq!(res.alt_kind, Some(RangeKind::Touch));
+        assert!(res.distance.is_none());
+        assert_eq!(res.notes.as_deref(), Some("See text (touch)"));
+
+        // An unrecognized parenthetical leaves the plain Special fallback untouched.
+        let res2 = parser.parse("See text (varies)");
+        assert_eq!(res2.kind, RangeKind::Special);
+        assert!(res2.alt_kind.is_none());
+        assert!(res2.notes.is_none());
+    }
+
+    #[test]
+    fn test_parse_range_touch_or_distance() {
+        let parser = RangeParser::new();
+        let res = parser.parse("Touch or 30 ft.");
+        assert_eq!(res.kind, RangeKind::Distance);
+        assert_eq!(res.unit, Some(RangeUnit::Ft));
+        assert_eq!(res.distance.as_ref().unwrap().value, Some(30.0));
+        assert_eq!(res.alt_kind, Some(RangeKind::Touch));
+        assert_eq!(res.raw_legacy_value.as_deref(), Some("Touch or 30 ft."));
+    }
+
+    #[test]
+    fn test_parse_range_personal_or_touch() {
+        let parser = RangeParser::new();
+        let res = parser.parse("Personal or touch");
+        assert_eq!(res.kind, RangeKind::Personal);
+        assert!(res.distance.is_none());
+        assert_eq!(res.alt_kind, Some(RangeKind::Touch));
+        assert_eq!(res.text.as_deref(), Some("Personal or touch"));
+
+        // Two distances are not a keyword alternative and stay special.
+        let res2 = parser.parse("10 ft. or 20 ft.");
+        assert_eq!(res2.kind, RangeKind::Special);
+        assert!(res2.alt_kind.is_none());
+    }
 }
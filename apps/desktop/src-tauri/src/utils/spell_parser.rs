@@ -64,6 +64,10 @@ impl SpellParser {
         self.components.parse_components(input)
     }
 
+    pub fn canonicalize_components(&self, input: &str) -> String {
+        self.components.canonicalize_components(input)
+    }
+
     pub fn parse_damage(&self, input: &str) -> SpellDamageSpec {
         self.mechanics.parse_damage(input)
     }
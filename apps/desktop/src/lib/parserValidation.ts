@@ -49,7 +49,11 @@ const CASTING_TIME_UNITS: Set<string> = new Set([
   "turn",
   "hour",
   "minute",
-  // "action", "bonus_action", "reaction" removed in v2 schema (task 3.1)
+  // "bonus_action" removed in v2 schema (task 3.1)
+  "action",
+  "reaction",
+  "bonus",
+  "free",
   "special",
   "instantaneous",
 ]);
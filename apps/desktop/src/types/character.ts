@@ -69,6 +69,17 @@ export interface BundleClassSpell {
   notes?: string | null;
 }
 
+export interface BundleSpellbookEntry {
+  spell: {
+    name: string;
+    level: number;
+    source?: string | null;
+  };
+  prepared: number;
+  known: number;
+  notes?: string | null;
+}
+
 export interface BundleClass {
   className: string;
   classLabel?: string | null;
@@ -89,6 +100,7 @@ export interface CharacterBundle {
   updatedAt?: string | null;
   abilities?: CharacterAbilities | null;
   classes: BundleClass[];
+  spellbook?: BundleSpellbookEntry[];
 }
 
 export interface CharacterSearchFilters {
@@ -6,6 +6,7 @@ import {
   damageToText,
   durationToText,
   formatDicePool,
+  listColumnToText,
   magicResistanceToText,
   rangeToText,
   savingThrowToText,
@@ -39,25 +40,16 @@ const _invalidDurationReaction: DurationSpec = {
   duration: { mode: "fixed", value: 1 },
 };
 
-// @ts-expect-error action is not a valid CastingTimeUnit
-const _invalidCastingTimeUnit: SpellCastingTime = { text: "1 action", unit: "action" };
 const _invalidCastingTimeBonusAction: SpellCastingTime = {
   text: "1 bonus action",
   // @ts-expect-error bonus_action is not a valid CastingTimeUnit
   unit: "bonus_action",
 };
-const _invalidCastingTimeReaction: SpellCastingTime = {
-  text: "1 reaction",
-  // @ts-expect-error reaction is not a valid CastingTimeUnit
-  unit: "reaction",
-};
 
 void _invalidDurationUnit;
 void _invalidDurationBonusAction;
 void _invalidDurationReaction;
-void _invalidCastingTimeUnit;
 void _invalidCastingTimeBonusAction;
-void _invalidCastingTimeReaction;
 
 describe("formatDicePool", () => {
   it("formats without a modifier", () => {
@@ -436,3 +428,24 @@ describe("castingTimeToText", () => {
     expect(castingTimeToText(ct)).toBe("3 segments");
   });
 });
+
+describe("listColumnToText", () => {
+  it("joins a stored JSON array", () => {
+    expect(listColumnToText('["AoE","Fire"]')).toBe("AoE, Fire");
+  });
+
+  it("returns comma-separated text unchanged", () => {
+    expect(listColumnToText("Wizard, Cleric")).toBe("Wizard, Cleric");
+    expect(listColumnToText("Fire, ")).toBe("Fire, ");
+  });
+
+  it("returns text that only looks like an array unchanged", () => {
+    expect(listColumnToText('["AoE"')).toBe('["AoE"');
+    expect(listColumnToText("[1,2]")).toBe("[1,2]");
+  });
+
+  it("returns empty text for missing values", () => {
+    expect(listColumnToText(null)).toBe("");
+    expect(listColumnToText(undefined)).toBe("");
+  });
+});
@@ -46,6 +46,7 @@ export interface SpellDetail {
   magicResistance: string | null;
   magicResistanceSpec?: MagicResistanceSpec;
   reversible: number | null;
+  reverseName?: string | null;
   tags?: string | null;
   source?: string | null;
   edition?: string | null;
@@ -57,6 +58,10 @@ export interface SpellDetail {
   artifacts?: SpellArtifact[] | null;
   canonicalData?: string | null;
   contentHash?: string | null;
+  createdAt?: string | null;
+  updatedAt?: string | null;
+  /** Read-only: a parsed material component has a gp value ("costs gold"). */
+  materialHasCost?: boolean;
 }
 
 export interface SpellArtifact {
@@ -67,6 +72,54 @@ export interface SpellArtifact {
   hash: string;
   importedAt: string;
   spellContentHash?: string | null;
+  isPrimary?: boolean;
+  exists?: boolean | null;
+}
+
+/** Result of `print_spell` / `print_spellbook`; page data is present only when the sidecar reports it. */
+export interface PrintResult {
+  path: string;
+  pages?: number | null;
+  spellPages?: Record<string, number> | null;
+}
+
+/** One measurement from `render_spell_at_level`; `value` is null for fields without a magnitude. */
+export interface ResolvedValue {
+  field: string;
+  text?: string | null;
+  formula?: string | null;
+  value?: number | null;
+  unit?: string | null;
+}
+
+export interface ResolvedDamage {
+  label?: string | null;
+  damageType: string;
+  base: string;
+  dice: string;
+}
+
+/** Result of `render_spell_at_level`: scaling fields evaluated for one caster level. */
+export interface ResolvedSpell {
+  spellId: number;
+  name: string;
+  casterLevel: number;
+  range?: ResolvedValue | null;
+  duration?: ResolvedValue | null;
+  area: ResolvedValue[];
+  damage: ResolvedDamage[];
+}
+
+/** One input a batch command could not process. */
+export interface BatchFailure {
+  inputId: number;
+  error: string;
+}
+
+/** Shared result of batch commands such as `reparse_artifacts` and `bulk_verify`. */
+export interface BatchResult<T> {
+  succeeded: T[];
+  failed: BatchFailure[];
 }
 
 export type SpellUpdate = SpellDetail & {
@@ -75,7 +128,14 @@ export type SpellUpdate = SpellDetail & {
 
 export type SpellCreate = Omit<
   SpellDetail,
-  "id" | "artifacts" | "contentHash" | "canonicalData" | "schemaVersion"
+  | "id"
+  | "artifacts"
+  | "contentHash"
+  | "canonicalData"
+  | "schemaVersion"
+  | "createdAt"
+  | "updatedAt"
+  | "materialHasCost"
 >;
 
 /** Range kinds that include distance + unit */
@@ -113,6 +173,7 @@ export interface RangeSpec {
   text?: string;
   unit?: RangeUnit;
   distance?: SpellScalar;
+  altKind?: RangeKind;
   notes?: string;
   rawLegacyValue?: string;
 }
@@ -160,6 +221,10 @@ export type CastingTimeUnit =
   | "turn"
   | "hour"
   | "minute"
+  | "action"
+  | "reaction"
+  | "bonus"
+  | "free"
   | "special"
   | "instantaneous";
 
@@ -197,6 +262,10 @@ export const CASTING_TIME_UNIT_LABELS: Record<CastingTimeUnit, string> = {
   turn: "Turn",
   hour: "Hour",
   minute: "Minute",
+  action: "Action",
+  reaction: "Reaction",
+  bonus: "Bonus Action",
+  free: "Free Action",
   special: "Special",
   instantaneous: "Instantaneous",
 };
@@ -732,6 +801,10 @@ export function castingTimeToText(ct: SpellCastingTime): string {
     turn: "turn",
     hour: "hour",
     minute: "minute",
+    action: "action",
+    reaction: "reaction",
+    bonus: "bonus action",
+    free: "free action",
     instantaneous: "instantaneous",
   };
   const u = unitLabels[unit] ?? unit;
@@ -744,3 +817,22 @@ export function castingTimeToText(ct: SpellCastingTime): string {
   if (base === 1) return `1 ${u}`;
   return `${base} ${u}s`;
 }
+
+/**
+ * Display text for a stored list column (`classList`, `tags`). The backend stores these as a
+ * JSON array; anything else (legacy rows, text being typed) is returned unchanged so the
+ * backend can normalize the comma-separated form on save.
+ */
+export function listColumnToText(value: string | null | undefined): string {
+  if (!value) return "";
+  if (!value.trimStart().startsWith("[")) return value;
+  try {
+    const items: unknown = JSON.parse(value);
+    if (Array.isArray(items) && items.every((item) => typeof item === "string")) {
+      return items.join(", ");
+    }
+  } catch {
+    // Not a JSON array; show it as typed.
+  }
+  return value;
+}
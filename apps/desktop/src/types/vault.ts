@@ -19,11 +19,34 @@ export interface VaultGcSummary {
   integrity: VaultIntegritySummary;
 }
 
+export interface IntegrityCheck {
+  ok: boolean;
+  messages: string[];
+}
+
+export interface RestoreDryRunReport {
+  valid: boolean;
+  entryCount: number;
+  spellFileCount: number;
+  verifiedHashCount: number;
+  hasSettings: boolean;
+  schemaVersion: number | null;
+  databaseIntegrity: IntegrityCheck | null;
+  problems: string[];
+}
+
 export type SourceRefUrlPolicy = "drop-ref" | "reject-spell";
 
 export interface VaultSettings {
   integrityCheckOnOpen: boolean;
   importSourceRefUrlPolicy: SourceRefUrlPolicy;
+  conversion?: ConversionConfig;
+}
+
+export interface ConversionConfig {
+  roundsPerTurn: number;
+  feetPerSquare: number;
+  segmentsPerRound: number;
 }
 
 export type VaultMaintenanceResult =
@@ -122,6 +122,11 @@ type PreviewResult = {
   spells: ParsedSpell[];
   artifacts: ImportArtifact[];
   conflicts: ImportConflict[];
+  stats?: {
+    meanConfidence: number;
+    lowConfidenceCount: number;
+    fullyParsedCount: number;
+  };
 };
 
 type ImportStep =
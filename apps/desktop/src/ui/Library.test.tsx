@@ -99,8 +99,9 @@ describe("Library heading hierarchy", () => {
         case "list_saved_searches":
           return [];
         case "search_keyword":
-        case "search_semantic":
           return [];
+        case "search_semantic":
+          return { mode: "vec0", degraded: false, spells: [] };
         default:
           return undefined;
       }
@@ -136,8 +137,9 @@ describe("Library notifications (Task 5)", () => {
         case "list_saved_searches":
           return [];
         case "search_keyword":
-        case "search_semantic":
           return [];
+        case "search_semantic":
+          return { mode: "vec0", degraded: false, spells: [] };
         default:
           return undefined;
       }
@@ -400,8 +402,9 @@ describe("Library empty states", () => {
         case "list_saved_searches":
           return [];
         case "search_keyword":
-        case "search_semantic":
           return [];
+        case "search_semantic":
+          return { mode: "vec0", degraded: false, spells: [] };
         default:
           return undefined;
       }
@@ -428,7 +431,7 @@ describe("Library empty states", () => {
           case "search_keyword":
             return searchDeferred.promise;
           case "search_semantic":
-            return Promise.resolve([]);
+            return Promise.resolve({ mode: "vec0", degraded: false, spells: [] });
           default:
             return Promise.resolve(undefined);
         }
@@ -489,7 +492,7 @@ describe("Library empty states", () => {
               ? initialSearchDeferred.promise
               : latestSearchDeferred.promise;
           case "search_semantic":
-            return Promise.resolve([]);
+            return Promise.resolve({ mode: "vec0", degraded: false, spells: [] });
           default:
             return Promise.resolve(undefined);
         }
@@ -530,7 +533,7 @@ describe("Library empty states", () => {
     });
 
     it("renders the empty-search state for semantic mode after the semantic search settles", async () => {
-      const semanticDeferred = createDeferred<unknown[]>();
+      const semanticDeferred = createDeferred<unknown>();
       vi.mocked(invoke).mockImplementation((cmd: string) => {
         switch (cmd) {
           case "list_facets":
@@ -564,7 +567,7 @@ describe("Library empty states", () => {
       });
       expect(screen.queryByText("No Results")).toBeNull();
 
-      semanticDeferred.resolve([]);
+      semanticDeferred.resolve({ mode: "vec0", degraded: false, spells: [] });
 
       const emptyState = await screen.findByTestId("empty-search-state");
       expect(within(emptyState).getByRole("heading", { name: "No Results" })).toBeTruthy();
@@ -637,7 +640,7 @@ describe("Library empty states", () => {
             }
             return secondEmptySearchDeferred.promise;
           case "search_semantic":
-            return Promise.resolve([]);
+            return Promise.resolve({ mode: "vec0", degraded: false, spells: [] });
           default:
             return Promise.resolve(undefined);
         }
@@ -711,7 +714,7 @@ describe("Library empty states", () => {
             return defaultLibraryDeferred.promise;
           }
           case "search_semantic":
-            return Promise.resolve([]);
+            return Promise.resolve({ mode: "vec0", degraded: false, spells: [] });
           default:
             return Promise.resolve(undefined);
         }
@@ -781,8 +784,9 @@ describe("Library empty states", () => {
               },
             ];
           case "search_keyword":
-          case "search_semantic":
             return [];
+          case "search_semantic":
+            return { mode: "vec0", degraded: false, spells: [] };
           default:
             return undefined;
         }
@@ -861,7 +865,7 @@ describe("Library focus indicators", () => {
             },
           ];
         case "search_semantic":
-          return [];
+          return { mode: "vec0", degraded: false, spells: [] };
         default:
           return undefined;
       }
@@ -913,8 +917,9 @@ describe("Library focus indicators", () => {
         case "list_saved_searches":
           return [];
         case "search_keyword":
-        case "search_semantic":
           return [];
+        case "search_semantic":
+          return { mode: "vec0", degraded: false, spells: [] };
         default:
           return undefined;
       }
@@ -959,7 +964,7 @@ describe("Library explicit search behavior", () => {
         case "search_keyword":
           return [];
         case "search_semantic":
-          return [];
+          return { mode: "vec0", degraded: false, spells: [] };
         default:
           return undefined;
       }
@@ -993,6 +998,42 @@ describe("Library explicit search behavior", () => {
       ).toHaveLength(initialSearchCount + 1);
     });
   });
+
+  it("renders a stored JSON class list as joined text", async () => {
+    vi.mocked(invoke).mockImplementation(async (cmd: string) => {
+      switch (cmd) {
+        case "list_facets":
+          return emptyFacets;
+        case "list_characters":
+          return [];
+        case "list_saved_searches":
+          return [];
+        case "search_keyword":
+          return [
+            {
+              id: 10,
+              name: "Fireball",
+              school: "Evocation",
+              level: 3,
+              classList: '["Cleric","Mage"]',
+              components: "V, S, M",
+              isQuestSpell: 0,
+              isCantrip: 0,
+            },
+          ];
+        case "search_semantic":
+          return { mode: "vec0", degraded: false, spells: [] };
+        default:
+          return undefined;
+      }
+    });
+
+    renderLibraryWithViewport();
+
+    const row = await screen.findByTestId("spell-row-fireball");
+    expect(row.textContent).toContain("Cleric, Mage");
+    expect(row.textContent).not.toContain('["Cleric"');
+  });
 });
 
 describe("Library saved-search delete modal", () => {
@@ -1047,8 +1088,9 @@ describe("Library saved-search delete modal", () => {
         case "list_saved_searches":
           return [];
         case "search_keyword":
-        case "search_semantic":
           return [];
+        case "search_semantic":
+          return { mode: "vec0", degraded: false, spells: [] };
         default:
           return undefined;
       }
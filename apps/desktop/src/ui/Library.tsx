@@ -4,6 +4,7 @@ import { useCallback, useEffect, useRef, useState } from "react";
 import { Link } from "react-router-dom";
 import { useModal } from "../store/useModal";
 import { useNotifications } from "../store/useNotifications";
+import { listColumnToText } from "../types/spell";
 import { EmptyState, EmptyStateLiveRegion } from "./components/EmptyState";
 
 type SpellSummary = {
@@ -19,6 +20,12 @@ type SpellSummary = {
   isCantrip: number;
 };
 
+type SemanticSearchResults = {
+  mode: "vec0" | "blob_fallback";
+  degraded: boolean;
+  spells: SpellSummary[];
+};
+
 type Facets = {
   schools: string[];
   sources: string[];
@@ -261,7 +268,8 @@ export default function Library() {
       try {
         const results =
           nextMode === "semantic"
-            ? await invoke<SpellSummary[]>("search_semantic", { query: nextQuery })
+            ? (await invoke<SemanticSearchResults>("search_semantic", { query: nextQuery }))
+                .spells
             : await invoke<SpellSummary[]>("search_keyword", { query: nextQuery, filters });
 
         if (requestId !== searchRequestIdRef.current) {
@@ -815,7 +823,7 @@ export default function Library() {
                 </td>
                 <td className="p-2">{s.school}</td>
                 <td className="p-2 text-center">{s.level}</td>
-                <td className="p-2">{s.classList}</td>
+                <td className="p-2">{listColumnToText(s.classList)}</td>
                 <td className="p-2 text-center">{s.components}</td>
               </tr>
             ))}
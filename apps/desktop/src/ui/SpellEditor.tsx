@@ -17,6 +17,7 @@ import {
   damageToText,
   defaultAreaSpec,
   defaultSpellDamageSpec,
+  listColumnToText,
   magicResistanceToText,
   savingThrowToText,
 } from "../types/spell";
@@ -25,6 +26,7 @@ import type {
   DurationSpec,
   MagicResistanceSpec,
   MaterialComponentSpec,
+  PrintResult,
   RangeSpec,
   SavingThrowSpec,
   SpellCastingTime,
@@ -1145,7 +1147,7 @@ export default function SpellEditor() {
     if (savePending || !form.id) return;
     setPrintStatus("Generating print…");
     try {
-      const path = await invoke<string>("print_spell", {
+      const { path } = await invoke<PrintResult>("print_spell", {
         spellId: form.id,
         layout,
         pageSize,
@@ -1987,7 +1989,7 @@ export default function SpellEditor() {
                 className={`w-full border p-2 ${spellInputSurface} ${getSpellFocusVisibleRing(isFieldInvalid("spell-classes"))} ${
                   isFieldInvalid("spell-classes") ? spellInputBorderInvalid : spellInputBorderOk
                 }`}
-                value={form.classList || ""}
+                value={listColumnToText(form.classList)}
                 onChange={(e) => {
                   handleChange("classList", e.target.value);
                   // H-002: reveal classList's own field immediately on change so
@@ -2087,6 +2089,23 @@ export default function SpellEditor() {
                 Reversible
               </label>
             </div>
+            {Boolean(form.reversible) && (
+              <div>
+                <label
+                  htmlFor="spell-reverse-name"
+                  className="block text-sm text-neutral-500 dark:text-neutral-400"
+                >
+                  Reverse name
+                </label>
+                <input
+                  id="spell-reverse-name"
+                  data-testid="spell-reverse-name-input"
+                  className={`w-full ${spellInputSurface} ${spellInputBorderOk} ${spellFocusVisibleRing}`}
+                  value={form.reverseName || ""}
+                  onChange={(e) => handleChange("reverseName", e.target.value)}
+                />
+              </div>
+            )}
           </div>
         </section>
 
@@ -2121,7 +2140,7 @@ export default function SpellEditor() {
             className={`w-full min-h-[80px] ${spellInputSurface} ${spellInputBorderOk} ${spellFocusVisibleRing}`}
             placeholder="Comma-separated tags"
             aria-describedby="spell-tags-hint"
-            value={form.tags || ""}
+            value={listColumnToText(form.tags)}
             onChange={(e) => handleChange("tags", e.target.value)}
           />
           <p id="spell-tags-hint" className="mt-1 text-xs text-neutral-500 dark:text-neutral-400">
@@ -2180,10 +2199,16 @@ export default function SpellEditor() {
 
                   try {
                     setReparsePending(true);
-                    const updated = await invoke<SpellDetail>("reparse_artifact", { artifactId });
+                    const { spell: updated, changes } = await invoke<{
+                      spell: SpellDetail;
+                      changes: { field: string; old: string; new: string }[];
+                    }>("reparse_artifact", { artifactId });
                     setForm(updated);
+                    const changedFields = changes.map((c) => c.field).join(", ");
                     await modalAlert(
-                      "Spell re-parsed successfully!",
+                      changes.length === 0
+                        ? "Spell re-parsed successfully; no fields changed."
+                        : `Spell re-parsed successfully; changed: ${changedFields}.`,
                       "Reparse Complete",
                       "success",
                     );
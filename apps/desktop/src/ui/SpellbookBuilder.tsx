@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { Link, useParams } from "react-router-dom";
 import { useNotifications } from "../store/useNotifications";
+import type { PrintResult } from "../types/spell";
 import { EmptyState, EmptyStateLiveRegion } from "./components/EmptyState";
 
 function formatBuilderError(error: unknown): string {
@@ -243,7 +244,7 @@ export default function SpellbookBuilder() {
     if (!character) return;
     setStatusMessage("Generating spellbook print…");
     try {
-      const path = await invoke<string>("print_spellbook", {
+      const { path } = await invoke<PrintResult>("print_spellbook", {
         characterId: character.id,
         layout,
         pageSize,
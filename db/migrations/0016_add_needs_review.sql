@@ -0,0 +1,10 @@
+-- Migration 0016 (phase 2)
+-- Column creation for spell.needs_review is performed in load_migrations()
+-- before this SQL is executed so the migration remains idempotent on upgraded DBs.
+--
+-- needs_review = 1 marks spells whose range/duration/area fell back to the `special`
+-- kind during import and should be checked by a human.
+
+CREATE INDEX IF NOT EXISTS idx_spell_needs_review
+ON spell(needs_review)
+WHERE needs_review = 1;
@@ -0,0 +1,13 @@
+-- Migration 0017: audit trail for character detail edits (mirrors change_log for spells).
+CREATE TABLE IF NOT EXISTS character_change_log (
+  id INTEGER PRIMARY KEY,
+  character_id INTEGER NOT NULL REFERENCES "character"(id) ON DELETE CASCADE,
+  changed_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ','now')),
+  field TEXT NOT NULL,
+  old_value TEXT,
+  new_value TEXT,
+  actor TEXT DEFAULT 'local'
+);
+
+CREATE INDEX IF NOT EXISTS idx_character_change_log_character
+ON character_change_log(character_id, changed_at);
@@ -0,0 +1,7 @@
+-- Migration 0018: reusable spell templates (partial SpellDetail JSON) for homebrew starting points.
+CREATE TABLE IF NOT EXISTS spell_template (
+  id INTEGER PRIMARY KEY,
+  name TEXT NOT NULL,
+  partial_json TEXT NOT NULL,
+  created_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ','now'))
+);
@@ -0,0 +1,11 @@
+-- Migration 0019 (phase 2)
+-- Column creation for artifact.is_primary is performed in load_migrations()
+-- before this SQL is executed so the migration remains idempotent on upgraded DBs.
+--
+-- is_primary = 1 marks the artifact used as the canonical source when a spell is
+-- reparsed by spell id. At most one artifact per spell is primary (enforced by
+-- set_primary_artifact).
+
+CREATE INDEX IF NOT EXISTS idx_artifact_primary
+ON artifact(spell_id)
+WHERE is_primary = 1;
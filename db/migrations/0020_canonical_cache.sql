@@ -0,0 +1,7 @@
+-- Migration 0020: per-spell cache of canonical JSON keyed by the content hash it was computed for.
+CREATE TABLE IF NOT EXISTS canonical_cache (
+  spell_id INTEGER PRIMARY KEY REFERENCES spell(id) ON DELETE CASCADE,
+  content_hash TEXT NOT NULL,
+  canonical_json TEXT NOT NULL,
+  computed_at TEXT NOT NULL
+);
@@ -0,0 +1,7 @@
+-- Migration 0021: per-class spell level overrides (e.g. Wizard 3 / Cleric 4); spell.level is the fallback.
+CREATE TABLE IF NOT EXISTS class_spell_level (
+  spell_id INTEGER NOT NULL REFERENCES spell(id) ON DELETE CASCADE,
+  class TEXT NOT NULL COLLATE NOCASE,
+  level INTEGER NOT NULL,
+  PRIMARY KEY (spell_id, class)
+);
@@ -0,0 +1,3 @@
+-- Migration 0023: spell.verified marks spells a GM has reviewed as canon for the vault.
+-- The column itself is added in migrations.rs (only when missing); this file indexes the unverified queue.
+CREATE INDEX IF NOT EXISTS idx_spell_unverified ON spell(id) WHERE verified = 0;
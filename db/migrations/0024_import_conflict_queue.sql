@@ -0,0 +1,12 @@
+-- Migration 0024: unresolved import conflicts kept across sessions (serialized ImportConflict).
+CREATE TABLE IF NOT EXISTS import_conflict_queue (
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  existing_id INTEGER REFERENCES spell(id) ON DELETE CASCADE,
+  incoming_hash TEXT,
+  conflict_json TEXT NOT NULL,
+  queued_at TEXT DEFAULT (strftime('%Y-%m-%dT%H:%M:%SZ','now'))
+);
+
+-- Re-importing the same file must not queue the same conflict twice.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_import_conflict_queue_dedupe
+  ON import_conflict_queue(existing_id, incoming_hash);
@@ -0,0 +1,2 @@
+-- Migration 0025: one row per logical id, so a bundle imported with hash ids cannot fork a spell.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_spell_logical_id ON spell(logical_id) WHERE logical_id IS NOT NULL;
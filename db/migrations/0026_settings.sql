@@ -0,0 +1,5 @@
+-- Migration 0026: general key/value settings store (values are JSON text).
+CREATE TABLE IF NOT EXISTS settings (
+  key TEXT PRIMARY KEY,
+  value TEXT NOT NULL
+);
@@ -0,0 +1,10 @@
+-- Migration 0027: record of completed vault backups for last-backup tracking.
+CREATE TABLE IF NOT EXISTS backup_history (
+  id INTEGER PRIMARY KEY,
+  path TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  size INTEGER NOT NULL,
+  kind TEXT NOT NULL DEFAULT 'manual'
+);
+
+CREATE INDEX IF NOT EXISTS idx_backup_history_created_at ON backup_history(created_at);
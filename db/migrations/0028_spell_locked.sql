@@ -0,0 +1,3 @@
+-- Migration 0028: spell.locked protects hand-curated spells from reparse and overwrite imports.
+-- The column itself is added in migrations.rs (only when missing); this file indexes the locked rows.
+CREATE INDEX IF NOT EXISTS idx_spell_locked ON spell(id) WHERE locked = 1;
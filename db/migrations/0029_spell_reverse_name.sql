@@ -0,0 +1,3 @@
+-- Migration 0029: spell.reverse_name names the reversed form of a reversible spell.
+-- The column itself is added in migrations.rs (only when missing); this file indexes linked rows.
+CREATE INDEX IF NOT EXISTS idx_spell_reverse_name ON spell(reverse_name) WHERE reverse_name IS NOT NULL;
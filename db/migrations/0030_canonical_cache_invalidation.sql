@@ -0,0 +1,6 @@
+-- Migration 0030: drop a spell's canonical_cache entry whenever its content changes.
+CREATE TRIGGER IF NOT EXISTS canonical_cache_invalidate
+AFTER UPDATE OF content_hash, canonical_data ON spell
+BEGIN
+  DELETE FROM canonical_cache WHERE spell_id = NEW.id;
+END;